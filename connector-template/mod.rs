@@ -243,6 +243,17 @@ impl
     //TODO: implement sessions flow
 }
 
+impl api::PaymentsPreProcessing for {{project-name | downcase | pascal_case}} {}
+
+impl
+    ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for {{project-name | downcase | pascal_case}}
+{
+}
+
 impl api::PaymentAuthorize for {{project-name | downcase | pascal_case}} {}
 
 impl
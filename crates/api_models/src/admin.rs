@@ -176,6 +176,20 @@ pub struct MerchantDetails {
 #[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum RoutingAlgorithm {
     Single(api_enums::RoutableConnectors),
+    /// Connectors that are tried in order, moving to the next one when a payment is soft declined
+    Failover(Vec<api_enums::RoutableConnectors>),
+    /// Connectors that take turns processing payments
+    RoundRobin(Vec<api_enums::RoutableConnectors>),
+    /// Connectors that each process a percentage of the payments
+    Weighted(Vec<WeightedConnector>),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeightedConnector {
+    pub connector: api_enums::RoutableConnectors,
+    /// Percentage of payments sent to the connector, the weights of all connectors add up to 100
+    pub weight: u8,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
@@ -287,6 +301,10 @@ pub struct PaymentConnectorCreate {
     /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
     #[schema(value_type = Option<Object>,max_length = 255,example = json!({ "city": "NY", "unit": "245" }))]
     pub metadata: Option<serde_json::Value>,
+    /// Secret the connector signs its webhooks with, used to verify that incoming webhooks come from the connector. It is stored encrypted and never returned.
+    #[schema(value_type = Option<String>, example = "whsec_MyVerySecretWebhookSecret")]
+    #[serde(skip_serializing)]
+    pub webhook_secret: Option<Secret<String>>,
 }
 /// Details of all the payment methods enabled for the connector for the given merchant account
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -131,6 +131,20 @@ pub struct UpdateApiKeyRequest {
     pub expiration: Option<ApiKeyExpiration>,
 }
 
+/// The request body for rotating an API Key.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RotateApiKeyRequest {
+    /// An expiration date for the new API Key.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    pub expiration: ApiKeyExpiration,
+
+    /// The time in seconds for which the API Key being rotated keeps working alongside the new
+    /// API Key, so that your servers can be switched over to the new API Key. Defaults to a day.
+    #[schema(example = 86400)]
+    pub rotation_window: Option<i64>,
+}
+
 /// The response body for revoking an API Key.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct RevokeApiKeyResponse {
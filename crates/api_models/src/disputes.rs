@@ -1 +1,75 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
 
+/// Evidence contesting a dispute. Files are referenced by the id the connector assigned to them
+/// when they were uploaded.
+#[derive(Default, Debug, ToSchema, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubmitEvidenceRequest {
+    /// Description of the product or service that was sold
+    pub product_description: Option<String>,
+    /// Name of the customer
+    pub customer_name: Option<String>,
+    /// Email address of the customer
+    pub customer_email_address: Option<String>,
+    /// Why the customer is not entitled to a refund
+    pub refund_refusal_explanation: Option<String>,
+    /// Tracking number of the shipment
+    pub shipping_tracking_number: Option<String>,
+    /// Any other evidence or statement
+    pub uncategorized_text: Option<String>,
+    /// File with the receipt sent to the customer
+    pub receipt: Option<String>,
+    /// File with the communication with the customer
+    pub customer_communication: Option<String>,
+    /// File proving the product was shipped
+    pub shipping_documentation: Option<String>,
+    /// Any other evidence file
+    pub uncategorized_file: Option<String>,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+pub struct DisputeResponse {
+    /// The identifier for the dispute
+    pub dispute_id: String,
+    /// The identifier for the disputed payment
+    pub payment_id: String,
+    /// The identifier for the disputed payment attempt
+    pub attempt_id: String,
+    /// The connector the payment was processed through
+    pub connector: String,
+    /// The identifier for the dispute at the connector
+    pub connector_dispute_id: String,
+    /// The reason the connector gave for the dispute
+    pub connector_reason_code: Option<String>,
+    /// The disputed amount in the lowest denomination of the currency
+    pub amount: i64,
+    /// The three-letter ISO currency code
+    pub currency: String,
+    /// The stage of the dispute
+    pub dispute_status: DisputeStatus,
+    /// The outcome of the last evidence submission, if evidence was submitted
+    pub evidence_status: Option<EvidenceStatus>,
+    /// The timestamp at which the dispute was opened
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The stage of a dispute
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeStatus {
+    Opened,
+    Won,
+    Lost,
+}
+
+/// The outcome of an evidence submission
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceStatus {
+    Submitted,
+    Failed,
+}
@@ -21,8 +21,12 @@ pub enum AttemptStatus {
     AuthenticationPending,
     AuthenticationSuccessful,
     Authorized,
+    /// The connector authorized less than the requested amount
+    PartiallyAuthorized,
     AuthorizationFailed,
     Charged,
+    /// The payment method was verified without authorizing or charging any amount
+    Verified,
     Authorizing,
     CodInitiated,
     Voided,
@@ -257,6 +261,7 @@ pub enum Currency {
     strum::Display,
     strum::EnumString,
     frunk::LabelledGeneric,
+    ToSchema,
 )]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
@@ -343,6 +348,53 @@ pub enum PaymentMethodIssuerCode {
     JpBacs,
 }
 
+/// Exemptions from strong customer authentication that can be requested from the issuer.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ScaExemptionType {
+    /// The payment is below the low value threshold of the issuer's region
+    LowValue,
+    /// The acquirer assessed the payment as low risk
+    TransactionRiskAnalysis,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CardNetwork {
+    Visa,
+    Mastercard,
+    Amex,
+    Discover,
+    Jcb,
+    DinersClub,
+    Unknown,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -391,6 +443,7 @@ pub enum PaymentMethodType {
     #[default]
     BankTransfer,
     BankDebit,
+    BankRedirect,
     PayLater,
     Netbanking,
     Upi,
@@ -422,6 +475,38 @@ pub enum WalletIssuer {
     Paypal,
 }
 
+/// Banks a customer can be redirected to for bank redirect payments
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    ToSchema,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum BankNames {
+    AbnAmro,
+    AsnBank,
+    Bunq,
+    Handelsbanken,
+    Ing,
+    Knab,
+    Moneyou,
+    Rabobank,
+    Regiobank,
+    Revolut,
+    SnsBank,
+    TriodosBank,
+    VanLanschot,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -504,7 +589,7 @@ pub enum MandateStatus {
     serde::Deserialize,
     serde::Serialize,
     strum::Display,
-    strum::EnumString,
+    strum::EnumIter,
     frunk::LabelledGeneric,
 )]
 #[serde(rename_all = "snake_case")]
@@ -522,6 +607,7 @@ pub enum Connector {
     Fiserv,
     Globalpay,
     Klarna,
+    Opayo,
     Payu,
     Rapyd,
     Shift4,
@@ -532,8 +618,79 @@ pub enum Connector {
 
 impl Connector {
     pub fn supports_access_token(&self) -> bool {
-        matches!(self, Self::Globalpay | Self::Payu)
+        matches!(self, Self::Globalpay | Self::Opayo | Self::Payu)
     }
+
+    pub fn requires_pre_processing(&self) -> bool {
+        matches!(self, Self::Opayo)
+    }
+}
+
+impl std::str::FromStr for Connector {
+    type Err = ConnectorParseError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        <Self as strum::IntoEnumIterator>::iter()
+            .find(|connector| connector.to_string() == name)
+            .ok_or_else(|| ConnectorParseError::new(name))
+    }
+}
+
+/// Returned for a string that does not name a connector, along with the connector name closest
+/// to it, to point out typos.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConnectorParseError {
+    pub name: String,
+    pub suggestion: Option<Connector>,
+}
+
+impl ConnectorParseError {
+    pub fn new(name: &str) -> Self {
+        let suggestion = <Connector as strum::IntoEnumIterator>::iter()
+            .map(|connector| {
+                let distance = levenshtein_distance(name, &connector.to_string());
+                (connector, distance)
+            })
+            // Names that need more edits than a third of their length are unrelated
+            .filter(|(connector, distance)| {
+                *distance > 0 && *distance <= std::cmp::max(1, connector.to_string().len() / 3)
+            })
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(connector, _)| connector);
+        Self {
+            name: name.to_string(),
+            suggestion,
+        }
+    }
+}
+
+impl std::fmt::Display for ConnectorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid connector name `{}`", self.name)?;
+        match self.suggestion {
+            Some(suggestion) => write!(f, ", did you mean `{suggestion}`?"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ConnectorParseError {}
+
+/// Number of single character insertions, deletions and substitutions that turn `a` into `b`.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a_char != *b_char);
+            let insertion = current_row[j] + 1;
+            let deletion = previous_row[j + 1] + 1;
+            current_row.push(substitution.min(insertion).min(deletion));
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
 }
 
 #[derive(
@@ -560,6 +717,7 @@ pub enum RoutableConnectors {
     Fiserv,
     Globalpay,
     Klarna,
+    Opayo,
     Payu,
     Rapyd,
     Shift4,
@@ -581,12 +739,14 @@ pub enum SupportedWallets {
 impl From<AttemptStatus> for IntentStatus {
     fn from(s: AttemptStatus) -> Self {
         match s {
-            AttemptStatus::Charged | AttemptStatus::AutoRefunded => Self::Succeeded,
+            AttemptStatus::Charged | AttemptStatus::Verified | AttemptStatus::AutoRefunded => {
+                Self::Succeeded
+            }
 
             AttemptStatus::ConfirmationAwaited => Self::RequiresConfirmation,
             AttemptStatus::PaymentMethodAwaited => Self::RequiresPaymentMethod,
 
-            AttemptStatus::Authorized => Self::RequiresCapture,
+            AttemptStatus::Authorized | AttemptStatus::PartiallyAuthorized => Self::RequiresCapture,
             AttemptStatus::AuthenticationPending => Self::RequiresCustomerAction,
 
             AttemptStatus::PartialCharged
@@ -608,3 +768,41 @@ impl From<AttemptStatus> for IntentStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_connector_is_parsed_from_its_name() {
+        assert_eq!(Connector::from_str("stripe").unwrap(), Connector::Stripe);
+        assert_eq!(
+            Connector::from_str("authorizedotnet").unwrap(),
+            Connector::Authorizedotnet
+        );
+    }
+
+    #[test]
+    fn test_misspelt_connector_name_suggests_closest_connector() {
+        let error = Connector::from_str("strpie").unwrap_err();
+        assert_eq!(error.suggestion, Some(Connector::Stripe));
+        assert_eq!(
+            error.to_string(),
+            "Invalid connector name `strpie`, did you mean `stripe`?"
+        );
+        assert_eq!(
+            Connector::from_str("Adyen").unwrap_err().suggestion,
+            Some(Connector::Adyen)
+        );
+    }
+
+    #[test]
+    fn test_unknown_connector_name_has_no_suggestion() {
+        let error = Connector::from_str("paypal").unwrap_err();
+        assert_eq!(error.suggestion, None);
+        assert_eq!(error.to_string(), "Invalid connector name `paypal`");
+    }
+}
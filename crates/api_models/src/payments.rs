@@ -97,7 +97,7 @@ pub struct PaymentsRequest {
     #[schema(example = "187282ab-40ef-47a9-9206-5099ba31e432")]
     pub payment_token: Option<String>,
     /// This is used when payment is to be confirmed and the card is not saved
-    #[schema(value_type = Option<String>)]
+    #[schema(value_type = Option<String>, write_only, format = Password)]
     pub card_cvc: Option<Secret<String>>,
     /// The shipping address for the payment
     pub shipping: Option<Address>,
@@ -132,6 +132,27 @@ pub struct PaymentsRequest {
         "java_script_enabled":true
     }"#)]
     pub browser_info: Option<serde_json::Value>,
+    /// Breakdown of the surcharge added to the order. The original amount, the surcharge amount and the tax amount must add up to `amount`.
+    /// Sent to the connector along with the request that confirms the payment.
+    pub surcharge_details: Option<SurchargeDetails>,
+    /// The tax included in `amount`. It shall be in lowest denomination of the currency. Sent to the connector along with the request that confirms the payment.
+    #[schema(example = 440)]
+    pub tax_amount: Option<i64>,
+    /// Exemption from strong customer authentication to request from the issuer. Only honoured when `authentication_type` is `no_three_ds` and the connector supports the exemption.
+    pub sca_exemption: Option<api_enums::ScaExemptionType>,
+}
+
+#[derive(
+    Default, Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, ToSchema,
+)]
+#[serde(deny_unknown_fields)]
+pub struct SurchargeDetails {
+    /// The amount of the order before the surcharge and tax were added
+    #[schema(example = 6000)]
+    pub original_amount: i64,
+    /// The surcharge added to the order for the payment method used
+    #[schema(example = 100)]
+    pub surcharge_amount: i64,
 }
 
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq)]
@@ -251,6 +272,13 @@ pub struct MandateAmountData {
     /// The currency for the transaction
     #[schema(value_type = Currency, example = "USD")]
     pub currency: api_enums::Currency,
+    /// The date after which the mandate can no longer be debited
+    #[schema(example = "2023-09-10T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_date: Option<PrimitiveDateTime>,
+    /// The minimum number of days between two debits, for mandates debited on a fixed schedule
+    #[schema(example = 30)]
+    pub frequency_in_days: Option<u16>,
 }
 
 #[derive(Eq, PartialEq, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -303,22 +331,66 @@ pub struct OnlineMandate {
 #[derive(Default, Eq, PartialEq, Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct Card {
     /// The card number
-    #[schema(value_type = String, example = "4242424242424242")]
+    #[schema(value_type = String, write_only, format = Password, example = "4242424242424242")]
     pub card_number: Secret<String, pii::CardNumber>,
     /// The card's expiry month
-    #[schema(value_type = String, example = "24")]
+    #[schema(value_type = String, write_only, format = Password, example = "24")]
     pub card_exp_month: Secret<String>,
     /// The card's expiry year
-    #[schema(value_type = String, example = "24")]
+    #[schema(value_type = String, write_only, format = Password, example = "24")]
     pub card_exp_year: Secret<String>,
     /// The card holder's name
-    #[schema(value_type = String, example = "John Test")]
+    #[schema(value_type = String, write_only, format = Password, example = "John Test")]
     pub card_holder_name: Secret<String>,
     /// The CVC number for the card
-    #[schema(value_type = String, example = "242")]
+    #[schema(value_type = String, write_only, format = Password, example = "242")]
     pub card_cvc: Secret<String>,
 }
 
+impl Card {
+    /// Detects the card network from the leading digits (BIN) of the card number.
+    pub fn network(&self) -> api_enums::CardNetwork {
+        let card_number = self
+            .card_number
+            .peek()
+            .chars()
+            .filter(|character| !character.is_whitespace())
+            .collect::<String>();
+        let prefix = |length: usize| -> Option<u32> {
+            card_number
+                .get(..length)
+                .and_then(|prefix| prefix.parse().ok())
+        };
+        let prefix_in = |length: usize, range: std::ops::RangeInclusive<u32>| {
+            prefix(length).map_or(false, |prefix| range.contains(&prefix))
+        };
+
+        if prefix_in(2, 34..=34) || prefix_in(2, 37..=37) {
+            api_enums::CardNetwork::Amex
+        } else if prefix_in(4, 3528..=3589) {
+            api_enums::CardNetwork::Jcb
+        } else if prefix_in(3, 300..=305)
+            || prefix_in(4, 3095..=3095)
+            || prefix_in(2, 36..=36)
+            || prefix_in(2, 38..=39)
+        {
+            api_enums::CardNetwork::DinersClub
+        } else if prefix_in(1, 4..=4) {
+            api_enums::CardNetwork::Visa
+        } else if prefix_in(2, 51..=55) || prefix_in(4, 2221..=2720) {
+            api_enums::CardNetwork::Mastercard
+        } else if prefix_in(4, 6011..=6011)
+            || prefix_in(3, 644..=649)
+            || prefix_in(2, 65..=65)
+            || prefix_in(6, 622126..=622925)
+        {
+            api_enums::CardNetwork::Discover
+        } else {
+            api_enums::CardNetwork::Unknown
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum KlarnaIssuer {
@@ -354,6 +426,7 @@ pub enum PayLaterData {
         /// The issuer name of the sdk
         issuer_name: KlarnaIssuer,
         /// The token for the sdk workflow
+        #[schema(write_only, format = Password)]
         token: String,
     },
     /// For Affirm redirect as PayLater Option
@@ -383,6 +456,36 @@ pub enum PaymentMethod {
     Wallet(WalletData),
     PayLater(PayLaterData),
     Paypal,
+    BankRedirect(BankRedirectData),
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BankRedirectData {
+    /// For iDEAL as BankRedirect Option
+    Ideal {
+        /// The name of the account holder
+        #[schema(value_type = String, write_only, format = Password, example = "John Doe")]
+        billing_name: Secret<String>,
+        /// The bank the customer is redirected to
+        #[schema(value_type = BankNames)]
+        bank_name: api_enums::BankNames,
+    },
+    /// For Sofort as BankRedirect Option
+    Sofort {
+        /// The country of the customer's bank, as an ISO 3166-1 alpha-2 code
+        #[schema(example = "DE")]
+        country: String,
+        /// The language of the bank's authorization page
+        #[schema(example = "en")]
+        preferred_language: Option<String>,
+    },
+    /// For Giropay as BankRedirect Option
+    Giropay {
+        /// The name of the account holder
+        #[schema(value_type = String, write_only, format = Password, example = "John Doe")]
+        billing_name: Secret<String>,
+    },
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
@@ -391,6 +494,7 @@ pub struct WalletData {
     #[schema(value_type = WalletIssuer)]
     pub issuer_name: api_enums::WalletIssuer,
     /// Token generated for the wallet
+    #[schema(write_only, format = Password)]
     pub token: Option<String>,
 }
 
@@ -410,6 +514,7 @@ pub enum PaymentMethodDataResponse {
     Wallet(WalletData),
     PayLater(PayLaterData),
     Paypal,
+    BankRedirect(BankRedirectData),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -531,7 +636,7 @@ pub struct PhoneDetails {
     pub country_code: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, serde::Deserialize, ToSchema)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct PaymentsCaptureRequest {
     /// The unique identifier for the payment
     pub payment_id: Option<String>,
@@ -603,6 +708,9 @@ pub struct PaymentsResponse {
     /// The connector used for the payment
     #[schema(example = "stripe")]
     pub connector: Option<String>,
+    /// The reference the connector shows for this payment on its dashboard, useful for reconciliation
+    #[schema(example = "8636128")]
+    pub connector_response_reference_id: Option<String>,
     /// It's a token used for client side verification.
     #[schema(value_type = Option<String>, example = "pay_U42c409qyHwOkWo3vK60_secret_el9ksDkiB8hi6j9N78yo")]
     pub client_secret: Option<Secret<String>>,
@@ -690,6 +798,18 @@ pub struct PaymentsResponse {
     /// If there was an error while calling the connector the error message is received here
     #[schema(example = "Failed while verifying the card")]
     pub error_message: Option<String>,
+    /// If the connector declined the payment, the reason it gave
+    #[schema(example = "card_declined")]
+    pub error_reason: Option<String>,
+    /// The currency the merchant settles the payment in, when it differs from the currency of the payment
+    #[schema(value_type = Option<Currency>, example = "EUR")]
+    pub settlement_currency: Option<api_enums::Currency>,
+    /// The amount of the payment in the settlement currency, for information only as the payment is charged in its own currency
+    #[schema(example = 5974)]
+    pub settlement_amount: Option<i64>,
+    /// Units of the settlement currency for one unit of the payment currency, as quoted when the payment was created or updated
+    #[schema(example = "0.9134")]
+    pub fx_rate: Option<String>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, ToSchema)]
@@ -701,6 +821,8 @@ pub struct PaymentListConstraints {
     pub starting_after: Option<String>,
     /// A cursor for use in pagination, fetch the previous list before some object
     pub ending_before: Option<String>,
+    /// The `next_cursor` of the previous page, to fetch the page following it
+    pub cursor: Option<String>,
     /// limit on the number of objects to return
     #[serde(default = "default_limit")]
     pub limit: i64,
@@ -731,6 +853,8 @@ pub struct PaymentListResponse {
     pub size: usize,
     // The list of payments response objects
     pub data: Vec<PaymentsResponse>,
+    /// Cursor to fetch the next page with, absent on the last page
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Setter, Clone, Default, Debug, Eq, PartialEq, serde::Serialize)]
@@ -952,6 +1076,9 @@ impl From<PaymentMethod> for PaymentMethodDataResponse {
             PaymentMethod::PayLater(pay_later_data) => Self::PayLater(pay_later_data),
             PaymentMethod::Wallet(wallet_data) => Self::Wallet(wallet_data),
             PaymentMethod::Paypal => Self::Paypal,
+            PaymentMethod::BankRedirect(bank_redirect_data) => {
+                Self::BankRedirect(bank_redirect_data)
+            }
         }
     }
 }
@@ -1350,4 +1477,45 @@ mod tests {
             r#"{"multi_use":null}"#
         )
     }
+
+    fn get_card(card_number: &str) -> Card {
+        Card {
+            card_number: Secret::new(card_number.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_card_network_from_bin() {
+        let cases = [
+            ("4242424242424242", api_enums::CardNetwork::Visa),
+            ("4929 0000 0000 6", api_enums::CardNetwork::Visa),
+            ("5555555555554444", api_enums::CardNetwork::Mastercard),
+            ("2223003122003222", api_enums::CardNetwork::Mastercard),
+            ("378282246310005", api_enums::CardNetwork::Amex),
+            ("341111111111111", api_enums::CardNetwork::Amex),
+            ("6011111111111117", api_enums::CardNetwork::Discover),
+            ("6221260000000000", api_enums::CardNetwork::Discover),
+            ("6500000000000002", api_enums::CardNetwork::Discover),
+            ("3530111333300000", api_enums::CardNetwork::Jcb),
+            ("30569309025904", api_enums::CardNetwork::DinersClub),
+            ("36227206271667", api_enums::CardNetwork::DinersClub),
+        ];
+        for (card_number, network) in cases {
+            assert_eq!(get_card(card_number).network(), network, "{card_number}");
+        }
+    }
+
+    #[test]
+    fn test_card_network_falls_back_to_unknown() {
+        assert_eq!(
+            get_card("9999999999999999").network(),
+            api_enums::CardNetwork::Unknown
+        );
+        assert_eq!(get_card("").network(), api_enums::CardNetwork::Unknown);
+        assert_eq!(
+            get_card("2721000000000000").network(),
+            api_enums::CardNetwork::Unknown
+        );
+    }
 }
@@ -94,12 +94,46 @@ pub struct RefundResponse {
     pub updated_at: Option<PrimitiveDateTime>,
 }
 
+#[derive(Default, Debug, ToSchema, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BulkRefundRequest {
+    /// The refunds to create, each of them created independently of the others
+    pub refunds: Vec<RefundRequest>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
+pub struct BulkRefundResponse {
+    /// The outcome of each refund, in the order of the request
+    pub results: Vec<BulkRefundResult>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
+pub struct BulkRefundResult {
+    /// The identifier for the payment refunded
+    pub payment_id: String,
+    /// The refund, when it was created
+    pub refund: Option<RefundResponse>,
+    /// Why the refund could not be created
+    pub error: Option<BulkRefundError>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
+pub struct BulkRefundError {
+    /// The code identifying the error
+    #[schema(example = "IR_04")]
+    pub code: String,
+    /// A human readable description of the error
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
 pub struct RefundListRequest {
     /// The identifier for the payment
     pub payment_id: Option<String>,
     /// Limit on the number of objects to return
     pub limit: Option<i64>,
+    /// The `next_cursor` of the previous page, to fetch the page following it
+    pub cursor: Option<String>,
     /// The time at which refund is created
     #[serde(default, with = "custom_serde::iso8601::option")]
     pub created: Option<PrimitiveDateTime>,
@@ -129,6 +163,8 @@ pub struct RefundListRequest {
 pub struct RefundListResponse {
     /// The list of refund response
     pub data: Vec<RefundResponse>,
+    /// Cursor to fetch the next page with, absent on the last page
+    pub next_cursor: Option<String>,
 }
 
 /// The status for refunds
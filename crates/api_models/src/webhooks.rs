@@ -1,6 +1,7 @@
 use common_utils::custom_serde;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
+use utoipa::ToSchema;
 
 use crate::{enums as api_enums, payments};
 
@@ -9,11 +10,17 @@ use crate::{enums as api_enums, payments};
 pub enum IncomingWebhookEvent {
     PaymentIntentFailure,
     PaymentIntentSuccess,
+    RefundFailure,
+    RefundSuccess,
+    DisputeOpened,
+    DisputeWon,
+    DisputeLost,
 }
 
 pub enum WebhookFlow {
     Payment,
     Refund,
+    Dispute,
     Subscription,
 }
 
@@ -22,6 +29,11 @@ impl From<IncomingWebhookEvent> for WebhookFlow {
         match evt {
             IncomingWebhookEvent::PaymentIntentFailure => Self::Payment,
             IncomingWebhookEvent::PaymentIntentSuccess => Self::Payment,
+            IncomingWebhookEvent::RefundFailure => Self::Refund,
+            IncomingWebhookEvent::RefundSuccess => Self::Refund,
+            IncomingWebhookEvent::DisputeOpened
+            | IncomingWebhookEvent::DisputeWon
+            | IncomingWebhookEvent::DisputeLost => Self::Dispute,
         }
     }
 }
@@ -33,6 +45,17 @@ pub struct IncomingWebhookDetails {
     pub resource_object: Vec<u8>,
 }
 
+/// Dispute details the connector sends in a dispute webhook.
+#[derive(Debug, Clone)]
+pub struct DisputePayload {
+    pub connector_dispute_id: String,
+    /// Transaction id of the disputed payment at the connector
+    pub connector_transaction_id: String,
+    pub connector_reason_code: Option<String>,
+    pub amount: i64,
+    pub currency: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct OutgoingWebhook {
     pub merchant_id: String,
@@ -48,3 +71,39 @@ pub struct OutgoingWebhook {
 pub enum OutgoingWebhookContent {
     PaymentDetails(payments::PaymentsResponse),
 }
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookDeliveryAttemptListRequest {
+    /// The identifier for the payment
+    pub payment_id: Option<String>,
+    /// Limit on the number of objects to return
+    pub limit: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryAttemptResponse {
+    /// The identifier of the event the webhook was sent for
+    pub event_id: String,
+    /// The type of the event
+    pub event_type: api_enums::EventType,
+    /// The identifier of the object the event is about, such as the payment id
+    pub object_id: String,
+    /// The URL the webhook was sent to
+    pub webhook_url: String,
+    /// The HTTP status code the merchant responded with, absent if no response was received
+    pub status_code: Option<u16>,
+    /// The delivery attempt, starting at 1 for the first delivery
+    pub attempt_number: i32,
+    /// The reason the delivery failed, if it did
+    pub error_message: Option<String>,
+    /// The time at which the delivery was attempted
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryAttemptListResponse {
+    /// The list of webhook delivery attempts, most recent first
+    pub data: Vec<WebhookDeliveryAttemptResponse>,
+}
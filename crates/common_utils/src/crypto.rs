@@ -1,6 +1,6 @@
 //! Utilities for cryptographic algorithms
 use error_stack::{IntoReport, ResultExt};
-use ring::{aead, hmac};
+use ring::{aead, hmac, signature};
 
 use crate::errors::{self, CustomResult};
 
@@ -150,6 +150,66 @@ impl VerifySignature for HmacSha512 {
     }
 }
 
+/// Represents RSASSA-PKCS1-v1_5 signatures with SHA-256, verified against a DER encoded
+/// RSAPublicKey (PKCS#1) passed as the secret
+#[derive(Debug)]
+pub struct RsaSha256;
+
+impl VerifySignature for RsaSha256 {
+    fn verify_signature(
+        &self,
+        public_key: &[u8],
+        signature: &[u8],
+        msg: &[u8],
+    ) -> CustomResult<bool, errors::CryptoError> {
+        if public_key.is_empty() {
+            return Err(errors::CryptoError::SignatureVerificationFailed)
+                .into_report()
+                .attach_printable("Public key is missing");
+        }
+        let public_key =
+            signature::UnparsedPublicKey::new(&signature::RSA_PKCS1_2048_8192_SHA256, public_key);
+
+        Ok(public_key.verify(msg, signature).is_ok())
+    }
+}
+
+/// Scheme a connector signs its webhooks with
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WebhookSignatureScheme {
+    /// Webhooks are not signed and are accepted as is
+    None,
+    /// HMAC-SHA-256 with a secret shared with the connector
+    HmacSha256,
+    /// HMAC-SHA-512 with a secret shared with the connector
+    HmacSha512,
+    /// RSASSA-PKCS1-v1_5 with SHA-256, verified with the public key of the connector
+    RsaSha256,
+}
+
+impl WebhookSignatureScheme {
+    /// Returns the algorithm verifying signatures of the scheme
+    pub fn get_verifier(self) -> Box<dyn VerifySignature + Send> {
+        match self {
+            Self::None => Box::new(NoAlgorithm),
+            Self::HmacSha256 => Box::new(HmacSha256),
+            Self::HmacSha512 => Box::new(HmacSha512),
+            Self::RsaSha256 => Box::new(RsaSha256),
+        }
+    }
+
+    /// Verifies `signature` of `msg` with the algorithm of the scheme, `secret` being the shared
+    /// secret for HMAC schemes and the public key for RSA
+    pub fn verify_signature(
+        self,
+        secret: &[u8],
+        signature: &[u8],
+        msg: &[u8],
+    ) -> CustomResult<bool, errors::CryptoError> {
+        self.get_verifier().verify_signature(secret, signature, msg)
+    }
+}
+
 /// Represents the GCM-AES-256 algorithm
 #[derive(Debug)]
 pub struct GcmAes256 {
@@ -215,6 +275,39 @@ impl DecodeMessage for GcmAes256 {
     }
 }
 
+/// Encrypts `plaintext` with GCM-AES-256 under a freshly generated nonce.
+/// The output holds the nonce, followed by the ciphertext and the authentication tag, and can be
+/// passed to [`decrypt`] as is.
+pub fn encrypt(key: &[u8], plaintext: &[u8]) -> CustomResult<Vec<u8>, errors::CryptoError> {
+    let nonce = generate_cryptographically_secure_random_bytes::<{ aead::NONCE_LEN }>();
+    let (mut ciphertext, mut tag) = GcmAes256 {
+        nonce: nonce.to_vec(),
+    }
+    .encode_message(key, plaintext)?;
+
+    let mut output = nonce.to_vec();
+    output.append(&mut ciphertext);
+    output.append(&mut tag);
+    Ok(output)
+}
+
+/// Decrypts a message produced by [`encrypt`], failing with
+/// [`CryptoError::DecodingFailed`](errors::CryptoError::DecodingFailed) if the key is wrong or
+/// the message was tampered with.
+pub fn decrypt(key: &[u8], message: &[u8]) -> CustomResult<Vec<u8>, errors::CryptoError> {
+    if message.len() < aead::NONCE_LEN {
+        return Err(errors::CryptoError::DecodingFailed)
+            .into_report()
+            .attach_printable("Message is shorter than the nonce");
+    }
+    let (nonce, ciphertext) = message.split_at(aead::NONCE_LEN);
+
+    GcmAes256 {
+        nonce: nonce.to_vec(),
+    }
+    .decode_message(key, ciphertext)
+}
+
 /// Secure Hash Algorithm 512
 #[derive(Debug)]
 pub struct Sha512;
@@ -387,4 +480,116 @@ mod crypto_tests {
 
         assert!(err_decoded.is_err());
     }
+
+    fn get_gcm_aes_256_key() -> Vec<u8> {
+        hex::decode("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f")
+            .expect("Secret decoding")
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let key = get_gcm_aes_256_key();
+        let plaintext = r#"{"card_number":"4111111111111111"}"#.as_bytes();
+
+        let encrypted = super::encrypt(&key, plaintext).expect("Encrypted message");
+        let decrypted = super::decrypt(&key, &encrypted).expect("Decrypted message");
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_encrypt_uses_a_fresh_nonce() {
+        let key = get_gcm_aes_256_key();
+        let plaintext = "4111111111111111".as_bytes();
+
+        let first = super::encrypt(&key, plaintext).expect("Encrypted message");
+        let second = super::encrypt(&key, plaintext).expect("Encrypted message");
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_decrypt_detects_tampering() {
+        let key = get_gcm_aes_256_key();
+        let mut encrypted =
+            super::encrypt(&key, "4111111111111111".as_bytes()).expect("Encrypted message");
+        let last = encrypted.len() - 1;
+        encrypted[last] ^= 0x01;
+
+        let result = super::decrypt(&key, &encrypted);
+
+        assert!(matches!(
+            result.as_ref().map_err(|err| err.current_context()),
+            Err(super::errors::CryptoError::DecodingFailed)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_fails_with_wrong_key_or_truncated_message() {
+        let key = get_gcm_aes_256_key();
+        let mut wrong_key = get_gcm_aes_256_key();
+        wrong_key[0] ^= 0x01;
+        let encrypted =
+            super::encrypt(&key, "4111111111111111".as_bytes()).expect("Encrypted message");
+
+        assert!(super::decrypt(&wrong_key, &encrypted).is_err());
+        assert!(super::decrypt(&key, &encrypted[..4]).is_err());
+    }
+
+    const RSA_PUBLIC_KEY: &str = "3082010a0282010100d0fae66ed909bf98828ca1f3f5613ba8c11a2f7a7e651a5f6e9ef8c578805425d41de34e878c074c6cff0c2a13b08f52a8b85f3de73b7c653f7a7b24b7e1fd8f9840c3dfc4e33bca722f4891766b5f1c0eedd65acea5785a4444bbf03b605fe41d904757fc3f83f0b16c22a8928f31c6362a172c08ceeaaf94f52892006edfdc56fb2dad7a7467f0c043c4a59567252012d10f57fa11283c30ae0170fb208a9c5a6114e661fcb53cec82ae3be32ec49edd0cde85ab4048bacbf28e57324bb31f461c4e93878d5d171a551f9c6afdd6fef9d452c144ddae1a56b68bd5ca13fdf29e899c8be3f01797c505ba9959f2e3704193a0be964e48320c6bf1567a4984530203010001";
+    const RSA_SHA256_SIGNATURE: &str = "687c49152109f6cb3107b325b99d9542d99671f9ea7b9035392202405dcb88c41bd886ff67d7408a0ee12262a6e301abb3cbb468354ba8261e62a7d7f9fe5840f55dfc0ed21a77a367416dcc2613f941b24d530c87d38e774618c41eec62aa68e8b353d4d06c3f69557a47272a3e210cb8fe93248bb3beee3a824445acdf092ff5dea3a39679a04bac5f027f77f414d568120d69a3cb0942645be83856be6a31d3dffa9fce3afb09f983f8a770075b711d0f80e4ed24da0dd93f9564fde1e31674a1c27c10765bf85fb3921b5a35cc830b78366df762d086214a8ffa1c72fdbc4d4ca9e7f9ea89b7a35c47b8297a8f8a09fd20635c0edc9ce731b5c27df161b2";
+
+    #[test]
+    fn test_webhook_signature_scheme_verifies_hmac_sha256() {
+        let right_signature =
+            hex::decode("d5550730377011948f12cc28889bee590d2a5434d6f54b87562f2dbc2657823e")
+                .expect("Right signature decoding");
+        let secret = "hmac_secret_1234".as_bytes();
+        let data = r#"{"type":"payment_intent"}"#.as_bytes();
+
+        let right_verified = super::WebhookSignatureScheme::HmacSha256
+            .verify_signature(secret, &right_signature, data)
+            .expect("Right signature verification result");
+        let wrong_verified = super::WebhookSignatureScheme::HmacSha256
+            .verify_signature(secret, &right_signature, r#"{"type":"refund"}"#.as_bytes())
+            .expect("Wrong signature verification result");
+
+        assert!(right_verified);
+        assert!(!wrong_verified);
+    }
+
+    #[test]
+    fn test_webhook_signature_scheme_verifies_rsa_sha256() {
+        let public_key = hex::decode(RSA_PUBLIC_KEY).expect("Public key decoding");
+        let right_signature = hex::decode(RSA_SHA256_SIGNATURE).expect("Signature decoding");
+        let mut wrong_signature = right_signature.clone();
+        wrong_signature[0] ^= 0x01;
+        let data = r#"{"type":"payment_intent"}"#.as_bytes();
+
+        let right_verified = super::WebhookSignatureScheme::RsaSha256
+            .verify_signature(&public_key, &right_signature, data)
+            .expect("Right signature verification result");
+        let wrong_verified = super::WebhookSignatureScheme::RsaSha256
+            .verify_signature(&public_key, &wrong_signature, data)
+            .expect("Wrong signature verification result");
+
+        assert!(right_verified);
+        assert!(!wrong_verified);
+    }
+
+    #[test]
+    fn test_rsa_sha256_requires_public_key() {
+        let signature = hex::decode(RSA_SHA256_SIGNATURE).expect("Signature decoding");
+
+        let result = super::WebhookSignatureScheme::RsaSha256.verify_signature(
+            &[],
+            &signature,
+            r#"{"type":"payment_intent"}"#.as_bytes(),
+        );
+
+        assert!(matches!(
+            result.as_ref().map_err(|err| err.current_context()),
+            Err(super::errors::CryptoError::SignatureVerificationFailed)
+        ));
+    }
 }
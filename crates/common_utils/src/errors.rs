@@ -70,6 +70,9 @@ pub enum CryptoError {
     /// The cryptographic algorithm was unable to verify the given signature
     #[error("Failed to verify signature")]
     SignatureVerificationFailed,
+    /// The cryptogram authenticating a payment with a network token could not be obtained
+    #[error("Failed to obtain a network token cryptogram")]
+    CryptogramGenerationFailed,
 }
 
 /// Allows [error_stack::Report] to change between error contexts
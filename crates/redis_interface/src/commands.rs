@@ -15,10 +15,10 @@ use common_utils::{
 };
 use error_stack::{IntoReport, ResultExt};
 use fred::{
-    interfaces::{HashesInterface, KeysInterface, StreamsInterface},
+    interfaces::{ClientLike, HashesInterface, KeysInterface, StreamsInterface},
     types::{
         Expiration, FromRedis, MultipleIDs, MultipleKeys, MultipleOrderedPairs, MultipleStrings,
-        RedisKey, RedisMap, RedisValue, SetOptions, XCap, XReadResponse,
+        RedisKey, RedisMap, RedisValue, SetOptions, XCap, XReadResponse, XID,
     },
 };
 use futures::StreamExt;
@@ -30,6 +30,15 @@ use crate::{
 };
 
 impl super::RedisConnectionPool {
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn ping(&self) -> CustomResult<(), errors::RedisError> {
+        self.pool
+            .ping()
+            .await
+            .into_report()
+            .change_context(errors::RedisError::PingFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_key<V>(&self, key: &str, value: V) -> CustomResult<(), errors::RedisError>
     where
@@ -91,6 +100,16 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::GetFailed)
     }
 
+    /// Increments the integer stored at `key`, which starts at zero if the key does not exist.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn increment_key(&self, key: &str) -> CustomResult<i64, errors::RedisError> {
+        self.pool
+            .incr(key)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::IncrementFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn exists<V>(&self, key: &str) -> CustomResult<bool, errors::RedisError>
     where
@@ -171,6 +190,30 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_key_if_not_exists_with_expiry<V>(
+        &self,
+        key: &str,
+        value: V,
+        seconds: i64,
+    ) -> CustomResult<SetnxReply, errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug,
+        V::Error: Into<fred::error::RedisError>,
+    {
+        self.pool
+            .set(
+                key,
+                value,
+                Some(Expiration::EX(seconds)),
+                Some(SetOptions::NX),
+                false,
+            )
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SetFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_expiry(
         &self,
@@ -500,6 +543,34 @@ impl super::RedisConnectionPool {
 
     //                                              Consumer Group API
 
+    /// Returns the pending entries with IDs between `start` and `end` that have been idle for at
+    /// least `min_idle_time` milliseconds, as `(id, consumer, idle time, delivery count)` tuples.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_get_pending_entries(
+        &self,
+        stream: &str,
+        group: &str,
+        min_idle_time: u64,
+        start: &str,
+        end: &str,
+        count: u64,
+    ) -> CustomResult<Vec<(String, String, u64, u64)>, errors::RedisError> {
+        self.pool
+            .xpending(
+                stream,
+                group,
+                (
+                    min_idle_time,
+                    XID::Manual(start.into()),
+                    XID::Manual(end.into()),
+                    count,
+                ),
+            )
+            .await
+            .into_report()
+            .change_context(errors::RedisError::GetPendingEntriesFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn consumer_group_create(
         &self,
@@ -14,6 +14,8 @@ pub enum RedisError {
     SetExpiryFailed,
     #[error("Failed to get key value in Redis")]
     GetFailed,
+    #[error("Failed to increment key value in Redis")]
+    IncrementFailed,
     #[error("Failed to delete key value in Redis")]
     DeleteFailed,
     #[error("Failed to append entry to Redis stream")]
@@ -54,4 +56,8 @@ pub enum RedisError {
     InvalidRedisEntryId,
     #[error("Failed to establish Redis connection")]
     RedisConnectionError,
+    #[error("Failed to ping Redis")]
+    PingFailed,
+    #[error("Failed to get pending entries of Redis stream consumer group")]
+    GetPendingEntriesFailed,
 }
@@ -19,13 +19,14 @@
 
 pub mod commands;
 pub mod errors;
+pub mod stream_consumer;
 pub mod types;
 
 use common_utils::errors::CustomResult;
 use error_stack::{IntoReport, ResultExt};
 use router_env::logger;
 
-pub use self::{commands::*, types::*};
+pub use self::{commands::*, stream_consumer::*, types::*};
 
 pub struct RedisConnectionPool {
     pub pool: fred::pool::RedisPool,
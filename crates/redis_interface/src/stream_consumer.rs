@@ -0,0 +1,406 @@
+//! A consumer of a Redis stream that reads entries as a member of a consumer group
+//!
+//! Wraps the consumer group commands so that callers only deal with the stream, group and
+//! consumer names once, and receive entries with their fields already collected.
+
+use std::{collections::HashMap, sync::Arc};
+
+use common_utils::errors::CustomResult;
+use error_stack::Report;
+use router_env::{instrument, logger, tracing};
+
+use crate::{errors, RedisConnectionPool, RedisEntryId};
+
+/// An entry read from a stream, along with its fields.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StreamEntry {
+    pub id: String,
+    pub fields: HashMap<String, Option<String>>,
+}
+
+/// A pending entry of the consumer group, delivered to a consumer but not acknowledged yet.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PendingEntry {
+    pub id: String,
+    pub consumer: String,
+    /// Milliseconds since the entry was last delivered
+    pub idle_time: u64,
+    pub deliveries: u64,
+}
+
+/// Decides when an entry that keeps failing is given up on and moved to a dead-letter stream,
+/// instead of staying pending forever.
+#[derive(Debug, Clone)]
+pub struct DeadLetterPolicy {
+    pub stream: String,
+    /// Milliseconds a pending entry must be idle for before it is claimed for another attempt
+    pub min_idle_time: u64,
+    pub max_deliveries: u64,
+}
+
+pub struct StreamConsumer {
+    redis: Arc<RedisConnectionPool>,
+    stream: String,
+    group: String,
+    consumer: String,
+}
+
+impl StreamConsumer {
+    pub fn new(
+        redis: Arc<RedisConnectionPool>,
+        stream: impl Into<String>,
+        group: impl Into<String>,
+        consumer: impl Into<String>,
+    ) -> Self {
+        Self {
+            redis,
+            stream: stream.into(),
+            group: group.into(),
+            consumer: consumer.into(),
+        }
+    }
+
+    pub fn stream(&self) -> &str {
+        &self.stream
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn consumer(&self) -> &str {
+        &self.consumer
+    }
+
+    /// Creates the consumer group, starting at `start_id`. Succeeds if the group already exists,
+    /// so that every consumer can call this on startup.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn create_group(
+        &self,
+        start_id: &RedisEntryId,
+    ) -> CustomResult<(), errors::RedisError> {
+        match self
+            .redis
+            .consumer_group_create(&self.stream, &self.group, start_id)
+            .await
+        {
+            Err(error) if is_group_exists_error(&error) => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Reads entries that have not been delivered to any consumer of the group yet
+    /// (`XREADGROUP ... >`), waiting up to `block` milliseconds for new entries if provided.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn read(
+        &self,
+        count: Option<u64>,
+        block: Option<u64>,
+    ) -> CustomResult<Vec<StreamEntry>, errors::RedisError> {
+        let mut response = self
+            .redis
+            .stream_read_with_options(
+                self.stream.as_str(),
+                RedisEntryId::UndeliveredEntryID,
+                count,
+                block,
+                Some((self.group.as_str(), self.consumer.as_str())),
+            )
+            .await?;
+
+        Ok(response
+            .remove(&self.stream)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, fields)| StreamEntry { id, fields })
+            .collect())
+    }
+
+    /// Acknowledges processed entries, removing them from the pending entries list of the group.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn acknowledge(&self, ids: Vec<String>) -> CustomResult<usize, errors::RedisError> {
+        self.redis
+            .stream_acknowledge_entries(&self.stream, &self.group, ids)
+            .await
+    }
+
+    /// Deletes entries from the stream, typically once they have been acknowledged.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn delete(&self, ids: Vec<String>) -> CustomResult<usize, errors::RedisError> {
+        self.redis.stream_delete_entries(&self.stream, ids).await
+    }
+
+    /// Takes ownership of pending entries that have been idle for at least `min_idle_time`
+    /// milliseconds, such as those delivered to a consumer that stopped before acknowledging them.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn claim(
+        &self,
+        min_idle_time: u64,
+        ids: Vec<String>,
+    ) -> CustomResult<Vec<StreamEntry>, errors::RedisError> {
+        let entries: Vec<(String, HashMap<String, Option<String>>)> = self
+            .redis
+            .consumer_group_set_message_owner(
+                &self.stream,
+                &self.group,
+                &self.consumer,
+                min_idle_time,
+                ids,
+            )
+            .await?;
+
+        Ok(entries
+            .into_iter()
+            .map(|(id, fields)| StreamEntry { id, fields })
+            .collect())
+    }
+
+    /// Returns up to `count` pending entries of the group that have been idle for at least
+    /// `min_idle_time` milliseconds.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn pending(
+        &self,
+        min_idle_time: u64,
+        count: u64,
+    ) -> CustomResult<Vec<PendingEntry>, errors::RedisError> {
+        self.get_pending_entries(min_idle_time, "-", "+", count)
+            .await
+    }
+
+    async fn get_pending_entries(
+        &self,
+        min_idle_time: u64,
+        start: &str,
+        end: &str,
+        count: u64,
+    ) -> CustomResult<Vec<PendingEntry>, errors::RedisError> {
+        Ok(self
+            .redis
+            .stream_get_pending_entries(&self.stream, &self.group, min_idle_time, start, end, count)
+            .await?
+            .into_iter()
+            .map(|(id, consumer, idle_time, deliveries)| PendingEntry {
+                id,
+                consumer,
+                idle_time,
+                deliveries,
+            })
+            .collect())
+    }
+
+    /// Claims up to `count` entries that have been pending for longer than the idle threshold of
+    /// the policy, so that they can be processed again. Entries that have already been delivered
+    /// `max_deliveries` times are moved to the dead-letter stream instead of being returned.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn claim_stuck_entries(
+        &self,
+        policy: &DeadLetterPolicy,
+        count: u64,
+    ) -> CustomResult<Vec<StreamEntry>, errors::RedisError> {
+        let pending = self.pending(policy.min_idle_time, count).await?;
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let entries = self
+            .claim(
+                policy.min_idle_time,
+                pending.iter().map(|entry| entry.id.clone()).collect(),
+            )
+            .await?;
+
+        let mut retries = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let deliveries = pending
+                .iter()
+                .find(|pending_entry| pending_entry.id == entry.id)
+                .map(|pending_entry| pending_entry.deliveries)
+                .unwrap_or_default();
+            if deliveries >= policy.max_deliveries {
+                let last_error = format!("Gave up after {deliveries} deliveries");
+                self.dead_letter(&entry, policy, deliveries, &last_error)
+                    .await?;
+            } else {
+                retries.push(entry);
+            }
+        }
+        Ok(retries)
+    }
+
+    /// Records a failed attempt at processing `entry`. The entry stays pending to be claimed again
+    /// later, unless it has reached the maximum number of deliveries, in which case it is moved
+    /// to the dead-letter stream along with `error`. Returns whether the entry was dead-lettered.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn fail(
+        &self,
+        entry: &StreamEntry,
+        policy: &DeadLetterPolicy,
+        error: &str,
+    ) -> CustomResult<bool, errors::RedisError> {
+        let deliveries = self
+            .get_pending_entries(0, &entry.id, &entry.id, 1)
+            .await?
+            .first()
+            .map(|pending_entry| pending_entry.deliveries)
+            .unwrap_or_default();
+
+        if deliveries < policy.max_deliveries {
+            return Ok(false);
+        }
+        self.dead_letter(entry, policy, deliveries, error).await?;
+        Ok(true)
+    }
+
+    /// Appends the entry to the dead-letter stream with the reason it was given up on, and then
+    /// removes it from this stream.
+    async fn dead_letter(
+        &self,
+        entry: &StreamEntry,
+        policy: &DeadLetterPolicy,
+        deliveries: u64,
+        last_error: &str,
+    ) -> CustomResult<(), errors::RedisError> {
+        let mut fields = entry
+            .fields
+            .iter()
+            .filter_map(|(key, value)| value.clone().map(|value| (key.clone(), value)))
+            .collect::<Vec<_>>();
+        fields.extend([
+            ("original_stream".to_string(), self.stream.clone()),
+            ("original_id".to_string(), entry.id.clone()),
+            ("deliveries".to_string(), deliveries.to_string()),
+            ("last_error".to_string(), last_error.to_string()),
+        ]);
+
+        self.redis
+            .stream_append_entry(&policy.stream, &RedisEntryId::AutoGeneratedID, fields)
+            .await?;
+        self.acknowledge(vec![entry.id.clone()]).await?;
+        self.delete(vec![entry.id.clone()]).await?;
+        logger::warn!(
+            stream = %self.stream,
+            entry_id = %entry.id,
+            dead_letter_stream = %policy.stream,
+            deliveries,
+            "Moved entry to dead-letter stream"
+        );
+        Ok(())
+    }
+}
+
+/// Redis replies with a `BUSYGROUP` error when the consumer group already exists.
+fn is_group_exists_error(error: &Report<errors::RedisError>) -> bool {
+    error
+        .downcast_ref::<fred::error::RedisError>()
+        .map(|error| error.details().starts_with("BUSYGROUP"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::RedisSettings;
+
+    #[tokio::test]
+    async fn test_stream_entry_round_trip() {
+        let redis = Arc::new(
+            RedisConnectionPool::new(&RedisSettings::default())
+                .await
+                .unwrap(),
+        );
+        let stream = format!("STREAM_CONSUMER_TEST_{}", std::process::id());
+        let consumer = StreamConsumer::new(redis.clone(), stream.as_str(), "GTEST", "CTEST");
+
+        consumer
+            .create_group(&RedisEntryId::AfterLastID)
+            .await
+            .unwrap();
+        // Creating the group again is not an error
+        consumer
+            .create_group(&RedisEntryId::AfterLastID)
+            .await
+            .unwrap();
+
+        redis
+            .stream_append_entry(
+                &stream,
+                &RedisEntryId::AutoGeneratedID,
+                vec![("task", "sync_refund")],
+            )
+            .await
+            .unwrap();
+
+        let entries = consumer.read(Some(10), None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].fields.get("task"),
+            Some(&Some("sync_refund".to_string()))
+        );
+
+        let acknowledged = consumer
+            .acknowledge(entries.into_iter().map(|entry| entry.id).collect())
+            .await
+            .unwrap();
+        assert_eq!(acknowledged, 1);
+
+        redis.delete_key(&stream).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_poison_entry_is_moved_to_dead_letter_stream() {
+        let redis = Arc::new(
+            RedisConnectionPool::new(&RedisSettings::default())
+                .await
+                .unwrap(),
+        );
+        let stream = format!("STREAM_CONSUMER_POISON_TEST_{}", std::process::id());
+        let policy = DeadLetterPolicy {
+            stream: format!("{stream}_DLQ"),
+            min_idle_time: 0,
+            max_deliveries: 2,
+        };
+        let consumer = StreamConsumer::new(redis.clone(), stream.as_str(), "GTEST", "CTEST");
+        consumer
+            .create_group(&RedisEntryId::AfterLastID)
+            .await
+            .unwrap();
+        redis
+            .stream_append_entry(
+                &stream,
+                &RedisEntryId::AutoGeneratedID,
+                vec![("task", "unparseable")],
+            )
+            .await
+            .unwrap();
+
+        // First delivery fails and the entry stays pending
+        let entry = consumer.read(Some(1), None).await.unwrap().remove(0);
+        assert!(!consumer.fail(&entry, &policy, "parse error").await.unwrap());
+
+        // Second delivery, through a claim, fails as well and exhausts the deliveries
+        let entry = consumer
+            .claim_stuck_entries(&policy, 10)
+            .await
+            .unwrap()
+            .remove(0);
+        assert!(consumer.fail(&entry, &policy, "parse error").await.unwrap());
+
+        assert!(consumer.pending(0, 10).await.unwrap().is_empty());
+        assert_eq!(redis.stream_get_length(stream.as_str()).await.unwrap(), 0);
+        let dead_letters = redis
+            .stream_read_entries(policy.stream.as_str(), "0", Some(10))
+            .await
+            .unwrap();
+        let (_, fields) = &dead_letters[&policy.stream][0];
+        assert_eq!(fields.get("task"), Some(&"unparseable".to_string()));
+        assert_eq!(fields.get("last_error"), Some(&"parse error".to_string()));
+        assert_eq!(fields.get("deliveries"), Some(&"2".to_string()));
+
+        redis.delete_key(&stream).await.unwrap();
+        redis.delete_key(&policy.stream).await.unwrap();
+    }
+}
@@ -28,9 +28,13 @@ async fn main() -> ApplicationResult<()> {
     }
 
     #[allow(clippy::expect_used)]
-    let conf = Settings::with_config_path(cmd_line.config_path)
+    let mut conf = Settings::with_config_path(cmd_line.config_path)
         .expect("Unable to construct application configuration");
     #[allow(clippy::expect_used)]
+    conf.resolve_secrets()
+        .await
+        .expect("Failed to resolve the secrets of the router configuration");
+    #[allow(clippy::expect_used)]
     conf.validate()
         .expect("Failed to validate router configuration");
 
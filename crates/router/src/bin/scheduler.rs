@@ -3,7 +3,11 @@ use std::sync::Arc;
 
 use router::{
     configs::settings::{CmdLineConf, Settings},
-    core::errors::{self, CustomResult},
+    core::{
+        errors::{self, CustomResult},
+        payments::{response_retention, stale_sync},
+        refunds,
+    },
     logger, routes, scheduler,
 };
 
@@ -16,8 +20,12 @@ async fn main() -> CustomResult<(), errors::ProcessTrackerError> {
     let cmd_line = <CmdLineConf as clap::Parser>::parse();
 
     #[allow(clippy::expect_used)]
-    let conf = Settings::with_config_path(cmd_line.config_path)
+    let mut conf = Settings::with_config_path(cmd_line.config_path)
         .expect("Unable to construct application configuration");
+    #[allow(clippy::expect_used)]
+    conf.resolve_secrets()
+        .await
+        .expect("Failed to resolve the secrets of the scheduler configuration");
 
     let mut state = routes::AppState::new(conf).await;
     let _guard =
@@ -71,6 +79,11 @@ async fn start_scheduler(
         .scheduler
         .clone()
         .ok_or(errors::ProcessTrackerError::ConfigurationError)?;
+
+    refunds::add_pending_refunds_sync_task(&*state.store).await?;
+    stale_sync::add_stale_payments_sync_task(&*state.store).await?;
+    response_retention::add_connector_response_retention_task(&*state.store).await?;
+
     scheduler::start_process_tracker(state, Arc::new(options), flow, Arc::new(scheduler_settings))
         .await
 }
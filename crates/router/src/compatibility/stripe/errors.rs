@@ -55,6 +55,15 @@ pub enum StripeErrorCode {
     #[error(error_type = StripeErrorType::ApiError, code = "internal_server_error", message = "Server is down")]
     InternalServerError,
 
+    #[error(error_type = StripeErrorType::ApiError, code = "rate_limit", message = "Too many requests hit the API too quickly")]
+    RateLimit,
+
+    #[error(error_type = StripeErrorType::ApiError, code = "gateway_timeout", message = "The connector did not respond in time")]
+    GatewayTimeout,
+
+    #[error(error_type = StripeErrorType::ApiError, code = "connector_unavailable", message = "The connector is temporarily unavailable")]
+    ConnectorUnavailable,
+
     #[error(error_type = StripeErrorType::ApiError, code = "internal_server_error", message = "Server is down")]
     DuplicateRefundRequest,
 
@@ -168,6 +177,8 @@ pub enum StripeErrorCode {
     PaymentIntentMandateInvalid { message: String },
     #[error(error_type = StripeErrorType::InvalidRequestError, code = "", message = "The payment with the specified payment_id '{payment_id}' already exists in our records.")]
     DuplicatePayment { payment_id: String },
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_key_in_use", message = "A request with the same idempotency key is still being processed.")]
+    IdempotencyKeyInUse,
     // [#216]: https://github.com/juspay/hyperswitch/issues/216
     // Implement the remaining stripe error codes
 
@@ -210,7 +221,6 @@ pub enum StripeErrorCode {
         DebitNotAuthorized,
         EmailInvalid,
         ExpiredCard,
-        IdempotencyKeyInUse,
         IncorrectAddress,
         IncorrectCvc,
         IncorrectNumber,
@@ -331,6 +341,7 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::Unauthorized
             | errors::ApiErrorResponse::InvalidJwtToken
             | errors::ApiErrorResponse::GenericUnauthorized { .. }
+            | errors::ApiErrorResponse::AccessForbidden
             | errors::ApiErrorResponse::InvalidEphemeralKey => Self::Unauthorized,
             errors::ApiErrorResponse::InvalidRequestUrl
             | errors::ApiErrorResponse::InvalidHttpMethod => Self::InvalidRequestUrl,
@@ -367,9 +378,16 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::CardExpired { data } => Self::ExpiredCard,
             errors::ApiErrorResponse::RefundNotPossible { connector } => Self::RefundFailed,
             errors::ApiErrorResponse::RefundFailed { data } => Self::RefundFailed, // Nothing at stripe to map
+            errors::ApiErrorResponse::EvidenceSubmissionFailed { data } => {
+                Self::InternalServerError
+            } // not a stripe code
 
             errors::ApiErrorResponse::InternalServerError => Self::InternalServerError, // not a stripe code
             errors::ApiErrorResponse::ExternalConnectorError { .. } => Self::InternalServerError,
+            errors::ApiErrorResponse::ConnectorRateLimited { .. }
+            | errors::ApiErrorResponse::RateLimited { .. } => Self::RateLimit,
+            errors::ApiErrorResponse::ConnectorTimeout => Self::GatewayTimeout,
+            errors::ApiErrorResponse::ConnectorUnavailable => Self::ConnectorUnavailable,
             errors::ApiErrorResponse::IncorrectConnectorNameGiven => Self::InternalServerError,
             errors::ApiErrorResponse::MandateActive => Self::MandateActive, //not a stripe code
             errors::ApiErrorResponse::CustomerRedacted => Self::CustomerRedacted, //not a stripe code
@@ -377,6 +395,8 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::DuplicateRefundRequest => Self::DuplicateRefundRequest,
             errors::ApiErrorResponse::RefundNotFound => Self::RefundNotFound,
             errors::ApiErrorResponse::CustomerNotFound => Self::CustomerNotFound,
+            errors::ApiErrorResponse::DisputeNotFound
+            | errors::ApiErrorResponse::WebhookEventNotFound => Self::ResourceIdNotFound,
             errors::ApiErrorResponse::PaymentNotFound => Self::PaymentNotFound,
             errors::ApiErrorResponse::PaymentMethodNotFound => Self::PaymentMethodNotFound,
             errors::ApiErrorResponse::ClientSecretNotGiven => Self::ClientSecretNotFound,
@@ -411,6 +431,11 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             },
             errors::ApiErrorResponse::MaximumRefundCount => Self::MaximumRefundCount,
             errors::ApiErrorResponse::PaymentNotSucceeded => Self::PaymentFailed,
+            errors::ApiErrorResponse::CaptureAmountExceedsCapturableAmount { .. } => {
+                Self::PaymentIntentInvalidParameter {
+                    param: "amount_to_capture".to_owned(),
+                }
+            }
             errors::ApiErrorResponse::DuplicateMandate => Self::DuplicateMandate,
             errors::ApiErrorResponse::SuccessfulPaymentNotFound => Self::SuccessfulPaymentNotFound,
             errors::ApiErrorResponse::AddressNotFound => Self::AddressNotFound,
@@ -429,6 +454,7 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::DuplicatePayment { payment_id } => {
                 Self::DuplicatePayment { payment_id }
             }
+            errors::ApiErrorResponse::IdempotentRequestInProgress => Self::IdempotencyKeyInUse,
         }
     }
 }
@@ -479,7 +505,12 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::InternalServerError
             | Self::MandateActive
             | Self::CustomerRedacted => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::ReturnUrlUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Self::ReturnUrlUnavailable | Self::ConnectorUnavailable => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            Self::RateLimit => StatusCode::TOO_MANY_REQUESTS,
+            Self::IdempotencyKeyInUse => StatusCode::CONFLICT,
+            Self::GatewayTimeout => StatusCode::GATEWAY_TIMEOUT,
         }
     }
 
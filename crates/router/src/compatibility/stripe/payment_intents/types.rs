@@ -329,6 +329,7 @@ impl TryFrom<StripePaymentListConstraints> for payments::PaymentListConstraints
             customer_id: item.customer,
             starting_after: item.starting_after,
             ending_before: item.ending_before,
+            cursor: None,
             limit: item.limit,
             created: from_timestamp_to_datetime(item.created)?,
             created_lt: from_timestamp_to_datetime(item.created_lt)?,
@@ -1,3 +1,4 @@
 mod defaults;
+pub mod secrets;
 pub mod settings;
 mod validations;
@@ -24,11 +24,25 @@ impl Default for super::settings::Database {
     }
 }
 
+impl Default for super::settings::VaultSettings {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:8200".into(),
+            token: String::new(),
+            mount: "secret".into(),
+        }
+    }
+}
+
 impl Default for super::settings::Secrets {
     fn default() -> Self {
         Self {
             jwt_secret: "secret".into(),
             admin_api_key: "test_admin".into(),
+            // Keys encrypting stored data have no default, so that they are never left at a
+            // value published in the repository
+            vault_encryption_key: String::new(),
+            connector_credentials_encryption_key: String::new(),
         }
     }
 }
@@ -68,6 +82,11 @@ impl Default for super::settings::Refund {
         Self {
             max_attempts: 10,
             max_age: 365,
+            sync_batch_size: 100,
+            sync_concurrency: 10,
+            sync_interval: 300,
+            bulk_max_refunds: 100,
+            bulk_concurrency: 10,
         }
     }
 }
@@ -78,6 +97,83 @@ impl Default for super::settings::EphemeralConfig {
     }
 }
 
+impl Default for super::settings::ConnectorRetry {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay_in_millis: 100,
+        }
+    }
+}
+
+impl Default for super::settings::ConnectorRequestTimeout {
+    fn default() -> Self {
+        Self {
+            default_in_secs: crate::consts::REQUEST_TIME_OUT,
+            overrides: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl Default for super::settings::ConnectorCircuitBreaker {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_ratio_threshold: 0.5,
+            minimum_requests: 20,
+            window_in_secs: 60,
+            cooldown_in_secs: 30,
+        }
+    }
+}
+
+impl Default for super::settings::ApiRateLimit {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_requests: 100,
+            window_in_secs: 10,
+        }
+    }
+}
+
+impl Default for super::settings::StalePaymentsSync {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            concurrency: 10,
+            interval: 300,
+            stale_after: 900,
+            max_sync_failures: 10,
+        }
+    }
+}
+
+impl Default for super::settings::ConnectorResponseRetention {
+    fn default() -> Self {
+        Self {
+            retention_period: 30 * 24 * 60 * 60,
+            interval: 24 * 60 * 60,
+        }
+    }
+}
+
+impl Default for super::settings::FxQuotes {
+    fn default() -> Self {
+        Self { max_quote_age: 300 }
+    }
+}
+
+impl Default for super::settings::AutoCapture {
+    fn default() -> Self {
+        Self {
+            // Most card networks hold an authorization for 7 days
+            authorization_validity: 604800,
+            default_capture_delay: None,
+        }
+    }
+}
+
 impl Default for super::settings::SchedulerSettings {
     fn default() -> Self {
         Self {
@@ -105,6 +201,8 @@ impl Default for super::settings::ConsumerSettings {
         Self {
             disabled: false,
             consumer_group: "SCHEDULER_GROUP".into(),
+            pending_idle_threshold: 300_000,
+            max_deliveries: 5,
         }
     }
 }
@@ -121,3 +219,13 @@ impl Default for super::settings::DrainerSettings {
         }
     }
 }
+
+impl Default for super::settings::WebhooksSettings {
+    fn default() -> Self {
+        Self {
+            outgoing_enabled: false,
+            signature_algorithm: super::settings::WebhookSignatureAlgorithm::HmacSha256,
+            signature_header: "X-Webhook-Signature".into(),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Resolution of secret references in the configuration.
+//!
+//! A secret value of the form `<scheme>:<path>` is a reference, fetched from the provider of the
+//! scheme when the application starts:
+//!
+//! - `env:ROUTER_JWT_SECRET` reads the environment variable `ROUTER_JWT_SECRET`.
+//! - `file:/run/secrets/jwt_secret` reads the file, without its trailing newline.
+//! - `vault:router/secrets#jwt_secret` reads the key `jwt_secret` of the secret `router/secrets`
+//!   from the KV version 2 engine of HashiCorp Vault, configured in `[secrets_provider.vault]`.
+//!
+//! Any other value is used as is.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::settings::{SecretsProviderSettings, VaultSettings};
+use crate::core::errors::{ApplicationError, ApplicationResult};
+
+const ENV_SCHEME: &str = "env";
+const FILE_SCHEME: &str = "file";
+const VAULT_SCHEME: &str = "vault";
+
+/// Schemes that always denote a reference, so that a reference to a provider which is not
+/// configured fails instead of being used as the secret itself.
+const RESERVED_SCHEMES: [&str; 3] = [ENV_SCHEME, FILE_SCHEME, VAULT_SCHEME];
+
+#[async_trait]
+pub trait SecretsProvider: Send + Sync {
+    /// Scheme of the references the provider resolves, `env` for `env:ROUTER_JWT_SECRET`.
+    fn scheme(&self) -> &'static str;
+
+    /// Fetches the secret at `path`, the part of the reference following the scheme.
+    async fn get_secret(&self, path: &str) -> ApplicationResult<String>;
+}
+
+pub struct EnvSecretsProvider;
+
+#[async_trait]
+impl SecretsProvider for EnvSecretsProvider {
+    fn scheme(&self) -> &'static str {
+        ENV_SCHEME
+    }
+
+    async fn get_secret(&self, path: &str) -> ApplicationResult<String> {
+        std::env::var(path).map_err(|error| {
+            resolution_error(format!(
+                "environment variable {path} is not usable: {error}"
+            ))
+        })
+    }
+}
+
+pub struct FileSecretsProvider;
+
+#[async_trait]
+impl SecretsProvider for FileSecretsProvider {
+    fn scheme(&self) -> &'static str {
+        FILE_SCHEME
+    }
+
+    async fn get_secret(&self, path: &str) -> ApplicationResult<String> {
+        std::fs::read_to_string(path)
+            .map(|secret| secret.trim_end_matches(['\r', '\n']).to_string())
+            .map_err(|error| resolution_error(format!("failed to read file {path}: {error}")))
+    }
+}
+
+pub struct VaultSecretsProvider {
+    client: reqwest::Client,
+    settings: VaultSettings,
+}
+
+#[derive(Deserialize)]
+struct VaultSecretResponse {
+    data: VaultSecretData,
+}
+
+#[derive(Deserialize)]
+struct VaultSecretData {
+    data: HashMap<String, String>,
+}
+
+impl VaultSecretsProvider {
+    pub fn new(settings: VaultSettings) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            settings,
+        }
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for VaultSecretsProvider {
+    fn scheme(&self) -> &'static str {
+        VAULT_SCHEME
+    }
+
+    async fn get_secret(&self, path: &str) -> ApplicationResult<String> {
+        let (secret_path, key) = path.split_once('#').ok_or_else(|| {
+            resolution_error(format!(
+                "Vault reference {path} is missing the key, expected <path>#<key>"
+            ))
+        })?;
+        let url = format!(
+            "{}/v1/{}/data/{secret_path}",
+            self.settings.url.trim_end_matches('/'),
+            self.settings.mount
+        );
+
+        let response = self
+            .client
+            .get(url)
+            .header("X-Vault-Token", &self.settings.token)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|error| {
+                resolution_error(format!(
+                    "failed to fetch Vault secret {secret_path}: {error}"
+                ))
+            })?
+            .json::<VaultSecretResponse>()
+            .await
+            .map_err(|error| {
+                resolution_error(format!(
+                    "failed to parse Vault secret {secret_path}: {error}"
+                ))
+            })?;
+
+        response
+            .data
+            .data
+            .get(key)
+            .cloned()
+            .ok_or_else(|| resolution_error(format!("Vault secret {secret_path} has no key {key}")))
+    }
+}
+
+/// Resolves secret references with the providers it holds.
+pub struct SecretsResolver {
+    providers: Vec<Box<dyn SecretsProvider>>,
+}
+
+impl SecretsResolver {
+    pub fn new(providers: Vec<Box<dyn SecretsProvider>>) -> Self {
+        Self { providers }
+    }
+
+    /// Environment variable and file providers, and the Vault provider if Vault is configured.
+    pub fn from_settings(settings: &SecretsProviderSettings) -> Self {
+        let mut providers: Vec<Box<dyn SecretsProvider>> =
+            vec![Box::new(EnvSecretsProvider), Box::new(FileSecretsProvider)];
+        if let Some(vault) = settings.vault.clone() {
+            providers.push(Box::new(VaultSecretsProvider::new(vault)));
+        }
+        Self::new(providers)
+    }
+
+    /// Replaces `value` with the secret it refers to, leaving values that are not references
+    /// untouched.
+    pub async fn resolve(&self, value: &mut String) -> ApplicationResult<()> {
+        let (scheme, path) = match value.split_once(':') {
+            Some(reference) => reference,
+            None => return Ok(()),
+        };
+        match self
+            .providers
+            .iter()
+            .find(|provider| provider.scheme() == scheme)
+        {
+            Some(provider) => {
+                *value = provider.get_secret(path).await?;
+                Ok(())
+            }
+            None if RESERVED_SCHEMES.contains(&scheme) => Err(resolution_error(format!(
+                "no secrets provider is configured for {scheme} references"
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn resolution_error(message: String) -> ApplicationError {
+    ApplicationError::ConfigurationError(config::ConfigError::Message(format!(
+        "Failed to resolve secret: {message}"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    struct FakeSecretsProvider;
+
+    #[async_trait]
+    impl SecretsProvider for FakeSecretsProvider {
+        fn scheme(&self) -> &'static str {
+            "fake"
+        }
+
+        async fn get_secret(&self, path: &str) -> ApplicationResult<String> {
+            match path {
+                "router/jwt_secret" => Ok("resolved_jwt_secret".to_string()),
+                path => Err(resolution_error(format!("{path} not found"))),
+            }
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_referenced_secret_is_resolved() {
+        let resolver = SecretsResolver::new(vec![Box::new(FakeSecretsProvider)]);
+        let mut secret = "fake:router/jwt_secret".to_string();
+
+        resolver.resolve(&mut secret).await.unwrap();
+
+        assert_eq!(secret, "resolved_jwt_secret");
+    }
+
+    #[actix_rt::test]
+    async fn test_secret_references_in_settings_are_resolved() {
+        let resolver = SecretsResolver::new(vec![Box::new(FakeSecretsProvider)]);
+        let mut settings = crate::configs::settings::Settings::default();
+        settings.secrets.jwt_secret = "fake:router/jwt_secret".to_string();
+
+        settings.resolve_secrets_with(&resolver).await.unwrap();
+
+        assert_eq!(settings.secrets.jwt_secret, "resolved_jwt_secret");
+        assert_eq!(settings.secrets.admin_api_key, "test_admin");
+    }
+
+    #[actix_rt::test]
+    async fn test_plain_secret_is_kept() {
+        let resolver = SecretsResolver::new(vec![Box::new(FakeSecretsProvider)]);
+        for plain_secret in ["test_admin", "db_user:db_pass"] {
+            let mut secret = plain_secret.to_string();
+
+            resolver.resolve(&mut secret).await.unwrap();
+
+            assert_eq!(secret, plain_secret);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_unresolvable_secret_is_a_configuration_error() {
+        let resolver = SecretsResolver::new(vec![Box::new(FakeSecretsProvider)]);
+        for reference in ["fake:router/missing", "vault:router/secrets#jwt_secret"] {
+            let mut secret = reference.to_string();
+
+            let result = resolver.resolve(&mut secret).await;
+
+            assert!(matches!(
+                result,
+                Err(ApplicationError::ConfigurationError(_))
+            ));
+            assert_eq!(secret, reference);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_environment_variable_secret_is_resolved() {
+        std::env::set_var("ROUTER_TEST_SECRETS_PROVIDER_JWT", "jwt_from_env");
+        let resolver = SecretsResolver::from_settings(&SecretsProviderSettings::default());
+        let mut secret = "env:ROUTER_TEST_SECRETS_PROVIDER_JWT".to_string();
+
+        resolver.resolve(&mut secret).await.unwrap();
+
+        assert_eq!(secret, "jwt_from_env");
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
 
 use common_utils::ext_traits::ConfigExt;
 use config::{Environment, File};
@@ -6,9 +6,11 @@ use redis_interface::RedisSettings;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use serde::Deserialize;
 
+use super::secrets::SecretsResolver;
 use crate::{
     core::errors::{ApplicationError, ApplicationResult},
     env::{self, logger, Env},
+    types::{api::FlowName, storage::enums::Currency},
 };
 
 #[derive(clap::Parser, Default)]
@@ -42,15 +44,29 @@ pub struct Settings {
     pub redis: RedisSettings,
     pub log: Log,
     pub secrets: Secrets,
+    pub secrets_provider: SecretsProviderSettings,
     pub locker: Locker,
+    pub network_tokenization: NetworkTokenization,
+    pub test_mode: TestMode,
     pub connectors: Connectors,
     pub refund: Refund,
     pub eph_key: EphemeralConfig,
+    pub connector_retry: ConnectorRetry,
+    pub connector_request_timeout: ConnectorRequestTimeout,
+    pub connector_flow_policies: ConnectorFlowPolicies,
+    pub connector_circuit_breaker: ConnectorCircuitBreaker,
+    pub api_rate_limit: ApiRateLimit,
+    pub stale_payments_sync: StalePaymentsSync,
+    pub connector_response_retention: ConnectorResponseRetention,
+    pub auto_capture: AutoCapture,
     pub scheduler: Option<SchedulerSettings>,
     #[cfg(feature = "kv_store")]
     pub drainer: DrainerSettings,
     pub jwekey: Jwekey,
     pub webhooks: WebhooksSettings,
+    pub return_url: ReturnUrl,
+    pub merchant_currencies: MerchantCurrencies,
+    pub fx_quotes: FxQuotes,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -58,6 +74,27 @@ pub struct Settings {
 pub struct Secrets {
     pub jwt_secret: String,
     pub admin_api_key: String,
+    /// Hex encoded 256-bit key used to encrypt card data stored by the mock locker.
+    pub vault_encryption_key: String,
+    /// Hex encoded 256-bit key used to encrypt the connector credentials of merchants.
+    pub connector_credentials_encryption_key: String,
+}
+
+/// Providers of the secrets referenced in the configuration, see [`super::secrets`].
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SecretsProviderSettings {
+    /// Resolves `vault:` references when set.
+    pub vault: Option<VaultSettings>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct VaultSettings {
+    pub url: String,
+    pub token: String,
+    /// Mount path of the KV version 2 secrets engine.
+    pub mount: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -68,11 +105,40 @@ pub struct Locker {
     pub basilisk_host: String,
 }
 
+/// Token service exchanging saved cards for network tokens, see
+/// [`crate::core::payment_methods::network_tokenization`].
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkTokenization {
+    /// Store network tokens instead of card numbers for the cards saved by customers.
+    pub enabled: bool,
+    pub url: String,
+    pub api_key: String,
+}
+
+/// Magic test cards, see [`crate::core::payments::test_cards`].
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TestMode {
+    /// Answer payments with test cards by their fixed outcome instead of rejecting them.
+    pub enabled: bool,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct Refund {
     pub max_attempts: usize,
     pub max_age: i64,
+    /// Maximum number of pending refunds picked up by one run of the refund sync job.
+    pub sync_batch_size: i64,
+    /// Number of pending refunds synced with the connectors concurrently.
+    pub sync_concurrency: usize,
+    /// Interval between two runs of the refund sync job, in seconds.
+    pub sync_interval: i64,
+    /// Maximum number of refunds in one bulk refund request.
+    pub bulk_max_refunds: usize,
+    /// Number of payments whose refunds of a bulk refund request are created concurrently.
+    pub bulk_concurrency: usize,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -81,6 +147,166 @@ pub struct EphemeralConfig {
     pub validity: i64,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorRetry {
+    pub max_retries: u32,
+    pub base_delay_in_millis: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorRequestTimeout {
+    pub default_in_secs: u64,
+    /// Timeouts for specific connectors, keyed by connector name.
+    pub overrides: HashMap<String, u64>,
+}
+
+/// Timeout and retry policies of the connector requests of specific flows, replacing
+/// `connector_retry` and `connector_request_timeout` for those flows.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ConnectorFlowPolicies {
+    pub authorize: ConnectorFlowPolicy,
+    pub capture: ConnectorFlowPolicy,
+    /// Policy of refund executions.
+    pub refund: ConnectorFlowPolicy,
+    /// Policy of payment and refund syncs.
+    pub sync: ConnectorFlowPolicy,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ConnectorFlowPolicy {
+    /// A timeout configured for the connector in `connector_request_timeout.overrides` takes
+    /// precedence over this one.
+    pub timeout_in_secs: Option<u64>,
+    pub max_retries: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorCircuitBreaker {
+    pub enabled: bool,
+    /// Share of failed requests in a window above which the circuit opens.
+    pub failure_ratio_threshold: f64,
+    /// Requests needed in a window before the failure ratio is considered.
+    pub minimum_requests: u32,
+    pub window_in_secs: i64,
+    /// Time an open circuit waits before letting a trial request through.
+    pub cooldown_in_secs: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ApiRateLimit {
+    pub enabled: bool,
+    /// Requests an API key can send in a window.
+    pub max_requests: u32,
+    pub window_in_secs: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct StalePaymentsSync {
+    /// Maximum number of stale payment attempts picked up by one run of the job.
+    pub batch_size: i64,
+    /// Number of stale payment attempts synced with the connectors concurrently.
+    pub concurrency: usize,
+    /// Interval between two runs of the job, in seconds.
+    pub interval: i64,
+    /// Time since the last update after which an in-flight attempt is considered stale, in
+    /// seconds.
+    pub stale_after: i64,
+    /// Number of failed syncs after which an attempt is no longer picked up and is left for
+    /// manual review.
+    pub max_sync_failures: i32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorResponseRetention {
+    /// Time since the last update of a payment attempt after which its stored connector response
+    /// body is dropped, in seconds.
+    pub retention_period: i64,
+    /// Interval between two runs of the job, in seconds.
+    pub interval: i64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct AutoCapture {
+    /// Time after which an authorization is assumed to have expired at the connector, in
+    /// seconds. Scheduled captures falling after it fail without calling the connector.
+    pub authorization_validity: i64,
+    /// Time after their authorization at which manual capture payments without a `capture_on`
+    /// are captured, in seconds. Such payments are left to the merchant to capture when unset.
+    pub default_capture_delay: Option<i64>,
+}
+
+impl ConnectorRequestTimeout {
+    pub fn get_timeout(&self, connector_name: &str, flow_policy: &ConnectorFlowPolicy) -> Duration {
+        Duration::from_secs(
+            self.overrides
+                .get(connector_name)
+                .copied()
+                .or(flow_policy.timeout_in_secs)
+                .unwrap_or(self.default_in_secs),
+        )
+    }
+}
+
+impl MerchantCurrencies {
+    pub fn get_default_currency(&self, merchant_id: &str) -> Option<Currency> {
+        self.overrides
+            .get(merchant_id)
+            .and_then(|merchant_currencies| merchant_currencies.default_currency)
+            .or(self.default_currency)
+    }
+
+    pub fn supports_currency(&self, merchant_id: &str, currency: Currency) -> bool {
+        self.overrides
+            .get(merchant_id)
+            .map_or(true, |merchant_currencies| {
+                merchant_currencies.supported_currencies.is_empty()
+                    || merchant_currencies.supported_currencies.contains(&currency)
+            })
+    }
+
+    pub fn get_settlement_currency(&self, merchant_id: &str) -> Option<Currency> {
+        self.overrides
+            .get(merchant_id)
+            .and_then(|merchant_currencies| merchant_currencies.settlement_currency)
+    }
+}
+
+impl ConnectorRetry {
+    pub fn with_flow_policy(&self, flow_policy: &ConnectorFlowPolicy) -> Self {
+        Self {
+            max_retries: flow_policy.max_retries.unwrap_or(self.max_retries),
+            ..self.clone()
+        }
+    }
+}
+
+impl ConnectorFlowPolicies {
+    /// Policy of the flow `flow`. Flows without a policy of their own, like `Void`, get the
+    /// default one.
+    pub fn get_policy(&self, flow: FlowName) -> ConnectorFlowPolicy {
+        match flow {
+            FlowName::Authorize | FlowName::Verify => self.authorize.clone(),
+            FlowName::Capture => self.capture.clone(),
+            FlowName::Execute => self.refund.clone(),
+            FlowName::PSync | FlowName::RSync => self.sync.clone(),
+            FlowName::Void
+            | FlowName::Session
+            | FlowName::PreProcessing
+            | FlowName::Evidence
+            | FlowName::AccessTokenAuth => ConnectorFlowPolicy::default(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct Jwekey {
@@ -141,9 +367,12 @@ pub struct Connectors {
     pub braintree: ConnectorParams,
     pub checkout: ConnectorParams,
     pub cybersource: ConnectorParams,
+    #[cfg(feature = "dummy_connector")]
+    pub dummyconnector: ConnectorParams,
     pub fiserv: ConnectorParams,
     pub globalpay: ConnectorParams,
     pub klarna: ConnectorParams,
+    pub opayo: ConnectorParams,
     pub payu: ConnectorParams,
     pub rapyd: ConnectorParams,
     pub shift4: ConnectorParams,
@@ -169,6 +398,21 @@ pub struct SchedulerSettings {
     pub consumer: ConsumerSettings,
 }
 
+impl SchedulerSettings {
+    /// Stream entries that repeatedly fail to be processed are moved to this stream.
+    pub fn dead_letter_stream(&self) -> String {
+        format!("{}_DEAD_LETTER", self.stream)
+    }
+
+    pub fn dead_letter_policy(&self) -> redis_interface::DeadLetterPolicy {
+        redis_interface::DeadLetterPolicy {
+            stream: self.dead_letter_stream(),
+            min_idle_time: self.consumer.pending_idle_threshold,
+            max_deliveries: self.consumer.max_deliveries,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct ProducerSettings {
@@ -185,6 +429,11 @@ pub struct ProducerSettings {
 pub struct ConsumerSettings {
     pub disabled: bool,
     pub consumer_group: String,
+    /// Time in milliseconds after which an unacknowledged stream entry is claimed for another
+    /// attempt
+    pub pending_idle_threshold: u64,
+    /// Number of deliveries after which a failing stream entry is moved to the dead-letter stream
+    pub max_deliveries: u64,
 }
 
 #[cfg(feature = "kv_store")]
@@ -198,10 +447,57 @@ pub struct DrainerSettings {
     pub loop_interval: u32,     // in milliseconds
 }
 
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct WebhooksSettings {
     pub outgoing_enabled: bool,
+    /// Algorithm used to sign outgoing webhook bodies with the merchant's hash key.
+    pub signature_algorithm: WebhookSignatureAlgorithm,
+    /// Header carrying the hex encoded signature of outgoing webhooks.
+    pub signature_header: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ReturnUrl {
+    /// Accepts plain http return URLs, which should only be enabled outside production.
+    pub allow_http: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MerchantCurrencies {
+    /// Currency of the payments that omit one, for merchants without a default of their own.
+    pub default_currency: Option<Currency>,
+    /// Currencies of specific merchants, keyed by merchant id.
+    pub overrides: HashMap<String, MerchantCurrencyConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MerchantCurrencyConfig {
+    pub default_currency: Option<Currency>,
+    /// Currencies the merchant accepts payments in, any currency when empty.
+    pub supported_currencies: Vec<Currency>,
+    /// Currency the merchant settles in, when it differs from the currency of its payments.
+    pub settlement_currency: Option<Currency>,
+}
+
+/// Quotes used to annotate payments with the amount the merchant settles, see
+/// [`crate::core::payments::fx`].
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct FxQuotes {
+    /// Age after which a quote is stale and no longer used, in seconds.
+    pub max_quote_age: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookSignatureAlgorithm {
+    #[default]
+    HmacSha256,
+    HmacSha512,
 }
 
 impl Settings {
@@ -217,17 +513,48 @@ impl Settings {
         //    specified by the `RUN_ENV` environment variable. `RUN_ENV` can be one of
         //    `Development`, `Sandbox` or `Production`. If nothing is specified for `RUN_ENV`,
         //    `/config/Development.toml` file is read.
+        //    When the config directory has a `config.toml` file, it is treated as the base file
+        //    and the `RUN_ENV` specific file, if present, is layered on top of it.
+        //    A config file passed explicitly is read on its own, without any layering.
         // 3. Environment variables prefixed with `ROUTER` and each level separated by double
         //    underscores.
         //
         // Values in config file override the defaults in `Default` trait, and the values set using
         // environment variables override both the defaults and the config file values.
 
-        let environment = env::which();
-        let config_path = router_env::Config::config_path(&environment.to_string(), config_path);
+        let environment = env::which().to_string();
+        let (base_path, overlay_path) = match config_path {
+            Some(config_path) => (config_path, None),
+            None => {
+                let environment_path = router_env::Config::config_path(&environment, None);
+                match environment_path
+                    .parent()
+                    .map(|directory| directory.join("config.toml"))
+                    .filter(|base_path| base_path.is_file())
+                {
+                    Some(base_path) => (base_path, Some(environment_path)),
+                    None => (environment_path, None),
+                }
+            }
+        };
+
+        Self::with_layers(&environment, base_path, overlay_path)
+    }
+
+    /// Loads the base config file, the optional overlay file and the `ROUTER` environment
+    /// variables, each overriding the values of the previous ones.
+    pub fn with_layers(
+        environment: &str,
+        base_path: PathBuf,
+        overlay_path: Option<PathBuf>,
+    ) -> ApplicationResult<Self> {
+        let mut builder = router_env::Config::builder(environment)?
+            .add_source(File::from(base_path).required(true));
+        if let Some(overlay_path) = overlay_path {
+            builder = builder.add_source(File::from(overlay_path).required(false));
+        }
 
-        let config = router_env::Config::builder(&environment.to_string())?
-            .add_source(File::from(config_path).required(true))
+        let config = builder
             .add_source(
                 Environment::with_prefix("ROUTER")
                     .try_parsing(true)
@@ -245,6 +572,34 @@ impl Settings {
         })
     }
 
+    /// Replaces the secret references in the configuration with the secrets they refer to.
+    pub async fn resolve_secrets(&mut self) -> ApplicationResult<()> {
+        let resolver = SecretsResolver::from_settings(&self.secrets_provider);
+        self.resolve_secrets_with(&resolver).await
+    }
+
+    pub async fn resolve_secrets_with(
+        &mut self,
+        resolver: &SecretsResolver,
+    ) -> ApplicationResult<()> {
+        for secret in [
+            &mut self.secrets.jwt_secret,
+            &mut self.secrets.admin_api_key,
+            &mut self.secrets.vault_encryption_key,
+            &mut self.secrets.connector_credentials_encryption_key,
+            &mut self.network_tokenization.api_key,
+            &mut self.master_database.password,
+        ] {
+            resolver.resolve(secret).await?;
+        }
+        #[cfg(feature = "olap")]
+        resolver
+            .resolve(&mut self.replica_database.password)
+            .await?;
+
+        Ok(())
+    }
+
     pub fn validate(&self) -> ApplicationResult<()> {
         self.server.validate()?;
         self.master_database.validate()?;
@@ -269,7 +624,9 @@ impl Settings {
         }
         self.secrets.validate()?;
         self.locker.validate()?;
+        self.network_tokenization.validate()?;
         self.connectors.validate()?;
+        self.fx_quotes.validate()?;
 
         self.scheduler
             .as_ref()
@@ -282,3 +639,74 @@ impl Settings {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}_{name}.toml", uuid::Uuid::new_v4()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_overlay_overrides_base_config() {
+        let base_path = write_config(
+            "base",
+            r#"
+[server]
+port = 8080
+
+[connectors.stripe]
+base_url = "https://api.stripe.com/"
+"#,
+        );
+        let overlay_path = write_config(
+            "overlay",
+            r#"
+[connectors.stripe]
+base_url = "http://localhost:12111/"
+"#,
+        );
+
+        let settings =
+            Settings::with_layers("Sandbox", base_path.clone(), Some(overlay_path.clone()))
+                .unwrap();
+        fs::remove_file(base_path).unwrap();
+        fs::remove_file(overlay_path).unwrap();
+
+        assert_eq!(
+            settings.connectors.stripe.base_url,
+            "http://localhost:12111/"
+        );
+        assert_eq!(settings.server.port, 8080);
+    }
+
+    #[test]
+    fn test_missing_overlay_is_ignored() {
+        let base_path = write_config("base", "[server]\nport = 8080\n");
+        let overlay_path = std::env::temp_dir().join(format!("{}.toml", uuid::Uuid::new_v4()));
+
+        let settings = Settings::with_layers("Sandbox", base_path.clone(), Some(overlay_path));
+        fs::remove_file(base_path).unwrap();
+
+        assert_eq!(settings.unwrap().server.port, 8080);
+    }
+
+    #[test]
+    fn test_missing_base_config_is_a_configuration_error() {
+        let base_path = std::env::temp_dir().join(format!("{}.toml", uuid::Uuid::new_v4()));
+
+        let result = Settings::with_layers("Sandbox", base_path, None);
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::ConfigurationError(_))
+        ));
+    }
+}
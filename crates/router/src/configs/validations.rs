@@ -2,6 +2,43 @@ use common_utils::ext_traits::ConfigExt;
 
 use crate::core::errors::ApplicationError;
 
+/// Encryption keys set in the development configs of the repository. Anyone can decrypt data
+/// encrypted with them, so they are only accepted in development.
+const DEVELOPMENT_ENCRYPTION_KEYS: &[&str] = &[
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210",
+];
+
+fn validate_encryption_key(
+    name: &str,
+    key: &str,
+    environment: router_env::env::Env,
+) -> Result<(), ApplicationError> {
+    use common_utils::fp_utils::when;
+
+    when(key.is_default_or_empty(), || {
+        Err(ApplicationError::InvalidConfigurationValueError(format!(
+            "{name} must be set"
+        )))
+    })?;
+
+    when(hex::decode(key).map_or(true, |key| key.len() != 32), || {
+        Err(ApplicationError::InvalidConfigurationValueError(format!(
+            "{name} must be a hex encoded 32 byte key"
+        )))
+    })?;
+
+    when(
+        !matches!(environment, router_env::env::Env::Development)
+            && DEVELOPMENT_ENCRYPTION_KEYS.contains(&key),
+        || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "{name} must not be a development key outside development"
+            )))
+        },
+    )
+}
+
 impl super::settings::Secrets {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
@@ -16,7 +53,19 @@ impl super::settings::Secrets {
             Err(ApplicationError::InvalidConfigurationValueError(
                 "admin API key must not be empty".into(),
             ))
-        })
+        })?;
+
+        validate_encryption_key(
+            "vault encryption key",
+            &self.vault_encryption_key,
+            router_env::env::which(),
+        )?;
+
+        validate_encryption_key(
+            "connector credentials encryption key",
+            &self.connector_credentials_encryption_key,
+            router_env::env::which(),
+        )
     }
 }
 
@@ -41,6 +90,16 @@ impl super::settings::Locker {
     }
 }
 
+impl super::settings::NetworkTokenization {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        common_utils::fp_utils::when(self.enabled && self.url.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "token service url must not be empty when network tokenization is enabled".into(),
+            ))
+        })
+    }
+}
+
 impl super::settings::Jwekey {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         #[cfg(feature = "kms")]
@@ -184,3 +243,41 @@ impl super::settings::DrainerSettings {
         })
     }
 }
+
+impl super::settings::FxQuotes {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.max_quote_age <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "FX max quote age must be positive".into(),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use router_env::env::Env;
+
+    use super::*;
+
+    const KEY: &str = "5c1f9e0a7b3d8c2e6f4a1b9d0e7c3f5a8b2d6e1f9c4a7b0d3e8f2c6a1b5d9e0f";
+
+    #[test]
+    fn test_encryption_key_must_be_set() {
+        assert!(validate_encryption_key("key", "", Env::Development).is_err());
+        assert!(validate_encryption_key("key", "not a hex key", Env::Development).is_err());
+        assert!(validate_encryption_key("key", "0123abcd", Env::Development).is_err());
+        assert!(validate_encryption_key("key", KEY, Env::Production).is_ok());
+    }
+
+    #[test]
+    fn test_development_encryption_keys_are_rejected_outside_development() {
+        for key in DEVELOPMENT_ENCRYPTION_KEYS {
+            assert!(validate_encryption_key("key", key, Env::Development).is_ok());
+            assert!(validate_encryption_key("key", key, Env::Sandbox).is_err());
+            assert!(validate_encryption_key("key", key, Env::Production).is_err());
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use async_bb8_diesel::{AsyncConnection, ConnectionError};
 use bb8::{CustomizeConnection, PooledConnection};
 use diesel::PgConnection;
+use router_env::logger;
 
-use crate::configs::settings::Database;
+use crate::{configs::settings::Database, routes::metrics};
 
 pub type PgPool = bb8::Pool<async_bb8_diesel::ConnectionManager<PgConnection>>;
 
@@ -57,7 +58,70 @@ pub async fn diesel_make_pg_pool(database: &Database, test_transaction: bool) ->
 pub async fn pg_connection(
     pool: &PgPool,
 ) -> PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>> {
-    pool.get()
-        .await
-        .expect("Couldn't retrieve PostgreSQL connection")
+    let acquire_started = std::time::Instant::now();
+    let connection = pool.get().await;
+    metrics::DB_CONNECTION_WAIT_TIME.record(
+        &metrics::CONTEXT,
+        acquire_started.elapsed().as_secs_f64() * 1000f64,
+        &[],
+    );
+    if matches!(connection, Err(bb8::RunError::TimedOut)) {
+        metrics::DB_CONNECTION_ACQUIRE_TIMEOUT.add(&metrics::CONTEXT, 1, &[]);
+    }
+    connection.expect("Couldn't retrieve PostgreSQL connection")
+}
+
+/// Connections of a pool, split by whether they are checked out or idle.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PoolState {
+    pub in_use: u32,
+    pub idle: u32,
+}
+
+pub fn get_pool_state(pool: &PgPool) -> PoolState {
+    let state = pool.state();
+    PoolState {
+        in_use: state.connections.saturating_sub(state.idle_connections),
+        idle: state.idle_connections,
+    }
+}
+
+/// Reports the connections of the pool through the pool gauges, labelled with the pool name.
+pub fn register_pool_metrics(pool: &PgPool, pool_name: &'static str) {
+    let pool = pool.clone();
+    let attributes = [metrics::KeyValue::new("pool", pool_name)];
+    let registered = metrics::GLOBAL_METER.register_callback(move |cx| {
+        let state = get_pool_state(&pool);
+        metrics::DB_POOL_CONNECTIONS_IN_USE.observe(cx, state.in_use.into(), &attributes);
+        metrics::DB_POOL_CONNECTIONS_IDLE.observe(cx, state.idle.into(), &attributes);
+    });
+    if let Err(error) = registered {
+        logger::error!(
+            ?error,
+            pool_name,
+            "Failed to register connection pool metrics"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::settings::Settings;
+
+    #[allow(clippy::expect_used)]
+    #[actix_rt::test]
+    async fn test_pool_gauges_count_acquired_connection() {
+        let conf = Settings::new().expect("invalid settings");
+        let pool = diesel_make_pg_pool(&conf.master_database, true).await;
+
+        let connection = pg_connection(&pool).await;
+        let state = get_pool_state(&pool);
+        assert_eq!(state.in_use, 1);
+
+        drop(connection);
+        let state = get_pool_state(&pool);
+        assert_eq!(state.in_use, 0);
+        assert!(state.idle >= 1);
+    }
 }
@@ -5,9 +5,12 @@ pub mod authorizedotnet;
 pub mod braintree;
 pub mod checkout;
 pub mod cybersource;
+#[cfg(feature = "dummy_connector")]
+pub mod dummyconnector;
 pub mod fiserv;
 pub mod globalpay;
 pub mod klarna;
+pub mod opayo;
 pub mod payu;
 pub mod rapyd;
 pub mod shift4;
@@ -16,9 +19,11 @@ pub mod utils;
 pub mod worldline;
 pub mod worldpay;
 
+#[cfg(feature = "dummy_connector")]
+pub use self::dummyconnector::DummyConnector;
 pub use self::{
     aci::Aci, adyen::Adyen, applepay::Applepay, authorizedotnet::Authorizedotnet,
     braintree::Braintree, checkout::Checkout, cybersource::Cybersource, fiserv::Fiserv,
-    globalpay::Globalpay, klarna::Klarna, payu::Payu, rapyd::Rapyd, shift4::Shift4, stripe::Stripe,
-    worldline::Worldline, worldpay::Worldpay,
+    globalpay::Globalpay, klarna::Klarna, opayo::Opayo, payu::Payu, rapyd::Rapyd, shift4::Shift4,
+    stripe::Stripe, worldline::Worldline, worldpay::Worldpay,
 };
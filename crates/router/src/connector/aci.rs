@@ -46,6 +46,17 @@ impl ConnectorCommon for Aci {
 
 impl api::Payment for Aci {}
 
+impl api::PaymentsPreProcessing for Aci {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Aci
+{
+}
+
 impl api::PaymentAuthorize for Aci {}
 impl api::PaymentSync for Aci {}
 impl api::PaymentVoid for Aci {}
@@ -195,6 +206,7 @@ impl
                 })
             }),
             status_code: res.status_code,
+            decline_type: None,
         })
     }
 }
@@ -307,6 +319,7 @@ impl
                     )
                 })
             }),
+            decline_type: None,
         })
     }
 }
@@ -408,6 +421,7 @@ impl
                     )
                 })
             }),
+            decline_type: None,
         })
     }
 }
@@ -517,6 +531,7 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
                     )
                 })
             }),
+            decline_type: None,
         })
     }
 }
@@ -526,6 +541,18 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
 {
 }
 
+impl api::SubmitEvidence for Aci {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Aci
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Aci {
     fn get_webhook_object_reference_id(
@@ -114,6 +114,9 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for AciPaymentsRequest {
             api::PaymentMethod::PayLater(_) => PaymentDetails::Klarna,
             api::PaymentMethod::Wallet(_) => PaymentDetails::Wallet,
             api::PaymentMethod::Paypal => PaymentDetails::Paypal,
+            api::PaymentMethod::BankRedirect(_) => Err(errors::ConnectorError::NotImplemented(
+                "Payment methods".to_string(),
+            ))?,
         };
 
         let auth = AciAuthType::try_from(&item.connector_auth_type)?;
@@ -219,6 +222,9 @@ impl<F, T>
                 redirect: false,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
@@ -12,11 +12,13 @@ use crate::{
     configs::settings,
     consts,
     core::{
+        connector_credentials,
         errors::{self, CustomResult},
         payments,
     },
-    db::StorageInterface,
-    headers, logger, services,
+    headers, logger,
+    routes::AppState,
+    services,
     types::{
         self,
         api::{self, ConnectorCommon},
@@ -45,9 +47,27 @@ impl ConnectorCommon for Adyen {
     fn base_url<'a>(&self, connectors: &'a settings::Connectors) -> &'a str {
         connectors.adyen.base_url.as_ref()
     }
+
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            multiple_captures: true,
+            ..Default::default()
+        }
+    }
 }
 
 impl api::Payment for Adyen {}
+impl api::PaymentsPreProcessing for Adyen {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Adyen
+{
+}
+
 impl api::PaymentAuthorize for Adyen {}
 impl api::PaymentSync for Adyen {}
 impl api::PaymentVoid for Adyen {}
@@ -180,6 +200,7 @@ impl
             code: response.error_code,
             message: response.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -315,6 +336,7 @@ impl
             code: response.error_code,
             message: response.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -428,6 +450,7 @@ impl
             code: response.error_code,
             message: response.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -526,6 +549,7 @@ impl
             code: response.error_code,
             message: response.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -628,6 +652,7 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
             code: response.error_code,
             message: response.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -652,14 +677,22 @@ fn get_webhook_object_from_body(
     Ok(item_object.notification_request_item)
 }
 
+impl api::SubmitEvidence for Adyen {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Adyen
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Adyen {
-    fn get_webhook_source_verification_algorithm(
-        &self,
-        _headers: &actix_web::http::header::HeaderMap,
-        _body: &[u8],
-    ) -> CustomResult<Box<dyn crypto::VerifySignature + Send>, errors::ConnectorError> {
-        Ok(Box::new(crypto::HmacSha256))
+    fn get_webhook_signature_scheme(&self) -> crypto::WebhookSignatureScheme {
+        crypto::WebhookSignatureScheme::HmacSha256
     }
 
     fn get_webhook_source_verification_signature(
@@ -707,16 +740,16 @@ impl api::IncomingWebhook for Adyen {
 
     async fn get_webhook_source_verification_merchant_secret(
         &self,
-        db: &dyn StorageInterface,
+        state: &AppState,
         merchant_id: &str,
     ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
-        let key = format!("whsec_verification_{}_{}", self.id(), merchant_id);
-        let secret = db
-            .get_key(&key)
-            .await
-            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)?;
-
-        Ok(secret)
+        connector_credentials::find_webhook_source_verification_secret(
+            state,
+            merchant_id,
+            self.id(),
+            &format!("whsec_verification_{}_{}", self.id(), merchant_id),
+        )
+        .await
     }
 
     fn get_webhook_object_reference_id(
@@ -729,6 +762,19 @@ impl api::IncomingWebhook for Adyen {
         Ok(notif.psp_reference)
     }
 
+    fn get_webhook_event_id(
+        &self,
+        body: &[u8],
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        let notif = get_webhook_object_from_body(body)
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+
+        Ok(Some(format!(
+            "{}_{}",
+            notif.psp_reference, notif.event_code
+        )))
+    }
+
     fn get_webhook_event_type(
         &self,
         body: &[u8],
@@ -736,8 +782,13 @@ impl api::IncomingWebhook for Adyen {
         let notif = get_webhook_object_from_body(body)
             .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
 
+        let is_success = notif.success == "true";
         Ok(match notif.event_code.as_str() {
-            "AUTHORISATION" => api::IncomingWebhookEvent::PaymentIntentSuccess,
+            "AUTHORISATION" if is_success => api::IncomingWebhookEvent::PaymentIntentSuccess,
+            "AUTHORISATION" => api::IncomingWebhookEvent::PaymentIntentFailure,
+            "REFUND" if is_success => api::IncomingWebhookEvent::RefundSuccess,
+            "REFUND" => api::IncomingWebhookEvent::RefundFailure,
+            "CHARGEBACK" | "NOTIFICATION_OF_CHARGEBACK" => api::IncomingWebhookEvent::DisputeOpened,
             _ => Err(errors::ConnectorError::WebhookEventTypeNotFound).into_report()?,
         })
     }
@@ -776,3 +827,57 @@ impl services::ConnectorRedirectResponse for Adyen {
         Ok(payments::CallConnectorAction::Trigger)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::Adyen;
+    use crate::types::api::{IncomingWebhook, IncomingWebhookEvent};
+
+    fn get_webhook_body(event_code: &str, success: &str) -> String {
+        format!(
+            r#"{{
+                "notificationItems": [{{
+                    "NotificationRequestItem": {{
+                        "additionalData": {{ "hmacSignature": "signature" }},
+                        "amount": {{ "value": 1000, "currency": "EUR" }},
+                        "pspReference": "psp_123",
+                        "eventCode": "{event_code}",
+                        "merchantAccountCode": "merchant_123",
+                        "merchantReference": "pay_123",
+                        "success": "{success}"
+                    }}
+                }}]
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_webhook_event_type_mapping() {
+        let events = [
+            (
+                "AUTHORISATION",
+                "true",
+                IncomingWebhookEvent::PaymentIntentSuccess,
+            ),
+            (
+                "AUTHORISATION",
+                "false",
+                IncomingWebhookEvent::PaymentIntentFailure,
+            ),
+            ("REFUND", "true", IncomingWebhookEvent::RefundSuccess),
+            ("REFUND", "false", IncomingWebhookEvent::RefundFailure),
+            ("CHARGEBACK", "true", IncomingWebhookEvent::DisputeOpened),
+        ];
+        for (event_code, success, expected_event) in events {
+            let body = get_webhook_body(event_code, success);
+            assert_eq!(
+                Adyen.get_webhook_event_type(body.as_bytes()).unwrap(),
+                expected_event
+            );
+        }
+        assert!(Adyen
+            .get_webhook_event_type(get_webhook_body("REPORT_AVAILABLE", "true").as_bytes())
+            .is_err());
+    }
+}
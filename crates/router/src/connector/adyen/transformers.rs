@@ -6,7 +6,7 @@ use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils::{self, PaymentsRequestData},
+    connector::utils::{self, RouterData},
     consts,
     core::errors,
     pii::{self, Email, Secret},
@@ -98,6 +98,7 @@ pub struct AdyenPaymentRequest {
     delivery_address: Option<Address>,
     country_code: Option<String>,
     line_items: Option<Vec<LineItem>>,
+    shopper_statement: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -421,19 +422,31 @@ fn get_address_info(address: Option<&api_models::payments::Address>) -> Option<A
 
 fn get_line_items(item: &types::PaymentsAuthorizeRouterData) -> Vec<LineItem> {
     let order_details = item.request.order_details.as_ref();
+    let tax_amount = item.request.tax_amount;
     let line_item = LineItem {
         amount_including_tax: Some(item.request.amount),
-        amount_excluding_tax: None,
+        amount_excluding_tax: tax_amount.map(|tax_amount| item.request.amount - tax_amount),
         description: order_details.map(|details| details.product_name.clone()),
         // We support only one product details in payment request as of now, therefore hard coded the id.
         // If we begin to support multiple product details in future then this logic should be made to create ID dynamically
         id: Some(String::from("Items #1")),
-        tax_amount: None,
+        tax_amount,
         quantity: order_details.map(|details| details.quantity),
     };
     vec![line_item]
 }
 
+/// The card schemes show at most 22 characters of the shopper statement.
+const SHOPPER_STATEMENT_MAX_LENGTH: usize = 22;
+
+fn get_shopper_statement(item: &types::PaymentsAuthorizeRouterData) -> Option<String> {
+    utils::get_statement_descriptor(
+        item.request.statement_descriptor_prefix.as_deref(),
+        item.request.statement_descriptor_suffix.as_deref(),
+        SHOPPER_STATEMENT_MAX_LENGTH,
+    )
+}
+
 fn get_telephone_number(item: &types::PaymentsAuthorizeRouterData) -> Option<Secret<String>> {
     let phone = item
         .address
@@ -534,7 +547,8 @@ fn get_payment_method_data(
             _ => Err(errors::ConnectorError::NotImplemented("Payment methods".to_string()).into()),
         },
         api_models::payments::PaymentMethod::BankTransfer
-        | api_models::payments::PaymentMethod::Paypal => {
+        | api_models::payments::PaymentMethod::Paypal
+        | api_models::payments::PaymentMethod::BankRedirect(_) => {
             Err(errors::ConnectorError::NotImplemented("Payment methods".to_string()).into())
         }
     }
@@ -568,6 +582,7 @@ fn get_card_specific_payment_data(
         delivery_address: None,
         country_code: None,
         line_items: None,
+        shopper_statement: get_shopper_statement(item),
     })
 }
 
@@ -599,6 +614,7 @@ fn get_wallet_specific_payment_data(
         delivery_address: None,
         country_code: None,
         line_items: None,
+        shopper_statement: get_shopper_statement(item),
     })
 }
 
@@ -637,6 +653,7 @@ fn get_paylater_specific_payment_data(
         delivery_address,
         country_code,
         line_items,
+        shopper_statement: get_shopper_statement(item),
     })
 }
 
@@ -675,6 +692,9 @@ impl TryFrom<types::PaymentsCancelResponseRouterData<AdyenCancelResponse>>
                 redirect: false,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
@@ -714,6 +734,7 @@ pub fn get_adyen_response(
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
             status_code,
+            decline_type: None,
         })
     } else {
         None
@@ -725,6 +746,9 @@ pub fn get_adyen_response(
         redirect: false,
         mandate_reference: None,
         connector_metadata: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_reference_id: None,
     };
     Ok((status, error, payments_response_data))
 }
@@ -752,6 +776,7 @@ pub fn get_redirection_response(
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
             status_code,
+            decline_type: None,
         })
     } else {
         None
@@ -786,6 +811,9 @@ pub fn get_redirection_response(
         redirect: true,
         mandate_reference: None,
         connector_metadata: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_reference_id: None,
     };
     Ok((status, error, payments_response_data))
 }
@@ -880,6 +908,9 @@ impl TryFrom<types::PaymentsCaptureResponseRouterData<AdyenCaptureResponse>>
                 redirection_data: None,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             amount_captured,
             ..item.data
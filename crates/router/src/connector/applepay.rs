@@ -31,6 +31,17 @@ impl ConnectorCommon for Applepay {
 }
 
 impl api::Payment for Applepay {}
+impl api::PaymentsPreProcessing for Applepay {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Applepay
+{
+}
+
 impl api::PaymentAuthorize for Applepay {}
 impl api::PaymentSync for Applepay {}
 impl api::PaymentVoid for Applepay {}
@@ -184,6 +195,7 @@ impl
             code: response.status_code,
             message: response.status_message,
             reason: None,
+            decline_type: None,
         })
     }
 
@@ -238,6 +250,18 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
 
 impl services::ConnectorRedirectResponse for Applepay {}
 
+impl api::SubmitEvidence for Applepay {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Applepay
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Applepay {
     fn get_webhook_object_reference_id(
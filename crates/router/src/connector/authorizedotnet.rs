@@ -37,6 +37,17 @@ impl ConnectorCommon for Authorizedotnet {
 }
 
 impl api::Payment for Authorizedotnet {}
+impl api::PaymentsPreProcessing for Authorizedotnet {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Authorizedotnet
+{
+}
+
 impl api::PaymentAuthorize for Authorizedotnet {}
 impl api::PaymentSync for Authorizedotnet {}
 impl api::PaymentVoid for Authorizedotnet {}
@@ -144,6 +155,11 @@ impl
         Ok(Some(request))
     }
 
+    // The transaction details are requested with a POST, which only reads the transaction
+    fn is_read_only_request(&self, _req: &types::PaymentsSyncRouterData) -> bool {
+        true
+    }
+
     fn handle_response(
         &self,
         data: &types::PaymentsSyncRouterData,
@@ -535,6 +551,11 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
         Ok(Some(request))
     }
 
+    // The transaction details are requested with a POST, which only reads the transaction
+    fn is_read_only_request(&self, _req: &types::RefundsRouterData<api::RSync>) -> bool {
+        true
+    }
+
     fn handle_response(
         &self,
         data: &types::RefundsRouterData<api::RSync>,
@@ -568,6 +589,18 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
     }
 }
 
+impl api::SubmitEvidence for Authorizedotnet {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Authorizedotnet
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Authorizedotnet {
     fn get_webhook_object_reference_id(
@@ -616,6 +649,7 @@ fn get_error_response(
                 message: error.error_text,
                 reason: None,
                 status_code,
+                decline_type: None,
             })
         })
         .unwrap_or_else(|| types::ErrorResponse {
@@ -623,5 +657,6 @@ fn get_error_response(
             message: consts::NO_ERROR_MESSAGE.to_string(),
             reason: None,
             status_code,
+            decline_type: None,
         }))
 }
@@ -14,6 +14,8 @@ use crate::{
 pub enum TransactionType {
     #[serde(rename = "authCaptureTransaction")]
     Payment,
+    #[serde(rename = "authOnlyTransaction")]
+    Authorization,
     #[serde(rename = "refundTransaction")]
     Refund,
     #[serde(rename = "voidTransaction")]
@@ -65,6 +67,7 @@ enum PaymentDetails {
     Wallet,
     Klarna,
     Paypal,
+    BankRedirect,
 }
 
 impl From<api_models::payments::PaymentMethod> for PaymentDetails {
@@ -86,6 +89,7 @@ impl From<api_models::payments::PaymentMethod> for PaymentDetails {
             api::PaymentMethod::PayLater(_) => Self::Klarna,
             api::PaymentMethod::Wallet(_) => Self::Wallet,
             api::PaymentMethod::Paypal => Self::Paypal,
+            api::PaymentMethod::BankRedirect(_) => Self::BankRedirect,
         }
     }
 }
@@ -164,8 +168,12 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for CreateTransactionRequest {
             item.request.capture_method.map(|c| AuthorizationIndicator {
                 authorization_indicator: c.into(),
             });
+        let transaction_type = match item.request.capture_method {
+            Some(enums::CaptureMethod::Manual) => TransactionType::Authorization,
+            _ => TransactionType::Payment,
+        };
         let transaction_request = TransactionRequest {
-            transaction_type: TransactionType::Payment,
+            transaction_type,
             amount: item.request.amount,
             payment: payment_details,
             currency_code: item.request.currency.to_string(),
@@ -264,6 +272,64 @@ pub struct TransactionResponse {
     transaction_id: String,
     pub(super) account_number: Option<String>,
     pub(super) errors: Option<Vec<ErrorMessage>>,
+    avs_result_code: Option<String>,
+    cvv_result_code: Option<String>,
+    pre_paid_card: Option<PrePaidCard>,
+}
+
+/// Amounts of a prepaid card payment, which the connector can approve for less than requested
+/// when the balance on the card does not cover the whole amount.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrePaidCard {
+    requested_amount: Option<String>,
+    approved_amount: Option<String>,
+}
+
+/// Amount the connector approved, in the minor unit of `currency`, when it approved less than the
+/// requested amount. The connector gives both amounts in the major unit, e.g. `10.00`.
+fn get_partially_authorized_amount(
+    pre_paid_card: &PrePaidCard,
+    currency: enums::Currency,
+) -> Result<Option<i64>, error_stack::Report<errors::ConnectorError>> {
+    let parse_amount = |amount: &Option<String>| {
+        amount
+            .as_deref()
+            .map(|amount| types::Money::from_major_unit_str(amount, currency))
+            .transpose()
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)
+    };
+    let requested_amount = parse_amount(&pre_paid_card.requested_amount)?;
+    let approved_amount = parse_amount(&pre_paid_card.approved_amount)?;
+    Ok(match (requested_amount, approved_amount) {
+        (Some(requested_amount), Some(approved_amount))
+            if approved_amount.minor_unit() < requested_amount.minor_unit() =>
+        {
+            Some(approved_amount.minor_unit())
+        }
+        _ => None,
+    })
+}
+
+/// Maps the address verification code of Authorize.Net to the normalized result.
+fn get_avs_result(avs_result_code: &str) -> enums::AvsResult {
+    match avs_result_code {
+        // Street address and five or nine digit postal code match
+        "X" | "Y" => enums::AvsResult::Match,
+        // Street address only, or five or nine digit postal code only match
+        "A" | "W" | "Z" => enums::AvsResult::PartialMatch,
+        "N" => enums::AvsResult::NoMatch,
+        _ => enums::AvsResult::Unavailable,
+    }
+}
+
+/// Maps the card code verification code of Authorize.Net to the normalized result.
+fn get_cvv_result(cvv_result_code: &str) -> enums::CvvResult {
+    match cvv_result_code {
+        "M" => enums::CvvResult::Match,
+        "N" => enums::CvvResult::NoMatch,
+        _ => enums::CvvResult::Unavailable,
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -273,70 +339,162 @@ pub struct AuthorizedotnetPaymentsResponse {
     pub messages: ResponseMessages,
 }
 
-impl<F, T>
+impl<F>
     TryFrom<
         types::ResponseRouterData<
             F,
             AuthorizedotnetPaymentsResponse,
-            T,
+            types::PaymentsAuthorizeData,
             types::PaymentsResponseData,
         >,
-    > for types::RouterData<F, T, types::PaymentsResponseData>
+    > for types::RouterData<F, types::PaymentsAuthorizeData, types::PaymentsResponseData>
 {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(
         item: types::ResponseRouterData<
             F,
             AuthorizedotnetPaymentsResponse,
-            T,
+            types::PaymentsAuthorizeData,
             types::PaymentsResponseData,
         >,
     ) -> Result<Self, Self::Error> {
-        let status = enums::AttemptStatus::from(item.response.transaction_response.response_code);
-        let error = item
-            .response
-            .transaction_response
-            .errors
-            .and_then(|errors| {
-                errors.into_iter().next().map(|error| types::ErrorResponse {
-                    code: error.error_code,
-                    message: error.error_text,
-                    reason: None,
-                    status_code: item.http_code,
-                })
-            });
+        let is_auth_only = item.data.request.capture_method == Some(enums::CaptureMethod::Manual);
+        let currency = item.data.request.currency;
+        get_payments_router_data(item, is_auth_only, Some(currency))
+    }
+}
 
-        let metadata = item
-            .response
-            .transaction_response
-            .account_number
-            .map(|acc_no| {
-                Encode::<'_, PaymentDetails>::encode_to_value(&construct_refund_payment_details(
-                    acc_no,
-                ))
+impl<F>
+    TryFrom<
+        types::ResponseRouterData<
+            F,
+            AuthorizedotnetPaymentsResponse,
+            types::PaymentsCancelData,
+            types::PaymentsResponseData,
+        >,
+    > for types::RouterData<F, types::PaymentsCancelData, types::PaymentsResponseData>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            F,
+            AuthorizedotnetPaymentsResponse,
+            types::PaymentsCancelData,
+            types::PaymentsResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        get_payments_router_data(item, false, None)
+    }
+}
+
+/// Builds the router data of a transaction response. A partial approval of an
+/// `authOnlyTransaction` leaves the approved amount to be captured, while a partial approval of
+/// an `authCaptureTransaction` has already captured it. Partial approvals are only read when the
+/// `currency` of the amounts is known, which it is for authorizations but not for voids.
+fn get_payments_router_data<F, T>(
+    item: types::ResponseRouterData<
+        F,
+        AuthorizedotnetPaymentsResponse,
+        T,
+        types::PaymentsResponseData,
+    >,
+    is_auth_only: bool,
+    currency: Option<enums::Currency>,
+) -> Result<
+    types::RouterData<F, T, types::PaymentsResponseData>,
+    error_stack::Report<errors::ConnectorError>,
+> {
+    let partially_approved_amount = item
+        .response
+        .transaction_response
+        .pre_paid_card
+        .as_ref()
+        .zip(currency)
+        .map(|(pre_paid_card, currency)| get_partially_authorized_amount(pre_paid_card, currency))
+        .transpose()?
+        .flatten();
+    let (status, authorized_amount, amount_captured) = match (
+        item.response.transaction_response.response_code,
+        partially_approved_amount,
+    ) {
+        (AuthorizedotnetPaymentStatus::Approved, Some(approved_amount)) if is_auth_only => (
+            enums::AttemptStatus::PartiallyAuthorized,
+            Some(approved_amount),
+            item.data.amount_captured,
+        ),
+        (AuthorizedotnetPaymentStatus::Approved, Some(approved_amount)) => (
+            enums::AttemptStatus::Charged,
+            item.data.authorized_amount,
+            Some(approved_amount),
+        ),
+        (AuthorizedotnetPaymentStatus::Approved, None) if is_auth_only => (
+            enums::AttemptStatus::Authorized,
+            item.data.authorized_amount,
+            item.data.amount_captured,
+        ),
+        (response_code, _) => (
+            enums::AttemptStatus::from(response_code),
+            item.data.authorized_amount,
+            item.data.amount_captured,
+        ),
+    };
+    let error = item
+        .response
+        .transaction_response
+        .errors
+        .and_then(|errors| {
+            errors.into_iter().next().map(|error| types::ErrorResponse {
+                code: error.error_code,
+                message: error.error_text,
+                reason: None,
+                status_code: item.http_code,
+                decline_type: None,
             })
-            .transpose()
-            .change_context(errors::ConnectorError::MissingRequiredField {
-                field_name: "connector_metadata",
-            })?;
+        });
 
-        Ok(Self {
-            status,
-            response: match error {
-                Some(err) => Err(err),
-                None => Ok(types::PaymentsResponseData::TransactionResponse {
-                    resource_id: types::ResponseId::ConnectorTransactionId(
-                        item.response.transaction_response.transaction_id,
-                    ),
-                    redirection_data: None,
-                    redirect: false,
-                    mandate_reference: None,
-                    connector_metadata: metadata,
-                }),
-            },
-            ..item.data
+    let metadata = item
+        .response
+        .transaction_response
+        .account_number
+        .map(|acc_no| {
+            Encode::<'_, PaymentDetails>::encode_to_value(&construct_refund_payment_details(acc_no))
         })
-    }
+        .transpose()
+        .change_context(errors::ConnectorError::MissingRequiredField {
+            field_name: "connector_metadata",
+        })?;
+
+    Ok(types::RouterData {
+        status,
+        response: match error {
+            Some(err) => Err(err),
+            None => Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.response.transaction_response.transaction_id,
+                ),
+                redirection_data: None,
+                redirect: false,
+                mandate_reference: None,
+                connector_metadata: metadata,
+                avs_result: item
+                    .response
+                    .transaction_response
+                    .avs_result_code
+                    .as_deref()
+                    .map(get_avs_result),
+                cvv_result: item
+                    .response
+                    .transaction_response
+                    .cvv_result_code
+                    .as_deref()
+                    .map(get_cvv_result),
+                connector_response_reference_id: None,
+            }),
+        },
+        authorized_amount,
+        amount_captured,
+        ..item.data
+    })
 }
 
 #[derive(Debug, Serialize)]
@@ -434,6 +592,7 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AuthorizedotnetRefundRespons
                 message: error.error_text.clone(),
                 reason: None,
                 status_code: item.http_code,
+                decline_type: None,
             })
         });
 
@@ -609,6 +768,9 @@ impl<F, Req>
                 redirect: false,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             status: payment_status,
             ..item.data
@@ -637,3 +799,196 @@ fn construct_refund_payment_details(masked_number: String) -> PaymentDetails {
         card_code: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use masking::Secret;
+
+    use super::*;
+    use crate::connector::utils::get_test_router_data;
+
+    fn get_authorize_router_data(
+        capture_method: Option<enums::CaptureMethod>,
+    ) -> types::PaymentsAuthorizeRouterData {
+        get_test_router_data(
+            "authorizedotnet",
+            types::PaymentsAuthorizeData {
+                payment_method_data: api::PaymentMethod::Card(api::Card {
+                    card_number: Secret::new("4111111111111111".to_string()),
+                    card_exp_month: Secret::new("10".to_string()),
+                    card_exp_year: Secret::new("2035".to_string()),
+                    card_holder_name: Secret::new("John Doe".to_string()),
+                    card_cvc: Secret::new("123".to_string()),
+                }),
+                amount: 1000,
+                email: None,
+                currency: enums::Currency::USD,
+                confirm: true,
+                statement_descriptor_suffix: None,
+                statement_descriptor_prefix: None,
+                capture_method,
+                setup_future_usage: None,
+                mandate_id: None,
+                off_session: None,
+                setup_mandate_details: None,
+                browser_info: None,
+                order_details: None,
+                surcharge_details: None,
+                tax_amount: None,
+                sca_exemption: None,
+                connector_meta: None,
+                network_token: None,
+            },
+            Err(types::ErrorResponse::default()),
+        )
+    }
+
+    fn get_prepaid_card_payment_router_data(
+        capture_method: Option<enums::CaptureMethod>,
+        requested_amount: &str,
+        approved_amount: &str,
+    ) -> Result<types::PaymentsAuthorizeRouterData, error_stack::Report<errors::ConnectorError>>
+    {
+        let response = serde_json::json!({
+            "transactionResponse": {
+                "responseCode": "1",
+                "authCode": "HH5414",
+                "transId": "2149186848",
+                "accountNumber": "XXXX0015",
+                "prePaidCard": {
+                    "requestedAmount": requested_amount,
+                    "approvedAmount": approved_amount,
+                    "balanceOnCard": "0.00"
+                }
+            },
+            "messages": {
+                "resultCode": "Ok",
+                "message": [{ "code": "I00001", "text": "Successful." }]
+            }
+        });
+        let item = types::ResponseRouterData {
+            response: serde_json::from_value::<AuthorizedotnetPaymentsResponse>(response).unwrap(),
+            data: get_authorize_router_data(capture_method),
+            http_code: 200,
+        };
+        types::RouterData::try_from(item)
+    }
+
+    #[test]
+    fn test_avs_and_cvv_results_are_normalized() {
+        let transaction_response: TransactionResponse = serde_json::from_str(
+            r#"{
+                "responseCode": "1",
+                "authCode": "HH5414",
+                "avsResultCode": "Y",
+                "cvvResultCode": "M",
+                "transId": "2149186848",
+                "accountNumber": "XXXX0015"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            transaction_response
+                .avs_result_code
+                .as_deref()
+                .map(get_avs_result),
+            Some(enums::AvsResult::Match)
+        );
+        assert_eq!(
+            transaction_response
+                .cvv_result_code
+                .as_deref()
+                .map(get_cvv_result),
+            Some(enums::CvvResult::Match)
+        );
+    }
+
+    #[test]
+    fn test_unknown_avs_and_cvv_codes_are_unavailable() {
+        assert_eq!(get_avs_result("P"), enums::AvsResult::Unavailable);
+        assert_eq!(get_avs_result("Z"), enums::AvsResult::PartialMatch);
+        assert_eq!(get_cvv_result("U"), enums::CvvResult::Unavailable);
+    }
+
+    #[test]
+    fn test_manual_capture_payment_is_an_auth_only_transaction() {
+        let request = CreateTransactionRequest::try_from(&get_authorize_router_data(Some(
+            enums::CaptureMethod::Manual,
+        )))
+        .unwrap();
+        assert_eq!(
+            request
+                .create_transaction_request
+                .transaction_request
+                .transaction_type,
+            TransactionType::Authorization
+        );
+
+        let request = CreateTransactionRequest::try_from(&get_authorize_router_data(Some(
+            enums::CaptureMethod::Automatic,
+        )))
+        .unwrap();
+        assert_eq!(
+            request
+                .create_transaction_request
+                .transaction_request
+                .transaction_type,
+            TransactionType::Payment
+        );
+    }
+
+    #[test]
+    fn test_partially_approved_auth_only_payment_is_partially_authorized() {
+        let router_data = get_prepaid_card_payment_router_data(
+            Some(enums::CaptureMethod::Manual),
+            "10.00",
+            "6.00",
+        )
+        .unwrap();
+
+        assert_eq!(
+            router_data.status,
+            enums::AttemptStatus::PartiallyAuthorized
+        );
+        assert_eq!(router_data.authorized_amount, Some(600));
+        assert_eq!(router_data.amount_captured, None);
+        assert!(router_data.response.is_ok());
+    }
+
+    #[test]
+    fn test_partially_approved_auth_capture_payment_is_charged_for_the_approved_amount() {
+        let router_data = get_prepaid_card_payment_router_data(None, "10.00", "6.00").unwrap();
+
+        assert_eq!(router_data.status, enums::AttemptStatus::Charged);
+        assert_eq!(router_data.amount_captured, Some(600));
+        assert_eq!(router_data.authorized_amount, None);
+        assert!(router_data.response.is_ok());
+    }
+
+    #[test]
+    fn test_fully_approved_prepaid_card_payment_is_not_partially_authorized() {
+        let router_data = get_prepaid_card_payment_router_data(None, "10.00", "10.00").unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Charged);
+        assert_eq!(router_data.authorized_amount, None);
+
+        let router_data = get_prepaid_card_payment_router_data(
+            Some(enums::CaptureMethod::Manual),
+            "10.00",
+            "10.00",
+        )
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Authorized);
+        assert_eq!(router_data.authorized_amount, None);
+    }
+
+    #[test]
+    fn test_invalid_prepaid_card_amount_fails_deserialization() {
+        let result = get_prepaid_card_payment_router_data(None, "10.00", "six dollars");
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ConnectorError::ResponseDeserializationFailed)
+        ));
+    }
+}
@@ -45,6 +45,17 @@ impl ConnectorCommon for Braintree {
 
 impl api::Payment for Braintree {}
 
+impl api::PaymentsPreProcessing for Braintree {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Braintree
+{
+}
+
 impl api::PaymentAuthorize for Braintree {}
 impl api::PaymentSync for Braintree {}
 impl api::PaymentVoid for Braintree {}
@@ -141,6 +152,7 @@ impl
             code: consts::NO_ERROR_CODE.to_string(),
             message: response.api_error_response.message,
             reason: None,
+            decline_type: None,
         })
     }
 
@@ -275,6 +287,7 @@ impl
             code: consts::NO_ERROR_CODE.to_string(),
             message: response.api_error_response.message,
             reason: None,
+            decline_type: None,
         })
     }
 
@@ -408,6 +421,7 @@ impl
             code: consts::NO_ERROR_CODE.to_string(),
             message: response.api_error_response.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -486,6 +500,7 @@ impl
             code: consts::NO_ERROR_CODE.to_string(),
             message: response.api_error_response.message,
             reason: None,
+            decline_type: None,
         })
     }
 
@@ -683,6 +698,18 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
     }
 }
 
+impl api::SubmitEvidence for Braintree {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Braintree
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Braintree {
     fn get_webhook_object_reference_id(
@@ -213,6 +213,9 @@ impl<F, T>
                 redirect: false,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
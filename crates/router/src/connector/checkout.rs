@@ -51,10 +51,28 @@ impl ConnectorCommon for Checkout {
     fn base_url<'a>(&self, connectors: &'a settings::Connectors) -> &'a str {
         connectors.checkout.base_url.as_ref()
     }
+
+    fn get_capabilities(&self) -> api::ConnectorCapabilities {
+        api::ConnectorCapabilities {
+            network_tokens: true,
+            ..Default::default()
+        }
+    }
 }
 
 impl api::Payment for Checkout {}
 
+impl api::PaymentsPreProcessing for Checkout {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Checkout
+{
+}
+
 impl api::PaymentAuthorize for Checkout {}
 impl api::PaymentSync for Checkout {}
 impl api::PaymentVoid for Checkout {}
@@ -91,7 +109,98 @@ impl
         types::PaymentsResponseData,
     > for Checkout
 {
-    // Issue: #173
+    fn get_headers(
+        &self,
+        req: &types::VerifyRouterData,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, String)>, errors::ConnectorError> {
+        let mut header = vec![
+            (
+                headers::CONTENT_TYPE.to_string(),
+                types::VerifyType::get_content_type(self).to_string(),
+            ),
+            (headers::X_ROUTER.to_string(), "test".to_string()),
+        ];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        Ok(header)
+    }
+
+    fn get_url(
+        &self,
+        _req: &types::VerifyRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        Ok(format!("{}{}", self.base_url(connectors), "payments"))
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::VerifyRouterData,
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        let connector_req = checkout::PaymentsRequest::try_from(req)?;
+        let checkout_req =
+            utils::Encode::<checkout::PaymentsRequest>::encode_to_string_of_json(&connector_req)
+                .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(checkout_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::VerifyRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Post)
+                .url(&types::VerifyType::get_url(self, req, connectors)?)
+                .headers(types::VerifyType::get_headers(self, req, connectors)?)
+                .header(headers::X_ROUTER, "test")
+                .body(types::VerifyType::get_request_body(self, req)?)
+                .build(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        data: &types::VerifyRouterData,
+        res: types::Response,
+    ) -> CustomResult<types::VerifyRouterData, errors::ConnectorError> {
+        logger::debug!(payments_verify_response=?res);
+        let response: checkout::PaymentsResponse = res
+            .response
+            .parse_struct("PaymentsResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        logger::debug!(checkout_error_response=?res);
+        let response: checkout::ErrorResponse = res
+            .response
+            .parse_struct("ErrorResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        Ok(types::ErrorResponse {
+            status_code: res.status_code,
+            code: response
+                .error_codes
+                .unwrap_or_else(|| vec![consts::NO_ERROR_CODE.to_string()])
+                .join(" & "),
+            message: response
+                .error_type
+                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+            reason: None,
+            decline_type: None,
+        })
+    }
 }
 
 impl
@@ -195,6 +304,7 @@ impl
                 .error_type
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -295,6 +405,7 @@ impl
                 .error_type
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -402,6 +513,7 @@ impl
                 .error_type
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -505,6 +617,7 @@ impl
                 .error_type
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -618,6 +731,7 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
                 .error_type
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -713,10 +827,23 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
                 .error_type
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type: None,
         })
     }
 }
 
+impl api::SubmitEvidence for Checkout {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Checkout
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Checkout {
     fn get_webhook_object_reference_id(
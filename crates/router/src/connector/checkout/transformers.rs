@@ -1,3 +1,4 @@
+use common_utils::ext_traits::{StringExt, ValueExt};
 use error_stack::{IntoReport, ResultExt};
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -10,6 +11,7 @@ use crate::{
         storage::enums,
         transformers::{self, ForeignFrom},
     },
+    utils::{apple_pay, OptionExt},
 };
 
 #[derive(Debug, Serialize)]
@@ -21,10 +23,101 @@ pub struct CardSource {
     pub expiry_year: Option<pii::Secret<String>>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct NetworkTokenSource {
+    #[serde(rename = "type")]
+    pub source_type: String,
+    pub token: pii::Secret<String, pii::CardNumber>,
+    pub expiry_month: pii::Secret<String>,
+    pub expiry_year: pii::Secret<String>,
+    pub token_type: String,
+    pub cryptogram: pii::Secret<String>,
+    pub eci: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum Source {
     Card(CardSource),
+    NetworkToken(NetworkTokenSource),
+}
+
+/// Connector metadata holding the keys used to decrypt Apple Pay tokens.
+#[derive(Debug, Deserialize)]
+pub struct CheckoutMetadata {
+    pub apple_pay: apple_pay::ApplePayDecryptionKeys,
+}
+
+/// Decrypts the Apple Pay token and sends the device account number as a network token.
+fn get_apple_pay_source(
+    item: &types::PaymentsAuthorizeRouterData,
+    wallet_data: &api::WalletData,
+) -> Result<Source, error_stack::Report<errors::ConnectorError>> {
+    let metadata: CheckoutMetadata = item
+        .connector_meta_data
+        .clone()
+        .get_required_value("connector_meta_data")
+        .change_context(errors::ConnectorError::NoConnectorMetaData)?
+        .parse_value("CheckoutMetadata")
+        .change_context(errors::ConnectorError::NoConnectorMetaData)?;
+    let payment_data: apple_pay::ApplePayPaymentData = wallet_data
+        .token
+        .clone()
+        .get_required_value("token")
+        .change_context(errors::ConnectorError::MissingRequiredField {
+            field_name: "payment_method_data.wallet.token",
+        })?
+        .parse_struct("ApplePayPaymentData")
+        .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+    let decrypted_data = apple_pay::decrypt_payment_data(&payment_data, &metadata.apple_pay)
+        .change_context(errors::ConnectorError::RequestEncodingFailed)
+        .attach_printable("Failed to decrypt Apple Pay token")?;
+    if decrypted_data.transaction_amount != item.request.amount {
+        Err(errors::ConnectorError::RequestEncodingFailedWithReason(
+            "The amount of the Apple Pay token does not match the amount of the payment"
+                .to_string(),
+        ))?
+    }
+
+    Ok(Source::NetworkToken(NetworkTokenSource {
+        source_type: "network_token".to_string(),
+        expiry_month: decrypted_data
+            .get_expiry_month()
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?
+            .into(),
+        expiry_year: decrypted_data
+            .get_expiry_year()
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?
+            .into(),
+        token: decrypted_data.application_primary_account_number,
+        token_type: "applepay".to_string(),
+        cryptogram: decrypted_data.payment_data.online_payment_cryptogram,
+        eci: decrypted_data.payment_data.eci_indicator,
+    }))
+}
+
+/// Sends a saved card stored as a network token, whose token takes the place of the card number.
+fn get_network_token_source(
+    token: &api::Card,
+    network_token: &types::NetworkTokenData,
+) -> Result<Source, error_stack::Report<errors::ConnectorError>> {
+    let token_type = match token.network() {
+        api_models::enums::CardNetwork::Visa => "vts",
+        api_models::enums::CardNetwork::Mastercard => "mdes",
+        network => Err(errors::ConnectorError::NotImplemented(format!(
+            "Network tokens of {network}"
+        )))?,
+    };
+
+    Ok(Source::NetworkToken(NetworkTokenSource {
+        source_type: "network_token".to_string(),
+        token: token.card_number.clone(),
+        expiry_month: token.card_exp_month.clone(),
+        expiry_year: token.card_exp_year.clone(),
+        token_type: token_type.to_string(),
+        cryptogram: network_token.cryptogram.clone(),
+        eci: network_token.eci.clone(),
+    }))
 }
 
 pub struct CheckoutAuthType {
@@ -70,6 +163,27 @@ impl TryFrom<&types::ConnectorAuthType> for CheckoutAuthType {
         }
     }
 }
+
+fn get_three_ds(auth_type: enums::AuthenticationType) -> CheckoutThreeDS {
+    match auth_type {
+        enums::AuthenticationType::ThreeDs => CheckoutThreeDS {
+            enabled: true,
+            force_3ds: true,
+        },
+        enums::AuthenticationType::NoThreeDs => CheckoutThreeDS {
+            enabled: false,
+            force_3ds: false,
+        },
+    }
+}
+
+fn get_return_url(router_return_url: Option<&String>) -> ReturnUrl {
+    ReturnUrl {
+        success_url: router_return_url.map(|return_url| format!("{return_url}?status=success")),
+        failure_url: router_return_url.map(|return_url| format!("{return_url}?status=failure")),
+    }
+}
+
 impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentsRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
@@ -78,42 +192,40 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentsRequest {
             api::PaymentMethod::BankTransfer
             | api::PaymentMethod::Wallet(_)
             | api::PaymentMethod::PayLater(_)
-            | api::PaymentMethod::Paypal => None,
-        };
-
-        let three_ds = match item.auth_type {
-            enums::AuthenticationType::ThreeDs => CheckoutThreeDS {
-                enabled: true,
-                force_3ds: true,
-            },
-            enums::AuthenticationType::NoThreeDs => CheckoutThreeDS {
-                enabled: false,
-                force_3ds: false,
-            },
+            | api::PaymentMethod::Paypal
+            | api::PaymentMethod::BankRedirect(_) => None,
         };
 
-        let return_url = ReturnUrl {
-            success_url: item
-                .router_return_url
-                .as_ref()
-                .map(|return_url| format!("{return_url}?status=success")),
-            failure_url: item
-                .router_return_url
-                .as_ref()
-                .map(|return_url| format!("{return_url}?status=failure")),
-        };
+        let three_ds = get_three_ds(item.auth_type);
+        let return_url = get_return_url(item.router_return_url.as_ref());
 
         let capture = matches!(
             item.request.capture_method,
             Some(enums::CaptureMethod::Automatic)
         );
 
-        let source_var = Source::Card(CardSource {
-            source_type: Some("card".to_owned()),
-            number: ccard.map(|x| x.card_number.clone()),
-            expiry_month: ccard.map(|x| x.card_exp_month.clone()),
-            expiry_year: ccard.map(|x| x.card_exp_year.clone()),
-        });
+        let source_var = match (
+            &item.request.payment_method_data,
+            &item.request.network_token,
+        ) {
+            (api::PaymentMethod::Wallet(wallet_data), _) => match wallet_data.issuer_name {
+                api_models::enums::WalletIssuer::ApplePay => {
+                    get_apple_pay_source(item, wallet_data)?
+                }
+                _ => Err(errors::ConnectorError::NotImplemented(
+                    "Wallets other than Apple Pay".to_string(),
+                ))?,
+            },
+            (api::PaymentMethod::Card(token), Some(network_token)) => {
+                get_network_token_source(token, network_token)?
+            }
+            _ => Source::Card(CardSource {
+                source_type: Some("card".to_owned()),
+                number: ccard.map(|x| x.card_number.clone()),
+                expiry_month: ccard.map(|x| x.card_exp_month.clone()),
+                expiry_year: ccard.map(|x| x.card_exp_year.clone()),
+            }),
+        };
         let connector_auth = &item.connector_auth_type;
         let auth_type: CheckoutAuthType = connector_auth.try_into()?;
         let processing_channel_id = auth_type.processing_channel_id;
@@ -129,6 +241,39 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentsRequest {
     }
 }
 
+/// Checkout verifies a card through a zero amount payment, which returns a `Card Verified` status
+/// instead of authorizing the card.
+impl TryFrom<&types::VerifyRouterData> for PaymentsRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::VerifyRouterData) -> Result<Self, Self::Error> {
+        let source = match item.request.payment_method_data {
+            api::PaymentMethod::Card(ref ccard) => Source::Card(CardSource {
+                source_type: Some("card".to_owned()),
+                number: Some(ccard.card_number.clone()),
+                expiry_month: Some(ccard.card_exp_month.clone()),
+                expiry_year: Some(ccard.card_exp_year.clone()),
+            }),
+            api::PaymentMethod::BankTransfer
+            | api::PaymentMethod::Wallet(_)
+            | api::PaymentMethod::PayLater(_)
+            | api::PaymentMethod::Paypal
+            | api::PaymentMethod::BankRedirect(_) => Err(errors::ConnectorError::NotImplemented(
+                "Verification of payment methods other than cards".to_string(),
+            ))?,
+        };
+        let auth_type: CheckoutAuthType = (&item.connector_auth_type).try_into()?;
+        Ok(Self {
+            source,
+            amount: 0,
+            currency: item.request.currency.to_string(),
+            processing_channel_id: auth_type.processing_channel_id,
+            three_ds: get_three_ds(item.auth_type),
+            return_url: get_return_url(item.router_return_url.as_ref()),
+            capture: false,
+        })
+    }
+}
+
 #[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize)]
 pub enum CheckoutPaymentStatus {
     Authorized,
@@ -250,6 +395,57 @@ impl TryFrom<types::PaymentsResponseRouterData<PaymentsResponse>>
                 redirection_data,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
+            }),
+            ..item.data
+        })
+    }
+}
+
+impl TryFrom<types::VerifyResponseRouterData<PaymentsResponse>> for types::VerifyRouterData {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::VerifyResponseRouterData<PaymentsResponse>,
+    ) -> Result<Self, Self::Error> {
+        let redirection_url = item
+            .response
+            .links
+            .redirect
+            .map(|data| Url::parse(&data.href))
+            .transpose()
+            .into_report()
+            .change_context(errors::ConnectorError::ResponseHandlingFailed)
+            .attach_printable("Could not parse the redirection data")?;
+
+        let redirection_data = redirection_url.map(|url| services::RedirectForm {
+            url: url.to_string(),
+            method: services::Method::Get,
+            form_fields: std::collections::HashMap::from_iter(
+                url.query_pairs()
+                    .map(|(k, v)| (k.to_string(), v.to_string())),
+            ),
+        });
+        // A zero amount payment leaves nothing to capture once the card is accepted
+        let status = match item.response.status {
+            CheckoutPaymentStatus::CardVerified
+            | CheckoutPaymentStatus::Authorized
+            | CheckoutPaymentStatus::Captured => enums::AttemptStatus::Verified,
+            CheckoutPaymentStatus::Declined => enums::AttemptStatus::Failure,
+            CheckoutPaymentStatus::Pending => enums::AttemptStatus::AuthenticationPending,
+        };
+        Ok(Self {
+            status,
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(item.response.id),
+                redirect: redirection_data.is_some(),
+                redirection_data,
+                mandate_reference: None,
+                connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
@@ -293,6 +489,9 @@ impl TryFrom<types::PaymentsSyncResponseRouterData<PaymentsResponse>>
                 redirection_data,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
@@ -336,6 +535,9 @@ impl TryFrom<types::PaymentsCancelResponseRouterData<PaymentVoidResponse>>
                 redirection_data: None,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             status: response.into(),
             ..item.data
@@ -408,6 +610,9 @@ impl TryFrom<types::PaymentsCaptureResponseRouterData<PaymentCaptureResponse>>
                 redirection_data: None,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             status,
             amount_captured,
@@ -592,3 +797,120 @@ impl From<CheckoutRedirectResponseStatus> for enums::AttemptStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::panic)]
+    use super::*;
+    use crate::connector::utils::get_test_router_data;
+
+    fn get_verify_router_data() -> types::VerifyRouterData {
+        types::RouterData {
+            connector_auth_type: types::ConnectorAuthType::BodyKey {
+                api_key: "sk_test".to_string(),
+                key1: "pc_123".to_string(),
+            },
+            ..get_test_router_data(
+                "checkout",
+                types::VerifyRequestData {
+                    payment_method_data: api::PaymentMethod::Card(api::Card {
+                        card_number: pii::Secret::new("4242424242424242".to_string()),
+                        card_exp_month: pii::Secret::new("10".to_string()),
+                        card_exp_year: pii::Secret::new("2030".to_string()),
+                        card_holder_name: pii::Secret::new("John Doe".to_string()),
+                        card_cvc: pii::Secret::new("123".to_string()),
+                    }),
+                    currency: enums::Currency::GBP,
+                    confirm: true,
+                    statement_descriptor_suffix: None,
+                    mandate_id: None,
+                    setup_future_usage: Some(enums::FutureUsage::OffSession),
+                    off_session: None,
+                    setup_mandate_details: None,
+                },
+                Err(types::ErrorResponse::default()),
+            )
+        }
+    }
+
+    #[test]
+    fn test_verify_request_is_a_zero_amount_payment() {
+        let request = PaymentsRequest::try_from(&get_verify_router_data()).unwrap();
+
+        assert_eq!(request.amount, 0);
+        assert_eq!(request.currency, "GBP");
+        assert_eq!(request.processing_channel_id, "pc_123");
+        assert!(!request.capture);
+        assert!(matches!(
+            request.source,
+            Source::Card(CardSource {
+                source_type: Some(ref source_type),
+                ..
+            }) if source_type == "card"
+        ));
+    }
+
+    #[test]
+    fn test_verified_card_is_reported_as_verified() {
+        let response: PaymentsResponse = serde_json::from_value(serde_json::json!({
+            "id": "pay_verified",
+            "amount": 0,
+            "status": "Card Verified",
+            "source": {
+                "id": "src_reusable",
+                "type": "card",
+            },
+            "balances": {
+                "available_to_capture": 0,
+            },
+            "_links": {},
+        }))
+        .unwrap();
+
+        let router_data = types::VerifyRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_verify_router_data(),
+            http_code: 201,
+        })
+        .unwrap();
+
+        assert_eq!(router_data.status, enums::AttemptStatus::Verified);
+        match router_data.response.unwrap() {
+            types::PaymentsResponseData::TransactionResponse {
+                resource_id,
+                mandate_reference,
+                ..
+            } => {
+                assert!(matches!(
+                    resource_id,
+                    types::ResponseId::ConnectorTransactionId(id) if id == "pay_verified"
+                ));
+                // The source id is not a mandate that can be charged off session
+                assert_eq!(mandate_reference, None);
+            }
+            response => panic!("Unexpected payments response: {response:?}"),
+        }
+    }
+
+    #[test]
+    fn test_declined_verification_fails() {
+        let response: PaymentsResponse = serde_json::from_value(serde_json::json!({
+            "id": "pay_declined",
+            "status": "Declined",
+            "balances": {
+                "available_to_capture": 0,
+            },
+            "_links": {},
+        }))
+        .unwrap();
+
+        let router_data = types::VerifyRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_verify_router_data(),
+            http_code: 201,
+        })
+        .unwrap();
+
+        assert_eq!(router_data.status, enums::AttemptStatus::Failure);
+    }
+}
@@ -109,6 +109,7 @@ impl ConnectorCommon for Cybersource {
                 })
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: response.reason,
+            decline_type: None,
         })
     }
 }
@@ -172,6 +173,17 @@ where
 }
 
 impl api::Payment for Cybersource {}
+impl api::PaymentsPreProcessing for Cybersource {}
+
+impl
+    ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Cybersource
+{
+}
+
 impl api::PaymentAuthorize for Cybersource {}
 impl api::PaymentSync for Cybersource {}
 impl api::PaymentVoid for Cybersource {}
@@ -692,6 +704,18 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::SubmitEvidence for Cybersource {}
+
+impl
+    ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Cybersource
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Cybersource {
     fn get_webhook_object_reference_id(
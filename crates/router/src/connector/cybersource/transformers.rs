@@ -4,7 +4,9 @@ use masking::Secret;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    connector::utils::{self, AddressDetailsData, PaymentsRequestData, PhoneDetailsData},
+    connector::utils::{
+        self, AddressDetailsData, PaymentsRequestData, PhoneDetailsData, RouterData,
+    },
     consts,
     core::errors,
     pii::PeekInterface,
@@ -311,6 +313,7 @@ impl<F, T>
                     message: error.message,
                     reason: Some(error.reason),
                     status_code: item.http_code,
+                    decline_type: None,
                 }),
                 _ => Ok(types::PaymentsResponseData::TransactionResponse {
                     resource_id: types::ResponseId::ConnectorTransactionId(item.response.id),
@@ -318,6 +321,9 @@ impl<F, T>
                     redirect: false,
                     mandate_reference: None,
                     connector_metadata: None,
+                    avs_result: None,
+                    cvv_result: None,
+                    connector_response_reference_id: None,
                 }),
             },
             ..item.data
@@ -382,6 +388,9 @@ impl<F, T>
                 redirect: false,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
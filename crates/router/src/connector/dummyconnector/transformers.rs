@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    connector::utils::{CardData, RouterData},
+    core::errors,
+    pii::{self, Secret},
+    services,
+    types::{self, api, storage::enums},
+};
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub struct DummyConnectorPaymentsRequest {
+    amount: i64,
+    currency: enums::Currency,
+    card: DummyConnectorCard,
+    /// Payments that are not auto captured are only authorized by the connector.
+    capture: bool,
+    three_ds: bool,
+    return_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub struct DummyConnectorCard {
+    number: Secret<String, pii::CardNumber>,
+    expiry_month: Secret<String>,
+    expiry_year: Secret<String>,
+    cvc: Secret<String>,
+}
+
+impl TryFrom<&types::PaymentsAuthorizeRouterData> for DummyConnectorPaymentsRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
+        let card = match item.request.payment_method_data {
+            api::PaymentMethod::Card(ref ccard) => DummyConnectorCard {
+                number: Secret::new(ccard.get_card_number()),
+                expiry_month: Secret::new(ccard.get_card_expiry_month()),
+                expiry_year: Secret::new(ccard.get_card_expiry_year()),
+                cvc: Secret::new(ccard.get_card_cvc()),
+            },
+            _ => Err(errors::ConnectorError::NotImplemented(
+                "Payment methods other than card".to_string(),
+            ))?,
+        };
+        Ok(Self {
+            amount: item.request.amount,
+            currency: item.request.currency,
+            card,
+            capture: !matches!(
+                item.request.capture_method,
+                Some(enums::CaptureMethod::Manual)
+            ),
+            three_ds: item.auth_type == enums::AuthenticationType::ThreeDs,
+            return_url: item.get_return_url().ok(),
+        })
+    }
+}
+
+// Auth Struct
+pub struct DummyConnectorAuthType {
+    pub(super) api_key: String,
+}
+
+impl TryFrom<&types::ConnectorAuthType> for DummyConnectorAuthType {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(auth_type: &types::ConnectorAuthType) -> Result<Self, Self::Error> {
+        if let types::ConnectorAuthType::HeaderKey { api_key } = auth_type {
+            Ok(Self {
+                api_key: api_key.to_string(),
+            })
+        } else {
+            Err(errors::ConnectorError::FailedToObtainAuthType.into())
+        }
+    }
+}
+
+// PaymentsResponse
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DummyConnectorPaymentStatus {
+    Succeeded,
+    Authorized,
+    Failed,
+    #[default]
+    Processing,
+    RequiresCustomerAction,
+}
+
+impl From<DummyConnectorPaymentStatus> for enums::AttemptStatus {
+    fn from(item: DummyConnectorPaymentStatus) -> Self {
+        match item {
+            DummyConnectorPaymentStatus::Succeeded => Self::Charged,
+            DummyConnectorPaymentStatus::Authorized => Self::Authorized,
+            DummyConnectorPaymentStatus::Failed => Self::Failure,
+            DummyConnectorPaymentStatus::Processing => Self::Pending,
+            DummyConnectorPaymentStatus::RequiresCustomerAction => Self::AuthenticationPending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DummyConnectorPaymentsResponse {
+    id: String,
+    status: DummyConnectorPaymentStatus,
+    /// Page the customer is sent to when the payment requires customer action (3DS).
+    redirect_url: Option<String>,
+}
+
+impl<F, T>
+    TryFrom<
+        types::ResponseRouterData<
+            F,
+            DummyConnectorPaymentsResponse,
+            T,
+            types::PaymentsResponseData,
+        >,
+    > for types::RouterData<F, T, types::PaymentsResponseData>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            F,
+            DummyConnectorPaymentsResponse,
+            T,
+            types::PaymentsResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        let redirection_data = match item.response.status {
+            DummyConnectorPaymentStatus::RequiresCustomerAction => {
+                let url = item.response.redirect_url.ok_or(
+                    errors::ConnectorError::MissingRequiredField {
+                        field_name: "redirect_url",
+                    },
+                )?;
+                Some(services::RedirectForm {
+                    url,
+                    method: services::Method::Get,
+                    form_fields: HashMap::new(),
+                })
+            }
+            _ => None,
+        };
+        Ok(Self {
+            status: enums::AttemptStatus::from(item.response.status),
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(item.response.id),
+                redirect: redirection_data.is_some(),
+                redirection_data,
+                mandate_reference: None,
+                connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
+            }),
+            ..item.data
+        })
+    }
+}
+
+// CAPTURE :
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub struct DummyConnectorCaptureRequest {
+    amount: i64,
+}
+
+impl TryFrom<&types::PaymentsCaptureRouterData> for DummyConnectorCaptureRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCaptureRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            amount: item
+                .request
+                .amount_to_capture
+                .unwrap_or(item.request.amount),
+        })
+    }
+}
+
+// REFUND :
+// Type definition for RefundRequest
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub struct DummyConnectorRefundRequest {
+    amount: i64,
+}
+
+impl<F> TryFrom<&types::RefundsRouterData<F>> for DummyConnectorRefundRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            amount: item.request.refund_amount,
+        })
+    }
+}
+
+// Type definition for Refund Response
+#[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundStatus {
+    Succeeded,
+    Failed,
+    #[default]
+    Pending,
+}
+
+impl From<RefundStatus> for enums::RefundStatus {
+    fn from(item: RefundStatus) -> Self {
+        match item {
+            RefundStatus::Succeeded => Self::Success,
+            RefundStatus::Failed => Self::Failure,
+            RefundStatus::Pending => Self::Pending,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RefundResponse {
+    id: String,
+    status: RefundStatus,
+}
+
+impl<F> TryFrom<types::RefundsResponseRouterData<F, RefundResponse>>
+    for types::RefundsRouterData<F>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::RefundsResponseRouterData<F, RefundResponse>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            response: Ok(types::RefundsResponseData {
+                connector_refund_id: item.response.id,
+                refund_status: enums::RefundStatus::from(item.response.status),
+            }),
+            ..item.data
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DummyConnectorErrorResponse {
+    pub error: DummyConnectorError,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DummyConnectorError {
+    pub code: String,
+    pub message: String,
+    pub reason: Option<String>,
+}
@@ -241,6 +241,7 @@ impl
             code: consts::NO_ERROR_CODE.to_string(),
             message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -257,6 +258,17 @@ impl
 {
 }
 
+impl api::PaymentsPreProcessing for Fiserv {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Fiserv
+{
+}
+
 impl api::PaymentAuthorize for Fiserv {}
 
 impl
@@ -392,6 +404,7 @@ impl
             code: consts::NO_ERROR_CODE.to_string(),
             message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -412,6 +425,18 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
 {
 }
 
+impl api::SubmitEvidence for Fiserv {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Fiserv
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Fiserv {
     fn get_webhook_object_reference_id(
@@ -229,6 +229,9 @@ impl<F, T>
                 redirect: false,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
@@ -341,3 +344,35 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
         Err(errors::ConnectorError::NotImplemented("fiserv".to_string()).into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_auth_type_from_signature_key() {
+        let auth_type = FiservAuthType::try_from(&types::ConnectorAuthType::SignatureKey {
+            api_key: "api_key".to_string(),
+            key1: "merchant_account".to_string(),
+            api_secret: "api_secret".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(auth_type.api_key, "api_key");
+        assert_eq!(auth_type.merchant_account, "merchant_account");
+        assert_eq!(auth_type.api_secret, "api_secret");
+    }
+
+    #[test]
+    fn test_auth_type_rejects_other_auth_types() {
+        let result = FiservAuthType::try_from(&types::ConnectorAuthType::HeaderKey {
+            api_key: "api_key".to_string(),
+        });
+
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ConnectorError::FailedToObtainAuthType)
+        ));
+    }
+}
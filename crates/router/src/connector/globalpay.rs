@@ -94,6 +94,7 @@ impl ConnectorCommon for Globalpay {
             code: response.error_code,
             message: response.detailed_error_description,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -189,6 +190,7 @@ impl ConnectorIntegration<api::AccessTokenAuth, types::AccessTokenRequestData, t
             code: response.error_code,
             message: response.detailed_error_description,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -444,6 +446,17 @@ impl ConnectorIntegration<api::Session, types::PaymentsSessionData, types::Payme
 {
 }
 
+impl api::PaymentsPreProcessing for Globalpay {}
+
+impl
+    ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Globalpay
+{
+}
+
 impl api::PaymentAuthorize for Globalpay {}
 
 impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::PaymentsResponseData>
@@ -689,6 +702,18 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::SubmitEvidence for Globalpay {}
+
+impl
+    ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Globalpay
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Globalpay {
     fn get_webhook_object_reference_id(
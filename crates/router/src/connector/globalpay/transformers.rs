@@ -47,6 +47,10 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for GlobalpayPaymentsRequest {
                 }),
                 ..Default::default()
             },
+            surcharge_amount: item
+                .request
+                .surcharge_details
+                .map(|surcharge_details| surcharge_details.surcharge_amount.to_string()),
             ..Default::default()
         })
     }
@@ -168,6 +172,9 @@ fn get_payment_response(
             redirect: false,
             mandate_reference: None,
             connector_metadata: None,
+            avs_result: None,
+            cvv_result: None,
+            connector_response_reference_id: None,
         }),
     }
 }
@@ -46,6 +46,17 @@ impl ConnectorCommon for Klarna {
 
 impl api::Payment for Klarna {}
 
+impl api::PaymentsPreProcessing for Klarna {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Klarna
+{
+}
+
 impl api::PaymentAuthorize for Klarna {}
 impl api::PaymentSync for Klarna {}
 impl api::PaymentVoid for Klarna {}
@@ -165,6 +176,7 @@ impl
             code: response.error_code,
             message: response.error_messages.join(" & "),
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -313,6 +325,7 @@ impl
             code: response.error_code,
             message: response.error_messages.join(" & "),
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -340,6 +353,18 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
 {
 }
 
+impl api::SubmitEvidence for Klarna {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Klarna
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Klarna {
     fn get_webhook_object_reference_id(
@@ -0,0 +1,2099 @@
+use std::collections::HashMap;
+
+use common_utils::ext_traits::ValueExt;
+use error_stack::{report, IntoReport, ResultExt};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{
+    connector::utils::{
+        self, AccessTokenRequestInfo, AddressDetailsData, CardData, PaymentsRequestData, RouterData,
+    },
+    consts,
+    core::errors,
+    pii::{self, ExposeInterface, PeekInterface, Secret},
+    services,
+    types::{self, api, storage::enums},
+};
+
+/// Currencies Opayo accepts for card payments.
+pub(super) const SUPPORTED_CURRENCIES: [enums::Currency; 14] = [
+    enums::Currency::AUD,
+    enums::Currency::CAD,
+    enums::Currency::CHF,
+    enums::Currency::CZK,
+    enums::Currency::DKK,
+    enums::Currency::EUR,
+    enums::Currency::GBP,
+    enums::Currency::HKD,
+    enums::Currency::JPY,
+    enums::Currency::NOK,
+    enums::Currency::NZD,
+    enums::Currency::SEK,
+    enums::Currency::SGD,
+    enums::Currency::USD,
+];
+
+// Auth Struct
+// The vendor name, sent in the merchant session key request, is the `key1` of the auth type.
+pub struct OpayoAuthType {
+    pub(super) integration_key: String,
+    pub(super) integration_password: String,
+}
+
+impl TryFrom<&types::ConnectorAuthType> for OpayoAuthType {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(auth_type: &types::ConnectorAuthType) -> Result<Self, Self::Error> {
+        if let types::ConnectorAuthType::SignatureKey {
+            api_key,
+            api_secret,
+            ..
+        } = auth_type
+        {
+            Ok(Self {
+                integration_key: api_key.to_string(),
+                integration_password: api_secret.to_string(),
+            })
+        } else {
+            Err(errors::ConnectorError::FailedToObtainAuthType.into())
+        }
+    }
+}
+
+// MERCHANT SESSION KEY :
+// Merchant session keys are short lived tokens used to tokenize the card details
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoMerchantSessionKeyRequest {
+    vendor_name: String,
+}
+
+impl TryFrom<&types::RefreshTokenRouterData> for OpayoMerchantSessionKeyRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::RefreshTokenRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            vendor_name: item.get_request_id()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoMerchantSessionKeyResponse {
+    pub merchant_session_key: Secret<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expiry: OffsetDateTime,
+}
+
+impl<F, T>
+    TryFrom<types::ResponseRouterData<F, OpayoMerchantSessionKeyResponse, T, types::AccessToken>>
+    for types::RouterData<F, T, types::AccessToken>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<F, OpayoMerchantSessionKeyResponse, T, types::AccessToken>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            response: Ok(types::AccessToken {
+                token: item.response.merchant_session_key.expose(),
+                expires: (item.response.expiry - OffsetDateTime::now_utc()).whole_seconds(),
+            }),
+            ..item.data
+        })
+    }
+}
+
+// CARD IDENTIFIER :
+// Card details are tokenized into a card identifier before the transaction is created
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoCardIdentifierRequest {
+    card_details: OpayoCardDetails,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoCardDetails {
+    cardholder_name: Secret<String>,
+    card_number: Secret<String, pii::CardNumber>,
+    expiry_date: Secret<String>,
+    security_code: Secret<String>,
+}
+
+impl TryFrom<&types::PaymentsPreProcessingRouterData> for OpayoCardIdentifierRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsPreProcessingRouterData) -> Result<Self, Self::Error> {
+        match item.request.payment_method_data {
+            api::PaymentMethod::Card(ref ccard) => Ok(Self {
+                card_details: OpayoCardDetails {
+                    cardholder_name: ccard.card_holder_name.clone(),
+                    card_number: ccard.card_number.clone(),
+                    expiry_date: Secret::new(format!(
+                        "{}{}",
+                        ccard.get_card_expiry_month(),
+                        ccard.get_card_expiry_year_2_digit()
+                    )),
+                    security_code: ccard.card_cvc.clone(),
+                },
+            }),
+            _ => Err(errors::ConnectorError::NotImplemented(
+                "Payment methods other than card".to_string(),
+            )
+            .into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoCardIdentifierResponse {
+    pub card_identifier: Secret<String>,
+}
+
+/// Values obtained in the pre-processing step that are required to create the transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct OpayoConnectorMetadata {
+    pub card_identifier: Secret<String>,
+}
+
+impl TryFrom<serde_json::Value> for OpayoConnectorMetadata {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let invalid_metadata_error = || errors::ConnectorError::InvalidConnectorMetadata {
+            connector: "opayo".to_string(),
+        };
+        let metadata: Self = value
+            .parse_value("OpayoConnectorMetadata")
+            .change_context(invalid_metadata_error())?;
+        if metadata.card_identifier.peek().trim().is_empty() {
+            return Err(report!(invalid_metadata_error()))
+                .attach_printable("card_identifier is empty");
+        }
+        Ok(metadata)
+    }
+}
+
+impl TryFrom<types::PaymentsPreProcessingResponseRouterData<OpayoCardIdentifierResponse>>
+    for types::PaymentsPreProcessingRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::PaymentsPreProcessingResponseRouterData<OpayoCardIdentifierResponse>,
+    ) -> Result<Self, Self::Error> {
+        let connector_metadata = serde_json::to_value(OpayoConnectorMetadata {
+            card_identifier: item.response.card_identifier.clone(),
+        })
+        .into_report()
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)?;
+        Ok(Self {
+            response: Ok(types::PaymentsResponseData::PreProcessingResponse {
+                pre_processing_id: item.response.card_identifier.expose(),
+                connector_metadata: Some(connector_metadata),
+            }),
+            ..item.data
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub enum OpayoTransactionType {
+    Payment,
+    Deferred,
+    Repeat,
+    Refund,
+    Authenticate,
+}
+
+/// Outcome of a transaction as reported in the `status` field of every Opayo transaction object.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum OpayoTransactionStatus {
+    Ok,
+    NotAuthed,
+    Rejected,
+    #[serde(rename = "3DAuth")]
+    ThreeDAuth,
+    Malformed,
+    Invalid,
+    #[default]
+    Error,
+}
+
+// PAYMENTS :
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoPaymentsRequest {
+    transaction_type: OpayoTransactionType,
+    payment_method: OpayoPaymentMethod,
+    vendor_tx_code: String,
+    #[serde(flatten)]
+    amount: OpayoAmount,
+    description: String,
+    #[serde(rename = "apply3DSecure")]
+    apply3_d_secure: OpayoApply3DSecure,
+    customer_first_name: Secret<String>,
+    customer_last_name: Secret<String>,
+    billing_address: OpayoBillingAddress,
+    entry_method: String,
+    strong_customer_authentication: Option<OpayoStrongCustomerAuthentication>,
+    credential_type: Option<OpayoCredentialType>,
+}
+
+/// Whether Opayo authenticates the customer with 3-D Secure before authorising the transaction.
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub enum OpayoApply3DSecure {
+    /// Leaves the decision to the 3-D Secure rules configured on the Opayo account
+    #[serde(rename = "UseMSPSetting")]
+    UseMspSetting,
+    Force,
+    Disable,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub struct OpayoPaymentMethod {
+    card: OpayoCardSession,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoCardSession {
+    merchant_session_key: Secret<String>,
+    card_identifier: Secret<String>,
+    /// Set when the card identifier refers to card details stored by an earlier transaction.
+    reusable: bool,
+    /// Asks Opayo to store the card details so that the card identifier can be reused.
+    save: bool,
+}
+
+/// Describes how stored card details are used, as required by the card schemes for
+/// credential-on-file transactions.
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoCredentialType {
+    cof_usage: OpayoCofUsage,
+    initiated_type: OpayoInitiatedType,
+    mit_type: Option<OpayoMitType>,
+    recurring_expiry: Option<String>,
+    recurring_frequency: Option<String>,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub enum OpayoCofUsage {
+    First,
+    Subsequent,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub enum OpayoInitiatedType {
+    #[serde(rename = "CIT")]
+    Customer,
+    #[serde(rename = "MIT")]
+    Merchant,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub enum OpayoMitType {
+    Recurring,
+    Unscheduled,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoBillingAddress {
+    address1: Secret<String>,
+    city: String,
+    postal_code: Secret<String>,
+    country: String,
+}
+
+/// Browser details used by the issuer to decide whether a 3DS challenge is required.
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoStrongCustomerAuthentication {
+    #[serde(rename = "notificationURL")]
+    notification_url: String,
+    #[serde(rename = "browserIP")]
+    browser_ip: Option<std::net::IpAddr>,
+    browser_accept_header: String,
+    browser_javascript_enabled: bool,
+    browser_java_enabled: bool,
+    browser_language: String,
+    browser_color_depth: String,
+    browser_screen_height: String,
+    browser_screen_width: String,
+    #[serde(rename = "browserTZ")]
+    browser_tz: String,
+    browser_user_agent: String,
+    challenge_window_size: OpayoChallengeWindowSize,
+    trans_type: String,
+    #[serde(rename = "threeDSExemptionIndicator")]
+    three_ds_exemption_indicator: Option<OpayoThreeDSExemptionIndicator>,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub enum OpayoThreeDSExemptionIndicator {
+    LowValue,
+    TransactionRiskAnalysis,
+}
+
+impl From<api_models::enums::ScaExemptionType> for OpayoThreeDSExemptionIndicator {
+    fn from(exemption: api_models::enums::ScaExemptionType) -> Self {
+        match exemption {
+            api_models::enums::ScaExemptionType::LowValue => Self::LowValue,
+            api_models::enums::ScaExemptionType::TransactionRiskAnalysis => {
+                Self::TransactionRiskAnalysis
+            }
+        }
+    }
+}
+
+/// Forces 3-D Secure for payments that require it. Otherwise 3-D Secure is disabled, unless the
+/// merchant requests an exemption, which the issuer may decline and ask for a challenge instead.
+fn get_apply_3d_secure(
+    auth_type: enums::AuthenticationType,
+    sca_exemption: Option<api_models::enums::ScaExemptionType>,
+) -> OpayoApply3DSecure {
+    match (auth_type, sca_exemption) {
+        (enums::AuthenticationType::ThreeDs, _) => OpayoApply3DSecure::Force,
+        (enums::AuthenticationType::NoThreeDs, Some(_)) => OpayoApply3DSecure::UseMspSetting,
+        (enums::AuthenticationType::NoThreeDs, None) => OpayoApply3DSecure::Disable,
+    }
+}
+
+/// Challenge window sizes defined by the EMV 3-D Secure 2.x specification.
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub enum OpayoChallengeWindowSize {
+    /// 250 x 400 pixels
+    #[serde(rename = "01")]
+    Small,
+    /// 390 x 400 pixels
+    #[serde(rename = "02")]
+    Medium,
+    /// 500 x 600 pixels
+    #[serde(rename = "03")]
+    Large,
+    /// 600 x 400 pixels
+    #[serde(rename = "04")]
+    ExtraLarge,
+    #[serde(rename = "05")]
+    FullScreen,
+}
+
+/// Picks the challenge window with the largest area that fits within the customer's screen,
+/// falling back to a full screen challenge when none of the windows fit. The 500 x 600 window is
+/// larger than the 600 x 400 one despite its name.
+fn get_challenge_window_size(screen_width: u32, screen_height: u32) -> OpayoChallengeWindowSize {
+    match (screen_width, screen_height) {
+        (500.., 600..) => OpayoChallengeWindowSize::Large,
+        (600.., 400..) => OpayoChallengeWindowSize::ExtraLarge,
+        (390.., 400..) => OpayoChallengeWindowSize::Medium,
+        (250.., 400..) => OpayoChallengeWindowSize::Small,
+        _ => OpayoChallengeWindowSize::FullScreen,
+    }
+}
+
+impl TryFrom<&types::PaymentsAuthorizeRouterData> for OpayoStrongCustomerAuthentication {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
+        let browser_info = item.get_browser_info()?;
+        Ok(Self {
+            notification_url: item.get_return_url()?,
+            browser_ip: browser_info.ip_address,
+            browser_accept_header: browser_info.accept_header.clone(),
+            browser_javascript_enabled: browser_info.java_script_enabled,
+            browser_java_enabled: browser_info.java_enabled,
+            browser_language: browser_info.language.clone(),
+            browser_color_depth: browser_info.color_depth.to_string(),
+            browser_screen_height: browser_info.screen_height.to_string(),
+            browser_screen_width: browser_info.screen_width.to_string(),
+            browser_tz: browser_info.time_zone.to_string(),
+            browser_user_agent: browser_info.user_agent.clone(),
+            challenge_window_size: get_challenge_window_size(
+                browser_info.screen_width,
+                browser_info.screen_height,
+            ),
+            trans_type: "GoodsAndServicePurchase".to_string(),
+            // An exemption cannot be requested for a payment that is forced through 3-D Secure
+            three_ds_exemption_indicator: match item.auth_type {
+                enums::AuthenticationType::ThreeDs => None,
+                enums::AuthenticationType::NoThreeDs => item.request.sca_exemption.map(Into::into),
+            },
+        })
+    }
+}
+
+/// Returns the card identifier stored with the mandate when the payment is a merchant initiated
+/// charge, in which case the customer is not present and no card details are tokenized.
+fn get_mandate_card_identifier(
+    item: &types::PaymentsAuthorizeRouterData,
+) -> Result<Option<Secret<String>>, errors::ConnectorError> {
+    match (item.request.off_session, item.request.mandate_id.as_ref()) {
+        (Some(true), Some(mandate_ids)) => mandate_ids
+            .connector_mandate_id
+            .clone()
+            .map(|id| Some(Secret::new(id)))
+            .ok_or(errors::ConnectorError::MissingRequiredField {
+                field_name: "mandate_id.connector_mandate_id",
+            }),
+        _ => Ok(None),
+    }
+}
+
+/// Builds the credential type of the customer initiated payment that sets up a mandate, which
+/// stores the card details for the merchant initiated payments that follow.
+fn get_first_use_credential_type(
+    item: &types::PaymentsAuthorizeRouterData,
+) -> Option<OpayoCredentialType> {
+    if item.request.setup_mandate_details.is_none()
+        && item.request.setup_future_usage != Some(enums::FutureUsage::OffSession)
+    {
+        return None;
+    }
+    let mandate_amount_data =
+        item.request
+            .setup_mandate_details
+            .as_ref()
+            .and_then(|mandate_data| match &mandate_data.mandate_type {
+                api::MandateType::SingleUse(data) => Some(data),
+                api::MandateType::MultiUse(data) => data.as_ref(),
+            });
+    // Opayo only accepts a recurring mandate along with the interval between debits
+    let credential_type = match mandate_amount_data.and_then(|data| {
+        data.frequency_in_days
+            .map(|frequency| (data.end_date, frequency))
+    }) {
+        Some((end_date, frequency_in_days)) => OpayoCredentialType {
+            cof_usage: OpayoCofUsage::First,
+            initiated_type: OpayoInitiatedType::Customer,
+            mit_type: Some(OpayoMitType::Recurring),
+            recurring_expiry: end_date.map(|end_date| {
+                format!(
+                    "{:04}{:02}{:02}",
+                    end_date.year(),
+                    u8::from(end_date.month()),
+                    end_date.day()
+                )
+            }),
+            recurring_frequency: Some(frequency_in_days.to_string()),
+        },
+        None => OpayoCredentialType {
+            cof_usage: OpayoCofUsage::First,
+            initiated_type: OpayoInitiatedType::Customer,
+            mit_type: Some(OpayoMitType::Unscheduled),
+            recurring_expiry: None,
+            recurring_frequency: None,
+        },
+    };
+    Some(credential_type)
+}
+
+impl TryFrom<&types::PaymentsAuthorizeRouterData> for OpayoPaymentsRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
+        utils::validate_currency(item.request.currency, &SUPPORTED_CURRENCIES, "opayo")?;
+        let merchant_session_key = item
+            .access_token
+            .as_ref()
+            .map(|access_token| Secret::new(access_token.token.clone()))
+            .ok_or(errors::ConnectorError::FailedToObtainAuthType)?;
+        let (card, credential_type) = match get_mandate_card_identifier(item)? {
+            Some(card_identifier) => (
+                OpayoCardSession {
+                    merchant_session_key,
+                    card_identifier,
+                    reusable: true,
+                    save: false,
+                },
+                Some(OpayoCredentialType {
+                    cof_usage: OpayoCofUsage::Subsequent,
+                    initiated_type: OpayoInitiatedType::Merchant,
+                    mit_type: Some(OpayoMitType::Unscheduled),
+                    recurring_expiry: None,
+                    recurring_frequency: None,
+                }),
+            ),
+            None => {
+                let metadata: OpayoConnectorMetadata =
+                    utils::to_connector_meta(item.request.connector_meta.clone())?;
+                let credential_type = get_first_use_credential_type(item);
+                (
+                    OpayoCardSession {
+                        merchant_session_key,
+                        card_identifier: metadata.card_identifier,
+                        reusable: false,
+                        save: credential_type.is_some(),
+                    },
+                    credential_type,
+                )
+            }
+        };
+        let address = item.get_billing_address()?;
+        let transaction_type = match item.request.capture_method {
+            Some(enums::CaptureMethod::Automatic) | None => OpayoTransactionType::Payment,
+            Some(enums::CaptureMethod::Manual) => OpayoTransactionType::Deferred,
+            // A deferred transaction can only be released once, when the merchant captures it
+            Some(enums::CaptureMethod::ManualMultiple | enums::CaptureMethod::Scheduled) => {
+                Err(errors::ConnectorError::NotImplemented(
+                    "Multiple and scheduled captures for Opayo".to_string(),
+                ))?
+            }
+        };
+        let apply3_d_secure = get_apply_3d_secure(item.auth_type, item.request.sca_exemption);
+        Ok(Self {
+            transaction_type,
+            payment_method: OpayoPaymentMethod { card },
+            vendor_tx_code: item.get_attempt_id()?,
+            amount: types::Money::new(item.request.amount, item.request.currency).into(),
+            description: unwrap_or_connector_default(item.description.clone(), || {
+                item.payment_id.clone()
+            }),
+            apply3_d_secure,
+            customer_first_name: address.get_first_name()?.to_owned(),
+            customer_last_name: address.get_last_name()?.to_owned(),
+            billing_address: OpayoBillingAddress {
+                address1: address.get_line1()?.to_owned(),
+                city: address.get_city()?.to_owned(),
+                postal_code: address.get_zip()?.to_owned(),
+                country: address.get_country()?.to_owned(),
+            },
+            entry_method: "Ecommerce".to_string(),
+            // The customer is not present to complete a challenge for merchant initiated charges
+            strong_customer_authentication: match (&apply3_d_secure, &credential_type) {
+                (OpayoApply3DSecure::Force | OpayoApply3DSecure::UseMspSetting, None) => {
+                    Some(OpayoStrongCustomerAuthentication::try_from(item)?)
+                }
+                _ => None,
+            },
+            credential_type,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum OpayoPaymentsResponse {
+    ThreeDSResponse(OpayoThreeDSResponse),
+    TransactionResponse(OpayoTransactionResponse),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoTransactionResponse {
+    pub transaction_id: String,
+    pub transaction_type: OpayoTransactionType,
+    pub status: OpayoTransactionStatus,
+    pub status_code: Option<String>,
+    pub status_detail: Option<String>,
+    /// Numeric reference that identifies the transaction on the Opayo dashboard
+    pub retrieval_reference: Option<u64>,
+    pub payment_method: Option<OpayoPaymentMethodResponse>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpayoPaymentMethodResponse {
+    pub card: Option<OpayoCardResponse>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoCardResponse {
+    pub card_identifier: Secret<String>,
+    /// Whether the card details have been stored and can be used for later payments.
+    #[serde(default)]
+    pub reusable: bool,
+}
+
+/// Returned with a `3DAuth` status when the issuer requires the customer to complete a challenge.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoThreeDSResponse {
+    pub transaction_id: String,
+    pub status: OpayoTransactionStatus,
+    pub acs_url: String,
+    pub c_req: String,
+}
+
+/// Whether Opayo could not process the transaction at the time, because the bank did not respond
+/// in time (2002) or Opayo hit an internal error (2003). Other errors, like a transaction that is
+/// not found (5036), fail the same way when the request is sent again.
+fn is_transient_status_code(status_code: Option<&str>) -> bool {
+    matches!(status_code, Some("2002" | "2003"))
+}
+
+/// Classifies a transaction that reached the bank but was not authorised.
+fn get_decline_type(
+    status: &OpayoTransactionStatus,
+    status_code: Option<&str>,
+) -> Option<types::DeclineType> {
+    if is_transient_status_code(status_code) {
+        return Some(types::DeclineType::Transient);
+    }
+    match status {
+        OpayoTransactionStatus::NotAuthed => Some(types::DeclineType::Soft),
+        // Rejected by the merchant's own fraud rules, which another connector should honour too
+        OpayoTransactionStatus::Rejected => Some(types::DeclineType::Hard),
+        _ => None,
+    }
+}
+
+/// Why Opayo declined or failed a transaction, kept as the reason of the error so that declines
+/// can be explained without knowing Opayo's status codes.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum OpayoDeclineReason {
+    CardDeclined,
+    RejectedByFraudRules,
+    AuthorisationTimedOut,
+    AuthenticationFailed,
+    ExpiredCard,
+}
+
+impl OpayoDeclineReason {
+    fn from_status_code(status_code: &str) -> Option<Self> {
+        match status_code {
+            "2000" => Some(Self::CardDeclined),
+            "2001" => Some(Self::RejectedByFraudRules),
+            "2002" => Some(Self::AuthorisationTimedOut),
+            "4026" => Some(Self::AuthenticationFailed),
+            "5013" => Some(Self::ExpiredCard),
+            _ => None,
+        }
+    }
+}
+
+fn get_decline_reason(status_code: Option<&str>) -> Option<String> {
+    status_code
+        .and_then(OpayoDeclineReason::from_status_code)
+        .map(|reason| reason.to_string())
+}
+
+/// Opayo rejects empty text fields, so a missing or empty optional value is replaced by the
+/// default instead.
+fn unwrap_or_connector_default(value: Option<String>, default: impl FnOnce() -> String) -> String {
+    value
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(default)
+}
+
+/// Declined and failed transactions are returned as errors, so the status code and detail
+/// reported by Opayo are kept on the payment attempt.
+fn get_transaction_error_response(
+    response: &OpayoTransactionResponse,
+    http_code: u16,
+) -> types::ErrorResponse {
+    types::ErrorResponse {
+        code: unwrap_or_connector_default(response.status_code.clone(), || {
+            consts::NO_ERROR_CODE.to_string()
+        }),
+        message: unwrap_or_connector_default(response.status_detail.clone(), || {
+            consts::NO_ERROR_MESSAGE.to_string()
+        }),
+        reason: get_decline_reason(response.status_code.as_deref()),
+        status_code: http_code,
+        decline_type: get_decline_type(&response.status, response.status_code.as_deref()),
+    }
+}
+
+fn get_attempt_status(
+    status: OpayoTransactionStatus,
+    transaction_type: OpayoTransactionType,
+) -> enums::AttemptStatus {
+    match status {
+        OpayoTransactionStatus::Ok => match transaction_type {
+            OpayoTransactionType::Deferred => enums::AttemptStatus::Authorized,
+            _ => enums::AttemptStatus::Charged,
+        },
+        OpayoTransactionStatus::ThreeDAuth => enums::AttemptStatus::AuthenticationPending,
+        OpayoTransactionStatus::NotAuthed | OpayoTransactionStatus::Rejected => {
+            enums::AttemptStatus::AuthorizationFailed
+        }
+        OpayoTransactionStatus::Malformed
+        | OpayoTransactionStatus::Invalid
+        | OpayoTransactionStatus::Error => enums::AttemptStatus::Failure,
+    }
+}
+
+impl<F, T>
+    TryFrom<types::ResponseRouterData<F, OpayoPaymentsResponse, T, types::PaymentsResponseData>>
+    for types::RouterData<F, T, types::PaymentsResponseData>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<F, OpayoPaymentsResponse, T, types::PaymentsResponseData>,
+    ) -> Result<Self, Self::Error> {
+        let (status, transaction_id, redirection_data, mandate_reference, retrieval_reference) =
+            match item.response {
+                OpayoPaymentsResponse::ThreeDSResponse(response) => {
+                    let form_fields = HashMap::from([
+                        ("creq".to_string(), response.c_req),
+                        (
+                            "threeDSSessionData".to_string(),
+                            response.transaction_id.clone(),
+                        ),
+                    ]);
+                    (
+                        enums::AttemptStatus::AuthenticationPending,
+                        response.transaction_id,
+                        Some(services::RedirectForm {
+                            url: response.acs_url,
+                            method: services::Method::Post,
+                            form_fields,
+                        }),
+                        None,
+                        None,
+                    )
+                }
+                OpayoPaymentsResponse::TransactionResponse(response) => {
+                    let status = get_attempt_status(
+                        response.status.clone(),
+                        response.transaction_type.clone(),
+                    );
+                    if matches!(
+                        status,
+                        enums::AttemptStatus::AuthorizationFailed | enums::AttemptStatus::Failure
+                    ) {
+                        return Ok(Self {
+                            status,
+                            response: Err(get_transaction_error_response(
+                                &response,
+                                item.http_code,
+                            )),
+                            ..item.data
+                        });
+                    }
+                    // The stored card identifier is what later merchant initiated payments reference
+                    let mandate_reference = response
+                        .payment_method
+                        .and_then(|payment_method| payment_method.card)
+                        .filter(|card| card.reusable)
+                        .map(|card| card.card_identifier.expose());
+                    (
+                        status,
+                        response.transaction_id,
+                        None,
+                        mandate_reference,
+                        response.retrieval_reference,
+                    )
+                }
+            };
+        Ok(Self {
+            status,
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(transaction_id),
+                redirect: redirection_data.is_some(),
+                redirection_data,
+                mandate_reference,
+                connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: retrieval_reference
+                    .map(|reference| reference.to_string()),
+            }),
+            ..item.data
+        })
+    }
+}
+
+// 3DS CHALLENGE :
+// The ACS posts the challenge result back to the router, which is forwarded to Opayo
+#[derive(Debug, Deserialize)]
+pub struct OpayoRedirectResponse {
+    cres: String,
+    #[serde(rename = "threeDSSessionData")]
+    three_ds_session_data: Option<String>,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoThreeDSChallengeRequest {
+    c_res: String,
+    #[serde(rename = "threeDSSessionData")]
+    three_ds_session_data: Option<String>,
+}
+
+impl TryFrom<&types::PaymentsSyncRouterData> for OpayoThreeDSChallengeRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsSyncRouterData) -> Result<Self, Self::Error> {
+        let encoded_data = item
+            .request
+            .encoded_data
+            .as_ref()
+            .ok_or_else(utils::missing_field_err("encoded_data"))?;
+        let redirect_response: OpayoRedirectResponse = serde_urlencoded::from_str(encoded_data)
+            .into_report()
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Self {
+            c_res: redirect_response.cres,
+            three_ds_session_data: redirect_response.three_ds_session_data,
+        })
+    }
+}
+
+// INSTRUCTIONS :
+// Deferred transactions are released (captured) through instructions on the original transaction
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OpayoInstructionType {
+    Release,
+    Void,
+    Abort,
+}
+
+/// Amount in the minor unit of the currency, along with its ISO 4217 currency code.
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub struct OpayoAmount {
+    amount: i64,
+    currency: &'static str,
+}
+
+impl From<types::Money> for OpayoAmount {
+    fn from(money: types::Money) -> Self {
+        Self {
+            amount: money.minor_unit(),
+            currency: money.currency_code(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoCaptureRequest {
+    instruction_type: OpayoInstructionType,
+    amount: i64,
+}
+
+impl TryFrom<&types::PaymentsCaptureRouterData> for OpayoCaptureRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsCaptureRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            instruction_type: OpayoInstructionType::Release,
+            amount: types::Money::new(
+                item.request
+                    .amount_to_capture
+                    .unwrap_or(item.request.amount),
+                item.request.currency,
+            )
+            .minor_unit(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoInstructionResponse {
+    pub instruction_type: OpayoInstructionType,
+    pub date: String,
+}
+
+impl TryFrom<types::PaymentsCaptureResponseRouterData<OpayoInstructionResponse>>
+    for types::PaymentsCaptureRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::PaymentsCaptureResponseRouterData<OpayoInstructionResponse>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            status: enums::AttemptStatus::Charged,
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.data.request.connector_transaction_id.clone(),
+                ),
+                redirection_data: None,
+                redirect: false,
+                mandate_reference: None,
+                connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
+            }),
+            ..item.data
+        })
+    }
+}
+
+// Uncaptured (deferred) transactions are cancelled by aborting them
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoVoidRequest {
+    instruction_type: OpayoInstructionType,
+}
+
+impl TryFrom<&types::PaymentsCancelRouterData> for OpayoVoidRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(_item: &types::PaymentsCancelRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            instruction_type: OpayoInstructionType::Abort,
+        })
+    }
+}
+
+impl TryFrom<types::PaymentsCancelResponseRouterData<OpayoInstructionResponse>>
+    for types::PaymentsCancelRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::PaymentsCancelResponseRouterData<OpayoInstructionResponse>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            status: enums::AttemptStatus::Voided,
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.data.request.connector_transaction_id.clone(),
+                ),
+                redirection_data: None,
+                redirect: false,
+                mandate_reference: None,
+                connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
+            }),
+            ..item.data
+        })
+    }
+}
+
+// REFUND :
+// Refunds are created as a new transaction referencing the original one. Unlike releases and voids,
+// they are not instructions on the original transaction: Opayo only accepts `instructionType` on
+// `/transactions/{transactionId}/instructions`, while refunds are posted to `/transactions` with
+// `transactionType: Refund` like any other transaction.
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoRefundRequest {
+    transaction_type: OpayoTransactionType,
+    vendor_tx_code: String,
+    reference_transaction_id: String,
+    amount: i64,
+    description: String,
+}
+
+impl<F> TryFrom<&types::RefundsRouterData<F>> for OpayoRefundRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transaction_type: OpayoTransactionType::Refund,
+            vendor_tx_code: item.request.refund_id.clone(),
+            reference_transaction_id: item.request.connector_transaction_id.clone(),
+            amount: types::Money::new(item.request.refund_amount, item.request.currency)
+                .minor_unit(),
+            description: unwrap_or_connector_default(item.request.reason.clone(), || {
+                "Refund".to_string()
+            }),
+        })
+    }
+}
+
+impl From<OpayoTransactionStatus> for enums::RefundStatus {
+    fn from(item: OpayoTransactionStatus) -> Self {
+        match item {
+            OpayoTransactionStatus::Ok => Self::Success,
+            OpayoTransactionStatus::ThreeDAuth => Self::Pending,
+            OpayoTransactionStatus::NotAuthed
+            | OpayoTransactionStatus::Rejected
+            | OpayoTransactionStatus::Malformed
+            | OpayoTransactionStatus::Invalid
+            | OpayoTransactionStatus::Error => Self::Failure,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundResponse {
+    pub transaction_id: String,
+    pub status: OpayoTransactionStatus,
+    pub status_code: Option<String>,
+    pub status_detail: Option<String>,
+}
+
+impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
+    for types::RefundsRouterData<api::Execute>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::RefundsResponseRouterData<api::Execute, RefundResponse>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            response: Ok(types::RefundsResponseData {
+                connector_refund_id: item.response.transaction_id,
+                refund_status: enums::RefundStatus::from(item.response.status),
+            }),
+            ..item.data
+        })
+    }
+}
+
+impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
+    for types::RefundsRouterData<api::RSync>
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::RefundsResponseRouterData<api::RSync, RefundResponse>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            response: Ok(types::RefundsResponseData {
+                connector_refund_id: item.response.transaction_id,
+                refund_status: enums::RefundStatus::from(item.response.status),
+            }),
+            ..item.data
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoErrorResponse {
+    /// Every invalid property of a request that failed validation
+    #[serde(default)]
+    pub errors: Vec<OpayoErrorDetails>,
+    /// Set instead of `errors` when the transaction was processed but declined
+    pub status: Option<OpayoTransactionStatus>,
+    pub status_code: Option<String>,
+    pub status_detail: Option<String>,
+}
+
+impl OpayoErrorResponse {
+    pub fn get_decline_type(&self) -> Option<types::DeclineType> {
+        self.status
+            .as_ref()
+            .and_then(|status| get_decline_type(status, self.status_code.as_deref()))
+    }
+
+    pub fn get_decline_reason(&self) -> Option<String> {
+        get_decline_reason(self.status_code.as_deref())
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpayoErrorDetails {
+    pub code: i64,
+    pub description: String,
+    pub property: Option<String>,
+    pub client_message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::connector::utils::get_test_router_data;
+
+    fn get_router_data<F, Req, Res>(request: Req) -> types::RouterData<F, Req, Res> {
+        types::RouterData {
+            status: enums::AttemptStatus::Charged,
+            connector_auth_type: types::ConnectorAuthType::SignatureKey {
+                api_key: "integration_key".to_string(),
+                key1: "vendor".to_string(),
+                api_secret: "integration_password".to_string(),
+            },
+            ..get_test_router_data("opayo", request, Err(types::ErrorResponse::default()))
+        }
+    }
+
+    fn get_refund_router_data<F>() -> types::RefundsRouterData<F> {
+        get_router_data(types::RefundsData {
+            refund_id: "ref_123".to_string(),
+            connector_transaction_id: "txn_123".to_string(),
+            connector_refund_id: None,
+            currency: enums::Currency::GBP,
+            amount: 1000,
+            reason: None,
+            refund_amount: 500,
+            connector_metadata: None,
+        })
+    }
+
+    fn get_sync_router_data(encoded_data: Option<String>) -> types::PaymentsSyncRouterData {
+        get_router_data(types::PaymentsSyncData {
+            connector_transaction_id: types::ResponseId::ConnectorTransactionId(
+                "txn_123".to_string(),
+            ),
+            encoded_data,
+            capture_method: None,
+        })
+    }
+
+    fn get_authorize_router_data(
+        amount: i64,
+        currency: enums::Currency,
+    ) -> types::PaymentsAuthorizeRouterData {
+        types::RouterData {
+            address: types::PaymentAddress {
+                billing: Some(api::Address {
+                    address: Some(api::AddressDetails {
+                        first_name: Some(Secret::new("John".to_string())),
+                        last_name: Some(Secret::new("Doe".to_string())),
+                        line1: Some(Secret::new("88 The Road".to_string())),
+                        city: Some("Harrogate".to_string()),
+                        zip: Some(Secret::new("412".to_string())),
+                        country: Some("GB".to_string()),
+                        ..Default::default()
+                    }),
+                    phone: None,
+                }),
+                ..Default::default()
+            },
+            access_token: Some(types::AccessToken {
+                token: "merchant_session_key".to_string(),
+                expires: 400,
+            }),
+            ..get_router_data(types::PaymentsAuthorizeData {
+                payment_method_data: api::PaymentMethod::Card(api::Card {
+                    card_number: Secret::new("4929000000006".to_string()),
+                    card_exp_month: Secret::new("03".to_string()),
+                    card_exp_year: Secret::new("2029".to_string()),
+                    card_holder_name: Secret::new("John Doe".to_string()),
+                    card_cvc: Secret::new("123".to_string()),
+                }),
+                amount,
+                email: None,
+                currency,
+                confirm: true,
+                statement_descriptor_suffix: None,
+                statement_descriptor_prefix: None,
+                capture_method: None,
+                setup_future_usage: None,
+                mandate_id: None,
+                off_session: None,
+                setup_mandate_details: None,
+                browser_info: None,
+                order_details: None,
+                surcharge_details: None,
+                tax_amount: None,
+                sca_exemption: None,
+                connector_meta: Some(serde_json::json!({ "card_identifier": "card_123" })),
+                network_token: None,
+            })
+        }
+    }
+
+    fn get_pre_processing_router_data() -> types::PaymentsPreProcessingRouterData {
+        get_router_data(types::PaymentsPreProcessingData {
+            payment_method_data: api::PaymentMethod::Card(api::Card {
+                card_number: Secret::new("4929000000006".to_string()),
+                card_exp_month: Secret::new("03".to_string()),
+                card_exp_year: Secret::new("2029".to_string()),
+                card_holder_name: Secret::new("John Doe".to_string()),
+                card_cvc: Secret::new("123".to_string()),
+            }),
+            amount: 1000,
+            currency: enums::Currency::GBP,
+            email: None,
+        })
+    }
+
+    fn get_capture_router_data(amount_to_capture: Option<i64>) -> types::PaymentsCaptureRouterData {
+        get_router_data(types::PaymentsCaptureData {
+            amount_to_capture,
+            currency: enums::Currency::GBP,
+            connector_transaction_id: "txn_123".to_string(),
+            amount: 1000,
+        })
+    }
+
+    fn get_refund_response(status: &str) -> RefundResponse {
+        serde_json::from_str(&format!(
+            r#"{{
+                "transactionId": "rfd_456",
+                "transactionType": "Refund",
+                "status": "{status}",
+                "statusCode": "0000",
+                "statusDetail": "The Authorisation was Successful."
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_refund_request_references_original_transaction() {
+        let request =
+            OpayoRefundRequest::try_from(&get_refund_router_data::<api::Execute>()).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["transactionType"], "Refund");
+        assert_eq!(value["vendorTxCode"], "ref_123");
+        assert_eq!(value["referenceTransactionId"], "txn_123");
+        assert_eq!(value["amount"], 500);
+    }
+
+    #[test]
+    fn test_unwrap_or_connector_default_replaces_missing_and_empty_values() {
+        let default = || "Refund".to_string();
+        assert_eq!(
+            unwrap_or_connector_default(Some("Damaged item".to_string()), default),
+            "Damaged item"
+        );
+        assert_eq!(unwrap_or_connector_default(None, default), "Refund");
+        assert_eq!(
+            unwrap_or_connector_default(Some(" ".to_string()), default),
+            "Refund"
+        );
+    }
+
+    #[test]
+    fn test_refund_request_without_reason_uses_default_description() {
+        let mut router_data = get_refund_router_data::<api::Execute>();
+        router_data.request.reason = Some(String::new());
+        let request = OpayoRefundRequest::try_from(&router_data).unwrap();
+        assert_eq!(
+            serde_json::to_value(request).unwrap()["description"],
+            "Refund"
+        );
+    }
+
+    #[test]
+    fn test_refund_execute_response_maps_status() {
+        let router_data =
+            types::RefundsRouterData::<api::Execute>::try_from(types::ResponseRouterData {
+                response: get_refund_response("Ok"),
+                data: get_refund_router_data(),
+                http_code: 201,
+            })
+            .unwrap();
+        let response = router_data.response.unwrap();
+        assert_eq!(response.connector_refund_id, "rfd_456");
+        assert_eq!(response.refund_status, enums::RefundStatus::Success);
+    }
+
+    #[test]
+    fn test_refund_sync_response_maps_status() {
+        let router_data =
+            types::RefundsRouterData::<api::RSync>::try_from(types::ResponseRouterData {
+                response: get_refund_response("Rejected"),
+                data: get_refund_router_data(),
+                http_code: 200,
+            })
+            .unwrap();
+        assert_eq!(
+            router_data.response.unwrap().refund_status,
+            enums::RefundStatus::Failure
+        );
+    }
+
+    #[test]
+    fn test_payments_request_uses_merchant_session_key_and_card_identifier() {
+        let request =
+            OpayoPaymentsRequest::try_from(&get_authorize_router_data(1000, enums::Currency::GBP))
+                .unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(
+            value["paymentMethod"]["card"]["merchantSessionKey"],
+            "merchant_session_key"
+        );
+        assert_eq!(value["paymentMethod"]["card"]["cardIdentifier"], "card_123");
+    }
+
+    #[test]
+    fn test_card_identifier_request_formats_expiry_date() {
+        let request =
+            OpayoCardIdentifierRequest::try_from(&get_pre_processing_router_data()).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["cardDetails"]["cardNumber"], "4929000000006");
+        assert_eq!(value["cardDetails"]["expiryDate"], "0329");
+        assert_eq!(value["cardDetails"]["securityCode"], "123");
+    }
+
+    #[test]
+    fn test_card_identifier_response_is_stored_in_connector_metadata() {
+        let router_data =
+            types::PaymentsPreProcessingRouterData::try_from(types::ResponseRouterData {
+                response: serde_json::from_str::<OpayoCardIdentifierResponse>(
+                    r#"{
+                        "cardIdentifier": "C6F92981-8C2D-457A-AA1E-16EBCD6D3AC6",
+                        "expiry": "2015-06-16T10:46:23.434Z",
+                        "cardType": "Visa"
+                    }"#,
+                )
+                .unwrap(),
+                data: get_pre_processing_router_data(),
+                http_code: 201,
+            })
+            .unwrap();
+        let connector_metadata = match router_data.response.unwrap() {
+            types::PaymentsResponseData::PreProcessingResponse {
+                connector_metadata, ..
+            } => connector_metadata,
+            _ => None,
+        };
+        let metadata = OpayoConnectorMetadata::try_from(connector_metadata.unwrap()).unwrap();
+        assert_eq!(
+            metadata.card_identifier.expose(),
+            "C6F92981-8C2D-457A-AA1E-16EBCD6D3AC6"
+        );
+    }
+
+    #[test]
+    fn test_valid_connector_metadata_is_parsed() {
+        let metadata =
+            OpayoConnectorMetadata::try_from(serde_json::json!({ "card_identifier": "card_123" }))
+                .unwrap();
+        assert_eq!(metadata.card_identifier.expose(), "card_123");
+    }
+
+    #[test]
+    fn test_invalid_connector_metadata_is_rejected() {
+        for metadata in [
+            serde_json::json!({}),
+            serde_json::json!({ "card_identifier": 123 }),
+            serde_json::json!({ "card_identifier": " " }),
+            serde_json::json!("card_123"),
+        ] {
+            let error = OpayoConnectorMetadata::try_from(metadata).unwrap_err();
+            assert_eq!(
+                error.current_context(),
+                &errors::ConnectorError::InvalidConnectorMetadata {
+                    connector: "opayo".to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_payments_request_rejects_invalid_connector_metadata() {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.request.connector_meta = Some(serde_json::json!({ "cardIdentifier": "x" }));
+        let error = OpayoPaymentsRequest::try_from(&router_data).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::InvalidConnectorMetadata { .. }
+        ));
+
+        router_data.request.connector_meta = None;
+        let error = OpayoPaymentsRequest::try_from(&router_data).unwrap_err();
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::MissingRequiredField {
+                field_name: "connector_meta"
+            }
+        );
+    }
+
+    #[test]
+    fn test_card_identifier_request_debug_output_is_masked() {
+        let request =
+            OpayoCardIdentifierRequest::try_from(&get_pre_processing_router_data()).unwrap();
+        utils::assert_debug_output_is_masked(
+            &request,
+            &["4929000000006", "0329", "John Doe", "123"],
+        );
+    }
+
+    #[test]
+    fn test_payments_request_debug_output_is_masked() {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.request.setup_future_usage = Some(enums::FutureUsage::OffSession);
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        utils::assert_debug_output_is_masked(
+            &request,
+            &[
+                "merchant_session_key",
+                "card_123",
+                "John",
+                "Doe",
+                "88 The Road",
+                "412",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_card_identifier_response_debug_output_is_masked() {
+        let response: OpayoCardIdentifierResponse =
+            serde_json::from_str(r#"{"cardIdentifier": "C6F92981-8C2D-457A-AA1E-16EBCD6D3AC6"}"#)
+                .unwrap();
+        utils::assert_debug_output_is_masked(&response, &["C6F92981-8C2D-457A-AA1E-16EBCD6D3AC6"]);
+    }
+
+    #[test]
+    fn test_error_response_surfaces_first_error() {
+        let response = types::Response {
+            response: bytes::Bytes::from(
+                r#"{
+                    "errors": [
+                        {
+                            "description": "Contains invalid characters",
+                            "property": "cardDetails.cardholderName",
+                            "clientMessage": "The card name contains invalid characters",
+                            "code": 1003
+                        },
+                        {
+                            "description": "Missing mandatory field",
+                            "property": "cardDetails.expiryDate",
+                            "code": 1003
+                        }
+                    ]
+                }"#,
+            ),
+            status_code: 422,
+        };
+        let error =
+            api::ConnectorCommon::build_error_response(&crate::connector::Opayo, response).unwrap();
+        assert_eq!(error.status_code, 422);
+        assert_eq!(error.code, "1003");
+        assert_eq!(error.message, "Contains invalid characters");
+        assert_eq!(error.reason, Some("cardDetails.cardholderName".to_string()));
+        assert_eq!(error.decline_type, None);
+    }
+
+    #[test]
+    fn test_error_response_surfaces_bank_decline() {
+        let response = types::Response {
+            response: bytes::Bytes::from(
+                r#"{
+                    "transactionId": "txn_123",
+                    "transactionType": "Payment",
+                    "status": "NotAuthed",
+                    "statusCode": "2000",
+                    "statusDetail": "The Authorisation was Declined by the bank."
+                }"#,
+            ),
+            status_code: 422,
+        };
+        let error =
+            api::ConnectorCommon::build_error_response(&crate::connector::Opayo, response).unwrap();
+        assert_eq!(error.status_code, 422);
+        assert_eq!(error.code, "2000");
+        assert_eq!(error.message, "The Authorisation was Declined by the bank.");
+        assert_eq!(error.reason, Some("card_declined".to_string()));
+        assert_eq!(error.decline_type, Some(types::DeclineType::Soft));
+    }
+
+    fn get_error_decline_type(status: &str, status_code: &str) -> Option<types::DeclineType> {
+        let response = types::Response {
+            response: bytes::Bytes::from(
+                serde_json::json!({
+                    "transactionId": "txn_123",
+                    "transactionType": "Payment",
+                    "status": status,
+                    "statusCode": status_code,
+                    "statusDetail": "The transaction could not be processed."
+                })
+                .to_string(),
+            ),
+            status_code: 422,
+        };
+        api::ConnectorCommon::build_error_response(&crate::connector::Opayo, response)
+            .unwrap()
+            .decline_type
+    }
+
+    #[test]
+    fn test_error_response_of_temporary_failure_is_transient() {
+        for (status, status_code) in [("Error", "2003"), ("NotAuthed", "2002")] {
+            assert_eq!(
+                get_error_decline_type(status, status_code),
+                Some(types::DeclineType::Transient),
+                "{status_code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_response_of_permanent_error_is_not_transient() {
+        assert_eq!(get_error_decline_type("Error", "5036"), None);
+    }
+
+    #[test]
+    fn test_rejected_payments_response_is_returned_as_error() {
+        let response: OpayoPaymentsResponse = serde_json::from_str(
+            r#"{
+                "transactionId": "txn_123",
+                "transactionType": "Payment",
+                "status": "Rejected",
+                "statusCode": "2001",
+                "statusDetail": "The Authorisation was Rejected by the vendor rule-base."
+            }"#,
+        )
+        .unwrap();
+        let router_data = types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_authorize_router_data(1000, enums::Currency::GBP),
+            http_code: 201,
+        })
+        .unwrap();
+        assert_eq!(
+            router_data.status,
+            enums::AttemptStatus::AuthorizationFailed
+        );
+        let error = router_data.response.unwrap_err();
+        assert_eq!(error.code, "2001");
+        assert_eq!(error.decline_type, Some(types::DeclineType::Hard));
+    }
+
+    fn get_failed_payments_response(
+        status: &str,
+        status_code: &str,
+        status_detail: &str,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let response: OpayoPaymentsResponse = serde_json::from_value(serde_json::json!({
+            "transactionId": "txn_123",
+            "transactionType": "Payment",
+            "status": status,
+            "statusCode": status_code,
+            "statusDetail": status_detail
+        }))
+        .unwrap();
+        types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_authorize_router_data(1000, enums::Currency::GBP),
+            http_code: 201,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_declined_card_keeps_decline_reason() {
+        let router_data = get_failed_payments_response(
+            "NotAuthed",
+            "2000",
+            "The Authorisation was Declined by the bank.",
+        );
+        assert_eq!(
+            router_data.status,
+            enums::AttemptStatus::AuthorizationFailed
+        );
+        let error = router_data.response.unwrap_err();
+        assert_eq!(error.code, "2000");
+        assert_eq!(error.message, "The Authorisation was Declined by the bank.");
+        assert_eq!(error.reason, Some("card_declined".to_string()));
+    }
+
+    #[test]
+    fn test_expired_card_keeps_decline_reason() {
+        let router_data = get_failed_payments_response("Invalid", "5013", "The card has expired.");
+        assert_eq!(router_data.status, enums::AttemptStatus::Failure);
+        let error = router_data.response.unwrap_err();
+        assert_eq!(error.code, "5013");
+        assert_eq!(error.message, "The card has expired.");
+        assert_eq!(error.reason, Some("expired_card".to_string()));
+        assert_eq!(error.decline_type, None);
+    }
+
+    #[test]
+    fn test_unknown_status_code_has_no_decline_reason() {
+        let router_data =
+            get_failed_payments_response("Error", "5036", "Transaction operation not allowed.");
+        assert_eq!(router_data.status, enums::AttemptStatus::Failure);
+        assert_eq!(router_data.response.unwrap_err().reason, None);
+    }
+
+    #[test]
+    fn test_three_ds_challenge_response_redirects_to_acs() {
+        let response: OpayoPaymentsResponse = serde_json::from_str(
+            r#"{
+                "statusCode": "2021",
+                "statusDetail": "Please redirect your customer to the ACSURL to complete the 3DS Transaction",
+                "transactionId": "2B97808F-9A36-6E71-F87F-6714667E7AF4",
+                "acsUrl": "https://test-acs.example.com/challenge",
+                "acsTransId": "0a2c4f39-8e6b-4a5a-9f1d-4d6f7b0e2c11",
+                "dsTransId": "9c1f3e2a-5b7d-4e8f-a6c3-2d1b0f9e8a77",
+                "status": "3DAuth",
+                "cReq": "eyJtZXNzYWdlVHlwZSI6IkNSZXEifQ"
+            }"#,
+        )
+        .unwrap();
+        let router_data = types::PaymentsSyncRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_sync_router_data(None),
+            http_code: 202,
+        })
+        .unwrap();
+        assert_eq!(
+            router_data.status,
+            enums::AttemptStatus::AuthenticationPending
+        );
+        let (redirection_data, redirect) = match router_data.response.unwrap() {
+            types::PaymentsResponseData::TransactionResponse {
+                redirection_data,
+                redirect,
+                ..
+            } => (redirection_data, redirect),
+            _ => (None, false),
+        };
+        let redirection_data = redirection_data.unwrap();
+        assert!(redirect);
+        assert_eq!(
+            redirection_data.url,
+            "https://test-acs.example.com/challenge"
+        );
+        assert_eq!(
+            redirection_data.form_fields.get("creq"),
+            Some(&"eyJtZXNzYWdlVHlwZSI6IkNSZXEifQ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_three_ds_challenge_request_from_redirect_response() {
+        let request = OpayoThreeDSChallengeRequest::try_from(&get_sync_router_data(Some(
+            "cres=eyJ0cmFuc1N0YXR1cyI6IlkifQ&threeDSSessionData=2B97808F".to_string(),
+        )))
+        .unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["cRes"], "eyJ0cmFuc1N0YXR1cyI6IlkifQ");
+        assert_eq!(value["threeDSSessionData"], "2B97808F");
+    }
+
+    fn get_authorize_router_data_with_capture_method(
+        capture_method: Option<enums::CaptureMethod>,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.request.capture_method = capture_method;
+        router_data
+    }
+
+    fn get_payments_response(transaction_type: &str) -> OpayoPaymentsResponse {
+        serde_json::from_str(&format!(
+            r#"{{
+                "transactionId": "txn_123",
+                "transactionType": "{transaction_type}",
+                "status": "Ok",
+                "statusCode": "0000",
+                "statusDetail": "The Authorisation was Successful."
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_automatic_capture_charges_payment() {
+        let router_data =
+            get_authorize_router_data_with_capture_method(Some(enums::CaptureMethod::Automatic));
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        assert_eq!(request.transaction_type, OpayoTransactionType::Payment);
+
+        let router_data = types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+            response: get_payments_response("Payment"),
+            data: router_data,
+            http_code: 201,
+        })
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Charged);
+    }
+
+    #[test]
+    fn test_manual_capture_only_authorizes_payment() {
+        let router_data =
+            get_authorize_router_data_with_capture_method(Some(enums::CaptureMethod::Manual));
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        assert_eq!(request.transaction_type, OpayoTransactionType::Deferred);
+
+        let router_data = types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+            response: get_payments_response("Deferred"),
+            data: router_data,
+            http_code: 201,
+        })
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Authorized);
+    }
+
+    #[test]
+    fn test_multiple_captures_are_rejected() {
+        let router_data = get_authorize_router_data_with_capture_method(Some(
+            enums::CaptureMethod::ManualMultiple,
+        ));
+        assert!(OpayoPaymentsRequest::try_from(&router_data).is_err());
+    }
+
+    #[test]
+    fn test_payments_request_uses_iso_currency_code() {
+        let request =
+            OpayoPaymentsRequest::try_from(&get_authorize_router_data(1000, enums::Currency::USD))
+                .unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["currency"], "USD");
+        assert_eq!(value["amount"], 1000);
+    }
+
+    #[test]
+    fn test_payments_request_for_zero_decimal_currency() {
+        // Amounts are already in the smallest currency unit, which for JPY is the yen itself
+        let request =
+            OpayoPaymentsRequest::try_from(&get_authorize_router_data(1000, enums::Currency::JPY))
+                .unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["currency"], "JPY");
+        assert_eq!(value["amount"], 1000);
+    }
+
+    #[test]
+    fn test_payments_request_rejects_unsupported_currency() {
+        let error =
+            OpayoPaymentsRequest::try_from(&get_authorize_router_data(1000, enums::Currency::INR))
+                .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::CurrencyNotSupported { currency, connector }
+                if currency == "INR" && connector == "opayo"
+        ));
+    }
+
+    #[test]
+    fn test_off_session_mandate_payment_reuses_stored_card_identifier() {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.auth_type = enums::AuthenticationType::ThreeDs;
+        router_data.request.connector_meta = None;
+        router_data.request.off_session = Some(true);
+        router_data.request.mandate_id = Some(api_models::payments::MandateIds {
+            mandate_id: "man_123".to_string(),
+            connector_mandate_id: Some("stored_card_123".to_string()),
+        });
+
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(
+            value["paymentMethod"]["card"],
+            serde_json::json!({
+                "merchantSessionKey": "merchant_session_key",
+                "cardIdentifier": "stored_card_123",
+                "reusable": true,
+                "save": false
+            })
+        );
+        assert_eq!(
+            value["credentialType"],
+            serde_json::json!({
+                "cofUsage": "Subsequent",
+                "initiatedType": "MIT",
+                "mitType": "Unscheduled",
+                "recurringExpiry": null,
+                "recurringFrequency": null
+            })
+        );
+        assert!(value["strongCustomerAuthentication"].is_null());
+    }
+
+    #[test]
+    fn test_first_recurring_payment_stores_card_for_merchant_initiated_use() {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.request.setup_mandate_details = Some(api::MandateData {
+            customer_acceptance: api::CustomerAcceptance::default(),
+            mandate_type: api::MandateType::MultiUse(Some(
+                api_models::payments::MandateAmountData {
+                    amount: 1000,
+                    currency: api_models::enums::Currency::GBP,
+                    end_date: Some(time::macros::datetime!(2025-06-30 23:59:59)),
+                    frequency_in_days: Some(30),
+                },
+            )),
+        });
+
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["paymentMethod"]["card"]["cardIdentifier"], "card_123");
+        assert_eq!(value["paymentMethod"]["card"]["save"], true);
+        assert_eq!(value["paymentMethod"]["card"]["reusable"], false);
+        assert_eq!(
+            value["credentialType"],
+            serde_json::json!({
+                "cofUsage": "First",
+                "initiatedType": "CIT",
+                "mitType": "Recurring",
+                "recurringExpiry": "20250630",
+                "recurringFrequency": "30"
+            })
+        );
+    }
+
+    #[test]
+    fn test_first_payment_without_schedule_is_unscheduled() {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.request.setup_future_usage = Some(enums::FutureUsage::OffSession);
+
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["credentialType"]["cofUsage"], "First");
+        assert_eq!(value["credentialType"]["initiatedType"], "CIT");
+        assert_eq!(value["credentialType"]["mitType"], "Unscheduled");
+        assert!(value["credentialType"]["recurringFrequency"].is_null());
+    }
+
+    #[test]
+    fn test_on_session_payment_has_no_credential_type() {
+        let request =
+            OpayoPaymentsRequest::try_from(&get_authorize_router_data(1000, enums::Currency::GBP))
+                .unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert!(value["credentialType"].is_null());
+        assert_eq!(value["paymentMethod"]["card"]["save"], false);
+    }
+
+    #[test]
+    fn test_payments_response_returns_reusable_card_as_mandate_reference() {
+        let response: OpayoPaymentsResponse = serde_json::from_str(
+            r#"{
+                "transactionId": "txn_123",
+                "transactionType": "Payment",
+                "status": "Ok",
+                "statusCode": "0000",
+                "statusDetail": "The Authorisation was Successful.",
+                "paymentMethod": {
+                    "card": {
+                        "cardIdentifier": "card_123",
+                        "reusable": true
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let router_data = types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_authorize_router_data(1000, enums::Currency::GBP),
+            http_code: 201,
+        })
+        .unwrap();
+        let mandate_reference = match router_data.response.unwrap() {
+            types::PaymentsResponseData::TransactionResponse {
+                mandate_reference, ..
+            } => mandate_reference,
+            _ => None,
+        };
+        assert_eq!(mandate_reference, Some("card_123".to_string()));
+    }
+
+    #[test]
+    fn test_off_session_mandate_payment_requires_connector_mandate_id() {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.request.off_session = Some(true);
+        router_data.request.mandate_id =
+            Some(api_models::payments::MandateIds::new("man_123".to_string()));
+
+        let error = OpayoPaymentsRequest::try_from(&router_data).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::MissingRequiredField {
+                field_name: "mandate_id.connector_mandate_id"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_challenge_window_size_boundaries() {
+        let cases = [
+            (249, 800, "05"),
+            (250, 800, "01"),
+            (389, 800, "01"),
+            (390, 800, "02"),
+            (499, 800, "02"),
+            (500, 800, "03"),
+            (599, 800, "03"),
+            (600, 800, "03"),
+            (1920, 1080, "03"),
+            (600, 599, "04"),
+            (1920, 599, "04"),
+        ];
+        for (screen_width, screen_height, expected) in cases {
+            let window_size = get_challenge_window_size(screen_width, screen_height);
+            assert_eq!(
+                serde_json::to_value(window_size).unwrap(),
+                expected,
+                "screen {screen_width}x{screen_height}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_challenge_window_size_considers_screen_height() {
+        // A 500 x 600 window does not fit a screen shorter than 600 pixels
+        assert_eq!(
+            get_challenge_window_size(550, 599),
+            OpayoChallengeWindowSize::Medium
+        );
+        assert_eq!(
+            get_challenge_window_size(1920, 399),
+            OpayoChallengeWindowSize::FullScreen
+        );
+    }
+
+    #[test]
+    fn test_challenge_window_size_prefers_the_largest_area() {
+        // Both the 500 x 600 and the 600 x 400 windows fit, the first one is larger
+        assert_eq!(
+            get_challenge_window_size(600, 600),
+            OpayoChallengeWindowSize::Large
+        );
+    }
+
+    fn get_browser_authorize_router_data(
+        auth_type: enums::AuthenticationType,
+        sca_exemption: Option<api_models::enums::ScaExemptionType>,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let mut router_data = get_authorize_router_data(1000, enums::Currency::GBP);
+        router_data.auth_type = auth_type;
+        router_data.router_return_url = Some("https://router.test/return".to_string());
+        router_data.request.sca_exemption = sca_exemption;
+        router_data.request.browser_info = Some(types::BrowserInformation {
+            color_depth: 24,
+            java_enabled: false,
+            java_script_enabled: true,
+            language: "en-GB".to_string(),
+            screen_height: 1080,
+            screen_width: 1920,
+            time_zone: 0,
+            ip_address: None,
+            accept_header: "text/html".to_string(),
+            user_agent: "Mozilla/5.0".to_string(),
+        });
+        router_data
+    }
+
+    #[test]
+    fn test_three_ds_payment_forces_authentication() {
+        let router_data = get_browser_authorize_router_data(
+            enums::AuthenticationType::ThreeDs,
+            Some(api_models::enums::ScaExemptionType::LowValue),
+        );
+
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["apply3DSecure"], "Force");
+        assert_eq!(
+            value["strongCustomerAuthentication"]["notificationURL"],
+            "https://router.test/return"
+        );
+        assert!(value["strongCustomerAuthentication"]["threeDSExemptionIndicator"].is_null());
+    }
+
+    #[test]
+    fn test_three_ds_payment_without_browser_info_is_rejected() {
+        let mut router_data =
+            get_browser_authorize_router_data(enums::AuthenticationType::ThreeDs, None);
+        router_data.request.browser_info = None;
+
+        let error = OpayoPaymentsRequest::try_from(&router_data).unwrap_err();
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::MissingRequiredField {
+                field_name: "browser_info"
+            }
+        );
+    }
+
+    #[test]
+    fn test_no_three_ds_payment_disables_authentication() {
+        let router_data =
+            get_browser_authorize_router_data(enums::AuthenticationType::NoThreeDs, None);
+
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["apply3DSecure"], "Disable");
+        assert!(value["strongCustomerAuthentication"].is_null());
+    }
+
+    #[test]
+    fn test_sca_exemption_is_requested_from_issuer() {
+        let router_data = get_browser_authorize_router_data(
+            enums::AuthenticationType::NoThreeDs,
+            Some(api_models::enums::ScaExemptionType::TransactionRiskAnalysis),
+        );
+
+        let request = OpayoPaymentsRequest::try_from(&router_data).unwrap();
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["apply3DSecure"], "UseMSPSetting");
+        assert_eq!(
+            value["strongCustomerAuthentication"]["threeDSExemptionIndicator"],
+            "TransactionRiskAnalysis"
+        );
+    }
+
+    #[test]
+    fn test_capture_request_releases_requested_amount() {
+        let full_capture = OpayoCaptureRequest::try_from(&get_capture_router_data(None)).unwrap();
+        let partial_capture =
+            OpayoCaptureRequest::try_from(&get_capture_router_data(Some(400))).unwrap();
+        let value = serde_json::to_value(full_capture).unwrap();
+        assert_eq!(value["instructionType"], "release");
+        assert_eq!(value["amount"], 1000);
+        assert_eq!(
+            serde_json::to_value(partial_capture).unwrap()["amount"],
+            400
+        );
+    }
+
+    #[test]
+    fn test_capture_response_marks_payment_charged() {
+        let router_data = types::PaymentsCaptureRouterData::try_from(types::ResponseRouterData {
+            response: serde_json::from_str::<OpayoInstructionResponse>(
+                r#"{"instructionType": "release", "date": "2023-01-20T10:15:30.000+00:00"}"#,
+            )
+            .unwrap(),
+            data: get_capture_router_data(None),
+            http_code: 201,
+        })
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Charged);
+    }
+
+    #[test]
+    fn test_void_response_marks_payment_voided() {
+        let data = get_router_data(types::PaymentsCancelData {
+            connector_transaction_id: "txn_123".to_string(),
+            cancellation_reason: Some("requested_by_customer".to_string()),
+        });
+        let request = OpayoVoidRequest::try_from(&data).unwrap();
+        assert_eq!(
+            serde_json::to_value(request).unwrap()["instructionType"],
+            "abort"
+        );
+        let router_data = types::PaymentsCancelRouterData::try_from(types::ResponseRouterData {
+            response: serde_json::from_str::<OpayoInstructionResponse>(
+                r#"{"instructionType": "abort", "date": "2023-01-20T10:15:30.000+00:00"}"#,
+            )
+            .unwrap(),
+            data,
+            http_code: 201,
+        })
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Voided);
+    }
+
+    #[test]
+    fn test_sync_response_moves_pending_payment_to_charged() {
+        let mut data = get_sync_router_data(None);
+        data.status = enums::AttemptStatus::Pending;
+        let router_data = types::PaymentsSyncRouterData::try_from(types::ResponseRouterData {
+            response: serde_json::from_str::<OpayoPaymentsResponse>(
+                r#"{
+                    "transactionId": "txn_123",
+                    "transactionType": "Payment",
+                    "status": "Ok",
+                    "statusCode": "0000",
+                    "statusDetail": "The Authorisation was Successful."
+                }"#,
+            )
+            .unwrap(),
+            data,
+            http_code: 200,
+        })
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Charged);
+    }
+
+    #[test]
+    fn test_payments_response_captures_retrieval_reference() {
+        let response: OpayoPaymentsResponse = serde_json::from_str(
+            r#"{
+                "transactionId": "txn_123",
+                "transactionType": "Payment",
+                "status": "Ok",
+                "statusCode": "0000",
+                "statusDetail": "The Authorisation was Successful.",
+                "retrievalReference": 8636128
+            }"#,
+        )
+        .unwrap();
+        let router_data = types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_authorize_router_data(1000, enums::Currency::GBP),
+            http_code: 201,
+        })
+        .unwrap();
+        let connector_response_reference_id = match router_data.response.unwrap() {
+            types::PaymentsResponseData::TransactionResponse {
+                connector_response_reference_id,
+                ..
+            } => connector_response_reference_id,
+            _ => None,
+        };
+        assert_eq!(connector_response_reference_id, Some("8636128".to_string()));
+    }
+}
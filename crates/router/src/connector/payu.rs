@@ -90,6 +90,7 @@ impl ConnectorCommon for Payu {
             code: response.status.status_code,
             message: response.status.status_desc,
             reason: response.status.code_literal,
+            decline_type: None,
         })
     }
 }
@@ -265,6 +266,7 @@ impl ConnectorIntegration<api::AccessTokenAuth, types::AccessTokenRequestData, t
             code: response.error,
             message: response.error_description,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -437,6 +439,17 @@ impl ConnectorIntegration<api::Session, types::PaymentsSessionData, types::Payme
     //TODO: implement sessions flow
 }
 
+impl api::PaymentsPreProcessing for Payu {}
+
+impl
+    ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Payu
+{
+}
+
 impl api::PaymentAuthorize for Payu {}
 
 impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::PaymentsResponseData>
@@ -680,6 +693,18 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::SubmitEvidence for Payu {}
+
+impl
+    ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Payu
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Payu {
     fn get_webhook_object_reference_id(
@@ -13,11 +13,13 @@ use crate::{
     connector::utils as conn_utils,
     consts,
     core::{
+        connector_credentials,
         errors::{self, CustomResult},
         payments,
     },
-    db::StorageInterface,
-    headers, services,
+    headers,
+    routes::AppState,
+    services,
     types::{
         self,
         api::{self, ConnectorCommon},
@@ -51,6 +53,23 @@ impl Rapyd {
         let signature_value = consts::BASE64_ENGINE_URL_SAFE.encode(hmac_sign);
         Ok(signature_value)
     }
+
+    /// Request body in the canonical form it is signed and sent in.
+    fn get_signed_request_body<'a, T, R>(
+        &self,
+        req: &'a R,
+    ) -> CustomResult<String, errors::ConnectorError>
+    where
+        T: TryFrom<&'a R, Error = error_stack::Report<errors::ConnectorError>>
+            + serde::Serialize
+            + Debug,
+    {
+        let request = T::try_from(req)?;
+        let body = utils::Encode::<T>::encode_to_value(&request)
+            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        self.get_canonical_request_body(&body)
+            .change_context(errors::ConnectorError::RequestEncodingFailed)
+    }
 }
 
 impl ConnectorCommon for Rapyd {
@@ -86,6 +105,7 @@ impl ConnectorCommon for Rapyd {
             code: response.status.error_code,
             message: response.status.status.unwrap_or_default(),
             reason: response.status.message,
+            decline_type: None,
         })
     }
 }
@@ -101,6 +121,17 @@ impl
 {
 }
 
+impl api::PaymentsPreProcessing for Rapyd {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Rapyd
+{
+}
+
 impl api::PaymentAuthorize for Rapyd {}
 
 impl
@@ -145,8 +176,7 @@ impl
         let timestamp = date_time::now_unix_timestamp();
         let salt = Alphanumeric.sample_string(&mut rand::thread_rng(), 12);
 
-        let rapyd_req = utils::Encode::<rapyd::RapydPaymentsRequest>::convert_and_encode(req)
-            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        let rapyd_req = self.get_signed_request_body::<rapyd::RapydPaymentsRequest, _>(req)?;
 
         let auth: rapyd::RapydAuthType = rapyd::RapydAuthType::try_from(&req.connector_auth_type)?;
         let signature =
@@ -441,8 +471,7 @@ impl
         let timestamp = date_time::now_unix_timestamp();
         let salt = Alphanumeric.sample_string(&mut rand::thread_rng(), 12);
 
-        let rapyd_req = utils::Encode::<rapyd::CaptureRequest>::convert_and_encode(req)
-            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        let rapyd_req = self.get_signed_request_body::<rapyd::CaptureRequest, _>(req)?;
 
         let auth: rapyd::RapydAuthType = rapyd::RapydAuthType::try_from(&req.connector_auth_type)?;
         let url_path = format!(
@@ -566,8 +595,7 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
         let timestamp = date_time::now_unix_timestamp();
         let salt = Alphanumeric.sample_string(&mut rand::thread_rng(), 12);
 
-        let rapyd_req = utils::Encode::<rapyd::RapydRefundRequest>::convert_and_encode(req)
-            .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        let rapyd_req = self.get_signed_request_body::<rapyd::RapydRefundRequest, _>(req)?;
 
         let auth: rapyd::RapydAuthType = rapyd::RapydAuthType::try_from(&req.connector_auth_type)?;
         let signature =
@@ -662,14 +690,22 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
     }
 }
 
+impl api::SubmitEvidence for Rapyd {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Rapyd
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Rapyd {
-    fn get_webhook_source_verification_algorithm(
-        &self,
-        _headers: &actix_web::http::header::HeaderMap,
-        _body: &[u8],
-    ) -> CustomResult<Box<dyn crypto::VerifySignature + Send>, errors::ConnectorError> {
-        Ok(Box::new(crypto::HmacSha256))
+    fn get_webhook_signature_scheme(&self) -> crypto::WebhookSignatureScheme {
+        crypto::WebhookSignatureScheme::HmacSha256
     }
 
     fn get_webhook_source_verification_signature(
@@ -687,16 +723,16 @@ impl api::IncomingWebhook for Rapyd {
 
     async fn get_webhook_source_verification_merchant_secret(
         &self,
-        db: &dyn StorageInterface,
+        state: &AppState,
         merchant_id: &str,
     ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
-        let key = format!("wh_mer_sec_verification_{}_{}", self.id(), merchant_id);
-        let secret = db
-            .get_key(&key)
-            .await
-            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)?;
-
-        Ok(secret)
+        connector_credentials::find_webhook_source_verification_secret(
+            state,
+            merchant_id,
+            self.id(),
+            &format!("wh_mer_sec_verification_{}_{}", self.id(), merchant_id),
+        )
+        .await
     }
 
     fn get_webhook_source_verification_message(
@@ -731,7 +767,7 @@ impl api::IncomingWebhook for Rapyd {
 
     async fn verify_webhook_source(
         &self,
-        db: &dyn StorageInterface,
+        state: &AppState,
         headers: &actix_web::http::header::HeaderMap,
         body: &[u8],
         merchant_id: &str,
@@ -740,7 +776,7 @@ impl api::IncomingWebhook for Rapyd {
             .get_webhook_source_verification_signature(headers, body)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
         let secret = self
-            .get_webhook_source_verification_merchant_secret(db, merchant_id)
+            .get_webhook_source_verification_merchant_secret(state, merchant_id)
             .await
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
         let message = self
@@ -814,3 +850,60 @@ impl services::ConnectorRedirectResponse for Rapyd {
         Ok(payments::CallConnectorAction::Trigger)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{connector::utils::get_test_router_data, types::storage::enums};
+
+    #[test]
+    fn test_signature_of_refund_request() {
+        let mut router_data: types::RefundsRouterData<api::Execute> = get_test_router_data(
+            "rapyd",
+            types::RefundsData {
+                refund_id: "ref_123".to_string(),
+                connector_transaction_id: "payment_9f1c2b".to_string(),
+                connector_refund_id: None,
+                currency: enums::Currency::USD,
+                amount: 1000,
+                reason: None,
+                refund_amount: 1000,
+                connector_metadata: None,
+            },
+            Err(ErrorResponse::default()),
+        );
+        router_data.connector_auth_type = types::ConnectorAuthType::BodyKey {
+            api_key: "rak_access".to_string(),
+            key1: "rsk_secret".to_string(),
+        };
+        let auth = rapyd::RapydAuthType::try_from(&router_data.connector_auth_type).unwrap();
+
+        let body = Rapyd
+            .get_signed_request_body::<rapyd::RapydRefundRequest, _>(&router_data)
+            .unwrap();
+        let signature = Rapyd
+            .generate_signature(
+                &auth,
+                "post",
+                "/v1/refunds",
+                &body,
+                &1_681_000_000,
+                "a1b2c3d4e5f6",
+            )
+            .unwrap();
+
+        assert_eq!(
+            body,
+            r#"{"amount":1000,"currency":"USD","payment":"payment_9f1c2b"}"#
+        );
+        // HMAC-SHA256 with the secret key of
+        // post/v1/refundsa1b2c3d4e5f61681000000rak_accessrsk_secret{"amount":1000,"currency":"USD","payment":"payment_9f1c2b"}
+        // hex encoded, then base64 encoded
+        assert_eq!(
+            signature,
+            "MTQwZjkxZmUwMTlmYmY4YmFmNWU2OTRhN2ZiNGY4ZTdhMTg0ZmJkNTJlZDBmNjU2NmJiNDQzZGYzYmZmYzdlZA=="
+        );
+    }
+}
@@ -386,6 +386,7 @@ impl<F, T>
                             status_code: item.http_code,
                             message: item.response.status.status.unwrap_or_default(),
                             reason: data.failure_message.to_owned(),
+                            decline_type: None,
                         }),
                     ),
                     _ => {
@@ -418,6 +419,9 @@ impl<F, T>
                                 redirection_data,
                                 mandate_reference: None,
                                 connector_metadata: None,
+                                avs_result: None,
+                                cvv_result: None,
+                                connector_response_reference_id: None,
                             }),
                         )
                     }
@@ -430,6 +434,7 @@ impl<F, T>
                     status_code: item.http_code,
                     message: item.response.status.status.unwrap_or_default(),
                     reason: item.response.status.message,
+                    decline_type: None,
                 }),
             ),
         };
@@ -91,6 +91,7 @@ impl ConnectorCommon for Shift4 {
                 .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
             message: response.error.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -271,6 +272,17 @@ impl ConnectorIntegration<api::Session, types::PaymentsSessionData, types::Payme
     //TODO: implement sessions flow
 }
 
+impl api::PaymentsPreProcessing for Shift4 {}
+
+impl
+    ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Shift4
+{
+}
+
 impl api::PaymentAuthorize for Shift4 {}
 
 impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::PaymentsResponseData>
@@ -495,6 +507,18 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::SubmitEvidence for Shift4 {}
+
+impl
+    ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Shift4
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Shift4 {
     fn get_webhook_object_reference_id(
@@ -11,11 +11,13 @@ use crate::{
     configs::settings,
     consts,
     core::{
+        connector_credentials,
         errors::{self, CustomResult},
         payments,
     },
-    db::StorageInterface,
-    headers, logger, services,
+    headers, logger,
+    routes::AppState,
+    services,
     types::{
         self,
         api::{self, ConnectorCommon},
@@ -56,6 +58,17 @@ impl ConnectorCommon for Stripe {
 
 impl api::Payment for Stripe {}
 
+impl api::PaymentsPreProcessing for Stripe {}
+
+impl
+    services::ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Stripe
+{
+}
+
 impl api::PaymentAuthorize for Stripe {}
 impl api::PaymentSync for Stripe {}
 impl api::PaymentVoid for Stripe {}
@@ -155,6 +168,14 @@ impl
         ))
     }
 
+    fn get_idempotency_key_header(&self) -> Option<&'static str> {
+        Some(headers::IDEMPOTENCY_KEY)
+    }
+
+    fn get_idempotency_key(&self, req: &types::PaymentsCaptureRouterData) -> String {
+        req.get_capture_idempotency_key()
+    }
+
     fn handle_response(
         &self,
         data: &types::PaymentsCaptureRouterData,
@@ -185,6 +206,7 @@ impl
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
 
+        let decline_type = response.error.get_decline_type();
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -196,6 +218,7 @@ impl
                 .message
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type,
         })
     }
 }
@@ -286,6 +309,7 @@ impl
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
 
+        let decline_type = response.error.get_decline_type();
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -297,6 +321,7 @@ impl
                 .message
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type,
         })
     }
 }
@@ -371,6 +396,10 @@ impl
         ))
     }
 
+    fn get_idempotency_key_header(&self) -> Option<&'static str> {
+        Some(headers::IDEMPOTENCY_KEY)
+    }
+
     fn handle_response(
         &self,
         data: &types::PaymentsAuthorizeRouterData,
@@ -398,6 +427,7 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        let decline_type = response.error.get_decline_type();
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -409,6 +439,7 @@ impl
                 .message
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type,
         })
     }
 }
@@ -479,6 +510,14 @@ impl
         Ok(Some(request))
     }
 
+    fn get_idempotency_key_header(&self) -> Option<&'static str> {
+        Some(headers::IDEMPOTENCY_KEY)
+    }
+
+    fn get_idempotency_key(&self, req: &types::PaymentsCancelRouterData) -> String {
+        req.get_void_idempotency_key()
+    }
+
     fn handle_response(
         &self,
         data: &types::PaymentsCancelRouterData,
@@ -505,6 +544,7 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        let decline_type = response.error.get_decline_type();
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -516,6 +556,7 @@ impl
                 .message
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type,
         })
     }
 }
@@ -632,6 +673,7 @@ impl
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        let decline_type = response.error.get_decline_type();
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -643,6 +685,7 @@ impl
                 .message
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type,
         })
     }
 }
@@ -708,6 +751,14 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
         Ok(Some(request))
     }
 
+    fn get_idempotency_key_header(&self) -> Option<&'static str> {
+        Some(headers::IDEMPOTENCY_KEY)
+    }
+
+    fn get_idempotency_key(&self, req: &types::RefundsRouterData<api::Execute>) -> String {
+        req.get_refund_idempotency_key()
+    }
+
     #[instrument(skip_all)]
     fn handle_response(
         &self,
@@ -736,6 +787,7 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        let decline_type = response.error.get_decline_type();
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -747,6 +799,7 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
                 .message
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type,
         })
     }
 }
@@ -831,6 +884,7 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
             .response
             .parse_struct("ErrorResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        let decline_type = response.error.get_decline_type();
         Ok(types::ErrorResponse {
             status_code: res.status_code,
             code: response
@@ -842,6 +896,7 @@ impl services::ConnectorIntegration<api::RSync, types::RefundsData, types::Refun
                 .message
                 .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
             reason: None,
+            decline_type,
         })
     }
 }
@@ -876,14 +931,120 @@ fn get_signature_elements_from_header(
     Ok(security_header_kvs)
 }
 
+impl api::SubmitEvidence for Stripe {}
+
+impl
+    services::ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Stripe
+{
+    fn get_headers(
+        &self,
+        req: &types::SubmitEvidenceRouterData,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, String)>, errors::ConnectorError> {
+        let mut header = vec![(
+            headers::CONTENT_TYPE.to_string(),
+            types::SubmitEvidenceType::get_content_type(self).to_string(),
+        )];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        Ok(header)
+    }
+
+    fn get_content_type(&self) -> &'static str {
+        "application/x-www-form-urlencoded"
+    }
+
+    fn get_url(
+        &self,
+        req: &types::SubmitEvidenceRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        Ok(format!(
+            "{}v1/disputes/{}",
+            self.base_url(connectors),
+            req.request.connector_dispute_id
+        ))
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::SubmitEvidenceRouterData,
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        let stripe_req =
+            utils::Encode::<stripe::StripeSubmitEvidenceRequest>::convert_and_url_encode(req)
+                .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(stripe_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::SubmitEvidenceRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Post)
+                .url(&types::SubmitEvidenceType::get_url(self, req, connectors)?)
+                .headers(types::SubmitEvidenceType::get_headers(
+                    self, req, connectors,
+                )?)
+                .body(types::SubmitEvidenceType::get_request_body(self, req)?)
+                .build(),
+        ))
+    }
+
+    #[instrument(skip_all)]
+    fn handle_response(
+        &self,
+        data: &types::SubmitEvidenceRouterData,
+        res: types::Response,
+    ) -> CustomResult<types::SubmitEvidenceRouterData, errors::ConnectorError> {
+        logger::debug!(response=?res);
+
+        let response: stripe::StripeDisputeObject = res
+            .response
+            .parse_struct("StripeDisputeObject")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        let response: stripe::ErrorResponse = res
+            .response
+            .parse_struct("ErrorResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        Ok(types::ErrorResponse {
+            status_code: res.status_code,
+            code: response
+                .error
+                .code
+                .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+            message: response
+                .error
+                .message
+                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+            reason: None,
+            decline_type: None,
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Stripe {
-    fn get_webhook_source_verification_algorithm(
-        &self,
-        _headers: &actix_web::http::header::HeaderMap,
-        _body: &[u8],
-    ) -> CustomResult<Box<dyn crypto::VerifySignature + Send>, errors::ConnectorError> {
-        Ok(Box::new(crypto::HmacSha256))
+    fn get_webhook_signature_scheme(&self) -> crypto::WebhookSignatureScheme {
+        crypto::WebhookSignatureScheme::HmacSha256
     }
 
     fn get_webhook_source_verification_signature(
@@ -927,16 +1088,16 @@ impl api::IncomingWebhook for Stripe {
 
     async fn get_webhook_source_verification_merchant_secret(
         &self,
-        db: &dyn StorageInterface,
+        state: &AppState,
         merchant_id: &str,
     ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
-        let key = format!("whsec_verification_{}_{}", self.id(), merchant_id);
-        let secret = db
-            .get_key(&key)
-            .await
-            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)?;
-
-        Ok(secret)
+        connector_credentials::find_webhook_source_verification_secret(
+            state,
+            merchant_id,
+            self.id(),
+            &format!("whsec_verification_{}_{}", self.id(), merchant_id),
+        )
+        .await
     }
 
     fn get_webhook_object_reference_id(
@@ -950,6 +1111,17 @@ impl api::IncomingWebhook for Stripe {
         Ok(details.data.object.id)
     }
 
+    fn get_webhook_event_id(
+        &self,
+        body: &[u8],
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        let details: stripe::StripeWebhookObjectEventId = body
+            .parse_struct("StripeWebhookObjectEventId")
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+
+        Ok(Some(details.id))
+    }
+
     fn get_webhook_event_type(
         &self,
         body: &[u8],
@@ -961,10 +1133,33 @@ impl api::IncomingWebhook for Stripe {
         Ok(match details.event_type.as_str() {
             "payment_intent.payment_failed" => api::IncomingWebhookEvent::PaymentIntentFailure,
             "payment_intent.succeeded" => api::IncomingWebhookEvent::PaymentIntentSuccess,
+            "charge.refunded" => api::IncomingWebhookEvent::RefundSuccess,
+            "charge.dispute.created" => api::IncomingWebhookEvent::DisputeOpened,
+            "charge.dispute.closed" => {
+                let details: stripe::StripeWebhookObjectDispute = body
+                    .parse_struct("StripeWebhookObjectDispute")
+                    .change_context(errors::ConnectorError::WebhookEventTypeNotFound)?;
+                match details.data.object.status {
+                    stripe::StripeDisputeStatus::Won => api::IncomingWebhookEvent::DisputeWon,
+                    stripe::StripeDisputeStatus::Lost => api::IncomingWebhookEvent::DisputeLost,
+                    _ => Err(errors::ConnectorError::WebhookEventTypeNotFound).into_report()?,
+                }
+            }
             _ => Err(errors::ConnectorError::WebhookEventTypeNotFound).into_report()?,
         })
     }
 
+    fn get_dispute_details(
+        &self,
+        body: &[u8],
+    ) -> CustomResult<api::DisputePayload, errors::ConnectorError> {
+        let details: stripe::StripeWebhookObjectDispute = body
+            .parse_struct("StripeWebhookObjectDispute")
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+
+        Ok(details.data.object.into())
+    }
+
     fn get_webhook_resource_object(
         &self,
         body: &[u8],
@@ -994,3 +1189,113 @@ impl services::ConnectorRedirectResponse for Stripe {
             }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+
+    use super::Stripe;
+    use crate::{
+        core::errors,
+        types::api::{IncomingWebhook, IncomingWebhookEvent},
+    };
+
+    const WEBHOOK_SECRET: &[u8] = b"whsec_test_secret";
+    const WEBHOOK_BODY: &[u8] = br#"{"id":"evt_123","type":"payment_intent.succeeded"}"#;
+
+    fn get_webhook_headers(signature_header: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("stripe-signature"),
+            HeaderValue::from_static(signature_header),
+        );
+        headers
+    }
+
+    fn verify_webhook_signature(headers: &HeaderMap, body: &[u8]) -> bool {
+        let algorithm = Stripe
+            .get_webhook_source_verification_algorithm(headers, body)
+            .unwrap();
+        let signature = Stripe
+            .get_webhook_source_verification_signature(headers, body)
+            .unwrap();
+        let message = Stripe
+            .get_webhook_source_verification_message(headers, body, "merchant_123", WEBHOOK_SECRET)
+            .unwrap();
+        algorithm
+            .verify_signature(WEBHOOK_SECRET, &signature, &message)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_webhook_source_verification_with_known_signature() {
+        let headers = get_webhook_headers(
+            "t=1677000000,v1=565f1aa18ce0b795355e247dae7415c2cc85262a5e5e373b68e23321cc509ea8",
+        );
+        assert!(verify_webhook_signature(&headers, WEBHOOK_BODY));
+        assert!(!verify_webhook_signature(
+            &headers,
+            br#"{"id":"evt_123","type":"payment_intent.payment_failed"}"#
+        ));
+    }
+
+    #[test]
+    fn test_webhook_source_verification_without_signature() {
+        let headers = get_webhook_headers("t=1677000000");
+        let error = Stripe
+            .get_webhook_source_verification_signature(&headers, WEBHOOK_BODY)
+            .unwrap_err();
+        assert_eq!(
+            error.current_context(),
+            &errors::ConnectorError::WebhookSignatureNotFound
+        );
+    }
+
+    #[test]
+    fn test_webhook_event_type_mapping() {
+        let events = [
+            (
+                "payment_intent.succeeded",
+                IncomingWebhookEvent::PaymentIntentSuccess,
+            ),
+            (
+                "payment_intent.payment_failed",
+                IncomingWebhookEvent::PaymentIntentFailure,
+            ),
+            ("charge.refunded", IncomingWebhookEvent::RefundSuccess),
+            (
+                "charge.dispute.created",
+                IncomingWebhookEvent::DisputeOpened,
+            ),
+        ];
+        for (event_type, expected_event) in events {
+            let body = format!(r#"{{"id":"evt_123","type":"{event_type}"}}"#);
+            assert_eq!(
+                Stripe.get_webhook_event_type(body.as_bytes()).unwrap(),
+                expected_event
+            );
+        }
+        assert!(Stripe
+            .get_webhook_event_type(br#"{"id":"evt_123","type":"customer.created"}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_closed_dispute_webhook() {
+        let body = br#"{"id":"evt_123","type":"charge.dispute.closed","data":{"object":{"id":"dp_123","amount":1000,"currency":"usd","payment_intent":"pi_123","reason":"fraudulent","status":"won"}}}"#;
+        assert_eq!(
+            Stripe.get_webhook_event_type(body).unwrap(),
+            IncomingWebhookEvent::DisputeWon
+        );
+
+        let dispute_details = Stripe.get_dispute_details(body).unwrap();
+        assert_eq!(dispute_details.connector_dispute_id, "dp_123");
+        assert_eq!(dispute_details.connector_transaction_id, "pi_123");
+        assert_eq!(
+            dispute_details.connector_reason_code.as_deref(),
+            Some("fraudulent")
+        );
+        assert_eq!(dispute_details.currency, "USD");
+    }
+}
@@ -10,12 +10,18 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::{
+    connector::utils as connector_utils,
     core::errors,
     pii::{self, ExposeOptionInterface, Secret},
     services,
     types::{self, api, storage::enums},
 };
 
+/// Stripe truncates longer statement descriptor suffixes of card payments.
+/// Stripe shows `{prefix}* {suffix}` on the statement, and the whole of it is limited to 22
+/// characters.
+const STATEMENT_DESCRIPTOR_MAX_LENGTH: usize = 22;
+
 pub struct StripeAuthType {
     pub(super) api_key: String,
 }
@@ -136,6 +142,22 @@ pub struct StripePayLaterData {
     pub billing_name: Option<String>,
 }
 
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct StripeBankRedirectData {
+    #[serde(rename = "payment_method_types[]")]
+    pub payment_method_types: StripePaymentMethodType,
+    #[serde(rename = "payment_method_data[type]")]
+    pub payment_method_data_type: StripePaymentMethodType,
+    #[serde(rename = "payment_method_data[billing_details][name]")]
+    pub billing_name: Option<Secret<String>>,
+    #[serde(rename = "payment_method_data[ideal][bank]")]
+    pub ideal_bank_name: Option<api_models::enums::BankNames>,
+    #[serde(rename = "payment_method_data[sofort][country]")]
+    pub sofort_country: Option<String>,
+    #[serde(rename = "payment_method_options[sofort][preferred_language]")]
+    pub sofort_preferred_language: Option<String>,
+}
+
 #[derive(Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum StripePaymentMethodData {
@@ -143,6 +165,7 @@ pub enum StripePaymentMethodData {
     Klarna(StripePayLaterData),
     Affirm(StripePayLaterData),
     AfterpayClearpay(StripePayLaterData),
+    BankRedirect(StripeBankRedirectData),
     Bank,
     Wallet,
     Paypal,
@@ -155,6 +178,9 @@ pub enum StripePaymentMethodType {
     Klarna,
     Affirm,
     AfterpayClearpay,
+    Ideal,
+    Sofort,
+    Giropay,
 }
 
 fn validate_shipping_address_against_payment_method(
@@ -260,7 +286,17 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentIntentRequest {
         Ok(Self {
             amount: item.request.amount, //hopefully we don't loose some cents here
             currency: item.request.currency.to_string(), //we need to copy the value and not transfer ownership
-            statement_descriptor_suffix: item.request.statement_descriptor_suffix.clone(),
+            statement_descriptor_suffix: item
+                .request
+                .statement_descriptor_suffix
+                .as_deref()
+                .and_then(|suffix| {
+                    connector_utils::get_statement_descriptor_suffix(
+                        item.request.statement_descriptor_prefix.as_deref(),
+                        suffix,
+                        STATEMENT_DESCRIPTOR_MAX_LENGTH,
+                    )
+                }),
             metadata_order_id,
             metadata_txn_id,
             metadata_txn_uuid,
@@ -384,22 +420,7 @@ impl<F, T>
     fn try_from(
         item: types::ResponseRouterData<F, PaymentIntentResponse, T, types::PaymentsResponseData>,
     ) -> Result<Self, Self::Error> {
-        let redirection_data = item.response.next_action.as_ref().map(
-            |StripeNextActionResponse::RedirectToUrl(response)| {
-                let mut base_url = response.url.clone();
-                base_url.set_query(None);
-                services::RedirectForm {
-                    url: base_url.to_string(),
-                    method: services::Method::Get,
-                    form_fields: std::collections::HashMap::from_iter(
-                        response
-                            .url
-                            .query_pairs()
-                            .map(|(k, v)| (k.to_string(), v.to_string())),
-                    ),
-                }
-            },
-        );
+        let redirection_data = item.response.next_action.as_ref().map(get_redirection_form);
 
         let mandate_reference =
             item.response
@@ -410,7 +431,10 @@ impl<F, T>
                     } => mandate_options.map(|mandate_options| mandate_options.reference),
                     StripePaymentMethodOptions::Klarna {} => None,
                     StripePaymentMethodOptions::Affirm {} => None,
-                    StripePaymentMethodOptions::AfterpayClearpay {} => None,
+                    StripePaymentMethodOptions::AfterpayClearpay {}
+                    | StripePaymentMethodOptions::Ideal {}
+                    | StripePaymentMethodOptions::Sofort {}
+                    | StripePaymentMethodOptions::Giropay {} => None,
                 });
 
         Ok(Self {
@@ -425,6 +449,9 @@ impl<F, T>
                 redirection_data,
                 mandate_reference,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             amount_captured: Some(item.response.amount_received),
             ..item.data
@@ -440,22 +467,7 @@ impl<F, T>
     fn try_from(
         item: types::ResponseRouterData<F, SetupIntentResponse, T, types::PaymentsResponseData>,
     ) -> Result<Self, Self::Error> {
-        let redirection_data = item.response.next_action.as_ref().map(
-            |StripeNextActionResponse::RedirectToUrl(response)| {
-                let mut base_url = response.url.clone();
-                base_url.set_query(None);
-                services::RedirectForm {
-                    url: base_url.to_string(),
-                    method: services::Method::Get,
-                    form_fields: std::collections::HashMap::from_iter(
-                        response
-                            .url
-                            .query_pairs()
-                            .map(|(k, v)| (k.to_string(), v.to_string())),
-                    ),
-                }
-            },
-        );
+        let redirection_data = item.response.next_action.as_ref().map(get_redirection_form);
 
         let mandate_reference =
             item.response
@@ -466,7 +478,10 @@ impl<F, T>
                     } => mandate_options.map(|mandate_option| mandate_option.reference),
                     StripePaymentMethodOptions::Klarna {} => None,
                     StripePaymentMethodOptions::Affirm {} => None,
-                    StripePaymentMethodOptions::AfterpayClearpay {} => None,
+                    StripePaymentMethodOptions::AfterpayClearpay {}
+                    | StripePaymentMethodOptions::Ideal {}
+                    | StripePaymentMethodOptions::Sofort {}
+                    | StripePaymentMethodOptions::Giropay {} => None,
                 });
 
         Ok(Self {
@@ -477,6 +492,9 @@ impl<F, T>
                 redirection_data,
                 mandate_reference,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..item.data
         })
@@ -515,6 +533,24 @@ pub struct StripeRedirectToUrlResponse {
     url: Url,
 }
 
+/// Builds the form that redirects the customer to the page Stripe asks them to visit, such as
+/// the 3DS challenge or the bank's authorization page.
+fn get_redirection_form(next_action: &StripeNextActionResponse) -> services::RedirectForm {
+    let StripeNextActionResponse::RedirectToUrl(response) = next_action;
+    let mut base_url = response.url.clone();
+    base_url.set_query(None);
+    services::RedirectForm {
+        url: base_url.to_string(),
+        method: services::Method::Get,
+        form_fields: std::collections::HashMap::from_iter(
+            response
+                .url
+                .query_pairs()
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        ),
+    }
+}
+
 // REFUND :
 // Type definition for Stripe RefundRequest
 
@@ -622,6 +658,49 @@ pub struct ErrorDetails {
     pub error_type: Option<String>,
     pub message: Option<String>,
     pub param: Option<String>,
+    /// Reason given by the issuer for a `card_declined` error.
+    pub decline_code: Option<String>,
+}
+
+impl ErrorDetails {
+    /// Classifies a card decline by its decline code, falling back to the error code for card
+    /// errors that are reported without one.
+    pub fn get_decline_type(&self) -> Option<types::DeclineType> {
+        let decline_code = self.decline_code.as_deref().or(self.code.as_deref())?;
+        match decline_code {
+            "insufficient_funds"
+            | "do_not_honor"
+            | "generic_decline"
+            | "card_declined"
+            | "try_again_later"
+            | "processing_error"
+            | "issuer_not_available"
+            | "reenter_transaction"
+            | "approve_with_id"
+            | "call_issuer"
+            | "card_velocity_exceeded"
+            | "withdrawal_count_limit_exceeded" => Some(types::DeclineType::Soft),
+            "stolen_card"
+            | "lost_card"
+            | "pickup_card"
+            | "restricted_card"
+            | "fraudulent"
+            | "merchant_blacklist"
+            | "security_violation"
+            | "invalid_account"
+            | "card_not_supported"
+            | "currency_not_supported"
+            | "transaction_not_allowed"
+            | "expired_card"
+            | "incorrect_number"
+            | "invalid_number"
+            | "revocation_of_authorization"
+            | "revocation_of_all_authorizations"
+            | "stop_payment_order"
+            | "do_not_try_again" => Some(types::DeclineType::Hard),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
@@ -701,6 +780,9 @@ pub enum StripePaymentMethodOptions {
     Klarna {},
     Affirm {},
     AfterpayClearpay {},
+    Ideal {},
+    Sofort {},
+    Giropay {},
 }
 // #[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
 // pub struct Card
@@ -781,6 +863,11 @@ pub struct StripeWebhookObjectResource {
     pub data: StripeWebhookDataResource,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct StripeWebhookObjectEventId {
+    pub id: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct StripeWebhookObjectEventType {
     #[serde(rename = "type")]
@@ -792,6 +879,182 @@ pub struct StripeWebhookObjectId {
     pub data: StripeWebhookDataId,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct StripeWebhookObjectDispute {
+    pub data: StripeWebhookDataDispute,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripeWebhookDataDispute {
+    pub object: StripeDisputeObject,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripeDisputeObject {
+    pub id: String,
+    pub amount: i64,
+    pub currency: String,
+    pub payment_intent: String,
+    pub reason: Option<String>,
+    pub status: StripeDisputeStatus,
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StripeDisputeStatus {
+    WarningNeedsResponse,
+    WarningUnderReview,
+    WarningClosed,
+    NeedsResponse,
+    UnderReview,
+    ChargeRefunded,
+    Won,
+    Lost,
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct StripeSubmitEvidenceRequest {
+    #[serde(
+        rename = "evidence[product_description]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub product_description: Option<String>,
+    #[serde(
+        rename = "evidence[customer_name]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub customer_name: Option<String>,
+    #[serde(
+        rename = "evidence[customer_email_address]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub customer_email_address: Option<String>,
+    #[serde(
+        rename = "evidence[refund_refusal_explanation]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub refund_refusal_explanation: Option<String>,
+    #[serde(
+        rename = "evidence[shipping_tracking_number]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub shipping_tracking_number: Option<String>,
+    #[serde(
+        rename = "evidence[uncategorized_text]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub uncategorized_text: Option<String>,
+    #[serde(rename = "evidence[receipt]", skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<String>,
+    #[serde(
+        rename = "evidence[customer_communication]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub customer_communication: Option<String>,
+    #[serde(
+        rename = "evidence[shipping_documentation]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub shipping_documentation: Option<String>,
+    #[serde(
+        rename = "evidence[uncategorized_file]",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub uncategorized_file: Option<String>,
+    /// Stripe only saves the evidence as a draft unless it is submitted
+    pub submit: bool,
+}
+
+impl TryFrom<&types::SubmitEvidenceRouterData> for StripeSubmitEvidenceRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::SubmitEvidenceRouterData) -> Result<Self, Self::Error> {
+        let evidence = item.request.evidence.clone();
+        Ok(Self {
+            product_description: evidence.product_description,
+            customer_name: evidence.customer_name,
+            customer_email_address: evidence.customer_email_address,
+            refund_refusal_explanation: evidence.refund_refusal_explanation,
+            shipping_tracking_number: evidence.shipping_tracking_number,
+            uncategorized_text: evidence.uncategorized_text,
+            receipt: evidence.receipt,
+            customer_communication: evidence.customer_communication,
+            shipping_documentation: evidence.shipping_documentation,
+            uncategorized_file: evidence.uncategorized_file,
+            submit: true,
+        })
+    }
+}
+
+impl TryFrom<types::SubmitEvidenceResponseRouterData<StripeDisputeObject>>
+    for types::SubmitEvidenceRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::SubmitEvidenceResponseRouterData<StripeDisputeObject>,
+    ) -> Result<Self, Self::Error> {
+        // Submitted evidence moves the dispute under review until the issuer decides on it
+        let evidence_status = match item.response.status {
+            StripeDisputeStatus::UnderReview | StripeDisputeStatus::WarningUnderReview => {
+                enums::EvidenceStatus::Submitted
+            }
+            _ => enums::EvidenceStatus::Failed,
+        };
+        Ok(Self {
+            response: Ok(types::SubmitEvidenceResponse { evidence_status }),
+            ..item.data
+        })
+    }
+}
+
+impl From<StripeDisputeObject> for api::DisputePayload {
+    fn from(dispute: StripeDisputeObject) -> Self {
+        Self {
+            connector_dispute_id: dispute.id,
+            connector_transaction_id: dispute.payment_intent,
+            connector_reason_code: dispute.reason,
+            amount: dispute.amount,
+            currency: dispute.currency.to_uppercase(),
+        }
+    }
+}
+
+impl From<payments::BankRedirectData> for StripeBankRedirectData {
+    fn from(bank_redirect_data: payments::BankRedirectData) -> Self {
+        match bank_redirect_data {
+            payments::BankRedirectData::Ideal {
+                billing_name,
+                bank_name,
+            } => Self {
+                payment_method_types: StripePaymentMethodType::Ideal,
+                payment_method_data_type: StripePaymentMethodType::Ideal,
+                billing_name: Some(billing_name),
+                ideal_bank_name: Some(bank_name),
+                sofort_country: None,
+                sofort_preferred_language: None,
+            },
+            payments::BankRedirectData::Sofort {
+                country,
+                preferred_language,
+            } => Self {
+                payment_method_types: StripePaymentMethodType::Sofort,
+                payment_method_data_type: StripePaymentMethodType::Sofort,
+                billing_name: None,
+                ideal_bank_name: None,
+                sofort_country: Some(country),
+                sofort_preferred_language: preferred_language,
+            },
+            payments::BankRedirectData::Giropay { billing_name } => Self {
+                payment_method_types: StripePaymentMethodType::Giropay,
+                payment_method_data_type: StripePaymentMethodType::Giropay,
+                billing_name: Some(billing_name),
+                ideal_bank_name: None,
+                sofort_country: None,
+                sofort_preferred_language: None,
+            },
+        }
+    }
+}
+
 impl TryFrom<(api::PaymentMethod, enums::AuthenticationType)> for StripePaymentMethodData {
     type Error = errors::ConnectorError;
     fn try_from(
@@ -850,8 +1113,217 @@ impl TryFrom<(api::PaymentMethod, enums::AuthenticationType)> for StripePaymentM
                     "Stripe does not support payment through provided payment method",
                 ))),
             },
+            api::PaymentMethod::BankRedirect(bank_redirect_data) => Ok(Self::BankRedirect(
+                StripeBankRedirectData::from(bank_redirect_data),
+            )),
             api::PaymentMethod::Wallet(_) => Ok(Self::Wallet),
             api::PaymentMethod::Paypal => Ok(Self::Paypal),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::{connector::utils::get_test_router_data, types::DeclineType};
+
+    fn get_card_declined_error(decline_code: &str) -> ErrorDetails {
+        ErrorDetails {
+            code: Some("card_declined".to_string()),
+            error_type: Some("card_error".to_string()),
+            message: Some("Your card was declined.".to_string()),
+            param: None,
+            decline_code: Some(decline_code.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_statement_descriptor_suffix_is_truncated_in_the_request() {
+        let router_data: types::PaymentsAuthorizeRouterData = get_test_router_data(
+            "stripe",
+            types::PaymentsAuthorizeData {
+                payment_method_data: api::PaymentMethod::Card(api::Card {
+                    card_number: Secret::new("4242424242424242".to_string()),
+                    card_exp_month: Secret::new("10".to_string()),
+                    card_exp_year: Secret::new("2035".to_string()),
+                    card_holder_name: Secret::new("John Doe".to_string()),
+                    card_cvc: Secret::new("123".to_string()),
+                }),
+                amount: 1000,
+                email: None,
+                currency: enums::Currency::USD,
+                confirm: true,
+                statement_descriptor_suffix: Some("ORDER 12345 FOR SHOES AND SOCKS".to_string()),
+                statement_descriptor_prefix: None,
+                capture_method: None,
+                setup_future_usage: None,
+                mandate_id: None,
+                off_session: None,
+                setup_mandate_details: None,
+                browser_info: None,
+                order_details: None,
+                surcharge_details: None,
+                tax_amount: None,
+                sca_exemption: None,
+                connector_meta: None,
+                network_token: None,
+            },
+            Err(types::ErrorResponse::default()),
+        );
+
+        let request = PaymentIntentRequest::try_from(&router_data).unwrap();
+        assert_eq!(
+            request.statement_descriptor_suffix.as_deref(),
+            Some("ORDER 12345 FOR SHOES")
+        );
+
+        let mut router_data = router_data;
+        router_data.request.statement_descriptor_prefix = Some("HYPERSWITCH".to_string());
+        let request = PaymentIntentRequest::try_from(&router_data).unwrap();
+        // HYPERSWITCH* ORDER 123 is 22 characters long
+        assert_eq!(
+            request.statement_descriptor_suffix.as_deref(),
+            Some("ORDER 123")
+        );
+    }
+
+    #[test]
+    fn test_retryable_declines_are_soft() {
+        for decline_code in ["insufficient_funds", "do_not_honor", "try_again_later"] {
+            assert_eq!(
+                get_card_declined_error(decline_code).get_decline_type(),
+                Some(DeclineType::Soft),
+                "{decline_code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_permanent_declines_are_hard() {
+        for decline_code in ["stolen_card", "lost_card", "invalid_account", "fraudulent"] {
+            assert_eq!(
+                get_card_declined_error(decline_code).get_decline_type(),
+                Some(DeclineType::Hard),
+                "{decline_code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_code_is_used_without_decline_code() {
+        let error = ErrorDetails {
+            code: Some("expired_card".to_string()),
+            decline_code: None,
+            ..get_card_declined_error("")
+        };
+        assert_eq!(error.get_decline_type(), Some(DeclineType::Hard));
+    }
+
+    #[test]
+    fn test_non_decline_errors_are_not_classified() {
+        let error = ErrorDetails {
+            code: Some("parameter_missing".to_string()),
+            error_type: Some("invalid_request_error".to_string()),
+            message: Some("Missing required param: amount.".to_string()),
+            param: Some("amount".to_string()),
+            decline_code: None,
+        };
+        assert_eq!(error.get_decline_type(), None);
+    }
+
+    #[test]
+    fn test_ideal_payment_method_data_is_url_encoded() {
+        let payment_method_data = StripePaymentMethodData::try_from((
+            api::PaymentMethod::BankRedirect(payments::BankRedirectData::Ideal {
+                billing_name: Secret::new("John Doe".to_string()),
+                bank_name: api_models::enums::BankNames::Ing,
+            }),
+            enums::AuthenticationType::NoThreeDs,
+        ))
+        .unwrap();
+
+        let encoded = serde_urlencoded::to_string(&payment_method_data).unwrap();
+        let fields = serde_urlencoded::from_str::<Vec<(String, String)>>(&encoded).unwrap();
+
+        assert_eq!(
+            fields,
+            vec![
+                ("payment_method_types[]".to_string(), "ideal".to_string()),
+                ("payment_method_data[type]".to_string(), "ideal".to_string()),
+                (
+                    "payment_method_data[billing_details][name]".to_string(),
+                    "John Doe".to_string()
+                ),
+                (
+                    "payment_method_data[ideal][bank]".to_string(),
+                    "ing".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redirect_url_is_split_into_form_fields() {
+        let next_action: StripeNextActionResponse = serde_json::from_str(
+            r#"{
+                "type": "redirect_to_url",
+                "redirect_to_url": {
+                    "return_url": "https://example.com/return",
+                    "url": "https://hooks.stripe.com/redirect/authenticate/src_123?client_secret=src_client_secret_456&source_redirect_slug=test_789"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let redirect_form = get_redirection_form(&next_action);
+
+        assert_eq!(
+            redirect_form.url,
+            "https://hooks.stripe.com/redirect/authenticate/src_123"
+        );
+        assert_eq!(
+            redirect_form.form_fields,
+            std::collections::HashMap::from([
+                (
+                    "client_secret".to_string(),
+                    "src_client_secret_456".to_string()
+                ),
+                ("source_redirect_slug".to_string(), "test_789".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_evidence_is_submitted_with_the_request() {
+        let router_data: types::SubmitEvidenceRouterData = get_test_router_data(
+            "stripe",
+            types::SubmitEvidenceRequestData {
+                dispute_id: "dp_hs_123".to_string(),
+                connector_dispute_id: "dp_123".to_string(),
+                evidence: api::SubmitEvidenceRequest {
+                    product_description: Some("Annual subscription".to_string()),
+                    receipt: Some("file_123".to_string()),
+                    ..Default::default()
+                },
+            },
+            Err(types::ErrorResponse::default()),
+        );
+
+        let evidence_request = StripeSubmitEvidenceRequest::try_from(&router_data).unwrap();
+        let encoded = serde_urlencoded::to_string(&evidence_request).unwrap();
+        let fields = serde_urlencoded::from_str::<Vec<(String, String)>>(&encoded).unwrap();
+
+        assert_eq!(
+            fields,
+            vec![
+                (
+                    "evidence[product_description]".to_string(),
+                    "Annual subscription".to_string()
+                ),
+                ("evidence[receipt]".to_string(), "file_123".to_string()),
+                ("submit".to_string(), "true".to_string()),
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use common_utils::errors::CryptoError;
 use error_stack::{report, IntoReport, ResultExt};
 use masking::Secret;
 
@@ -21,6 +22,17 @@ pub fn missing_field_err(
 
 type Error = error_stack::Report<errors::ConnectorError>;
 
+/// Parses the metadata stored with the payment attempt into the typed metadata of the connector,
+/// which validates its shape.
+pub fn to_connector_meta<T>(connector_meta: Option<serde_json::Value>) -> Result<T, Error>
+where
+    T: TryFrom<serde_json::Value, Error = Error>,
+{
+    connector_meta
+        .ok_or_else(missing_field_err("connector_meta"))?
+        .try_into()
+}
+
 pub trait AccessTokenRequestInfo {
     fn get_request_id(&self) -> Result<String, Error>;
 }
@@ -34,13 +46,43 @@ impl AccessTokenRequestInfo for types::RefreshTokenRouterData {
     }
 }
 
+/// Fields of the router data that are common to all flows, failing with
+/// [`errors::ConnectorError::MissingRequiredField`] when the connector needs a field that was not
+/// provided.
+pub trait RouterData {
+    fn get_billing(&self) -> Result<&api::Address, Error>;
+    fn get_billing_address(&self) -> Result<&api::AddressDetails, Error>;
+    fn get_return_url(&self) -> Result<String, Error>;
+}
+
+impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Response> {
+    fn get_billing(&self) -> Result<&api::Address, Error> {
+        self.address
+            .billing
+            .as_ref()
+            .ok_or_else(missing_field_err("billing"))
+    }
+
+    fn get_billing_address(&self) -> Result<&api::AddressDetails, Error> {
+        self.get_billing()?
+            .address
+            .as_ref()
+            .ok_or_else(missing_field_err("billing.address"))
+    }
+
+    fn get_return_url(&self) -> Result<String, Error> {
+        self.router_return_url
+            .clone()
+            .ok_or_else(missing_field_err("router_return_url"))
+    }
+}
+
 pub trait PaymentsRequestData {
     fn get_attempt_id(&self) -> Result<String, Error>;
-    fn get_billing(&self) -> Result<&api::Address, Error>;
     fn get_billing_country(&self) -> Result<String, Error>;
     fn get_billing_phone(&self) -> Result<&api::PhoneDetails, Error>;
+    fn get_browser_info(&self) -> Result<&types::BrowserInformation, Error>;
     fn get_card(&self) -> Result<api::Card, Error>;
-    fn get_return_url(&self) -> Result<String, Error>;
 }
 
 pub trait RefundsRequestData {
@@ -86,17 +128,12 @@ impl PaymentsRequestData for types::PaymentsAuthorizeRouterData {
             .and_then(|a| a.phone.as_ref())
             .ok_or_else(missing_field_err("billing.phone"))
     }
-    fn get_billing(&self) -> Result<&api::Address, Error> {
-        self.address
-            .billing
-            .as_ref()
-            .ok_or_else(missing_field_err("billing"))
-    }
 
-    fn get_return_url(&self) -> Result<String, Error> {
-        self.router_return_url
-            .clone()
-            .ok_or_else(missing_field_err("router_return_url"))
+    fn get_browser_info(&self) -> Result<&types::BrowserInformation, Error> {
+        self.request
+            .browser_info
+            .as_ref()
+            .ok_or_else(missing_field_err("browser_info"))
     }
 }
 
@@ -198,6 +235,79 @@ impl AddressDetailsData for api::AddressDetails {
     }
 }
 
+/// Fails with [`errors::ConnectorError::CurrencyNotSupported`] when the connector does not list
+/// the currency among its supported currencies.
+pub fn validate_currency(
+    currency: types::storage::enums::Currency,
+    supported_currencies: &[types::storage::enums::Currency],
+    connector: &str,
+) -> Result<(), Error> {
+    if supported_currencies.contains(&currency) {
+        Ok(())
+    } else {
+        Err(errors::ConnectorError::CurrencyNotSupported {
+            currency: currency.to_string(),
+            connector: connector.to_string(),
+        }
+        .into())
+    }
+}
+
+/// Truncates the statement descriptor to at most `max_length` characters.
+pub fn truncate_statement_descriptor(descriptor: &str, max_length: usize) -> String {
+    descriptor
+        .chars()
+        .take(max_length)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+const STATEMENT_DESCRIPTOR_SEPARATOR: &str = "* ";
+
+/// Truncates the suffix so that the `{prefix}* {suffix}` descriptor shown on the customer's
+/// statement fits in `max_length` characters, for connectors that take the suffix alone and add
+/// the prefix themselves. Returns `None` when the prefix leaves no room for the suffix.
+pub fn get_statement_descriptor_suffix(
+    prefix: Option<&str>,
+    suffix: &str,
+    max_length: usize,
+) -> Option<String> {
+    let suffix_length = match prefix {
+        Some(prefix) => max_length
+            .saturating_sub(prefix.chars().count())
+            .saturating_sub(STATEMENT_DESCRIPTOR_SEPARATOR.len()),
+        None => max_length,
+    };
+    let suffix = truncate_statement_descriptor(suffix, suffix_length);
+    (!suffix.is_empty()).then_some(suffix)
+}
+
+/// Builds the descriptor shown on the customer's statement as `{prefix}* {suffix}`, for
+/// connectors that accept a single descriptor of at most `max_length` characters.
+///
+/// The prefix is kept whole and the suffix is truncated to fit in the remaining length. If the
+/// prefix alone does not leave room for the suffix, the prefix is truncated to `max_length`
+/// characters and the suffix is dropped.
+pub fn get_statement_descriptor(
+    prefix: Option<&str>,
+    suffix: Option<&str>,
+    max_length: usize,
+) -> Option<String> {
+    match (prefix, suffix) {
+        (Some(prefix), Some(suffix)) => Some(
+            match get_statement_descriptor_suffix(Some(prefix), suffix, max_length) {
+                Some(suffix) => format!("{prefix}{STATEMENT_DESCRIPTOR_SEPARATOR}{suffix}"),
+                None => truncate_statement_descriptor(prefix, max_length),
+            },
+        ),
+        (Some(descriptor), None) | (None, Some(descriptor)) => {
+            Some(truncate_statement_descriptor(descriptor, max_length))
+        }
+        (None, None) => None,
+    }
+}
+
 pub fn get_header_key_value<'a>(
     key: &str,
     headers: &'a actix_web::http::header::HeaderMap,
@@ -214,3 +324,278 @@ pub fn get_header_key_value<'a>(
             errors::ConnectorError::WebhookSourceVerificationFailed
         ))?
 }
+
+/// Order in which a signed connector expects the fields of a request body in the message it signs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureFieldOrder {
+    /// Keys of every object sorted lexicographically.
+    SortedKeys,
+    /// Top level fields in the order of the connector's specification, followed by the fields it
+    /// does not list in sorted order. Keys of nested objects are sorted.
+    Specified(&'static [&'static str]),
+}
+
+/// Serializes `body` as compact JSON with its fields in `field_order`, so that the message signed
+/// does not depend on the order the request happened to be serialized in.
+pub fn get_canonical_json(
+    body: &serde_json::Value,
+    field_order: SignatureFieldOrder,
+) -> CustomResult<String, CryptoError> {
+    let top_level_fields = match field_order {
+        SignatureFieldOrder::SortedKeys => &[][..],
+        SignatureFieldOrder::Specified(fields) => fields,
+    };
+    let mut canonical_json = String::new();
+    write_canonical_json(body, top_level_fields, &mut canonical_json)?;
+    Ok(canonical_json)
+}
+
+fn write_canonical_json(
+    value: &serde_json::Value,
+    field_order: &[&str],
+    canonical_json: &mut String,
+) -> CustomResult<(), CryptoError> {
+    match value {
+        serde_json::Value::Array(items) => {
+            canonical_json.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    canonical_json.push(',');
+                }
+                write_canonical_json(item, &[], canonical_json)?;
+            }
+            canonical_json.push(']');
+        }
+        serde_json::Value::Object(fields) => {
+            let get_rank = |key: &str| {
+                field_order
+                    .iter()
+                    .position(|field| *field == key)
+                    .unwrap_or(field_order.len())
+            };
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort_by(|left, right| (get_rank(left), *left).cmp(&(get_rank(right), *right)));
+
+            canonical_json.push('{');
+            for (index, key) in keys.into_iter().enumerate() {
+                if index > 0 {
+                    canonical_json.push(',');
+                }
+                canonical_json.push_str(&encode_json_scalar(key)?);
+                canonical_json.push(':');
+                if let Some(field) = fields.get(key) {
+                    write_canonical_json(field, &[], canonical_json)?;
+                }
+            }
+            canonical_json.push('}');
+        }
+        scalar => canonical_json.push_str(&encode_json_scalar(scalar)?),
+    }
+    Ok(())
+}
+
+fn encode_json_scalar<T: serde::Serialize>(value: &T) -> CustomResult<String, CryptoError> {
+    serde_json::to_string(value)
+        .into_report()
+        .change_context(CryptoError::MessageSigningFailed)
+}
+
+/// Fails the test when the debug representation of `value`, which is what ends up in the logs,
+/// contains any of the `raw_values` the value was built from.
+#[cfg(test)]
+pub fn assert_debug_output_is_masked<T: std::fmt::Debug>(value: &T, raw_values: &[&str]) {
+    let debug_output = format!("{value:?}");
+    for raw_value in raw_values {
+        assert!(
+            !debug_output.contains(raw_value),
+            "`{raw_value}` is not masked in {debug_output}"
+        );
+    }
+}
+
+/// Router data of a pending card payment through `connector`, for tests to override the fields
+/// they depend on.
+#[cfg(test)]
+pub fn get_test_router_data<F, Req, Resp>(
+    connector: &str,
+    request: Req,
+    response: Result<Resp, types::ErrorResponse>,
+) -> types::RouterData<F, Req, Resp> {
+    types::RouterData {
+        flow: std::marker::PhantomData,
+        merchant_id: "merchant_123".to_string(),
+        connector: connector.to_string(),
+        payment_id: "pay_123".to_string(),
+        attempt_id: Some("pay_123_1".to_string()),
+        status: types::storage::enums::AttemptStatus::Pending,
+        payment_method: types::storage::enums::PaymentMethodType::Card,
+        connector_auth_type: types::ConnectorAuthType::HeaderKey {
+            api_key: "api_key".to_string(),
+        },
+        description: None,
+        return_url: None,
+        router_return_url: None,
+        address: types::PaymentAddress::default(),
+        auth_type: types::storage::enums::AuthenticationType::NoThreeDs,
+        connector_meta_data: None,
+        amount_captured: None,
+        authorized_amount: None,
+        access_token: None,
+        request,
+        response,
+        payment_method_id: None,
+        connector_response_body: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_router_data(address: types::PaymentAddress) -> types::RouterData<(), (), ()> {
+        types::RouterData {
+            address,
+            ..get_test_router_data("opayo", (), Ok(()))
+        }
+    }
+
+    fn assert_missing_field(error: &Error, expected_field_name: &str) {
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::MissingRequiredField { field_name }
+                if *field_name == expected_field_name
+        ));
+    }
+
+    #[test]
+    fn test_present_router_data_fields_are_returned() {
+        let mut router_data = get_router_data(types::PaymentAddress {
+            billing: Some(api::Address {
+                address: Some(api::AddressDetails {
+                    city: Some("Harrogate".to_string()),
+                    ..Default::default()
+                }),
+                phone: None,
+            }),
+            shipping: None,
+        });
+        router_data.router_return_url = Some("https://router.test/return".to_string());
+
+        assert_eq!(
+            router_data.get_billing_address().unwrap().city.as_deref(),
+            Some("Harrogate")
+        );
+        assert_eq!(
+            router_data.get_return_url().unwrap(),
+            "https://router.test/return"
+        );
+    }
+
+    #[test]
+    fn test_missing_router_data_fields_are_reported_by_name() {
+        let router_data = get_router_data(types::PaymentAddress::default());
+        assert_missing_field(&router_data.get_billing().unwrap_err(), "billing");
+        assert_missing_field(&router_data.get_billing_address().unwrap_err(), "billing");
+        assert_missing_field(
+            &router_data.get_return_url().unwrap_err(),
+            "router_return_url",
+        );
+
+        let router_data = get_router_data(types::PaymentAddress {
+            billing: Some(api::Address {
+                address: None,
+                phone: None,
+            }),
+            shipping: None,
+        });
+        assert_missing_field(
+            &router_data.get_billing_address().unwrap_err(),
+            "billing.address",
+        );
+    }
+
+    #[test]
+    fn test_statement_descriptor_suffix_is_truncated_to_fit() {
+        assert_eq!(
+            get_statement_descriptor(Some("HYPERSWITCH"), Some("ORDER 12345"), 22),
+            Some("HYPERSWITCH* ORDER 123".to_string())
+        );
+        assert_eq!(
+            get_statement_descriptor(Some("SHOP"), Some("SHOES"), 22),
+            Some("SHOP* SHOES".to_string())
+        );
+    }
+
+    #[test]
+    fn test_statement_descriptor_suffix_leaves_room_for_the_prefix() {
+        assert_eq!(
+            get_statement_descriptor_suffix(Some("HYPERSWITCH"), "ORDER 12345", 22),
+            Some("ORDER 123".to_string())
+        );
+        assert_eq!(
+            get_statement_descriptor_suffix(None, "ORDER 12345 FOR SHOES AND SOCKS", 22),
+            Some("ORDER 12345 FOR SHOES".to_string())
+        );
+        assert_eq!(
+            get_statement_descriptor_suffix(Some("HYPERSWITCH PAYMENTS"), "ORDER 12345", 22),
+            None
+        );
+    }
+
+    #[test]
+    fn test_long_statement_descriptor_prefix_drops_the_suffix() {
+        assert_eq!(
+            get_statement_descriptor(
+                Some("HYPERSWITCH PAYMENTS LIMITED"),
+                Some("ORDER 12345"),
+                22
+            ),
+            Some("HYPERSWITCH PAYMENTS L".to_string())
+        );
+        assert_eq!(
+            get_statement_descriptor(None, Some("ORDER 12345 FOR SHOES AND SOCKS"), 22),
+            Some("ORDER 12345 FOR SHOES".to_string())
+        );
+        assert_eq!(get_statement_descriptor(None, None, 22), None);
+    }
+
+    fn get_signed_request_body() -> serde_json::Value {
+        serde_json::json!({
+            "currency": "EUR",
+            "amount": 1000,
+            "metadata": { "order_id": "ord_1", "customer": "cus_1" },
+            "payment_method": {
+                "type": "card",
+                "fields": [{ "number": "4111111111111111", "cvv": "123" }]
+            },
+            "description": "Shoes \"and\" socks"
+        })
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys_at_every_level() {
+        let canonical_json =
+            get_canonical_json(&get_signed_request_body(), SignatureFieldOrder::SortedKeys)
+                .unwrap();
+
+        assert_eq!(
+            canonical_json,
+            r#"{"amount":1000,"currency":"EUR","description":"Shoes \"and\" socks","metadata":{"customer":"cus_1","order_id":"ord_1"},"payment_method":{"fields":[{"cvv":"123","number":"4111111111111111"}],"type":"card"}}"#
+        );
+    }
+
+    #[test]
+    fn test_canonical_json_follows_specified_field_order() {
+        let canonical_json = get_canonical_json(
+            &get_signed_request_body(),
+            SignatureFieldOrder::Specified(&["payment_method", "currency", "amount"]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            canonical_json,
+            r#"{"payment_method":{"fields":[{"cvv":"123","number":"4111111111111111"}],"type":"card"},"currency":"EUR","amount":1000,"description":"Shoes \"and\" socks","metadata":{"customer":"cus_1","order_id":"ord_1"}}"#
+        );
+    }
+}
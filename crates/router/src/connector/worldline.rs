@@ -403,6 +403,17 @@ impl ConnectorIntegration<api::Session, types::PaymentsSessionData, types::Payme
     // Not Implemented
 }
 
+impl api::PaymentsPreProcessing for Worldline {}
+
+impl
+    ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Worldline
+{
+}
+
 impl api::PaymentAuthorize for Worldline {}
 
 impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::PaymentsResponseData>
@@ -651,6 +662,18 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::SubmitEvidence for Worldline {}
+
+impl
+    ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Worldline
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Worldline {
     fn get_webhook_object_reference_id(
@@ -83,6 +83,7 @@ impl ConnectorCommon for Worldpay {
             code: response.error_name,
             message: response.message,
             reason: None,
+            decline_type: None,
         })
     }
 }
@@ -163,6 +164,9 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
                         redirect: false,
                         mandate_reference: None,
                         connector_metadata: None,
+                        avs_result: None,
+                        cvv_result: None,
+                        connector_response_reference_id: None,
                     }),
                     ..data.clone()
                 })
@@ -259,6 +263,9 @@ impl ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsRe
                 redirect: false,
                 mandate_reference: None,
                 connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
             }),
             ..data.clone()
         })
@@ -317,6 +324,9 @@ impl ConnectorIntegration<api::Capture, types::PaymentsCaptureData, types::Payme
                         redirect: false,
                         mandate_reference: None,
                         connector_metadata: None,
+                        avs_result: None,
+                        cvv_result: None,
+                        connector_response_reference_id: None,
                     }),
                     ..data.clone()
                 })
@@ -353,6 +363,17 @@ impl ConnectorIntegration<api::Session, types::PaymentsSessionData, types::Payme
 {
 }
 
+impl api::PaymentsPreProcessing for Worldpay {}
+
+impl
+    ConnectorIntegration<
+        api::PreProcessing,
+        types::PaymentsPreProcessingData,
+        types::PaymentsResponseData,
+    > for Worldpay
+{
+}
+
 impl api::PaymentAuthorize for Worldpay {}
 
 impl ConnectorIntegration<api::Authorize, types::PaymentsAuthorizeData, types::PaymentsResponseData>
@@ -593,6 +614,18 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+impl api::SubmitEvidence for Worldpay {}
+
+impl
+    ConnectorIntegration<
+        api::Evidence,
+        types::SubmitEvidenceRequestData,
+        types::SubmitEvidenceResponse,
+    > for Worldpay
+{
+    // Not Implemented (R)
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Worldpay {
     fn get_webhook_object_reference_id(
@@ -23,3 +23,9 @@ pub(crate) const BASE64_ENGINE_URL_SAFE: base64::engine::GeneralPurpose =
     base64::engine::general_purpose::URL_SAFE;
 
 pub(crate) const API_KEY_LENGTH: usize = 64;
+
+/// Maximum length of the connector response body stored on the payment attempt
+pub(crate) const MAX_CONNECTOR_RESPONSE_BODY_LENGTH: usize = 4096;
+
+/// Maximum length of the return URL, as stored on the payment intent and attempt
+pub(crate) const MAX_RETURN_URL_LENGTH: usize = 255;
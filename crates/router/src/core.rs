@@ -1,7 +1,9 @@
 pub mod admin;
 pub mod api_keys;
 pub mod configs;
+pub mod connector_credentials;
 pub mod customers;
+pub mod disputes;
 pub mod errors;
 pub mod mandate;
 pub mod payment_methods;
@@ -1,13 +1,21 @@
+use std::str::FromStr;
+
 use common_utils::ext_traits::ValueExt;
-use error_stack::{report, FutureExt, ResultExt};
+use error_stack::{report, FutureExt, IntoReport, ResultExt};
+use masking::PeekInterface;
 use storage_models::{enums, merchant_account};
 use uuid::Uuid;
 
 use crate::{
     consts,
-    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    core::{
+        connector_credentials,
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments,
+    },
     db::StorageInterface,
     pii::Secret,
+    routes::AppState,
     services::api as service_api,
     types::{
         self, api,
@@ -49,13 +57,14 @@ pub async fn create_merchant_account(
     );
 
     if let Some(ref routing_algorithm) = req.routing_algorithm {
-        let _: api::RoutingAlgorithm = routing_algorithm
+        let routing_algorithm: api::RoutingAlgorithm = routing_algorithm
             .clone()
             .parse_value("RoutingAlgorithm")
             .change_context(errors::ApiErrorResponse::InvalidDataValue {
                 field_name: "routing_algorithm",
             })
             .attach_printable("Invalid routing algorithm given")?;
+        payments::routing::validate_routing_algorithm(&routing_algorithm)?;
     }
 
     let merchant_account = storage::MerchantAccountNew {
@@ -127,13 +136,14 @@ pub async fn merchant_account_update(
     }
 
     if let Some(ref routing_algorithm) = req.routing_algorithm {
-        let _: api::RoutingAlgorithm = routing_algorithm
+        let routing_algorithm: api::RoutingAlgorithm = routing_algorithm
             .clone()
             .parse_value("RoutingAlgorithm")
             .change_context(errors::ApiErrorResponse::InvalidDataValue {
                 field_name: "routing_algorithm",
             })
             .attach_printable("Invalid routing algorithm given")?;
+        payments::routing::validate_routing_algorithm(&routing_algorithm)?;
     }
 
     let updated_merchant_account = storage::MerchantAccountUpdate::Update {
@@ -243,10 +253,11 @@ async fn validate_merchant_id<S: Into<String>>(
 //                          with unique merchant_connector_id for Create Operation
 
 pub async fn create_payment_connector(
-    store: &dyn StorageInterface,
+    state: &AppState,
     req: api::PaymentConnectorCreate,
     merchant_id: &String,
 ) -> RouterResponse<api::PaymentConnectorCreate> {
+    let store = &*state.store;
     let _merchant_account = store
         .find_merchant_account_by_merchant_id(merchant_id)
         .await
@@ -271,22 +282,20 @@ pub async fn create_payment_connector(
         None => None,
     };
 
+    validate_connector_name(&req.connector_name)?;
     // Validate Merchant api details and return error if not in correct format
-    let _: types::ConnectorAuthType = req
-        .connector_account_details
-        .clone()
-        .parse_value("ConnectorAuthType")
-        .change_context(errors::ApiErrorResponse::InvalidDataFormat {
-            field_name: "connector_account_details".to_string(),
-            expected_format: "auth_type and api_key".to_string(),
-        })?;
+    let auth_type = parse_connector_account_details(req.connector_account_details)?;
+    let connector_name = req.connector_name.clone();
+    let webhook_secret = req.webhook_secret;
 
     let merchant_connector_account = storage::MerchantConnectorAccountNew {
         merchant_id: Some(merchant_id.to_string()),
         connector_type: Some(req.connector_type.foreign_into()),
         connector_name: Some(req.connector_name),
         merchant_connector_id: utils::generate_id(consts::ID_LENGTH, "mca"),
-        connector_account_details: req.connector_account_details,
+        connector_account_details: Some(connector_credentials::get_redacted_account_details(
+            &auth_type,
+        )?),
         payment_methods_enabled,
         test_mode: req.test_mode,
         disabled: req.disabled,
@@ -299,11 +308,51 @@ pub async fn create_payment_connector(
         .map_err(|error| {
             error.to_duplicate_response(errors::ApiErrorResponse::DuplicateMerchantConnectorAccount)
         })?;
+    connector_credentials::store_connector_auth_type(
+        state,
+        merchant_id,
+        &connector_name,
+        &auth_type,
+    )
+    .await?;
+    if let Some(webhook_secret) = webhook_secret {
+        connector_credentials::store_webhook_source_verification_secret(
+            state,
+            merchant_id,
+            &connector_name,
+            webhook_secret.peek().as_bytes(),
+        )
+        .await?;
+    }
 
     response.merchant_connector_id = Some(mca.merchant_connector_id);
     Ok(service_api::ApplicationResponse::Json(response))
 }
 
+/// Rejects a merchant connector account for a name that does not match any connector, pointing
+/// out the closest connector name in the error message.
+fn validate_connector_name(connector_name: &str) -> RouterResult<()> {
+    api_models::enums::Connector::from_str(connector_name)
+        .map(|_| ())
+        .map_err(|error| errors::ApiErrorResponse::InvalidRequestData {
+            message: error.to_string(),
+        })
+        .into_report()
+}
+
+/// Parses the connector credentials of a merchant connector account request, which are stored
+/// encrypted apart from the account.
+fn parse_connector_account_details(
+    connector_account_details: Option<Secret<serde_json::Value>>,
+) -> RouterResult<types::ConnectorAuthType> {
+    connector_account_details
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "connector_account_details".to_string(),
+            expected_format: "auth_type and api_key".to_string(),
+        })
+}
+
 pub async fn retrieve_payment_connector(
     store: &dyn StorageInterface,
     merchant_id: String,
@@ -360,11 +409,12 @@ pub async fn list_payment_connectors(
 }
 
 pub async fn update_payment_connector(
-    db: &dyn StorageInterface,
+    state: &AppState,
     merchant_id: &str,
     merchant_connector_id: &str,
     req: api::PaymentConnectorCreate,
 ) -> RouterResponse<api::PaymentConnectorCreate> {
+    let db = &*state.store;
     let _merchant_account = db
         .find_merchant_account_by_merchant_id(merchant_id)
         .await
@@ -382,6 +432,7 @@ pub async fn update_payment_connector(
             error.to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound)
         })?;
 
+    validate_connector_name(&req.connector_name)?;
     let payment_methods_enabled = req.payment_methods_enabled.map(|pm_enabled| {
         pm_enabled
             .iter()
@@ -391,12 +442,39 @@ pub async fn update_payment_connector(
             .collect::<Vec<serde_json::Value>>()
     });
 
+    let auth_type = req
+        .connector_account_details
+        .map(|details| parse_connector_account_details(Some(details)))
+        .transpose()?;
+    let connector_account_details = auth_type
+        .as_ref()
+        .map(connector_credentials::get_redacted_account_details)
+        .transpose()?;
+    if let Some(auth_type) = &auth_type {
+        connector_credentials::store_connector_auth_type(
+            state,
+            merchant_id,
+            &mca.connector_name,
+            auth_type,
+        )
+        .await?;
+    }
+    if let Some(webhook_secret) = &req.webhook_secret {
+        connector_credentials::store_webhook_source_verification_secret(
+            state,
+            merchant_id,
+            &mca.connector_name,
+            webhook_secret.peek().as_bytes(),
+        )
+        .await?;
+    }
+
     let payment_connector = storage::MerchantConnectorAccountUpdate::Update {
         merchant_id: Some(merchant_id.to_string()),
         connector_type: Some(req.connector_type.foreign_into()),
         connector_name: Some(req.connector_name),
         merchant_connector_id: Some(merchant_connector_id.to_string()),
-        connector_account_details: req.connector_account_details,
+        connector_account_details,
         payment_methods_enabled,
         test_mode: req.test_mode,
         disabled: req.disabled,
@@ -431,6 +509,7 @@ pub async fn update_payment_connector(
         disabled: updated_mca.disabled,
         payment_methods_enabled: updated_pm_enabled,
         metadata: updated_mca.metadata,
+        webhook_secret: None,
     };
     Ok(service_api::ApplicationResponse::Json(response))
 }
@@ -5,13 +5,17 @@ use router_env::{instrument, tracing};
 
 use crate::{
     consts,
-    core::errors::{self, RouterResponse, StorageErrorExt},
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
     db::StorageInterface,
     services::ApplicationResponse,
     types::{api, storage, transformers::ForeignInto},
     utils,
 };
 
+/// Time (in seconds) for which a rotated API key keeps working alongside the new API key, unless
+/// the rotation request specifies another window.
+const DEFAULT_API_KEY_ROTATION_WINDOW: i64 = 24 * 60 * 60;
+
 // Defining new types `PlaintextApiKey` and `HashedApiKey` in the hopes of reducing the possibility
 // of plaintext API key being stored in the data store.
 pub struct PlaintextApiKey(Secret<String>);
@@ -93,24 +97,80 @@ impl PlaintextApiKey {
     }
 }
 
+impl From<&str> for PlaintextApiKey {
+    fn from(api_key: &str) -> Self {
+        Self(api_key.to_owned().into())
+    }
+}
+
+fn is_active(api_key: &storage::ApiKey, now: time::PrimitiveDateTime) -> bool {
+    api_key
+        .expires_at
+        .map_or(true, |expires_at| expires_at > now)
+}
+
+fn get_hash_key(
+    api_key: &storage::ApiKey,
+) -> CustomResult<[u8; PlaintextApiKey::HASH_KEY_LEN], errors::ApiKeyError> {
+    hex::decode(api_key.hash_key.peek())
+        .into_report()
+        .change_context(errors::ApiKeyError::FailedToReadHashFromHex)?
+        .try_into()
+        .map_err(|_| report!(errors::ApiKeyError::FailedToReadHashFromHex))
+}
+
+/// Finds the unexpired API key that `api_key` was issued for, verifying it against the stored
+/// hash. Issued API keys are of the form `{key_id}-{plaintext API key}`.
+///
+/// A rotated API key expires only at the end of its rotation window, so that both the old and the
+/// new API key of a merchant are accepted until then.
 #[instrument(skip_all)]
-pub async fn create_api_key(
+pub async fn find_active_api_key(
+    store: &dyn StorageInterface,
+    api_key: &str,
+) -> RouterResult<Option<storage::ApiKey>> {
+    let (key_id, plaintext_api_key) = match api_key.split_once('-') {
+        Some((key_id, plaintext_api_key)) => (key_id, PlaintextApiKey::from(plaintext_api_key)),
+        None => return Ok(None),
+    };
+    let stored_api_key = store
+        .find_api_key_optional(key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?;
+
+    Ok(stored_api_key.filter(|stored_api_key| {
+        is_active(stored_api_key, date_time::now())
+            && get_hash_key(stored_api_key)
+                .and_then(|hash_key| {
+                    plaintext_api_key.verify_hash(
+                        &hash_key,
+                        &HashedApiKey(stored_api_key.hashed_api_key.clone().into_inner()),
+                    )
+                })
+                .is_ok()
+    }))
+}
+
+async fn insert_new_api_key(
     store: &dyn StorageInterface,
-    api_key: api::CreateApiKeyRequest,
     merchant_id: String,
-) -> RouterResponse<api::CreateApiKeyResponse> {
+    name: String,
+    description: Option<String>,
+    expires_at: Option<time::PrimitiveDateTime>,
+) -> RouterResult<(storage::ApiKey, PlaintextApiKey)> {
     let hash_key = PlaintextApiKey::new_hash_key();
     let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
     let api_key = storage::ApiKeyNew {
         key_id: PlaintextApiKey::new_key_id(),
         merchant_id,
-        name: api_key.name,
-        description: api_key.description,
+        name,
+        description,
         hash_key: Secret::from(hex::encode(hash_key)),
         hashed_api_key: plaintext_api_key.keyed_hash(&hash_key).into(),
         prefix: plaintext_api_key.prefix(),
         created_at: date_time::now(),
-        expires_at: api_key.expiration.into(),
+        expires_at,
         last_used: None,
     };
 
@@ -120,6 +180,24 @@ pub async fn create_api_key(
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Failed to insert new API key")?;
 
+    Ok((api_key, plaintext_api_key))
+}
+
+#[instrument(skip_all)]
+pub async fn create_api_key(
+    store: &dyn StorageInterface,
+    api_key: api::CreateApiKeyRequest,
+    merchant_id: String,
+) -> RouterResponse<api::CreateApiKeyResponse> {
+    let (api_key, plaintext_api_key) = insert_new_api_key(
+        store,
+        merchant_id,
+        api_key.name,
+        api_key.description,
+        api_key.expiration.into(),
+    )
+    .await?;
+
     Ok(ApplicationResponse::Json(
         (api_key, plaintext_api_key).foreign_into(),
     ))
@@ -170,6 +248,70 @@ pub async fn revoke_api_key(
     }))
 }
 
+/// Replaces the API key with a new one, shortening the expiry of the old API key to the end of the
+/// rotation window.
+#[instrument(skip_all)]
+pub async fn rotate_api_key(
+    store: &dyn StorageInterface,
+    key_id: &str,
+    request: api::RotateApiKeyRequest,
+) -> RouterResponse<api::CreateApiKeyResponse> {
+    let rotation_window = request
+        .rotation_window
+        .unwrap_or(DEFAULT_API_KEY_ROTATION_WINDOW);
+    fp_utils::when(rotation_window < 0, || {
+        Err(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "rotation_window",
+        })
+        .into_report()
+    })?;
+
+    let api_key = store
+        .find_api_key_optional(key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?
+        .ok_or(report!(errors::ApiErrorResponse::ApiKeyNotFound))?;
+    let now = date_time::now();
+    fp_utils::when(!is_active(&api_key, now), || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "An expired API key cannot be rotated".to_string(),
+        })
+        .into_report()
+    })?;
+
+    let (new_api_key, plaintext_api_key) = insert_new_api_key(
+        store,
+        api_key.merchant_id.clone(),
+        api_key.name.clone(),
+        api_key.description.clone(),
+        request.expiration.into(),
+    )
+    .await?;
+
+    let rotation_ends_at = now.saturating_add(time::Duration::seconds(rotation_window));
+    let expires_at = api_key.expires_at.map_or(rotation_ends_at, |expires_at| {
+        expires_at.min(rotation_ends_at)
+    });
+    store
+        .update_api_key(
+            api_key.key_id,
+            storage::ApiKeyUpdate::Update {
+                name: None,
+                description: None,
+                expires_at: Some(Some(expires_at)),
+                last_used: None,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to expire rotated API key")?;
+
+    Ok(ApplicationResponse::Json(
+        (new_api_key, plaintext_api_key).foreign_into(),
+    ))
+}
+
 #[instrument(skip_all)]
 pub async fn list_api_keys(
     store: &dyn StorageInterface,
@@ -198,8 +340,9 @@ impl From<HashedApiKey> for storage::HashedApiKey {
 
 #[cfg(test)]
 mod tests {
-    #![allow(clippy::unwrap_used)]
+    #![allow(clippy::unwrap_used, clippy::panic)]
     use super::*;
+    use crate::{db::StorageImpl, routes};
 
     #[test]
     fn test_hashing_and_verification() {
@@ -216,4 +359,105 @@ mod tests {
             .verify_hash(&hash_key, &hashed_api_key)
             .unwrap();
     }
+
+    #[test]
+    fn test_verification_fails_for_another_api_key() {
+        let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
+        let hash_key = PlaintextApiKey::new_hash_key();
+        let hashed_api_key = plaintext_api_key.keyed_hash(&hash_key);
+
+        let error = PlaintextApiKey::new(consts::API_KEY_LENGTH)
+            .verify_hash(&hash_key, &hashed_api_key)
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiKeyError::HashVerificationFailed
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_api_key_is_verified_against_stored_hash() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let store = &*state.store;
+        let (api_key, plaintext_api_key) = insert_new_api_key(
+            store,
+            "merchant_1".to_string(),
+            "key".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_ne!(
+            api_key.hashed_api_key.clone().into_inner(),
+            plaintext_api_key.peek()
+        );
+
+        let issued_api_key = format!("{}-{}", api_key.key_id, plaintext_api_key.peek());
+        let found_api_key = find_active_api_key(store, &issued_api_key)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found_api_key.key_id, api_key.key_id);
+
+        let tampered_api_key = format!("{issued_api_key}0");
+        assert!(find_active_api_key(store, &tampered_api_key)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_both_api_keys_are_active_during_rotation_window() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let store = &*state.store;
+        let (api_key, plaintext_api_key) = insert_new_api_key(
+            store,
+            "merchant_1".to_string(),
+            "key".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let old_issued_api_key = format!("{}-{}", api_key.key_id, plaintext_api_key.peek());
+
+        let request = api::RotateApiKeyRequest {
+            expiration: api::ApiKeyExpiration::Never,
+            rotation_window: Some(3600),
+        };
+        let new_issued_api_key = match rotate_api_key(store, &api_key.key_id, request)
+            .await
+            .unwrap()
+        {
+            ApplicationResponse::Json(response) => response.api_key.peek().to_owned(),
+            response => panic!("Unexpected rotation response: {response:?}"),
+        };
+
+        let old_api_key = find_active_api_key(store, &old_issued_api_key)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(old_api_key.key_id, api_key.key_id);
+        let new_api_key = find_active_api_key(store, &new_issued_api_key)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(new_api_key.key_id, api_key.key_id);
+        assert_eq!(new_api_key.merchant_id, api_key.merchant_id);
+
+        // The old API key stops working once the rotation window is over
+        let rotation_ends_at = old_api_key.expires_at.unwrap();
+        assert!(is_active(&old_api_key, date_time::now()));
+        assert!(!is_active(&old_api_key, rotation_ends_at));
+        assert!(is_active(&new_api_key, rotation_ends_at));
+    }
 }
@@ -0,0 +1,435 @@
+use common_utils::crypto;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors::{self, CustomResult, RouterResult},
+    db::StorageInterface,
+    logger,
+    routes::AppState,
+    types::{self, storage},
+    utils::{self, ByteSliceExt, ValueExt},
+};
+
+fn get_connector_credentials_key(merchant_id: &str, connector_name: &str) -> String {
+    format!("connector_credentials_{merchant_id}_{connector_name}")
+}
+
+fn get_webhook_secret_key(merchant_id: &str, connector_name: &str) -> String {
+    format!("webhook_secret_{merchant_id}_{connector_name}")
+}
+
+fn get_connector_credentials_encryption_key(
+    state: &AppState,
+) -> CustomResult<Vec<u8>, errors::EncryptionError> {
+    hex::decode(&state.conf.secrets.connector_credentials_encryption_key)
+        .into_report()
+        .change_context(errors::EncryptionError)
+        .attach_printable("Failed to hex decode the connector credentials encryption key")
+}
+
+fn encrypt_connector_secret(
+    state: &AppState,
+    secret: &[u8],
+) -> CustomResult<String, errors::EncryptionError> {
+    let key = get_connector_credentials_encryption_key(state)?;
+    crypto::encrypt(&key, secret)
+        .map(hex::encode)
+        .change_context(errors::EncryptionError)
+}
+
+fn decrypt_connector_secret(
+    state: &AppState,
+    encrypted_secret: String,
+) -> CustomResult<Vec<u8>, errors::EncryptionError> {
+    let encrypted_secret = hex::decode(encrypted_secret)
+        .into_report()
+        .change_context(errors::EncryptionError)
+        .attach_printable("Failed to hex decode the encrypted connector secret")?;
+    let key = get_connector_credentials_encryption_key(state)?;
+    crypto::decrypt(&key, &encrypted_secret).change_context(errors::EncryptionError)
+}
+
+async fn insert_or_update_config(
+    db: &dyn StorageInterface,
+    key: String,
+    config: String,
+) -> CustomResult<(), errors::StorageError> {
+    match db.find_config_by_key(&key).await {
+        Ok(_) => {
+            db.update_config_by_key(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await?;
+        }
+        Err(error) if error.current_context().is_db_not_found() => {
+            db.insert_config(storage::ConfigNew { key, config }).await?;
+        }
+        Err(error) => return Err(error),
+    }
+
+    Ok(())
+}
+
+/// Encrypts and stores the credentials of the merchant for the connector, replacing any
+/// credentials stored earlier.
+#[instrument(skip_all)]
+pub async fn store_connector_auth_type(
+    state: &AppState,
+    merchant_id: &str,
+    connector_name: &str,
+    auth_type: &types::ConnectorAuthType,
+) -> RouterResult<()> {
+    let auth_type = utils::Encode::<types::ConnectorAuthType>::encode_to_string_of_json(auth_type)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode connector credentials")?;
+    let encrypted_auth_type = encrypt_connector_secret(state, auth_type.as_bytes())
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encrypt connector credentials")?;
+
+    insert_or_update_config(
+        &*state.store,
+        get_connector_credentials_key(merchant_id, connector_name),
+        encrypted_auth_type,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store connector credentials")
+}
+
+/// Connector credentials to keep in the merchant connector account once they are stored
+/// encrypted, which only tell the kind of credentials the connector uses.
+pub fn get_redacted_account_details(
+    auth_type: &types::ConnectorAuthType,
+) -> RouterResult<masking::Secret<serde_json::Value>> {
+    let auth_type = utils::Encode::<types::ConnectorAuthType>::encode_to_value(auth_type)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode connector credentials")?;
+    Ok(masking::Secret::new(
+        serde_json::json!({ "auth_type": auth_type.get("auth_type") }),
+    ))
+}
+
+fn decrypt_connector_auth_type(
+    state: &AppState,
+    encrypted_auth_type: String,
+) -> CustomResult<types::ConnectorAuthType, errors::ConnectorError> {
+    decrypt_connector_secret(state, encrypted_auth_type)
+        .change_context(errors::ConnectorError::FailedToObtainAuthType)
+        .attach_printable("Failed to decrypt connector credentials")?
+        .parse_struct("ConnectorAuthType")
+        .change_context(errors::ConnectorError::FailedToObtainAuthType)
+}
+
+/// Credentials to call the connector of the merchant connector account with. Accounts created
+/// before credentials were stored encrypted still carry them in `connector_account_details`.
+#[instrument(skip_all)]
+pub async fn get_connector_auth_type(
+    state: &AppState,
+    merchant_connector_account: &storage::MerchantConnectorAccount,
+) -> RouterResult<types::ConnectorAuthType> {
+    match state
+        .store
+        .find_config_by_key(&get_connector_credentials_key(
+            &merchant_connector_account.merchant_id,
+            &merchant_connector_account.connector_name,
+        ))
+        .await
+    {
+        Ok(config) => decrypt_connector_auth_type(state, config.config)
+            .change_context(errors::ApiErrorResponse::InternalServerError),
+        Err(error) if error.current_context().is_db_not_found() => merchant_connector_account
+            .connector_account_details
+            .clone()
+            .parse_value("ConnectorAuthType")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while parsing value for ConnectorAuthType"),
+        Err(error) => Err(error.change_context(errors::ApiErrorResponse::InternalServerError))
+            .attach_printable("Failed to fetch connector credentials"),
+    }
+}
+
+/// Fetches and decrypts the credentials of the merchant for the connector.
+#[instrument(skip_all)]
+pub async fn find_connector_auth_type(
+    state: &AppState,
+    merchant_id: &str,
+    connector_name: &str,
+) -> CustomResult<types::ConnectorAuthType, errors::ConnectorError> {
+    let encrypted_auth_type = state
+        .store
+        .find_config_by_key(&get_connector_credentials_key(merchant_id, connector_name))
+        .await
+        .change_context(errors::ConnectorError::FailedToObtainAuthType)
+        .attach_printable_lazy(|| {
+            format!(
+                "No credentials found for merchant {merchant_id} and connector {connector_name}"
+            )
+        })?
+        .config;
+
+    decrypt_connector_auth_type(state, encrypted_auth_type)
+}
+
+/// Encrypts and stores the secret the connector signs the webhooks of the merchant with,
+/// replacing any secret stored earlier.
+#[instrument(skip_all)]
+pub async fn store_webhook_source_verification_secret(
+    state: &AppState,
+    merchant_id: &str,
+    connector_name: &str,
+    secret: &[u8],
+) -> RouterResult<()> {
+    let encrypted_secret = encrypt_connector_secret(state, secret)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encrypt webhook secret")?;
+
+    insert_or_update_config(
+        &*state.store,
+        get_webhook_secret_key(merchant_id, connector_name),
+        encrypted_secret,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store webhook secret")
+}
+
+/// Fetches and decrypts the secret used to verify the source of the connector's webhooks.
+/// Secrets stored in plain text in redis at `legacy_redis_key`, where they were kept earlier, are
+/// moved to the encrypted store the first time they are used.
+#[instrument(skip_all)]
+pub async fn find_webhook_source_verification_secret(
+    state: &AppState,
+    merchant_id: &str,
+    connector_name: &str,
+    legacy_redis_key: &str,
+) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+    match state
+        .store
+        .find_config_by_key(&get_webhook_secret_key(merchant_id, connector_name))
+        .await
+    {
+        Ok(config) => decrypt_connector_secret(state, config.config)
+            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)
+            .attach_printable("Failed to decrypt webhook secret"),
+        Err(error) if error.current_context().is_db_not_found() => {
+            migrate_legacy_webhook_secret(state, merchant_id, connector_name, legacy_redis_key)
+                .await
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)
+            .attach_printable("Failed to fetch webhook secret"),
+    }
+}
+
+async fn migrate_legacy_webhook_secret(
+    state: &AppState,
+    merchant_id: &str,
+    connector_name: &str,
+    legacy_redis_key: &str,
+) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+    let secret = state
+        .store
+        .get_key(legacy_redis_key)
+        .await
+        .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)?;
+    utils::when(secret.is_empty(), || {
+        Err(errors::ConnectorError::WebhookVerificationSecretNotFound)
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!(
+                    "No webhook secret found for merchant {merchant_id} and connector {connector_name}"
+                )
+            })
+    })?;
+
+    store_webhook_source_verification_secret(state, merchant_id, connector_name, &secret)
+        .await
+        .change_context(errors::ConnectorError::WebhookVerificationSecretNotFound)
+        .attach_printable("Failed to move the webhook secret out of redis")?;
+    if let Err(error) = state.store.delete_key(legacy_redis_key).await {
+        logger::error!(
+            ?error,
+            "Failed to delete the plain text webhook secret from redis"
+        );
+    }
+
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::panic)]
+
+    use masking::ExposeInterface;
+
+    use super::*;
+    use crate::db::StorageImpl;
+
+    #[actix_rt::test]
+    async fn test_stored_credentials_are_encrypted_and_decrypted() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let auth_type = types::ConnectorAuthType::BodyKey {
+            api_key: "vendor_name".to_string(),
+            key1: "integration_key".to_string(),
+        };
+
+        store_connector_auth_type(&state, "merchant_1", "opayo", &auth_type)
+            .await
+            .expect("Failed to store connector credentials");
+
+        let stored_value = state
+            .store
+            .find_config_by_key("connector_credentials_merchant_1_opayo")
+            .await
+            .expect("Connector credentials were not stored")
+            .config;
+        assert!(!stored_value.contains("integration_key"));
+
+        let auth_type = find_connector_auth_type(&state, "merchant_1", "opayo")
+            .await
+            .expect("Failed to find connector credentials");
+        match auth_type {
+            types::ConnectorAuthType::BodyKey { api_key, key1 } => {
+                assert_eq!(api_key, "vendor_name");
+                assert_eq!(key1, "integration_key");
+            }
+            auth_type => panic!("Unexpected connector auth type: {auth_type:?}"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_missing_credentials_fail_to_obtain_auth_type() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        store_connector_auth_type(
+            &state,
+            "merchant_1",
+            "opayo",
+            &types::ConnectorAuthType::HeaderKey {
+                api_key: "api_key".to_string(),
+            },
+        )
+        .await
+        .expect("Failed to store connector credentials");
+
+        let error = find_connector_auth_type(&state, "merchant_1", "stripe")
+            .await
+            .expect_err("Found credentials for a connector that was not configured");
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::FailedToObtainAuthType
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_auth_type_is_resolved_from_the_encrypted_credentials() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let auth_type = types::ConnectorAuthType::HeaderKey {
+            api_key: "encrypted_api_key".to_string(),
+        };
+        let get_merchant_connector_account =
+            |connector_name: &str| storage::MerchantConnectorAccount {
+                id: 1,
+                merchant_id: "merchant_1".to_string(),
+                connector_name: connector_name.to_string(),
+                connector_account_details: serde_json::json!({
+                    "auth_type": "HeaderKey",
+                    "api_key": "plaintext_api_key",
+                }),
+                test_mode: None,
+                disabled: None,
+                merchant_connector_id: "mca_1".to_string(),
+                payment_methods_enabled: None,
+                connector_type: storage::enums::ConnectorType::PaymentProcessor,
+                metadata: None,
+            };
+        store_connector_auth_type(&state, "merchant_1", "stripe", &auth_type)
+            .await
+            .expect("Failed to store connector credentials");
+        assert_eq!(
+            get_redacted_account_details(&auth_type)
+                .expect("Failed to redact connector credentials")
+                .expose(),
+            serde_json::json!({ "auth_type": "HeaderKey" })
+        );
+
+        let auth_type = get_connector_auth_type(&state, &get_merchant_connector_account("stripe"))
+            .await
+            .expect("Failed to resolve connector credentials");
+        assert!(matches!(
+            auth_type,
+            types::ConnectorAuthType::HeaderKey { api_key } if api_key == "encrypted_api_key"
+        ));
+
+        // Accounts created before credentials were encrypted keep working
+        let auth_type = get_connector_auth_type(&state, &get_merchant_connector_account("adyen"))
+            .await
+            .expect("Failed to resolve connector credentials");
+        assert!(matches!(
+            auth_type,
+            types::ConnectorAuthType::HeaderKey { api_key } if api_key == "plaintext_api_key"
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_webhook_secret_is_found() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        store_webhook_source_verification_secret(&state, "merchant_1", "stripe", b"whsec_test")
+            .await
+            .expect("Failed to store webhook secret");
+
+        let secret = find_webhook_source_verification_secret(
+            &state,
+            "merchant_1",
+            "stripe",
+            "whsec_verification_stripe_merchant_1",
+        )
+        .await
+        .expect("Failed to find webhook secret");
+        assert_eq!(secret, b"whsec_test");
+    }
+
+    #[actix_rt::test]
+    async fn test_missing_webhook_secret_is_not_found() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        store_webhook_source_verification_secret(&state, "merchant_1", "stripe", b"whsec_test")
+            .await
+            .expect("Failed to store webhook secret");
+
+        let error = find_webhook_source_verification_secret(
+            &state,
+            "merchant_2",
+            "stripe",
+            "whsec_verification_stripe_merchant_2",
+        )
+        .await
+        .expect_err("Found a webhook secret of another merchant");
+        assert!(matches!(
+            error.current_context(),
+            errors::ConnectorError::WebhookVerificationSecretNotFound
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::{
+        errors::{self, ConnectorErrorExt, RouterResponse, StorageErrorExt},
+        payments::{self, access_token},
+        utils as core_utils,
+    },
+    logger,
+    routes::AppState,
+    services,
+    types::{
+        self, api,
+        storage::{self, enums},
+        transformers::{Foreign, ForeignInto},
+    },
+};
+
+#[instrument(skip_all)]
+pub async fn submit_evidence(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    dispute_id: &str,
+    req: api::SubmitEvidenceRequest,
+) -> RouterResponse<api::DisputeResponse> {
+    let db = &*state.store;
+    let dispute = db
+        .find_dispute_by_merchant_id_dispute_id(&merchant_account.merchant_id, dispute_id)
+        .await
+        .map_err(|error| error.to_not_found_response(errors::ApiErrorResponse::DisputeNotFound))?;
+
+    if dispute.dispute_status != enums::DisputeStatus::Opened {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Evidence cannot be submitted for a dispute that is {}",
+                dispute.dispute_status
+            ),
+        })?;
+    }
+
+    let payment_attempt = db
+        .find_payment_attempt_by_merchant_id_attempt_id(
+            &merchant_account.merchant_id,
+            &dispute.attempt_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .map_err(|error| error.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound))?;
+
+    let connector = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        &dispute.connector,
+        api::GetToken::Connector,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to get the connector")?;
+
+    let mut router_data = core_utils::construct_submit_evidence_router_data(
+        state,
+        &merchant_account,
+        &payment_attempt,
+        &dispute,
+        req,
+    )
+    .await?;
+
+    connector
+        .connector
+        .get_capabilities()
+        .validate(
+            &dispute.connector,
+            &[api::ConnectorFlow::SubmitEvidence],
+            router_data.payment_method,
+            None,
+        )
+        .map_err(|error| error.to_evidence_submission_failed_response())?;
+
+    let add_access_token_result =
+        access_token::add_access_token(state, &connector, &merchant_account, &router_data).await?;
+
+    access_token::update_router_data_with_access_token_result(
+        &add_access_token_result,
+        &mut router_data,
+        &payments::CallConnectorAction::Trigger,
+    );
+
+    let router_data_res = if !(add_access_token_result.connector_supports_access_token
+        && router_data.access_token.is_none())
+    {
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::Evidence,
+            types::SubmitEvidenceRequestData,
+            types::SubmitEvidenceResponse,
+        > = connector.connector.get_connector_integration();
+        services::execute_connector_processing_step(
+            state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+        )
+        .await
+        .map_err(|error| error.to_evidence_submission_failed_response())?
+    } else {
+        router_data
+    };
+
+    let (evidence_status, connector_error) = match router_data_res.response {
+        Ok(response) => (response.evidence_status, None),
+        Err(error) => {
+            logger::error!(?error, %dispute_id, "Connector rejected the dispute evidence");
+            (enums::EvidenceStatus::Failed, Some(error))
+        }
+    };
+
+    let dispute = db
+        .update_dispute(
+            dispute,
+            storage::DisputeUpdate::EvidenceUpdate { evidence_status },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Failed while updating dispute: {dispute_id}"))?;
+
+    if let Some(error) = connector_error {
+        return Err(errors::ApiErrorResponse::ExternalConnectorError {
+            code: error.code,
+            message: error.message,
+            connector: dispute.connector,
+            status_code: error.status_code,
+        })
+        .into_report();
+    }
+
+    Ok(services::ApplicationResponse::Json(dispute.foreign_into()))
+}
+
+impl From<Foreign<storage::Dispute>> for Foreign<api::DisputeResponse> {
+    fn from(dispute: Foreign<storage::Dispute>) -> Self {
+        let dispute = dispute.0;
+        api::DisputeResponse {
+            dispute_id: dispute.dispute_id,
+            payment_id: dispute.payment_id,
+            attempt_id: dispute.attempt_id,
+            connector: dispute.connector,
+            connector_dispute_id: dispute.connector_dispute_id,
+            connector_reason_code: dispute.connector_reason_code,
+            amount: dispute.amount,
+            currency: dispute.currency,
+            dispute_status: dispute.dispute_status.foreign_into(),
+            evidence_status: dispute.evidence_status.map(ForeignInto::foreign_into),
+            created_at: dispute.created_at,
+        }
+        .into()
+    }
+}
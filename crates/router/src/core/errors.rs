@@ -69,6 +69,8 @@ pub enum StorageError {
     CustomerRedacted,
     #[error("Deserialization failure")]
     DeserializationFailed,
+    #[error("Invalid pagination cursor")]
+    InvalidCursor,
 }
 
 impl From<error_stack::Report<storage_errors::DatabaseError>> for StorageError {
@@ -101,6 +103,16 @@ impl StorageError {
 
 impl_error_type!(EncryptionError, "Encryption error");
 
+#[derive(Debug, thiserror::Error)]
+pub enum HealthCheckError {
+    #[error("Failed to obtain a database connection")]
+    DatabaseConnectionFailed,
+    #[error("Failed to run a query against the database")]
+    DatabaseQueryFailed,
+    #[error("Failed to reach Redis")]
+    RedisUnreachable,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ApplicationError {
     // Display's impl can be overridden by the attribute error marco.
@@ -142,11 +154,34 @@ impl From<ConfigError> for ApplicationError {
     }
 }
 
-fn error_response<T: Display>(err: &T) -> actix_web::HttpResponse {
+impl ApplicationError {
+    /// Stable, machine-readable code for the error, included in the error response body.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::ConfigurationError(_) => "configuration_error",
+            Self::InvalidConfigurationValueError(_) => "invalid_configuration_value",
+            Self::MetricsError(_) => "metrics_error",
+            Self::IoError(_) => "io_error",
+        }
+    }
+}
+
+fn error_response<T: Display>(
+    err: &T,
+    code: &str,
+    error_type: api_error_response::ErrorType,
+) -> actix_web::HttpResponse {
+    let body = serde_json::json!({
+        "error": {
+            "type": error_type,
+            "code": code,
+            "message": err.to_string(),
+        }
+    });
     actix_web::HttpResponse::BadRequest()
         .append_header(("Via", "Juspay_Router"))
         .content_type("application/json")
-        .body(format!(r#"{{ "error": {{ "message": "{err}" }} }}"#))
+        .body(body.to_string())
 }
 
 impl ResponseError for ApplicationError {
@@ -160,7 +195,11 @@ impl ResponseError for ApplicationError {
     }
 
     fn error_response(&self) -> actix_web::HttpResponse {
-        error_response(self)
+        error_response(
+            self,
+            self.error_code(),
+            api_error_response::ErrorType::RouterError,
+        )
     }
 }
 
@@ -186,11 +225,17 @@ pub enum ApiClientError {
     UrlEncodingFailed,
     #[error("Failed to send request to connector {0}")]
     RequestNotSent(String),
+    #[error("Failed to connect to connector {0}")]
+    ConnectionFailed(String),
     #[error("Failed to decode response")]
     ResponseDecodingFailed,
 
     #[error("Server responded with Request Timeout")]
     RequestTimeoutReceived,
+    #[error("Server responded with Too Many Requests")]
+    TooManyRequestsReceived {
+        retry_after: Option<std::time::Duration>,
+    },
 
     #[error("Server responded with Internal Server Error")]
     InternalServerErrorReceived,
@@ -204,6 +249,63 @@ pub enum ApiClientError {
     UnexpectedServerResponse,
 }
 
+impl ApiClientError {
+    /// Transient failures for which the request can be sent again to the connector.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RequestTimeoutReceived
+                | Self::BadGatewayReceived
+                | Self::ServiceUnavailableReceived
+                | Self::GatewayTimeoutReceived
+        )
+    }
+
+    /// Failures to reach the connector or server errors it responded with, which may go away when
+    /// the request is sent again later.
+    pub fn is_transient(&self) -> bool {
+        self.is_retryable()
+            || matches!(
+                self,
+                Self::RequestNotSent(_)
+                    | Self::ConnectionFailed(_)
+                    | Self::InternalServerErrorReceived
+                    | Self::TooManyRequestsReceived { .. }
+            )
+    }
+
+    /// Upstream statuses that are surfaced as-is to the caller instead of a generic server error.
+    pub fn upstream_status_code(&self) -> Option<u16> {
+        match self {
+            Self::TooManyRequestsReceived { .. } => Some(429),
+            Self::GatewayTimeoutReceived => Some(504),
+            _ => None,
+        }
+    }
+
+    /// Time the connector asked us to wait before sending another request.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            Self::TooManyRequestsReceived { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Connector error that a failed connector request is reported as, which tells whether the
+    /// request may succeed when it is tried again later.
+    pub fn get_connector_error(&self) -> ConnectorError {
+        match self.upstream_status_code() {
+            Some(status_code) => ConnectorError::UpstreamErrorReceived {
+                status_code,
+                retry_after: self.retry_after(),
+            },
+            None if matches!(self, Self::RequestTimeoutReceived) => ConnectorError::RequestTimedOut,
+            None if self.is_transient() => ConnectorError::TemporarilyUnavailable,
+            None => ConnectorError::ProcessingStepFailed(None),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error, PartialEq)]
 pub enum ConnectorError {
     #[error("Error while obtaining URL for the integration")]
@@ -216,24 +318,40 @@ pub enum ConnectorError {
     ResponseDeserializationFailed,
     #[error("Failed to execute a processing step: {0:?}")]
     ProcessingStepFailed(Option<bytes::Bytes>),
+    #[error("The connector responded with status code {status_code}")]
+    UpstreamErrorReceived {
+        status_code: u16,
+        retry_after: Option<std::time::Duration>,
+    },
     #[error("The connector returned an unexpected response: {0:?}")]
     UnexpectedResponseError(bytes::Bytes),
     #[error("Failed to parse custom routing rules from merchant account")]
     RoutingRulesParsingError,
     #[error("Failed to obtain preferred connector from merchant account")]
     FailedToObtainPreferredConnector,
-    #[error("An invalid connector name was provided")]
-    InvalidConnectorName,
+    #[error("{0}")]
+    InvalidConnectorName(api_models::enums::ConnectorParseError),
     #[error("Failed to handle connector response")]
     ResponseHandlingFailed,
     #[error("Missing required field: {field_name}")]
     MissingRequiredField { field_name: &'static str },
+    #[error("The currency {currency} is not supported by {connector}")]
+    CurrencyNotSupported { currency: String, connector: String },
+    #[error("The {flow} flow is not supported by {connector}")]
+    FlowNotSupported { flow: String, connector: String },
+    #[error("The payment method {payment_method} is not supported by {connector}")]
+    PaymentMethodNotSupported {
+        payment_method: String,
+        connector: String,
+    },
     #[error("Failed to obtain authentication type")]
     FailedToObtainAuthType,
     #[error("Failed to obtain certificate")]
     FailedToObtainCertificate,
     #[error("Connector meta data not found")]
     NoConnectorMetaData,
+    #[error("Invalid connector metadata for {connector}")]
+    InvalidConnectorMetadata { connector: String },
     #[error("Failed to obtain certificate key")]
     FailedToObtainCertificateKey,
     #[error("This step has not been implemented for: {0}")]
@@ -260,6 +378,37 @@ pub enum ConnectorError {
     WebhookResourceObjectNotFound,
     #[error("Invalid Date/time format")]
     InvalidDateFormat,
+    #[error("Requests to {connector} are paused after repeated failures")]
+    CircuitBreakerOpen { connector: String },
+    #[error("The connector is temporarily unable to process the request")]
+    TemporarilyUnavailable,
+    #[error("Timed out waiting for the response of the connector")]
+    RequestTimedOut,
+}
+
+/// Whether a failed connector step may succeed when it is tried again later.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConnectorErrorType {
+    /// The connector could not be reached or could not process the request at the time
+    Transient,
+    /// The step fails the same way however often it is tried
+    Permanent,
+}
+
+impl ConnectorError {
+    pub fn get_error_type(&self) -> ConnectorErrorType {
+        match self {
+            Self::TemporarilyUnavailable
+            | Self::RequestTimedOut
+            | Self::CircuitBreakerOpen { .. } => ConnectorErrorType::Transient,
+            Self::UpstreamErrorReceived { status_code, .. }
+                if matches!(status_code, 408 | 429 | 500..=599) =>
+            {
+                ConnectorErrorType::Transient
+            }
+            _ => ConnectorErrorType::Permanent,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -280,6 +429,8 @@ pub enum VaultError {
     MissingRequiredField { field_name: &'static str },
     #[error("The card vault returned an unexpected response: {0:?}")]
     UnexpectedResponseError(bytes::Bytes),
+    #[error("Failed to exchange the card for a network token")]
+    NetworkTokenizationFailed,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -326,6 +477,21 @@ pub enum ProcessTrackerError {
     EParsingError(error_stack::Report<ParsingError>),
     #[error("Validation Error Received: {0}")]
     EValidationError(error_stack::Report<ValidationError>),
+    #[error("Received Error WebhooksFlowError: {0}")]
+    EWebhooksFlowError(error_stack::Report<WebhooksFlowError>),
+}
+
+impl ProcessTrackerError {
+    /// Type of the connector error the process failed with, `None` if it did not fail while
+    /// calling a connector.
+    pub fn get_connector_error_type(&self) -> Option<ConnectorErrorType> {
+        match self {
+            Self::EApiErrorResponse(error) => error
+                .downcast_ref::<ConnectorError>()
+                .map(ConnectorError::get_error_type),
+            _ => None,
+        }
+    }
 }
 
 macro_rules! error_to_process_tracker_error {
@@ -371,6 +537,11 @@ error_to_process_tracker_error!(
     ProcessTrackerError::EValidationError(error_stack::Report<ValidationError>)
 );
 
+error_to_process_tracker_error!(
+    error_stack::Report<WebhooksFlowError>,
+    ProcessTrackerError::EWebhooksFlowError(error_stack::Report<WebhooksFlowError>)
+);
+
 #[derive(Debug, thiserror::Error)]
 pub enum WebhooksFlowError {
     #[error("Merchant webhook config not found")]
@@ -381,14 +552,26 @@ pub enum WebhooksFlowError {
     MerchantWebhookURLNotConfigured,
     #[error("Payments core flow failed")]
     PaymentsCoreFailed,
+    #[error("Object referenced by the webhook not found")]
+    WebhookObjectNotFound,
+    #[error("Disputes core flow failed")]
+    DisputesCoreFailed,
+    #[error("Refunds core flow failed")]
+    RefundsCoreFailed,
     #[error("Webhook event creation failed")]
     WebhookEventCreationFailed,
     #[error("Unable to fork webhooks flow for outgoing webhooks")]
     ForkFlowFailed,
     #[error("Webhook api call to merchant failed")]
     CallToMerchantFailed,
-    #[error("Webhook not received by merchant")]
-    NotReceivedByMerchant,
+    #[error("Webhook not received by merchant, responded with status code {status_code}")]
+    NotReceivedByMerchant { status_code: u16 },
+    #[error("Failed to encode outgoing webhook")]
+    OutgoingWebhookEncodingFailed,
+    #[error("Failed to sign outgoing webhook")]
+    OutgoingWebhookSigningFailed,
+    #[error("Failed to schedule outgoing webhook for retry")]
+    OutgoingWebhookRetrySchedulingFailed,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -398,3 +581,74 @@ pub enum ApiKeyError {
     #[error("Failed to verify provided API key hash against stored API key hash")]
     HashVerificationFailed,
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum FxQuoteError {
+    #[error("No FX rate is available from {from} to {to}")]
+    RateUnavailable {
+        from: storage_models::enums::Currency,
+        to: storage_models::enums::Currency,
+    },
+    #[error("Invalid FX rate {rate}")]
+    InvalidRate { rate: String },
+    #[error("FX quote is {age} seconds old, more than the {max_age} seconds allowed")]
+    StaleQuote { age: i64, max_age: i64 },
+    #[error("Settlement amount is out of range")]
+    AmountOverflow,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use actix_web::ResponseError;
+
+    use super::ApplicationError;
+
+    async fn get_error_body(error: ApplicationError) -> serde_json::Value {
+        let response = error.error_response();
+        assert_eq!(response.headers().get("Via").unwrap(), "Juspay_Router");
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn test_application_error_response_carries_code_and_type() {
+        let errors = [
+            (
+                ApplicationError::ConfigurationError(config::ConfigError::Message(
+                    "missing field".to_string(),
+                )),
+                "configuration_error",
+            ),
+            (
+                ApplicationError::InvalidConfigurationValueError("port".to_string()),
+                "invalid_configuration_value",
+            ),
+            (
+                ApplicationError::MetricsError(
+                    router_env::opentelemetry::metrics::MetricsError::Other(
+                        "exporter unavailable".to_string(),
+                    ),
+                ),
+                "metrics_error",
+            ),
+            (
+                ApplicationError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "broken pipe",
+                )),
+                "io_error",
+            ),
+        ];
+
+        for (error, code) in errors {
+            let message = error.to_string();
+            let body = get_error_body(error).await;
+            assert_eq!(body["error"]["code"], code);
+            assert_eq!(body["error"]["type"], "router_error");
+            assert_eq!(body["error"]["message"], message);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code, unused_variables)]
 
-#[derive(Clone, Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ErrorType {
     InvalidRequestError,
@@ -14,6 +14,26 @@ pub enum ErrorType {
     ConnectorError,
 }
 
+/// The body of the error responses returned by the API, as produced by [`ApiErrorResponse`]. Only
+/// used to describe the error responses in the OpenAPI specification.
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct ErrorResponseBody {
+    pub error: ErrorDetails,
+}
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct ErrorDetails {
+    /// The category of the error
+    #[serde(rename = "type")]
+    pub error_type: ErrorType,
+    /// The code identifying the error
+    #[schema(example = "IR_04")]
+    pub code: String,
+    /// A human readable description of the error
+    #[schema(example = "Missing required param: amount")]
+    pub message: String,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, router_derive::ApiError)]
 #[error(error_type_enum = ErrorType)]
@@ -78,6 +98,16 @@ pub enum ApiErrorResponse {
         message = "{message}",
     )]
     GenericUnauthorized { message: String },
+    #[error(
+        error_type = ErrorType::InvalidRequestError, code = "IR_19",
+        message = "Too many requests were sent with this API key. Retry after {retry_after} seconds"
+    )]
+    RateLimited { retry_after: u64 },
+    #[error(
+        error_type = ErrorType::InvalidRequestError, code = "IR_20",
+        message = "The key provided is not allowed to access this API"
+    )]
+    AccessForbidden,
 
     #[error(error_type = ErrorType::ConnectorError, code = "CE_00", message = "{code}: {message}", ignore = "status_code")]
     ExternalConnectorError {
@@ -100,6 +130,14 @@ pub enum ApiErrorResponse {
     RefundFailed { data: Option<serde_json::Value> },
     #[error(error_type = ErrorType::ProcessingError, code = "CE_07", message = "Verification failed while processing with connector. Retry operation")]
     VerificationFailed { data: Option<serde_json::Value> },
+    #[error(error_type = ErrorType::ConnectorError, code = "CE_08", message = "Too many requests sent to the connector. Retry after some time")]
+    ConnectorRateLimited { retry_after: Option<u64> },
+    #[error(error_type = ErrorType::BadGateway, code = "CE_09", message = "The connector did not respond in time. Retry operation")]
+    ConnectorTimeout,
+    #[error(error_type = ErrorType::ServerNotAvailable, code = "CE_10", message = "The connector is temporarily unavailable. Retry after some time")]
+    ConnectorUnavailable,
+    #[error(error_type = ErrorType::ProcessingError, code = "CE_11", message = "Evidence submission failed while processing with connector. Retry operation")]
+    EvidenceSubmissionFailed { data: Option<serde_json::Value> },
 
     #[error(error_type = ErrorType::ServerNotAvailable, code = "HE_00", message = "Something went wrong")]
     InternalServerError,
@@ -115,10 +153,14 @@ pub enum ApiErrorResponse {
     DuplicatePaymentMethod,
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The payment with the specified payment_id '{payment_id}' already exists in our records")]
     DuplicatePayment { payment_id: String },
+    #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "A request with the same idempotency key is still being processed")]
+    IdempotentRequestInProgress,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Refund does not exist in our records")]
     RefundNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Customer does not exist in our records")]
     CustomerNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Dispute does not exist in our records")]
+    DisputeNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "RE_02", message = "Config key does not exist in our records.")]
     ConfigNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payment does not exist in our records")]
@@ -135,6 +177,8 @@ pub enum ApiErrorResponse {
     MandateNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "API Key does not exist in our records")]
     ApiKeyNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Webhook event does not exist in our records")]
+    WebhookEventNotFound,
     #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "Return URL is not configured and not passed in payments request")]
     ReturnUrlUnavailable,
     #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "This refund is not possible through Hyperswitch. Please raise the refund through {connector} dashboard")]
@@ -143,6 +187,8 @@ pub enum ApiErrorResponse {
     MandateValidationFailed { reason: String },
     #[error(error_type= ErrorType::ValidationError, code = "HE_03", message = "The payment has not succeeded yet. Please pass a successful payment to initiate refund")]
     PaymentNotSucceeded,
+    #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "The amount to capture exceeds the amount of {amount_capturable} left to capture on this payment")]
+    CaptureAmountExceedsCapturableAmount { amount_capturable: i64 },
     #[error(error_type= ErrorType::ObjectNotFound, code = "HE_04", message = "Successful payment not found for the given payment id")]
     SuccessfulPaymentNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_04", message = "The connector provided in the request is incorrect or not available")]
@@ -190,6 +236,7 @@ impl actix_web::ResponseError for ApiErrorResponse {
             | Self::InvalidEphemeralKey
             | Self::InvalidJwtToken
             | Self::GenericUnauthorized { .. } => StatusCode::UNAUTHORIZED, // 401
+            Self::AccessForbidden => StatusCode::FORBIDDEN, // 403
             Self::ExternalConnectorError { status_code, .. } => {
                 StatusCode::from_u16(*status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
             }
@@ -204,6 +251,7 @@ impl actix_web::ResponseError for ApiErrorResponse {
             Self::RefundAmountExceedsPaymentAmount => StatusCode::BAD_REQUEST, // 400
             Self::MaximumRefundCount => StatusCode::BAD_REQUEST, // 400
             Self::PreconditionFailed { .. } => StatusCode::BAD_REQUEST, // 400
+            Self::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS, // 429
 
             Self::PaymentAuthorizationFailed { .. }
             | Self::PaymentAuthenticationFailed { .. }
@@ -213,13 +261,19 @@ impl actix_web::ResponseError for ApiErrorResponse {
             | Self::RefundFailed { .. }
             | Self::RefundNotPossible { .. }
             | Self::VerificationFailed { .. }
+            | Self::EvidenceSubmissionFailed { .. }
             | Self::PaymentUnexpectedState { .. }
             | Self::MandateValidationFailed { .. } => StatusCode::BAD_REQUEST, // 400
 
             Self::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR, // 500
+            Self::ConnectorRateLimited { .. } => StatusCode::TOO_MANY_REQUESTS, // 429
+            Self::ConnectorTimeout => StatusCode::GATEWAY_TIMEOUT,          // 504
+            Self::ConnectorUnavailable => StatusCode::SERVICE_UNAVAILABLE,  // 503
             Self::DuplicateRefundRequest | Self::DuplicatePayment { .. } => StatusCode::BAD_REQUEST, // 400
+            Self::IdempotentRequestInProgress => StatusCode::CONFLICT, // 409
             Self::RefundNotFound
             | Self::CustomerNotFound
+            | Self::DisputeNotFound
             | Self::MandateActive
             | Self::CustomerRedacted
             | Self::PaymentNotFound
@@ -234,23 +288,35 @@ impl actix_web::ResponseError for ApiErrorResponse {
             | Self::ResourceIdNotFound
             | Self::ConfigNotFound
             | Self::AddressNotFound
-            | Self::ApiKeyNotFound => StatusCode::BAD_REQUEST, // 400
+            | Self::ApiKeyNotFound
+            | Self::WebhookEventNotFound => StatusCode::BAD_REQUEST, // 400
             Self::DuplicateMerchantAccount
             | Self::DuplicateMerchantConnectorAccount
             | Self::DuplicatePaymentMethod
             | Self::DuplicateMandate => StatusCode::BAD_REQUEST, // 400
             Self::ReturnUrlUnavailable => StatusCode::SERVICE_UNAVAILABLE, // 503
-            Self::PaymentNotSucceeded => StatusCode::BAD_REQUEST,          // 400
-            Self::NotImplemented { .. } => StatusCode::NOT_IMPLEMENTED,    // 501
+            Self::PaymentNotSucceeded => StatusCode::BAD_REQUEST,      // 400
+            Self::CaptureAmountExceedsCapturableAmount { .. } => StatusCode::BAD_REQUEST, // 400
+            Self::NotImplemented { .. } => StatusCode::NOT_IMPLEMENTED, // 501
         }
     }
 
     fn error_response(&self) -> actix_web::HttpResponse {
         use actix_web::http::header;
 
-        actix_web::HttpResponseBuilder::new(self.status_code())
+        let mut response = actix_web::HttpResponseBuilder::new(self.status_code());
+        response
             .insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON))
-            .insert_header((header::VIA, "Juspay_Router"))
-            .body(self.to_string())
+            .insert_header((header::VIA, "Juspay_Router"));
+        match self {
+            Self::ConnectorRateLimited {
+                retry_after: Some(retry_after),
+            }
+            | Self::RateLimited { retry_after } => {
+                response.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+            }
+            _ => (),
+        }
+        response.body(self.to_string())
     }
 }
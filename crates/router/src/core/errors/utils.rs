@@ -24,6 +24,11 @@ impl StorageErrorExt for error_stack::Report<errors::StorageError> {
             errors::StorageError::CustomerRedacted => {
                 self.change_context(errors::ApiErrorResponse::CustomerRedacted)
             }
+            errors::StorageError::InvalidCursor => {
+                self.change_context(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "cursor is invalid".to_string(),
+                })
+            }
             _ => self.change_context(errors::ApiErrorResponse::InternalServerError),
         }
     }
@@ -40,14 +45,61 @@ impl StorageErrorExt for error_stack::Report<errors::StorageError> {
     }
 }
 
+/// Upstream failures that are surfaced with their own status instead of a flow specific error.
+fn get_upstream_error_response(error: &errors::ConnectorError) -> Option<errors::ApiErrorResponse> {
+    match error {
+        errors::ConnectorError::UpstreamErrorReceived {
+            status_code: 429,
+            retry_after,
+        } => Some(errors::ApiErrorResponse::ConnectorRateLimited {
+            retry_after: retry_after.map(|retry_after| retry_after.as_secs()),
+        }),
+        errors::ConnectorError::UpstreamErrorReceived {
+            status_code: 504, ..
+        }
+        | errors::ConnectorError::RequestTimedOut => {
+            Some(errors::ApiErrorResponse::ConnectorTimeout)
+        }
+        errors::ConnectorError::CircuitBreakerOpen { .. } => {
+            Some(errors::ApiErrorResponse::ConnectorUnavailable)
+        }
+        _ => None,
+    }
+}
+
+/// Requests the connector cannot handle, these are rejected as invalid before reaching it.
+fn get_unsupported_request_response(
+    error: &errors::ConnectorError,
+) -> Option<errors::ApiErrorResponse> {
+    match error {
+        errors::ConnectorError::CurrencyNotSupported { .. }
+        | errors::ConnectorError::FlowNotSupported { .. }
+        | errors::ConnectorError::PaymentMethodNotSupported { .. } => {
+            Some(errors::ApiErrorResponse::InvalidRequestData {
+                message: error.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
 pub trait ConnectorErrorExt {
     fn to_refund_failed_response(self) -> error_stack::Report<errors::ApiErrorResponse>;
     fn to_payment_failed_response(self) -> error_stack::Report<errors::ApiErrorResponse>;
     fn to_verify_failed_response(self) -> error_stack::Report<errors::ApiErrorResponse>;
+    fn to_evidence_submission_failed_response(
+        self,
+    ) -> error_stack::Report<errors::ApiErrorResponse>;
 }
 
 impl ConnectorErrorExt for error_stack::Report<errors::ConnectorError> {
     fn to_refund_failed_response(self) -> error_stack::Report<errors::ApiErrorResponse> {
+        if let Some(error) = get_upstream_error_response(self.current_context()) {
+            return self.change_context(error);
+        }
+        if let Some(error) = get_unsupported_request_response(self.current_context()) {
+            return self.change_context(error);
+        }
         let data = match self.current_context() {
             errors::ConnectorError::ProcessingStepFailed(Some(bytes)) => {
                 let response_str = std::str::from_utf8(bytes);
@@ -85,9 +137,21 @@ impl ConnectorErrorExt for error_stack::Report<errors::ConnectorError> {
                 };
                 errors::ApiErrorResponse::PaymentAuthorizationFailed { data }
             }
+            errors::ConnectorError::UpstreamErrorReceived { .. }
+            | errors::ConnectorError::RequestTimedOut
+            | errors::ConnectorError::CircuitBreakerOpen { .. } => {
+                get_upstream_error_response(self.current_context())
+                    .unwrap_or(errors::ApiErrorResponse::InternalServerError)
+            }
             errors::ConnectorError::MissingRequiredField { field_name } => {
                 errors::ApiErrorResponse::MissingRequiredField { field_name }
             }
+            errors::ConnectorError::CurrencyNotSupported { .. }
+            | errors::ConnectorError::FlowNotSupported { .. }
+            | errors::ConnectorError::PaymentMethodNotSupported { .. } => {
+                get_unsupported_request_response(self.current_context())
+                    .unwrap_or(errors::ApiErrorResponse::InternalServerError)
+            }
             errors::ConnectorError::NotImplemented(reason) => {
                 errors::ApiErrorResponse::NotImplemented {
                     message: errors::api_error_response::NotImplementedMessage::Reason(
@@ -101,6 +165,12 @@ impl ConnectorErrorExt for error_stack::Report<errors::ConnectorError> {
     }
 
     fn to_verify_failed_response(self) -> error_stack::Report<errors::ApiErrorResponse> {
+        if let Some(error) = get_upstream_error_response(self.current_context()) {
+            return self.change_context(error);
+        }
+        if let Some(error) = get_unsupported_request_response(self.current_context()) {
+            return self.change_context(error);
+        }
         let data = match self.current_context() {
             errors::ConnectorError::ProcessingStepFailed(Some(bytes)) => {
                 let response_str = std::str::from_utf8(bytes);
@@ -118,6 +188,33 @@ impl ConnectorErrorExt for error_stack::Report<errors::ConnectorError> {
         };
         self.change_context(errors::ApiErrorResponse::PaymentAuthorizationFailed { data })
     }
+
+    fn to_evidence_submission_failed_response(
+        self,
+    ) -> error_stack::Report<errors::ApiErrorResponse> {
+        if let Some(error) = get_upstream_error_response(self.current_context()) {
+            return self.change_context(error);
+        }
+        if let Some(error) = get_unsupported_request_response(self.current_context()) {
+            return self.change_context(error);
+        }
+        let data = match self.current_context() {
+            errors::ConnectorError::ProcessingStepFailed(Some(bytes)) => {
+                let response_str = std::str::from_utf8(bytes);
+                match response_str {
+                    Ok(s) => serde_json::from_str(s)
+                        .map_err(|err| logger::error!(%err, "Failed to convert response to JSON"))
+                        .ok(),
+                    Err(err) => {
+                        logger::error!(%err, "Failed to convert response to UTF8 string");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        self.change_context(errors::ApiErrorResponse::EvidenceSubmissionFailed { data })
+    }
 }
 
 pub trait RedisErrorExt {
@@ -134,3 +231,74 @@ impl RedisErrorExt for error_stack::Report<errors::RedisError> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use actix_web::ResponseError;
+    use error_stack::report;
+
+    use super::ConnectorErrorExt;
+    use crate::core::errors;
+
+    #[test]
+    fn test_too_many_requests_surfaces_as_429() {
+        let error = errors::ApiClientError::TooManyRequestsReceived {
+            retry_after: Some(std::time::Duration::from_secs(120)),
+        };
+        let payment_error = report!(error.get_connector_error()).to_payment_failed_response();
+        assert_eq!(payment_error.current_context().status_code().as_u16(), 429);
+        let refund_error = report!(error.get_connector_error()).to_refund_failed_response();
+        assert_eq!(refund_error.current_context().status_code().as_u16(), 429);
+        assert!(matches!(
+            refund_error.current_context(),
+            errors::ApiErrorResponse::ConnectorRateLimited {
+                retry_after: Some(120)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_gateway_timeout_surfaces_as_504() {
+        let error = errors::ApiClientError::GatewayTimeoutReceived;
+        let payment_error = report!(error.get_connector_error()).to_payment_failed_response();
+        assert_eq!(payment_error.current_context().status_code().as_u16(), 504);
+        let verify_error = report!(error.get_connector_error()).to_verify_failed_response();
+        assert_eq!(verify_error.current_context().status_code().as_u16(), 504);
+    }
+
+    #[test]
+    fn test_other_client_errors_are_internal_server_errors() {
+        let error = errors::ApiClientError::BadGatewayReceived;
+        let payment_error = report!(error.get_connector_error()).to_payment_failed_response();
+        assert_eq!(payment_error.current_context().status_code().as_u16(), 500);
+    }
+
+    #[test]
+    fn test_connector_error_types() {
+        for error in [
+            errors::ApiClientError::BadGatewayReceived,
+            errors::ApiClientError::InternalServerErrorReceived,
+            errors::ApiClientError::RequestNotSent("connection reset".to_string()),
+            errors::ApiClientError::ConnectionFailed("connection refused".to_string()),
+            errors::ApiClientError::GatewayTimeoutReceived,
+            errors::ApiClientError::RequestTimeoutReceived,
+            errors::ApiClientError::TooManyRequestsReceived { retry_after: None },
+        ] {
+            assert_eq!(
+                error.get_connector_error().get_error_type(),
+                errors::ConnectorErrorType::Transient,
+                "{error}"
+            );
+        }
+        for error in [
+            errors::ApiClientError::ResponseDecodingFailed,
+            errors::ApiClientError::UnexpectedServerResponse,
+        ] {
+            assert_eq!(
+                error.get_connector_error().get_error_type(),
+                errors::ConnectorErrorType::Permanent,
+                "{error}"
+            );
+        }
+    }
+}
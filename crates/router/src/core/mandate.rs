@@ -109,6 +109,7 @@ where
                 .find_mandate_by_merchant_id_mandate_id(resp.merchant_id.as_ref(), mandate_id)
                 .await
                 .change_context(errors::ApiErrorResponse::MandateNotFound)?;
+            let connector_mandate_id = get_mandate_reference(&resp.response);
             let mandate = match mandate.mandate_type {
                 storage_enums::MandateType::SingleUse => state
                     .store
@@ -136,6 +137,26 @@ where
                     .change_context(errors::ApiErrorResponse::MandateNotFound),
             }?;
 
+            // The connector may rotate the reference of a stored credential on every use
+            let mandate = match connector_mandate_id {
+                Some(connector_mandate_id)
+                    if mandate.connector_mandate_id.as_ref() != Some(&connector_mandate_id) =>
+                {
+                    state
+                        .store
+                        .update_mandate_by_merchant_id_mandate_id(
+                            &resp.merchant_id,
+                            mandate_id,
+                            storage::MandateUpdate::ConnectorReferenceUpdate {
+                                connector_mandate_id: Some(connector_mandate_id),
+                            },
+                        )
+                        .await
+                        .change_context(errors::ApiErrorResponse::MandateNotFound)?
+                }
+                _ => mandate,
+            };
+
             resp.payment_method_id = Some(mandate.payment_method_id);
         }
         None => {
@@ -151,13 +172,7 @@ where
                 .payment_method_id;
 
                 resp.payment_method_id = Some(payment_method_id.clone());
-                let mandate_reference = match resp.response.as_ref().ok() {
-                    Some(types::PaymentsResponseData::TransactionResponse {
-                        mandate_reference,
-                        ..
-                    }) => mandate_reference.clone(),
-                    _ => None,
-                };
+                let mandate_reference = get_mandate_reference(&resp.response);
 
                 if let Some(new_mandate_data) = helpers::generate_mandate(
                     resp.merchant_id.clone(),
@@ -167,7 +182,7 @@ where
                     payment_method_id,
                     mandate_reference,
                 ) {
-                    logger::error!("{:?}", new_mandate_data);
+                    logger::debug!(new_mandate=?new_mandate_data);
                     resp.request
                         .set_mandate_id(api_models::payments::MandateIds {
                             mandate_id: new_mandate_data.mandate_id.clone(),
@@ -178,9 +193,7 @@ where
                         .insert_mandate(new_mandate_data)
                         .await
                         .map_err(|err| {
-                            err.to_duplicate_response(
-                                errors::ApiErrorResponse::DuplicateRefundRequest,
-                            )
+                            err.to_duplicate_response(errors::ApiErrorResponse::DuplicateMandate)
                         })?;
                 };
             } else if resp.request.get_setup_future_usage().is_some() {
@@ -199,6 +212,17 @@ where
     Ok(resp)
 }
 
+fn get_mandate_reference(
+    response: &Result<types::PaymentsResponseData, types::ErrorResponse>,
+) -> Option<String> {
+    match response {
+        Ok(types::PaymentsResponseData::TransactionResponse {
+            mandate_reference, ..
+        }) => mandate_reference.clone(),
+        _ => None,
+    }
+}
+
 pub trait MandateBehaviour {
     fn get_amount(&self) -> i64;
     fn get_setup_future_usage(&self) -> Option<storage_models::enums::FutureUsage>;
@@ -207,3 +231,132 @@ pub trait MandateBehaviour {
     fn get_payment_method_data(&self) -> api_models::payments::PaymentMethod;
     fn get_setup_mandate_details(&self) -> Option<&api_models::payments::MandateData>;
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{connector::utils::get_test_router_data, db::StorageImpl, types::api};
+
+    fn off_session_charge(
+        mandate_id: &str,
+        connector_mandate_id: Option<String>,
+    ) -> types::PaymentsAuthorizeRouterData {
+        let request = types::PaymentsAuthorizeData {
+            // Off-session charges carry no card data, only the mandate to debit
+            payment_method_data: api::PaymentMethod::default(),
+            amount: 1000,
+            email: None,
+            currency: storage_enums::Currency::USD,
+            confirm: true,
+            statement_descriptor_suffix: None,
+            statement_descriptor_prefix: None,
+            capture_method: None,
+            setup_future_usage: None,
+            mandate_id: Some(api_models::payments::MandateIds::new(
+                mandate_id.to_string(),
+            )),
+            off_session: Some(true),
+            setup_mandate_details: None,
+            browser_info: None,
+            order_details: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
+            connector_meta: None,
+            network_token: None,
+        };
+        let response = types::PaymentsResponseData::TransactionResponse {
+            resource_id: types::ResponseId::ConnectorTransactionId("txn_1".to_string()),
+            redirection_data: None,
+            redirect: false,
+            mandate_reference: connector_mandate_id,
+            connector_metadata: None,
+            avs_result: None,
+            cvv_result: None,
+            connector_response_reference_id: None,
+        };
+        types::RouterData {
+            merchant_id: "merchant_1".to_string(),
+            status: storage_enums::AttemptStatus::Charged,
+            ..get_test_router_data("stripe", request, Ok(response))
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_off_session_charge_debits_stored_mandate() {
+        let state = crate::routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let merchant_account = storage::MerchantAccount {
+            id: 1,
+            merchant_id: "merchant_1".to_string(),
+            api_key: None,
+            return_url: None,
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: None,
+            storage_scheme: storage_enums::MerchantStorageScheme::PostgresOnly,
+            locker_id: None,
+            metadata: None,
+            routing_algorithm: None,
+        };
+        let customer = storage::Customer {
+            id: 1,
+            customer_id: "cus_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            name: None,
+            email: None,
+            phone: None,
+            phone_country_code: None,
+            description: None,
+            created_at: common_utils::date_time::now(),
+            metadata: None,
+        };
+
+        let new_mandate = helpers::generate_mandate(
+            "merchant_1".to_string(),
+            "stripe".to_string(),
+            Some(api::MandateData {
+                customer_acceptance: api::CustomerAcceptance::default(),
+                mandate_type: api::MandateType::MultiUse(None),
+            }),
+            &Some(customer.clone()),
+            "pm_1".to_string(),
+            Some("pm_connector_1".to_string()),
+        )
+        .unwrap();
+        let mandate = state.store.insert_mandate(new_mandate).await.unwrap();
+
+        let resp = mandate_procedure(
+            &state,
+            off_session_charge(&mandate.mandate_id, Some("pm_connector_2".to_string())),
+            &Some(customer),
+            &merchant_account,
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.payment_method_id, Some("pm_1".to_string()));
+
+        let mandate = state
+            .store
+            .find_mandate_by_merchant_id_mandate_id("merchant_1", &mandate.mandate_id)
+            .await
+            .unwrap();
+        assert_eq!(mandate.mandate_status, storage_enums::MandateStatus::Active);
+        assert_eq!(mandate.amount_captured, Some(1000));
+        assert_eq!(
+            mandate.connector_mandate_id,
+            Some("pm_connector_2".to_string())
+        );
+    }
+}
@@ -7,13 +7,14 @@ use router_env::{instrument, tracing};
 use crate::{
     core::{
         errors::{self, StorageErrorExt},
-        payment_methods::{transformers as payment_methods, vault},
+        payment_methods::{network_tokenization, transformers as payment_methods, vault},
         payments::helpers,
     },
     db,
     pii::prelude::*,
     routes, services,
     types::{
+        self,
         api::{self, CreatePaymentMethodExt},
         storage::{self, enums},
         transformers::ForeignInto,
@@ -28,6 +29,7 @@ pub async fn create_payment_method(
     customer_id: &str,
     payment_method_id: &str,
     merchant_id: &str,
+    network_token_reference: Option<String>,
 ) -> errors::CustomResult<storage::PaymentMethod, errors::StorageError> {
     let response = db
         .insert_payment_method(storage::PaymentMethodNew {
@@ -38,6 +40,7 @@ pub async fn create_payment_method(
             payment_method_type: req.payment_method_type.map(ForeignInto::foreign_into),
             payment_method_issuer: req.payment_method_issuer.clone(),
             metadata: req.metadata.clone(),
+            network_token_reference,
             ..storage::PaymentMethodNew::default()
         })
         .await?;
@@ -55,10 +58,20 @@ pub async fn add_payment_method(
     let merchant_id = &merchant_account.merchant_id;
     let customer_id = req.customer_id.clone().get_required_value("customer_id")?;
     match req.card.clone() {
-        Some(card) => add_card(state, req, card, customer_id, merchant_account)
+        Some(card) => {
+            let network_token_provider = network_tokenization::get_network_token_provider(state);
+            add_card(
+                state,
+                req,
+                card,
+                customer_id,
+                merchant_account,
+                network_token_provider.as_deref(),
+            )
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Add Card Failed"),
+            .attach_printable("Add Card Failed")
+        }
         None => {
             let payment_method_id = generate_id(consts::ID_LENGTH, "pm");
             create_payment_method(
@@ -67,6 +80,7 @@ pub async fn add_payment_method(
                 &customer_id,
                 &payment_method_id,
                 merchant_id,
+                None,
             )
             .await
             .map_err(|error| {
@@ -133,6 +147,7 @@ pub async fn add_card(
     card: api::CardDetail,
     customer_id: String,
     merchant_account: &storage::MerchantAccount,
+    network_token_provider: Option<&dyn network_tokenization::NetworkTokenProvider>,
 ) -> errors::CustomResult<api::PaymentMethodResponse, errors::VaultError> {
     let locker = &state.conf.locker;
     let db = &*state.store;
@@ -143,46 +158,59 @@ pub async fn add_card(
         .get_required_value("locker_id")
         .change_context(errors::VaultError::SaveCardFailed)?;
 
+    // With network tokenization the locker stores the network token instead of the card number
+    let (locker_card, network_token_reference) = match network_token_provider {
+        Some(provider) => {
+            let network_token = provider.tokenize_card(&card).await?;
+            (
+                network_token.to_card_detail(&card),
+                Some(network_token.token_reference),
+            )
+        }
+        None => (card.clone(), None),
+    };
+
     let request = payment_methods::mk_add_card_request(
         locker,
-        &card,
+        &locker_card,
         &customer_id,
         &req,
         &locker_id,
         merchant_id,
     )?;
     let response = if !locker.mock_locker {
-        let response = services::call_connector_api(state, request)
-            .await
-            .change_context(errors::VaultError::SaveCardFailed)?;
-
-        let response: payment_methods::AddCardResponse = match response {
-            Ok(card) => card
-                .response
-                .parse_struct("AddCardResponse")
-                .change_context(errors::VaultError::ResponseDeserializationFailed),
-            Err(err) => Err(report!(errors::VaultError::UnexpectedResponseError(
-                err.response
-            ))),
-        }?;
-        response
+        save_card_in_locker(state, request).await?
     } else {
         let card_id = generate_id(consts::ID_LENGTH, "card");
-        mock_add_card(db, &card_id, &card, None, None, Some(&customer_id)).await?
+        mock_add_card(db, &card_id, &locker_card, None, None, Some(&customer_id)).await?
     };
 
     if let Some(false) = response.duplicate {
-        create_payment_method(db, &req, &customer_id, &response.card_id, merchant_id)
-            .await
-            .change_context(errors::VaultError::PaymentMethodCreationFailed)?;
+        create_payment_method(
+            db,
+            &req,
+            &customer_id,
+            &response.card_id,
+            merchant_id,
+            network_token_reference,
+        )
+        .await
+        .change_context(errors::VaultError::PaymentMethodCreationFailed)?;
     } else {
         match db.find_payment_method(&response.card_id).await {
             Ok(_) => (),
             Err(err) => {
                 if err.current_context().is_db_not_found() {
-                    create_payment_method(db, &req, &customer_id, &response.card_id, merchant_id)
-                        .await
-                        .change_context(errors::VaultError::PaymentMethodCreationFailed)?;
+                    create_payment_method(
+                        db,
+                        &req,
+                        &customer_id,
+                        &response.card_id,
+                        merchant_id,
+                        network_token_reference,
+                    )
+                    .await
+                    .change_context(errors::VaultError::PaymentMethodCreationFailed)?;
                 } else {
                     Err(errors::VaultError::PaymentMethodCreationFailed)?;
                 }
@@ -194,6 +222,45 @@ pub async fn add_card(
     Ok(payment_method_resp)
 }
 
+/// Sends an add card request to the locker and parses the stored card from its response.
+#[instrument(skip_all)]
+pub async fn save_card_in_locker(
+    state: &routes::AppState,
+    request: services::Request,
+) -> errors::CustomResult<payment_methods::AddCardResponse, errors::VaultError> {
+    let response = services::call_connector_api(state, request)
+        .await
+        .change_context(errors::VaultError::SaveCardFailed)?;
+    parse_locker_response(response, "AddCardResponse")
+}
+
+/// Sends a get card request to the locker and parses the card from its response.
+#[instrument(skip_all)]
+pub async fn fetch_card_from_locker(
+    state: &routes::AppState,
+    request: services::Request,
+) -> errors::CustomResult<payment_methods::GetCardResponse, errors::VaultError> {
+    let response = services::call_connector_api(state, request)
+        .await
+        .change_context(errors::VaultError::FetchCardFailed)?;
+    parse_locker_response(response, "GetCardResponse")
+}
+
+fn parse_locker_response<T: serde::de::DeserializeOwned>(
+    response: Result<types::Response, types::Response>,
+    type_name: &str,
+) -> errors::CustomResult<T, errors::VaultError> {
+    match response {
+        Ok(response) => response
+            .response
+            .parse_struct(type_name)
+            .change_context(errors::VaultError::ResponseDeserializationFailed),
+        Err(response) => Err(report!(errors::VaultError::UnexpectedResponseError(
+            response.response
+        ))),
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn mock_add_card(
     db: &dyn db::StorageInterface,
@@ -298,12 +365,10 @@ pub async fn get_card_from_legacy_locker<'a>(
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Making get card request failed")?;
     let get_card_result = if !locker.mock_locker {
-        let response = services::call_connector_api(state, request)
+        fetch_card_from_locker(state, request)
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while executing call_connector_api for get_card");
-
-        response.get_response_inner("AddCardResponse")?
+            .attach_printable("Failed while fetching card from locker")?
     } else {
         let (get_card_response, _) = mock_get_card(&*state.store, card_id)
             .await
@@ -925,3 +990,133 @@ pub async fn delete_payment_method(
         },
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::db::StorageImpl;
+
+    fn get_locker_response(status_code: u16, body: &'static str) -> types::Response {
+        types::Response {
+            response: body.into(),
+            status_code,
+        }
+    }
+
+    #[test]
+    fn test_parse_locker_response_for_stored_card() {
+        let response = get_locker_response(
+            200,
+            r#"{"card": {"cardId": "card_123", "externalId": "ext_123", "cardFingerprint": "fp", "cardGlobalFingerprint": "gfp", "cardNumber": "4111111111111111", "cardExpYear": "2030", "cardExpMonth": "10"}}"#,
+        );
+
+        let card: payment_methods::GetCardResponse =
+            parse_locker_response(Ok(response), "GetCardResponse").unwrap();
+
+        assert_eq!(card.card.card_id, "card_123");
+        assert_eq!(card.card.external_id, "ext_123");
+    }
+
+    #[test]
+    fn test_parse_locker_response_for_malformed_body() {
+        let response = get_locker_response(200, r#"{"card": {"cardId": 123}}"#);
+
+        let result: errors::CustomResult<payment_methods::GetCardResponse, _> =
+            parse_locker_response(Ok(response), "GetCardResponse");
+
+        assert!(matches!(
+            result.as_ref().map_err(|err| err.current_context()),
+            Err(errors::VaultError::ResponseDeserializationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_parse_locker_response_for_error_status() {
+        let response = get_locker_response(500, "locker unavailable");
+
+        let result: errors::CustomResult<payment_methods::AddCardResponse, _> =
+            parse_locker_response(Err(response), "AddCardResponse");
+
+        assert!(matches!(
+            result.as_ref().map_err(|err| err.current_context()),
+            Err(errors::VaultError::UnexpectedResponseError(_))
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_add_card_stores_network_token_in_locker() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let merchant_account = storage::MerchantAccount {
+            id: 1,
+            merchant_id: "merchant_1".to_string(),
+            api_key: None,
+            return_url: None,
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: None,
+            storage_scheme: enums::MerchantStorageScheme::PostgresOnly,
+            locker_id: Some("m0010".to_string()),
+            metadata: None,
+            routing_algorithm: None,
+        };
+        let card = api::CardDetail {
+            card_number: "4111111111111111".to_string().into(),
+            card_exp_month: "10".to_string().into(),
+            card_exp_year: "2030".to_string().into(),
+            card_holder_name: Some("John Doe".to_string().into()),
+        };
+        let req = api::CreatePaymentMethod {
+            payment_method: api_models::enums::PaymentMethodType::Card,
+            payment_method_type: None,
+            payment_method_issuer: None,
+            payment_method_issuer_code: None,
+            card: Some(card.clone()),
+            metadata: None,
+            customer_id: Some("cus_1".to_string()),
+        };
+
+        let response = add_card(
+            &state,
+            req,
+            card,
+            "cus_1".to_string(),
+            &merchant_account,
+            Some(&network_tokenization::MockNetworkTokenProvider),
+        )
+        .await
+        .unwrap();
+
+        let payment_method = state
+            .store
+            .find_payment_method(&response.payment_method_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            payment_method.network_token_reference.as_deref(),
+            Some("ntr_123")
+        );
+        let (locker_card, _) = mock_get_card(&*state.store, &response.payment_method_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            locker_card
+                .card
+                .card_number
+                .as_ref()
+                .map(|number| number.peek().as_str()),
+            Some("4895370012003478")
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! Network tokenization of the cards customers save.
+//!
+//! When `[network_tokenization]` is enabled, a saved card is exchanged for a network token with
+//! the token service and the locker stores the token in place of the card number. The payment
+//! method keeps the reference of the token, with which a cryptogram is requested for every payment
+//! made with the saved card.
+
+use async_trait::async_trait;
+use common_utils::errors::CryptoError;
+use error_stack::{report, ResultExt};
+use router_env::{instrument, tracing};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::errors::{self, CustomResult, RouterResult, StorageErrorExt},
+    headers,
+    pii::{self, Secret},
+    routes, services,
+    types::{self, api, storage},
+    utils::{self, BytesExt},
+};
+
+/// Network token issued for a card, with the reference the token service knows it by.
+#[derive(Clone, Debug)]
+pub struct NetworkToken {
+    pub token_number: Secret<String, pii::CardNumber>,
+    pub token_exp_month: Secret<String>,
+    pub token_exp_year: Secret<String>,
+    pub token_reference: String,
+}
+
+impl NetworkToken {
+    /// Card details the locker stores in place of `card`.
+    pub fn to_card_detail(&self, card: &api::CardDetail) -> api::CardDetail {
+        api::CardDetail {
+            card_number: self.token_number.clone(),
+            card_exp_month: self.token_exp_month.clone(),
+            card_exp_year: self.token_exp_year.clone(),
+            card_holder_name: card.card_holder_name.clone(),
+        }
+    }
+}
+
+#[async_trait]
+pub trait NetworkTokenProvider: Send + Sync {
+    /// Exchanges the card for a network token.
+    async fn tokenize_card(
+        &self,
+        card: &api::CardDetail,
+    ) -> CustomResult<NetworkToken, errors::VaultError>;
+
+    /// Requests the cryptogram authenticating one payment with the token of `token_reference`.
+    async fn get_cryptogram(
+        &self,
+        token_reference: &str,
+    ) -> CustomResult<types::NetworkTokenData, CryptoError>;
+}
+
+#[derive(Debug, Serialize)]
+struct TokenizeCardRequest {
+    card_number: Secret<String, pii::CardNumber>,
+    card_exp_month: Secret<String>,
+    card_exp_year: Secret<String>,
+    card_holder_name: Option<Secret<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenizeCardResponse {
+    token_reference: String,
+    token_number: Secret<String, pii::CardNumber>,
+    token_exp_month: Secret<String>,
+    token_exp_year: Secret<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CryptogramResponse {
+    cryptogram: Secret<String>,
+    eci: Option<String>,
+}
+
+/// Token service configured in `[network_tokenization]`.
+pub struct TokenServiceProvider<'a> {
+    state: &'a routes::AppState,
+}
+
+impl<'a> TokenServiceProvider<'a> {
+    pub fn new(state: &'a routes::AppState) -> Self {
+        Self { state }
+    }
+
+    fn build_request(&self, path: &str, body: String) -> services::Request {
+        let settings = &self.state.conf.network_tokenization;
+        let url = format!("{}{path}", settings.url.trim_end_matches('/'));
+        let mut request = services::Request::new(services::Method::Post, &url);
+        request.add_header(headers::CONTENT_TYPE, "application/json");
+        request.add_header(headers::X_API_KEY, &settings.api_key);
+        request.set_body(body);
+        request
+    }
+}
+
+#[async_trait]
+impl<'a> NetworkTokenProvider for TokenServiceProvider<'a> {
+    #[instrument(skip_all)]
+    async fn tokenize_card(
+        &self,
+        card: &api::CardDetail,
+    ) -> CustomResult<NetworkToken, errors::VaultError> {
+        let body =
+            utils::Encode::<TokenizeCardRequest>::encode_to_string_of_json(&TokenizeCardRequest {
+                card_number: card.card_number.clone(),
+                card_exp_month: card.card_exp_month.clone(),
+                card_exp_year: card.card_exp_year.clone(),
+                card_holder_name: card.card_holder_name.clone(),
+            })
+            .change_context(errors::VaultError::RequestEncodingFailed)?;
+        let response: TokenizeCardResponse =
+            services::call_connector_api(self.state, self.build_request("/tokens", body))
+                .await
+                .change_context(errors::VaultError::NetworkTokenizationFailed)?
+                .map_err(|response| {
+                    report!(errors::VaultError::UnexpectedResponseError(
+                        response.response
+                    ))
+                })?
+                .response
+                .parse_struct("TokenizeCardResponse")
+                .change_context(errors::VaultError::ResponseDeserializationFailed)?;
+
+        Ok(NetworkToken {
+            token_number: response.token_number,
+            token_exp_month: response.token_exp_month,
+            token_exp_year: response.token_exp_year,
+            token_reference: response.token_reference,
+        })
+    }
+
+    #[instrument(skip_all)]
+    async fn get_cryptogram(
+        &self,
+        token_reference: &str,
+    ) -> CustomResult<types::NetworkTokenData, CryptoError> {
+        let request = self.build_request(
+            &format!("/tokens/{token_reference}/cryptograms"),
+            String::from("{}"),
+        );
+        let response: CryptogramResponse = services::call_connector_api(self.state, request)
+            .await
+            .change_context(CryptoError::CryptogramGenerationFailed)?
+            .map_err(|response| {
+                report!(CryptoError::CryptogramGenerationFailed).attach_printable(format!(
+                    "Token service responded with status {}",
+                    response.status_code
+                ))
+            })?
+            .response
+            .parse_struct("CryptogramResponse")
+            .change_context(CryptoError::CryptogramGenerationFailed)?;
+
+        Ok(types::NetworkTokenData {
+            cryptogram: response.cryptogram,
+            eci: response.eci,
+        })
+    }
+}
+
+/// Token service saved cards are exchanged with, `None` when network tokenization is disabled.
+pub fn get_network_token_provider(
+    state: &routes::AppState,
+) -> Option<Box<dyn NetworkTokenProvider + '_>> {
+    if !state.conf.network_tokenization.enabled {
+        return None;
+    }
+    let provider: Box<dyn NetworkTokenProvider + '_> = Box::new(TokenServiceProvider::new(state));
+    Some(provider)
+}
+
+/// Cryptogram for a payment with the saved card, `None` when the card is stored as a card number.
+pub async fn get_network_token_data(
+    provider: &dyn NetworkTokenProvider,
+    payment_method: &storage::PaymentMethod,
+) -> CustomResult<Option<types::NetworkTokenData>, CryptoError> {
+    match payment_method.network_token_reference {
+        Some(ref token_reference) => provider.get_cryptogram(token_reference).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Cryptogram for a payment with the saved card `payment_method_id`, which is authorized with its
+/// network token when it has one. With network tokenization disabled, the card stored in the
+/// locker is used as is.
+#[instrument(skip_all)]
+pub async fn get_payment_network_token_data(
+    state: &routes::AppState,
+    payment_method_id: &str,
+) -> RouterResult<Option<types::NetworkTokenData>> {
+    let provider = match get_network_token_provider(state) {
+        Some(provider) => provider,
+        None => return Ok(None),
+    };
+    let payment_method = state
+        .store
+        .find_payment_method(payment_method_id)
+        .await
+        .map_err(|error| {
+            error.to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)
+        })?;
+
+    get_network_token_data(provider.as_ref(), &payment_method)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to obtain a cryptogram for the network token")
+}
+
+/// Token service answering with a fixed network token and cryptogram, for tests.
+#[cfg(test)]
+pub struct MockNetworkTokenProvider;
+
+#[cfg(test)]
+#[async_trait]
+impl NetworkTokenProvider for MockNetworkTokenProvider {
+    async fn tokenize_card(
+        &self,
+        _card: &api::CardDetail,
+    ) -> CustomResult<NetworkToken, errors::VaultError> {
+        Ok(NetworkToken {
+            token_number: Secret::new("4895370012003478".to_string()),
+            token_exp_month: Secret::new("12".to_string()),
+            token_exp_year: Secret::new("2031".to_string()),
+            token_reference: "ntr_123".to_string(),
+        })
+    }
+
+    async fn get_cryptogram(
+        &self,
+        token_reference: &str,
+    ) -> CustomResult<types::NetworkTokenData, CryptoError> {
+        Ok(types::NetworkTokenData {
+            cryptogram: Secret::new(format!("cryptogram_{token_reference}")),
+            eci: Some("05".to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use masking::PeekInterface;
+
+    use super::*;
+    use crate::{
+        configs::settings::{self, Settings},
+        db::StorageImpl,
+    };
+
+    fn get_card() -> api::CardDetail {
+        api::CardDetail {
+            card_number: Secret::new("4111111111111111".to_string()),
+            card_exp_month: Secret::new("10".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_holder_name: Some(Secret::new("John Doe".to_string())),
+        }
+    }
+
+    fn get_payment_method(network_token_reference: Option<String>) -> storage::PaymentMethod {
+        let now = common_utils::date_time::now();
+        storage::PaymentMethod {
+            id: 1,
+            customer_id: "cus_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            payment_method_id: "pm_1".to_string(),
+            accepted_currency: None,
+            scheme: None,
+            token: None,
+            cardholder_name: None,
+            issuer_name: None,
+            issuer_country: None,
+            payer_country: None,
+            is_stored: None,
+            swift_code: None,
+            direct_debit_token: None,
+            created_at: now,
+            last_modified: now,
+            payment_method: storage::enums::PaymentMethodType::Card,
+            payment_method_type: None,
+            payment_method_issuer: None,
+            payment_method_issuer_code: None,
+            metadata: None,
+            network_token_reference,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_to_card_detail_replaces_card_number_with_token() {
+        let card = get_card();
+        let network_token = MockNetworkTokenProvider.tokenize_card(&card).await.unwrap();
+
+        let locker_card = network_token.to_card_detail(&card);
+
+        assert_eq!(locker_card.card_number.peek(), "4895370012003478");
+        assert_eq!(locker_card.card_exp_month.peek(), "12");
+        assert_eq!(locker_card.card_exp_year.peek(), "2031");
+        assert_eq!(
+            locker_card
+                .card_holder_name
+                .as_ref()
+                .map(PeekInterface::peek),
+            Some(&"John Doe".to_string())
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_get_network_token_data_for_card_number() {
+        let payment_method = get_payment_method(None);
+
+        let network_token = get_network_token_data(&MockNetworkTokenProvider, &payment_method)
+            .await
+            .unwrap();
+
+        assert!(network_token.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_get_network_token_data_for_network_token() {
+        let payment_method = get_payment_method(Some("ntr_123".to_string()));
+
+        let network_token = get_network_token_data(&MockNetworkTokenProvider, &payment_method)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(network_token.cryptogram.peek(), "cryptogram_ntr_123");
+        assert_eq!(network_token.eci.as_deref(), Some("05"));
+    }
+
+    async fn get_payment_network_token_data_with_settings(
+        network_tokenization: settings::NetworkTokenization,
+    ) -> Option<types::NetworkTokenData> {
+        let mut conf = Settings::new().unwrap();
+        conf.network_tokenization = network_tokenization;
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        state
+            .store
+            .insert_payment_method(storage::PaymentMethodNew {
+                customer_id: "cus_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                payment_method_id: "pm_1".to_string(),
+                network_token_reference: Some("ntr_123".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        get_payment_network_token_data(&state, "pm_1")
+            .await
+            .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn test_payment_network_token_data_requests_cryptogram_when_enabled() {
+        let token_service = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/tokens/ntr_123/cryptograms"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "cryptogram": "AgAAAAAA", "eci": "05" })),
+            )
+            .expect(1)
+            .mount(&token_service)
+            .await;
+
+        let network_token =
+            get_payment_network_token_data_with_settings(settings::NetworkTokenization {
+                enabled: true,
+                url: token_service.uri(),
+                api_key: "token_service_key".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(network_token.cryptogram.peek(), "AgAAAAAA");
+    }
+
+    #[actix_rt::test]
+    async fn test_payment_network_token_data_uses_stored_card_when_disabled() {
+        let token_service = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&token_service)
+            .await;
+
+        let network_token =
+            get_payment_network_token_data_with_settings(settings::NetworkTokenization {
+                enabled: false,
+                url: token_service.uri(),
+                api_key: "token_service_key".to_string(),
+            })
+            .await;
+
+        assert!(network_token.is_none());
+    }
+}
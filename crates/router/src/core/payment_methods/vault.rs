@@ -1,12 +1,12 @@
 use std::str::FromStr;
 
+#[cfg(not(feature = "basilisk"))]
+use common_utils::crypto;
 use common_utils::generate_id_with_default_len;
 use error_stack::{IntoReport, ResultExt};
 use masking::PeekInterface;
 use router_env::{instrument, tracing};
 
-#[cfg(not(feature = "basilisk"))]
-use crate::types::storage;
 use crate::{
     core::errors::{self, CustomResult, RouterResult},
     logger, routes,
@@ -15,6 +15,8 @@ use crate::{
 };
 #[cfg(feature = "basilisk")]
 use crate::{core::payment_methods::transformers as payment_methods, services, utils::BytesExt};
+#[cfg(not(feature = "basilisk"))]
+use crate::{types::storage, utils::ByteSliceExt};
 #[cfg(feature = "basilisk")]
 const VAULT_SERVICE_NAME: &str = "CARD";
 #[cfg(feature = "basilisk")]
@@ -237,8 +239,16 @@ impl Vault {
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Could not find payment method in vault")?;
 
-        let tokenize_value: MockTokenizeDBValue = config
-            .config
+        let key = get_vault_encryption_key(state)?;
+        let encrypted_value = hex::decode(config.config)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to hex decode mock tokenize db value")?;
+        let decrypted_value = crypto::decrypt(&key, &encrypted_value)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to decrypt mock tokenize db value")?;
+
+        let tokenize_value: MockTokenizeDBValue = decrypted_value
             .parse_struct("MockTokenizeDBValue")
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Unable to deserialize Mock tokenize db value")?;
@@ -277,6 +287,12 @@ impl Vault {
                 .change_context(errors::ApiErrorResponse::InternalServerError)
                 .attach_printable("Failed to encode payment method as mock tokenize db value")?;
 
+        let key = get_vault_encryption_key(state)?;
+        let value_string = crypto::encrypt(&key, value_string.as_bytes())
+            .map(hex::encode)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encrypt mock tokenize db value")?;
+
         let already_present = state.store.find_config_by_key(&lookup_key).await;
 
         if already_present.is_err() {
@@ -321,6 +337,14 @@ impl Vault {
     }
 }
 
+#[cfg(not(feature = "basilisk"))]
+fn get_vault_encryption_key(state: &routes::AppState) -> RouterResult<Vec<u8>> {
+    hex::decode(&state.conf.secrets.vault_encryption_key)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to hex decode the vault encryption key")
+}
+
 #[cfg(feature = "basilisk")]
 impl Vault {
     #[instrument(skip_all)]
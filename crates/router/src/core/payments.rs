@@ -1,7 +1,14 @@
 pub mod access_token;
+pub mod auto_capture;
 pub mod flows;
+pub mod fx;
 pub mod helpers;
+pub mod idempotency;
 pub mod operations;
+pub mod response_retention;
+pub mod routing;
+pub mod stale_sync;
+pub mod test_cards;
 pub mod transformers;
 
 use std::{fmt::Debug, marker::PhantomData, time::Instant};
@@ -148,6 +155,19 @@ where
                 )
                 .await?
             }
+            api::ConnectorCallType::Failover(connectors) => {
+                call_connector_service_with_failover(
+                    state,
+                    &merchant_account,
+                    &validate_result.payment_id,
+                    connectors,
+                    &operation,
+                    payment_data,
+                    &customer,
+                    call_connector_action,
+                )
+                .await?
+            }
             api::ConnectorCallType::Multiple(connectors) => {
                 call_multiple_connectors_service(
                     state,
@@ -344,16 +364,59 @@ where
     // To perform router related operation for PaymentResponse
     PaymentResponse: Operation<F, Req>,
 {
-    let db = &*state.store;
-
     let stime_connector = Instant::now();
 
+    let router_data_res = call_connector(
+        state,
+        merchant_account,
+        &connector,
+        &payment_data,
+        customer,
+        call_connector_action,
+    )
+    .await;
+
+    let response = update_tracker_with_connector_response(
+        state,
+        merchant_account,
+        payment_id,
+        payment_data,
+        router_data_res,
+    )
+    .await?;
+
+    let etime_connector = Instant::now();
+    let duration_connector = etime_connector.saturating_duration_since(stime_connector);
+    tracing::info!(duration = format!("Duration taken: {}", duration_connector.as_millis()));
+
+    Ok(response)
+}
+
+/// Builds the router data for `connector` and sends the payment to it.
+async fn call_connector<F, Req>(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    connector: &api::ConnectorData,
+    payment_data: &PaymentData<F>,
+    customer: &Option<storage::Customer>,
+    call_connector_action: CallConnectorAction,
+) -> RouterResult<types::RouterData<F, Req, types::PaymentsResponseData>>
+where
+    F: Send + Clone,
+
+    // To create connector flow specific interface data
+    PaymentData<F>: ConstructFlowSpecificData<F, Req, types::PaymentsResponseData>,
+    types::RouterData<F, Req, types::PaymentsResponseData>: Feature<F, Req> + Send,
+
+    // To construct connector flow specific api
+    dyn api::Connector: services::api::ConnectorIntegration<F, Req, types::PaymentsResponseData>,
+{
     let mut router_data = payment_data
         .construct_router_data(state, connector.connector.id(), merchant_account)
         .await?;
 
     let add_access_token_result = router_data
-        .add_access_token(state, &connector, merchant_account)
+        .add_access_token(state, connector, merchant_account)
         .await?;
 
     access_token::update_router_data_with_access_token_result(
@@ -362,13 +425,13 @@ where
         &call_connector_action,
     );
 
-    let router_data_res = if !(add_access_token_result.connector_supports_access_token
+    if !(add_access_token_result.connector_supports_access_token
         && router_data.access_token.is_none())
     {
         router_data
             .decide_flows(
                 state,
-                &connector,
+                connector,
                 customer,
                 call_connector_action,
                 merchant_account,
@@ -376,9 +439,23 @@ where
             .await
     } else {
         Ok(router_data)
-    };
+    }
+}
 
-    let response = router_data_res
+async fn update_tracker_with_connector_response<F, Req>(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    payment_id: &api::PaymentIdType,
+    payment_data: PaymentData<F>,
+    router_data_res: RouterResult<types::RouterData<F, Req, types::PaymentsResponseData>>,
+) -> RouterResult<PaymentData<F>>
+where
+    F: Send + Clone,
+    PaymentResponse: Operation<F, Req>,
+{
+    let db = &*state.store;
+    let previous_attempt_status = payment_data.payment_attempt.status;
+    let payment_data = router_data_res
         .async_and_then(|response| async {
             let operation = helpers::response_operation::<F, Req>();
             let payment_data = operation
@@ -394,12 +471,89 @@ where
             Ok(payment_data)
         })
         .await?;
+    auto_capture::schedule_auto_capture(
+        state,
+        previous_attempt_status,
+        &payment_data.payment_attempt,
+    )
+    .await;
+    Ok(payment_data)
+}
 
-    let etime_connector = Instant::now();
-    let duration_connector = etime_connector.saturating_duration_since(stime_connector);
-    tracing::info!(duration = format!("Duration taken: {}", duration_connector.as_millis()));
+/// Sends the payment to `connectors` in order, failing over to the next connector when it is
+/// soft declined or could not be called. The payment is recorded against the connector that
+/// processed it last.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all)]
+pub async fn call_connector_service_with_failover<F, Op, Req>(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    payment_id: &api::PaymentIdType,
+    connectors: Vec<api::ConnectorData>,
+    _operation: &Op,
+    payment_data: PaymentData<F>,
+    customer: &Option<storage::Customer>,
+    call_connector_action: CallConnectorAction,
+) -> RouterResult<PaymentData<F>>
+where
+    Op: Debug + Sync,
+    F: Send + Clone,
 
-    Ok(response)
+    // To create connector flow specific interface data
+    PaymentData<F>: ConstructFlowSpecificData<F, Req, types::PaymentsResponseData>,
+    types::RouterData<F, Req, types::PaymentsResponseData>: Feature<F, Req> + Send,
+
+    // To construct connector flow specific api
+    dyn api::Connector: services::api::ConnectorIntegration<F, Req, types::PaymentsResponseData>,
+
+    // To perform router related operation for PaymentResponse
+    PaymentResponse: Operation<F, Req>,
+{
+    let router_data_res = {
+        let payment_data = &payment_data;
+        let call_connector_action = &call_connector_action;
+        routing::call_connectors_with_failover(
+            connectors,
+            |connector| async move {
+                call_connector(
+                    state,
+                    merchant_account,
+                    &connector,
+                    payment_data,
+                    customer,
+                    call_connector_action.clone(),
+                )
+                .await
+            },
+            |connector_name, error_response| async move {
+                state
+                    .store
+                    .insert_failed_payment_attempt(storage::FailedPaymentAttemptNew {
+                        payment_id: payment_data.payment_attempt.payment_id.clone(),
+                        merchant_id: payment_data.payment_attempt.merchant_id.clone(),
+                        attempt_id: payment_data.payment_attempt.attempt_id.clone(),
+                        connector: connector_name,
+                        error_code: error_response.code,
+                        error_message: error_response.message,
+                        error_reason: error_response.reason,
+                    })
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed while recording the soft declined payment attempt")?;
+                Ok(())
+            },
+        )
+        .await
+    };
+
+    update_tracker_with_connector_response(
+        state,
+        merchant_account,
+        payment_id,
+        payment_data,
+        router_data_res,
+    )
+    .await
 }
 
 pub async fn call_multiple_connectors_service<F, Op, Req>(
@@ -474,6 +628,7 @@ where
     Ok(payment_data)
 }
 
+#[derive(Clone)]
 pub enum CallConnectorAction {
     Trigger,
     Avoid,
@@ -509,6 +664,12 @@ where
     pub sessions_token: Vec<api::SessionToken>,
     pub card_cvc: Option<pii::Secret<String>>,
     pub email: Option<masking::Secret<String, pii::Email>>,
+    pub surcharge_details: Option<api_models::payments::SurchargeDetails>,
+    pub tax_amount: Option<i64>,
+    pub sca_exemption: Option<api_models::enums::ScaExemptionType>,
+    /// Payment method of the saved card being paid with, which is authorized with a cryptogram
+    /// when it is stored as a network token.
+    pub saved_payment_method_id: Option<String>,
 }
 
 #[derive(Debug, Default)]
@@ -615,6 +776,7 @@ pub async fn list_payments(
                     errors::ApiErrorResponse::PaymentNotFound,
                 )
             })?;
+    let next_cursor = crate::db::pagination::get_next_cursor(&payment_intent, constraints.limit);
 
     let data: Vec<api::PaymentsResponse> = payment_intent
         .into_iter()
@@ -624,6 +786,7 @@ pub async fn list_payments(
         api::PaymentListResponse {
             size: data.len(),
             data,
+            next_cursor,
         },
     ))
 }
@@ -688,6 +851,46 @@ where
 
             let connector_name = match routing_algorithm {
                 api::RoutingAlgorithm::Single(conn) => conn.to_string(),
+                api::RoutingAlgorithm::Failover(connectors) => {
+                    let connectors = connectors
+                        .iter()
+                        .map(|connector| {
+                            api::ConnectorData::get_connector_by_name(
+                                &state.conf.connectors,
+                                &connector.to_string(),
+                                api::GetToken::Connector,
+                            )
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Routing algorithm gave invalid connector")?;
+                    // The first connector is recorded until the payment fails over
+                    payment_data.payment_attempt.connector = connectors
+                        .first()
+                        .map(|connector| connector.connector_name.to_string());
+                    return Ok(api::ConnectorCallType::Failover(connectors));
+                }
+                api::RoutingAlgorithm::RoundRobin(connectors) => {
+                    let counter = state
+                        .store
+                        .increment_round_robin_counter(&merchant_account.merchant_id)
+                        .await
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Failed to update round robin counter")?;
+                    routing::select_round_robin_connector(&connectors, counter)
+                        .ok_or(errors::ApiErrorResponse::InternalServerError)
+                        .into_report()
+                        .attach_printable("Round robin routing has no connectors")?
+                        .to_string()
+                }
+                api::RoutingAlgorithm::Weighted(connectors) => routing::select_weighted_connector(
+                    &connectors,
+                    &payment_data.payment_intent.payment_id,
+                )
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Weighted routing has no connectors with a weight")?
+                .to_string(),
             };
 
             let connector_data = api::ConnectorData::get_connector_by_name(
@@ -706,3 +909,203 @@ where
         call_type @ api::ConnectorCallType::Multiple(_) => Ok(call_type),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{
+        configs::settings::Settings, connector::utils as connector_utils, db::StorageImpl,
+    };
+
+    const DEFAULT_CAPTURE_DELAY: i64 = 3600;
+
+    async fn get_confirmed_payment_data(
+        state: &AppState,
+    ) -> (storage::MerchantAccount, PaymentData<api::Authorize>) {
+        let db = &*state.store;
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+        let mut payment =
+            helpers::get_test_payment("pay_1", "dummy", storage_enums::AttemptStatus::Pending);
+        payment.payment_attempt.capture_method = Some(storage_enums::CaptureMethod::Manual);
+        payment.payment_attempt.confirm = true;
+        let (merchant_account, payment_attempt) = helpers::insert_test_payment(db, payment).await;
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id("pay_1", "merchant_1", storage_scheme)
+            .await
+            .expect("Failed to fetch payment intent");
+        let connector_response = db
+            .find_connector_response_by_payment_id_merchant_id_attempt_id(
+                "pay_1",
+                "merchant_1",
+                &payment_attempt.attempt_id,
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to fetch connector response");
+
+        let payment_data = PaymentData {
+            flow: PhantomData,
+            amount: payment_attempt.amount.into(),
+            currency: storage_enums::Currency::USD,
+            payment_intent,
+            payment_attempt,
+            connector_response,
+            mandate_id: None,
+            setup_mandate: None,
+            address: PaymentAddress::default(),
+            token: None,
+            confirm: Some(true),
+            force_sync: None,
+            payment_method_data: None,
+            refunds: vec![],
+            sessions_token: vec![],
+            card_cvc: None,
+            email: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
+            saved_payment_method_id: None,
+        };
+        (
+            merchant_account.expect("Merchant account was not inserted"),
+            payment_data,
+        )
+    }
+
+    fn get_authorized_response(
+        payment_data: &PaymentData<api::Authorize>,
+    ) -> RouterResult<types::PaymentsAuthorizeRouterData> {
+        Ok(types::RouterData {
+            status: storage_enums::AttemptStatus::Authorized,
+            ..connector_utils::get_test_router_data(
+                "dummy",
+                types::PaymentsAuthorizeData {
+                    payment_method_data: api::PaymentMethod::Card(api::Card {
+                        card_number: masking::Secret::new("4242424242424242".to_string()),
+                        card_exp_month: masking::Secret::new("03".to_string()),
+                        card_exp_year: masking::Secret::new("2030".to_string()),
+                        card_holder_name: masking::Secret::new("John Doe".to_string()),
+                        card_cvc: masking::Secret::new("123".to_string()),
+                    }),
+                    amount: payment_data.payment_attempt.amount,
+                    email: None,
+                    currency: payment_data.currency,
+                    confirm: true,
+                    statement_descriptor_suffix: None,
+                    statement_descriptor_prefix: None,
+                    capture_method: Some(storage_enums::CaptureMethod::Manual),
+                    setup_future_usage: None,
+                    mandate_id: None,
+                    off_session: None,
+                    setup_mandate_details: None,
+                    browser_info: None,
+                    order_details: None,
+                    surcharge_details: None,
+                    tax_amount: None,
+                    sca_exemption: None,
+                    connector_meta: None,
+                    network_token: None,
+                },
+                Ok(types::PaymentsResponseData::TransactionResponse {
+                    resource_id: types::ResponseId::ConnectorTransactionId(
+                        "dummy_pay_1".to_string(),
+                    ),
+                    redirection_data: None,
+                    redirect: false,
+                    mandate_reference: None,
+                    connector_metadata: None,
+                    avs_result: None,
+                    cvv_result: None,
+                    connector_response_reference_id: None,
+                }),
+            )
+        })
+    }
+
+    async fn find_auto_capture_process(
+        state: &AppState,
+        payment_attempt: &storage::PaymentAttempt,
+    ) -> Option<storage::ProcessTracker> {
+        let process_tracker_id = pt_utils::get_process_tracker_id(
+            auto_capture::AUTO_CAPTURE_RUNNER,
+            auto_capture::AUTO_CAPTURE_TASK,
+            &payment_attempt.attempt_id,
+            &payment_attempt.merchant_id,
+        );
+        state
+            .store
+            .find_process_by_id(&process_tracker_id)
+            .await
+            .expect("Failed to fetch auto capture task")
+    }
+
+    #[actix_rt::test]
+    async fn test_confirmed_manual_capture_payment_is_scheduled_for_capture() {
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.auto_capture.default_capture_delay = Some(DEFAULT_CAPTURE_DELAY);
+        let state = AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let (merchant_account, payment_data) = get_confirmed_payment_data(&state).await;
+        let response = get_authorized_response(&payment_data);
+
+        let payment_data = update_tracker_with_connector_response(
+            &state,
+            &merchant_account,
+            &api::PaymentIdType::PaymentIntentId("pay_1".to_string()),
+            payment_data,
+            response,
+        )
+        .await
+        .expect("Failed to update the payment with the connector response");
+        let payment_attempt = payment_data.payment_attempt;
+        assert_eq!(
+            payment_attempt.status,
+            storage_enums::AttemptStatus::Authorized
+        );
+
+        let authorized_at = payment_attempt
+            .authorized_at
+            .expect("Authorization time was not recorded");
+        let process = find_auto_capture_process(&state, &payment_attempt)
+            .await
+            .expect("Auto capture task was not scheduled");
+        assert_eq!(
+            process.schedule_time,
+            Some(authorized_at.saturating_add(time::Duration::seconds(DEFAULT_CAPTURE_DELAY)))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_sync_of_authorized_payment_is_not_scheduled_for_capture() {
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.auto_capture.default_capture_delay = Some(DEFAULT_CAPTURE_DELAY);
+        let state = AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let (_, payment_data) = get_confirmed_payment_data(&state).await;
+        let payment_attempt = storage::PaymentAttempt {
+            status: storage_enums::AttemptStatus::Authorized,
+            authorized_at: Some(common_utils::date_time::now()),
+            ..payment_data.payment_attempt
+        };
+
+        auto_capture::schedule_auto_capture(
+            &state,
+            storage_enums::AttemptStatus::Authorized,
+            &payment_attempt,
+        )
+        .await;
+        assert!(find_auto_capture_process(&state, &payment_attempt)
+            .await
+            .is_none());
+    }
+}
@@ -32,6 +32,7 @@ pub fn router_data_type_conversion<F1, F2, Req1, Req2, Res1, Res2>(
         merchant_id: router_data.merchant_id,
         address: router_data.address,
         amount_captured: router_data.amount_captured,
+        authorized_amount: router_data.authorized_amount,
         auth_type: router_data.auth_type,
         connector: router_data.connector,
         connector_auth_type: router_data.connector_auth_type,
@@ -41,6 +42,7 @@ pub fn router_data_type_conversion<F1, F2, Req1, Req2, Res1, Res2>(
         payment_id: router_data.payment_id,
         payment_method: router_data.payment_method,
         payment_method_id: router_data.payment_method_id,
+        connector_response_body: router_data.connector_response_body,
         return_url: router_data.return_url,
         status: router_data.status,
         attempt_id: router_data.attempt_id,
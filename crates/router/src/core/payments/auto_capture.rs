@@ -0,0 +1,217 @@
+use router_env::{instrument, tracing};
+
+use super::{operations, CallConnectorAction};
+use crate::{
+    configs::settings,
+    core::errors,
+    db, logger,
+    routes::AppState,
+    scheduler::utils as pt_utils,
+    types::{
+        api,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils::{OptionExt, ValueExt},
+};
+
+pub const AUTO_CAPTURE_RUNNER: &str = "AUTO_CAPTURE_WORKFLOW";
+pub const AUTO_CAPTURE_TASK: &str = "AUTO_CAPTURE";
+
+pub const AUTHORIZATION_EXPIRED_ERROR_CODE: &str = "authorization_expired";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AutoCaptureAction {
+    Capture,
+    /// The authorization is past its validity, so the connector would decline the capture
+    ExpireAuthorization,
+    /// The payment was voided, captured or failed before the scheduled capture
+    Skip,
+}
+
+/// Schedules the capture of a manual capture payment at `schedule_time`.
+#[instrument(skip_all)]
+pub async fn add_auto_capture_task(
+    db: &dyn db::StorageInterface,
+    payment_attempt: &storage::PaymentAttempt,
+    schedule_time: time::PrimitiveDateTime,
+) -> Result<(), errors::ProcessTrackerError> {
+    let tracking_data = api::PaymentsCaptureRequest {
+        payment_id: Some(payment_attempt.payment_id.clone()),
+        merchant_id: Some(payment_attempt.merchant_id.clone()),
+        ..Default::default()
+    };
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        AUTO_CAPTURE_RUNNER,
+        AUTO_CAPTURE_TASK,
+        &payment_attempt.attempt_id,
+        &payment_attempt.merchant_id,
+    );
+    let process_tracker_entry = storage::ProcessTracker::make_process_tracker_new(
+        process_tracker_id,
+        AUTO_CAPTURE_TASK,
+        AUTO_CAPTURE_RUNNER,
+        tracking_data,
+        schedule_time,
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
+/// Returns when a manual capture payment is to be captured: at its `capture_on`, or after the
+/// configured default delay from its authorization.
+pub fn get_auto_capture_time(
+    config: &settings::AutoCapture,
+    payment_attempt: &storage::PaymentAttempt,
+) -> Option<time::PrimitiveDateTime> {
+    if payment_attempt.capture_method != Some(enums::CaptureMethod::Manual) {
+        return None;
+    }
+    payment_attempt.capture_on.or_else(|| {
+        config.default_capture_delay.map(|delay| {
+            payment_attempt
+                .authorized_at
+                .unwrap_or(payment_attempt.modified_at)
+                .saturating_add(time::Duration::seconds(delay))
+        })
+    })
+}
+
+/// Schedules the capture of a manual capture payment once its attempt is authorized. Failing to
+/// schedule it is only logged, since the authorization has already happened at the connector.
+#[instrument(skip_all)]
+pub async fn schedule_auto_capture(
+    state: &AppState,
+    previous_status: enums::AttemptStatus,
+    payment_attempt: &storage::PaymentAttempt,
+) {
+    // Syncs of an already authorized attempt must not schedule the capture again
+    if previous_status == enums::AttemptStatus::Authorized
+        || payment_attempt.status != enums::AttemptStatus::Authorized
+    {
+        return;
+    }
+    if let Some(schedule_time) = get_auto_capture_time(&state.conf.auto_capture, payment_attempt) {
+        if let Err(error) =
+            add_auto_capture_task(&*state.store, payment_attempt, schedule_time).await
+        {
+            logger::error!(
+                ?error,
+                payment_id = %payment_attempt.payment_id,
+                "Failed while adding auto capture task to process tracker"
+            );
+        }
+    }
+}
+
+pub fn get_auto_capture_action(
+    intent_status: enums::IntentStatus,
+    authorized_at: time::PrimitiveDateTime,
+    current_time: time::PrimitiveDateTime,
+    authorization_validity: i64,
+) -> AutoCaptureAction {
+    match intent_status {
+        enums::IntentStatus::RequiresCapture
+            if current_time
+                > authorized_at.saturating_add(time::Duration::seconds(authorization_validity)) =>
+        {
+            AutoCaptureAction::ExpireAuthorization
+        }
+        enums::IntentStatus::RequiresCapture => AutoCaptureAction::Capture,
+        _ => AutoCaptureAction::Skip,
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn auto_capture_workflow(
+    state: &AppState,
+    process: storage::ProcessTracker,
+) -> Result<(), errors::ProcessTrackerError> {
+    let db = &*state.store;
+    let tracking_data: api::PaymentsCaptureRequest = process
+        .tracking_data
+        .clone()
+        .parse_value("PaymentsCaptureRequest")?;
+    let merchant_id = tracking_data
+        .merchant_id
+        .clone()
+        .get_required_value("merchant_id")?;
+    let payment_id = tracking_data
+        .payment_id
+        .clone()
+        .get_required_value("payment_id")?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id)
+        .await?;
+    let storage_scheme = merchant_account.storage_scheme;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(&payment_id, &merchant_id, storage_scheme)
+        .await?;
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id(&payment_id, &merchant_id, storage_scheme)
+        .await?;
+
+    let action = get_auto_capture_action(
+        payment_intent.status,
+        // Attempts authorized before the authorization time was recorded fall back to their
+        // creation time
+        payment_attempt
+            .authorized_at
+            .unwrap_or(payment_attempt.created_at),
+        common_utils::date_time::now(),
+        state.conf.auto_capture.authorization_validity,
+    );
+    let business_status = match action {
+        AutoCaptureAction::Capture => {
+            let (payment_data, _, _) = super::payments_operation_core::<api::Capture, _, _, _>(
+                state,
+                merchant_account,
+                operations::PaymentCapture,
+                tracking_data,
+                CallConnectorAction::Trigger,
+            )
+            .await?;
+            match payment_data.payment_attempt.status {
+                enums::AttemptStatus::Charged => "CAPTURED_BY_PT",
+                status => {
+                    logger::error!(?status, %payment_id, "Scheduled capture failed");
+                    "CAPTURE_FAILED"
+                }
+            }
+        }
+        AutoCaptureAction::ExpireAuthorization => {
+            logger::error!(%payment_id, "Authorization expired before the scheduled capture");
+            db.update_payment_attempt(
+                payment_attempt,
+                storage::PaymentAttemptUpdate::ErrorUpdate {
+                    connector: None,
+                    status: enums::AttemptStatus::CaptureFailed,
+                    error_code: Some(AUTHORIZATION_EXPIRED_ERROR_CODE.to_string()),
+                    error_message: Some(
+                        "The authorization expired before the payment could be captured"
+                            .to_string(),
+                    ),
+                    error_reason: None,
+                    connector_response_body: None,
+                },
+                storage_scheme,
+            )
+            .await?;
+            db.update_payment_intent(
+                payment_intent,
+                storage::PaymentIntentUpdate::PGStatusUpdate {
+                    status: enums::IntentStatus::Failed,
+                },
+                storage_scheme,
+            )
+            .await?;
+            "AUTHORIZATION_EXPIRED"
+        }
+        AutoCaptureAction::Skip => "SKIPPED_BY_PT",
+    };
+
+    process
+        .finish_with_status(db, business_status.to_string())
+        .await
+}
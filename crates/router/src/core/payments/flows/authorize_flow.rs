@@ -3,10 +3,12 @@ use async_trait::async_trait;
 use super::{ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
-        errors::{ConnectorErrorExt, RouterResult},
+        errors::{self, ConnectorErrorExt, RouterResult},
         mandate,
-        payments::{self, access_token, transformers, PaymentData},
+        payment_methods::network_tokenization,
+        payments::{self, access_token, test_cards, transformers, PaymentData},
     },
+    logger,
     routes::AppState,
     scheduler::metrics,
     services,
@@ -33,13 +35,23 @@ impl
             types::PaymentsResponseData,
         >,
     > {
-        transformers::construct_payment_router_data::<api::Authorize, types::PaymentsAuthorizeData>(
-            state,
-            self.clone(),
-            connector_id,
-            merchant_account,
-        )
-        .await
+        let mut router_data = transformers::construct_payment_router_data::<
+            api::Authorize,
+            types::PaymentsAuthorizeData,
+        >(state, self.clone(), connector_id, merchant_account)
+        .await?;
+
+        // Saved cards stored as a network token are authorized with a cryptogram
+        if let (Some(payment_method_id), api::PaymentMethod::Card(_)) = (
+            self.saved_payment_method_id.as_deref(),
+            &router_data.request.payment_method_data,
+        ) {
+            router_data.request.network_token =
+                network_tokenization::get_payment_network_token_data(state, payment_method_id)
+                    .await?;
+        }
+
+        Ok(router_data)
     }
 }
 
@@ -53,6 +65,24 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
         call_connector_action: payments::CallConnectorAction,
         merchant_account: &storage::MerchantAccount,
     ) -> RouterResult<Self> {
+        if let Some(outcome) = test_cards::get_mock_outcome(
+            &state.conf.test_mode,
+            Some(&self.request.payment_method_data),
+        )? {
+            return Ok(self.mock_authorize(outcome));
+        }
+
+        connector
+            .connector
+            .get_capabilities()
+            .validate(
+                connector.connector.id(),
+                &self.get_connector_flows(),
+                self.payment_method,
+                Some(self.request.currency),
+            )
+            .map_err(|error| error.to_payment_failed_response())?;
+
         let resp = self
             .decide_flow(
                 state,
@@ -80,6 +110,46 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
 }
 
 impl types::PaymentsAuthorizeRouterData {
+    /// Flows the connector has to support to authorize this payment.
+    fn get_connector_flows(&self) -> Vec<api::ConnectorFlow> {
+        let mut flows = vec![api::ConnectorFlow::Authorize];
+        if self.auth_type == storage::enums::AuthenticationType::ThreeDs {
+            flows.push(api::ConnectorFlow::ThreeDs);
+        }
+        if self.request.setup_mandate_details.is_some() || self.request.mandate_id.is_some() {
+            flows.push(api::ConnectorFlow::Mandate);
+        }
+        if self.request.capture_method == Some(storage::enums::CaptureMethod::ManualMultiple) {
+            flows.push(api::ConnectorFlow::MultipleCapture);
+        }
+        if self.request.network_token.is_some() {
+            flows.push(api::ConnectorFlow::NetworkToken);
+        }
+        flows
+    }
+
+    /// Router data of an authorization the connector did not answer in time. The connector may
+    /// have authorized or even charged the payment, so the attempt is left in flight rather than
+    /// failed, which would have the merchant retry and charge the customer twice. Without a
+    /// connector transaction ID it cannot be synced, so the stale payments sync leaves it for
+    /// manual review.
+    fn into_timed_out(self) -> Self {
+        Self {
+            status: storage::enums::AttemptStatus::Authorizing,
+            response: Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::NoResponseId,
+                redirection_data: None,
+                redirect: false,
+                mandate_reference: None,
+                connector_metadata: None,
+                avs_result: None,
+                cvv_result: None,
+                connector_response_reference_id: None,
+            }),
+            ..self
+        }
+    }
+
     pub async fn decide_flow<'a, 'b>(
         &'b self,
         state: &'a AppState,
@@ -91,20 +161,47 @@ impl types::PaymentsAuthorizeRouterData {
     ) -> RouterResult<Self> {
         match confirm {
             Some(true) => {
+                let router_data = match self
+                    .preprocessing_steps(state, connector, &call_connector_action)
+                    .await?
+                {
+                    Ok(router_data) => router_data,
+                    // The payment cannot be authorized without the values obtained in pre-processing
+                    Err(error_response) => {
+                        return Ok(Self {
+                            response: Err(error_response),
+                            ..self.clone()
+                        })
+                    }
+                };
+
                 let connector_integration: services::BoxedConnectorIntegration<
                     '_,
                     api::Authorize,
                     types::PaymentsAuthorizeData,
                     types::PaymentsResponseData,
                 > = connector.connector.get_connector_integration();
-                let resp = services::execute_connector_processing_step(
+                let resp = match services::execute_connector_processing_step(
                     state,
                     connector_integration,
-                    self,
+                    &router_data,
                     call_connector_action,
                 )
                 .await
-                .map_err(|error| error.to_payment_failed_response())?;
+                {
+                    // The outcome is settled by a sync, the stale payments sync picking up the
+                    // attempt like any other attempt left in flight
+                    Err(error)
+                        if matches!(
+                            error.current_context(),
+                            errors::ConnectorError::RequestTimedOut
+                        ) =>
+                    {
+                        logger::warn!(?error, "Authorize request to the connector timed out");
+                        return Ok(router_data.into_timed_out());
+                    }
+                    resp => resp.map_err(|error| error.to_payment_failed_response())?,
+                };
 
                 Ok(
                     mandate::mandate_procedure(state, resp, maybe_customer, merchant_account)
@@ -114,6 +211,68 @@ impl types::PaymentsAuthorizeRouterData {
             _ => Ok(self.clone()),
         }
     }
+
+    /// Calls the pre-processing flow of connectors that need intermediate values (such as
+    /// tokenized card details) before a payment can be authorized, and threads the returned
+    /// connector metadata into the authorize request.
+    async fn preprocessing_steps(
+        &self,
+        state: &AppState,
+        connector: &api::ConnectorData,
+        call_connector_action: &payments::CallConnectorAction,
+    ) -> RouterResult<Result<Self, types::ErrorResponse>> {
+        // Merchant initiated charges reuse the payment method the connector stored for the mandate
+        let is_connector_mandate_payment = self.request.off_session == Some(true)
+            && self
+                .request
+                .mandate_id
+                .as_ref()
+                .and_then(|mandate_ids| mandate_ids.connector_mandate_id.as_ref())
+                .is_some();
+        if !(connector.connector_name.requires_pre_processing()
+            && matches!(
+                call_connector_action,
+                payments::CallConnectorAction::Trigger
+            ))
+            || is_connector_mandate_payment
+        {
+            return Ok(Ok(self.clone()));
+        }
+
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::PreProcessing,
+            types::PaymentsPreProcessingData,
+            types::PaymentsResponseData,
+        > = connector.connector.get_connector_integration();
+
+        let pre_processing_router_data =
+            access_token::router_data_type_conversion::<_, api::PreProcessing, _, _, _, _>(
+                self.clone(),
+                types::PaymentsPreProcessingData::from(&self.request),
+                Err(types::ErrorResponse::default()),
+            );
+
+        let resp = services::execute_connector_processing_step(
+            state,
+            connector_integration,
+            &pre_processing_router_data,
+            payments::CallConnectorAction::Trigger,
+        )
+        .await
+        .map_err(|error| error.to_payment_failed_response())?;
+
+        Ok(resp.response.map(|response| {
+            let mut router_data = self.clone();
+            if let types::PaymentsResponseData::PreProcessingResponse {
+                connector_metadata, ..
+            } = response
+            {
+                router_data.request.connector_meta = connector_metadata;
+            }
+            router_data
+        }))
+    }
 }
 
 impl mandate::MandateBehaviour for types::PaymentsAuthorizeData {
@@ -4,7 +4,7 @@ use super::{ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{ConnectorErrorExt, RouterResult},
-        payments::{self, access_token, transformers, PaymentData},
+        payments::{self, access_token, test_cards, transformers, PaymentData},
     },
     routes::AppState,
     services,
@@ -43,6 +43,24 @@ impl Feature<api::Void, types::PaymentsCancelData>
         call_connector_action: payments::CallConnectorAction,
         _merchant_account: &storage::MerchantAccount,
     ) -> RouterResult<Self> {
+        if test_cards::is_mock_transaction(
+            &state.conf.test_mode,
+            &self.request.connector_transaction_id,
+        ) {
+            return Ok(self.mock_void());
+        }
+
+        connector
+            .connector
+            .get_capabilities()
+            .validate(
+                connector.connector.id(),
+                &[api::ConnectorFlow::Void],
+                self.payment_method,
+                None,
+            )
+            .map_err(|error| error.to_payment_failed_response())?;
+
         self.decide_flow(
             state,
             connector,
@@ -4,7 +4,7 @@ use super::ConstructFlowSpecificData;
 use crate::{
     core::{
         errors::{ConnectorErrorExt, RouterResult},
-        payments::{self, access_token, transformers, Feature, PaymentData},
+        payments::{self, access_token, test_cards, transformers, Feature, PaymentData},
     },
     routes::AppState,
     services,
@@ -44,6 +44,24 @@ impl Feature<api::Capture, types::PaymentsCaptureData>
         call_connector_action: payments::CallConnectorAction,
         _merchant_account: &storage::MerchantAccount,
     ) -> RouterResult<Self> {
+        if test_cards::is_mock_transaction(
+            &state.conf.test_mode,
+            &self.request.connector_transaction_id,
+        ) {
+            return Ok(self.mock_capture());
+        }
+
+        connector
+            .connector
+            .get_capabilities()
+            .validate(
+                connector.connector.id(),
+                &[api::ConnectorFlow::Capture],
+                self.payment_method,
+                Some(self.request.currency),
+            )
+            .map_err(|error| error.to_payment_failed_response())?;
+
         self.decide_flow(
             state,
             connector,
@@ -4,7 +4,7 @@ use super::{ConstructFlowSpecificData, Feature};
 use crate::{
     core::{
         errors::{ConnectorErrorExt, RouterResult},
-        payments::{self, access_token, transformers, PaymentData},
+        payments::{self, access_token, test_cards, transformers, PaymentData},
     },
     routes::AppState,
     services,
@@ -45,6 +45,16 @@ impl Feature<api::PSync, types::PaymentsSyncData>
         call_connector_action: payments::CallConnectorAction,
         _merchant_account: &storage::MerchantAccount,
     ) -> RouterResult<Self> {
+        if let Ok(connector_transaction_id) = self
+            .request
+            .connector_transaction_id
+            .get_connector_transaction_id()
+        {
+            if test_cards::is_mock_transaction(&state.conf.test_mode, &connector_transaction_id) {
+                return Ok(self.mock_sync(connector_transaction_id));
+            }
+        }
+
         self.decide_flow(
             state,
             connector,
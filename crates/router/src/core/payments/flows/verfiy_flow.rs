@@ -42,6 +42,17 @@ impl Feature<api::Verify, types::VerifyRequestData> for types::VerifyRouterData
         call_connector_action: payments::CallConnectorAction,
         merchant_account: &storage::MerchantAccount,
     ) -> RouterResult<Self> {
+        connector
+            .connector
+            .get_capabilities()
+            .validate(
+                connector.connector.id(),
+                &[api::ConnectorFlow::Verify],
+                self.payment_method,
+                None,
+            )
+            .map_err(|error| error.to_verify_failed_response())?;
+
         self.decide_flow(
             state,
             connector,
@@ -0,0 +1,451 @@
+//! FX quotes for merchants that settle in another currency than the one they charge in.
+//!
+//! Payments of such merchants are annotated with the rate quoted for the settlement currency and
+//! the amount the merchant settles. The annotation is informational, the payment is still charged
+//! in its own currency and amount.
+//!
+//! Rates are refreshed into the `configs` table, under the key returned by [`get_rate_key`], by
+//! whichever feed quotes them. A feed that stops refreshing leaves its rates to go stale rather
+//! than be used indefinitely.
+
+use async_trait::async_trait;
+use common_utils::{ext_traits::StringExt, fp_utils::when};
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{
+    configs::settings::Settings,
+    core::errors::{self, CustomResult},
+    db::StorageInterface,
+    types::storage::enums,
+};
+
+/// Decimal places a rate can be quoted with.
+pub const MAX_RATE_DECIMAL_PLACES: usize = 10;
+
+/// Exchange rate, kept as an integer number of its last decimal place so that settlement amounts
+/// are computed without floating point rounding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FxRate {
+    value: u64,
+    decimal_places: u32,
+}
+
+impl FxRate {
+    /// Parses a rate written as a positive decimal number, like `"0.9134"`.
+    pub fn parse(rate: &str) -> CustomResult<Self, errors::FxQuoteError> {
+        let invalid_rate = || errors::FxQuoteError::InvalidRate {
+            rate: rate.to_string(),
+        };
+        let (integer, fraction) = rate.split_once('.').unwrap_or((rate, ""));
+        when(
+            integer.is_empty()
+                || fraction.len() > MAX_RATE_DECIMAL_PLACES
+                || !integer
+                    .chars()
+                    .chain(fraction.chars())
+                    .all(|digit| digit.is_ascii_digit()),
+            || Err(report!(invalid_rate())),
+        )?;
+
+        let value: u64 = format!("{integer}{fraction}")
+            .parse()
+            .into_report()
+            .change_context(invalid_rate())?;
+        when(value == 0, || {
+            Err(report!(invalid_rate())).attach_printable("Rate must be positive")
+        })?;
+        let decimal_places = u32::try_from(fraction.len())
+            .into_report()
+            .change_context(invalid_rate())?;
+
+        Ok(Self {
+            value,
+            decimal_places,
+        })
+    }
+
+    /// Amount in the minor unit of `to` worth `amount` in the minor unit of `from`, rounded half
+    /// up to the minor unit.
+    pub fn convert(
+        &self,
+        amount: i64,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<i64, errors::FxQuoteError> {
+        let converted = i128::from(amount)
+            .checked_mul(i128::from(self.value))
+            .and_then(|amount| amount.checked_mul(10_i128.pow(to.minor_unit_exponent())))
+            .ok_or(errors::FxQuoteError::AmountOverflow)
+            .into_report()?;
+        let divisor = 10_i128.pow(self.decimal_places + from.minor_unit_exponent());
+
+        i64::try_from((converted + divisor / 2) / divisor)
+            .into_report()
+            .change_context(errors::FxQuoteError::AmountOverflow)
+    }
+}
+
+impl std::fmt::Display for FxRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.decimal_places == 0 {
+            return write!(f, "{}", self.value);
+        }
+        let divisor = 10_u64.pow(self.decimal_places);
+        write!(
+            f,
+            "{}.{:0width$}",
+            self.value / divisor,
+            self.value % divisor,
+            width = usize::try_from(self.decimal_places).unwrap_or_default()
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FxQuote {
+    pub from: enums::Currency,
+    pub to: enums::Currency,
+    /// Units of `to` for one unit of `from`.
+    pub rate: FxRate,
+    pub quoted_at: PrimitiveDateTime,
+}
+
+impl FxQuote {
+    /// Rejects the quote when it is more than `max_age` seconds old at `now`.
+    pub fn validate_age(
+        &self,
+        now: PrimitiveDateTime,
+        max_age: i64,
+    ) -> CustomResult<(), errors::FxQuoteError> {
+        let age = (now - self.quoted_at).whole_seconds();
+        when(age > max_age, || {
+            Err(report!(errors::FxQuoteError::StaleQuote { age, max_age }))
+        })
+    }
+}
+
+#[async_trait]
+pub trait FxQuoteProvider: Send + Sync {
+    /// Quotes the rate to convert amounts in `from` to `to`.
+    async fn get_quote(
+        &self,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<FxQuote, errors::FxQuoteError>;
+}
+
+/// Key of the config the rate from `from` to `to` is refreshed into.
+pub fn get_rate_key(from: enums::Currency, to: enums::Currency) -> String {
+    format!("fx_rate_{from}_{to}")
+}
+
+/// Rate as refreshed into the `configs` table.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoredFxRate {
+    /// Units of `to` for one unit of `from`, as a decimal string like `"0.9134"`.
+    pub rate: String,
+    /// Time the feed quoted the rate at, which its age is measured from.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub quoted_at: PrimitiveDateTime,
+}
+
+/// Quotes the rates last refreshed into the `configs` table, as of the time they were quoted.
+pub struct StoredRatesProvider<'a> {
+    db: &'a dyn StorageInterface,
+}
+
+impl<'a> StoredRatesProvider<'a> {
+    pub fn new(db: &'a dyn StorageInterface) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl FxQuoteProvider for StoredRatesProvider<'_> {
+    async fn get_quote(
+        &self,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<FxQuote, errors::FxQuoteError> {
+        let config = self
+            .db
+            .find_config_by_key(&get_rate_key(from, to))
+            .await
+            .change_context(errors::FxQuoteError::RateUnavailable { from, to })?;
+        let stored_rate: StoredFxRate = config.config.parse_struct("StoredFxRate").change_context(
+            errors::FxQuoteError::InvalidRate {
+                rate: config.config.clone(),
+            },
+        )?;
+
+        Ok(FxQuote {
+            from,
+            to,
+            rate: FxRate::parse(&stored_rate.rate)?,
+            quoted_at: stored_rate.quoted_at,
+        })
+    }
+}
+
+/// Currency and amount a payment settles in, with the rate they were quoted at.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settlement {
+    pub currency: enums::Currency,
+    pub amount: i64,
+    pub fx_rate: FxRate,
+}
+
+/// Settlement of `amount` in `currency` for a merchant settling in `settlement_currency`, `None`
+/// when the payment is already in the settlement currency. Quotes older than `max_quote_age`
+/// seconds at `now` are rejected.
+pub async fn get_settlement(
+    provider: &dyn FxQuoteProvider,
+    (amount, currency): (i64, enums::Currency),
+    settlement_currency: enums::Currency,
+    now: PrimitiveDateTime,
+    max_quote_age: i64,
+) -> CustomResult<Option<Settlement>, errors::FxQuoteError> {
+    if currency == settlement_currency {
+        return Ok(None);
+    }
+
+    let quote = provider.get_quote(currency, settlement_currency).await?;
+    quote.validate_age(now, max_quote_age)?;
+
+    Ok(Some(Settlement {
+        currency: settlement_currency,
+        amount: quote.rate.convert(amount, currency, settlement_currency)?,
+        fx_rate: quote.rate,
+    }))
+}
+
+/// Settlement the payment of the merchant is annotated with, if the merchant has a settlement
+/// currency. The annotation is informational, so payments that cannot be quoted, because no
+/// current rate is available, are left without it rather than rejected.
+#[instrument(skip_all)]
+pub async fn get_payment_settlement(
+    db: &dyn StorageInterface,
+    conf: &Settings,
+    merchant_id: &str,
+    money: (i64, enums::Currency),
+) -> Option<Settlement> {
+    let settlement_currency = conf
+        .merchant_currencies
+        .get_settlement_currency(merchant_id)?;
+
+    get_settlement(
+        &StoredRatesProvider::new(db),
+        money,
+        settlement_currency,
+        common_utils::date_time::now(),
+        conf.fx_quotes.max_quote_age,
+    )
+    .await
+    .unwrap_or_else(|error| {
+        logger::error!(
+            ?error,
+            %merchant_id,
+            %settlement_currency,
+            "Failed to quote the settlement amount of the payment"
+        );
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{db::StorageImpl, routes, types::storage};
+
+    struct FakeFxQuoteProvider {
+        quoted_at: PrimitiveDateTime,
+    }
+
+    #[async_trait]
+    impl FxQuoteProvider for FakeFxQuoteProvider {
+        async fn get_quote(
+            &self,
+            from: enums::Currency,
+            to: enums::Currency,
+        ) -> CustomResult<FxQuote, errors::FxQuoteError> {
+            Ok(FxQuote {
+                from,
+                to,
+                rate: FxRate::parse("0.9134").unwrap(),
+                quoted_at: self.quoted_at,
+            })
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_fresh_quote_annotates_settlement() {
+        let now = common_utils::date_time::now();
+        let provider = FakeFxQuoteProvider {
+            quoted_at: now.saturating_sub(time::Duration::seconds(60)),
+        };
+
+        let settlement = get_settlement(
+            &provider,
+            (10050, enums::Currency::USD),
+            enums::Currency::EUR,
+            now,
+            300,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(settlement.currency, enums::Currency::EUR);
+        assert_eq!(settlement.amount, 9180);
+        assert_eq!(settlement.fx_rate.to_string(), "0.9134");
+    }
+
+    #[actix_rt::test]
+    async fn test_stale_quote_is_rejected() {
+        let now = common_utils::date_time::now();
+        let provider = FakeFxQuoteProvider {
+            quoted_at: now.saturating_sub(time::Duration::seconds(600)),
+        };
+
+        let result = get_settlement(
+            &provider,
+            (10050, enums::Currency::USD),
+            enums::Currency::EUR,
+            now,
+            300,
+        )
+        .await;
+
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::FxQuoteError::StaleQuote {
+                age: 600,
+                max_age: 300
+            })
+        ));
+    }
+
+    async fn store_rate(db: &dyn StorageInterface, rate: &str, quoted_at: PrimitiveDateTime) {
+        db.insert_config(storage::ConfigNew {
+            key: get_rate_key(enums::Currency::USD, enums::Currency::EUR),
+            config: serde_json::to_string(&StoredFxRate {
+                rate: rate.to_string(),
+                quoted_at,
+            })
+            .unwrap(),
+        })
+        .await
+        .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn test_stored_rate_is_as_old_as_its_quote() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let now = common_utils::date_time::now();
+        store_rate(
+            &*state.store,
+            "0.9134",
+            now.saturating_sub(time::Duration::seconds(600)),
+        )
+        .await;
+
+        let result = get_settlement(
+            &StoredRatesProvider::new(&*state.store),
+            (10050, enums::Currency::USD),
+            enums::Currency::EUR,
+            now,
+            300,
+        )
+        .await;
+
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::FxQuoteError::StaleQuote { max_age: 300, .. })
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_payment_without_a_current_rate_is_not_annotated() {
+        let mut conf = Settings::default();
+        conf.merchant_currencies.overrides.insert(
+            "merchant_1".to_string(),
+            crate::configs::settings::MerchantCurrencyConfig {
+                default_currency: None,
+                supported_currencies: Vec::new(),
+                settlement_currency: Some(enums::Currency::EUR),
+            },
+        );
+        let state = routes::AppState::test_builder()
+            .settings(conf.clone())
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let db = &*state.store;
+        let money = (10050, enums::Currency::USD);
+
+        assert_eq!(
+            get_payment_settlement(db, &conf, "merchant_1", money).await,
+            None
+        );
+
+        let now = common_utils::date_time::now();
+        store_rate(
+            db,
+            "0.9134",
+            now.saturating_sub(time::Duration::seconds(600)),
+        )
+        .await;
+        assert_eq!(
+            get_payment_settlement(db, &conf, "merchant_1", money).await,
+            None
+        );
+
+        db.update_config_by_key(
+            &get_rate_key(enums::Currency::USD, enums::Currency::EUR),
+            storage::ConfigUpdate::Update {
+                config: Some(
+                    serde_json::to_string(&StoredFxRate {
+                        rate: "0.9134".to_string(),
+                        quoted_at: now,
+                    })
+                    .unwrap(),
+                ),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_payment_settlement(db, &conf, "merchant_1", money)
+                .await
+                .map(|settlement| settlement.amount),
+            Some(9180)
+        );
+        assert_eq!(
+            get_payment_settlement(db, &conf, "merchant_2", money).await,
+            None
+        );
+    }
+
+    #[test]
+    fn test_rate_converts_between_minor_units() {
+        let rate = FxRate::parse("149.5").unwrap();
+
+        assert_eq!(
+            rate.convert(1050, enums::Currency::USD, enums::Currency::JPY)
+                .unwrap(),
+            1570
+        );
+        assert!(FxRate::parse("0").is_err());
+        assert!(FxRate::parse("-1.2").is_err());
+    }
+}
@@ -3,16 +3,16 @@ use std::borrow::Cow;
 use common_utils::{ext_traits::AsyncExt, fp_utils};
 // TODO : Evaluate all the helper functions ()
 use error_stack::{report, IntoReport, ResultExt};
-use masking::ExposeOptionInterface;
+use masking::{ExposeOptionInterface, PeekInterface};
 use router_env::{instrument, tracing};
 use uuid::Uuid;
 
 use super::{
     operations::{BoxedOperation, Operation, PaymentResponse},
-    CustomerDetails, PaymentData,
+    test_cards, CustomerDetails, PaymentData,
 };
 use crate::{
-    configs::settings::Server,
+    configs::settings::{MerchantCurrencies, ReturnUrl, Server, TestMode},
     consts,
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
@@ -225,6 +225,9 @@ pub fn validate_request_amount_and_amount_to_capture(
         (Some(amount), Some(amount_to_capture)) => {
             match amount {
                 api::Amount::Value(amount_inner) => {
+                    // Only a zero amount payment, which verifies the payment method, has nothing
+                    // to capture
+                    validate_amount_to_capture_is_positive(amount_to_capture)?;
                     // If both amount and amount to capture is present
                     // then amount to be capture should be less than or equal to request amount
                     utils::when(!amount_to_capture.le(&amount_inner.get()), || {
@@ -247,6 +250,91 @@ pub fn validate_request_amount_and_amount_to_capture(
     }
 }
 
+/// Validates that the surcharge and tax breakdown of the payment adds up to its amount.
+pub fn validate_surcharge_and_tax_amounts(
+    amount: i64,
+    surcharge_details: Option<api_models::payments::SurchargeDetails>,
+    tax_amount: Option<i64>,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    if surcharge_details.is_none() && tax_amount.is_none() {
+        return Ok(());
+    }
+    let tax_amount = tax_amount.unwrap_or_default();
+
+    match surcharge_details {
+        Some(surcharge_details) => {
+            utils::when(
+                surcharge_details.original_amount < 0
+                    || surcharge_details.surcharge_amount < 0
+                    || tax_amount < 0,
+                || {
+                    Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                        message:
+                            "original_amount, surcharge_amount and tax_amount must not be negative"
+                                .to_string()
+                    }))
+                },
+            )?;
+            let total_amount = surcharge_details
+                .original_amount
+                .saturating_add(surcharge_details.surcharge_amount)
+                .saturating_add(tax_amount);
+            utils::when(total_amount != amount, || {
+                Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                    message: format!(
+                        "original_amount, surcharge_amount and tax_amount add up to {total_amount} instead of amount {amount}"
+                    )
+                }))
+            })
+        }
+        None => utils::when(tax_amount < 0 || tax_amount > amount, || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!("tax_amount must be between 0 and amount {amount}")
+            }))
+        }),
+    }
+}
+
+/// Rebuilds the surcharge breakdown stored on a payment attempt, the original amount being
+/// whatever is left of the attempt amount once the surcharge and tax are taken out.
+pub fn get_stored_surcharge_details(
+    amount: i64,
+    surcharge_amount: Option<i64>,
+    tax_amount: Option<i64>,
+) -> Option<api_models::payments::SurchargeDetails> {
+    surcharge_amount.map(|surcharge_amount| api_models::payments::SurchargeDetails {
+        original_amount: amount
+            .saturating_sub(surcharge_amount)
+            .saturating_sub(tax_amount.unwrap_or_default()),
+        surcharge_amount,
+    })
+}
+
+/// Merges the surcharge and tax of the request with the ones stored on the payment attempt,
+/// validates them against the amount the payment is made for and keeps them on the attempt.
+pub fn update_surcharge_and_tax_amounts(
+    payment_attempt: &mut storage::PaymentAttempt,
+    amount: i64,
+    surcharge_details: Option<api_models::payments::SurchargeDetails>,
+    tax_amount: Option<i64>,
+) -> RouterResult<(Option<api_models::payments::SurchargeDetails>, Option<i64>)> {
+    let surcharge_details = surcharge_details.or_else(|| {
+        get_stored_surcharge_details(
+            payment_attempt.amount,
+            payment_attempt.surcharge_amount,
+            payment_attempt.tax_amount,
+        )
+    });
+    let tax_amount = tax_amount.or(payment_attempt.tax_amount);
+
+    validate_surcharge_and_tax_amounts(amount, surcharge_details, tax_amount)?;
+
+    payment_attempt.surcharge_amount =
+        surcharge_details.map(|surcharge_details| surcharge_details.surcharge_amount);
+    payment_attempt.tax_amount = tax_amount;
+    Ok((surcharge_details, tax_amount))
+}
+
 pub fn validate_mandate(
     req: impl Into<api::MandateValidationFields>,
 ) -> RouterResult<Option<api::MandateTxnType>> {
@@ -700,6 +788,8 @@ pub async fn make_pm_data<'a, F: Clone, R>(
                 Some(api::PaymentMethod::Card(card)) => {
                     payment_data.payment_attempt.payment_method =
                         Some(storage_enums::PaymentMethodType::Card);
+                    payment_data.saved_payment_method_id =
+                        supplementary_data.payment_method_id.clone();
                     if let Some(cvc) = card_cvc {
                         let mut updated_card = card;
                         updated_card.card_cvc = cvc;
@@ -757,6 +847,7 @@ pub async fn make_pm_data<'a, F: Clone, R>(
             Ok(pm_opt.to_owned())
         }
         (pm @ Some(api::PaymentMethod::PayLater(_)), _) => Ok(pm.to_owned()),
+        (pm @ Some(api::PaymentMethod::BankRedirect(_)), _) => Ok(pm.to_owned()),
         (pm_opt @ Some(pm @ api::PaymentMethod::Wallet(_)), _) => {
             let token = vault::Vault::store_payment_method_data_in_locker(
                 state,
@@ -806,11 +897,20 @@ pub(crate) fn validate_status(status: storage_enums::IntentStatus) -> RouterResu
     )
 }
 
+fn validate_amount_to_capture_is_positive(amount_to_capture: i64) -> RouterResult<()> {
+    utils::when(amount_to_capture <= 0, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "amount_to_capture should be greater than 0".to_string()
+        }))
+    })
+}
+
 #[instrument(skip_all)]
 pub(crate) fn validate_amount_to_capture(
     amount: i64,
     amount_to_capture: Option<i64>,
 ) -> RouterResult<()> {
+    amount_to_capture.map_or(Ok(()), validate_amount_to_capture_is_positive)?;
     utils::when(
         amount_to_capture.is_some() && (Some(amount) < amount_to_capture),
         || {
@@ -821,6 +921,37 @@ pub(crate) fn validate_amount_to_capture(
     )
 }
 
+/// Returns the amount to capture on a payment captured in multiple parts, which defaults to the
+/// amount left to capture.
+#[instrument(skip_all)]
+pub(crate) fn get_amount_to_capture_for_multiple_capture(
+    amount: i64,
+    amount_captured: Option<i64>,
+    amount_to_capture: Option<i64>,
+) -> RouterResult<i64> {
+    let amount_capturable = amount - amount_captured.unwrap_or(0);
+    let amount_to_capture = amount_to_capture.unwrap_or(amount_capturable);
+    utils::when(amount_to_capture > amount_capturable, || {
+        Err(report!(
+            errors::ApiErrorResponse::CaptureAmountExceedsCapturableAmount { amount_capturable }
+        ))
+    })?;
+    Ok(amount_to_capture)
+}
+
+/// Status of a payment captured in multiple parts, given the amount captured so far. The payment
+/// stays capturable until the whole authorization is captured.
+pub(crate) fn get_multiple_capture_status(
+    amount: i64,
+    amount_captured: Option<i64>,
+) -> storage_enums::AttemptStatus {
+    if amount_captured.unwrap_or(0) < amount {
+        storage_enums::AttemptStatus::PartialCharged
+    } else {
+        storage_enums::AttemptStatus::Charged
+    }
+}
+
 #[instrument(skip_all)]
 pub(crate) fn validate_payment_method_fields_present(
     req: &api::PaymentsRequest,
@@ -848,14 +979,27 @@ pub(crate) fn validate_payment_method_fields_present(
     Ok(())
 }
 
+/// Whether a connector response can move the payment attempt from its current status to the
+/// status of the response. Responses that cannot, like a charged payment going back to
+/// authorizing or the failed sync of a charged payment, must leave the attempt as it is so that
+/// a stale or wrong response cannot corrupt the state of the payment.
+pub fn is_valid_attempt_status_transition(
+    current_status: storage_enums::AttemptStatus,
+    next_status: storage_enums::AttemptStatus,
+) -> bool {
+    current_status.can_transition_to(next_status)
+}
+
 pub fn can_call_connector(status: &storage_enums::AttemptStatus) -> bool {
     !matches!(
         status,
         storage_enums::AttemptStatus::Charged
+            | storage_enums::AttemptStatus::Verified
             | storage_enums::AttemptStatus::AutoRefunded
             | storage_enums::AttemptStatus::Voided
             | storage_enums::AttemptStatus::CodInitiated
             | storage_enums::AttemptStatus::Authorized
+            | storage_enums::AttemptStatus::PartiallyAuthorized
             | storage_enums::AttemptStatus::Started
             | storage_enums::AttemptStatus::Failure
     )
@@ -890,7 +1034,10 @@ pub(super) fn validate_payment_list_request(
             message: "limit should be in between 1 and 100".to_string(),
         })
     })?;
-    Ok(())
+    storage::payment_intent::validate_created_time_range(req).map_err(|error| {
+        let message = error.current_context().to_string();
+        error.change_context(errors::ApiErrorResponse::InvalidRequestData { message })
+    })
 }
 
 pub fn get_handle_response_url(
@@ -1205,19 +1352,683 @@ pub(crate) async fn verify_client_secret(
         .transpose()
 }
 
+#[instrument(skip_all)]
+pub(crate) fn validate_card_data(
+    req: &api::PaymentsRequest,
+    test_mode: &TestMode,
+) -> RouterResult<()> {
+    // Test cards are rejected outside of test mode, in which they never reach a connector and are
+    // thus accepted whatever their expiry
+    if test_cards::get_mock_outcome(test_mode, req.payment_method_data.as_ref())?.is_some() {
+        return Ok(());
+    }
+    if let Some(api::PaymentMethod::Card(card)) = &req.payment_method_data {
+        validate_card_expiry(
+            card.card_exp_month.peek(),
+            card.card_exp_year.peek(),
+            common_utils::date_time::now().date(),
+        )
+        .map_err(|error| {
+            let field_name = match error.current_context() {
+                errors::ValidationError::IncorrectValueProvided { field_name } => *field_name,
+                _ => "card_exp_year",
+            };
+            error.change_context(errors::ApiErrorResponse::InvalidDataValue { field_name })
+        })?;
+    }
+    Ok(())
+}
+
+/// Validates that the card expiry month is between 01 and 12 and that the card has not expired
+/// as of `current_date`. The expiry year may be given with two or four digits.
+pub(crate) fn validate_card_expiry(
+    card_exp_month: &str,
+    card_exp_year: &str,
+    current_date: time::Date,
+) -> CustomResult<(), errors::ValidationError> {
+    let exp_month = card_exp_month
+        .parse::<u8>()
+        .ok()
+        .filter(|month| (1..=12).contains(month) && card_exp_month.len() <= 2)
+        .ok_or(errors::ValidationError::IncorrectValueProvided {
+            field_name: "card_exp_month",
+        })?;
+
+    let exp_year = match (card_exp_year.len(), card_exp_year.parse::<i32>()) {
+        (2, Ok(year)) => year + 2000,
+        (4, Ok(year)) => year,
+        _ => Err(errors::ValidationError::IncorrectValueProvided {
+            field_name: "card_exp_year",
+        })?,
+    };
+
+    let current_year = current_date.year();
+    let current_month = u8::from(current_date.month());
+    if exp_year < current_year {
+        Err(errors::ValidationError::IncorrectValueProvided {
+            field_name: "card_exp_year",
+        })?
+    }
+    if exp_year == current_year && exp_month < current_month {
+        Err(errors::ValidationError::IncorrectValueProvided {
+            field_name: "card_exp_month",
+        })?
+    }
+    Ok(())
+}
+
 #[inline]
 pub(crate) fn get_payment_id_from_client_secret(cs: &str) -> String {
     cs.split('_').take(2).collect::<Vec<&str>>().join("_")
 }
 
+/// Parses the IP address of the customer's browser. IPv4 addresses mapped into IPv6 are returned
+/// as plain IPv4 addresses.
+pub(crate) fn parse_browser_ip_address(
+    ip_address: &str,
+) -> CustomResult<std::net::IpAddr, errors::ValidationError> {
+    let ip_address = ip_address
+        .parse::<std::net::IpAddr>()
+        .into_report()
+        .change_context(errors::ValidationError::IncorrectValueProvided {
+            field_name: "browser_ip",
+        })?;
+    Ok(match ip_address {
+        std::net::IpAddr::V6(ipv6_address) => ipv6_address
+            .to_ipv4_mapped()
+            .map_or(ip_address, std::net::IpAddr::V4),
+        std::net::IpAddr::V4(_) => ip_address,
+    })
+}
+
+/// Rejects browser information with a malformed IP address and rewrites the address in its
+/// canonical form, so that only well formed addresses reach the connector.
+#[instrument(skip_all)]
+pub(crate) fn validate_browser_info(
+    browser_info: Option<serde_json::Value>,
+) -> RouterResult<Option<serde_json::Value>> {
+    let mut browser_info = match browser_info {
+        Some(browser_info) => browser_info,
+        None => return Ok(None),
+    };
+    if let Some(ip_address) = browser_info
+        .get_mut("ip_address")
+        .filter(|ip_address| !ip_address.is_null())
+    {
+        let canonical_ip_address = ip_address
+            .as_str()
+            .ok_or(errors::ValidationError::IncorrectValueProvided {
+                field_name: "browser_ip",
+            })
+            .into_report()
+            .and_then(parse_browser_ip_address)
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "browser_ip",
+            })?;
+        *ip_address = serde_json::Value::String(canonical_ip_address.to_string());
+    }
+    Ok(Some(browser_info))
+}
+
+/// Currency of a new payment, which is the merchant's default currency when the request omits
+/// one. Currencies the merchant does not accept payments in are rejected.
+pub fn get_payment_currency(
+    request_currency: Option<api_enums::Currency>,
+    merchant_id: &str,
+    merchant_currencies: &MerchantCurrencies,
+) -> RouterResult<storage_enums::Currency> {
+    let currency = request_currency
+        .map(ForeignInto::foreign_into)
+        .or_else(|| merchant_currencies.get_default_currency(merchant_id))
+        .get_required_value("currency")?;
+
+    utils::when(
+        !merchant_currencies.supports_currency(merchant_id, currency),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!("Payments in {currency} are not enabled for the merchant"),
+            }))
+        },
+    )?;
+    Ok(currency)
+}
+
+/// Rejects return URLs that are not absolute https URLs, as the customer is redirected there
+/// once the payment is authenticated. Plain http URLs are accepted when the configuration allows
+/// them. URLs longer than the column they are stored in are rejected as well.
+pub(crate) fn validate_return_url(
+    return_url: Option<&str>,
+    return_url_config: &ReturnUrl,
+) -> RouterResult<()> {
+    let return_url = match return_url {
+        Some(return_url) => return_url,
+        None => return Ok(()),
+    };
+    utils::when(return_url.len() > consts::MAX_RETURN_URL_LENGTH, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "return_url must be at most {} characters long",
+                consts::MAX_RETURN_URL_LENGTH
+            ),
+        }))
+    })?;
+    let is_allowed_scheme =
+        |scheme: &str| scheme == "https" || (return_url_config.allow_http && scheme == "http");
+    let is_valid = url::Url::parse(return_url)
+        .map(|url| url.has_host() && is_allowed_scheme(url.scheme()))
+        .unwrap_or(false);
+
+    utils::when(!is_valid, || {
+        Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "return_url".to_string(),
+            expected_format: if return_url_config.allow_http {
+                "absolute http or https URL".to_string()
+            } else {
+                "absolute https URL".to_string()
+            },
+        }))
+    })
+}
+
+/// Records of a card payment of `merchant_1` through `connector`, for tests to override the fields
+/// they depend on before inserting them with [`insert_test_payment`].
+#[cfg(test)]
+pub struct TestPayment {
+    /// `None` for tests inserting the merchant account themselves
+    pub merchant_account: Option<storage::MerchantAccountNew>,
+    pub payment_intent: storage::PaymentIntentNew,
+    pub connector_response: storage::ConnectorResponseNew,
+    pub payment_attempt: storage::PaymentAttemptNew,
+}
+
+#[cfg(test)]
+pub fn get_test_payment(
+    payment_id: &str,
+    connector: &str,
+    status: storage_enums::AttemptStatus,
+) -> TestPayment {
+    let current_time = common_utils::date_time::now();
+    let attempt_id = format!("{payment_id}_1");
+    let connector_transaction_id = format!("{connector}_{payment_id}");
+    TestPayment {
+        merchant_account: Some(storage::MerchantAccountNew {
+            merchant_id: "merchant_1".to_string(),
+            ..Default::default()
+        }),
+        payment_intent: storage::PaymentIntentNew {
+            payment_id: payment_id.to_string(),
+            merchant_id: "merchant_1".to_string(),
+            status: status.foreign_into(),
+            amount: 1000,
+            currency: Some(storage_enums::Currency::USD),
+            ..Default::default()
+        },
+        connector_response: storage::ConnectorResponseNew {
+            payment_id: payment_id.to_string(),
+            merchant_id: "merchant_1".to_string(),
+            attempt_id: attempt_id.clone(),
+            created_at: current_time,
+            modified_at: current_time,
+            connector_name: Some(connector.to_string()),
+            connector_transaction_id: Some(connector_transaction_id.clone()),
+            authentication_data: None,
+            encoded_data: None,
+        },
+        payment_attempt: storage::PaymentAttemptNew {
+            payment_id: payment_id.to_string(),
+            merchant_id: "merchant_1".to_string(),
+            attempt_id,
+            status,
+            amount: 1000,
+            currency: Some(storage_enums::Currency::USD),
+            connector: Some(connector.to_string()),
+            connector_transaction_id: Some(connector_transaction_id),
+            payment_method: Some(storage_enums::PaymentMethodType::Card),
+            ..Default::default()
+        },
+    }
+}
+
+/// Inserts the records of the payment, returning the merchant account when it was inserted along
+/// with the payment attempt.
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+pub async fn insert_test_payment(
+    db: &dyn StorageInterface,
+    payment: TestPayment,
+) -> (Option<storage::MerchantAccount>, storage::PaymentAttempt) {
+    let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+    let merchant_account = match payment.merchant_account {
+        Some(merchant_account) => Some(
+            db.insert_merchant(merchant_account)
+                .await
+                .expect("Failed to insert merchant account"),
+        ),
+        None => None,
+    };
+    db.insert_payment_intent(payment.payment_intent, storage_scheme)
+        .await
+        .expect("Failed to insert payment intent");
+    db.insert_connector_response(payment.connector_response, storage_scheme)
+        .await
+        .expect("Failed to insert connector response");
+    let payment_attempt = db
+        .insert_payment_attempt(payment.payment_attempt, storage_scheme)
+        .await
+        .expect("Failed to insert payment attempt");
+    (merchant_account, payment_attempt)
+}
+
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
 
+    #[test]
+    fn test_surcharge_and_tax_matching_the_amount() {
+        let surcharge_details = api_models::payments::SurchargeDetails {
+            original_amount: 6000,
+            surcharge_amount: 100,
+        };
+
+        assert!(
+            validate_surcharge_and_tax_amounts(6540, Some(surcharge_details), Some(440)).is_ok()
+        );
+        assert!(validate_surcharge_and_tax_amounts(6100, Some(surcharge_details), None).is_ok());
+        assert!(validate_surcharge_and_tax_amounts(6540, None, Some(440)).is_ok());
+        assert!(validate_surcharge_and_tax_amounts(6540, None, None).is_ok());
+    }
+
+    #[test]
+    fn test_surcharge_and_tax_not_matching_the_amount_are_rejected() {
+        let surcharge_details = api_models::payments::SurchargeDetails {
+            original_amount: 6000,
+            surcharge_amount: 100,
+        };
+
+        let result = validate_surcharge_and_tax_amounts(6540, Some(surcharge_details), None);
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        ));
+        assert!(validate_surcharge_and_tax_amounts(400, None, Some(440)).is_err());
+    }
+
+    #[test]
+    fn test_stored_surcharge_and_tax_are_validated_against_the_new_amount() {
+        let surcharge_details = get_stored_surcharge_details(6540, Some(100), Some(440));
+        assert_eq!(
+            surcharge_details,
+            Some(api_models::payments::SurchargeDetails {
+                original_amount: 6000,
+                surcharge_amount: 100,
+            })
+        );
+        assert_eq!(get_stored_surcharge_details(6540, None, Some(440)), None);
+
+        assert!(validate_surcharge_and_tax_amounts(6540, surcharge_details, Some(440)).is_ok());
+        // The stored breakdown no longer adds up once the amount changes without it
+        assert!(validate_surcharge_and_tax_amounts(7000, surcharge_details, Some(440)).is_err());
+    }
+
+    #[test]
+    fn test_partial_captures_summing_to_the_authorization() {
+        let amount = 1000;
+
+        let first_capture = get_amount_to_capture_for_multiple_capture(amount, None, Some(600));
+        assert_eq!(first_capture.unwrap(), 600);
+        assert_eq!(
+            get_multiple_capture_status(amount, Some(600)),
+            storage_enums::AttemptStatus::PartialCharged
+        );
+
+        let second_capture = get_amount_to_capture_for_multiple_capture(amount, Some(600), None);
+        assert_eq!(second_capture.unwrap(), 400);
+        assert_eq!(
+            get_multiple_capture_status(amount, Some(1000)),
+            storage_enums::AttemptStatus::Charged
+        );
+    }
+
+    #[test]
+    fn test_zero_amount_to_capture_is_rejected() {
+        assert!(validate_amount_to_capture(1000, Some(0)).is_err());
+        assert!(validate_amount_to_capture(1000, Some(-100)).is_err());
+        assert!(validate_amount_to_capture(1000, Some(1000)).is_ok());
+        assert!(validate_amount_to_capture(1000, None).is_ok());
+        assert!(validate_request_amount_and_amount_to_capture(Some(1000.into()), Some(0)).is_err());
+        // A zero amount payment verifies the payment method without capturing anything
+        assert!(validate_request_amount_and_amount_to_capture(Some(0.into()), None).is_ok());
+        assert!(validate_request_amount_and_amount_to_capture(Some(0.into()), Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_over_capture_is_rejected() {
+        let error =
+            get_amount_to_capture_for_multiple_capture(1000, Some(600), Some(500)).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::CaptureAmountExceedsCapturableAmount {
+                amount_capturable: 400
+            }
+        ));
+    }
+
     #[test]
     fn test_authenticate_client_secret() {
         let req_cs = Some("1".to_string());
         let pi_cs = Some("2".to_string());
         assert!(authenticate_client_secret(req_cs.as_ref(), pi_cs.as_ref()).is_err())
     }
+
+    fn get_card_expiry_error_field(
+        card_exp_month: &str,
+        card_exp_year: &str,
+    ) -> Option<&'static str> {
+        let current_date = time::macros::date!(2023 - 03 - 15);
+        validate_card_expiry(card_exp_month, card_exp_year, current_date)
+            .err()
+            .and_then(|error| match error.current_context() {
+                errors::ValidationError::IncorrectValueProvided { field_name } => Some(*field_name),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn test_card_expiry_rejects_expired_card() {
+        assert_eq!(
+            get_card_expiry_error_field("12", "2022"),
+            Some("card_exp_year")
+        );
+        assert_eq!(
+            get_card_expiry_error_field("02", "2023"),
+            Some("card_exp_month")
+        );
+    }
+
+    #[test]
+    fn test_card_expiry_accepts_current_month() {
+        assert_eq!(get_card_expiry_error_field("03", "2023"), None);
+        assert_eq!(get_card_expiry_error_field("3", "2023"), None);
+    }
+
+    #[test]
+    fn test_card_expiry_rejects_malformed_month() {
+        assert_eq!(
+            get_card_expiry_error_field("13", "2030"),
+            Some("card_exp_month")
+        );
+        assert_eq!(
+            get_card_expiry_error_field("00", "2030"),
+            Some("card_exp_month")
+        );
+        assert_eq!(
+            get_card_expiry_error_field("ab", "2030"),
+            Some("card_exp_month")
+        );
+    }
+
+    #[test]
+    fn test_card_expiry_normalizes_two_digit_year() {
+        assert_eq!(get_card_expiry_error_field("03", "23"), None);
+        assert_eq!(get_card_expiry_error_field("12", "30"), None);
+        assert_eq!(
+            get_card_expiry_error_field("12", "20"),
+            Some("card_exp_year")
+        );
+        assert_eq!(
+            get_card_expiry_error_field("12", "203"),
+            Some("card_exp_year")
+        );
+    }
+
+    fn get_card_payment_request(card_number: &str) -> api::PaymentsRequest {
+        api::PaymentsRequest {
+            payment_method_data: Some(api::PaymentMethod::Card(api::Card {
+                card_number: card_number.to_string().into(),
+                card_exp_month: "01".to_string().into(),
+                card_exp_year: "2020".to_string().into(),
+                card_holder_name: "John Doe".to_string().into(),
+                card_cvc: "123".to_string().into(),
+            })),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_card_data_of_test_card_depends_on_test_mode() {
+        let request = get_card_payment_request("4000000000000002");
+
+        assert!(validate_card_data(&request, &TestMode { enabled: true }).is_ok());
+        assert!(matches!(
+            validate_card_data(&request, &TestMode { enabled: false })
+                .as_ref()
+                .map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_card_data_of_expired_card_is_rejected_in_test_mode() {
+        let request = get_card_payment_request("4242424242424242");
+
+        assert!(matches!(
+            validate_card_data(&request, &TestMode { enabled: true })
+                .as_ref()
+                .map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::InvalidDataValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_browser_ip_address_is_parsed() {
+        assert_eq!(
+            parse_browser_ip_address("192.168.1.10").unwrap(),
+            std::net::IpAddr::from([192, 168, 1, 10])
+        );
+        assert_eq!(
+            parse_browser_ip_address("2001:0DB8:0000:0000:0000:0000:0000:0001")
+                .unwrap()
+                .to_string(),
+            "2001:db8::1"
+        );
+        assert_eq!(
+            parse_browser_ip_address("::ffff:192.168.1.10").unwrap(),
+            std::net::IpAddr::from([192, 168, 1, 10])
+        );
+    }
+
+    #[test]
+    fn test_malformed_browser_ip_address_is_rejected() {
+        for ip_address in ["192.168.1.256", "not an ip", "", "2001:db8::1::2"] {
+            let error = parse_browser_ip_address(ip_address).unwrap_err();
+            assert!(matches!(
+                error.current_context(),
+                errors::ValidationError::IncorrectValueProvided {
+                    field_name: "browser_ip"
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_browser_info_ip_address_is_normalized() {
+        let browser_info = validate_browser_info(Some(serde_json::json!({
+            "ip_address": "2001:DB8:0:0:0:0:0:1",
+            "language": "en-GB",
+        })))
+        .unwrap()
+        .unwrap();
+        assert_eq!(browser_info["ip_address"], "2001:db8::1");
+        assert_eq!(browser_info["language"], "en-GB");
+
+        let error =
+            validate_browser_info(Some(serde_json::json!({ "ip_address": 127 }))).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "browser_ip"
+            }
+        ));
+        assert!(validate_browser_info(Some(serde_json::json!({ "ip_address": null }))).is_ok());
+    }
+
+    fn get_merchant_currencies() -> MerchantCurrencies {
+        MerchantCurrencies {
+            default_currency: Some(storage_enums::Currency::USD),
+            overrides: std::collections::HashMap::from([(
+                "merchant_eu".to_string(),
+                crate::configs::settings::MerchantCurrencyConfig {
+                    default_currency: Some(storage_enums::Currency::EUR),
+                    supported_currencies: vec![
+                        storage_enums::Currency::EUR,
+                        storage_enums::Currency::GBP,
+                    ],
+                    settlement_currency: None,
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_default_currency_is_applied_when_omitted() {
+        let merchant_currencies = get_merchant_currencies();
+
+        assert_eq!(
+            get_payment_currency(None, "merchant_eu", &merchant_currencies).unwrap(),
+            storage_enums::Currency::EUR
+        );
+        assert_eq!(
+            get_payment_currency(None, "merchant_1", &merchant_currencies).unwrap(),
+            storage_enums::Currency::USD
+        );
+
+        let error =
+            get_payment_currency(None, "merchant_1", &MerchantCurrencies::default()).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "currency"
+            }
+        ));
+    }
+
+    #[test]
+    fn test_explicit_currency_overrides_default() {
+        let merchant_currencies = get_merchant_currencies();
+
+        assert_eq!(
+            get_payment_currency(
+                Some(api_enums::Currency::GBP),
+                "merchant_eu",
+                &merchant_currencies
+            )
+            .unwrap(),
+            storage_enums::Currency::GBP
+        );
+
+        let error = get_payment_currency(
+            Some(api_enums::Currency::USD),
+            "merchant_eu",
+            &merchant_currencies,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_https_return_url_is_accepted() {
+        let return_url_config = ReturnUrl { allow_http: false };
+        assert!(validate_return_url(
+            Some("https://example.com/return?order=1"),
+            &return_url_config
+        )
+        .is_ok());
+        assert!(validate_return_url(None, &return_url_config).is_ok());
+    }
+
+    #[test]
+    fn test_non_https_return_url_is_rejected() {
+        let return_url_config = ReturnUrl { allow_http: false };
+        for return_url in [
+            "http://example.com/return",
+            "www.example.com/return",
+            "/return",
+            "javascript:alert(1)",
+        ] {
+            let error = validate_return_url(Some(return_url), &return_url_config).unwrap_err();
+            assert!(matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::InvalidDataFormat { field_name, .. }
+                    if field_name == "return_url"
+            ));
+        }
+
+        let return_url_config = ReturnUrl { allow_http: true };
+        assert!(
+            validate_return_url(Some("http://localhost:8080/return"), &return_url_config).is_ok()
+        );
+        assert!(validate_return_url(Some("ftp://example.com/return"), &return_url_config).is_err());
+    }
+
+    #[test]
+    fn test_return_url_longer_than_its_column_is_rejected() {
+        let return_url_config = ReturnUrl { allow_http: false };
+        let return_url = format!("https://example.com/{}", "a".repeat(255));
+        let error = validate_return_url(Some(&return_url), &return_url_config).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+        ));
+
+        let return_url = format!("https://example.com/{}", "a".repeat(235));
+        assert_eq!(return_url.len(), consts::MAX_RETURN_URL_LENGTH);
+        assert!(validate_return_url(Some(&return_url), &return_url_config).is_ok());
+    }
+
+    #[test]
+    fn test_legal_attempt_status_transition_is_accepted() {
+        for (current_status, next_status) in [
+            (
+                storage_enums::AttemptStatus::Authorizing,
+                storage_enums::AttemptStatus::Authorized,
+            ),
+            (
+                storage_enums::AttemptStatus::Authorized,
+                storage_enums::AttemptStatus::Charged,
+            ),
+            (
+                storage_enums::AttemptStatus::Charged,
+                storage_enums::AttemptStatus::Charged,
+            ),
+        ] {
+            assert!(is_valid_attempt_status_transition(
+                current_status,
+                next_status
+            ));
+        }
+    }
+
+    #[test]
+    fn test_illegal_attempt_status_transition_is_rejected() {
+        assert!(!is_valid_attempt_status_transition(
+            storage_enums::AttemptStatus::Charged,
+            storage_enums::AttemptStatus::Authorizing,
+        ));
+        assert!(!is_valid_attempt_status_transition(
+            storage_enums::AttemptStatus::Charged,
+            storage_enums::AttemptStatus::Failure,
+        ));
+        assert!(!is_valid_attempt_status_transition(
+            storage_enums::AttemptStatus::Voided,
+            storage_enums::AttemptStatus::Charged,
+        ));
+        assert!(!is_valid_attempt_status_transition(
+            storage_enums::AttemptStatus::Authorized,
+            storage_enums::AttemptStatus::Authorizing,
+        ));
+    }
 }
@@ -0,0 +1,419 @@
+//! Idempotent payment creation.
+//!
+//! Clients can send an `Idempotency-Key` header with a create payment request. The key is stored
+//! in Redis along with the payment ID and a hash of the request, so that a retried request
+//! returns the payment created by the first one instead of creating another payment. The key
+//! stays bound to its payment ID even if the first request fails, and a request sent while
+//! another one with the same key is still being processed is rejected.
+
+use std::future::Future;
+
+use error_stack::{IntoReport, ResultExt};
+use redis_interface::SetnxReply;
+use serde::{Deserialize, Serialize};
+
+use super::{payments_core, CallConnectorAction, PaymentStatus};
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    db::StorageInterface,
+    logger,
+    routes::AppState,
+    services,
+    types::{api, storage},
+    utils::{self, ByteSliceExt},
+};
+
+/// Idempotency keys are remembered for a day (in seconds).
+const IDEMPOTENCY_KEY_TTL: i64 = 24 * 60 * 60;
+/// Time after which the lock of a request that never completed is released (in seconds).
+const IDEMPOTENCY_LOCK_TTL: i64 = 5 * 60;
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    request_hash: String,
+    payment_id: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IdempotencyCheck {
+    /// The key has been reserved for the given payment, which is yet to be created.
+    New,
+    /// The key was used before with an identical request that created the given payment.
+    Replay { payment_id: String },
+}
+
+fn get_idempotency_key(merchant_id: &str, idempotency_key: &str) -> String {
+    format!("idempotency_{merchant_id}_{idempotency_key}")
+}
+
+fn get_idempotency_lock_key(merchant_id: &str, idempotency_key: &str) -> String {
+    format!("idempotency_lock_{merchant_id}_{idempotency_key}")
+}
+
+/// Locks the idempotency key while its request is processed, rejecting concurrent requests with
+/// the same key.
+pub async fn lock_idempotency_key(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    idempotency_key: &str,
+) -> RouterResult<()> {
+    let reply = db
+        .set_key_if_not_exists_with_expiry(
+            &get_idempotency_lock_key(merchant_id, idempotency_key),
+            "locked",
+            IDEMPOTENCY_LOCK_TTL,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to lock idempotency key")?;
+    utils::when(reply != SetnxReply::KeySet, || {
+        Err(errors::ApiErrorResponse::IdempotentRequestInProgress).into_report()
+    })
+}
+
+async fn unlock_idempotency_key(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    idempotency_key: &str,
+) {
+    if let Err(error) = db
+        .delete_key(&get_idempotency_lock_key(merchant_id, idempotency_key))
+        .await
+    {
+        logger::error!(?error, "Failed to unlock idempotency key");
+    }
+}
+
+fn get_request_hash(req: &api::PaymentsRequest) -> RouterResult<String> {
+    let request = serde_json::to_vec(req)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize payments request")?;
+    Ok(blake3::hash(&request).to_hex().to_string())
+}
+
+/// Reserves `idempotency_key` for `payment_id`, unless it was already used by the merchant.
+/// Reusing a key for a request that does not match the original one is rejected.
+pub async fn check_idempotency_key(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    idempotency_key: &str,
+    request_hash: &str,
+    payment_id: &str,
+) -> RouterResult<IdempotencyCheck> {
+    utils::when(
+        idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LENGTH,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "Idempotency key must be between 1 and {MAX_IDEMPOTENCY_KEY_LENGTH} characters"
+                ),
+            })
+            .into_report()
+        },
+    )?;
+
+    let key = get_idempotency_key(merchant_id, idempotency_key);
+    let record = serde_json::to_string(&IdempotencyRecord {
+        request_hash: request_hash.to_string(),
+        payment_id: payment_id.to_string(),
+    })
+    .into_report()
+    .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let reply = db
+        .set_key_if_not_exists_with_expiry(&key, &record, IDEMPOTENCY_KEY_TTL)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to store idempotency key")?;
+    if reply == SetnxReply::KeySet {
+        return Ok(IdempotencyCheck::New);
+    }
+
+    let existing_record: IdempotencyRecord = db
+        .get_key(&key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .parse_struct("IdempotencyRecord")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    utils::when(existing_record.request_hash != request_hash, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "Idempotency key has already been used with a different request".to_string(),
+        })
+        .into_report()
+    })?;
+
+    Ok(IdempotencyCheck::Replay {
+        payment_id: existing_record.payment_id,
+    })
+}
+
+/// Creates a payment with `create_payment`, or returns the payment created earlier with the same
+/// idempotency key.
+pub async fn create_payment_with_idempotency_key<F, Fut>(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    mut req: api::PaymentsRequest,
+    idempotency_key: Option<&str>,
+    create_payment: F,
+) -> RouterResponse<api::PaymentsResponse>
+where
+    F: FnOnce(storage::MerchantAccount, api::PaymentsRequest) -> Fut,
+    Fut: Future<Output = RouterResponse<api::PaymentsResponse>>,
+{
+    let idempotency_key = match idempotency_key {
+        Some(idempotency_key) => idempotency_key,
+        None => return create_payment(merchant_account, req).await,
+    };
+
+    let request_hash = get_request_hash(&req)?;
+    // The payment ID has to be known before the payment is created, so that it can be stored
+    // with the idempotency key
+    let payment_id = match &req.payment_id {
+        Some(payment_id) => payment_id
+            .get_payment_intent_id()
+            .change_context(errors::ApiErrorResponse::PaymentNotFound)?,
+        None => {
+            let payment_id = utils::generate_id(consts::ID_LENGTH, "pay");
+            req.payment_id = Some(api::PaymentIdType::PaymentIntentId(payment_id.clone()));
+            payment_id
+        }
+    };
+
+    let check = check_idempotency_key(
+        &*state.store,
+        &merchant_account.merchant_id,
+        idempotency_key,
+        &request_hash,
+        &payment_id,
+    )
+    .await?;
+    let merchant_id = merchant_account.merchant_id.clone();
+    lock_idempotency_key(&*state.store, &merchant_id, idempotency_key).await?;
+
+    let response = match check {
+        IdempotencyCheck::New => create_payment(merchant_account, req).await,
+        IdempotencyCheck::Replay { payment_id } => {
+            replay_payment(state, merchant_account, req, payment_id, create_payment).await
+        }
+    };
+    unlock_idempotency_key(&*state.store, &merchant_id, idempotency_key).await;
+    response
+}
+
+/// Returns the payment created with the same idempotency key, or creates it with the payment ID
+/// bound to the key if the earlier request failed before the payment was created.
+async fn replay_payment<F, Fut>(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    mut req: api::PaymentsRequest,
+    payment_id: String,
+    create_payment: F,
+) -> RouterResponse<api::PaymentsResponse>
+where
+    F: FnOnce(storage::MerchantAccount, api::PaymentsRequest) -> Fut,
+    Fut: Future<Output = RouterResponse<api::PaymentsResponse>>,
+{
+    match state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+    {
+        Err(error) if error.current_context().is_db_not_found() => {
+            logger::info!(%payment_id, "Creating payment of an idempotency key whose request failed");
+            req.payment_id = Some(api::PaymentIdType::PaymentIntentId(payment_id));
+            create_payment(merchant_account, req).await
+        }
+        Err(error) => Err(error.change_context(errors::ApiErrorResponse::InternalServerError)),
+        Ok(_) => {
+            logger::info!(%payment_id, "Returning payment created with the same idempotency key");
+            payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+                state,
+                merchant_account,
+                PaymentStatus,
+                api::PaymentsRetrieveRequest {
+                    resource_id: api::PaymentIdType::PaymentIntentId(payment_id),
+                    merchant_id: None,
+                    force_sync: false,
+                    param: None,
+                    connector: None,
+                },
+                services::AuthFlow::Merchant,
+                CallConnectorAction::Avoid,
+            )
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::{
+        check_idempotency_key, create_payment_with_idempotency_key, lock_idempotency_key,
+        IdempotencyCheck,
+    };
+    use crate::{
+        core::errors,
+        db::StorageImpl,
+        routes::AppState,
+        types::{api, storage},
+        utils,
+    };
+
+    #[actix_rt::test]
+    async fn test_replayed_request_returns_same_payment() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let idempotency_key = utils::generate_id(16, "idem");
+
+        let first = check_idempotency_key(
+            &*state.store,
+            "merchant_1",
+            &idempotency_key,
+            "request_hash",
+            "pay_first",
+        )
+        .await
+        .unwrap();
+        assert_eq!(first, IdempotencyCheck::New);
+
+        let replay = check_idempotency_key(
+            &*state.store,
+            "merchant_1",
+            &idempotency_key,
+            "request_hash",
+            "pay_second",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            replay,
+            IdempotencyCheck::Replay {
+                payment_id: "pay_first".to_string()
+            }
+        );
+
+        // Keys are scoped to the merchant
+        let other_merchant = check_idempotency_key(
+            &*state.store,
+            "merchant_2",
+            &idempotency_key,
+            "request_hash",
+            "pay_third",
+        )
+        .await
+        .unwrap();
+        assert_eq!(other_merchant, IdempotencyCheck::New);
+    }
+
+    #[actix_rt::test]
+    async fn test_conflicting_request_is_rejected() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let idempotency_key = utils::generate_id(16, "idem");
+
+        check_idempotency_key(
+            &*state.store,
+            "merchant_1",
+            &idempotency_key,
+            "request_hash",
+            "pay_first",
+        )
+        .await
+        .unwrap();
+
+        let conflict = check_idempotency_key(
+            &*state.store,
+            "merchant_1",
+            &idempotency_key,
+            "other_request_hash",
+            "pay_second",
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            conflict.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_concurrent_request_is_rejected_while_in_flight() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let idempotency_key = utils::generate_id(16, "idem");
+
+        lock_idempotency_key(&*state.store, "merchant_1", &idempotency_key)
+            .await
+            .unwrap();
+        let conflict = lock_idempotency_key(&*state.store, "merchant_1", &idempotency_key)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            conflict.current_context(),
+            errors::ApiErrorResponse::IdempotentRequestInProgress
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_failed_request_keeps_the_payment_id_of_the_key() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let merchant_account = state
+            .store
+            .insert_merchant(storage::MerchantAccountNew {
+                merchant_id: "merchant_1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let idempotency_key = utils::generate_id(16, "idem");
+
+        let mut payment_ids = Vec::new();
+        for _ in 0..2 {
+            let response = create_payment_with_idempotency_key(
+                &state,
+                merchant_account.clone(),
+                api::PaymentsRequest {
+                    amount: Some(1000.into()),
+                    ..Default::default()
+                },
+                Some(&idempotency_key),
+                |_, req| {
+                    payment_ids.push(req.payment_id);
+                    async { Err(errors::ApiErrorResponse::InternalServerError.into()) }
+                },
+            )
+            .await;
+            assert!(response.is_err());
+        }
+
+        // The retry creates the payment with the payment ID of the first request, and the key is
+        // released once the request completes
+        assert_eq!(payment_ids.len(), 2);
+        assert!(payment_ids[0].is_some());
+        assert_eq!(payment_ids[0], payment_ids[1]);
+        lock_idempotency_key(&*state.store, "merchant_1", &idempotency_key)
+            .await
+            .unwrap();
+    }
+}
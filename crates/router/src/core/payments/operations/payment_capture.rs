@@ -59,8 +59,6 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
 
         helpers::validate_status(payment_intent.status)?;
 
-        helpers::validate_amount_to_capture(payment_intent.amount, request.amount_to_capture)?;
-
         payment_attempt = db
             .find_payment_attempt_by_payment_id_merchant_id(
                 &payment_id,
@@ -72,9 +70,17 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
                 error.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
             })?;
 
-        payment_attempt
-            .amount_to_capture
-            .update_value(request.amount_to_capture);
+        // A partially authorized payment can be captured up to the amount the connector authorized
+        let authorized_amount = payment_attempt
+            .authorized_amount
+            .unwrap_or(payment_intent.amount);
+        helpers::validate_amount_to_capture(authorized_amount, request.amount_to_capture)?;
+
+        payment_attempt.amount_to_capture.update_value(
+            request
+                .amount_to_capture
+                .or(payment_attempt.authorized_amount),
+        );
 
         let capture_method = payment_attempt
             .capture_method
@@ -82,6 +88,15 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
 
         helpers::validate_capture_method(capture_method)?;
 
+        if capture_method == enums::CaptureMethod::ManualMultiple {
+            payment_attempt.amount_to_capture =
+                Some(helpers::get_amount_to_capture_for_multiple_capture(
+                    authorized_amount,
+                    payment_intent.amount_captured,
+                    request.amount_to_capture,
+                )?);
+        }
+
         currency = payment_attempt.currency.get_required_value("currency")?;
 
         amount = payment_attempt.amount.into();
@@ -139,6 +154,10 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
                 connector_response,
                 sessions_token: vec![],
                 card_cvc: None,
+                surcharge_details: None,
+                tax_amount: None,
+                sca_exemption: None,
+                saved_payment_method_id: None,
             },
             None,
         ))
@@ -194,3 +213,118 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsCaptureRequest> for Paymen
         ))
     }
 }
+
+#[cfg(all(test, feature = "dummy_connector"))]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{configs::settings::Settings, db::StorageImpl, routes};
+
+    async fn create_authorized_payment(state: &AppState) -> storage::MerchantAccount {
+        let db = &*state.store;
+        let mut payment =
+            helpers::get_test_payment("pay_1", "dummy", enums::AttemptStatus::Authorized);
+        payment.payment_attempt.capture_method = Some(enums::CaptureMethod::ManualMultiple);
+        let (merchant_account, _) = helpers::insert_test_payment(db, payment).await;
+        db.insert_merchant_connector_account(storage::MerchantConnectorAccountNew {
+            merchant_id: Some("merchant_1".to_string()),
+            connector_name: Some("dummy".to_string()),
+            connector_account_details: Some(masking::Secret::new(serde_json::json!({
+                "auth_type": "HeaderKey",
+                "api_key": "dummy_api_key",
+            }))),
+            merchant_connector_id: "mca_1".to_string(),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to insert merchant connector account");
+        merchant_account.expect("Merchant account was not inserted")
+    }
+
+    async fn capture(
+        state: &AppState,
+        merchant_account: &storage::MerchantAccount,
+        amount_to_capture: i64,
+    ) -> RouterResult<payments::PaymentData<api::Capture>> {
+        payments::payments_operation_core::<api::Capture, _, _, _>(
+            state,
+            merchant_account.clone(),
+            PaymentCapture,
+            api::PaymentsCaptureRequest {
+                payment_id: Some("pay_1".to_string()),
+                merchant_id: Some("merchant_1".to_string()),
+                amount_to_capture: Some(amount_to_capture),
+                ..Default::default()
+            },
+            payments::CallConnectorAction::Trigger,
+        )
+        .await
+        .map(|(payment_data, _, _)| payment_data)
+    }
+
+    #[actix_rt::test]
+    async fn test_partial_captures_add_up_to_the_authorization() {
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/payments/dummy_pay_1/capture"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "id": "dummy_pay_1", "status": "succeeded" }),
+                ),
+            )
+            .expect(2)
+            .mount(&connector_server)
+            .await;
+
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.connectors.dummyconnector.base_url = format!("{}/", connector_server.uri());
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let merchant_account = create_authorized_payment(&state).await;
+
+        let payment_data = capture(&state, &merchant_account, 600).await.unwrap();
+        assert_eq!(
+            payment_data.payment_attempt.status,
+            enums::AttemptStatus::PartialCharged
+        );
+        assert_eq!(
+            payment_data.payment_intent.status,
+            enums::IntentStatus::RequiresCapture
+        );
+        assert_eq!(payment_data.payment_intent.amount_captured, Some(600));
+
+        let error = capture(&state, &merchant_account, 500).await.unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::CaptureAmountExceedsCapturableAmount {
+                amount_capturable: 400
+            }
+        ));
+
+        let payment_data = capture(&state, &merchant_account, 400).await.unwrap();
+        assert_eq!(
+            payment_data.payment_attempt.status,
+            enums::AttemptStatus::Charged
+        );
+        assert_eq!(
+            payment_data.payment_intent.status,
+            enums::IntentStatus::Succeeded
+        );
+
+        let payment_intent = state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch payment intent");
+        assert_eq!(payment_intent.amount_captured, Some(1000));
+    }
+}
@@ -15,7 +15,6 @@ use crate::{
     db::StorageInterface,
     routes::AppState,
     types::{
-        self,
         api::{self, PaymentIdTypeExt},
         storage::{self, enums as storage_enums},
         transformers::ForeignInto,
@@ -47,6 +46,8 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         let storage_scheme = merchant_account.storage_scheme;
         let (mut payment_intent, mut payment_attempt, currency, amount, connector_response);
 
+        helpers::validate_card_data(request, &state.conf.test_mode)?;
+
         let payment_id = payment_id
             .get_payment_intent_id()
             .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
@@ -81,14 +82,8 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             payment_intent.client_secret.as_ref(),
         )?;
 
-        let browser_info = request
-            .browser_info
-            .clone()
-            .map(|x| utils::Encode::<types::BrowserInformation>::encode_to_value(&x))
-            .transpose()
-            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "browser_info",
-            })?;
+        let browser_info = helpers::validate_browser_info(request.browser_info.clone())?;
+        helpers::validate_return_url(request.return_url.as_deref(), &state.conf.return_url)?;
 
         payment_attempt = db
             .find_payment_attempt_by_payment_id_merchant_id(
@@ -115,6 +110,13 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         currency = payment_attempt.currency.get_required_value("currency")?;
         amount = payment_attempt.amount.into();
 
+        let (surcharge_details, tax_amount) = helpers::update_surcharge_and_tax_amounts(
+            &mut payment_attempt,
+            amount.into(),
+            request.surcharge_details,
+            request.tax_amount,
+        )?;
+
         helpers::validate_customer_id_mandatory_cases(
             request.shipping.is_some(),
             request.billing.is_some(),
@@ -157,6 +159,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         payment_intent.shipping_address_id = shipping_address.clone().map(|i| i.address_id);
         payment_intent.billing_address_id = billing_address.clone().map(|i| i.address_id);
         payment_intent.return_url = request.return_url.clone();
+        payment_attempt.return_url = request.return_url.clone().or(payment_attempt.return_url);
 
         Ok((
             Box::new(self),
@@ -181,6 +184,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 refunds: vec![],
                 sessions_token: vec![],
                 card_cvc: request.card_cvc.clone(),
+                surcharge_details,
+                tax_amount,
+                sca_exemption: request.sca_exemption,
+                saved_payment_method_id: None,
             },
             Some(CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -310,6 +317,9 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     browser_info,
                     connector,
                     payment_token,
+                    return_url: payment_data.payment_attempt.return_url.clone(),
+                    surcharge_amount: payment_data.payment_attempt.surcharge_amount,
+                    tax_amount: payment_data.payment_attempt.tax_amount,
                 },
                 storage_scheme,
             )
@@ -9,16 +9,16 @@ use uuid::Uuid;
 
 use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, ValidateRequest};
 use crate::{
+    configs::settings::MerchantCurrencies,
     consts,
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{self, fx, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
         utils as core_utils,
     },
     db::StorageInterface,
     routes::AppState,
     types::{
-        self,
         api::{self, PaymentIdTypeExt},
         storage::{
             self,
@@ -54,7 +54,13 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         let (payment_intent, payment_attempt, connector_response);
 
-        let money @ (amount, currency) = payments_create_request_validation(request)?;
+        let money @ (amount, currency) = payments_create_request_validation(
+            request,
+            merchant_id,
+            &state.conf.merchant_currencies,
+        )?;
+
+        helpers::validate_card_data(request, &state.conf.test_mode)?;
 
         let payment_id = payment_id
             .get_payment_intent_id()
@@ -87,16 +93,16 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         )
         .await?;
 
-        let browser_info = request
-            .browser_info
-            .clone()
-            .map(|x| {
-                common_utils::ext_traits::Encode::<types::BrowserInformation>::encode_to_value(&x)
-            })
-            .transpose()
-            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "browser_info",
-            })?;
+        let browser_info = helpers::validate_browser_info(request.browser_info.clone())?;
+        helpers::validate_return_url(request.return_url.as_deref(), &state.conf.return_url)?;
+        helpers::validate_surcharge_and_tax_amounts(
+            amount.into(),
+            request.surcharge_details,
+            request.tax_amount,
+        )?;
+        let settlement =
+            fx::get_payment_settlement(db, &state.conf, merchant_id, (amount.into(), currency))
+                .await;
 
         payment_attempt = db
             .insert_payment_attempt(
@@ -107,6 +113,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                     payment_method_type,
                     request,
                     browser_info,
+                    settlement,
                 ),
                 storage_scheme,
             )
@@ -192,6 +199,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 connector_response,
                 sessions_token: vec![],
                 card_cvc: request.card_cvc.clone(),
+                surcharge_details: request.surcharge_details,
+                tax_amount: request.tax_amount,
+                sca_exemption: request.sca_exemption,
+                saved_payment_method_id: None,
             },
             Some(CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -412,6 +423,7 @@ impl PaymentCreate {
         payment_method: Option<enums::PaymentMethodType>,
         request: &api::PaymentsRequest,
         browser_info: Option<serde_json::Value>,
+        settlement: Option<fx::Settlement>,
     ) -> storage::PaymentAttemptNew {
         let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
         let status =
@@ -433,6 +445,14 @@ impl PaymentCreate {
             last_synced,
             authentication_type: request.authentication_type.map(ForeignInto::foreign_into),
             browser_info,
+            return_url: request.return_url.clone(),
+            surcharge_amount: request
+                .surcharge_details
+                .map(|surcharge_details| surcharge_details.surcharge_amount),
+            tax_amount: request.tax_amount,
+            settlement_currency: settlement.as_ref().map(|settlement| settlement.currency),
+            settlement_amount: settlement.as_ref().map(|settlement| settlement.amount),
+            fx_rate: settlement.map(|settlement| settlement.fx_rate.to_string()),
             ..storage::PaymentAttemptNew::default()
         }
     }
@@ -503,11 +523,15 @@ impl PaymentCreate {
 #[instrument(skip_all)]
 pub fn payments_create_request_validation(
     req: &api::PaymentsRequest,
+    merchant_id: &str,
+    merchant_currencies: &MerchantCurrencies,
 ) -> RouterResult<(api::Amount, enums::Currency)> {
-    let currency = req
-        .currency
-        .map(ForeignInto::foreign_into)
-        .get_required_value("currency")?;
+    let currency = helpers::get_payment_currency(req.currency, merchant_id, merchant_currencies)?;
     let amount = req.amount.get_required_value("amount")?;
+
+    core_utils::validate_amount_for_currency(amount.into(), currency)?;
+    if let Some(amount_to_capture) = req.amount_to_capture {
+        core_utils::validate_amount_for_currency(amount_to_capture, currency)?;
+    }
     Ok((amount, currency))
 }
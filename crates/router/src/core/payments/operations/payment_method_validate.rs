@@ -151,6 +151,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::VerifyRequest> for Paym
                 refunds: vec![],
                 sessions_token: vec![],
                 card_cvc: None,
+                surcharge_details: None,
+                tax_amount: None,
+                sca_exemption: None,
+                saved_payment_method_id: None,
             },
             Some(payments::CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -1,14 +1,15 @@
 use async_trait::async_trait;
-use error_stack::ResultExt;
+use error_stack::{report, ResultExt};
 use router_derive;
 
 use super::{Operation, PostUpdateTracker};
 use crate::{
     core::{
         errors::{self, RouterResult, StorageErrorExt},
-        payments::PaymentData,
+        payments::{helpers, PaymentData},
     },
     db::StorageInterface,
+    logger,
     services::RedirectForm,
     types::{
         self, api,
@@ -99,15 +100,65 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsCaptureData>
         &'b self,
         db: &dyn StorageInterface,
         payment_id: &api::PaymentIdType,
-        payment_data: PaymentData<F>,
-        response: types::RouterData<F, types::PaymentsCaptureData, types::PaymentsResponseData>,
+        mut payment_data: PaymentData<F>,
+        mut response: types::RouterData<F, types::PaymentsCaptureData, types::PaymentsResponseData>,
         storage_scheme: enums::MerchantStorageScheme,
     ) -> RouterResult<PaymentData<F>>
     where
         F: 'b + Send,
     {
-        payment_response_update_tracker(db, payment_id, payment_data, response, storage_scheme)
-            .await
+        let is_multiple_capture = payment_data.payment_attempt.capture_method
+            == Some(enums::CaptureMethod::ManualMultiple)
+            && response.status == enums::AttemptStatus::Charged;
+
+        if is_multiple_capture {
+            payment_data.payment_intent = db
+                .add_amount_captured_to_payment_intent(
+                    payment_data.payment_intent,
+                    response
+                        .request
+                        .amount_to_capture
+                        .unwrap_or(response.request.amount),
+                    storage_scheme,
+                )
+                .await
+                .map_err(|error| {
+                    error.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+                })?;
+            response.status = helpers::get_multiple_capture_status(
+                payment_data
+                    .payment_attempt
+                    .authorized_amount
+                    .unwrap_or(payment_data.payment_intent.amount),
+                payment_data.payment_intent.amount_captured,
+            );
+            // Already added to the payment intent above
+            response.amount_captured = None;
+        }
+
+        let mut payment_data =
+            payment_response_update_tracker(db, payment_id, payment_data, response, storage_scheme)
+                .await?;
+
+        // The rest of the authorization can still be captured
+        if is_multiple_capture
+            && payment_data.payment_attempt.status == enums::AttemptStatus::PartialCharged
+        {
+            payment_data.payment_intent = db
+                .update_payment_intent(
+                    payment_data.payment_intent,
+                    storage::PaymentIntentUpdate::PGStatusUpdate {
+                        status: enums::IntentStatus::RequiresCapture,
+                    },
+                    storage_scheme,
+                )
+                .await
+                .map_err(|error| {
+                    error.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+                })?;
+        }
+
+        Ok(payment_data)
     }
 }
 
@@ -162,6 +213,42 @@ async fn payment_response_update_tracker<F: Clone, T>(
     storage_scheme: enums::MerchantStorageScheme,
 ) -> RouterResult<PaymentData<F>> {
     let connector = router_data.connector.clone();
+    let next_status = match router_data.response {
+        Err(_) => Some(storage::enums::AttemptStatus::Failure),
+        Ok(types::PaymentsResponseData::TransactionResponse { .. }) => Some(router_data.status),
+        Ok(_) => None,
+    };
+    let current_status = payment_data.payment_attempt.status;
+    if let Some(next_status) = next_status.filter(|next_status| {
+        !helpers::is_valid_attempt_status_transition(current_status, *next_status)
+    }) {
+        return match router_data.response {
+            // The failed sync of a payment that has since moved on, like a charged payment, is
+            // stale and leaves the payment as it is
+            Err(error_response) => {
+                logger::warn!(
+                    %current_status,
+                    %next_status,
+                    "Ignoring failed connector response that cannot change the payment attempt status"
+                );
+                Err(report!(errors::ApiErrorResponse::ExternalConnectorError {
+                    message: error_response.message,
+                    code: error_response.code,
+                    status_code: error_response.status_code,
+                    connector,
+                }))
+            }
+            // A successful response the attempt cannot move to, like a voided attempt being
+            // charged, means the payment is out of sync with the connector
+            Ok(_) => Err(report!(errors::ApiErrorResponse::InternalServerError)).attach_printable(
+                format!(
+                    "Connector {connector} responded with {next_status} for a payment attempt in \
+                     {current_status}, which it cannot move to"
+                ),
+            ),
+        };
+    }
+
     let (payment_attempt_update, connector_response_update) = match router_data.response.clone() {
         Err(err) => (
             Some(storage::PaymentAttemptUpdate::ErrorUpdate {
@@ -169,6 +256,8 @@ async fn payment_response_update_tracker<F: Clone, T>(
                 status: storage::enums::AttemptStatus::Failure,
                 error_message: Some(err.message),
                 error_code: Some(err.code),
+                error_reason: err.reason,
+                connector_response_body: router_data.connector_response_body.clone(),
             }),
             Some(storage::ConnectorResponseUpdate::ErrorUpdate {
                 connector_name: Some(router_data.connector.clone()),
@@ -180,6 +269,9 @@ async fn payment_response_update_tracker<F: Clone, T>(
                 redirection_data,
                 redirect,
                 connector_metadata,
+                connector_response_reference_id,
+                avs_result,
+                cvv_result,
                 ..
             } => {
                 let connector_transaction_id = match resource_id {
@@ -189,7 +281,7 @@ async fn payment_response_update_tracker<F: Clone, T>(
                 };
 
                 let encoded_data = payment_data.connector_response.encoded_data.clone();
-                let connector_name = payment_data.payment_attempt.connector.clone();
+                let connector_name = Some(router_data.connector.clone());
 
                 let authentication_data = redirection_data
                     .map(|data| utils::Encode::<RedirectForm>::encode_to_value(&data))
@@ -209,6 +301,15 @@ async fn payment_response_update_tracker<F: Clone, T>(
                         .clone()
                         .map(|mandate| mandate.mandate_id),
                     connector_metadata,
+                    connector_response_reference_id,
+                    connector_response_body: router_data.connector_response_body.clone(),
+                    authorized_amount: router_data.authorized_amount,
+                    avs_result,
+                    cvv_result,
+                    authorized_at: get_authorized_at(
+                        &payment_data.payment_attempt,
+                        router_data.status,
+                    ),
                 };
 
                 let connector_response_update = storage::ConnectorResponseUpdate::ResponseUpdate {
@@ -224,7 +325,8 @@ async fn payment_response_update_tracker<F: Clone, T>(
                 )
             }
 
-            types::PaymentsResponseData::SessionResponse { .. } => (None, None),
+            types::PaymentsResponseData::SessionResponse { .. }
+            | types::PaymentsResponseData::PreProcessingResponse { .. } => (None, None),
         },
     };
 
@@ -287,3 +389,13 @@ async fn payment_response_update_tracker<F: Clone, T>(
 
     Ok(payment_data)
 }
+
+/// Time to record as the authorization time of the attempt, when the connector reports it as
+/// authorized for the first time.
+fn get_authorized_at(
+    payment_attempt: &storage::PaymentAttempt,
+    status: enums::AttemptStatus,
+) -> Option<time::PrimitiveDateTime> {
+    (payment_attempt.authorized_at.is_none() && status == enums::AttemptStatus::Authorized)
+        .then(common_utils::date_time::now)
+}
@@ -157,6 +157,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsSessionRequest>
                 sessions_token: vec![],
                 connector_response,
                 card_cvc: None,
+                surcharge_details: None,
+                tax_amount: None,
+                sca_exemption: None,
+                saved_payment_method_id: None,
             },
             Some(customer_details),
         ))
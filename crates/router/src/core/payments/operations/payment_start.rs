@@ -143,6 +143,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsStartRequest> f
                 refunds: vec![],
                 sessions_token: vec![],
                 card_cvc: None,
+                surcharge_details: None,
+                tax_amount: None,
+                sca_exemption: None,
+                saved_payment_method_id: None,
             },
             Some(customer_details),
         ))
@@ -268,6 +268,10 @@ async fn get_tracker_for_sync<
             refunds,
             sessions_token: vec![],
             card_cvc: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
+            saved_payment_method_id: None,
         },
         None,
     ))
@@ -10,7 +10,7 @@ use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, Valida
 use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{self, fx, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
         utils as core_utils,
     },
     db::StorageInterface,
@@ -104,6 +104,21 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .amount
             .unwrap_or_else(|| payment_attempt.amount.into());
 
+        let (surcharge_details, tax_amount) = helpers::update_surcharge_and_tax_amounts(
+            &mut payment_attempt,
+            amount.into(),
+            request.surcharge_details,
+            request.tax_amount,
+        )?;
+
+        let settlement =
+            fx::get_payment_settlement(db, &state.conf, merchant_id, (amount.into(), currency))
+                .await;
+        payment_attempt.settlement_currency =
+            settlement.as_ref().map(|settlement| settlement.currency);
+        payment_attempt.settlement_amount = settlement.as_ref().map(|settlement| settlement.amount);
+        payment_attempt.fx_rate = settlement.map(|settlement| settlement.fx_rate.to_string());
+
         if request.confirm.unwrap_or(false) {
             helpers::validate_customer_id_mandatory_cases(
                 request.shipping.is_some(),
@@ -135,7 +150,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         payment_intent.shipping_address_id = shipping_address.clone().map(|x| x.address_id);
         payment_intent.billing_address_id = billing_address.clone().map(|x| x.address_id);
+        helpers::validate_return_url(request.return_url.as_deref(), &state.conf.return_url)?;
         payment_intent.return_url = request.return_url.clone();
+        payment_attempt.return_url = request.return_url.clone().or(payment_attempt.return_url);
 
         let token = token.or_else(|| payment_attempt.payment_token.clone());
 
@@ -218,6 +235,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 connector_response,
                 sessions_token: vec![],
                 card_cvc: request.card_cvc.clone(),
+                surcharge_details,
+                tax_amount,
+                sca_exemption: request.sca_exemption,
+                saved_payment_method_id: None,
             },
             Some(CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -328,6 +349,12 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     authentication_type: None,
                     payment_method,
                     payment_token: payment_data.token.clone(),
+                    return_url: payment_data.payment_attempt.return_url.clone(),
+                    settlement_currency: payment_data.payment_attempt.settlement_currency,
+                    settlement_amount: payment_data.payment_attempt.settlement_amount,
+                    fx_rate: payment_data.payment_attempt.fx_rate.clone(),
+                    surcharge_amount: payment_data.payment_attempt.surcharge_amount,
+                    tax_amount: payment_data.payment_attempt.tax_amount,
                 },
                 storage_scheme,
             )
@@ -0,0 +1,105 @@
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors,
+    db, logger,
+    routes::AppState,
+    types::storage::{self, enums, ProcessTrackerExt},
+};
+
+pub const CONNECTOR_RESPONSE_RETENTION_RUNNER: &str = "CONNECTOR_RESPONSE_RETENTION_WORKFLOW";
+pub const CONNECTOR_RESPONSE_RETENTION_TASK: &str = "CLEAR_CONNECTOR_RESPONSES";
+
+/// Schedules the recurring job dropping expired connector response bodies, reviving it if it
+/// was finished earlier.
+#[instrument(skip_all)]
+pub async fn add_connector_response_retention_task(
+    db: &dyn db::StorageInterface,
+) -> Result<(), errors::ProcessTrackerError> {
+    let process_tracker_id =
+        format!("{CONNECTOR_RESPONSE_RETENTION_RUNNER}_{CONNECTOR_RESPONSE_RETENTION_TASK}");
+    let current_time = common_utils::date_time::now();
+
+    let existing_process = db
+        .find_process_by_id(&process_tracker_id)
+        .await
+        .map_err(|error| {
+            logger::error!(?error, "Failed to fetch connector response retention task");
+            errors::ProcessTrackerError::ProcessFetchingFailed
+        })?;
+
+    match existing_process {
+        None => {
+            let process_tracker_entry = storage::ProcessTracker::make_process_tracker_new(
+                process_tracker_id,
+                CONNECTOR_RESPONSE_RETENTION_TASK,
+                CONNECTOR_RESPONSE_RETENTION_RUNNER,
+                serde_json::json!({}),
+                current_time,
+            )?;
+            db.insert_process(process_tracker_entry)
+                .await
+                .map_err(|error| {
+                    logger::error!(?error, "Failed to insert connector response retention task");
+                    errors::ProcessTrackerError::ProcessInsertionFailed
+                })?;
+        }
+        Some(process) if process.status == enums::ProcessTrackerStatus::Finish => {
+            reschedule_connector_response_retention_task(db, process, current_time).await?;
+        }
+        Some(_) => (),
+    }
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn clear_expired_connector_responses_workflow(
+    state: &AppState,
+    process: storage::ProcessTracker,
+) -> Result<(), errors::ProcessTrackerError> {
+    let retention_config = &state.conf.connector_response_retention;
+    let modified_before = common_utils::date_time::now()
+        .saturating_sub(time::Duration::seconds(retention_config.retention_period));
+    let cleared_count = state
+        .store
+        .clear_connector_response_bodies_modified_before(modified_before)
+        .await
+        .map_err(|error| {
+            logger::error!(?error, "Failed to clear expired connector responses");
+            errors::ProcessTrackerError::ProcessUpdateFailed
+        })?;
+    logger::info!(cleared_count, "Cleared expired connector responses");
+
+    let schedule_time = common_utils::date_time::now()
+        .saturating_add(time::Duration::seconds(retention_config.interval));
+    reschedule_connector_response_retention_task(&*state.store, process, schedule_time).await
+}
+
+pub async fn reschedule_connector_response_retention_task(
+    db: &dyn db::StorageInterface,
+    process: storage::ProcessTracker,
+    schedule_time: time::PrimitiveDateTime,
+) -> Result<(), errors::ProcessTrackerError> {
+    db.update_process_tracker(
+        process,
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: Some(schedule_time),
+            tracking_data: None,
+            business_status: None,
+            status: Some(enums::ProcessTrackerStatus::Pending),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await
+    .map_err(|error| {
+        logger::error!(
+            ?error,
+            "Failed to reschedule connector response retention task"
+        );
+        errors::ProcessTrackerError::ProcessUpdateFailed
+    })?;
+    Ok(())
+}
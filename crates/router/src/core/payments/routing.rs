@@ -0,0 +1,447 @@
+use std::future::Future;
+
+use error_stack::{IntoReport, Report, ResultExt};
+
+use crate::{
+    core::errors::{self, RouterResult},
+    logger,
+    routes::metrics,
+    services,
+    types::{self, api, storage::enums},
+};
+
+/// Checks that the connector lists are not empty and that the weights add up to 100.
+pub fn validate_routing_algorithm(routing_algorithm: &api::RoutingAlgorithm) -> RouterResult<()> {
+    let is_valid = match routing_algorithm {
+        api::RoutingAlgorithm::Single(_) => true,
+        api::RoutingAlgorithm::Failover(connectors)
+        | api::RoutingAlgorithm::RoundRobin(connectors) => !connectors.is_empty(),
+        api::RoutingAlgorithm::Weighted(connectors) => {
+            connectors
+                .iter()
+                .map(|connector| u32::from(connector.weight))
+                .sum::<u32>()
+                == 100
+        }
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "routing_algorithm",
+        })
+        .into_report()
+        .attach_printable("Routing algorithm needs connectors with weights adding up to 100")
+    }
+}
+
+/// Picks a connector with probability proportional to its weight. The choice is derived from the
+/// payment id, so the same payment is always sent to the same connector.
+pub fn select_weighted_connector(
+    connectors: &[api::WeightedConnector],
+    payment_id: &str,
+) -> Option<enums::RoutableConnectors> {
+    let total_weight = connectors
+        .iter()
+        .map(|connector| u64::from(connector.weight))
+        .sum::<u64>();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let seed = blake3::hash(payment_id.as_bytes())
+        .as_bytes()
+        .iter()
+        .take(8)
+        .fold(0_u64, |seed, byte| (seed << 8) | u64::from(*byte));
+    let mut bucket = seed % total_weight;
+
+    connectors.iter().find_map(|connector| {
+        let weight = u64::from(connector.weight);
+        if bucket < weight {
+            Some(connector.connector)
+        } else {
+            bucket -= weight;
+            None
+        }
+    })
+}
+
+/// Picks the connector whose turn it is, given how many payments have been routed so far.
+pub fn select_round_robin_connector(
+    connectors: &[enums::RoutableConnectors],
+    counter: u64,
+) -> Option<enums::RoutableConnectors> {
+    let connector_count = u64::try_from(connectors.len()).ok()?;
+    let index = counter.checked_rem(connector_count)?;
+    connectors.get(usize::try_from(index).ok()?).copied()
+}
+
+/// Whether the connector declined the payment in a way that another connector might accept.
+pub fn is_soft_decline<F, Req>(
+    router_data: &types::RouterData<F, Req, types::PaymentsResponseData>,
+) -> bool {
+    matches!(
+        router_data.response,
+        Err(types::ErrorResponse {
+            decline_type: Some(types::DeclineType::Soft | types::DeclineType::Transient),
+            ..
+        })
+    )
+}
+
+/// Whether a connector could not be called at all, so that the payment is safe to send to another
+/// connector. Errors after the request was sent, like timeouts, may hide a processed payment.
+pub fn is_failover_error(error: &Report<errors::ApiErrorResponse>) -> bool {
+    !services::is_connector_request_sent(error)
+        || matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::ConnectorUnavailable
+        )
+}
+
+/// Sends the payment to `connectors` in order, moving on to the next connector as long as the
+/// payment is soft declined or the connector could not be called. Each decline that is failed over
+/// is handed to `on_soft_decline` together with the name of the declining connector, so that it can
+/// be recorded. The result of the last connector that was called is returned.
+pub async fn call_connectors_with_failover<F, Req, C, Fut, D, DFut>(
+    connectors: Vec<api::ConnectorData>,
+    mut call_connector: C,
+    mut on_soft_decline: D,
+) -> RouterResult<types::RouterData<F, Req, types::PaymentsResponseData>>
+where
+    C: FnMut(api::ConnectorData) -> Fut,
+    Fut: Future<Output = RouterResult<types::RouterData<F, Req, types::PaymentsResponseData>>>,
+    D: FnMut(String, types::ErrorResponse) -> DFut,
+    DFut: Future<Output = RouterResult<()>>,
+{
+    let mut connectors = connectors.into_iter();
+    let mut connector = connectors
+        .next()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("No connectors given for failover")?;
+
+    loop {
+        let connector_name = connector.connector_name.to_string();
+        let result = call_connector(connector).await;
+        let next_connector = match connectors.next() {
+            Some(next_connector) => next_connector,
+            None => return result,
+        };
+        match result {
+            Ok(router_data) if is_soft_decline(&router_data) => {
+                if let Err(error_response) = router_data.response {
+                    on_soft_decline(connector_name.clone(), error_response).await?;
+                }
+                logger::info!(
+                    declined_by = %connector_name,
+                    next_connector = %next_connector.connector_name,
+                    "Payment soft declined, retrying with the next connector"
+                );
+            }
+            Err(error) if is_failover_error(&error) => {
+                logger::warn!(
+                    ?error,
+                    failed_connector = %connector_name,
+                    next_connector = %next_connector.connector_name,
+                    "Connector could not be called, retrying with the next connector"
+                );
+            }
+            result => return result,
+        }
+        metrics::CONNECTOR_FAILOVER.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new("connector", connector_name)],
+        );
+        connector = next_connector;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use error_stack::report;
+
+    use super::*;
+    use crate::{
+        configs::settings::Connectors, connector::utils::get_test_router_data, consts,
+        core::errors::utils::ConnectorErrorExt, utils,
+    };
+
+    fn get_connector_data(connector_name: &str) -> api::ConnectorData {
+        api::ConnectorData::get_connector_by_name(
+            &Connectors::default(),
+            connector_name,
+            api::GetToken::Connector,
+        )
+        .unwrap()
+    }
+
+    fn get_router_data(
+        connector: &str,
+        response: Result<types::PaymentsResponseData, types::ErrorResponse>,
+    ) -> types::PaymentsCancelRouterData {
+        get_test_router_data(
+            connector,
+            types::PaymentsCancelData {
+                connector_transaction_id: "txn_123".to_string(),
+                cancellation_reason: None,
+            },
+            response,
+        )
+    }
+
+    fn get_decline(decline_type: types::DeclineType) -> types::ErrorResponse {
+        types::ErrorResponse {
+            code: "card_declined".to_string(),
+            message: "Your card was declined".to_string(),
+            reason: None,
+            status_code: 402,
+            decline_type: Some(decline_type),
+        }
+    }
+
+    fn get_success() -> types::PaymentsResponseData {
+        types::PaymentsResponseData::TransactionResponse {
+            resource_id: types::ResponseId::ConnectorTransactionId("txn_123".to_string()),
+            redirection_data: None,
+            redirect: false,
+            mandate_reference: None,
+            connector_metadata: None,
+            avs_result: None,
+            cvv_result: None,
+            connector_response_reference_id: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_soft_decline_fails_over_to_next_connector() {
+        let mut attempted_connectors = Vec::new();
+        let mut recorded_declines = Vec::new();
+        let router_data = call_connectors_with_failover(
+            vec![get_connector_data("stripe"), get_connector_data("adyen")],
+            |connector| {
+                let connector_name = connector.connector_name.to_string();
+                attempted_connectors.push(connector_name.clone());
+                let response = match connector_name.as_str() {
+                    "stripe" => Err(get_decline(types::DeclineType::Soft)),
+                    _ => Ok(get_success()),
+                };
+                async move { Ok(get_router_data(&connector_name, response)) }
+            },
+            |connector_name, error_response| {
+                recorded_declines.push((connector_name, error_response.code));
+                async { Ok(()) }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(attempted_connectors, vec!["stripe", "adyen"]);
+        assert_eq!(
+            recorded_declines,
+            vec![("stripe".to_string(), "card_declined".to_string())]
+        );
+        assert_eq!(router_data.connector, "adyen");
+        assert!(router_data.response.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_hard_decline_does_not_fail_over() {
+        let mut attempted_connectors = Vec::new();
+        let mut recorded_declines = Vec::<String>::new();
+        let router_data = call_connectors_with_failover(
+            vec![get_connector_data("stripe"), get_connector_data("adyen")],
+            |connector| {
+                let connector_name = connector.connector_name.to_string();
+                attempted_connectors.push(connector_name.clone());
+                let response = Err(get_decline(types::DeclineType::Hard));
+                async move { Ok(get_router_data(&connector_name, response)) }
+            },
+            |connector_name, _| {
+                recorded_declines.push(connector_name);
+                async { Ok(()) }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(attempted_connectors, vec!["stripe"]);
+        assert!(recorded_declines.is_empty());
+        assert_eq!(router_data.connector, "stripe");
+    }
+
+    #[actix_rt::test]
+    async fn test_last_soft_decline_is_returned() {
+        let mut recorded_declines = Vec::new();
+        let router_data = call_connectors_with_failover(
+            vec![get_connector_data("stripe"), get_connector_data("adyen")],
+            |connector| {
+                let connector_name = connector.connector_name.to_string();
+                async move {
+                    Ok(get_router_data(
+                        &connector_name,
+                        Err(get_decline(types::DeclineType::Soft)),
+                    ))
+                }
+            },
+            |connector_name, _| {
+                recorded_declines.push(connector_name);
+                async { Ok(()) }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(recorded_declines, vec!["stripe"]);
+        assert_eq!(router_data.connector, "adyen");
+        assert!(router_data.response.is_err());
+    }
+
+    fn get_circuit_breaker_open_error(connector_name: &str) -> Report<errors::ApiErrorResponse> {
+        report!(errors::ConnectorError::CircuitBreakerOpen {
+            connector: connector_name.to_string(),
+        })
+        .attach(services::ConnectorRequestNotSent)
+        .to_payment_failed_response()
+    }
+
+    #[actix_rt::test]
+    async fn test_open_circuit_fails_over_to_next_connector() {
+        let mut attempted_connectors = Vec::new();
+        let mut recorded_declines = Vec::<String>::new();
+        let router_data = call_connectors_with_failover(
+            vec![get_connector_data("stripe"), get_connector_data("adyen")],
+            |connector| {
+                let connector_name = connector.connector_name.to_string();
+                attempted_connectors.push(connector_name.clone());
+                async move {
+                    match connector_name.as_str() {
+                        "stripe" => Err(get_circuit_breaker_open_error(&connector_name)),
+                        _ => Ok(get_router_data(&connector_name, Ok(get_success()))),
+                    }
+                }
+            },
+            |connector_name, _| {
+                recorded_declines.push(connector_name);
+                async { Ok(()) }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(attempted_connectors, vec!["stripe", "adyen"]);
+        assert!(recorded_declines.is_empty());
+        assert_eq!(router_data.connector, "adyen");
+        assert!(router_data.response.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_timeout_does_not_fail_over() {
+        let mut attempted_connectors = Vec::new();
+        let error = call_connectors_with_failover(
+            vec![get_connector_data("stripe"), get_connector_data("adyen")],
+            |connector| {
+                attempted_connectors.push(connector.connector_name.to_string());
+                async {
+                    Err::<types::PaymentsCancelRouterData, _>(
+                        report!(errors::ConnectorError::RequestTimedOut)
+                            .to_payment_failed_response(),
+                    )
+                }
+            },
+            |_, _| async { Ok(()) },
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(attempted_connectors, vec!["stripe"]);
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::ConnectorTimeout
+        ));
+    }
+
+    #[test]
+    fn test_weighted_selection_follows_weights() {
+        let connectors = vec![
+            api::WeightedConnector {
+                connector: enums::RoutableConnectors::Stripe,
+                weight: 70,
+            },
+            api::WeightedConnector {
+                connector: enums::RoutableConnectors::Adyen,
+                weight: 30,
+            },
+        ];
+
+        let stripe_count = (0..1000)
+            .map(|_| utils::generate_id(consts::ID_LENGTH, "pay"))
+            .filter_map(|payment_id| select_weighted_connector(&connectors, &payment_id))
+            .filter(|connector| *connector == enums::RoutableConnectors::Stripe)
+            .count();
+
+        assert!(
+            (640..=760).contains(&stripe_count),
+            "stripe was selected {stripe_count} times out of 1000"
+        );
+    }
+
+    #[test]
+    fn test_weighted_selection_is_stable_for_a_payment() {
+        let connectors = vec![
+            api::WeightedConnector {
+                connector: enums::RoutableConnectors::Stripe,
+                weight: 50,
+            },
+            api::WeightedConnector {
+                connector: enums::RoutableConnectors::Adyen,
+                weight: 50,
+            },
+        ];
+
+        assert_eq!(
+            select_weighted_connector(&connectors, "pay_123"),
+            select_weighted_connector(&connectors, "pay_123")
+        );
+    }
+
+    #[test]
+    fn test_round_robin_selection_cycles_through_connectors() {
+        let connectors = vec![
+            enums::RoutableConnectors::Stripe,
+            enums::RoutableConnectors::Adyen,
+            enums::RoutableConnectors::Checkout,
+        ];
+
+        let selected = (1..=6)
+            .filter_map(|counter| select_round_robin_connector(&connectors, counter))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            selected,
+            vec![
+                enums::RoutableConnectors::Adyen,
+                enums::RoutableConnectors::Checkout,
+                enums::RoutableConnectors::Stripe,
+                enums::RoutableConnectors::Adyen,
+                enums::RoutableConnectors::Checkout,
+                enums::RoutableConnectors::Stripe,
+            ]
+        );
+        assert_eq!(select_round_robin_connector(&[], 1), None);
+    }
+
+    #[test]
+    fn test_weights_must_add_up_to_100() {
+        let routing_algorithm = api::RoutingAlgorithm::Weighted(vec![api::WeightedConnector {
+            connector: enums::RoutableConnectors::Stripe,
+            weight: 70,
+        }]);
+
+        assert!(validate_routing_algorithm(&routing_algorithm).is_err());
+    }
+}
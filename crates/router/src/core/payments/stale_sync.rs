@@ -0,0 +1,234 @@
+use common_utils::fp_utils;
+use error_stack::report;
+use futures::StreamExt;
+use router_env::{instrument, tracing};
+
+use super::{operations, CallConnectorAction};
+use crate::{
+    core::errors::{self, RouterResult, StorageErrorExt},
+    db, logger,
+    routes::{metrics, AppState},
+    types::{
+        api,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+};
+
+pub const STALE_PAYMENTS_SYNC_RUNNER: &str = "STALE_PAYMENTS_SYNC_WORKFLOW";
+pub const STALE_PAYMENTS_SYNC_TASK: &str = "SYNC_STALE_PAYMENTS";
+
+/// Statuses of attempts that were sent to the connector but have not received a final status,
+/// which happens when the connector response was lost.
+pub const IN_FLIGHT_ATTEMPT_STATUSES: [enums::AttemptStatus; 4] = [
+    enums::AttemptStatus::Pending,
+    enums::AttemptStatus::Authorizing,
+    enums::AttemptStatus::CaptureInitiated,
+    enums::AttemptStatus::VoidInitiated,
+];
+
+/// Schedules the recurring stale payments sync job, reviving it if it was finished earlier.
+#[instrument(skip_all)]
+pub async fn add_stale_payments_sync_task(
+    db: &dyn db::StorageInterface,
+) -> Result<(), errors::ProcessTrackerError> {
+    let process_tracker_id = format!("{STALE_PAYMENTS_SYNC_RUNNER}_{STALE_PAYMENTS_SYNC_TASK}");
+    let current_time = common_utils::date_time::now();
+
+    let existing_process = db
+        .find_process_by_id(&process_tracker_id)
+        .await
+        .map_err(|error| {
+            logger::error!(?error, "Failed to fetch stale payments sync task");
+            errors::ProcessTrackerError::ProcessFetchingFailed
+        })?;
+
+    match existing_process {
+        None => {
+            let process_tracker_entry = storage::ProcessTracker::make_process_tracker_new(
+                process_tracker_id,
+                STALE_PAYMENTS_SYNC_TASK,
+                STALE_PAYMENTS_SYNC_RUNNER,
+                serde_json::json!({}),
+                current_time,
+            )?;
+            db.insert_process(process_tracker_entry)
+                .await
+                .map_err(|error| {
+                    logger::error!(?error, "Failed to insert stale payments sync task");
+                    errors::ProcessTrackerError::ProcessInsertionFailed
+                })?;
+        }
+        Some(process) if process.status == enums::ProcessTrackerStatus::Finish => {
+            reschedule_stale_payments_sync_task(db, process, current_time).await?;
+        }
+        Some(_) => (),
+    }
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn sync_stale_payments_workflow(
+    state: &AppState,
+    process: storage::ProcessTracker,
+) -> Result<(), errors::ProcessTrackerError> {
+    let sync_config = &state.conf.stale_payments_sync;
+    let modified_before = common_utils::date_time::now()
+        .saturating_sub(time::Duration::seconds(sync_config.stale_after));
+    let stale_attempts = state
+        .store
+        .find_payment_attempts_by_statuses_modified_before(
+            IN_FLIGHT_ATTEMPT_STATUSES.to_vec(),
+            modified_before,
+            sync_config.max_sync_failures,
+            sync_config.batch_size,
+        )
+        .await
+        .map_err(|error| {
+            logger::error!(?error, "Failed to fetch stale payment attempts");
+            errors::ProcessTrackerError::ProcessFetchingFailed
+        })?;
+
+    let attempts_count = stale_attempts.len();
+    let synced_attempts_count =
+        sync_payment_attempts_concurrently(stale_attempts, sync_config.concurrency, |attempt| {
+            sync_stale_payment_attempt(state, attempt)
+        })
+        .await;
+    logger::info!(
+        attempts_count,
+        synced_attempts_count,
+        "Synced stale payment attempts"
+    );
+
+    let schedule_time = common_utils::date_time::now()
+        .saturating_add(time::Duration::seconds(sync_config.interval));
+    reschedule_stale_payments_sync_task(&*state.store, process, schedule_time).await
+}
+
+/// Syncs the attempts with at most `concurrency` syncs in flight, returning the number of
+/// attempts that were synced successfully.
+pub async fn sync_payment_attempts_concurrently<F, Fut>(
+    payment_attempts: Vec<storage::PaymentAttempt>,
+    concurrency: usize,
+    sync_payment_attempt: F,
+) -> usize
+where
+    F: Fn(storage::PaymentAttempt) -> Fut,
+    Fut: futures::Future<Output = RouterResult<storage::PaymentAttempt>>,
+{
+    futures::stream::iter(payment_attempts.into_iter().map(sync_payment_attempt))
+        .buffer_unordered(concurrency.max(1))
+        .fold(0, |synced_attempts_count, result| async move {
+            match result {
+                Ok(_) => synced_attempts_count + 1,
+                Err(error) => {
+                    logger::error!(?error, "Failed to sync stale payment attempt");
+                    synced_attempts_count
+                }
+            }
+        })
+        .await
+}
+
+/// Syncs the attempt with the connector. Attempts that fail to sync are marked as modified, so that
+/// the next batches pick up the attempts that were not synced yet instead of retrying them, and
+/// are left for manual review once they failed to sync `max_sync_failures` times.
+///
+/// Attempts without a connector transaction ID, like authorizations that timed out, are left for
+/// manual review right away, since connectors need the ID to look the payment up.
+async fn sync_stale_payment_attempt(
+    state: &AppState,
+    payment_attempt: storage::PaymentAttempt,
+) -> RouterResult<storage::PaymentAttempt> {
+    let can_be_synced = payment_attempt.connector_transaction_id.is_some();
+    let sync_result = async {
+        fp_utils::when(!can_be_synced, || {
+            Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "connector_transaction_id",
+            })
+            .attach_printable("The payment attempt cannot be synced with the connector"))
+        })?;
+
+        let merchant_account = state
+            .store
+            .find_merchant_account_by_merchant_id(&payment_attempt.merchant_id)
+            .await
+            .map_err(|error| {
+                error.to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)
+            })?;
+
+        let request = api::PaymentsRetrieveRequest {
+            resource_id: api::PaymentIdType::PaymentAttemptId(payment_attempt.attempt_id.clone()),
+            merchant_id: Some(payment_attempt.merchant_id.clone()),
+            force_sync: true,
+            param: None,
+            connector: None,
+        };
+        let (payment_data, _, _) = super::payments_operation_core::<api::PSync, _, _, _>(
+            state,
+            merchant_account,
+            operations::PaymentStatus,
+            request,
+            CallConnectorAction::Trigger,
+        )
+        .await?;
+
+        Ok(payment_data.payment_attempt)
+    }
+    .await;
+
+    if sync_result.is_err() {
+        let attempt_id = payment_attempt.attempt_id.clone();
+        let sync_failure_count = if can_be_synced {
+            payment_attempt.sync_failure_count.saturating_add(1)
+        } else {
+            state.conf.stale_payments_sync.max_sync_failures
+        };
+        if let Err(error) = state
+            .store
+            .update_payment_attempt(
+                payment_attempt,
+                storage::PaymentAttemptUpdate::SyncFailureUpdate { sync_failure_count },
+                // Stale attempts are fetched from the database whatever the storage scheme
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+        {
+            logger::error!(?error, %attempt_id, "Failed to record the failed sync of the payment attempt");
+        } else if sync_failure_count >= state.conf.stale_payments_sync.max_sync_failures {
+            logger::error!(
+                %attempt_id,
+                sync_failure_count,
+                "Giving up syncing the stale payment attempt, it needs manual review"
+            );
+            metrics::STALE_PAYMENT_SYNC_ABANDONED.add(&metrics::CONTEXT, 1, &[]);
+        }
+    }
+    sync_result
+}
+
+pub async fn reschedule_stale_payments_sync_task(
+    db: &dyn db::StorageInterface,
+    process: storage::ProcessTracker,
+    schedule_time: time::PrimitiveDateTime,
+) -> Result<(), errors::ProcessTrackerError> {
+    db.update_process_tracker(
+        process,
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: Some(schedule_time),
+            tracking_data: None,
+            business_status: None,
+            status: Some(enums::ProcessTrackerStatus::Pending),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await
+    .map_err(|error| {
+        logger::error!(?error, "Failed to reschedule stale payments sync task");
+        errors::ProcessTrackerError::ProcessUpdateFailed
+    })?;
+    Ok(())
+}
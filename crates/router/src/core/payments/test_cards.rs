@@ -0,0 +1,340 @@
+//! Magic test cards, which have a fixed outcome so that merchants can exercise every payment path
+//! without a connector sandbox.
+//!
+//! With `[test_mode]` enabled, payments with one of these cards are answered by a mock connector
+//! with the outcome of the card instead of being sent to the connector, and so are the captures,
+//! syncs, voids and refunds of these payments. Outside of test mode the cards are rejected, so that
+//! they never reach a live connector.
+
+use common_utils::fp_utils::when;
+use error_stack::report;
+use masking::PeekInterface;
+
+use crate::{
+    configs::settings::TestMode,
+    core::errors::{self, RouterResult},
+    types::{self, api, storage::enums},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TestCardOutcome {
+    Succeed,
+    Decline,
+    InsufficientFunds,
+    ExpiredCard,
+}
+
+/// Card numbers with a fixed outcome in test mode.
+pub const TEST_CARDS: [(&str, TestCardOutcome); 5] = [
+    ("4000000000000077", TestCardOutcome::Succeed),
+    ("5200828282828210", TestCardOutcome::Succeed),
+    ("4000000000000002", TestCardOutcome::Decline),
+    ("4000000000009995", TestCardOutcome::InsufficientFunds),
+    ("4000000000000069", TestCardOutcome::ExpiredCard),
+];
+
+/// Prefix of the transaction IDs the mock connector answers authorizations with, by which the
+/// later operations on these payments are recognized.
+const MOCK_TRANSACTION_ID_PREFIX: &str = "test_card_";
+
+/// Outcome of the card if it is one of the [`TEST_CARDS`].
+pub fn get_test_card_outcome(card: &api::Card) -> Option<TestCardOutcome> {
+    let card_number = card.card_number.peek().replace(' ', "");
+    TEST_CARDS
+        .iter()
+        .find(|(test_card_number, _)| *test_card_number == card_number)
+        .map(|(_, outcome)| *outcome)
+}
+
+/// Outcome the mock connector answers the payment with, `None` when the payment is not made with
+/// a test card. Test cards are rejected outside of test mode.
+pub fn get_mock_outcome(
+    test_mode: &TestMode,
+    payment_method: Option<&api::PaymentMethod>,
+) -> RouterResult<Option<TestCardOutcome>> {
+    let outcome = match payment_method {
+        Some(api::PaymentMethod::Card(card)) => get_test_card_outcome(card),
+        _ => None,
+    };
+    when(outcome.is_some() && !test_mode.enabled, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "Test cards can only be used in test mode".to_string(),
+        }))
+    })?;
+    Ok(outcome)
+}
+
+/// Whether the connector transaction was made by the mock connector, in which case it is
+/// answered by the mock connector as well.
+pub fn is_mock_transaction(test_mode: &TestMode, connector_transaction_id: &str) -> bool {
+    test_mode.enabled && connector_transaction_id.starts_with(MOCK_TRANSACTION_ID_PREFIX)
+}
+
+fn get_mock_transaction_response(connector_transaction_id: String) -> types::PaymentsResponseData {
+    types::PaymentsResponseData::TransactionResponse {
+        resource_id: types::ResponseId::ConnectorTransactionId(connector_transaction_id),
+        redirection_data: None,
+        redirect: false,
+        mandate_reference: None,
+        connector_metadata: None,
+        avs_result: None,
+        cvv_result: None,
+        connector_response_reference_id: None,
+    }
+}
+
+impl TestCardOutcome {
+    /// Status and response of an authorization with the card, captured right away unless
+    /// `capture_method` is manual.
+    pub fn get_authorize_response(
+        self,
+        payment_id: &str,
+        capture_method: Option<enums::CaptureMethod>,
+    ) -> (
+        enums::AttemptStatus,
+        Result<types::PaymentsResponseData, types::ErrorResponse>,
+    ) {
+        match self {
+            Self::Succeed => {
+                let status = match capture_method {
+                    Some(enums::CaptureMethod::Manual) => enums::AttemptStatus::Authorized,
+                    _ => enums::AttemptStatus::Charged,
+                };
+                let response = get_mock_transaction_response(format!(
+                    "{MOCK_TRANSACTION_ID_PREFIX}{payment_id}"
+                ));
+                (status, Ok(response))
+            }
+            Self::Decline => get_decline_response(
+                "card_declined",
+                "The card was declined",
+                types::DeclineType::Hard,
+            ),
+            Self::InsufficientFunds => get_decline_response(
+                "insufficient_funds",
+                "The card has insufficient funds",
+                types::DeclineType::Soft,
+            ),
+            Self::ExpiredCard => get_decline_response(
+                "expired_card",
+                "The card has expired",
+                types::DeclineType::Hard,
+            ),
+        }
+    }
+}
+
+fn get_decline_response(
+    code: &str,
+    message: &str,
+    decline_type: types::DeclineType,
+) -> (
+    enums::AttemptStatus,
+    Result<types::PaymentsResponseData, types::ErrorResponse>,
+) {
+    (
+        enums::AttemptStatus::Failure,
+        Err(types::ErrorResponse {
+            code: code.to_string(),
+            message: message.to_string(),
+            reason: None,
+            status_code: 402,
+            decline_type: Some(decline_type),
+        }),
+    )
+}
+
+impl types::PaymentsAuthorizeRouterData {
+    /// Answers the authorization the way the mock connector does for `outcome`.
+    pub fn mock_authorize(self, outcome: TestCardOutcome) -> Self {
+        let (status, response) =
+            outcome.get_authorize_response(&self.payment_id, self.request.capture_method);
+        Self {
+            status,
+            response,
+            ..self
+        }
+    }
+}
+
+impl types::PaymentsCaptureRouterData {
+    /// Captures the amount to capture, or the whole amount, of a mock authorization.
+    pub fn mock_capture(self) -> Self {
+        let response = get_mock_transaction_response(self.request.connector_transaction_id.clone());
+        Self {
+            status: enums::AttemptStatus::Charged,
+            amount_captured: Some(
+                self.request
+                    .amount_to_capture
+                    .unwrap_or(self.request.amount),
+            ),
+            response: Ok(response),
+            ..self
+        }
+    }
+}
+
+impl types::PaymentsSyncRouterData {
+    /// Answers the sync of a mock payment with its current status, which never changes at the mock
+    /// connector.
+    pub fn mock_sync(self, connector_transaction_id: String) -> Self {
+        Self {
+            response: Ok(get_mock_transaction_response(connector_transaction_id)),
+            ..self
+        }
+    }
+}
+
+impl types::PaymentsCancelRouterData {
+    /// Voids a mock authorization.
+    pub fn mock_void(self) -> Self {
+        let response = get_mock_transaction_response(self.request.connector_transaction_id.clone());
+        Self {
+            status: enums::AttemptStatus::Voided,
+            response: Ok(response),
+            ..self
+        }
+    }
+}
+
+impl types::RefundsRouterData<api::Execute> {
+    /// Refunds a mock payment right away.
+    pub fn mock_refund(self) -> Self {
+        let response = types::RefundsResponseData {
+            connector_refund_id: format!("{MOCK_TRANSACTION_ID_PREFIX}{}", self.request.refund_id),
+            refund_status: enums::RefundStatus::Success,
+        };
+        Self {
+            response: Ok(response),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::panic)]
+
+    use super::*;
+    use crate::connector::utils::get_test_router_data;
+
+    fn card(card_number: &str) -> api::PaymentMethod {
+        api::PaymentMethod::Card(api::Card {
+            card_number: card_number.to_string().into(),
+            card_exp_month: "10".to_string().into(),
+            card_exp_year: "2035".to_string().into(),
+            card_holder_name: "John Doe".to_string().into(),
+            card_cvc: "123".to_string().into(),
+        })
+    }
+
+    #[test]
+    fn test_always_decline_card_fails_deterministically_in_test_mode() {
+        let test_mode = TestMode { enabled: true };
+        let payment_method = card("4000 0000 0000 0002");
+
+        let outcome = get_mock_outcome(&test_mode, Some(&payment_method))
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome, TestCardOutcome::Decline);
+
+        for _ in 0..2 {
+            let (status, response) = outcome.get_authorize_response("pay_123", None);
+            assert_eq!(status, enums::AttemptStatus::Failure);
+            let error = response.unwrap_err();
+            assert_eq!(error.code, "card_declined");
+            assert_eq!(error.decline_type, Some(types::DeclineType::Hard));
+        }
+    }
+
+    #[test]
+    fn test_test_card_is_rejected_outside_of_test_mode() {
+        let test_mode = TestMode { enabled: false };
+
+        let result = get_mock_outcome(&test_mode, Some(&card("4000000000000002")));
+
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_other_cards_are_sent_to_the_connector() {
+        for enabled in [true, false] {
+            let test_mode = TestMode { enabled };
+
+            let outcome = get_mock_outcome(&test_mode, Some(&card("4242424242424242"))).unwrap();
+
+            assert_eq!(outcome, None);
+        }
+    }
+
+    #[test]
+    fn test_operations_on_mock_payments_are_answered_in_test_mode_only() {
+        let (_, response) = TestCardOutcome::Succeed.get_authorize_response("pay_123", None);
+        let connector_transaction_id = match response.unwrap() {
+            types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(id),
+                ..
+            } => id,
+            response => panic!("Unexpected payments response: {response:?}"),
+        };
+
+        assert!(is_mock_transaction(
+            &TestMode { enabled: true },
+            &connector_transaction_id
+        ));
+        assert!(!is_mock_transaction(
+            &TestMode { enabled: false },
+            &connector_transaction_id
+        ));
+        assert!(!is_mock_transaction(
+            &TestMode { enabled: true },
+            "pi_3MqSCR2eZvKYlo2C1cvYAbYt"
+        ));
+    }
+
+    #[test]
+    fn test_mock_capture_charges_the_amount_to_capture() {
+        let router_data: types::PaymentsCaptureRouterData = get_test_router_data(
+            "stripe",
+            types::PaymentsCaptureData {
+                amount_to_capture: Some(600),
+                currency: enums::Currency::USD,
+                connector_transaction_id: "test_card_pay_123".to_string(),
+                amount: 1000,
+            },
+            Err(types::ErrorResponse::default()),
+        );
+
+        let router_data = router_data.mock_capture();
+
+        assert_eq!(router_data.status, enums::AttemptStatus::Charged);
+        assert_eq!(router_data.amount_captured, Some(600));
+        assert!(router_data.response.is_ok());
+    }
+
+    #[test]
+    fn test_mock_refund_succeeds() {
+        let router_data: types::RefundsRouterData<api::Execute> = get_test_router_data(
+            "stripe",
+            types::RefundsData {
+                refund_id: "ref_123".to_string(),
+                connector_transaction_id: "test_card_pay_123".to_string(),
+                connector_refund_id: None,
+                currency: enums::Currency::USD,
+                amount: 1000,
+                reason: None,
+                refund_amount: 1000,
+                connector_metadata: None,
+            },
+            Err(types::ErrorResponse::default()),
+        );
+
+        let response = router_data.mock_refund().response.unwrap();
+
+        assert_eq!(response.connector_refund_id, "test_card_ref_123");
+        assert_eq!(response.refund_status, enums::RefundStatus::Success);
+    }
+}
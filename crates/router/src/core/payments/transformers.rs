@@ -7,6 +7,7 @@ use super::{flows::Feature, PaymentAddress, PaymentData};
 use crate::{
     configs::settings::Server,
     core::{
+        connector_credentials,
         errors::{self, RouterResponse, RouterResult, StorageErrorExt},
         payments::{self, helpers},
     },
@@ -45,11 +46,8 @@ where
             error.to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound)
         })?;
 
-    let auth_type: types::ConnectorAuthType = merchant_connector_account
-        .connector_account_details
-        .parse_value("ConnectorAuthType")
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Failed while parsing value for ConnectorAuthType")?;
+    let auth_type =
+        connector_credentials::get_connector_auth_type(state, &merchant_connector_account).await?;
 
     payment_method = payment_data
         .payment_attempt
@@ -68,6 +66,9 @@ where
             redirect: false,
             mandate_reference: None,
             connector_metadata: None,
+            avs_result: None,
+            cvv_result: None,
+            connector_response_reference_id: None,
         });
 
     let router_return_url = Some(helpers::create_redirect_url(
@@ -86,9 +87,14 @@ where
         payment_method,
         connector_auth_type: auth_type,
         description: payment_data.payment_intent.description.clone(),
-        return_url: payment_data.payment_intent.return_url.clone(),
+        return_url: payment_data
+            .payment_attempt
+            .return_url
+            .clone()
+            .or_else(|| payment_data.payment_intent.return_url.clone()),
         router_return_url,
         payment_method_id: payment_data.payment_attempt.payment_method_id.clone(),
+        connector_response_body: None,
         address: payment_data.address.clone(),
         auth_type: payment_data
             .payment_attempt
@@ -98,6 +104,7 @@ where
         request: T::try_from(payment_data.clone())?,
         response: response.map_or_else(|| Err(types::ErrorResponse::default()), Ok),
         amount_captured: payment_data.payment_intent.amount_captured,
+        authorized_amount: payment_data.payment_attempt.authorized_amount,
         access_token: None,
     };
 
@@ -280,9 +287,12 @@ where
                         .set_merchant_id(Some(payment_attempt.merchant_id))
                         .set_status(payment_intent.status.foreign_into())
                         .set_amount(payment_attempt.amount)
-                        .set_amount_capturable(None)
+                        .set_amount_capturable(payment_attempt.authorized_amount)
                         .set_amount_received(payment_intent.amount_captured)
                         .set_connector(payment_attempt.connector)
+                        .set_connector_response_reference_id(
+                            payment_attempt.connector_response_reference_id,
+                        )
                         .set_client_secret(payment_intent.client_secret.map(masking::Secret::new))
                         .set_created(Some(payment_intent.created_at))
                         .set_currency(currency)
@@ -318,11 +328,21 @@ where
                         .set_payment_token(payment_attempt.payment_token)
                         .set_error_message(payment_attempt.error_message)
                         .set_error_code(payment_attempt.error_code)
+                        .set_error_reason(payment_attempt.error_reason)
+                        .set_settlement_currency(
+                            payment_attempt
+                                .settlement_currency
+                                .map(ForeignInto::foreign_into),
+                        )
+                        .set_settlement_amount(payment_attempt.settlement_amount)
+                        .set_fx_rate(payment_attempt.fx_rate)
                         .set_shipping(address.shipping)
                         .set_billing(address.billing)
                         .to_owned()
                         .set_next_action(next_action_response)
-                        .set_return_url(payment_intent.return_url)
+                        // Attempts created before the return URL was stored with them fall back
+                        // to the one of the intent
+                        .set_return_url(payment_attempt.return_url.or(payment_intent.return_url))
                         .set_cancellation_reason(payment_attempt.cancellation_reason)
                         .set_authentication_type(
                             payment_attempt
@@ -350,7 +370,7 @@ where
             merchant_id: Some(payment_attempt.merchant_id),
             status: payment_intent.status.foreign_into(),
             amount: payment_attempt.amount,
-            amount_capturable: None,
+            amount_capturable: payment_attempt.authorized_amount,
             amount_received: payment_intent.amount_captured,
             client_secret: payment_intent.client_secret.map(masking::Secret::new),
             created: Some(payment_intent.created_at),
@@ -366,6 +386,13 @@ where
                 .map(ForeignInto::foreign_into),
             error_message: payment_attempt.error_message,
             error_code: payment_attempt.error_code,
+            error_reason: payment_attempt.error_reason,
+            settlement_currency: payment_attempt
+                .settlement_currency
+                .map(ForeignInto::foreign_into),
+            settlement_amount: payment_attempt.settlement_amount,
+            fx_rate: payment_attempt.fx_rate,
+            connector_response_reference_id: payment_attempt.connector_response_reference_id,
             payment_method_data: payment_method_data.map(api::PaymentMethodDataResponse::from),
             email: customer
                 .as_ref()
@@ -425,12 +452,19 @@ impl<F: Clone> TryFrom<PaymentData<F>> for types::PaymentsAuthorizeData {
             setup_mandate_details: payment_data.setup_mandate.clone(),
             confirm: payment_data.payment_attempt.confirm,
             statement_descriptor_suffix: payment_data.payment_intent.statement_descriptor_suffix,
+            statement_descriptor_prefix: payment_data.payment_intent.statement_descriptor_name,
             capture_method: payment_data.payment_attempt.capture_method,
             amount: payment_data.amount.into(),
             currency: payment_data.currency,
             browser_info,
             email: payment_data.email,
             order_details,
+            surcharge_details: payment_data.surcharge_details,
+            tax_amount: payment_data.tax_amount,
+            sca_exemption: payment_data.sca_exemption,
+            connector_meta: None,
+            // Only requested for the authorization, see `construct_router_data` of its flow
+            network_token: None,
         })
     }
 }
@@ -526,6 +560,7 @@ impl<F: Clone> TryFrom<PaymentData<F>> for types::VerifyRequestData {
             payment_method_data: payment_data
                 .payment_method_data
                 .get_required_value("payment_method_data")?,
+            currency: payment_data.currency,
             statement_descriptor_suffix: payment_data.payment_intent.statement_descriptor_suffix,
             setup_future_usage: payment_data.payment_intent.setup_future_usage,
             off_session: payment_data.mandate_id.as_ref().map(|_| true),
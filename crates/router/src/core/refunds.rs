@@ -1,13 +1,14 @@
 pub mod validator;
 
 use error_stack::{report, IntoReport, ResultExt};
+use futures::StreamExt;
 use router_env::{instrument, tracing};
 
 use crate::{
     consts,
     core::{
         errors::{self, ConnectorErrorExt, RouterResponse, RouterResult, StorageErrorExt},
-        payments::{self, access_token},
+        payments::{self, access_token, test_cards},
         utils as core_utils,
     },
     db, logger,
@@ -31,6 +32,17 @@ pub async fn refund_create_core(
     merchant_account: storage::merchant_account::MerchantAccount,
     req: refunds::RefundRequest,
 ) -> RouterResponse<refunds::RefundResponse> {
+    create_refund(state, &merchant_account, req)
+        .await
+        .map(services::ApplicationResponse::Json)
+}
+
+#[instrument(skip_all)]
+pub async fn create_refund(
+    state: &AppState,
+    merchant_account: &storage::merchant_account::MerchantAccount,
+    req: refunds::RefundRequest,
+) -> RouterResult<refunds::RefundResponse> {
     let db = &*state.store;
     let (merchant_id, payment_intent, payment_attempt, amount);
 
@@ -75,14 +87,100 @@ pub async fn refund_create_core(
 
     validate_and_create_refund(
         state,
-        &merchant_account,
+        merchant_account,
         &payment_attempt,
         &payment_intent,
         amount,
         req,
     )
     .await
-    .map(services::ApplicationResponse::Json)
+}
+
+// ********************************************** BULK REFUND **********************************************
+
+/// Creates the refunds of the request like [`refund_create_core`], reporting the outcome of each
+/// refund instead of failing the whole request when one of them fails.
+///
+/// Refunds of different payments are created concurrently, while the refunds of a payment are
+/// created one after the other so that each of them is validated against the ones before it.
+/// Scheduled refunds are executed by the process tracker like any other refund.
+#[instrument(skip_all)]
+pub async fn bulk_refund_create_core(
+    state: &AppState,
+    merchant_account: storage::merchant_account::MerchantAccount,
+    req: refunds::BulkRefundRequest,
+) -> RouterResponse<refunds::BulkRefundResponse> {
+    let refund_config = &state.conf.refund;
+    utils::when(req.refunds.len() > refund_config.bulk_max_refunds, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "At most {} refunds can be created in one request",
+                refund_config.bulk_max_refunds
+            ),
+        }))
+    })?;
+
+    let mut refunds_by_payment: Vec<(String, Vec<(usize, refunds::RefundRequest)>)> = Vec::new();
+    for (position, refund_request) in req.refunds.into_iter().enumerate() {
+        match refunds_by_payment
+            .iter_mut()
+            .find(|(payment_id, _)| *payment_id == refund_request.payment_id)
+        {
+            Some((_, payment_refunds)) => payment_refunds.push((position, refund_request)),
+            None => refunds_by_payment.push((
+                refund_request.payment_id.clone(),
+                vec![(position, refund_request)],
+            )),
+        }
+    }
+
+    let merchant_account = &merchant_account;
+    let mut results = futures::stream::iter(refunds_by_payment.into_iter().map(
+        |(_, payment_refunds)| async move {
+            let mut results = Vec::with_capacity(payment_refunds.len());
+            for (position, refund_request) in payment_refunds {
+                let payment_id = refund_request.payment_id.clone();
+                let result = create_refund(state, merchant_account, refund_request).await;
+                results.push((position, get_bulk_refund_result(payment_id, result)));
+            }
+            results
+        },
+    ))
+    .buffer_unordered(refund_config.bulk_concurrency.max(1))
+    .concat()
+    .await;
+    results.sort_by_key(|(position, _)| *position);
+
+    Ok(services::ApplicationResponse::Json(
+        refunds::BulkRefundResponse {
+            results: results.into_iter().map(|(_, result)| result).collect(),
+        },
+    ))
+}
+
+fn get_bulk_refund_result(
+    payment_id: String,
+    result: RouterResult<refunds::RefundResponse>,
+) -> refunds::BulkRefundResult {
+    match result {
+        Ok(refund) => refunds::BulkRefundResult {
+            payment_id,
+            refund: Some(refund),
+            error: None,
+        },
+        Err(error) => {
+            logger::error!(?error, %payment_id, "Failed to create refund of bulk refund");
+            let error = error.current_context();
+            refunds::BulkRefundResult {
+                payment_id,
+                refund: None,
+                error: Some(refunds::BulkRefundError {
+                    code: error.error_code(),
+                    message: error.error_message(),
+                }),
+            }
+        }
+    }
 }
 
 #[instrument(skip_all)]
@@ -93,6 +191,14 @@ pub async fn trigger_refund_to_gateway(
     payment_attempt: &storage::PaymentAttempt,
     payment_intent: &storage::PaymentIntent,
 ) -> RouterResult<storage::Refund> {
+    if validator::is_refund_sent_to_gateway(refund) {
+        logger::info!(
+            refund_id = %refund.refund_id,
+            "Refund was already sent to the connector, skipping the connector call"
+        );
+        return Ok(refund.to_owned());
+    }
+
     let connector = payment_attempt
         .connector
         .clone()
@@ -126,6 +232,30 @@ pub async fn trigger_refund_to_gateway(
     )
     .await?;
 
+    if test_cards::is_mock_transaction(
+        &state.conf.test_mode,
+        &router_data.request.connector_transaction_id,
+    ) {
+        return update_refund_with_response(
+            state,
+            refund,
+            merchant_account,
+            router_data.mock_refund(),
+        )
+        .await;
+    }
+
+    connector
+        .connector
+        .get_capabilities()
+        .validate(
+            &connector_id,
+            &[api::ConnectorFlow::Refund],
+            router_data.payment_method,
+            Some(router_data.request.currency),
+        )
+        .map_err(|error| error.to_refund_failed_response())?;
+
     let add_access_token_result =
         access_token::add_access_token(state, &connector, merchant_account, &router_data).await?;
 
@@ -137,27 +267,81 @@ pub async fn trigger_refund_to_gateway(
         &payments::CallConnectorAction::Trigger,
     );
 
-    let router_data_res = if !(add_access_token_result.connector_supports_access_token
+    let (refund, router_data_res) = if !(add_access_token_result.connector_supports_access_token
         && router_data.access_token.is_none())
     {
+        let sent_refund =
+            update_refund_sent_to_gateway(state, refund, merchant_account, true).await?;
         let connector_integration: services::BoxedConnectorIntegration<
             '_,
             api::Execute,
             types::RefundsData,
             types::RefundsResponseData,
         > = connector.connector.get_connector_integration();
-        services::execute_connector_processing_step(
+        let router_data_res = match services::execute_connector_processing_step(
             state,
             connector_integration,
             &router_data,
             payments::CallConnectorAction::Trigger,
         )
         .await
-        .map_err(|error| error.to_refund_failed_response())?
+        {
+            Ok(router_data_res) => router_data_res,
+            Err(error) => {
+                // The connector never received the refund, which leaves it to be sent again
+                if !services::is_connector_request_sent(&error) {
+                    update_refund_sent_to_gateway(state, &sent_refund, merchant_account, false)
+                        .await?;
+                }
+                return Err(error.to_refund_failed_response());
+            }
+        };
+        (sent_refund, router_data_res)
     } else {
-        router_data
+        (refund.to_owned(), router_data)
     };
 
+    update_refund_with_response(state, &refund, merchant_account, router_data_res).await
+}
+
+/// Marks the refund as sent before it is sent to the connector. When the connector never
+/// answers, for instance on a timeout, the refund stays pending and retries sync it with the
+/// connector instead of sending it again. The mark is taken back when the request never reached
+/// the connector, for instance while its circuit breaker is open.
+async fn update_refund_sent_to_gateway(
+    state: &AppState,
+    refund: &storage::Refund,
+    merchant_account: &storage::merchant_account::MerchantAccount,
+    sent_to_gateway: bool,
+) -> RouterResult<storage::Refund> {
+    state
+        .store
+        .update_refund(
+            refund.to_owned(),
+            storage::RefundUpdate::StatusUpdate {
+                connector_refund_id: None,
+                sent_to_gateway,
+                refund_status: enums::RefundStatus::Pending,
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while updating whether refund was sent: refund_id: {}",
+                refund.refund_id
+            )
+        })
+}
+
+/// Stores the outcome of the refund as answered by the connector.
+async fn update_refund_with_response(
+    state: &AppState,
+    refund: &storage::Refund,
+    merchant_account: &storage::merchant_account::MerchantAccount,
+    router_data_res: types::RefundsRouterData<api::Execute>,
+) -> RouterResult<storage::Refund> {
     let refund_update = match router_data_res.response {
         Err(err) => storage::RefundUpdate::ErrorUpdate {
             refund_status: Some(enums::RefundStatus::Failure),
@@ -469,8 +653,16 @@ pub async fn validate_and_create_refund(
                 ),
             })?;
 
-            validator::validate_refund_amount(payment_attempt.amount, &all_refunds, refund_amount)
-                .change_context(errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount)?;
+            validator::validate_refund_amount(
+                payment_intent
+                    .amount_captured
+                    .unwrap_or(payment_attempt.amount),
+                &all_refunds,
+                refund_amount,
+            )
+            .change_context(errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount)?;
+
+            core_utils::validate_amount_for_currency(refund_amount, currency)?;
 
             validator::validate_maximum_refund_against_payment_attempt(
                 &all_refunds,
@@ -546,17 +738,19 @@ pub async fn refund_list(
             limit,
         )
         .await
-        .change_context(errors::ApiErrorResponse::RefundNotFound)?;
+        .map_err(|error| error.to_not_found_response(errors::ApiErrorResponse::RefundNotFound))?;
+    let next_cursor = db::pagination::get_next_cursor(&refund_list, limit);
 
     let data: Vec<refunds::RefundResponse> = refund_list
         .into_iter()
         .map(ForeignInto::foreign_into)
         .collect();
-    utils::when(data.is_empty(), || {
+    // A page fetched with a cursor is empty if the previous page held the last refunds
+    utils::when(data.is_empty() && req.cursor.is_none(), || {
         Err(errors::ApiErrorResponse::RefundNotFound)
     })?;
     Ok(services::ApplicationResponse::Json(
-        api_models::refunds::RefundListResponse { data },
+        api_models::refunds::RefundListResponse { data, next_cursor },
     ))
 }
 
@@ -950,3 +1144,475 @@ pub async fn retry_refund_sync_task(
         }
     }
 }
+
+// ********************************************** PENDING REFUNDS SYNC **********************************************
+
+pub const PENDING_REFUNDS_SYNC_RUNNER: &str = "PENDING_REFUNDS_SYNC_WORKFLOW";
+pub const PENDING_REFUNDS_SYNC_TASK: &str = "SYNC_PENDING_REFUNDS";
+
+/// Schedules the recurring pending refunds sync job, reviving it if it was finished earlier.
+#[instrument(skip_all)]
+pub async fn add_pending_refunds_sync_task(
+    db: &dyn db::StorageInterface,
+) -> Result<(), errors::ProcessTrackerError> {
+    let process_tracker_id = format!("{PENDING_REFUNDS_SYNC_RUNNER}_{PENDING_REFUNDS_SYNC_TASK}");
+    let current_time = common_utils::date_time::now();
+
+    let existing_process = db
+        .find_process_by_id(&process_tracker_id)
+        .await
+        .map_err(|error| {
+            logger::error!(?error, "Failed to fetch pending refunds sync task");
+            errors::ProcessTrackerError::ProcessFetchingFailed
+        })?;
+
+    match existing_process {
+        None => {
+            let process_tracker_entry = storage::ProcessTracker::make_process_tracker_new(
+                process_tracker_id,
+                PENDING_REFUNDS_SYNC_TASK,
+                PENDING_REFUNDS_SYNC_RUNNER,
+                serde_json::json!({}),
+                current_time,
+            )?;
+            db.insert_process(process_tracker_entry)
+                .await
+                .map_err(|error| {
+                    logger::error!(?error, "Failed to insert pending refunds sync task");
+                    errors::ProcessTrackerError::ProcessInsertionFailed
+                })?;
+        }
+        Some(process) if process.status == enums::ProcessTrackerStatus::Finish => {
+            reschedule_pending_refunds_sync_task(db, process, current_time).await?;
+        }
+        Some(_) => (),
+    }
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn sync_pending_refunds_workflow(
+    state: &AppState,
+    process: storage::ProcessTracker,
+) -> Result<(), errors::ProcessTrackerError> {
+    let refund_config = &state.conf.refund;
+    let pending_refunds = state
+        .store
+        .find_sent_refunds_by_status(enums::RefundStatus::Pending, refund_config.sync_batch_size)
+        .await
+        .map_err(|error| {
+            logger::error!(?error, "Failed to fetch pending refunds");
+            errors::ProcessTrackerError::ProcessFetchingFailed
+        })?;
+
+    let refunds_count = pending_refunds.len();
+    let synced_refunds_count =
+        sync_refunds_concurrently(pending_refunds, refund_config.sync_concurrency, |refund| {
+            sync_pending_refund(state, refund)
+        })
+        .await;
+    logger::info!(
+        refunds_count,
+        synced_refunds_count,
+        "Synced pending refunds"
+    );
+
+    let schedule_time = common_utils::date_time::now()
+        .saturating_add(time::Duration::seconds(refund_config.sync_interval));
+    reschedule_pending_refunds_sync_task(&*state.store, process, schedule_time).await
+}
+
+/// Syncs the refunds with at most `concurrency` syncs in flight, returning the number of refunds
+/// that were synced successfully.
+pub async fn sync_refunds_concurrently<F, Fut>(
+    refunds: Vec<storage::Refund>,
+    concurrency: usize,
+    sync_refund: F,
+) -> usize
+where
+    F: Fn(storage::Refund) -> Fut,
+    Fut: futures::Future<Output = RouterResult<storage::Refund>>,
+{
+    futures::stream::iter(refunds.into_iter().map(sync_refund))
+        .buffer_unordered(concurrency.max(1))
+        .fold(0, |synced_refunds_count, result| async move {
+            match result {
+                Ok(_) => synced_refunds_count + 1,
+                Err(error) => {
+                    logger::error!(?error, "Failed to sync pending refund");
+                    synced_refunds_count
+                }
+            }
+        })
+        .await
+}
+
+/// Syncs the refund with the connector. Refunds that fail to sync are marked as modified, so that
+/// the next batches pick up the refunds that were not synced yet instead of retrying them.
+async fn sync_pending_refund(
+    state: &AppState,
+    refund: storage::Refund,
+) -> RouterResult<storage::Refund> {
+    let sync_result = async {
+        let merchant_account = state
+            .store
+            .find_merchant_account_by_merchant_id(&refund.merchant_id)
+            .await
+            .map_err(|error| {
+                error.to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)
+            })?;
+
+        refund_retrieve_core(state, merchant_account, refund.refund_id.clone()).await
+    }
+    .await;
+
+    if sync_result.is_err() {
+        let refund_id = refund.refund_id.clone();
+        if let Err(error) = state
+            .store
+            .update_refund(
+                refund,
+                storage::RefundUpdate::SyncFailureUpdate,
+                // Pending refunds are fetched from the database whatever the storage scheme
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+        {
+            logger::error!(?error, %refund_id, "Failed to mark refund as modified after a failed sync");
+        }
+    }
+    sync_result
+}
+
+pub async fn reschedule_pending_refunds_sync_task(
+    db: &dyn db::StorageInterface,
+    process: storage::ProcessTracker,
+    schedule_time: time::PrimitiveDateTime,
+) -> Result<(), errors::ProcessTrackerError> {
+    db.update_process_tracker(
+        process,
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: Some(schedule_time),
+            tracking_data: None,
+            business_status: None,
+            status: Some(enums::ProcessTrackerStatus::Pending),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await
+    .map_err(|error| {
+        logger::error!(?error, "Failed to reschedule pending refunds sync task");
+        errors::ProcessTrackerError::ProcessUpdateFailed
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::panic)]
+
+    use super::*;
+    use crate::db::StorageImpl;
+
+    async fn create_succeeded_payment(state: &AppState) -> storage::MerchantAccount {
+        let payment =
+            payments::helpers::get_test_payment("pay_1", "stripe", enums::AttemptStatus::Charged);
+        let (merchant_account, _) =
+            payments::helpers::insert_test_payment(&*state.store, payment).await;
+        merchant_account.expect("Merchant account was not inserted")
+    }
+
+    fn get_refund_request(payment_id: &str, amount: i64) -> refunds::RefundRequest {
+        refunds::RefundRequest {
+            payment_id: payment_id.to_string(),
+            amount: Some(amount),
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_bulk_refund_reports_outcome_of_each_refund() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to build app state");
+        let merchant_account = create_succeeded_payment(&state).await;
+
+        let response = bulk_refund_create_core(
+            &state,
+            merchant_account,
+            refunds::BulkRefundRequest {
+                refunds: vec![
+                    get_refund_request("pay_unknown", 100),
+                    get_refund_request("pay_1", 600),
+                    // Exceeds what is left to refund after the refund before it
+                    get_refund_request("pay_1", 600),
+                ],
+            },
+        )
+        .await
+        .expect("Bulk refund failed as a whole");
+        let results = match response {
+            services::ApplicationResponse::Json(response) => response.results,
+            response => panic!("Unexpected bulk refund response {response:?}"),
+        };
+
+        let payment_ids: Vec<_> = results
+            .iter()
+            .map(|result| result.payment_id.as_str())
+            .collect();
+        assert_eq!(payment_ids, vec!["pay_unknown", "pay_1", "pay_1"]);
+
+        let error = results[0].error.as_ref().expect("Unknown payment refunded");
+        assert_eq!(
+            error.code,
+            errors::ApiErrorResponse::SuccessfulPaymentNotFound.error_code()
+        );
+        assert_eq!(results[0].refund, None);
+
+        let refund = results[1].refund.as_ref().expect("Refund was not created");
+        assert_eq!(refund.amount, 600);
+        assert_eq!(refund.status, refunds::RefundStatus::Pending);
+        assert_eq!(results[1].error, None);
+
+        let error = results[2].error.as_ref().expect("Payment refunded twice");
+        assert_eq!(
+            error.code,
+            errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount.error_code()
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_bulk_refund_with_too_many_refunds_is_rejected() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to build app state");
+        let merchant_account = create_succeeded_payment(&state).await;
+        let refunds = (0..=state.conf.refund.bulk_max_refunds)
+            .map(|_| get_refund_request("pay_1", 1))
+            .collect();
+
+        let result = bulk_refund_create_core(
+            &state,
+            merchant_account,
+            refunds::BulkRefundRequest { refunds },
+        )
+        .await;
+
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+    }
+
+    /// Inserts a charged payment of the dummy connector together with a pending refund of it.
+    #[cfg(feature = "dummy_connector")]
+    async fn insert_dummy_refund(
+        state: &AppState,
+    ) -> (
+        storage::MerchantAccount,
+        storage::PaymentAttempt,
+        storage::PaymentIntent,
+        storage::Refund,
+    ) {
+        let db = &*state.store;
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+        let (merchant_account, payment_attempt) = payments::helpers::insert_test_payment(
+            db,
+            payments::helpers::get_test_payment("pay_1", "dummy", enums::AttemptStatus::Charged),
+        )
+        .await;
+        let merchant_account = merchant_account.expect("Merchant account was not inserted");
+        db.insert_merchant_connector_account(storage::MerchantConnectorAccountNew {
+            merchant_id: Some("merchant_1".to_string()),
+            connector_name: Some("dummy".to_string()),
+            connector_account_details: Some(masking::Secret::new(serde_json::json!({
+                "auth_type": "HeaderKey",
+                "api_key": "dummy_api_key",
+            }))),
+            merchant_connector_id: "mca_1".to_string(),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to insert merchant connector account");
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id("pay_1", "merchant_1", storage_scheme)
+            .await
+            .expect("Failed to fetch payment intent");
+        let refund = db
+            .insert_refund(
+                storage::RefundNew {
+                    refund_id: "refund_1".to_string(),
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    internal_reference_id: "refid_1".to_string(),
+                    connector_transaction_id: "dummy_pay_1".to_string(),
+                    connector: "dummy".to_string(),
+                    attempt_id: payment_attempt.attempt_id.clone(),
+                    total_amount: 1000,
+                    refund_amount: 100,
+                    currency: enums::Currency::USD,
+                    refund_status: enums::RefundStatus::Pending,
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to insert refund");
+        (merchant_account, payment_attempt, payment_intent, refund)
+    }
+
+    #[cfg(feature = "dummy_connector")]
+    async fn find_dummy_refund(state: &AppState) -> storage::Refund {
+        state
+            .store
+            .find_refund_by_merchant_id_refund_id(
+                "merchant_1",
+                "refund_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch refund")
+    }
+
+    #[cfg(feature = "dummy_connector")]
+    #[actix_rt::test]
+    async fn test_refund_timing_out_is_not_sent_again() {
+        // The connector never answers within the timeout
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/payments/dummy_pay_1/refund"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)),
+            )
+            .mount(&connector_server)
+            .await;
+
+        let mut conf = crate::configs::settings::Settings::new().expect("invalid settings");
+        conf.connectors.dummyconnector.base_url = format!("{}/", connector_server.uri());
+        conf.connector_request_timeout.default_in_secs = 1;
+        let state = AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to build app state");
+        let (merchant_account, payment_attempt, payment_intent, refund) =
+            insert_dummy_refund(&state).await;
+
+        trigger_refund_to_gateway(
+            &state,
+            &refund,
+            &merchant_account,
+            &payment_attempt,
+            &payment_intent,
+        )
+        .await
+        .expect_err("Refund without a connector response succeeded");
+        let refund = find_dummy_refund(&state).await;
+        assert!(refund.sent_to_gateway);
+        assert_eq!(refund.refund_status, enums::RefundStatus::Pending);
+
+        // The retry leaves the refund to the sync instead of sending it again
+        let sent_requests_count = connector_server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .len();
+        trigger_refund_to_gateway(
+            &state,
+            &refund,
+            &merchant_account,
+            &payment_attempt,
+            &payment_intent,
+        )
+        .await
+        .expect("Failed to retry refund");
+        assert_eq!(
+            connector_server
+                .received_requests()
+                .await
+                .unwrap_or_default()
+                .len(),
+            sent_requests_count
+        );
+    }
+
+    #[cfg(feature = "dummy_connector")]
+    #[actix_rt::test]
+    async fn test_refund_not_sent_while_circuit_is_open_is_retried() {
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/payments/dummy_pay_1/refund"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "id": "dummy_ref_1", "status": "succeeded" }),
+                ),
+            )
+            .mount(&connector_server)
+            .await;
+
+        let mut conf = crate::configs::settings::Settings::new().expect("invalid settings");
+        conf.connectors.dummyconnector.base_url = format!("{}/", connector_server.uri());
+        let state = AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to build app state");
+        let (merchant_account, payment_attempt, payment_intent, refund) =
+            insert_dummy_refund(&state).await;
+
+        // The circuit state is kept in Redis, which is shared by the test runs, so the circuit is
+        // only kept open for the refund that is short-circuited
+        state
+            .store
+            .open_circuit("dummy", 0, 1)
+            .await
+            .expect("Failed to open circuit");
+        let result = trigger_refund_to_gateway(
+            &state,
+            &refund,
+            &merchant_account,
+            &payment_attempt,
+            &payment_intent,
+        )
+        .await;
+        state
+            .store
+            .close_circuit("dummy")
+            .await
+            .expect("Failed to close circuit");
+        result.expect_err("Refund was sent while the circuit breaker was open");
+        let refund = find_dummy_refund(&state).await;
+        assert!(!refund.sent_to_gateway);
+        assert_eq!(refund.refund_status, enums::RefundStatus::Pending);
+        assert!(!validator::is_refund_sent_to_gateway(&refund));
+
+        // The retry sends the refund to the connector
+        let refund = trigger_refund_to_gateway(
+            &state,
+            &refund,
+            &merchant_account,
+            &payment_attempt,
+            &payment_intent,
+        )
+        .await
+        .expect("Failed to retry refund");
+        assert_eq!(refund.refund_status, enums::RefundStatus::Success);
+        assert_eq!(
+            connector_server
+                .received_requests()
+                .await
+                .unwrap_or_default()
+                .len(),
+            1
+        );
+    }
+}
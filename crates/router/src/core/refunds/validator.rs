@@ -15,8 +15,6 @@ use crate::{
 pub enum RefundValidationError {
     #[error("The payment attempt was not successful")]
     UnsuccessfulPaymentAttempt,
-    #[error("The refund amount exceeds the payment amount")]
-    RefundAmountExceedsPaymentAmount,
     #[error("The order has expired")]
     OrderExpired,
     #[error("The maximum refund count for this payment attempt")]
@@ -36,12 +34,15 @@ pub fn validate_success_transaction(
     Ok(())
 }
 
+/// Ensures the new refund along with the earlier refunds of the payment does not exceed the
+/// captured amount. Refunds that have not failed are counted, so that refunds still in flight
+/// cannot be used to over-refund the payment.
 #[instrument(skip_all)]
 pub fn validate_refund_amount(
-    payment_attempt_amount: i64, // &storage::PaymentAttempt,
+    captured_amount: i64,
     all_refunds: &[storage::Refund],
     refund_amount: i64,
-) -> CustomResult<(), RefundValidationError> {
+) -> CustomResult<(), errors::ValidationError> {
     let total_refunded_amount: i64 = all_refunds
         .iter()
         .filter_map(|refund| {
@@ -56,11 +57,15 @@ pub fn validate_refund_amount(
         .sum();
 
     utils::when(
-        refund_amount > (payment_attempt_amount - total_refunded_amount),
+        refund_amount > (captured_amount - total_refunded_amount),
         || {
-            Err(report!(
-                RefundValidationError::RefundAmountExceedsPaymentAmount
-            ))
+            Err(report!(errors::ValidationError::IncorrectValueProvided {
+                field_name: "refund_amount"
+            })
+            .attach_printable(format!(
+                "refund amount {refund_amount} exceeds the remaining refundable amount {}",
+                captured_amount - total_refunded_amount
+            )))
         },
     )
 }
@@ -123,6 +128,16 @@ pub async fn validate_uniqueness_of_refund_id_against_merchant_id(
     }
 }
 
+/// Whether the refund has already been accepted or processed by the connector, in which case
+/// sending it again, for instance when retrying after a timeout, could refund the payment twice.
+pub fn is_refund_sent_to_gateway(refund: &storage::Refund) -> bool {
+    match refund.refund_status {
+        enums::RefundStatus::Success => true,
+        enums::RefundStatus::Pending | enums::RefundStatus::ManualReview => refund.sent_to_gateway,
+        enums::RefundStatus::Failure | enums::RefundStatus::TransactionFailure => false,
+    }
+}
+
 pub fn validate_refund_list(limit: Option<i64>) -> CustomResult<i64, errors::ApiErrorResponse> {
     match limit {
         Some(limit_val) => {
@@ -170,3 +185,91 @@ pub fn validate_for_valid_refunds(
     )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_refund(refund_amount: i64, refund_status: enums::RefundStatus) -> storage::Refund {
+        let current_time = common_utils::date_time::now();
+        storage::Refund {
+            id: 1,
+            internal_reference_id: "refid_1".to_string(),
+            refund_id: "ref_1".to_string(),
+            payment_id: "pay_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            connector_transaction_id: "txn_1".to_string(),
+            connector: "stripe".to_string(),
+            connector_refund_id: None,
+            external_reference_id: None,
+            refund_type: enums::RefundType::RegularRefund,
+            total_amount: 1000,
+            currency: enums::Currency::USD,
+            refund_amount,
+            refund_status,
+            sent_to_gateway: true,
+            refund_error_message: None,
+            metadata: None,
+            refund_arn: None,
+            created_at: current_time,
+            updated_at: current_time,
+            description: None,
+            attempt_id: "attempt_1".to_string(),
+            refund_reason: None,
+            refund_error_code: None,
+        }
+    }
+
+    #[test]
+    fn test_refund_of_exact_remaining_amount_is_allowed() {
+        let all_refunds = [get_refund(600, enums::RefundStatus::Success)];
+
+        assert!(validate_refund_amount(1000, &all_refunds, 400).is_ok());
+    }
+
+    #[test]
+    fn test_refund_over_remaining_amount_is_rejected() {
+        let all_refunds = [get_refund(600, enums::RefundStatus::Success)];
+
+        let result = validate_refund_amount(1000, &all_refunds, 401);
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ValidationError::IncorrectValueProvided {
+                field_name: "refund_amount"
+            })
+        ));
+    }
+
+    #[test]
+    fn test_multiple_partial_refunds_are_summed() {
+        let mut all_refunds = vec![
+            get_refund(300, enums::RefundStatus::Success),
+            get_refund(300, enums::RefundStatus::Pending),
+            get_refund(500, enums::RefundStatus::Failure),
+        ];
+        assert!(validate_refund_amount(1000, &all_refunds, 400).is_ok());
+
+        all_refunds.push(get_refund(400, enums::RefundStatus::Success));
+        assert!(validate_refund_amount(1000, &all_refunds, 1).is_err());
+    }
+
+    #[test]
+    fn test_refund_accepted_by_connector_is_not_sent_again() {
+        assert!(is_refund_sent_to_gateway(&get_refund(
+            500,
+            enums::RefundStatus::Success
+        )));
+        assert!(is_refund_sent_to_gateway(&get_refund(
+            500,
+            enums::RefundStatus::Pending
+        )));
+
+        let mut refund = get_refund(500, enums::RefundStatus::Pending);
+        refund.sent_to_gateway = false;
+        assert!(!is_refund_sent_to_gateway(&refund));
+        assert!(!is_refund_sent_to_gateway(&get_refund(
+            500,
+            enums::RefundStatus::Failure
+        )));
+    }
+}
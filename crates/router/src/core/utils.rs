@@ -6,15 +6,28 @@ use router_env::{instrument, tracing};
 use super::payments::PaymentAddress;
 use crate::{
     consts,
-    core::errors::{self, RouterResult},
+    core::{
+        connector_credentials,
+        errors::{self, RouterResult},
+    },
     routes::AppState,
     types::{
-        self,
+        self, api,
         storage::{self, enums},
     },
-    utils::{generate_id, OptionExt, ValueExt},
+    utils::{generate_id, OptionExt},
 };
 
+/// Rejects amounts that cannot be charged in the currency without rounding.
+pub fn validate_amount_for_currency(amount: i64, currency: enums::Currency) -> RouterResult<()> {
+    types::Money::new(amount, currency)
+        .validate()
+        .map_err(|error| {
+            let message = error.current_context().to_string();
+            error.change_context(errors::ApiErrorResponse::InvalidRequestData { message })
+        })
+}
+
 #[instrument(skip_all)]
 #[allow(clippy::too_many_arguments)]
 pub async fn construct_refund_router_data<'a, F>(
@@ -35,10 +48,8 @@ pub async fn construct_refund_router_data<'a, F>(
         .await
         .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let auth_type: types::ConnectorAuthType = merchant_connector_account
-        .connector_account_details
-        .parse_value("ConnectorAuthType")
-        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let auth_type =
+        connector_credentials::get_connector_auth_type(state, &merchant_connector_account).await?;
 
     let status = payment_attempt.status;
 
@@ -61,11 +72,13 @@ pub async fn construct_refund_router_data<'a, F>(
         return_url: payment_intent.return_url.clone(),
         router_return_url: None,
         payment_method_id: payment_attempt.payment_method_id.clone(),
+        connector_response_body: None,
         // Does refund need shipping/billing address ?
         address: PaymentAddress::default(),
         auth_type: payment_attempt.authentication_type.unwrap_or_default(),
         connector_meta_data: None,
         amount_captured: payment_intent.amount_captured,
+        authorized_amount: None,
         request: types::RefundsData {
             refund_id: refund.refund_id.clone(),
             connector_transaction_id: refund.connector_transaction_id.clone(),
@@ -87,6 +100,60 @@ pub async fn construct_refund_router_data<'a, F>(
     Ok(router_data)
 }
 
+#[instrument(skip_all)]
+pub async fn construct_submit_evidence_router_data(
+    state: &AppState,
+    merchant_account: &storage::MerchantAccount,
+    payment_attempt: &storage::PaymentAttempt,
+    dispute: &storage::Dispute,
+    evidence: api::SubmitEvidenceRequest,
+) -> RouterResult<types::SubmitEvidenceRouterData> {
+    let db = &*state.store;
+    let merchant_connector_account = db
+        .find_merchant_connector_account_by_merchant_id_connector(
+            &merchant_account.merchant_id,
+            &dispute.connector,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let auth_type =
+        connector_credentials::get_connector_auth_type(state, &merchant_connector_account).await?;
+
+    let payment_method_type = payment_attempt
+        .payment_method
+        .get_required_value("payment_method_type")?;
+
+    Ok(types::RouterData {
+        flow: PhantomData,
+        merchant_id: merchant_account.merchant_id.clone(),
+        connector: merchant_connector_account.connector_name,
+        payment_id: payment_attempt.payment_id.clone(),
+        attempt_id: Some(payment_attempt.attempt_id.clone()),
+        status: payment_attempt.status,
+        payment_method: payment_method_type,
+        connector_auth_type: auth_type,
+        description: None,
+        return_url: None,
+        router_return_url: None,
+        payment_method_id: payment_attempt.payment_method_id.clone(),
+        connector_response_body: None,
+        address: PaymentAddress::default(),
+        auth_type: payment_attempt.authentication_type.unwrap_or_default(),
+        connector_meta_data: None,
+        amount_captured: None,
+        authorized_amount: None,
+        request: types::SubmitEvidenceRequestData {
+            dispute_id: dispute.dispute_id.clone(),
+            connector_dispute_id: dispute.connector_dispute_id.clone(),
+            evidence,
+        },
+        // Stays as is for connectors that cannot submit evidence
+        response: Err(types::ErrorResponse::get_not_implemented()),
+        access_token: None,
+    })
+}
+
 pub fn get_or_generate_id(
     key: &str,
     provided_id: &Option<String>,
@@ -1,37 +1,61 @@
 pub mod transformers;
 pub mod utils;
 
+use common_utils::crypto::{self, SignMessage};
 use error_stack::{IntoReport, ResultExt};
 use masking::ExposeInterface;
 use router_env::{instrument, tracing};
 
 use crate::{
+    configs::settings,
     consts,
     core::{
-        errors::{self, CustomResult, RouterResponse},
-        payments,
+        errors::{self, CustomResult, RouterResponse, StorageErrorExt},
+        payments, refunds,
     },
     db::StorageInterface,
     logger,
     routes::AppState,
+    scheduler::utils as pt_utils,
     services,
     types::{
         api,
-        storage::{self, enums},
-        transformers::{ForeignInto, ForeignTryInto},
+        storage::{self, enums, ProcessTrackerExt},
+        transformers::{Foreign, ForeignInto, ForeignTryInto},
     },
-    utils::{generate_id, Encode, OptionExt, ValueExt},
+    utils::{generate_id, when, Encode, OptionExt, ValueExt},
 };
 
 const OUTGOING_WEBHOOK_TIMEOUT_MS: u64 = 5000;
+const OUTGOING_WEBHOOK_RETRY_RUNNER: &str = "OUTGOING_WEBHOOK_RETRY_WORKFLOW";
+const OUTGOING_WEBHOOK_RETRY_TASK: &str = "OUTGOING_WEBHOOK_RETRY";
+/// Delays between delivery attempts of an outgoing webhook: 1m, 5m, 30m and 2h.
+const OUTGOING_WEBHOOK_RETRY_DELAYS_SECS: [i64; 4] = [60, 300, 1800, 7200];
+const WEBHOOK_DELIVERY_ATTEMPT_LIST_DEFAULT_LIMIT: i64 = 10;
 
 #[instrument(skip_all)]
 async fn payments_incoming_webhook_flow(
     state: AppState,
     merchant_account: storage::MerchantAccount,
+    connector_name: &str,
     webhook_details: api::IncomingWebhookDetails,
     source_verified: bool,
 ) -> CustomResult<(), errors::WebhooksFlowError> {
+    let payment_id = match utils::find_webhook_object_id(
+        &*state.store,
+        &merchant_account,
+        connector_name,
+        &api::WebhookFlow::Payment,
+        &webhook_details.object_reference_id,
+    )
+    .await?
+    {
+        utils::WebhookObjectId::Payment { payment_id } => payment_id,
+        object_id => Err(errors::WebhooksFlowError::WebhookObjectNotFound)
+            .into_report()
+            .attach_printable_lazy(|| format!("Payment webhook refers to {object_id:?}"))?,
+    };
+
     let consume_or_trigger_flow = if source_verified {
         payments::CallConnectorAction::HandleResponse(webhook_details.resource_object)
     } else {
@@ -43,9 +67,7 @@ async fn payments_incoming_webhook_flow(
         merchant_account.clone(),
         payments::operations::PaymentStatus,
         api::PaymentsRetrieveRequest {
-            resource_id: api::PaymentIdType::ConnectorTransactionId(
-                webhook_details.object_reference_id,
-            ),
+            resource_id: api::PaymentIdType::PaymentIntentId(payment_id),
             merchant_id: Some(merchant_account.merchant_id.clone()),
             force_sync: true,
             connector: None,
@@ -90,6 +112,166 @@ async fn payments_incoming_webhook_flow(
     Ok(())
 }
 
+fn get_refund_status(
+    event_type: &api::IncomingWebhookEvent,
+) -> CustomResult<enums::RefundStatus, errors::WebhooksFlowError> {
+    match event_type {
+        api::IncomingWebhookEvent::RefundSuccess => Ok(enums::RefundStatus::Success),
+        api::IncomingWebhookEvent::RefundFailure => Ok(enums::RefundStatus::Failure),
+        _ => Err(errors::WebhooksFlowError::RefundsCoreFailed)
+            .into_report()
+            .attach_printable_lazy(|| format!("{event_type:?} is not a refund event")),
+    }
+}
+
+/// Updates the refund that a refund webhook refers to. The status is taken from the webhook when
+/// its source is verified, and synced from the connector otherwise.
+#[instrument(skip_all)]
+pub async fn refunds_incoming_webhook_flow(
+    state: &AppState,
+    merchant_account: storage::MerchantAccount,
+    connector_name: &str,
+    webhook_details: api::IncomingWebhookDetails,
+    source_verified: bool,
+    event_type: &api::IncomingWebhookEvent,
+) -> CustomResult<storage::Refund, errors::WebhooksFlowError> {
+    let refund_id = match utils::find_webhook_object_id(
+        &*state.store,
+        &merchant_account,
+        connector_name,
+        &api::WebhookFlow::Refund,
+        &webhook_details.object_reference_id,
+    )
+    .await?
+    {
+        utils::WebhookObjectId::Refund { refund_id, .. } => refund_id,
+        object_id => Err(errors::WebhooksFlowError::WebhookObjectNotFound)
+            .into_report()
+            .attach_printable_lazy(|| format!("Refund webhook refers to {object_id:?}"))?,
+    };
+
+    if !source_verified {
+        return refunds::refund_retrieve_core(state, merchant_account, refund_id)
+            .await
+            .change_context(errors::WebhooksFlowError::RefundsCoreFailed);
+    }
+
+    let refund_status = get_refund_status(event_type)?;
+    let refund = state
+        .store
+        .find_refund_by_merchant_id_refund_id(
+            &merchant_account.merchant_id,
+            &refund_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::WebhooksFlowError::RefundsCoreFailed)?;
+    if !refund.refund_status.can_transition_to(refund_status) {
+        logger::info!(
+            refund_id = %refund.refund_id,
+            current_status = %refund.refund_status,
+            webhook_status = %refund_status,
+            "Ignoring refund webhook for a completed refund"
+        );
+        return Ok(refund);
+    }
+    state
+        .store
+        .update_refund(
+            refund,
+            storage::RefundUpdate::StatusUpdate {
+                connector_refund_id: Some(webhook_details.object_reference_id),
+                sent_to_gateway: true,
+                refund_status,
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::WebhooksFlowError::RefundsCoreFailed)
+}
+
+fn get_dispute_status(
+    event_type: &api::IncomingWebhookEvent,
+) -> CustomResult<enums::DisputeStatus, errors::WebhooksFlowError> {
+    match event_type {
+        api::IncomingWebhookEvent::DisputeOpened => Ok(enums::DisputeStatus::Opened),
+        api::IncomingWebhookEvent::DisputeWon => Ok(enums::DisputeStatus::Won),
+        api::IncomingWebhookEvent::DisputeLost => Ok(enums::DisputeStatus::Lost),
+        _ => Err(errors::WebhooksFlowError::DisputesCoreFailed)
+            .into_report()
+            .attach_printable_lazy(|| format!("{event_type:?} is not a dispute event")),
+    }
+}
+
+/// Records the dispute on its first webhook and moves it to the stage of the later ones.
+#[instrument(skip_all)]
+pub async fn disputes_incoming_webhook_flow(
+    db: &dyn StorageInterface,
+    merchant_account: &storage::MerchantAccount,
+    connector_name: &str,
+    dispute_details: api::DisputePayload,
+    event_type: &api::IncomingWebhookEvent,
+) -> CustomResult<storage::Dispute, errors::WebhooksFlowError> {
+    let dispute_status = get_dispute_status(event_type)?;
+
+    let existing_dispute = db
+        .find_dispute_by_merchant_id_connector_dispute_id(
+            &merchant_account.merchant_id,
+            connector_name,
+            &dispute_details.connector_dispute_id,
+        )
+        .await
+        .change_context(errors::WebhooksFlowError::DisputesCoreFailed)?;
+
+    match existing_dispute {
+        Some(dispute) if !dispute.dispute_status.can_transition_to(dispute_status) => {
+            logger::info!(
+                dispute_id = %dispute.dispute_id,
+                current_status = %dispute.dispute_status,
+                webhook_status = %dispute_status,
+                "Ignoring dispute webhook for a closed dispute"
+            );
+            Ok(dispute)
+        }
+        Some(dispute) => db
+            .update_dispute(
+                dispute,
+                storage::DisputeUpdate::StatusUpdate {
+                    dispute_status,
+                    connector_reason_code: dispute_details.connector_reason_code,
+                },
+            )
+            .await
+            .change_context(errors::WebhooksFlowError::DisputesCoreFailed),
+        None => {
+            let payment_attempt = db
+                .find_payment_attempt_by_merchant_id_connector_txn_id(
+                    &merchant_account.merchant_id,
+                    &dispute_details.connector_transaction_id,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .change_context(errors::WebhooksFlowError::DisputesCoreFailed)
+                .attach_printable("Could not find the disputed payment")?;
+
+            db.insert_dispute(storage::DisputeNew {
+                dispute_id: generate_id(consts::ID_LENGTH, "dp"),
+                merchant_id: merchant_account.merchant_id.clone(),
+                payment_id: payment_attempt.payment_id,
+                attempt_id: payment_attempt.attempt_id,
+                connector: connector_name.to_string(),
+                connector_dispute_id: dispute_details.connector_dispute_id,
+                connector_reason_code: dispute_details.connector_reason_code,
+                amount: dispute_details.amount,
+                currency: dispute_details.currency,
+                dispute_status,
+            })
+            .await
+            .change_context(errors::WebhooksFlowError::DisputesCoreFailed)
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 #[instrument(skip_all)]
 async fn create_event_and_trigger_outgoing_webhook(
@@ -125,15 +307,21 @@ async fn create_event_and_trigger_outgoing_webhook(
 
         let outgoing_webhook = api::OutgoingWebhook {
             merchant_id: merchant_account.merchant_id.clone(),
-            event_id: event.event_id,
+            event_id: event.event_id.clone(),
             event_type: event.event_type.foreign_into(),
             content,
             timestamp: event.created_at,
         };
 
         arbiter.spawn(async move {
-            let result =
-                trigger_webhook_to_merchant(merchant_account, outgoing_webhook, state.store).await;
+            let result = trigger_webhook_to_merchant(
+                merchant_account,
+                event,
+                outgoing_webhook,
+                state.store,
+                &state.conf.webhooks,
+            )
+            .await;
 
             if let Err(e) = result {
                 logger::error!(?e);
@@ -146,11 +334,47 @@ async fn create_event_and_trigger_outgoing_webhook(
 
 async fn trigger_webhook_to_merchant(
     merchant_account: storage::MerchantAccount,
+    event: storage::Event,
     webhook: api::OutgoingWebhook,
-    _db: Box<dyn StorageInterface>,
+    db: Box<dyn StorageInterface>,
+    webhooks_settings: &settings::WebhooksSettings,
 ) -> CustomResult<(), errors::WebhooksFlowError> {
+    let webhook_url = get_webhook_url(&merchant_account)?;
+    let webhook_body = Encode::<api::OutgoingWebhook>::encode_to_value(&webhook)
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)?;
+    let mut tracking_data = OutgoingWebhookTrackingData {
+        merchant_id: merchant_account.merchant_id.clone(),
+        event_id: event.event_id,
+        event_type: event.event_type,
+        primary_object_id: event.primary_object_id,
+        webhook: webhook_body,
+        last_error: None,
+    };
+
+    let delivery_result = send_webhook_to_merchant(
+        &webhook_url,
+        &tracking_data.webhook,
+        &merchant_account,
+        webhooks_settings,
+    )
+    .await;
+    record_webhook_delivery_attempt(&*db, &tracking_data, webhook_url, 1, &delivery_result).await;
+
+    if let Err(error) = delivery_result {
+        tracking_data.last_error = Some(error.current_context().to_string());
+        add_outgoing_webhook_retry_task(&*db, &tracking_data).await?;
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+fn get_webhook_url(
+    merchant_account: &storage::MerchantAccount,
+) -> CustomResult<String, errors::WebhooksFlowError> {
     let webhook_details_json = merchant_account
         .webhook_details
+        .clone()
         .get_required_value("webhook_details")
         .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?;
 
@@ -159,40 +383,413 @@ async fn trigger_webhook_to_merchant(
             .parse_value("WebhookDetails")
             .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?;
 
-    let webhook_url = webhook_details
+    webhook_details
         .webhook_url
         .get_required_value("webhook_url")
         .change_context(errors::WebhooksFlowError::MerchantWebhookURLNotConfigured)
-        .map(ExposeInterface::expose)?;
+        .map(ExposeInterface::expose)
+}
+
+async fn send_webhook_to_merchant(
+    webhook_url: &str,
+    webhook_body: &serde_json::Value,
+    merchant_account: &storage::MerchantAccount,
+    webhooks_settings: &settings::WebhooksSettings,
+) -> CustomResult<u16, errors::WebhooksFlowError> {
+    let body = serde_json::to_vec(webhook_body)
+        .into_report()
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)?;
+
+    let mut request = reqwest::Client::new()
+        .post(webhook_url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+    // Merchants without a hash key receive unsigned webhooks
+    if let Some(secret) = merchant_account.payment_response_hash_key.as_ref() {
+        let signature = sign_outgoing_webhook(
+            webhooks_settings.signature_algorithm,
+            secret.as_bytes(),
+            &body,
+        )
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)?;
+        request = request.header(webhooks_settings.signature_header.as_str(), signature);
+    }
 
-    let response = reqwest::Client::new()
-        .post(&webhook_url)
-        .header(reqwest::header::CONTENT_TYPE, "application/json")
-        .json(&webhook)
+    let response = request
+        .body(body)
         .timeout(core::time::Duration::from_millis(
             OUTGOING_WEBHOOK_TIMEOUT_MS,
         ))
         .send()
         .await;
 
-    match response {
-        Err(e) => {
-            // [#217]: Schedule webhook for retry.
-            Err(e)
-                .into_report()
-                .change_context(errors::WebhooksFlowError::CallToMerchantFailed)?;
+    let status = response
+        .into_report()
+        .change_context(errors::WebhooksFlowError::CallToMerchantFailed)?
+        .status();
+    if !status.is_success() {
+        Err(errors::WebhooksFlowError::NotReceivedByMerchant {
+            status_code: status.as_u16(),
+        })
+        .into_report()?;
+    }
+
+    Ok(status.as_u16())
+}
+
+/// Records an attempt at delivering an outgoing webhook. Failing to record the attempt is only
+/// logged, as it must not affect the delivery itself.
+async fn record_webhook_delivery_attempt(
+    db: &dyn StorageInterface,
+    tracking_data: &OutgoingWebhookTrackingData,
+    webhook_url: String,
+    attempt_number: i32,
+    delivery_result: &CustomResult<u16, errors::WebhooksFlowError>,
+) -> Option<storage::WebhookDeliveryAttempt> {
+    let (status_code, error_message) = match delivery_result {
+        Ok(status_code) => (Some(*status_code), None),
+        Err(error) => {
+            let status_code = match error.current_context() {
+                errors::WebhooksFlowError::NotReceivedByMerchant { status_code } => {
+                    Some(*status_code)
+                }
+                _ => None,
+            };
+            (status_code, Some(error.current_context().to_string()))
         }
-        Ok(res) => {
-            if !res.status().is_success() {
-                // [#217]: Schedule webhook for retry.
-                Err(errors::WebhooksFlowError::NotReceivedByMerchant).into_report()?;
-            }
+    };
+    let webhook_delivery_attempt = storage::WebhookDeliveryAttemptNew {
+        merchant_id: tracking_data.merchant_id.clone(),
+        event_id: tracking_data.event_id.clone(),
+        event_type: tracking_data.event_type,
+        primary_object_id: tracking_data.primary_object_id.clone(),
+        webhook_url,
+        status_code: status_code.map(i32::from),
+        attempt_number,
+        error_message,
+    };
+
+    db.insert_webhook_delivery_attempt(webhook_delivery_attempt)
+        .await
+        .map_err(|error| {
+            logger::error!(?error, event_id = %tracking_data.event_id, "Failed to record webhook delivery attempt");
+        })
+        .ok()
+}
+
+/// Delivers the outgoing webhook of `event_id` to the merchant again, recording the redelivery as
+/// a new delivery attempt. Only webhooks whose delivery failed can be redelivered, as their
+/// content is kept for retries; a successful redelivery completes the pending retries.
+#[instrument(skip_all)]
+pub async fn redeliver_outgoing_webhook(
+    state: &AppState,
+    merchant_id: &str,
+    event_id: &str,
+) -> RouterResponse<api::WebhookDeliveryAttemptResponse> {
+    let db = &*state.store;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id)
+        .await
+        .map_err(|error| {
+            error.to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)
+        })?;
+
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        OUTGOING_WEBHOOK_RETRY_RUNNER,
+        OUTGOING_WEBHOOK_RETRY_TASK,
+        event_id,
+        merchant_id,
+    );
+    let process = db
+        .find_process_by_id(&process_tracker_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch outgoing webhook retry task")?
+        .ok_or(errors::ApiErrorResponse::WebhookEventNotFound)
+        .into_report()
+        .attach_printable("No failed delivery of the webhook event")?;
+    let tracking_data: OutgoingWebhookTrackingData = process
+        .tracking_data
+        .clone()
+        .parse_value("OutgoingWebhookTrackingData")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let attempt_number = db
+        .find_webhook_delivery_attempts_by_merchant_id_event_id(merchant_id, event_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch webhook delivery attempts")?
+        .iter()
+        .map(|attempt| attempt.attempt_number)
+        .max()
+        .unwrap_or_default()
+        + 1;
+    let webhook_url = get_webhook_url(&merchant_account).change_context(
+        errors::ApiErrorResponse::PreconditionFailed {
+            message: "Merchant does not have a webhook URL configured".to_string(),
+        },
+    )?;
+
+    let delivery_result = send_webhook_to_merchant(
+        &webhook_url,
+        &tracking_data.webhook,
+        &merchant_account,
+        &state.conf.webhooks,
+    )
+    .await;
+    let webhook_delivery_attempt = record_webhook_delivery_attempt(
+        db,
+        &tracking_data,
+        webhook_url,
+        attempt_number,
+        &delivery_result,
+    )
+    .await
+    .ok_or(errors::ApiErrorResponse::InternalServerError)
+    .into_report()
+    .attach_printable("Failed to record webhook delivery attempt")?;
+
+    if delivery_result.is_ok() && process.status != enums::ProcessTrackerStatus::Finish {
+        if let Err(error) = process
+            .finish_with_status(db, "COMPLETED_BY_REDELIVERY".to_string())
+            .await
+        {
+            logger::error!(?error, %event_id, "Failed to complete retries of redelivered webhook");
         }
     }
 
+    Ok(services::ApplicationResponse::Json(
+        webhook_delivery_attempt.foreign_into(),
+    ))
+}
+
+#[instrument(skip_all)]
+pub async fn list_webhook_delivery_attempts(
+    db: &dyn StorageInterface,
+    merchant_account: storage::MerchantAccount,
+    req: api::WebhookDeliveryAttemptListRequest,
+) -> RouterResponse<api::WebhookDeliveryAttemptListResponse> {
+    let limit = req
+        .limit
+        .unwrap_or(WEBHOOK_DELIVERY_ATTEMPT_LIST_DEFAULT_LIMIT);
+    when(!(1..=100).contains(&limit), || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "limit should be in between 1 and 100".to_string(),
+        })
+    })?;
+
+    let webhook_delivery_attempts = match req.payment_id {
+        Some(payment_id) => {
+            db.find_webhook_delivery_attempts_by_merchant_id_primary_object_id(
+                &merchant_account.merchant_id,
+                &payment_id,
+                limit,
+            )
+            .await
+        }
+        None => {
+            db.find_webhook_delivery_attempts_by_merchant_id(&merchant_account.merchant_id, limit)
+                .await
+        }
+    }
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to list webhook delivery attempts")?;
+
+    Ok(services::ApplicationResponse::Json(
+        api::WebhookDeliveryAttemptListResponse {
+            data: webhook_delivery_attempts
+                .into_iter()
+                .map(ForeignInto::foreign_into)
+                .collect(),
+        },
+    ))
+}
+
+impl From<Foreign<storage::WebhookDeliveryAttempt>>
+    for Foreign<api::WebhookDeliveryAttemptResponse>
+{
+    fn from(webhook_delivery_attempt: Foreign<storage::WebhookDeliveryAttempt>) -> Self {
+        let webhook_delivery_attempt = webhook_delivery_attempt.0;
+        api::WebhookDeliveryAttemptResponse {
+            event_id: webhook_delivery_attempt.event_id,
+            event_type: webhook_delivery_attempt.event_type.foreign_into(),
+            object_id: webhook_delivery_attempt.primary_object_id,
+            webhook_url: webhook_delivery_attempt.webhook_url,
+            status_code: webhook_delivery_attempt
+                .status_code
+                .and_then(|status_code| u16::try_from(status_code).ok()),
+            attempt_number: webhook_delivery_attempt.attempt_number,
+            error_message: webhook_delivery_attempt.error_message,
+            created_at: webhook_delivery_attempt.created_at,
+        }
+        .into()
+    }
+}
+
+/// Signs the outgoing webhook body with the merchant's secret, returning the hex encoded signature.
+pub fn sign_outgoing_webhook(
+    algorithm: settings::WebhookSignatureAlgorithm,
+    secret: &[u8],
+    body: &[u8],
+) -> CustomResult<String, common_utils::errors::CryptoError> {
+    let signature = match algorithm {
+        settings::WebhookSignatureAlgorithm::HmacSha256 => {
+            crypto::HmacSha256.sign_message(secret, body)
+        }
+        settings::WebhookSignatureAlgorithm::HmacSha512 => {
+            crypto::HmacSha512.sign_message(secret, body)
+        }
+    }?;
+    Ok(hex::encode(signature))
+}
+
+/// Outgoing webhook that could not be delivered, tracked for retries by the process tracker.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OutgoingWebhookTrackingData {
+    pub merchant_id: String,
+    pub event_id: String,
+    pub event_type: enums::EventType,
+    pub primary_object_id: String,
+    pub webhook: serde_json::Value,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum OutgoingWebhookRetryAction {
+    Completed,
+    RetryAfter(time::Duration),
+    RetriesExceeded,
+}
+
+/// Delay before the retry numbered `retry_count` (starting at 0), `None` once retries are exhausted.
+fn get_outgoing_webhook_retry_delay(retry_count: i32) -> Option<time::Duration> {
+    usize::try_from(retry_count)
+        .ok()
+        .and_then(|retry_count| OUTGOING_WEBHOOK_RETRY_DELAYS_SECS.get(retry_count))
+        .map(|delay| time::Duration::seconds(*delay))
+}
+
+fn get_outgoing_webhook_retry_action(
+    retry_count: i32,
+    is_delivered: bool,
+) -> OutgoingWebhookRetryAction {
+    if is_delivered {
+        return OutgoingWebhookRetryAction::Completed;
+    }
+    get_outgoing_webhook_retry_delay(retry_count + 1).map_or(
+        OutgoingWebhookRetryAction::RetriesExceeded,
+        OutgoingWebhookRetryAction::RetryAfter,
+    )
+}
+
+async fn add_outgoing_webhook_retry_task(
+    db: &dyn StorageInterface,
+    tracking_data: &OutgoingWebhookTrackingData,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let schedule_time = get_outgoing_webhook_retry_delay(0)
+        .map(|delay| common_utils::date_time::now().saturating_add(delay))
+        .ok_or(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)
+        .into_report()?;
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        OUTGOING_WEBHOOK_RETRY_RUNNER,
+        OUTGOING_WEBHOOK_RETRY_TASK,
+        &tracking_data.event_id,
+        &tracking_data.merchant_id,
+    );
+    let process_tracker_entry = storage::ProcessTracker::make_process_tracker_new(
+        process_tracker_id,
+        OUTGOING_WEBHOOK_RETRY_TASK,
+        OUTGOING_WEBHOOK_RETRY_RUNNER,
+        tracking_data,
+        schedule_time,
+    )
+    .into_report()
+    .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)?;
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)?;
     Ok(())
 }
 
+#[instrument(skip_all)]
+pub async fn start_outgoing_webhook_retry_workflow(
+    state: &AppState,
+    process: storage::ProcessTracker,
+) -> Result<(), errors::ProcessTrackerError> {
+    let db = &*state.store;
+    let mut tracking_data: OutgoingWebhookTrackingData = process
+        .tracking_data
+        .clone()
+        .parse_value("OutgoingWebhookTrackingData")?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&tracking_data.merchant_id)
+        .await?;
+
+    let delivery_result = match get_webhook_url(&merchant_account) {
+        Ok(webhook_url) => {
+            let delivery_result = send_webhook_to_merchant(
+                &webhook_url,
+                &tracking_data.webhook,
+                &merchant_account,
+                &state.conf.webhooks,
+            )
+            .await;
+            // The first retry is the second delivery attempt
+            record_webhook_delivery_attempt(
+                db,
+                &tracking_data,
+                webhook_url,
+                process.retry_count + 2,
+                &delivery_result,
+            )
+            .await;
+            delivery_result
+        }
+        Err(error) => Err(error),
+    };
+
+    match get_outgoing_webhook_retry_action(process.retry_count, delivery_result.is_ok()) {
+        OutgoingWebhookRetryAction::Completed => {
+            let id = process.id.clone();
+            process
+                .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+                .await
+        }
+        OutgoingWebhookRetryAction::RetryAfter(delay) => {
+            tracking_data.last_error = delivery_result
+                .err()
+                .map(|error| error.current_context().to_string());
+            let current_time = common_utils::date_time::now();
+            let retry_count = process.retry_count + 1;
+            db.update_process_tracker(
+                process,
+                storage::ProcessTrackerUpdate::Update {
+                    name: None,
+                    retry_count: Some(retry_count),
+                    schedule_time: Some(current_time.saturating_add(delay)),
+                    tracking_data: Some(
+                        Encode::<OutgoingWebhookTrackingData>::encode_to_value(&tracking_data)
+                            .change_context(
+                                errors::WebhooksFlowError::OutgoingWebhookEncodingFailed,
+                            )?,
+                    ),
+                    business_status: None,
+                    status: Some(enums::ProcessTrackerStatus::Pending),
+                    updated_at: Some(current_time),
+                },
+            )
+            .await?;
+            Ok(())
+        }
+        OutgoingWebhookRetryAction::RetriesExceeded => {
+            process
+                .finish_with_status(db, "RETRIES_EXCEEDED".to_string())
+                .await
+        }
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn webhooks_core(
     state: &AppState,
@@ -212,12 +809,7 @@ pub async fn webhooks_core(
     let connector = connector.connector;
 
     let source_verified = connector
-        .verify_webhook_source(
-            &*state.store,
-            req.headers(),
-            &body,
-            &merchant_account.merchant_id,
-        )
+        .verify_webhook_source(state, req.headers(), &body, &merchant_account.merchant_id)
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("There was an issue in incoming webhook source verification")?;
@@ -247,40 +839,117 @@ pub async fn webhooks_core(
     .await;
 
     if process_webhook_further {
-        let object_ref_id = connector
-            .get_webhook_object_reference_id(&decoded_body)
+        let dedup_key = connector
+            .get_webhook_event_id(&decoded_body)
             .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Could not find object reference id in incoming webhook body")?;
+            .attach_printable("Could not find event id in incoming webhook body")?
+            .map(|event_id| {
+                utils::get_webhook_dedup_key(
+                    &merchant_account.merchant_id,
+                    connector_name,
+                    &event_id,
+                )
+            });
 
-        let event_object = connector
-            .get_webhook_resource_object(&decoded_body)
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Could not find resource object in incoming webhook body")?;
+        if let Some(dedup_key) = &dedup_key {
+            match utils::claim_webhook(&*state.store, dedup_key).await {
+                utils::WebhookDedupStatus::New => {}
+                // Not acknowledged, so that the connector redelivers it if the processing fails
+                utils::WebhookDedupStatus::InFlight => {
+                    logger::info!(%dedup_key, "Incoming webhook is already being processed");
+                    return Err(errors::ApiErrorResponse::IdempotentRequestInProgress.into());
+                }
+                utils::WebhookDedupStatus::Processed => {
+                    logger::info!(%dedup_key, "Skipping incoming webhook that was already processed");
+                    return connector
+                        .get_webhook_api_response()
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable(
+                            "Could not get incoming webhook api response from connector",
+                        );
+                }
+            }
+        }
 
-        let webhook_details = api::IncomingWebhookDetails {
-            object_reference_id: object_ref_id,
-            resource_object: Encode::<serde_json::Value>::encode_to_vec(&event_object)
+        let webhook_flow_result: errors::RouterResult<()> = async {
+            let object_ref_id = connector
+                .get_webhook_object_reference_id(&decoded_body)
                 .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable(
-                    "There was an issue when encoding the incoming webhook body to bytes",
-                )?,
-        };
+                .attach_printable("Could not find object reference id in incoming webhook body")?;
 
-        let flow_type: api::WebhookFlow = event_type.into();
-        match flow_type {
-            api::WebhookFlow::Payment => payments_incoming_webhook_flow(
-                state.clone(),
-                merchant_account,
-                webhook_details,
-                source_verified,
-            )
-            .await
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Incoming webhook flow for payments failed")?,
-            _ => Err(errors::ApiErrorResponse::InternalServerError)
-                .into_report()
-                .attach_printable("Unsupported Flow Type received in incoming webhooks")?,
+            let event_object = connector
+                .get_webhook_resource_object(&decoded_body)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Could not find resource object in incoming webhook body")?;
+
+            let webhook_details = api::IncomingWebhookDetails {
+                object_reference_id: object_ref_id,
+                resource_object: Encode::<serde_json::Value>::encode_to_vec(&event_object)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable(
+                        "There was an issue when encoding the incoming webhook body to bytes",
+                    )?,
+            };
+
+            let flow_type: api::WebhookFlow = event_type.clone().into();
+            match flow_type {
+                api::WebhookFlow::Payment => payments_incoming_webhook_flow(
+                    state.clone(),
+                    merchant_account,
+                    connector_name,
+                    webhook_details,
+                    source_verified,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Incoming webhook flow for payments failed")?,
+                api::WebhookFlow::Refund => {
+                    refunds_incoming_webhook_flow(
+                        state,
+                        merchant_account,
+                        connector_name,
+                        webhook_details,
+                        source_verified,
+                        &event_type,
+                    )
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Incoming webhook flow for refunds failed")?;
+                }
+                api::WebhookFlow::Dispute => {
+                    let dispute_details = connector
+                        .get_dispute_details(&decoded_body)
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable(
+                            "Could not find dispute details in incoming webhook body",
+                        )?;
+                    disputes_incoming_webhook_flow(
+                        &*state.store,
+                        &merchant_account,
+                        connector_name,
+                        dispute_details,
+                        &event_type,
+                    )
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Incoming webhook flow for disputes failed")?;
+                }
+                _ => Err(errors::ApiErrorResponse::InternalServerError)
+                    .into_report()
+                    .attach_printable("Unsupported Flow Type received in incoming webhooks")?,
+            }
+
+            Ok(())
         }
+        .await;
+
+        if let Some(dedup_key) = &dedup_key {
+            match &webhook_flow_result {
+                Ok(()) => utils::mark_webhook_processed(&*state.store, dedup_key).await,
+                Err(_) => utils::release_webhook_dedup_key(&*state.store, dedup_key).await,
+            }
+        }
+        webhook_flow_result?;
     }
 
     let response = connector
@@ -290,3 +959,701 @@ pub async fn webhooks_core(
 
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::panic)]
+
+    use common_utils::crypto::{self, SignMessage, VerifySignature};
+
+    use super::{
+        add_outgoing_webhook_retry_task, disputes_incoming_webhook_flow,
+        get_outgoing_webhook_retry_action, list_webhook_delivery_attempts,
+        record_webhook_delivery_attempt, redeliver_outgoing_webhook, refunds_incoming_webhook_flow,
+        sign_outgoing_webhook, start_outgoing_webhook_retry_workflow, trigger_webhook_to_merchant,
+        utils, webhooks_core, OutgoingWebhookRetryAction, OutgoingWebhookTrackingData,
+        OUTGOING_WEBHOOK_RETRY_RUNNER, OUTGOING_WEBHOOK_RETRY_TASK,
+    };
+    use crate::{
+        configs::settings::WebhookSignatureAlgorithm,
+        core::{connector_credentials, errors, payments},
+        db::{StorageImpl, StorageInterface},
+        routes,
+        scheduler::utils as pt_utils,
+        services,
+        types::{
+            api,
+            storage::{self, enums},
+            transformers::ForeignInto,
+        },
+    };
+
+    #[test]
+    fn test_outgoing_webhook_signature_verifies_with_same_secret() {
+        let secret = b"merchant_hash_key";
+        let body = br#"{"merchant_id":"merchant_1","event_id":"evt_1"}"#;
+
+        let signature = sign_outgoing_webhook(WebhookSignatureAlgorithm::HmacSha256, secret, body)
+            .expect("Failed to sign outgoing webhook");
+        let signature = hex::decode(signature).expect("Signature is not hex encoded");
+
+        assert!(crypto::HmacSha256
+            .verify_signature(secret, &signature, body)
+            .expect("Failed to verify signature"));
+        assert!(!crypto::HmacSha256
+            .verify_signature(b"other_secret", &signature, body)
+            .expect("Failed to verify signature"));
+    }
+
+    #[test]
+    fn test_outgoing_webhook_signature_uses_configured_algorithm() {
+        let secret = b"merchant_hash_key";
+        let body = br#"{"merchant_id":"merchant_1","event_id":"evt_1"}"#;
+
+        let signature = sign_outgoing_webhook(WebhookSignatureAlgorithm::HmacSha512, secret, body)
+            .expect("Failed to sign outgoing webhook");
+        let signature = hex::decode(signature).expect("Signature is not hex encoded");
+
+        assert!(crypto::HmacSha512
+            .verify_signature(secret, &signature, body)
+            .expect("Failed to verify signature"));
+    }
+
+    #[test]
+    fn test_outgoing_webhook_delivered_on_second_scheduled_attempt() {
+        // The first scheduled attempt fails, the retry is pushed to the next delay
+        let mut retry_count = 0;
+        assert_eq!(
+            get_outgoing_webhook_retry_action(retry_count, false),
+            OutgoingWebhookRetryAction::RetryAfter(time::Duration::minutes(5))
+        );
+        retry_count += 1;
+
+        // The second scheduled attempt succeeds and completes the task
+        assert_eq!(
+            get_outgoing_webhook_retry_action(retry_count, true),
+            OutgoingWebhookRetryAction::Completed
+        );
+    }
+
+    #[test]
+    fn test_outgoing_webhook_retries_are_capped() {
+        assert_eq!(
+            get_outgoing_webhook_retry_action(2, false),
+            OutgoingWebhookRetryAction::RetryAfter(time::Duration::hours(2))
+        );
+        assert_eq!(
+            get_outgoing_webhook_retry_action(3, false),
+            OutgoingWebhookRetryAction::RetriesExceeded
+        );
+    }
+
+    /// Inserts a Stripe refund in `refund_status`, with `re_123` as its connector refund id.
+    async fn create_refund(
+        db: &dyn StorageInterface,
+        refund_status: enums::RefundStatus,
+    ) -> storage::MerchantAccount {
+        let merchant_account = db
+            .insert_merchant(storage::MerchantAccountNew {
+                merchant_id: "merchant_1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to insert merchant account");
+        db.insert_refund(
+            storage::RefundNew {
+                refund_id: "ref_1".to_string(),
+                payment_id: "pay_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                attempt_id: "attempt_1".to_string(),
+                connector: "stripe".to_string(),
+                connector_transaction_id: "pi_123".to_string(),
+                connector_refund_id: Some("re_123".to_string()),
+                refund_status,
+                sent_to_gateway: true,
+                ..Default::default()
+            },
+            enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .expect("Failed to insert refund");
+        merchant_account
+    }
+
+    async fn ingest_verified_refund_webhook(
+        state: &routes::AppState,
+        merchant_account: storage::MerchantAccount,
+        event_type: api::IncomingWebhookEvent,
+    ) -> storage::Refund {
+        refunds_incoming_webhook_flow(
+            state,
+            merchant_account,
+            "stripe",
+            api::IncomingWebhookDetails {
+                object_reference_id: "re_123".to_string(),
+                resource_object: Vec::new(),
+            },
+            true,
+            &event_type,
+        )
+        .await
+        .expect("Failed to ingest refund webhook")
+    }
+
+    #[actix_rt::test]
+    async fn test_verified_refund_webhook_updates_the_refund() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let merchant_account = create_refund(&*state.store, enums::RefundStatus::Pending).await;
+
+        let refund = ingest_verified_refund_webhook(
+            &state,
+            merchant_account,
+            api::IncomingWebhookEvent::RefundSuccess,
+        )
+        .await;
+
+        assert_eq!(refund.refund_id, "ref_1");
+        assert_eq!(refund.refund_status, enums::RefundStatus::Success);
+    }
+
+    #[actix_rt::test]
+    async fn test_refund_webhook_does_not_change_a_completed_refund() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let merchant_account = create_refund(&*state.store, enums::RefundStatus::Success).await;
+
+        let refund = ingest_verified_refund_webhook(
+            &state,
+            merchant_account.clone(),
+            api::IncomingWebhookEvent::RefundFailure,
+        )
+        .await;
+        assert_eq!(refund.refund_status, enums::RefundStatus::Success);
+
+        let refund = state
+            .store
+            .find_refund_by_merchant_id_refund_id(
+                &merchant_account.merchant_id,
+                "ref_1",
+                merchant_account.storage_scheme,
+            )
+            .await
+            .expect("Failed to find refund");
+        assert_eq!(refund.refund_status, enums::RefundStatus::Success);
+    }
+
+    /// Inserts a Stripe payment, with `pi_123` as its connector transaction id, for disputes to
+    /// refer to.
+    async fn create_disputed_payment(db: &dyn StorageInterface) -> storage::MerchantAccount {
+        let mut payment =
+            payments::helpers::get_test_payment("pay_1", "stripe", enums::AttemptStatus::Charged);
+        let connector_transaction_id = Some("pi_123".to_string());
+        payment.connector_response.connector_transaction_id = connector_transaction_id.clone();
+        payment.payment_attempt.connector_transaction_id = connector_transaction_id;
+        let (merchant_account, _) = payments::helpers::insert_test_payment(db, payment).await;
+        merchant_account.expect("Merchant account was not inserted")
+    }
+
+    #[actix_rt::test]
+    async fn test_dispute_moves_from_opened_to_won() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let db = &*state.store;
+        let merchant_account = create_disputed_payment(db).await;
+
+        let get_dispute_details = |connector_reason_code: Option<&str>| api::DisputePayload {
+            connector_dispute_id: "dp_123".to_string(),
+            connector_transaction_id: "pi_123".to_string(),
+            connector_reason_code: connector_reason_code.map(ToString::to_string),
+            amount: 1000,
+            currency: "USD".to_string(),
+        };
+
+        let opened_dispute = disputes_incoming_webhook_flow(
+            db,
+            &merchant_account,
+            "stripe",
+            get_dispute_details(Some("fraudulent")),
+            &api::IncomingWebhookEvent::DisputeOpened,
+        )
+        .await
+        .expect("Failed to ingest opened dispute");
+        assert_eq!(opened_dispute.dispute_status, enums::DisputeStatus::Opened);
+        assert_eq!(opened_dispute.payment_id, "pay_1");
+        assert_eq!(opened_dispute.attempt_id, "pay_1_1");
+
+        let won_dispute = disputes_incoming_webhook_flow(
+            db,
+            &merchant_account,
+            "stripe",
+            get_dispute_details(Some("fraudulent")),
+            &api::IncomingWebhookEvent::DisputeWon,
+        )
+        .await
+        .expect("Failed to ingest won dispute");
+        assert_eq!(won_dispute.dispute_id, opened_dispute.dispute_id);
+
+        // An opened event delivered after the dispute is won must not reopen it
+        let late_dispute = disputes_incoming_webhook_flow(
+            db,
+            &merchant_account,
+            "stripe",
+            get_dispute_details(None),
+            &api::IncomingWebhookEvent::DisputeOpened,
+        )
+        .await
+        .expect("Failed to ingest late opened dispute");
+        assert_eq!(late_dispute.dispute_status, enums::DisputeStatus::Won);
+
+        let stored_dispute = db
+            .find_dispute_by_merchant_id_connector_dispute_id("merchant_1", "stripe", "dp_123")
+            .await
+            .expect("Failed to fetch dispute")
+            .expect("Dispute was not stored");
+        assert_eq!(stored_dispute.dispute_status, enums::DisputeStatus::Won);
+        assert_eq!(
+            stored_dispute.connector_reason_code.as_deref(),
+            Some("fraudulent")
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_webhook_delivery_attempts_are_listed_per_payment() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let db = &*state.store;
+        let merchant_account = db
+            .insert_merchant(storage::MerchantAccountNew {
+                merchant_id: "merchant_1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to insert merchant account");
+        let get_tracking_data = |event_id: &str, payment_id: &str| OutgoingWebhookTrackingData {
+            merchant_id: "merchant_1".to_string(),
+            event_id: event_id.to_string(),
+            event_type: enums::EventType::PaymentSucceeded,
+            primary_object_id: payment_id.to_string(),
+            webhook: serde_json::json!({}),
+            last_error: None,
+        };
+        let webhook_url = "https://merchant.example.com/webhooks";
+
+        // The first attempt is rejected by the merchant, the retry is delivered
+        let tracking_data = get_tracking_data("evt_1", "pay_1");
+        record_webhook_delivery_attempt(
+            db,
+            &tracking_data,
+            webhook_url.to_string(),
+            1,
+            &Err(error_stack::report!(
+                errors::WebhooksFlowError::NotReceivedByMerchant { status_code: 500 }
+            )),
+        )
+        .await;
+        record_webhook_delivery_attempt(db, &tracking_data, webhook_url.to_string(), 2, &Ok(200))
+            .await;
+        // Webhook of another payment
+        record_webhook_delivery_attempt(
+            db,
+            &get_tracking_data("evt_2", "pay_2"),
+            webhook_url.to_string(),
+            1,
+            &Ok(200),
+        )
+        .await;
+
+        let response = list_webhook_delivery_attempts(
+            db,
+            merchant_account,
+            api::WebhookDeliveryAttemptListRequest {
+                payment_id: Some("pay_1".to_string()),
+                limit: None,
+            },
+        )
+        .await
+        .expect("Failed to list webhook delivery attempts");
+        let attempts = match response {
+            services::ApplicationResponse::Json(response) => response.data,
+            _ => panic!("Unexpected response"),
+        };
+
+        assert_eq!(attempts.len(), 2);
+        assert!(attempts.iter().all(|attempt| attempt.event_id == "evt_1"
+            && attempt.object_id == "pay_1"
+            && attempt.webhook_url == webhook_url));
+        // Most recent attempt first
+        assert_eq!(attempts[0].attempt_number, 2);
+        assert_eq!(attempts[0].status_code, Some(200));
+        assert_eq!(attempts[0].error_message, None);
+        assert_eq!(attempts[1].attempt_number, 1);
+        assert_eq!(attempts[1].status_code, Some(500));
+        assert!(attempts[1].error_message.is_some());
+    }
+
+    #[actix_rt::test]
+    async fn test_failed_webhook_is_redelivered() {
+        let merchant_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/webhooks"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&merchant_server)
+            .await;
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let db = &*state.store;
+        let webhook_url = format!("{}/webhooks", merchant_server.uri());
+        db.insert_merchant(storage::MerchantAccountNew {
+            merchant_id: "merchant_1".to_string(),
+            webhook_details: Some(serde_json::json!({ "webhook_url": webhook_url })),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to insert merchant account");
+
+        // The first delivery was rejected by the merchant and is waiting to be retried
+        let tracking_data = OutgoingWebhookTrackingData {
+            merchant_id: "merchant_1".to_string(),
+            event_id: "evt_1".to_string(),
+            event_type: enums::EventType::PaymentSucceeded,
+            primary_object_id: "pay_1".to_string(),
+            webhook: serde_json::json!({ "event_id": "evt_1" }),
+            last_error: None,
+        };
+        record_webhook_delivery_attempt(
+            db,
+            &tracking_data,
+            webhook_url.clone(),
+            1,
+            &Err(error_stack::report!(
+                errors::WebhooksFlowError::NotReceivedByMerchant { status_code: 500 }
+            )),
+        )
+        .await;
+        add_outgoing_webhook_retry_task(db, &tracking_data)
+            .await
+            .expect("Failed to schedule webhook retry");
+
+        let response = redeliver_outgoing_webhook(&state, "merchant_1", "evt_1")
+            .await
+            .expect("Failed to redeliver webhook");
+        let attempt = match response {
+            services::ApplicationResponse::Json(response) => response,
+            _ => panic!("Unexpected response"),
+        };
+
+        assert_eq!(attempt.event_id, "evt_1");
+        assert_eq!(attempt.webhook_url, webhook_url);
+        assert_eq!(attempt.attempt_number, 2);
+        assert_eq!(attempt.status_code, Some(200));
+        assert_eq!(attempt.error_message, None);
+
+        let process = db
+            .find_process_by_id(&pt_utils::get_process_tracker_id(
+                OUTGOING_WEBHOOK_RETRY_RUNNER,
+                OUTGOING_WEBHOOK_RETRY_TASK,
+                "evt_1",
+                "merchant_1",
+            ))
+            .await
+            .expect("Failed to fetch webhook retry task")
+            .expect("Webhook retry task was not stored");
+        assert_eq!(process.status, enums::ProcessTrackerStatus::Finish);
+    }
+
+    #[actix_rt::test]
+    async fn test_rejected_webhook_is_delivered_by_the_retry_task() {
+        let merchant_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/webhooks"))
+            .respond_with(wiremock::ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&merchant_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/webhooks"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&merchant_server)
+            .await;
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let db = &*state.store;
+        let merchant_account = db
+            .insert_merchant(storage::MerchantAccountNew {
+                merchant_id: "merchant_1".to_string(),
+                webhook_details: Some(serde_json::json!({
+                    "webhook_url": format!("{}/webhooks", merchant_server.uri()),
+                })),
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to insert merchant account");
+        let event = storage::Event {
+            id: 1,
+            event_id: "evt_1".to_string(),
+            event_type: enums::EventType::PaymentSucceeded,
+            event_class: enums::EventClass::Payments,
+            is_webhook_notified: false,
+            intent_reference_id: None,
+            primary_object_id: "pay_1".to_string(),
+            primary_object_type: enums::EventObjectType::PaymentDetails,
+            created_at: common_utils::date_time::now(),
+        };
+        let webhook = api::OutgoingWebhook {
+            merchant_id: "merchant_1".to_string(),
+            event_id: event.event_id.clone(),
+            event_type: event.event_type.foreign_into(),
+            content: api::OutgoingWebhookContent::PaymentDetails(api::PaymentsResponse {
+                payment_id: Some("pay_1".to_string()),
+                ..Default::default()
+            }),
+            timestamp: event.created_at,
+        };
+
+        // The merchant rejects the first delivery, which schedules a retry
+        let result = trigger_webhook_to_merchant(
+            merchant_account,
+            event,
+            webhook,
+            state.store.clone(),
+            &state.conf.webhooks,
+        )
+        .await;
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::WebhooksFlowError::NotReceivedByMerchant { status_code: 500 })
+        ));
+        let process_tracker_id = pt_utils::get_process_tracker_id(
+            OUTGOING_WEBHOOK_RETRY_RUNNER,
+            OUTGOING_WEBHOOK_RETRY_TASK,
+            "evt_1",
+            "merchant_1",
+        );
+        let process = db
+            .find_process_by_id(&process_tracker_id)
+            .await
+            .expect("Failed to fetch webhook retry task")
+            .expect("Webhook retry task was not scheduled");
+
+        // The scheduled retry is accepted by the merchant
+        start_outgoing_webhook_retry_workflow(&state, process)
+            .await
+            .expect("Failed to retry webhook");
+        let process = db
+            .find_process_by_id(&process_tracker_id)
+            .await
+            .expect("Failed to fetch webhook retry task")
+            .expect("Webhook retry task was not stored");
+        assert_eq!(process.status, enums::ProcessTrackerStatus::Finish);
+
+        let deliveries = merchant_server
+            .received_requests()
+            .await
+            .expect("Requests are not recorded");
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].body, deliveries[1].body);
+
+        let attempts = db
+            .find_webhook_delivery_attempts_by_merchant_id_primary_object_id(
+                "merchant_1",
+                "pay_1",
+                10,
+            )
+            .await
+            .expect("Failed to fetch webhook delivery attempts");
+        assert_eq!(attempts.len(), 2);
+        // Most recent attempt first
+        assert_eq!(attempts[0].attempt_number, 2);
+        assert_eq!(attempts[0].status_code, Some(200));
+        assert_eq!(attempts[1].attempt_number, 1);
+        assert_eq!(attempts[1].status_code, Some(500));
+    }
+
+    #[actix_rt::test]
+    async fn test_delivered_webhook_cannot_be_redelivered() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        state
+            .store
+            .insert_merchant(storage::MerchantAccountNew {
+                merchant_id: "merchant_1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to insert merchant account");
+
+        let result = redeliver_outgoing_webhook(&state, "merchant_1", "evt_1").await;
+
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::WebhookEventNotFound)
+        ));
+    }
+
+    fn get_stripe_dispute_webhook(event_id: &str, event_type: &str, status: &str) -> Vec<u8> {
+        serde_json::json!({
+            "id": event_id,
+            "type": event_type,
+            "data": {
+                "object": {
+                    "id": "dp_123",
+                    "object": "dispute",
+                    "amount": 1000,
+                    "currency": "usd",
+                    "payment_intent": "pi_123",
+                    "reason": "fraudulent",
+                    "status": status,
+                }
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    async fn deliver_stripe_webhook(
+        state: &routes::AppState,
+        merchant_account: &storage::MerchantAccount,
+        body: Vec<u8>,
+    ) -> errors::RouterResponse<serde_json::Value> {
+        let timestamp = "1672531200";
+        let message = [format!("{timestamp}.").as_bytes(), &body].concat();
+        let signature = crypto::HmacSha256
+            .sign_message(b"whsec_test", &message)
+            .expect("Failed to sign webhook");
+        let req = actix_web::test::TestRequest::default()
+            .insert_header((
+                "Stripe-Signature",
+                format!("t={timestamp},v1={}", hex::encode(signature)),
+            ))
+            .to_http_request();
+        webhooks_core(
+            state,
+            &req,
+            merchant_account.clone(),
+            "stripe",
+            actix_web::web::Bytes::from(body),
+        )
+        .await
+    }
+
+    async fn find_dispute_status(state: &routes::AppState) -> enums::DisputeStatus {
+        state
+            .store
+            .find_dispute_by_merchant_id_connector_dispute_id("merchant_1", "stripe", "dp_123")
+            .await
+            .expect("Failed to fetch dispute")
+            .expect("Dispute was not stored")
+            .dispute_status
+    }
+
+    #[actix_rt::test]
+    async fn test_duplicate_webhook_is_processed_once() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let db = &*state.store;
+        let merchant_account = create_disputed_payment(db).await;
+        connector_credentials::store_webhook_source_verification_secret(
+            &state,
+            "merchant_1",
+            "stripe",
+            b"whsec_test",
+        )
+        .await
+        .expect("Failed to store webhook secret");
+
+        // Event ids are unique per run, the dedup keys are kept in Redis
+        let opened_event_id = format!("evt_{}", uuid::Uuid::new_v4());
+        deliver_stripe_webhook(
+            &state,
+            &merchant_account,
+            get_stripe_dispute_webhook(
+                &opened_event_id,
+                "charge.dispute.created",
+                "needs_response",
+            ),
+        )
+        .await
+        .expect("Failed to process webhook");
+        assert_eq!(
+            find_dispute_status(&state).await,
+            enums::DisputeStatus::Opened
+        );
+
+        // A redelivery of the event is acknowledged without being processed, it would
+        // otherwise close the dispute
+        deliver_stripe_webhook(
+            &state,
+            &merchant_account,
+            get_stripe_dispute_webhook(&opened_event_id, "charge.dispute.closed", "won"),
+        )
+        .await
+        .expect("Failed to acknowledge redelivered webhook");
+        assert_eq!(
+            find_dispute_status(&state).await,
+            enums::DisputeStatus::Opened
+        );
+
+        // A redelivery received while the event is being processed is not acknowledged, so that
+        // the connector delivers it again if the processing fails
+        let closed_event_id = format!("evt_{}", uuid::Uuid::new_v4());
+        let dedup_key = utils::get_webhook_dedup_key("merchant_1", "stripe", &closed_event_id);
+        assert_eq!(
+            utils::claim_webhook(db, &dedup_key).await,
+            utils::WebhookDedupStatus::New
+        );
+        let result = deliver_stripe_webhook(
+            &state,
+            &merchant_account,
+            get_stripe_dispute_webhook(&closed_event_id, "charge.dispute.closed", "won"),
+        )
+        .await;
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ApiErrorResponse::IdempotentRequestInProgress)
+        ));
+        assert_eq!(
+            find_dispute_status(&state).await,
+            enums::DisputeStatus::Opened
+        );
+
+        utils::release_webhook_dedup_key(db, &dedup_key).await;
+        deliver_stripe_webhook(
+            &state,
+            &merchant_account,
+            get_stripe_dispute_webhook(&closed_event_id, "charge.dispute.closed", "won"),
+        )
+        .await
+        .expect("Failed to process webhook");
+        assert_eq!(find_dispute_status(&state).await, enums::DisputeStatus::Won);
+        assert_eq!(
+            utils::claim_webhook(db, &dedup_key).await,
+            utils::WebhookDedupStatus::Processed
+        );
+    }
+}
@@ -1,10 +1,56 @@
+use error_stack::{IntoReport, ResultExt};
+use redis_interface::SetnxReply;
+use router_env::{instrument, tracing};
+
 use crate::{
+    core::errors::{self, CustomResult},
     db::{get_and_deserialize_key, StorageInterface},
-    types::api,
+    logger,
+    types::{api, storage},
 };
 
+/// Our object that an incoming webhook refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookObjectId {
+    Payment {
+        payment_id: String,
+    },
+    Refund {
+        payment_id: String,
+        refund_id: String,
+    },
+}
+
+/// Window within which a redelivered webhook is treated as a duplicate.
+const INCOMING_WEBHOOK_DEDUP_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Window within which a webhook being processed is not processed again. Webhooks whose
+/// processing was interrupted are processed again when redelivered after it.
+const INCOMING_WEBHOOK_IN_FLIGHT_TTL_SECS: i64 = 5 * 60;
+
+const WEBHOOK_IN_FLIGHT: &str = "in_flight";
+const WEBHOOK_PROCESSED: &str = "processed";
+
+/// Whether an incoming webhook was already received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookDedupStatus {
+    /// First delivery of the webhook, which is now claimed for processing.
+    New,
+    /// An earlier delivery of the webhook is still being processed.
+    InFlight,
+    /// An earlier delivery of the webhook was processed.
+    Processed,
+}
+
 fn default_webhook_config() -> api::MerchantWebhookConfig {
-    std::collections::HashSet::from([api::IncomingWebhookEvent::PaymentIntentSuccess])
+    std::collections::HashSet::from([
+        api::IncomingWebhookEvent::PaymentIntentSuccess,
+        api::IncomingWebhookEvent::RefundSuccess,
+        api::IncomingWebhookEvent::RefundFailure,
+        api::IncomingWebhookEvent::DisputeOpened,
+        api::IncomingWebhookEvent::DisputeWon,
+        api::IncomingWebhookEvent::DisputeLost,
+    ])
 }
 
 pub async fn lookup_webhook_event(
@@ -22,3 +68,209 @@ pub async fn lookup_webhook_event(
 
     webhook_config.contains(event)
 }
+
+pub fn get_webhook_dedup_key(merchant_id: &str, connector_id: &str, event_id: &str) -> String {
+    format!("whdedup_{merchant_id}_{connector_id}_{event_id}")
+}
+
+/// Claims the webhook for processing unless it was already received earlier.
+/// Webhooks are processed when Redis is unavailable, to avoid dropping them.
+pub async fn claim_webhook(db: &dyn StorageInterface, dedup_key: &str) -> WebhookDedupStatus {
+    match db
+        .set_key_if_not_exists_with_expiry(
+            dedup_key,
+            WEBHOOK_IN_FLIGHT,
+            INCOMING_WEBHOOK_IN_FLIGHT_TTL_SECS,
+        )
+        .await
+    {
+        Ok(SetnxReply::KeySet) => WebhookDedupStatus::New,
+        Ok(SetnxReply::KeyNotSet) => match db.get_key(dedup_key).await {
+            Ok(value) if value == WEBHOOK_PROCESSED.as_bytes() => WebhookDedupStatus::Processed,
+            Ok(_) => WebhookDedupStatus::InFlight,
+            // The connector redelivers the webhook, which is then claimed or skipped
+            Err(error) => {
+                logger::error!(error=?error.current_context(), %dedup_key, "Failed to read webhook dedup key");
+                WebhookDedupStatus::InFlight
+            }
+        },
+        Err(error) => {
+            logger::error!(error=?error.current_context(), %dedup_key, "Failed to check for duplicate webhook");
+            WebhookDedupStatus::New
+        }
+    }
+}
+
+/// Records that the webhook was processed, so that its redeliveries are skipped.
+pub async fn mark_webhook_processed(db: &dyn StorageInterface, dedup_key: &str) {
+    if let Err(error) = db
+        .set_key_with_expiry(
+            dedup_key,
+            WEBHOOK_PROCESSED,
+            INCOMING_WEBHOOK_DEDUP_TTL_SECS,
+        )
+        .await
+    {
+        logger::error!(error=?error.current_context(), %dedup_key, "Failed to mark webhook as processed");
+    }
+}
+
+/// Releases the dedup key so that the connector's redelivery of a failed webhook is processed.
+pub async fn release_webhook_dedup_key(db: &dyn StorageInterface, dedup_key: &str) {
+    if let Err(error) = db.delete_key(dedup_key).await {
+        logger::error!(error=?error.current_context(), %dedup_key, "Failed to release webhook dedup key");
+    }
+}
+
+/// Maps the object reference id of a connector webhook, which is the connector's id of the
+/// payment or refund, back to the payment or refund of the merchant it refers to.
+#[instrument(skip_all)]
+pub async fn find_webhook_object_id(
+    db: &dyn StorageInterface,
+    merchant_account: &storage::MerchantAccount,
+    connector_name: &str,
+    flow: &api::WebhookFlow,
+    object_reference_id: &str,
+) -> CustomResult<WebhookObjectId, errors::WebhooksFlowError> {
+    match flow {
+        api::WebhookFlow::Payment => {
+            let payment_attempt = db
+                .find_payment_attempt_by_merchant_id_connector_txn_id(
+                    &merchant_account.merchant_id,
+                    object_reference_id,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .change_context(errors::WebhooksFlowError::WebhookObjectNotFound)
+                .attach_printable_lazy(|| {
+                    format!("No payment found for connector transaction {object_reference_id}")
+                })?;
+            // Transaction ids are only unique per connector
+            if payment_attempt.connector.as_deref() != Some(connector_name) {
+                return Err(errors::WebhooksFlowError::WebhookObjectNotFound)
+                    .into_report()
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "Connector transaction {object_reference_id} belongs to a payment of another connector"
+                        )
+                    });
+            }
+            Ok(WebhookObjectId::Payment {
+                payment_id: payment_attempt.payment_id,
+            })
+        }
+        api::WebhookFlow::Refund => {
+            let refund = db
+                .find_refund_by_merchant_id_connector_refund_id_connector(
+                    &merchant_account.merchant_id,
+                    object_reference_id,
+                    connector_name,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .change_context(errors::WebhooksFlowError::WebhookObjectNotFound)
+                .attach_printable_lazy(|| {
+                    format!("No refund found for connector refund {object_reference_id}")
+                })?;
+            Ok(WebhookObjectId::Refund {
+                payment_id: refund.payment_id,
+                refund_id: refund.refund_id,
+            })
+        }
+        api::WebhookFlow::Dispute | api::WebhookFlow::Subscription => {
+            Err(errors::WebhooksFlowError::WebhookObjectNotFound)
+                .into_report()
+                .attach_printable("Only payment and refund webhooks refer to an object by its id")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{
+        connector,
+        core::payments,
+        db::StorageImpl,
+        routes,
+        types::{api::IncomingWebhook, storage::enums},
+    };
+
+    async fn create_payment_attempt(
+        db: &dyn StorageInterface,
+        connector_name: &str,
+    ) -> storage::MerchantAccount {
+        let mut payment = payments::helpers::get_test_payment(
+            "pay_1",
+            connector_name,
+            enums::AttemptStatus::Charged,
+        );
+        let connector_transaction_id = Some("pi_3MhGKt2eZvKYlo2C0v5Cy3Ob".to_string());
+        payment.connector_response.connector_transaction_id = connector_transaction_id.clone();
+        payment.payment_attempt.connector_transaction_id = connector_transaction_id;
+        let (merchant_account, _) = payments::helpers::insert_test_payment(db, payment).await;
+        merchant_account.expect("Merchant account was not inserted")
+    }
+
+    const STRIPE_PAYMENT_WEBHOOK: &[u8] = br#"{
+        "id": "evt_3MhGKt2eZvKYlo2C0SQ7g6cH",
+        "type": "payment_intent.succeeded",
+        "data": { "object": { "id": "pi_3MhGKt2eZvKYlo2C0v5Cy3Ob", "object": "payment_intent" } }
+    }"#;
+
+    #[actix_rt::test]
+    async fn test_payment_webhook_is_mapped_to_the_payment() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let merchant_account = create_payment_attempt(&*state.store, "stripe").await;
+
+        let object_reference_id = connector::Stripe
+            .get_webhook_object_reference_id(STRIPE_PAYMENT_WEBHOOK)
+            .expect("Failed to find object reference id");
+        let object_id = find_webhook_object_id(
+            &*state.store,
+            &merchant_account,
+            "stripe",
+            &api::WebhookFlow::Payment,
+            &object_reference_id,
+        )
+        .await
+        .expect("Failed to map webhook to payment");
+
+        assert_eq!(
+            object_id,
+            WebhookObjectId::Payment {
+                payment_id: "pay_1".to_string()
+            }
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_webhook_of_another_connector_is_not_mapped() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let merchant_account = create_payment_attempt(&*state.store, "adyen").await;
+
+        let error = find_webhook_object_id(
+            &*state.store,
+            &merchant_account,
+            "stripe",
+            &api::WebhookFlow::Payment,
+            "pi_3MhGKt2eZvKYlo2C0v5Cy3Ob",
+        )
+        .await
+        .expect_err("Mapped a webhook to a payment of another connector");
+        assert!(matches!(
+            error.current_context(),
+            errors::WebhooksFlowError::WebhookObjectNotFound
+        ));
+    }
+}
@@ -1,22 +1,30 @@
 pub mod address;
 pub mod api_keys;
 pub mod cache;
+pub mod circuit_breaker;
 pub mod configs;
 pub mod connector_response;
 pub mod customers;
+pub mod dispute;
 pub mod ephemeral_key;
 pub mod events;
+pub mod failed_payment_attempt;
+pub mod health_check;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
 pub mod merchant_connector_account;
+pub mod pagination;
 pub mod payment_attempt;
 pub mod payment_intent;
 pub mod payment_method;
 pub mod process_tracker;
 pub mod queue;
+pub mod rate_limiter;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod routing;
+pub mod webhook_delivery_attempt;
 
 use std::sync::Arc;
 
@@ -38,11 +46,15 @@ pub trait StorageInterface:
     + dyn_clone::DynClone
     + address::AddressInterface
     + api_keys::ApiKeyInterface
+    + circuit_breaker::CircuitBreakerInterface
     + configs::ConfigInterface
     + connector_response::ConnectorResponseInterface
     + customers::CustomerInterface
+    + dispute::DisputeInterface
     + ephemeral_key::EphemeralKeyInterface
     + events::EventInterface
+    + failed_payment_attempt::FailedPaymentAttemptInterface
+    + health_check::HealthCheckInterface
     + locker_mock_up::LockerMockUpInterface
     + mandate::MandateInterface
     + merchant_account::MerchantAccountInterface
@@ -53,8 +65,11 @@ pub trait StorageInterface:
     + payment_method::PaymentMethodInterface
     + process_tracker::ProcessTrackerInterface
     + queue::QueueInterface
+    + rate_limiter::RateLimiterInterface
     + refund::RefundInterface
     + reverse_lookup::ReverseLookupInterface
+    + routing::RoutingInterface
+    + webhook_delivery_attempt::WebhookDeliveryAttemptInterface
     + 'static
 {
     async fn close(&mut self) {}
@@ -74,13 +89,21 @@ impl StorageInterface for Store {
 #[derive(Clone)]
 pub struct MockDb {
     merchant_accounts: Arc<Mutex<Vec<storage::MerchantAccount>>>,
+    api_keys: Arc<Mutex<Vec<storage::ApiKey>>>,
+    configs: Arc<Mutex<Vec<storage::Config>>>,
     merchant_connector_accounts: Arc<Mutex<Vec<storage::MerchantConnectorAccount>>>,
     payment_attempts: Arc<Mutex<Vec<storage::PaymentAttempt>>>,
+    failed_payment_attempts: Arc<Mutex<Vec<storage::FailedPaymentAttempt>>>,
     payment_intents: Arc<Mutex<Vec<storage::PaymentIntent>>>,
     customers: Arc<Mutex<Vec<storage::Customer>>>,
     refunds: Arc<Mutex<Vec<storage::Refund>>>,
+    disputes: Arc<Mutex<Vec<storage::Dispute>>>,
+    mandates: Arc<Mutex<Vec<storage::Mandate>>>,
     processes: Arc<Mutex<Vec<storage::ProcessTracker>>>,
     connector_response: Arc<Mutex<Vec<storage::ConnectorResponse>>>,
+    webhook_delivery_attempts: Arc<Mutex<Vec<storage::WebhookDeliveryAttempt>>>,
+    payment_methods: Arc<Mutex<Vec<storage::PaymentMethod>>>,
+    locker_mock_ups: Arc<Mutex<Vec<storage::LockerMockUp>>>,
     redis: Arc<redis_interface::RedisConnectionPool>,
 }
 
@@ -88,13 +111,21 @@ impl MockDb {
     pub async fn new(redis: &crate::configs::settings::Settings) -> Self {
         Self {
             merchant_accounts: Default::default(),
+            api_keys: Default::default(),
+            configs: Default::default(),
             merchant_connector_accounts: Default::default(),
             payment_attempts: Default::default(),
+            failed_payment_attempts: Default::default(),
             payment_intents: Default::default(),
             customers: Default::default(),
             refunds: Default::default(),
+            disputes: Default::default(),
+            mandates: Default::default(),
             processes: Default::default(),
             connector_response: Default::default(),
+            webhook_delivery_attempts: Default::default(),
+            payment_methods: Default::default(),
+            locker_mock_ups: Default::default(),
             redis: Arc::new(crate::connection::redis_connection(redis).await),
         }
     }
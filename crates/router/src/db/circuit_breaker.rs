@@ -0,0 +1,303 @@
+use error_stack::ResultExt;
+use redis_interface::SetnxReply;
+
+use super::{MockDb, Store};
+use crate::{
+    core::errors::{self, CustomResult},
+    services::api::circuit_breaker::CircuitState,
+};
+
+/// Circuit breaker state of connectors, kept in Redis with atomic operations so that concurrent
+/// router instances do not overwrite each other's updates. Every key expires.
+#[async_trait::async_trait]
+pub trait CircuitBreakerInterface {
+    async fn find_circuit_state(
+        &self,
+        connector_name: &str,
+    ) -> CustomResult<CircuitState, errors::StorageError>;
+
+    /// Takes the probe token of a half-open circuit, returning whether it was free. The token is
+    /// released after `ttl_in_secs` if the probe never reports its outcome.
+    async fn acquire_circuit_probe(
+        &self,
+        connector_name: &str,
+        ttl_in_secs: i64,
+    ) -> CustomResult<bool, errors::StorageError>;
+
+    /// Counts a request of the window starting at `window_started_at`, returning the number of
+    /// requests and failures of the window.
+    async fn record_circuit_request(
+        &self,
+        connector_name: &str,
+        window_started_at: i64,
+        window_in_secs: i64,
+        success: bool,
+    ) -> CustomResult<(u32, u32), errors::StorageError>;
+
+    /// Opens the circuit for `cooldown_in_secs`, forgetting the requests of the window starting at
+    /// `window_started_at`. The circuit is then half-open for at most another cooldown, after
+    /// which it closes on its own if no probe reported its outcome.
+    async fn open_circuit(
+        &self,
+        connector_name: &str,
+        window_started_at: i64,
+        cooldown_in_secs: i64,
+    ) -> CustomResult<(), errors::StorageError>;
+
+    async fn close_circuit(&self, connector_name: &str) -> CustomResult<(), errors::StorageError>;
+}
+
+fn get_open_key(connector_name: &str) -> String {
+    format!("circuit_breaker_{connector_name}_open")
+}
+
+fn get_tripped_key(connector_name: &str) -> String {
+    format!("circuit_breaker_{connector_name}_tripped")
+}
+
+fn get_probe_key(connector_name: &str) -> String {
+    format!("circuit_breaker_{connector_name}_probe")
+}
+
+fn get_requests_key(connector_name: &str, window_started_at: i64) -> String {
+    format!("circuit_breaker_{connector_name}_{window_started_at}_requests")
+}
+
+fn get_failures_key(connector_name: &str, window_started_at: i64) -> String {
+    format!("circuit_breaker_{connector_name}_{window_started_at}_failures")
+}
+
+async fn key_exists(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    key: &str,
+) -> CustomResult<bool, errors::StorageError> {
+    redis_conn
+        .get_key::<Option<String>>(key)
+        .await
+        .map(|value| value.is_some())
+        .change_context(errors::StorageError::KVError)
+}
+
+async fn find_state(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    connector_name: &str,
+) -> CustomResult<CircuitState, errors::StorageError> {
+    if key_exists(redis_conn, &get_open_key(connector_name)).await? {
+        Ok(CircuitState::Open)
+    } else if key_exists(redis_conn, &get_tripped_key(connector_name)).await? {
+        Ok(CircuitState::HalfOpen)
+    } else {
+        Ok(CircuitState::Closed)
+    }
+}
+
+async fn acquire_probe(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    connector_name: &str,
+    ttl_in_secs: i64,
+) -> CustomResult<bool, errors::StorageError> {
+    redis_conn
+        .set_key_if_not_exists_with_expiry(&get_probe_key(connector_name), "1", ttl_in_secs)
+        .await
+        .map(|reply| reply == SetnxReply::KeySet)
+        .change_context(errors::StorageError::KVError)
+}
+
+async fn increment_window_count(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    key: &str,
+    window_in_secs: i64,
+) -> CustomResult<u32, errors::StorageError> {
+    let count = redis_conn
+        .increment_key(key)
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    // Counts are kept a little longer than their window, for requests that complete after it
+    redis_conn
+        .set_expiry(key, window_in_secs.saturating_mul(2))
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    Ok(u32::try_from(count).unwrap_or(u32::MAX))
+}
+
+async fn record_request(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    connector_name: &str,
+    window_started_at: i64,
+    window_in_secs: i64,
+    success: bool,
+) -> CustomResult<(u32, u32), errors::StorageError> {
+    let requests = increment_window_count(
+        redis_conn,
+        &get_requests_key(connector_name, window_started_at),
+        window_in_secs,
+    )
+    .await?;
+    let failures_key = get_failures_key(connector_name, window_started_at);
+    let failures = if success {
+        redis_conn
+            .get_key::<Option<i64>>(&failures_key)
+            .await
+            .change_context(errors::StorageError::KVError)?
+            .map_or(0, |failures| u32::try_from(failures).unwrap_or(u32::MAX))
+    } else {
+        increment_window_count(redis_conn, &failures_key, window_in_secs).await?
+    };
+    Ok((requests, failures))
+}
+
+async fn open(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    connector_name: &str,
+    window_started_at: i64,
+    cooldown_in_secs: i64,
+) -> CustomResult<(), errors::StorageError> {
+    redis_conn
+        .set_key_with_expiry(&get_open_key(connector_name), "1", cooldown_in_secs)
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    redis_conn
+        .set_key_with_expiry(
+            &get_tripped_key(connector_name),
+            "1",
+            cooldown_in_secs.saturating_mul(2),
+        )
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    for key in [
+        get_probe_key(connector_name),
+        get_requests_key(connector_name, window_started_at),
+        get_failures_key(connector_name, window_started_at),
+    ] {
+        redis_conn
+            .delete_key(&key)
+            .await
+            .change_context(errors::StorageError::KVError)?;
+    }
+    Ok(())
+}
+
+async fn close(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    connector_name: &str,
+) -> CustomResult<(), errors::StorageError> {
+    for key in [
+        get_open_key(connector_name),
+        get_tripped_key(connector_name),
+        get_probe_key(connector_name),
+    ] {
+        redis_conn
+            .delete_key(&key)
+            .await
+            .change_context(errors::StorageError::KVError)?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl CircuitBreakerInterface for Store {
+    async fn find_circuit_state(
+        &self,
+        connector_name: &str,
+    ) -> CustomResult<CircuitState, errors::StorageError> {
+        find_state(&self.redis_conn, connector_name).await
+    }
+
+    async fn acquire_circuit_probe(
+        &self,
+        connector_name: &str,
+        ttl_in_secs: i64,
+    ) -> CustomResult<bool, errors::StorageError> {
+        acquire_probe(&self.redis_conn, connector_name, ttl_in_secs).await
+    }
+
+    async fn record_circuit_request(
+        &self,
+        connector_name: &str,
+        window_started_at: i64,
+        window_in_secs: i64,
+        success: bool,
+    ) -> CustomResult<(u32, u32), errors::StorageError> {
+        record_request(
+            &self.redis_conn,
+            connector_name,
+            window_started_at,
+            window_in_secs,
+            success,
+        )
+        .await
+    }
+
+    async fn open_circuit(
+        &self,
+        connector_name: &str,
+        window_started_at: i64,
+        cooldown_in_secs: i64,
+    ) -> CustomResult<(), errors::StorageError> {
+        open(
+            &self.redis_conn,
+            connector_name,
+            window_started_at,
+            cooldown_in_secs,
+        )
+        .await
+    }
+
+    async fn close_circuit(&self, connector_name: &str) -> CustomResult<(), errors::StorageError> {
+        close(&self.redis_conn, connector_name).await
+    }
+}
+
+#[async_trait::async_trait]
+impl CircuitBreakerInterface for MockDb {
+    async fn find_circuit_state(
+        &self,
+        connector_name: &str,
+    ) -> CustomResult<CircuitState, errors::StorageError> {
+        find_state(&self.redis, connector_name).await
+    }
+
+    async fn acquire_circuit_probe(
+        &self,
+        connector_name: &str,
+        ttl_in_secs: i64,
+    ) -> CustomResult<bool, errors::StorageError> {
+        acquire_probe(&self.redis, connector_name, ttl_in_secs).await
+    }
+
+    async fn record_circuit_request(
+        &self,
+        connector_name: &str,
+        window_started_at: i64,
+        window_in_secs: i64,
+        success: bool,
+    ) -> CustomResult<(u32, u32), errors::StorageError> {
+        record_request(
+            &self.redis,
+            connector_name,
+            window_started_at,
+            window_in_secs,
+            success,
+        )
+        .await
+    }
+
+    async fn open_circuit(
+        &self,
+        connector_name: &str,
+        window_started_at: i64,
+        cooldown_in_secs: i64,
+    ) -> CustomResult<(), errors::StorageError> {
+        open(
+            &self.redis,
+            connector_name,
+            window_started_at,
+            cooldown_in_secs,
+        )
+        .await
+    }
+
+    async fn close_circuit(&self, connector_name: &str) -> CustomResult<(), errors::StorageError> {
+        close(&self.redis, connector_name).await
+    }
+}
@@ -1,4 +1,5 @@
 use error_stack::IntoReport;
+use storage_models::errors::DatabaseError;
 
 use super::{cache, MockDb, Store};
 use crate::{
@@ -103,47 +104,65 @@ impl ConfigInterface for Store {
 impl ConfigInterface for MockDb {
     async fn insert_config(
         &self,
-        _config: storage::ConfigNew,
+        config: storage::ConfigNew,
     ) -> CustomResult<storage::Config, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut configs = self.configs.lock().await;
+        let config = storage::Config {
+            id: i32::try_from(configs.len()).unwrap_or(i32::MAX),
+            key: config.key,
+            config: config.config,
+        };
+        configs.push(config.clone());
+        Ok(config)
     }
 
     async fn find_config_by_key(
         &self,
-        _key: &str,
+        key: &str,
     ) -> CustomResult<storage::Config, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let configs = self.configs.lock().await;
+        configs
+            .iter()
+            .find(|config| config.key == key)
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
     }
 
     async fn update_config_by_key(
         &self,
-        _key: &str,
-        _config_update: storage::ConfigUpdate,
+        key: &str,
+        config_update: storage::ConfigUpdate,
     ) -> CustomResult<storage::Config, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut configs = self.configs.lock().await;
+        let stored_config = configs
+            .iter_mut()
+            .find(|config| config.key == key)
+            .ok_or_else(|| errors::StorageError::DatabaseError(DatabaseError::NotFound.into()))?;
+        *stored_config = config_update.apply_changeset(stored_config.clone());
+        Ok(stored_config.clone())
     }
+
     async fn update_config_cached(
         &self,
-        _key: &str,
-        _config_update: storage::ConfigUpdate,
+        key: &str,
+        config_update: storage::ConfigUpdate,
     ) -> CustomResult<storage::Config, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        self.update_config_by_key(key, config_update).await
     }
 
-    async fn delete_config_by_key(&self, _key: &str) -> CustomResult<bool, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+    async fn delete_config_by_key(&self, key: &str) -> CustomResult<bool, errors::StorageError> {
+        let mut configs = self.configs.lock().await;
+        let configs_count = configs.len();
+        configs.retain(|config| config.key != key);
+        Ok(configs.len() != configs_count)
     }
 
     async fn find_config_by_key_cached(
         &self,
-        _key: &str,
+        key: &str,
     ) -> CustomResult<storage::Config, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        self.find_config_by_key(key).await
     }
 }
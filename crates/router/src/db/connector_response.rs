@@ -106,13 +106,27 @@ impl ConnectorResponseInterface for MockDb {
 
     async fn find_connector_response_by_payment_id_merchant_id_attempt_id(
         &self,
-        _payment_id: &str,
-        _merchant_id: &str,
-        _attempt_id: &str,
+        payment_id: &str,
+        merchant_id: &str,
+        attempt_id: &str,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage::ConnectorResponse, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let connector_response = self.connector_response.lock().await;
+
+        connector_response
+            .iter()
+            .find(|response| {
+                response.payment_id == payment_id
+                    && response.merchant_id == merchant_id
+                    && response.attempt_id == attempt_id
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!(
+                    "No connector response found for attempt {attempt_id}"
+                ))
+                .into()
+            })
     }
 
     // safety: interface only used for testing
@@ -0,0 +1,173 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection::pg_connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait DisputeInterface {
+    async fn insert_dispute(
+        &self,
+        dispute: storage::DisputeNew,
+    ) -> CustomResult<storage::Dispute, errors::StorageError>;
+
+    async fn find_dispute_by_merchant_id_dispute_id(
+        &self,
+        merchant_id: &str,
+        dispute_id: &str,
+    ) -> CustomResult<storage::Dispute, errors::StorageError>;
+
+    async fn find_dispute_by_merchant_id_connector_dispute_id(
+        &self,
+        merchant_id: &str,
+        connector: &str,
+        connector_dispute_id: &str,
+    ) -> CustomResult<Option<storage::Dispute>, errors::StorageError>;
+
+    async fn update_dispute(
+        &self,
+        this: storage::Dispute,
+        dispute: storage::DisputeUpdate,
+    ) -> CustomResult<storage::Dispute, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl DisputeInterface for Store {
+    async fn insert_dispute(
+        &self,
+        dispute: storage::DisputeNew,
+    ) -> CustomResult<storage::Dispute, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        dispute
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_dispute_by_merchant_id_dispute_id(
+        &self,
+        merchant_id: &str,
+        dispute_id: &str,
+    ) -> CustomResult<storage::Dispute, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        storage::Dispute::find_by_merchant_id_dispute_id(&conn, merchant_id, dispute_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_dispute_by_merchant_id_connector_dispute_id(
+        &self,
+        merchant_id: &str,
+        connector: &str,
+        connector_dispute_id: &str,
+    ) -> CustomResult<Option<storage::Dispute>, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        storage::Dispute::find_optional_by_merchant_id_connector_dispute_id(
+            &conn,
+            merchant_id,
+            connector,
+            connector_dispute_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_dispute(
+        &self,
+        this: storage::Dispute,
+        dispute: storage::DisputeUpdate,
+    ) -> CustomResult<storage::Dispute, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        this.update(&conn, dispute)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl DisputeInterface for MockDb {
+    async fn insert_dispute(
+        &self,
+        dispute: storage::DisputeNew,
+    ) -> CustomResult<storage::Dispute, errors::StorageError> {
+        let mut disputes = self.disputes.lock().await;
+        let current_time = common_utils::date_time::now();
+        let dispute = storage::Dispute {
+            id: i32::try_from(disputes.len()).unwrap_or(i32::MAX),
+            dispute_id: dispute.dispute_id,
+            merchant_id: dispute.merchant_id,
+            payment_id: dispute.payment_id,
+            attempt_id: dispute.attempt_id,
+            connector: dispute.connector,
+            connector_dispute_id: dispute.connector_dispute_id,
+            connector_reason_code: dispute.connector_reason_code,
+            amount: dispute.amount,
+            currency: dispute.currency,
+            dispute_status: dispute.dispute_status,
+            created_at: current_time,
+            modified_at: current_time,
+            evidence_status: None,
+        };
+        disputes.push(dispute.clone());
+        Ok(dispute)
+    }
+
+    async fn find_dispute_by_merchant_id_dispute_id(
+        &self,
+        merchant_id: &str,
+        dispute_id: &str,
+    ) -> CustomResult<storage::Dispute, errors::StorageError> {
+        let disputes = self.disputes.lock().await;
+        disputes
+            .iter()
+            .find(|dispute| dispute.merchant_id == merchant_id && dispute.dispute_id == dispute_id)
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!("No dispute found for id {dispute_id}"))
+                    .into()
+            })
+    }
+
+    async fn find_dispute_by_merchant_id_connector_dispute_id(
+        &self,
+        merchant_id: &str,
+        connector: &str,
+        connector_dispute_id: &str,
+    ) -> CustomResult<Option<storage::Dispute>, errors::StorageError> {
+        let disputes = self.disputes.lock().await;
+        Ok(disputes
+            .iter()
+            .find(|dispute| {
+                dispute.merchant_id == merchant_id
+                    && dispute.connector == connector
+                    && dispute.connector_dispute_id == connector_dispute_id
+            })
+            .cloned())
+    }
+
+    async fn update_dispute(
+        &self,
+        this: storage::Dispute,
+        dispute: storage::DisputeUpdate,
+    ) -> CustomResult<storage::Dispute, errors::StorageError> {
+        let mut disputes = self.disputes.lock().await;
+        let stored_dispute = disputes
+            .iter_mut()
+            .find(|stored_dispute| stored_dispute.dispute_id == this.dispute_id)
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!(
+                    "No dispute found for id {}",
+                    this.dispute_id
+                ))
+            })?;
+        *stored_dispute = dispute.apply_changeset(this);
+        Ok(stored_dispute.clone())
+    }
+}
@@ -0,0 +1,91 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection::pg_connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait FailedPaymentAttemptInterface {
+    async fn insert_failed_payment_attempt(
+        &self,
+        failed_payment_attempt: storage::FailedPaymentAttemptNew,
+    ) -> CustomResult<storage::FailedPaymentAttempt, errors::StorageError>;
+
+    async fn find_failed_payment_attempts_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::FailedPaymentAttempt>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl FailedPaymentAttemptInterface for Store {
+    async fn insert_failed_payment_attempt(
+        &self,
+        failed_payment_attempt: storage::FailedPaymentAttemptNew,
+    ) -> CustomResult<storage::FailedPaymentAttempt, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        failed_payment_attempt
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_failed_payment_attempts_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::FailedPaymentAttempt>, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        storage::FailedPaymentAttempt::find_by_merchant_id_payment_id(
+            &conn,
+            merchant_id,
+            payment_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl FailedPaymentAttemptInterface for MockDb {
+    async fn insert_failed_payment_attempt(
+        &self,
+        failed_payment_attempt: storage::FailedPaymentAttemptNew,
+    ) -> CustomResult<storage::FailedPaymentAttempt, errors::StorageError> {
+        let mut failed_payment_attempts = self.failed_payment_attempts.lock().await;
+        let failed_payment_attempt = storage::FailedPaymentAttempt {
+            id: i32::try_from(failed_payment_attempts.len()).unwrap_or(i32::MAX),
+            payment_id: failed_payment_attempt.payment_id,
+            merchant_id: failed_payment_attempt.merchant_id,
+            attempt_id: failed_payment_attempt.attempt_id,
+            connector: failed_payment_attempt.connector,
+            error_code: failed_payment_attempt.error_code,
+            error_message: failed_payment_attempt.error_message,
+            error_reason: failed_payment_attempt.error_reason,
+            created_at: common_utils::date_time::now(),
+        };
+        failed_payment_attempts.push(failed_payment_attempt.clone());
+        Ok(failed_payment_attempt)
+    }
+
+    async fn find_failed_payment_attempts_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::FailedPaymentAttempt>, errors::StorageError> {
+        let failed_payment_attempts = self.failed_payment_attempts.lock().await;
+        Ok(failed_payment_attempts
+            .iter()
+            .filter(|attempt| {
+                attempt.merchant_id == merchant_id && attempt.payment_id == payment_id
+            })
+            .cloned()
+            .collect())
+    }
+}
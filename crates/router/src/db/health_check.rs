@@ -0,0 +1,51 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::core::errors::{self, CustomResult};
+
+#[async_trait::async_trait]
+pub trait HealthCheckInterface {
+    async fn health_check_db(&self) -> CustomResult<(), errors::HealthCheckError>;
+
+    async fn health_check_redis(&self) -> CustomResult<(), errors::HealthCheckError>;
+}
+
+#[async_trait::async_trait]
+impl HealthCheckInterface for Store {
+    async fn health_check_db(&self) -> CustomResult<(), errors::HealthCheckError> {
+        let conn = self
+            .master_pool
+            .get()
+            .await
+            .into_report()
+            .change_context(errors::HealthCheckError::DatabaseConnectionFailed)?;
+        diesel::sql_query("SELECT 1")
+            .execute_async(&*conn)
+            .await
+            .into_report()
+            .change_context(errors::HealthCheckError::DatabaseQueryFailed)?;
+        Ok(())
+    }
+
+    async fn health_check_redis(&self) -> CustomResult<(), errors::HealthCheckError> {
+        self.redis_conn
+            .ping()
+            .await
+            .change_context(errors::HealthCheckError::RedisUnreachable)
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheckInterface for MockDb {
+    async fn health_check_db(&self) -> CustomResult<(), errors::HealthCheckError> {
+        Ok(())
+    }
+
+    async fn health_check_redis(&self) -> CustomResult<(), errors::HealthCheckError> {
+        self.redis
+            .ping()
+            .await
+            .change_context(errors::HealthCheckError::RedisUnreachable)
+    }
+}
@@ -0,0 +1,249 @@
+use base64::Engine;
+use error_stack::{IntoReport, ResultExt};
+use time::PrimitiveDateTime;
+
+use crate::{
+    consts,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+/// Position of an object in a list ordered by creation time and id, newest first.
+///
+/// Clients only ever see the cursor as an opaque string, obtained from [`Cursor::encode`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub created_at: PrimitiveDateTime,
+    pub id: i32,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let position = format!(
+            "{}:{}",
+            self.created_at.assume_utc().unix_timestamp_nanos(),
+            self.id
+        );
+        consts::BASE64_ENGINE_URL_SAFE.encode(position)
+    }
+
+    pub fn decode(cursor: &str) -> CustomResult<Self, errors::StorageError> {
+        let position = consts::BASE64_ENGINE_URL_SAFE
+            .decode(cursor)
+            .into_report()
+            .change_context(errors::StorageError::InvalidCursor)
+            .attach_printable("Cursor is not base64 encoded")?;
+        let position = String::from_utf8(position)
+            .into_report()
+            .change_context(errors::StorageError::InvalidCursor)?;
+        let (created_at, id) = position
+            .split_once(':')
+            .ok_or(errors::StorageError::InvalidCursor)
+            .into_report()
+            .attach_printable("Cursor is missing the id")?;
+
+        let created_at = created_at
+            .parse()
+            .into_report()
+            .change_context(errors::StorageError::InvalidCursor)
+            .attach_printable("Cursor has an invalid creation time")?;
+        let created_at = time::OffsetDateTime::from_unix_timestamp_nanos(created_at)
+            .into_report()
+            .change_context(errors::StorageError::InvalidCursor)
+            .attach_printable("Cursor has an invalid creation time")?;
+        let id = id
+            .parse()
+            .into_report()
+            .change_context(errors::StorageError::InvalidCursor)
+            .attach_printable("Cursor has an invalid id")?;
+
+        Ok(Self {
+            created_at: PrimitiveDateTime::new(created_at.date(), created_at.time()),
+            id,
+        })
+    }
+
+    /// Whether the object at `created_at` and `id` comes after the cursor in the list.
+    pub fn is_followed_by(&self, created_at: PrimitiveDateTime, id: i32) -> bool {
+        (created_at, id) < (self.created_at, self.id)
+    }
+}
+
+pub trait Paginate {
+    fn get_cursor(&self) -> Cursor;
+}
+
+impl Paginate for storage::PaymentIntent {
+    fn get_cursor(&self) -> Cursor {
+        Cursor {
+            created_at: self.created_at,
+            id: self.id,
+        }
+    }
+}
+
+impl Paginate for storage::Refund {
+    fn get_cursor(&self) -> Cursor {
+        Cursor {
+            created_at: self.created_at,
+            id: self.id,
+        }
+    }
+}
+
+/// Cursor of the page following `page`, `None` if `page` is the last page.
+pub fn get_next_cursor<T: Paginate>(page: &[T], limit: i64) -> Option<String> {
+    let is_full_page = i64::try_from(page.len()).map_or(true, |length| length >= limit);
+    page.last()
+        .filter(|_| is_full_page)
+        .map(|last| last.get_cursor().encode())
+}
+
+/// Orders `objects` newest first and returns at most `limit` of them following the cursor, for
+/// stores that hold their objects in memory.
+pub fn paginate<T: Paginate>(mut objects: Vec<T>, limit: i64, cursor: Option<&Cursor>) -> Vec<T> {
+    objects.sort_by_key(|object| {
+        let cursor = object.get_cursor();
+        std::cmp::Reverse((cursor.created_at, cursor.id))
+    });
+    objects
+        .into_iter()
+        .filter(|object| {
+            let object_cursor = object.get_cursor();
+            cursor.map_or(true, |cursor| {
+                cursor.is_followed_by(object_cursor.created_at, object_cursor.id)
+            })
+        })
+        .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        .collect()
+}
+
+#[cfg(all(test, feature = "olap"))]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{
+        db::StorageImpl,
+        routes,
+        types::storage::{self, enums},
+    };
+
+    fn get_refund_list_request(cursor: Option<String>) -> api_models::refunds::RefundListRequest {
+        api_models::refunds::RefundListRequest {
+            payment_id: None,
+            limit: Some(2),
+            cursor,
+            created: None,
+            created_lt: None,
+            created_gt: None,
+            created_lte: None,
+            created_gte: None,
+        }
+    }
+
+    async fn create_refunds(state: &routes::AppState) {
+        let created_at = common_utils::date_time::now();
+        // The last two refunds are created at the same time, their ids decide the order
+        for (index, minutes) in [0, 1, 2, 3, 3].into_iter().enumerate() {
+            state
+                .store
+                .insert_refund(
+                    storage::RefundNew {
+                        refund_id: format!("ref_{index}"),
+                        merchant_id: "merchant_1".to_string(),
+                        created_at: Some(
+                            created_at.saturating_add(time::Duration::minutes(minutes)),
+                        ),
+                        ..Default::default()
+                    },
+                    enums::MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .expect("Failed to insert refund");
+        }
+    }
+
+    async fn list_refunds(
+        state: &routes::AppState,
+        cursor: Option<String>,
+    ) -> (Vec<String>, Option<String>) {
+        let refunds = state
+            .store
+            .filter_refund_by_constraints(
+                "merchant_1",
+                &get_refund_list_request(cursor),
+                enums::MerchantStorageScheme::PostgresOnly,
+                2,
+            )
+            .await
+            .expect("Failed to list refunds");
+        let next_cursor = get_next_cursor(&refunds, 2);
+        let refund_ids = refunds.into_iter().map(|refund| refund.refund_id).collect();
+        (refund_ids, next_cursor)
+    }
+
+    #[actix_rt::test]
+    async fn test_first_page_holds_newest_objects() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        create_refunds(&state).await;
+
+        let (refund_ids, next_cursor) = list_refunds(&state, None).await;
+        assert_eq!(refund_ids, vec!["ref_4", "ref_3"]);
+        assert!(next_cursor.is_some());
+    }
+
+    #[actix_rt::test]
+    async fn test_next_pages_continue_where_previous_page_ended() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        create_refunds(&state).await;
+
+        let (first_page, next_cursor) = list_refunds(&state, None).await;
+        let (second_page, next_cursor) = list_refunds(&state, next_cursor).await;
+        let (third_page, next_cursor) = list_refunds(&state, next_cursor).await;
+
+        assert_eq!(first_page, vec!["ref_4", "ref_3"]);
+        assert_eq!(second_page, vec!["ref_2", "ref_1"]);
+        assert_eq!(third_page, vec!["ref_0"]);
+        assert_eq!(next_cursor, None);
+    }
+
+    #[actix_rt::test]
+    async fn test_invalid_cursor_is_rejected() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        create_refunds(&state).await;
+
+        for cursor in [
+            "not a cursor".to_string(),
+            consts::BASE64_ENGINE_URL_SAFE.encode("1676880000000000000"),
+            consts::BASE64_ENGINE_URL_SAFE.encode("yesterday:1"),
+        ] {
+            let error = state
+                .store
+                .filter_refund_by_constraints(
+                    "merchant_1",
+                    &get_refund_list_request(Some(cursor)),
+                    enums::MerchantStorageScheme::PostgresOnly,
+                    2,
+                )
+                .await
+                .expect_err("Listed refunds with an invalid cursor");
+            assert!(matches!(
+                error.current_context(),
+                errors::StorageError::InvalidCursor
+            ));
+        }
+    }
+}
@@ -54,6 +54,23 @@ pub trait PaymentAttemptInterface {
         attempt_id: &str,
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<types::PaymentAttempt, errors::StorageError>;
+
+    /// Attempts sent to a connector that are in one of the statuses, were last updated before the
+    /// given time and failed to sync fewer than `max_sync_failures` times, oldest first.
+    async fn find_payment_attempts_by_statuses_modified_before(
+        &self,
+        statuses: Vec<enums::AttemptStatus>,
+        modified_before: time::PrimitiveDateTime,
+        max_sync_failures: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<types::PaymentAttempt>, errors::StorageError>;
+
+    /// Drops the stored connector response bodies of the attempts last updated before the given
+    /// time, returning the number of attempts cleared.
+    async fn clear_connector_response_bodies_modified_before(
+        &self,
+        modified_before: time::PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError>;
 }
 
 #[cfg(not(feature = "kv_store"))]
@@ -175,29 +192,126 @@ mod storage {
                 .map_err(Into::into)
                 .into_report()
         }
+
+        async fn find_payment_attempts_by_statuses_modified_before(
+            &self,
+            statuses: Vec<enums::AttemptStatus>,
+            modified_before: time::PrimitiveDateTime,
+            max_sync_failures: i32,
+            limit: i64,
+        ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
+            let conn = pg_connection(&self.master_pool).await;
+            PaymentAttempt::find_by_statuses_modified_before(
+                &conn,
+                statuses,
+                modified_before,
+                max_sync_failures,
+                limit,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
+        async fn clear_connector_response_bodies_modified_before(
+            &self,
+            modified_before: time::PrimitiveDateTime,
+        ) -> CustomResult<usize, errors::StorageError> {
+            let conn = pg_connection(&self.master_pool).await;
+            PaymentAttempt::clear_connector_response_bodies_modified_before(&conn, modified_before)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
     }
 }
 
 #[async_trait::async_trait]
 impl PaymentAttemptInterface for MockDb {
+    async fn find_payment_attempts_by_statuses_modified_before(
+        &self,
+        statuses: Vec<enums::AttemptStatus>,
+        modified_before: time::PrimitiveDateTime,
+        max_sync_failures: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<types::PaymentAttempt>, errors::StorageError> {
+        let payment_attempts = self.payment_attempts.lock().await;
+        let mut stale_attempts = payment_attempts
+            .iter()
+            .filter(|payment_attempt| {
+                statuses.contains(&payment_attempt.status)
+                    && payment_attempt.modified_at < modified_before
+                    && payment_attempt.connector.is_some()
+                    && payment_attempt.sync_failure_count < max_sync_failures
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        stale_attempts.sort_by_key(|payment_attempt| payment_attempt.modified_at);
+        stale_attempts.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+
+        Ok(stale_attempts)
+    }
+
+    async fn clear_connector_response_bodies_modified_before(
+        &self,
+        modified_before: time::PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError> {
+        let mut payment_attempts = self.payment_attempts.lock().await;
+        let mut cleared_count = 0;
+        for payment_attempt in payment_attempts.iter_mut().filter(|payment_attempt| {
+            payment_attempt.modified_at < modified_before
+                && payment_attempt.connector_response_body.is_some()
+        }) {
+            payment_attempt.connector_response_body = None;
+            cleared_count += 1;
+        }
+        Ok(cleared_count)
+    }
+
     async fn find_payment_attempt_by_merchant_id_attempt_id(
         &self,
-        _merchant_id: &str,
-        _attempt_id: &str,
+        merchant_id: &str,
+        attempt_id: &str,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<types::PaymentAttempt, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        payment_attempts
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.attempt_id == attempt_id
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!(
+                    "No payment attempt found for attempt {attempt_id}"
+                ))
+                .into()
+            })
     }
 
     async fn find_payment_attempt_by_merchant_id_connector_txn_id(
         &self,
-        _merchant_id: &str,
-        _connector_txn_id: &str,
+        merchant_id: &str,
+        connector_txn_id: &str,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<types::PaymentAttempt, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        payment_attempts
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.connector_transaction_id.as_deref() == Some(connector_txn_id)
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!(
+                    "No payment attempt found for connector transaction {connector_txn_id}"
+                ))
+                .into()
+            })
     }
 
     #[allow(clippy::panic)]
@@ -244,6 +358,18 @@ impl PaymentAttemptInterface for MockDb {
             payment_token: None,
             error_code: payment_attempt.error_code,
             connector_metadata: None,
+            connector_response_reference_id: None,
+            connector_response_body: payment_attempt.connector_response_body,
+            return_url: payment_attempt.return_url,
+            error_reason: payment_attempt.error_reason,
+            authorized_amount: payment_attempt.authorized_amount,
+            settlement_currency: payment_attempt.settlement_currency,
+            settlement_amount: payment_attempt.settlement_amount,
+            fx_rate: payment_attempt.fx_rate,
+            avs_result: payment_attempt.avs_result,
+            cvv_result: payment_attempt.cvv_result,
+            authorized_at: payment_attempt.authorized_at,
+            sync_failure_count: 0,
         };
         payment_attempts.push(payment_attempt.clone());
         Ok(payment_attempt)
@@ -271,12 +397,25 @@ impl PaymentAttemptInterface for MockDb {
 
     async fn find_payment_attempt_by_payment_id_merchant_id(
         &self,
-        _payment_id: &str,
-        _merchant_id: &str,
+        payment_id: &str,
+        merchant_id: &str,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<types::PaymentAttempt, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        payment_attempts
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.payment_id == payment_id
+                    && payment_attempt.merchant_id == merchant_id
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!(
+                    "No payment attempt found for payment {payment_id}"
+                ))
+                .into()
+            })
     }
 
     async fn find_payment_attempt_by_connector_transaction_id_payment_id_merchant_id(
@@ -290,8 +429,6 @@ impl PaymentAttemptInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
-    // safety: only used for testing
-    #[allow(clippy::unwrap_used)]
     async fn find_payment_attempt_last_successful_attempt_by_payment_id_merchant_id(
         &self,
         payment_id: &str,
@@ -300,14 +437,19 @@ impl PaymentAttemptInterface for MockDb {
     ) -> CustomResult<types::PaymentAttempt, errors::StorageError> {
         let payment_attempts = self.payment_attempts.lock().await;
 
-        Ok(payment_attempts
+        payment_attempts
             .iter()
             .find(|payment_attempt| {
                 payment_attempt.payment_id == payment_id
                     && payment_attempt.merchant_id == merchant_id
             })
             .cloned()
-            .unwrap())
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!(
+                    "No successful payment attempt found for payment {payment_id}"
+                ))
+                .into()
+            })
     }
 }
 
@@ -383,6 +525,20 @@ mod storage {
                         payment_token: payment_attempt.payment_token.clone(),
                         error_code: payment_attempt.error_code.clone(),
                         connector_metadata: payment_attempt.connector_metadata.clone(),
+                        connector_response_reference_id: payment_attempt
+                            .connector_response_reference_id
+                            .clone(),
+                        connector_response_body: payment_attempt.connector_response_body.clone(),
+                        return_url: payment_attempt.return_url.clone(),
+                        error_reason: payment_attempt.error_reason.clone(),
+                        authorized_amount: payment_attempt.authorized_amount,
+                        settlement_currency: payment_attempt.settlement_currency,
+                        settlement_amount: payment_attempt.settlement_amount,
+                        fx_rate: payment_attempt.fx_rate.clone(),
+                        avs_result: payment_attempt.avs_result,
+                        cvv_result: payment_attempt.cvv_result,
+                        authorized_at: payment_attempt.authorized_at,
+                        sync_failure_count: 0,
                     };
 
                     let field = format!("pa_{}", created_attempt.attempt_id);
@@ -693,5 +849,107 @@ mod storage {
                 }
             }
         }
+
+        // Attempts are scanned from the database irrespective of the storage scheme, since the
+        // KV store cannot be queried by status.
+        async fn find_payment_attempts_by_statuses_modified_before(
+            &self,
+            statuses: Vec<enums::AttemptStatus>,
+            modified_before: time::PrimitiveDateTime,
+            max_sync_failures: i32,
+            limit: i64,
+        ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
+            let conn = pg_connection(&self.master_pool).await;
+            PaymentAttempt::find_by_statuses_modified_before(
+                &conn,
+                statuses,
+                modified_before,
+                max_sync_failures,
+                limit,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
+        // Attempts in the KV store expire after the hash TTL, so only the database holds response
+        // bodies past the retention period.
+        async fn clear_connector_response_bodies_modified_before(
+            &self,
+            modified_before: time::PrimitiveDateTime,
+        ) -> CustomResult<usize, errors::StorageError> {
+            let conn = pg_connection(&self.master_pool).await;
+            PaymentAttempt::clear_connector_response_bodies_modified_before(&conn, modified_before)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{db::StorageImpl, routes};
+
+    #[actix_rt::test]
+    async fn test_avs_and_cvv_results_are_persisted() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+        let payment_attempt = state
+            .store
+            .insert_payment_attempt(
+                types::PaymentAttemptNew {
+                    payment_id: "pay_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: "pay_1_1".to_string(),
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to insert payment attempt");
+
+        state
+            .store
+            .update_payment_attempt(
+                payment_attempt,
+                types::PaymentAttemptUpdate::ResponseUpdate {
+                    status: enums::AttemptStatus::Charged,
+                    connector: Some("authorizedotnet".to_string()),
+                    connector_transaction_id: Some("txn_1".to_string()),
+                    authentication_type: None,
+                    payment_method_id: None,
+                    redirect: None,
+                    mandate_id: None,
+                    connector_metadata: None,
+                    connector_response_reference_id: None,
+                    connector_response_body: None,
+                    authorized_amount: None,
+                    avs_result: Some(enums::AvsResult::PartialMatch),
+                    cvv_result: Some(enums::CvvResult::NoMatch),
+                    authorized_at: None,
+                },
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to update payment attempt");
+
+        let payment_attempt = state
+            .store
+            .find_payment_attempt_by_payment_id_merchant_id("pay_1", "merchant_1", storage_scheme)
+            .await
+            .expect("Failed to find payment attempt");
+        assert_eq!(
+            payment_attempt.avs_result,
+            Some(enums::AvsResult::PartialMatch)
+        );
+        assert_eq!(payment_attempt.cvv_result, Some(enums::CvvResult::NoMatch));
     }
 }
@@ -1,3 +1,7 @@
+use storage_models::errors::DatabaseError;
+
+#[cfg(feature = "olap")]
+use super::pagination;
 use super::MockDb;
 #[cfg(feature = "olap")]
 use crate::types::api;
@@ -15,6 +19,15 @@ pub trait PaymentIntentInterface {
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<types::PaymentIntent, errors::StorageError>;
 
+    /// Adds a captured amount to the amount captured so far, without losing the amounts of
+    /// concurrent captures of the same payment.
+    async fn add_amount_captured_to_payment_intent(
+        &self,
+        this: types::PaymentIntent,
+        amount: i64,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<types::PaymentIntent, errors::StorageError>;
+
     async fn insert_payment_intent(
         &self,
         new: types::PaymentIntentNew,
@@ -44,8 +57,6 @@ mod storage {
     use redis_interface::HsetnxReply;
 
     use super::PaymentIntentInterface;
-    #[cfg(feature = "olap")]
-    use crate::types::api;
     use crate::{
         connection::pg_connection,
         core::errors::{self, CustomResult},
@@ -53,6 +64,8 @@ mod storage {
         types::storage::{enums, kv, payment_intent::*},
         utils::{self, db_utils, storage_partitioning},
     };
+    #[cfg(feature = "olap")]
+    use crate::{db::pagination::Cursor, types::api};
 
     #[async_trait::async_trait]
     impl PaymentIntentInterface for Store {
@@ -182,6 +195,40 @@ mod storage {
             }
         }
 
+        async fn add_amount_captured_to_payment_intent(
+            &self,
+            this: PaymentIntent,
+            amount: i64,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<PaymentIntent, errors::StorageError> {
+            match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = pg_connection(&self.master_pool).await;
+                    this.add_amount_captured(&conn, amount)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+
+                // The intent is stored serialized in redis, which has no way of adding to a
+                // single field of it, so this falls back to updating the intent read earlier
+                enums::MerchantStorageScheme::RedisKv => {
+                    let amount_captured = Some(this.amount_captured.unwrap_or(0) + amount);
+                    let status = this.status;
+                    self.update_payment_intent(
+                        this,
+                        PaymentIntentUpdate::ResponseUpdate {
+                            status,
+                            amount_captured,
+                            return_url: None,
+                        },
+                        storage_scheme,
+                    )
+                    .await
+                }
+            }
+        }
+
         async fn find_payment_intent_by_payment_id_merchant_id(
             &self,
             payment_id: &str,
@@ -219,8 +266,9 @@ mod storage {
         ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
+                    let cursor = pc.cursor.as_deref().map(Cursor::decode).transpose()?;
                     let conn = pg_connection(&self.replica_pool).await;
-                    PaymentIntent::filter_by_constraints(&conn, merchant_id, pc)
+                    PaymentIntent::filter_by_constraints(&conn, merchant_id, pc, cursor.as_ref())
                         .await
                         .map_err(Into::into)
                         .into_report()
@@ -237,14 +285,14 @@ mod storage {
     use error_stack::IntoReport;
 
     use super::PaymentIntentInterface;
-    #[cfg(feature = "olap")]
-    use crate::types::api;
     use crate::{
         connection::pg_connection,
         core::errors::{self, CustomResult},
         services::Store,
         types::storage::{enums, payment_intent::*},
     };
+    #[cfg(feature = "olap")]
+    use crate::{db::pagination::Cursor, types::api};
 
     #[async_trait::async_trait]
     impl PaymentIntentInterface for Store {
@@ -270,6 +318,19 @@ mod storage {
                 .into_report()
         }
 
+        async fn add_amount_captured_to_payment_intent(
+            &self,
+            this: PaymentIntent,
+            amount: i64,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<PaymentIntent, errors::StorageError> {
+            let conn = pg_connection(&self.master_pool).await;
+            this.add_amount_captured(&conn, amount)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
         async fn find_payment_intent_by_payment_id_merchant_id(
             &self,
             payment_id: &str,
@@ -290,8 +351,9 @@ mod storage {
             pc: &api::PaymentListConstraints,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
+            let cursor = pc.cursor.as_deref().map(Cursor::decode).transpose()?;
             let conn = pg_connection(&self.replica_pool).await;
-            PaymentIntent::filter_by_constraints(&conn, merchant_id, pc)
+            PaymentIntent::filter_by_constraints(&conn, merchant_id, pc, cursor.as_ref())
                 .await
                 .map_err(Into::into)
                 .into_report()
@@ -304,12 +366,64 @@ impl PaymentIntentInterface for MockDb {
     #[cfg(feature = "olap")]
     async fn filter_payment_intent_by_constraints(
         &self,
-        _merchant_id: &str,
-        _pc: &api::PaymentListConstraints,
+        merchant_id: &str,
+        pc: &api::PaymentListConstraints,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Vec<types::PaymentIntent>, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let cursor = pc
+            .cursor
+            .as_deref()
+            .map(pagination::Cursor::decode)
+            .transpose()?;
+        let payment_intents = self.payment_intents.lock().await;
+        let find_id = |payment_id: &String| {
+            payment_intents
+                .iter()
+                .find(|payment_intent| {
+                    payment_intent.merchant_id == merchant_id
+                        && &payment_intent.payment_id == payment_id
+                })
+                .map(|payment_intent| payment_intent.id)
+                .ok_or_else(|| {
+                    error_stack::report!(errors::StorageError::DatabaseError(
+                        DatabaseError::NotFound.into()
+                    ))
+                })
+        };
+        let starting_after = pc.starting_after.as_ref().map(find_id).transpose()?;
+        let ending_before = pc.ending_before.as_ref().map(find_id).transpose()?;
+
+        let payment_intents = payment_intents
+            .iter()
+            .filter(|payment_intent| {
+                let created_at = payment_intent.created_at;
+                payment_intent.merchant_id == merchant_id
+                    && pc.customer_id.as_ref().map_or(true, |customer_id| {
+                        payment_intent.customer_id.as_ref() == Some(customer_id)
+                    })
+                    && pc.created.map_or(true, |created| created_at == created)
+                    && pc
+                        .created_lt
+                        .map_or(true, |created_lt| created_at < created_lt)
+                    && pc
+                        .created_gt
+                        .map_or(true, |created_gt| created_at > created_gt)
+                    && pc
+                        .created_lte
+                        .map_or(true, |created_lte| created_at <= created_lte)
+                    && pc
+                        .created_gte
+                        .map_or(true, |created_gte| created_at >= created_gte)
+                    && starting_after.map_or(true, |id| payment_intent.id > id)
+                    && ending_before.map_or(true, |id| payment_intent.id < id)
+            })
+            .cloned()
+            .collect();
+        Ok(pagination::paginate(
+            payment_intents,
+            pc.limit,
+            cursor.as_ref(),
+        ))
     }
 
     #[allow(clippy::panic)]
@@ -366,6 +480,22 @@ impl PaymentIntentInterface for MockDb {
         Ok(payment_intent.clone())
     }
 
+    async fn add_amount_captured_to_payment_intent(
+        &self,
+        this: types::PaymentIntent,
+        amount: i64,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<types::PaymentIntent, errors::StorageError> {
+        let mut payment_intents = self.payment_intents.lock().await;
+        let payment_intent = payment_intents
+            .iter_mut()
+            .find(|item| item.id == this.id)
+            .ok_or_else(|| errors::StorageError::DatabaseError(DatabaseError::NotFound.into()))?;
+        payment_intent.amount_captured = Some(payment_intent.amount_captured.unwrap_or(0) + amount);
+        payment_intent.modified_at = common_utils::date_time::now();
+        Ok(payment_intent.clone())
+    }
+
     // safety: only used for testing
     #[allow(clippy::unwrap_used)]
     async fn find_payment_intent_by_payment_id_merchant_id(
@@ -382,6 +512,94 @@ impl PaymentIntentInterface for MockDb {
                 payment_intent.payment_id == payment_id && payment_intent.merchant_id == merchant_id
             })
             .cloned()
-            .unwrap())
+            .ok_or_else(|| errors::StorageError::DatabaseError(DatabaseError::NotFound.into()))?)
+    }
+}
+
+#[cfg(all(test, feature = "olap"))]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{db::StorageImpl, routes};
+
+    fn get_payment_list_constraints(
+        created_gte: Option<time::PrimitiveDateTime>,
+        created_lt: Option<time::PrimitiveDateTime>,
+    ) -> api::PaymentListConstraints {
+        api::PaymentListConstraints {
+            customer_id: None,
+            starting_after: None,
+            ending_before: None,
+            cursor: None,
+            limit: 10,
+            created: None,
+            created_lt,
+            created_gt: None,
+            created_lte: None,
+            created_gte,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_payments_are_filtered_by_created_time_range() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let start_time = common_utils::date_time::now();
+        for hours in 0..4 {
+            state
+                .store
+                .insert_payment_intent(
+                    types::PaymentIntentNew {
+                        payment_id: format!("pay_{hours}"),
+                        merchant_id: "merchant_1".to_string(),
+                        created_at: Some(start_time.saturating_add(time::Duration::hours(hours))),
+                        ..Default::default()
+                    },
+                    enums::MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .expect("Failed to insert payment intent");
+        }
+
+        let constraints = get_payment_list_constraints(
+            Some(start_time.saturating_add(time::Duration::hours(1))),
+            Some(start_time.saturating_add(time::Duration::hours(3))),
+        );
+        types::payment_intent::validate_created_time_range(&constraints)
+            .expect("Rejected a valid created time range");
+        let payment_ids: Vec<_> = state
+            .store
+            .filter_payment_intent_by_constraints(
+                "merchant_1",
+                &constraints,
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to list payment intents")
+            .into_iter()
+            .map(|payment_intent| payment_intent.payment_id)
+            .collect();
+
+        assert_eq!(payment_ids, vec!["pay_2", "pay_1"]);
+    }
+
+    #[test]
+    fn test_inverted_created_time_range_is_rejected() {
+        let start_time = common_utils::date_time::now();
+        let constraints = get_payment_list_constraints(
+            Some(start_time.saturating_add(time::Duration::hours(1))),
+            Some(start_time),
+        );
+
+        let error = types::payment_intent::validate_created_time_range(&constraints)
+            .expect_err("Accepted an inverted created time range");
+        assert!(matches!(
+            error.current_context(),
+            errors::ValidationError::InvalidValue { .. }
+        ));
     }
 }
@@ -208,28 +208,50 @@ impl ProcessTrackerInterface for MockDb {
 
     async fn update_process(
         &self,
-        _this: storage::ProcessTracker,
-        _process: storage::ProcessTrackerUpdate,
+        this: storage::ProcessTracker,
+        process: storage::ProcessTrackerUpdate,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut processes = self.processes.lock().await;
+        let stored_process = processes
+            .iter_mut()
+            .find(|stored_process| stored_process.id == this.id)
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!("No process found for id {}", this.id))
+            })?;
+        *stored_process = process.apply_changeset(this);
+        Ok(stored_process.clone())
     }
 
     async fn update_process_tracker(
         &self,
-        _this: storage::ProcessTracker,
-        _process: storage::ProcessTrackerUpdate,
+        this: storage::ProcessTracker,
+        process: storage::ProcessTrackerUpdate,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut processes = self.processes.lock().await;
+        let stored_process = processes
+            .iter_mut()
+            .find(|stored_process| stored_process.id == this.id)
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(format!("No process found for id {}", this.id))
+            })?;
+        *stored_process = process.apply_changeset(this);
+        Ok(stored_process.clone())
     }
 
     async fn process_tracker_update_process_status_by_ids(
         &self,
-        _task_ids: Vec<String>,
-        _task_update: storage::ProcessTrackerUpdate,
+        task_ids: Vec<String>,
+        task_update: storage::ProcessTrackerUpdate,
     ) -> CustomResult<usize, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut processes = self.processes.lock().await;
+        let mut updated_count = 0;
+        for stored_process in processes
+            .iter_mut()
+            .filter(|stored_process| task_ids.contains(&stored_process.id))
+        {
+            *stored_process = task_update.clone().apply_changeset(stored_process.clone());
+            updated_count += 1;
+        }
+        Ok(updated_count)
     }
 }
@@ -1,4 +1,4 @@
-use redis_interface::{errors::RedisError, RedisEntryId, SetnxReply};
+use redis_interface::{errors::RedisError, DeadLetterPolicy, RedisEntryId, SetnxReply};
 use router_env::logger;
 
 use super::{MockDb, Store};
@@ -14,6 +14,7 @@ pub trait QueueInterface {
         stream_name: &str,
         group_name: &str,
         consumer_name: &str,
+        dead_letter_policy: &DeadLetterPolicy,
     ) -> CustomResult<Vec<storage::ProcessTracker>, ProcessTrackerError>;
 
     async fn consumer_group_create(
@@ -35,6 +36,22 @@ pub trait QueueInterface {
     ) -> CustomResult<(), RedisError>;
 
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError>;
+
+    async fn set_key_if_not_exists_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: i64,
+    ) -> CustomResult<SetnxReply, RedisError>;
+
+    async fn set_key_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: i64,
+    ) -> CustomResult<(), RedisError>;
+
+    async fn delete_key(&self, key: &str) -> CustomResult<(), RedisError>;
 }
 
 #[async_trait::async_trait]
@@ -44,13 +61,15 @@ impl QueueInterface for Store {
         stream_name: &str,
         group_name: &str,
         consumer_name: &str,
+        dead_letter_policy: &DeadLetterPolicy,
     ) -> CustomResult<Vec<storage::ProcessTracker>, ProcessTrackerError> {
         crate::scheduler::consumer::fetch_consumer_tasks(
             self,
-            &self.redis_conn.clone(),
+            self.redis_conn.clone(),
             stream_name,
             group_name,
             consumer_name,
+            dead_letter_policy,
         )
         .await
     }
@@ -116,6 +135,30 @@ impl QueueInterface for Store {
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError> {
         self.redis_conn.get_key::<Vec<u8>>(key).await
     }
+
+    async fn set_key_if_not_exists_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: i64,
+    ) -> CustomResult<SetnxReply, RedisError> {
+        self.redis_conn
+            .set_key_if_not_exists_with_expiry(key, value, ttl)
+            .await
+    }
+
+    async fn set_key_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: i64,
+    ) -> CustomResult<(), RedisError> {
+        self.redis_conn.set_key_with_expiry(key, value, ttl).await
+    }
+
+    async fn delete_key(&self, key: &str) -> CustomResult<(), RedisError> {
+        self.redis_conn.delete_key(key).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -125,6 +168,7 @@ impl QueueInterface for MockDb {
         _stream_name: &str,
         _group_name: &str,
         _consumer_name: &str,
+        _dead_letter_policy: &DeadLetterPolicy,
     ) -> CustomResult<Vec<storage::ProcessTracker>, ProcessTrackerError> {
         // [#172]: Implement function for `MockDb`
         Err(ProcessTrackerError::ResourceFetchingFailed {
@@ -171,4 +215,28 @@ impl QueueInterface for MockDb {
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError> {
         self.redis.get_key(key).await
     }
+
+    async fn set_key_if_not_exists_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: i64,
+    ) -> CustomResult<SetnxReply, RedisError> {
+        self.redis
+            .set_key_if_not_exists_with_expiry(key, value, ttl)
+            .await
+    }
+
+    async fn set_key_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: i64,
+    ) -> CustomResult<(), RedisError> {
+        self.redis.set_key_with_expiry(key, value, ttl).await
+    }
+
+    async fn delete_key(&self, key: &str) -> CustomResult<(), RedisError> {
+        self.redis.delete_key(key).await
+    }
 }
@@ -0,0 +1,65 @@
+use error_stack::ResultExt;
+
+use super::{MockDb, Store};
+use crate::core::errors::{self, CustomResult};
+
+/// Request counts of API keys, kept in Redis and incremented atomically so that concurrent router
+/// instances do not lose each other's requests. Every key expires.
+#[async_trait::async_trait]
+pub trait RateLimiterInterface {
+    /// Counts a request of the API key in the window starting at `window_started_at`, returning
+    /// the number of requests of the window including this one.
+    async fn increment_rate_limit_count(
+        &self,
+        key_id: &str,
+        window_started_at: i64,
+        window_in_secs: i64,
+    ) -> CustomResult<u32, errors::StorageError>;
+}
+
+fn get_rate_limit_key(key_id: &str, window_started_at: i64) -> String {
+    format!("rate_limit_{key_id}_{window_started_at}")
+}
+
+async fn increment_count(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    key_id: &str,
+    window_started_at: i64,
+    window_in_secs: i64,
+) -> CustomResult<u32, errors::StorageError> {
+    let key = get_rate_limit_key(key_id, window_started_at);
+    let count = redis_conn
+        .increment_key(&key)
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    // Counts are kept a little longer than their window, for requests sent at its very end
+    redis_conn
+        .set_expiry(&key, window_in_secs.max(1).saturating_mul(2))
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    Ok(u32::try_from(count).unwrap_or(u32::MAX))
+}
+
+#[async_trait::async_trait]
+impl RateLimiterInterface for Store {
+    async fn increment_rate_limit_count(
+        &self,
+        key_id: &str,
+        window_started_at: i64,
+        window_in_secs: i64,
+    ) -> CustomResult<u32, errors::StorageError> {
+        increment_count(&self.redis_conn, key_id, window_started_at, window_in_secs).await
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimiterInterface for MockDb {
+    async fn increment_rate_limit_count(
+        &self,
+        key_id: &str,
+        window_started_at: i64,
+        window_in_secs: i64,
+    ) -> CustomResult<u32, errors::StorageError> {
+        increment_count(&self.redis, key_id, window_started_at, window_in_secs).await
+    }
+}
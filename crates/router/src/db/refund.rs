@@ -1,5 +1,7 @@
 use storage_models::errors::DatabaseError;
 
+#[cfg(feature = "olap")]
+use super::pagination;
 use super::MockDb;
 use crate::{
     core::errors::{self, CustomResult},
@@ -50,12 +52,28 @@ pub trait RefundInterface {
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError>;
 
+    async fn find_refund_by_merchant_id_connector_refund_id_connector(
+        &self,
+        merchant_id: &str,
+        connector_refund_id: &str,
+        connector: &str,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<storage_types::Refund, errors::StorageError>;
+
     async fn insert_refund(
         &self,
         new: storage_types::RefundNew,
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage_types::Refund, errors::StorageError>;
 
+    /// Refunds sent to the connector with the status `refund_status`, least recently modified
+    /// first.
+    async fn find_sent_refunds_by_status(
+        &self,
+        refund_status: enums::RefundStatus,
+        limit: i64,
+    ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError>;
+
     #[cfg(feature = "olap")]
     async fn filter_refund_by_constraints(
         &self,
@@ -71,6 +89,8 @@ mod storage {
     use error_stack::IntoReport;
 
     use super::RefundInterface;
+    #[cfg(feature = "olap")]
+    use crate::db::pagination::Cursor;
     use crate::{
         connection::pg_connection,
         core::errors::{self, CustomResult},
@@ -123,6 +143,25 @@ mod storage {
             .into_report()
         }
 
+        async fn find_refund_by_merchant_id_connector_refund_id_connector(
+            &self,
+            merchant_id: &str,
+            connector_refund_id: &str,
+            connector: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            let conn = pg_connection(&self.master_pool).await;
+            storage_types::Refund::find_by_merchant_id_connector_refund_id_connector(
+                &conn,
+                merchant_id,
+                connector_refund_id,
+                connector,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         async fn update_refund(
             &self,
             this: storage_types::Refund,
@@ -173,6 +212,25 @@ mod storage {
                 .into_report()
         }
 
+        async fn find_sent_refunds_by_status(
+            &self,
+            refund_status: enums::RefundStatus,
+            limit: i64,
+        ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+            #[cfg(feature = "olap")]
+            let conn = pg_connection(&self.replica_pool).await;
+            #[cfg(not(feature = "olap"))]
+            let conn = pg_connection(&self.master_pool).await;
+            storage_types::Refund::find_sent_to_gateway_by_refund_status(
+                &conn,
+                refund_status,
+                limit,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_refund_by_constraints(
             &self,
@@ -181,12 +239,18 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
             limit: i64,
         ) -> CustomResult<Vec<storage_models::refund::Refund>, errors::StorageError> {
+            let cursor = refund_details
+                .cursor
+                .as_deref()
+                .map(Cursor::decode)
+                .transpose()?;
             let conn = pg_connection(&self.replica_pool).await;
             <storage_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(
                 &conn,
                 merchant_id,
                 refund_details,
                 limit,
+                cursor.as_ref(),
             )
             .await
             .map_err(Into::into)
@@ -202,6 +266,8 @@ mod storage {
     use redis_interface::HsetnxReply;
 
     use super::RefundInterface;
+    #[cfg(feature = "olap")]
+    use crate::db::pagination::Cursor;
     use crate::{
         connection::pg_connection,
         core::errors::{self, CustomResult},
@@ -399,6 +465,27 @@ mod storage {
             }
         }
 
+        // Refunds are not indexed by their connector refund id in redis, the lookup is served by
+        // the database for both storage schemes.
+        async fn find_refund_by_merchant_id_connector_refund_id_connector(
+            &self,
+            merchant_id: &str,
+            connector_refund_id: &str,
+            connector: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            let conn = pg_connection(&self.master_pool).await;
+            storage_types::Refund::find_by_merchant_id_connector_refund_id_connector(
+                &conn,
+                merchant_id,
+                connector_refund_id,
+                connector,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         async fn update_refund(
             &self,
             this: storage_types::Refund,
@@ -543,6 +630,27 @@ mod storage {
             }
         }
 
+        // Refunds are scanned from the database irrespective of the storage scheme, since the
+        // KV store cannot be queried by status.
+        async fn find_sent_refunds_by_status(
+            &self,
+            refund_status: enums::RefundStatus,
+            limit: i64,
+        ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+            #[cfg(feature = "olap")]
+            let conn = pg_connection(&self.replica_pool).await;
+            #[cfg(not(feature = "olap"))]
+            let conn = pg_connection(&self.master_pool).await;
+            storage_types::Refund::find_sent_to_gateway_by_refund_status(
+                &conn,
+                refund_status,
+                limit,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_refund_by_constraints(
             &self,
@@ -553,8 +661,13 @@ mod storage {
         ) -> CustomResult<Vec<storage_models::refund::Refund>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
+                    let cursor = refund_details
+                        .cursor
+                        .as_deref()
+                        .map(Cursor::decode)
+                        .transpose()?;
                     let conn = pg_connection(&self.replica_pool).await;
-                    <storage_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(&conn, merchant_id, refund_details, limit)
+                    <storage_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(&conn, merchant_id, refund_details, limit, cursor.as_ref())
                         .await
                         .map_err(Into::into)
                         .into_report()
@@ -634,14 +747,41 @@ impl RefundInterface for MockDb {
             .collect::<Vec<_>>())
     }
 
+    async fn find_refund_by_merchant_id_connector_refund_id_connector(
+        &self,
+        merchant_id: &str,
+        connector_refund_id: &str,
+        connector: &str,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+        let refunds = self.refunds.lock().await;
+
+        refunds
+            .iter()
+            .find(|refund| {
+                refund.merchant_id == merchant_id
+                    && refund.connector_refund_id.as_deref() == Some(connector_refund_id)
+                    && refund.connector == connector
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
+    }
+
     async fn update_refund(
         &self,
-        _this: storage_types::Refund,
-        _refund: storage_types::RefundUpdate,
+        this: storage_types::Refund,
+        refund: storage_types::RefundUpdate,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage_types::Refund, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut refunds = self.refunds.lock().await;
+        let stored_refund = refunds
+            .iter_mut()
+            .find(|item| item.id == this.id)
+            .ok_or_else(|| errors::StorageError::DatabaseError(DatabaseError::NotFound.into()))?;
+        *stored_refund = refund.apply_changeset(this);
+        Ok(stored_refund.clone())
     }
 
     async fn find_refund_by_merchant_id_refund_id(
@@ -663,23 +803,76 @@ impl RefundInterface for MockDb {
 
     async fn find_refund_by_payment_id_merchant_id(
         &self,
-        _payment_id: &str,
-        _merchant_id: &str,
+        payment_id: &str,
+        merchant_id: &str,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let refunds = self.refunds.lock().await;
+
+        Ok(refunds
+            .iter()
+            .filter(|refund| refund.merchant_id == merchant_id && refund.payment_id == payment_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_sent_refunds_by_status(
+        &self,
+        refund_status: enums::RefundStatus,
+        limit: i64,
+    ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+        let refunds = self.refunds.lock().await;
+        let mut refunds = refunds
+            .iter()
+            .filter(|refund| refund.refund_status == refund_status && refund.sent_to_gateway)
+            .cloned()
+            .collect::<Vec<_>>();
+        refunds.sort_by_key(|refund| refund.updated_at);
+        refunds.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+
+        Ok(refunds)
     }
 
     #[cfg(feature = "olap")]
     async fn filter_refund_by_constraints(
         &self,
-        _merchant_id: &str,
-        _refund_details: &api_models::refunds::RefundListRequest,
+        merchant_id: &str,
+        refund_details: &api_models::refunds::RefundListRequest,
         _storage_scheme: enums::MerchantStorageScheme,
-        _limit: i64,
+        limit: i64,
     ) -> CustomResult<Vec<storage_models::refund::Refund>, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let cursor = refund_details
+            .cursor
+            .as_deref()
+            .map(pagination::Cursor::decode)
+            .transpose()?;
+        let refunds = self.refunds.lock().await;
+        let refunds = refunds
+            .iter()
+            .filter(|refund| {
+                refund.merchant_id == merchant_id
+                    && refund_details
+                        .payment_id
+                        .as_ref()
+                        .map_or(true, |payment_id| &refund.payment_id == payment_id)
+                    && refund_details
+                        .created
+                        .map_or(true, |created| refund.created_at == created)
+                    && refund_details
+                        .created_lt
+                        .map_or(true, |created_lt| refund.created_at < created_lt)
+                    && refund_details
+                        .created_gt
+                        .map_or(true, |created_gt| refund.created_at > created_gt)
+                    && refund_details
+                        .created_lte
+                        .map_or(true, |created_lte| refund.created_at <= created_lte)
+                    && refund_details
+                        .created_gte
+                        .map_or(true, |created_gte| refund.created_at >= created_gte)
+            })
+            .cloned()
+            .collect();
+        Ok(pagination::paginate(refunds, limit, cursor.as_ref()))
     }
 }
@@ -0,0 +1,48 @@
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::core::errors::{self, CustomResult};
+
+#[async_trait::async_trait]
+pub trait RoutingInterface {
+    /// Returns the number of payments routed round-robin for the merchant so far, counting the
+    /// current one.
+    async fn increment_round_robin_counter(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<u64, errors::StorageError>;
+}
+
+async fn increment_counter(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    merchant_id: &str,
+) -> CustomResult<u64, errors::StorageError> {
+    let counter = redis_conn
+        .increment_key(&format!("routing_round_robin_{merchant_id}"))
+        .await
+        .change_context(errors::StorageError::KVError)?;
+    u64::try_from(counter)
+        .into_report()
+        .change_context(errors::StorageError::KVError)
+        .attach_printable("Round robin counter is negative")
+}
+
+#[async_trait::async_trait]
+impl RoutingInterface for Store {
+    async fn increment_round_robin_counter(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<u64, errors::StorageError> {
+        increment_counter(&self.redis_conn, merchant_id).await
+    }
+}
+
+#[async_trait::async_trait]
+impl RoutingInterface for MockDb {
+    async fn increment_round_robin_counter(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<u64, errors::StorageError> {
+        increment_counter(&self.redis, merchant_id).await
+    }
+}
@@ -0,0 +1,162 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection::pg_connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait WebhookDeliveryAttemptInterface {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        webhook_delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError>;
+
+    async fn find_webhook_delivery_attempts_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError>;
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_primary_object_id(
+        &self,
+        merchant_id: &str,
+        primary_object_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError>;
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAttemptInterface for Store {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        webhook_delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        webhook_delivery_attempt
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        storage::WebhookDeliveryAttempt::find_by_merchant_id(&conn, merchant_id, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_primary_object_id(
+        &self,
+        merchant_id: &str,
+        primary_object_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        storage::WebhookDeliveryAttempt::find_by_merchant_id_primary_object_id(
+            &conn,
+            merchant_id,
+            primary_object_id,
+            limit,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let conn = pg_connection(&self.master_pool).await;
+        storage::WebhookDeliveryAttempt::find_by_merchant_id_event_id(&conn, merchant_id, event_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAttemptInterface for MockDb {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        webhook_delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        let mut webhook_delivery_attempts = self.webhook_delivery_attempts.lock().await;
+        let webhook_delivery_attempt = storage::WebhookDeliveryAttempt {
+            id: i32::try_from(webhook_delivery_attempts.len()).unwrap_or(i32::MAX),
+            merchant_id: webhook_delivery_attempt.merchant_id,
+            event_id: webhook_delivery_attempt.event_id,
+            event_type: webhook_delivery_attempt.event_type,
+            primary_object_id: webhook_delivery_attempt.primary_object_id,
+            webhook_url: webhook_delivery_attempt.webhook_url,
+            status_code: webhook_delivery_attempt.status_code,
+            attempt_number: webhook_delivery_attempt.attempt_number,
+            error_message: webhook_delivery_attempt.error_message,
+            created_at: common_utils::date_time::now(),
+        };
+        webhook_delivery_attempts.push(webhook_delivery_attempt.clone());
+        Ok(webhook_delivery_attempt)
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let webhook_delivery_attempts = self.webhook_delivery_attempts.lock().await;
+        Ok(webhook_delivery_attempts
+            .iter()
+            .rev()
+            .filter(|attempt| attempt.merchant_id == merchant_id)
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_primary_object_id(
+        &self,
+        merchant_id: &str,
+        primary_object_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let webhook_delivery_attempts = self.webhook_delivery_attempts.lock().await;
+        Ok(webhook_delivery_attempts
+            .iter()
+            .rev()
+            .filter(|attempt| {
+                attempt.merchant_id == merchant_id && attempt.primary_object_id == primary_object_id
+            })
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let webhook_delivery_attempts = self.webhook_delivery_attempts.lock().await;
+        Ok(webhook_delivery_attempts
+            .iter()
+            .filter(|attempt| attempt.merchant_id == merchant_id && attempt.event_id == event_id)
+            .cloned()
+            .collect())
+    }
+}
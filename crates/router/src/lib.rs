@@ -50,6 +50,8 @@ pub mod headers {
     pub const X_API_VERSION: &str = "X-ApiVersion";
     pub const DATE: &str = "Date";
     pub const X_MERCHANT_ID: &str = "X-Merchant-Id";
+    pub const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+    pub const X_REQUEST_ID: &str = "X-Request-Id";
 }
 
 pub mod pii {
@@ -91,6 +93,7 @@ pub fn mk_app(
         server_app = server_app
             .service(routes::PaymentMethods::server(state.clone()))
             .service(routes::EphemeralKey::server(state.clone()))
+            .service(routes::Disputes::server(state.clone()))
             .service(routes::Webhooks::server(state.clone()));
     }
 
@@ -105,6 +108,10 @@ pub fn mk_app(
     {
         server_app = server_app.service(routes::StripeApis::server(state.clone()));
     }
+    #[cfg(feature = "openapi")]
+    {
+        server_app = server_app.service(routes::OpenApiSpec::server());
+    }
     server_app = server_app.service(routes::Health::server(state));
     server_app
 }
@@ -1,4 +1,24 @@
-/// Middleware to include request ID in response header.
+use actix_web::HttpMessage;
+use router_env::tracing;
+
+use crate::{
+    headers,
+    routes::AppState,
+    services::{
+        api,
+        authentication::{self as auth, AuthenticateAndFetch},
+    },
+};
+
+/// Longest request ID accepted from a client, longer ones are replaced by a generated ID.
+const MAX_REQUEST_ID_LENGTH: usize = 64;
+
+/// Request ID of the API request being served, stored in the request extensions.
+#[derive(Clone, Debug)]
+pub(crate) struct RequestIdentifier(pub(crate) String);
+
+/// Middleware to accept or generate a request ID, record it on the request span and include it in
+/// the response header.
 pub(crate) struct RequestId;
 
 impl<S, B> actix_web::dev::Transform<S, actix_web::dev::ServiceRequest> for RequestId
@@ -43,19 +63,207 @@ where
     actix_web::dev::forward_ready!(service);
 
     fn call(&self, req: actix_web::dev::ServiceRequest) -> Self::Future {
-        let mut req = req;
-        let request_id_fut = req.extract::<router_env::tracing_actix_web::RequestId>();
+        let request_id = get_incoming_request_id(&req).or_else(|| {
+            req.extensions()
+                .get::<router_env::tracing_actix_web::RequestId>()
+                .map(|request_id| request_id.as_hyphenated().to_string())
+        });
+        if let Some(request_id) = &request_id {
+            tracing::Span::current().record("request_id", request_id.as_str());
+            req.extensions_mut()
+                .insert(RequestIdentifier(request_id.clone()));
+        }
         let response_fut = self.service.call(req);
 
         Box::pin(async move {
-            let request_id = request_id_fut.await?;
             let mut response = response_fut.await?;
-            response.headers_mut().append(
-                http::header::HeaderName::from_static("x-request-id"),
-                http::HeaderValue::from_str(&request_id.as_hyphenated().to_string())?,
-            );
+            if let Some(request_id) = request_id {
+                response.headers_mut().append(
+                    http::header::HeaderName::from_static("x-request-id"),
+                    http::HeaderValue::from_str(&request_id)?,
+                );
+            }
 
             Ok(response)
         })
     }
 }
+
+/// Request ID sent by the client, so that it can correlate our logs with its own.
+fn get_incoming_request_id(req: &actix_web::dev::ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(headers::X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= MAX_REQUEST_ID_LENGTH
+                && value.bytes().all(|byte| byte.is_ascii_graphic())
+        })
+        .map(ToString::to_string)
+}
+
+/// Middleware to authenticate the merchant API key of the request and store the merchant account
+/// in the request extensions as an [`auth::AuthenticatedMerchant`]. Requests without a valid API
+/// key are rejected before reaching the route. The middleware must wrap a resource, or a scope
+/// nested in the one holding the [`AppState`], to find the application state.
+pub(crate) struct ApiKeyAuthentication;
+
+impl<S, B> actix_web::dev::Transform<S, actix_web::dev::ServiceRequest> for ApiKeyAuthentication
+where
+    S: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = ApiKeyAuthenticationMiddleware<S>;
+    type InitError = ();
+    type Future = std::future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        std::future::ready(Ok(ApiKeyAuthenticationMiddleware {
+            service: std::rc::Rc::new(service),
+        }))
+    }
+}
+
+pub(crate) struct ApiKeyAuthenticationMiddleware<S> {
+    service: std::rc::Rc<S>,
+}
+
+impl<S, B> actix_web::dev::Service<actix_web::dev::ServiceRequest>
+    for ApiKeyAuthenticationMiddleware<S>
+where
+    S: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = futures::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: actix_web::dev::ServiceRequest) -> Self::Future {
+        let service = std::rc::Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let merchant_account = match req.app_data::<actix_web::web::Data<AppState>>() {
+                Some(state) => {
+                    auth::ApiKeyAuth
+                        .authenticate_and_fetch(req.headers(), state.get_ref())
+                        .await
+                }
+                None => Err(error_stack::report!(
+                    crate::core::errors::ApiErrorResponse::InternalServerError
+                )
+                .attach_printable("Application state not found for API key authentication")),
+            };
+
+            match merchant_account {
+                Ok(merchant_account) => {
+                    req.extensions_mut()
+                        .insert(auth::AuthenticatedMerchant(merchant_account));
+                    service
+                        .call(req)
+                        .await
+                        .map(actix_web::dev::ServiceResponse::map_into_left_body)
+                }
+                Err(error) => {
+                    let response = api::log_and_return_error_response(error);
+                    Ok(req.into_response(response).map_into_right_body())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use actix_web::{body, http::StatusCode, test, web, App, HttpResponse};
+    use masking::StrongSecret;
+
+    use super::*;
+    use crate::{db::StorageImpl, types::storage};
+
+    async fn get_merchant_id(merchant: web::ReqData<auth::AuthenticatedMerchant>) -> HttpResponse {
+        HttpResponse::Ok().body(merchant.0.merchant_id.clone())
+    }
+
+    async fn send_request(api_key: Option<&str>) -> (StatusCode, String) {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        state
+            .store
+            .insert_merchant(storage::MerchantAccountNew {
+                merchant_id: "merchant_1".to_string(),
+                api_key: Some(StrongSecret::new("dev_api_key".to_string())),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        // Mounted the way the routes are, with the application state held by the scope
+        let app = test::init_service(
+            App::new().service(
+                web::scope("/merchant")
+                    .app_data(web::Data::new(state))
+                    .service(
+                        web::resource("")
+                            .wrap(ApiKeyAuthentication)
+                            .route(web::get().to(get_merchant_id)),
+                    ),
+            ),
+        )
+        .await;
+
+        let mut request = test::TestRequest::get().uri("/merchant");
+        if let Some(api_key) = api_key {
+            request = request.insert_header(("api-key", api_key));
+        }
+        let response = test::call_service(&app, request.to_request()).await;
+        let status = response.status();
+        let body = body::to_bytes(response.into_body()).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn test_request_without_api_key_is_unauthorized() {
+        let (status, body) = send_request(None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("IR_01"));
+    }
+
+    #[actix_rt::test]
+    async fn test_request_with_invalid_api_key_is_unauthorized() {
+        let (status, body) = send_request(Some("dev_invalid_api_key")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("IR_01"));
+    }
+
+    #[actix_rt::test]
+    async fn test_request_with_publishable_key_is_forbidden() {
+        let (status, body) = send_request(Some("pk_dev_api_key")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body.contains("IR_20"));
+    }
+
+    #[actix_rt::test]
+    async fn test_request_with_valid_api_key_reaches_the_route() {
+        let (status, body) = send_request(Some("dev_api_key")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "merchant_1");
+    }
+}
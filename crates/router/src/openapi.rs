@@ -54,6 +54,8 @@ Never share your secret api keys. Keep them guarded and secure.
         (name = "Merchant Connector Account", description = "Create and manage merchant connector accounts"),
         (name = "Payments", description = "Create and manage one-time payments, recurring payments and mandates"),
         (name = "Refunds", description = "Create and manage refunds for successful payments"),
+        (name = "Disputes", description = "Contest disputes raised against payments"),
+        (name = "Webhooks", description = "Look up the webhooks sent to merchants"),
         (name = "Mandates", description = "Manage mandates"),
         (name = "Customers", description = "Create and manage customers"),
         (name = "Payment Methods", description = "Create and manage payment methods of customers"),
@@ -61,10 +63,14 @@ Never share your secret api keys. Keep them guarded and secure.
     ),
     paths(
         crate::routes::refunds::refunds_create,
+        crate::routes::refunds::refunds_bulk_create,
         crate::routes::refunds::refunds_retrieve,
         crate::routes::refunds::refunds_update,
         crate::routes::refunds::refunds_list,
         crate::routes::refunds::refunds_create,
+        crate::routes::disputes::submit_dispute_evidence,
+        crate::routes::webhooks::webhook_delivery_attempts_list,
+        crate::routes::webhooks::outgoing_webhook_redeliver,
         crate::routes::admin::merchant_account_create,
         crate::routes::admin::retrieve_merchant_account,
         crate::routes::admin::update_merchant_account,
@@ -100,6 +106,7 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::api_keys::api_key_retrieve,
         crate::routes::api_keys::api_key_update,
         crate::routes::api_keys::api_key_revoke,
+        crate::routes::api_keys::api_key_rotate,
         crate::routes::api_keys::api_key_list,
     ),
     components(schemas(
@@ -108,6 +115,14 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::types::api::refunds::RefundResponse,
         crate::types::api::refunds::RefundStatus,
         crate::types::api::refunds::RefundUpdateRequest,
+        crate::types::api::refunds::BulkRefundRequest,
+        crate::types::api::refunds::BulkRefundResponse,
+        crate::types::api::refunds::BulkRefundResult,
+        crate::types::api::refunds::BulkRefundError,
+        crate::types::api::disputes::SubmitEvidenceRequest,
+        crate::types::api::disputes::DisputeResponse,
+        crate::types::api::disputes::DisputeStatus,
+        crate::types::api::disputes::EvidenceStatus,
         crate::types::api::admin::CreateMerchantAccount,
         crate::types::api::admin::DeleteMerchantAccountResponse,
         crate::types::api::admin::DeleteMcaResponse,
@@ -134,16 +149,19 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::FutureUsage,
         api_models::enums::AuthenticationType,
         api_models::enums::WalletIssuer,
+        api_models::enums::BankNames,
         api_models::enums::Connector,
         api_models::enums::PaymentMethodType,
         api_models::enums::SupportedWallets,
         api_models::enums::PaymentMethodIssuerCode,
         api_models::enums::MandateStatus,
+        api_models::enums::ScaExemptionType,
         api_models::admin::PaymentConnectorCreate,
         api_models::admin::PaymentMethods,
         api_models::payments::AddressDetails,
         api_models::payments::Address,
         api_models::payments::OrderDetails,
+        api_models::payments::SurchargeDetails,
         api_models::payments::NextActionType,
         api_models::payments::Metadata,
         api_models::payments::WalletData,
@@ -152,6 +170,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::AfterpayClearpayIssuer,
         api_models::payments::NextAction,
         api_models::payments::PayLaterData,
+        api_models::payments::BankRedirectData,
         api_models::payments::MandateData,
         api_models::payments::PhoneDetails,
         api_models::payments::PaymentMethod,
@@ -183,6 +202,10 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::PaymentListResponse,
         api_models::refunds::RefundListRequest,
         api_models::refunds::RefundListResponse,
+        api_models::enums::EventType,
+        api_models::webhooks::WebhookDeliveryAttemptListRequest,
+        api_models::webhooks::WebhookDeliveryAttemptResponse,
+        api_models::webhooks::WebhookDeliveryAttemptListResponse,
         api_models::mandates::MandateRevokedResponse,
         api_models::mandates::MandateResponse,
         api_models::mandates::MandateCardDetails,
@@ -195,7 +218,81 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::types::api::api_keys::CreateApiKeyResponse,
         crate::types::api::api_keys::RetrieveApiKeyResponse,
         crate::types::api::api_keys::RevokeApiKeyResponse,
-        crate::types::api::api_keys::UpdateApiKeyRequest
+        crate::types::api::api_keys::RotateApiKeyRequest,
+        crate::types::api::api_keys::UpdateApiKeyRequest,
+        crate::core::errors::api_error_response::ErrorResponseBody,
+        crate::core::errors::api_error_response::ErrorDetails,
+        crate::core::errors::api_error_response::ErrorType
     ))
 )]
 pub struct ApiDoc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_spec() -> serde_json::Value {
+        serde_json::to_value(<ApiDoc as utoipa::OpenApi>::openapi())
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    #[test]
+    fn test_spec_includes_payments_create() {
+        let spec = get_spec();
+        let payments_create = &spec["paths"]["/payments"]["post"];
+
+        assert_eq!(payments_create["operationId"], "Create a Payment");
+        assert_eq!(
+            payments_create["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/PaymentsRequest"
+        );
+        assert_eq!(
+            payments_create["responses"]["400"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ErrorResponseBody"
+        );
+    }
+
+    #[test]
+    fn test_card_fields_are_redacted() {
+        let spec = get_spec();
+        let card_properties = &spec["components"]["schemas"]["Card"]["properties"];
+
+        for field in [
+            "card_number",
+            "card_exp_month",
+            "card_exp_year",
+            "card_holder_name",
+            "card_cvc",
+        ] {
+            assert_eq!(card_properties[field]["writeOnly"], true, "{field}");
+            assert_eq!(card_properties[field]["format"], "password", "{field}");
+        }
+        let card_cvc = &spec["components"]["schemas"]["PaymentsRequest"]["properties"]["card_cvc"];
+        assert_eq!(card_cvc["writeOnly"], true);
+    }
+
+    #[test]
+    fn test_wallet_and_bank_fields_are_redacted() {
+        let spec = get_spec();
+        let schemas = &spec["components"]["schemas"];
+
+        let wallet_token = &schemas["WalletData"]["properties"]["token"];
+        assert_eq!(wallet_token["writeOnly"], true);
+        assert_eq!(wallet_token["format"], "password");
+
+        let bank_redirect_variants = schemas["BankRedirectData"]["oneOf"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        for variant in ["ideal", "giropay"] {
+            let billing_name = bank_redirect_variants
+                .iter()
+                .map(|schema| &schema["properties"][variant]["properties"]["billing_name"])
+                .find(|billing_name| !billing_name.is_null())
+                .cloned()
+                .unwrap_or_default();
+            assert_eq!(billing_name["writeOnly"], true, "{variant}");
+            assert_eq!(billing_name["format"], "password", "{variant}");
+        }
+    }
+}
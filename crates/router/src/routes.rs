@@ -3,19 +3,25 @@ pub mod api_keys;
 pub mod app;
 pub mod configs;
 pub mod customers;
+pub mod disputes;
 pub mod ephemeral_key;
 pub mod health;
 pub mod mandates;
 pub mod metrics;
+#[cfg(feature = "openapi")]
+pub mod openapi;
 pub mod payment_methods;
 pub mod payments;
 pub mod payouts;
 pub mod refunds;
 pub mod webhooks;
 
+#[cfg(feature = "openapi")]
+pub use self::app::OpenApiSpec;
 pub use self::app::{
-    ApiKeys, AppState, Configs, Customers, EphemeralKey, Health, Mandates, MerchantAccount,
-    MerchantConnectorAccount, PaymentMethods, Payments, Payouts, Refunds, Webhooks,
+    ApiKeys, AppState, AppStateBuilder, Configs, Customers, Disputes, EphemeralKey, Health,
+    Mandates, MerchantAccount, MerchantConnectorAccount, PaymentMethods, Payments, Payouts,
+    Refunds, Webhooks,
 };
 #[cfg(feature = "stripe")]
 pub use super::compatibility::stripe::StripeApis;
@@ -148,6 +148,43 @@ pub async fn api_key_revoke(
     .await
 }
 
+/// API Key - Rotate
+///
+/// Create a new API Key to replace the specified API Key, which keeps working until the end of the
+/// rotation window. The plaintext API Key will be displayed only once, so ensure you store it
+/// securely.
+#[utoipa::path(
+    post,
+    path = "/api_keys/{key_id}/rotate",
+    request_body = RotateApiKeyRequest,
+    params (("key_id" = String, Path, description = "The unique identifier for the API Key")),
+    responses(
+        (status = 200, description = "API Key rotated", body = CreateApiKeyResponse),
+        (status = 404, description = "API Key not found")
+    ),
+    tag = "API Key",
+    operation_id = "Rotate an API Key"
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ApiKeyRotate))]
+pub async fn api_key_rotate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<api_types::RotateApiKeyRequest>,
+) -> impl Responder {
+    let key_id = path.into_inner();
+    let payload = json_payload.into_inner();
+
+    api::server_wrap(
+        state.get_ref(),
+        &req,
+        (&key_id, payload),
+        |state, _, (key_id, payload)| api_keys::rotate_api_key(&*state.store, key_id, payload),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
 /// API Key - List
 ///
 /// List all API Keys associated with your merchant account.
@@ -1,14 +1,17 @@
 use actix_web::{web, Scope};
 
 use super::health::*;
+#[cfg(feature = "openapi")]
+use super::openapi::*;
 #[cfg(feature = "olap")]
 use super::{admin::*, api_keys::*};
 #[cfg(any(feature = "olap", feature = "oltp"))]
 use super::{configs::*, customers::*, mandates::*, payments::*, payouts::*, refunds::*};
 #[cfg(feature = "oltp")]
-use super::{ephemeral_key::*, payment_methods::*, webhooks::*};
+use super::{disputes::*, ephemeral_key::*, payment_methods::*, webhooks::*};
 use crate::{
-    configs::settings::Settings,
+    configs::settings::{Database, Settings},
+    core::errors::ApplicationResult,
     db::{MockDb, StorageImpl, StorageInterface},
     services::Store,
 };
@@ -59,6 +62,68 @@ impl AppState {
     pub async fn new(conf: Settings) -> Self {
         Self::with_storage(conf, StorageImpl::Postgresql).await
     }
+
+    /// Builder for the state used by tests, backed by the test database unless overridden.
+    pub fn test_builder() -> AppStateBuilder {
+        AppStateBuilder::default()
+    }
+}
+
+/// Builds an [`AppState`] from [`Settings::new()`], with the database, Redis and storage
+/// implementation overridable per test.
+pub struct AppStateBuilder {
+    conf: Option<Settings>,
+    master_database: Option<Database>,
+    redis: Option<redis_interface::RedisSettings>,
+    storage_impl: StorageImpl,
+}
+
+impl Default for AppStateBuilder {
+    fn default() -> Self {
+        Self {
+            conf: None,
+            master_database: None,
+            redis: None,
+            storage_impl: StorageImpl::PostgresqlTest,
+        }
+    }
+}
+
+impl AppStateBuilder {
+    /// Use these settings instead of loading them with [`Settings::new()`].
+    pub fn settings(mut self, conf: Settings) -> Self {
+        self.conf = Some(conf);
+        self
+    }
+
+    pub fn master_database(mut self, database: Database) -> Self {
+        self.master_database = Some(database);
+        self
+    }
+
+    pub fn redis(mut self, redis: redis_interface::RedisSettings) -> Self {
+        self.redis = Some(redis);
+        self
+    }
+
+    pub fn storage(mut self, storage_impl: StorageImpl) -> Self {
+        self.storage_impl = storage_impl;
+        self
+    }
+
+    pub async fn build(self) -> ApplicationResult<AppState> {
+        let mut conf = match self.conf {
+            Some(conf) => conf,
+            None => Settings::new()?,
+        };
+        if let Some(master_database) = self.master_database {
+            conf.master_database = master_database;
+        }
+        if let Some(redis) = self.redis {
+            conf.redis = redis;
+        }
+        Ok(AppState::with_storage(conf, self.storage_impl).await)
+    }
 }
 
 pub struct Health;
@@ -68,6 +133,17 @@ impl Health {
         web::scope("")
             .app_data(web::Data::new(state))
             .service(web::resource("/health").route(web::get().to(health)))
+            .service(web::resource("/health/ready").route(web::get().to(readiness)))
+    }
+}
+
+#[cfg(feature = "openapi")]
+pub struct OpenApiSpec;
+
+#[cfg(feature = "openapi")]
+impl OpenApiSpec {
+    pub fn server() -> Scope {
+        web::scope("/openapi.json").service(web::resource("").route(web::get().to(openapi_spec)))
     }
 }
 
@@ -110,7 +186,8 @@ impl Payments {
                 )
                 .service(
                     web::resource("/{payment_id}/{merchant_id}/response/{connector}")
-                        .route(web::get().to(payments_redirect_response)),
+                        .route(web::get().to(payments_redirect_response))
+                        .route(web::post().to(payments_redirect_response_with_form_data)),
                 );
         }
         route
@@ -166,6 +243,7 @@ impl Refunds {
         {
             route = route
                 .service(web::resource("").route(web::post().to(refunds_create)))
+                .service(web::resource("/bulk").route(web::post().to(refunds_bulk_create)))
                 .service(
                     web::resource("/{id}")
                         .route(web::get().to(refunds_retrieve))
@@ -306,6 +384,23 @@ impl Mandates {
     }
 }
 
+pub struct Disputes;
+
+#[cfg(feature = "oltp")]
+impl Disputes {
+    pub fn server(state: AppState) -> Scope {
+        // The API key authentication wraps the resources rather than the scope, since the scope
+        // only adds its app data to the request after its own middleware has run
+        web::scope("/disputes")
+            .app_data(web::Data::new(state))
+            .service(
+                web::resource("/{dispute_id}/evidence")
+                    .wrap(crate::middleware::ApiKeyAuthentication)
+                    .route(web::post().to(submit_dispute_evidence)),
+            )
+    }
+}
+
 pub struct Webhooks;
 
 #[cfg(feature = "oltp")]
@@ -313,6 +408,13 @@ impl Webhooks {
     pub fn server(config: AppState) -> Scope {
         web::scope("/webhooks")
             .app_data(web::Data::new(config))
+            .service(
+                web::resource("/attempts").route(web::get().to(webhook_delivery_attempts_list)),
+            )
+            .service(
+                web::resource("/{merchant_id}/events/{event_id}/redeliver")
+                    .route(web::post().to(outgoing_webhook_redeliver)),
+            )
             .service(
                 web::resource("/{merchant_id}/{connector}")
                     .route(web::post().to(receive_incoming_webhook)),
@@ -350,5 +452,6 @@ impl ApiKeys {
                     .route(web::post().to(api_key_update))
                     .route(web::delete().to(api_key_revoke)),
             )
+            .service(web::resource("/{key_id}/rotate").route(web::post().to(api_key_rotate)))
     }
 }
@@ -0,0 +1,49 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::disputes,
+    services::{api, authentication as auth},
+    types::api::disputes as dispute_types,
+};
+
+// Disputes - Submit Evidence
+
+///
+/// To submit evidence contesting a dispute to the connector the payment was processed through
+#[utoipa::path(
+    post,
+    path = "/disputes/{dispute_id}/evidence",
+    params(
+        ("dispute_id" = String, Path, description = "The identifier for dispute")
+    ),
+    request_body=SubmitEvidenceRequest,
+    responses(
+        (status = 200, description = "Evidence submitted", body = DisputeResponse),
+        (status = 404, description = "Dispute does not exist in our records")
+    ),
+    tag = "Disputes",
+    operation_id = "Submit Dispute Evidence"
+)]
+#[instrument(skip_all, fields(flow = ?Flow::DisputesEvidenceSubmit))]
+// #[post("/{id}/evidence")]
+pub async fn submit_dispute_evidence(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<dispute_types::SubmitEvidenceRequest>,
+    path: web::Path<String>,
+    merchant: web::ReqData<auth::AuthenticatedMerchant>,
+) -> HttpResponse {
+    let dispute_id = path.into_inner();
+    api::server_wrap(
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, merchant_account, req| {
+            disputes::submit_evidence(state, merchant_account, &dispute_id, req)
+        },
+        &*merchant,
+    )
+    .await
+}
@@ -1,6 +1,16 @@
+use std::time::Duration;
+
+use actix_web::{web, HttpResponse};
 use router_env::{instrument, logger, tracing};
 
-use crate::routes::metrics;
+use crate::{
+    core::errors::{CustomResult, HealthCheckError},
+    routes::{metrics, AppState},
+};
+
+/// Upper bound on the time spent waiting for a single dependency, so that the probe itself does
+/// not hang when a dependency stops responding.
+const DEPENDENCY_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// .
 // #[logger::instrument(skip_all, name = "name1", level = "warn", fields( key1 = "val1" ))]
@@ -11,3 +21,86 @@ pub async fn health() -> impl actix_web::Responder {
     logger::info!("Health was called");
     actix_web::HttpResponse::Ok().body("health is good")
 }
+
+#[derive(Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyStatus {
+    Healthy,
+    Unhealthy,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ReadinessResponse {
+    pub database: DependencyStatus,
+    pub redis: DependencyStatus,
+}
+
+impl ReadinessResponse {
+    fn is_ready(&self) -> bool {
+        self.database == DependencyStatus::Healthy && self.redis == DependencyStatus::Healthy
+    }
+}
+
+async fn check_dependency<F>(name: &str, check: F) -> DependencyStatus
+where
+    F: std::future::Future<Output = CustomResult<(), HealthCheckError>>,
+{
+    match tokio::time::timeout(DEPENDENCY_CHECK_TIMEOUT, check).await {
+        Ok(Ok(())) => DependencyStatus::Healthy,
+        Ok(Err(error)) => {
+            logger::error!(dependency = name, ?error, "Health check failed");
+            DependencyStatus::Unhealthy
+        }
+        Err(_) => {
+            logger::error!(dependency = name, "Health check timed out");
+            DependencyStatus::Unhealthy
+        }
+    }
+}
+
+/// Reports whether the router can serve traffic, by probing the database and Redis. Responds
+/// with `503 Service Unavailable` naming the unhealthy dependencies when either probe fails.
+#[instrument(skip_all)]
+pub async fn readiness(state: web::Data<AppState>) -> HttpResponse {
+    metrics::HEALTH_METRIC.add(&metrics::CONTEXT, 1, &[]);
+    let response = ReadinessResponse {
+        database: check_dependency("database", state.store.health_check_db()).await,
+        redis: check_dependency("redis", state.store.health_check_redis()).await,
+    };
+
+    if response.is_ready() {
+        HttpResponse::Ok().json(response)
+    } else {
+        HttpResponse::ServiceUnavailable().json(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use actix_web::{body, http::StatusCode};
+
+    use super::*;
+    use crate::db::StorageImpl;
+
+    #[actix_rt::test]
+    async fn test_readiness_reports_unreachable_redis() {
+        let mut state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        // Closing the connection pool leaves the router without a reachable Redis
+        state.store.close().await;
+
+        let response = readiness(web::Data::new(state)).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "database": "healthy", "redis": "unhealthy" })
+        );
+    }
+}
@@ -1,15 +1,57 @@
 use once_cell::sync::Lazy;
+pub use router_env::opentelemetry::KeyValue;
 use router_env::opentelemetry::{
     global,
-    metrics::{Counter, Meter},
+    metrics::{Counter, Histogram, Meter, ObservableGauge},
     Context,
 };
 
 pub static CONTEXT: Lazy<Context> = Lazy::new(Context::current);
-static GLOBAL_METER: Lazy<Meter> = Lazy::new(|| global::meter("ROUTER_API"));
+pub(crate) static GLOBAL_METER: Lazy<Meter> = Lazy::new(|| global::meter("ROUTER_API"));
 
 pub(crate) static HEALTH_METRIC: Lazy<Counter<u64>> =
     Lazy::new(|| GLOBAL_METER.u64_counter("HEALTH_API").init());
 
 pub(crate) static KV_MISS: Lazy<Counter<u64>> =
     Lazy::new(|| GLOBAL_METER.u64_counter("KV_MISS").init());
+
+pub(crate) static CONNECTOR_FAILOVER: Lazy<Counter<u64>> =
+    Lazy::new(|| GLOBAL_METER.u64_counter("CONNECTOR_FAILOVER").init());
+
+pub(crate) static STALE_PAYMENT_SYNC_ABANDONED: Lazy<Counter<u64>> = Lazy::new(|| {
+    GLOBAL_METER
+        .u64_counter("STALE_PAYMENT_SYNC_ABANDONED")
+        .init()
+});
+
+// Time in (ms) milliseconds
+pub(crate) static CONNECTOR_REQUEST_TIME: Lazy<Histogram<f64>> =
+    Lazy::new(|| GLOBAL_METER.f64_histogram("CONNECTOR_REQUEST_TIME").init());
+
+pub(crate) static CONNECTOR_REQUEST_SUCCESS: Lazy<Counter<u64>> =
+    Lazy::new(|| GLOBAL_METER.u64_counter("CONNECTOR_REQUEST_SUCCESS").init());
+
+pub(crate) static CONNECTOR_REQUEST_FAILURE: Lazy<Counter<u64>> =
+    Lazy::new(|| GLOBAL_METER.u64_counter("CONNECTOR_REQUEST_FAILURE").init());
+
+pub(crate) static DB_POOL_CONNECTIONS_IN_USE: Lazy<ObservableGauge<u64>> = Lazy::new(|| {
+    GLOBAL_METER
+        .u64_observable_gauge("DB_POOL_CONNECTIONS_IN_USE")
+        .init()
+});
+
+pub(crate) static DB_POOL_CONNECTIONS_IDLE: Lazy<ObservableGauge<u64>> = Lazy::new(|| {
+    GLOBAL_METER
+        .u64_observable_gauge("DB_POOL_CONNECTIONS_IDLE")
+        .init()
+});
+
+// Time in (ms) milliseconds
+pub(crate) static DB_CONNECTION_WAIT_TIME: Lazy<Histogram<f64>> =
+    Lazy::new(|| GLOBAL_METER.f64_histogram("DB_CONNECTION_WAIT_TIME").init());
+
+pub(crate) static DB_CONNECTION_ACQUIRE_TIMEOUT: Lazy<Counter<u64>> = Lazy::new(|| {
+    GLOBAL_METER
+        .u64_counter("DB_CONNECTION_ACQUIRE_TIMEOUT")
+        .init()
+});
@@ -0,0 +1,11 @@
+use actix_web::HttpResponse;
+use router_env::{instrument, tracing};
+
+use crate::openapi::ApiDoc;
+
+/// Serves the OpenAPI specification of the API, so that integrators can look up the shapes of the
+/// requests and responses.
+#[instrument(skip_all)]
+pub async fn openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(<ApiDoc as utoipa::OpenApi>::openapi())
+}
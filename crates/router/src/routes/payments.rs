@@ -4,7 +4,11 @@ use router_env::{instrument, tracing, Flow};
 
 use crate::{
     self as app,
-    core::{errors::http_not_implemented, payments},
+    core::{
+        errors::{self, http_not_implemented},
+        payments,
+    },
+    headers,
     services::{api, authentication as auth},
     types::api::{self as api_types, enums as api_enums, payments as payment_types},
 };
@@ -13,13 +17,18 @@ use crate::{
 
 ///
 /// To process a payment you will have to create a payment, attach a payment method and confirm. Depending on the user journey you wish to achieve, you may opt to all the steps in a single request or in a sequence of API request using following APIs: (i) Payments - Update, (ii) Payments - Confirm, and (iii) Payments - Capture
+///
+/// A request sent with an `Idempotency-Key` header that was used before returns the payment created by the original request.
 #[utoipa::path(
     post,
     path = "/payments",
     request_body=PaymentsRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Key identifying the request, so that a retried request does not create a second payment")
+    ),
     responses(
         (status = 200, description = "Payment created", body = PaymentsResponse),
-        (status = 400, description = "Missing Mandatory fields")
+        (status = 400, description = "Missing Mandatory fields", body = ErrorResponseBody)
     ),
     tag = "Payments",
     operation_id = "Create a Payment"
@@ -37,17 +46,43 @@ pub async fn payments_create(
         return http_not_implemented();
     };
 
+    let idempotency_key = match req
+        .headers()
+        .get(headers::IDEMPOTENCY_KEY)
+        .map(|value| value.to_str())
+        .transpose()
+    {
+        Ok(idempotency_key) => idempotency_key.map(str::to_owned),
+        // Ignoring the key would let a retried request create a second payment
+        Err(_) => {
+            return api::log_and_return_error_response(report!(
+                errors::ApiErrorResponse::InvalidRequestData {
+                    message: "Idempotency-Key header must only contain visible ASCII characters"
+                        .to_string(),
+                }
+            ))
+        }
+    };
+
     api::server_wrap(
         state.get_ref(),
         &req,
         payload,
         |state, merchant_account, req| {
-            authorize_verify_select(
-                payments::PaymentCreate,
+            payments::idempotency::create_payment_with_idempotency_key(
                 state,
                 merchant_account,
                 req,
-                api::AuthFlow::Merchant,
+                idempotency_key.as_deref(),
+                |merchant_account, req| {
+                    authorize_verify_select(
+                        payments::PaymentCreate,
+                        state,
+                        merchant_account,
+                        req,
+                        api::AuthFlow::Merchant,
+                    )
+                },
             )
         },
         &auth::ApiKeyAuth,
@@ -428,6 +463,40 @@ pub async fn payments_redirect_response(
     .await
 }
 
+/// Some connectors (e.g. 3DS access control servers) post the authentication result as form data
+/// instead of appending it to the return url.
+#[instrument(skip_all)]
+pub async fn payments_redirect_response_with_form_data(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    form_payload: String,
+    path: web::Path<(String, String, String)>,
+) -> impl Responder {
+    let (payment_id, merchant_id, connector) = path.into_inner();
+
+    let payload = payment_types::PaymentsRetrieveRequest {
+        resource_id: payment_types::PaymentIdType::PaymentIntentId(payment_id),
+        merchant_id: Some(merchant_id.clone()),
+        force_sync: true,
+        param: Some(form_payload),
+        connector: Some(connector),
+    };
+    api::server_wrap(
+        state.get_ref(),
+        &req,
+        payload,
+        |state, merchant_account, req| {
+            payments::handle_payments_redirect_response::<api_types::PSync>(
+                state,
+                merchant_account,
+                req,
+            )
+        },
+        &auth::MerchantIdAuth(merchant_id),
+    )
+    .await
+}
+
 // Payments - Cancel
 
 ///
@@ -488,6 +557,7 @@ pub async fn payments_cancel(
         ("customer_id" = String, Query, description = "The identifier for the customer"),
         ("starting_after" = String, Query, description = "A cursor for use in pagination, fetch the next list after some object"),
         ("ending_before" = String, Query, description = "A cursor for use in pagination, fetch the previous list before some object"),
+        ("cursor" = String, Query, description = "The next_cursor of the previous page, to fetch the page following it"),
         ("limit" = i64, Query, description = "Limit on the number of objects to return"),
         ("created" = PrimitiveDateTime, Query, description = "The time at which payment is created"),
         ("created_lt" = PrimitiveDateTime, Query, description = "Time less than the payment created time"),
@@ -573,3 +643,39 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use actix_web::http::header::HeaderValue;
+
+    use super::*;
+    use crate::db::StorageImpl;
+
+    #[actix_rt::test]
+    async fn test_non_ascii_idempotency_key_is_rejected() {
+        let state = app::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let req = actix_web::test::TestRequest::post()
+            .insert_header((
+                headers::IDEMPOTENCY_KEY,
+                HeaderValue::from_bytes(b"key_\xff").unwrap(),
+            ))
+            .to_http_request();
+
+        let response = payments_create(
+            web::Data::new(state),
+            req.clone(),
+            web::Json(payment_types::PaymentsRequest::default()),
+        )
+        .await
+        .respond_to(&req);
+        assert_eq!(
+            response.status(),
+            actix_web::http::StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+}
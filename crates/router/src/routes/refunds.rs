@@ -18,7 +18,7 @@ use crate::{
     request_body=RefundRequest,
     responses(
         (status = 200, description = "Refund created", body = RefundResponse),
-        (status = 400, description = "Missing Mandatory fields")
+        (status = 400, description = "Missing Mandatory fields", body = ErrorResponseBody)
     ),
     tag = "Refunds",
     operation_id = "Create a Refund"
@@ -40,6 +40,38 @@ pub async fn refunds_create(
     .await
 }
 
+// Refunds - Bulk Create
+
+///
+/// To create refunds against several processed payments at once. The outcome of each refund is reported in the response, refunds that could not be created do not fail the others
+#[utoipa::path(
+    post,
+    path = "/refunds/bulk",
+    request_body=BulkRefundRequest,
+    responses(
+        (status = 200, description = "Refunds processed", body = BulkRefundResponse),
+        (status = 400, description = "Too many refunds in the request", body = ErrorResponseBody)
+    ),
+    tag = "Refunds",
+    operation_id = "Create Refunds in Bulk"
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RefundsBulkCreate))]
+// #[post("/bulk")]
+pub async fn refunds_bulk_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<refunds::BulkRefundRequest>,
+) -> HttpResponse {
+    api::server_wrap(
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        bulk_refund_create_core,
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
 // Refunds - Retrieve
 
 ///
@@ -127,6 +159,7 @@ pub async fn refunds_update(
     params(
         ("payment_id" = String, Query, description = "The identifier for the payment"),
         ("limit" = i64, Query, description = "Limit on the number of objects to return"),
+        ("cursor" = String, Query, description = "The next_cursor of the previous page, to fetch the page following it"),
         ("created" = PrimitiveDateTime, Query, description = "The time at which refund is created"),
         ("created_lt" = PrimitiveDateTime, Query, description = "Time less than the refund created time"),
         ("created_gt" = PrimitiveDateTime, Query, description = "Time greater than the refund created time"),
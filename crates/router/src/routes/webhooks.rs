@@ -1,10 +1,11 @@
-use actix_web::{web, HttpRequest, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
     core::webhooks,
     services::{api, authentication as auth},
+    types::api::webhooks as webhook_types,
 };
 
 #[instrument(skip_all, fields(flow = ?Flow::IncomingWebhookReceive))]
@@ -27,3 +28,78 @@ pub async fn receive_incoming_webhook(
     )
     .await
 }
+
+// Webhooks - List Delivery Attempts
+
+///
+/// To list the attempts at delivering outgoing webhooks to the merchant, optionally for a single payment
+#[utoipa::path(
+    get,
+    path = "/webhooks/attempts",
+    params(
+        ("payment_id" = String, Query, description = "The identifier for the payment"),
+        ("limit" = i64, Query, description = "Limit on the number of objects to return")
+    ),
+    responses(
+        (status = 200, description = "List of webhook delivery attempts", body = WebhookDeliveryAttemptListResponse)
+    ),
+    tag = "Webhooks",
+    operation_id = "List Webhook Delivery Attempts"
+)]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookDeliveryAttemptsList))]
+// #[get("/attempts")]
+pub async fn webhook_delivery_attempts_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<webhook_types::WebhookDeliveryAttemptListRequest>,
+) -> HttpResponse {
+    api::server_wrap(
+        state.get_ref(),
+        &req,
+        payload.into_inner(),
+        |state, merchant_account, req| {
+            webhooks::list_webhook_delivery_attempts(&*state.store, merchant_account, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+// Webhooks - Redeliver
+
+///
+/// To deliver an outgoing webhook whose delivery failed to the merchant again, recorded as a new delivery attempt
+#[utoipa::path(
+    post,
+    path = "/webhooks/{merchant_id}/events/{event_id}/redeliver",
+    params(
+        ("merchant_id" = String, Path, description = "The identifier for the merchant account"),
+        ("event_id" = String, Path, description = "The identifier for the webhook event")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery attempt of the redelivery", body = WebhookDeliveryAttemptResponse),
+        (status = 400, description = "No failed delivery of the webhook event")
+    ),
+    tag = "Webhooks",
+    operation_id = "Redeliver an Outgoing Webhook"
+)]
+#[instrument(skip_all, fields(flow = ?Flow::OutgoingWebhookRedeliver))]
+// #[post("/{merchant_id}/events/{event_id}/redeliver")]
+pub async fn outgoing_webhook_redeliver(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (merchant_id, event_id) = path.into_inner();
+
+    api::server_wrap(
+        state.get_ref(),
+        &req,
+        (&merchant_id, &event_id),
+        |state, _, (merchant_id, event_id)| {
+            webhooks::redeliver_outgoing_webhook(state, merchant_id, event_id)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
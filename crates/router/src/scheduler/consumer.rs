@@ -1,14 +1,11 @@
 // TODO: Figure out what to log
 
-use std::{
-    fmt,
-    sync::{self, atomic},
-};
+use std::sync::{self, atomic};
 
 use common_utils::signals::{get_allowed_signals, oneshot};
 use error_stack::{IntoReport, ResultExt};
 use futures::future;
-use redis_interface::{RedisConnectionPool, RedisEntryId};
+use redis_interface::{DeadLetterPolicy, RedisConnectionPool, RedisEntryId, StreamConsumer};
 use router_env::{instrument, tracing};
 use time::PrimitiveDateTime;
 use uuid::Uuid;
@@ -128,7 +125,12 @@ pub async fn consumer_operations(
 
     let mut tasks = state
         .store
-        .fetch_consumer_tasks(&stream_name, &group_name, &consumer_name)
+        .fetch_consumer_tasks(
+            &stream_name,
+            &group_name,
+            &consumer_name,
+            &settings.dead_letter_policy(),
+        )
         .await?;
 
     logger::info!("{} picked {} tasks", consumer_name, tasks.len());
@@ -156,12 +158,14 @@ pub async fn consumer_operations(
 #[instrument(skip(db, redis_conn))]
 pub async fn fetch_consumer_tasks(
     db: &dyn StorageInterface,
-    redis_conn: &RedisConnectionPool,
+    redis_conn: sync::Arc<RedisConnectionPool>,
     stream_name: &str,
     group_name: &str,
     consumer_name: &str,
+    dead_letter_policy: &DeadLetterPolicy,
 ) -> CustomResult<Vec<storage::ProcessTracker>, errors::ProcessTrackerError> {
-    let batches = pt_utils::get_batches(redis_conn, stream_name, group_name, consumer_name).await?;
+    let consumer = StreamConsumer::new(redis_conn, stream_name, group_name, consumer_name);
+    let batches = pt_utils::get_batches(&consumer, dead_letter_policy).await?;
 
     let mut tasks = batches.into_iter().fold(Vec::new(), |mut acc, batch| {
         acc.extend_from_slice(
@@ -230,15 +234,32 @@ pub async fn run_executor<'a>(
     metrics::TASK_PROCESSED.add(&metrics::CONTEXT, 1, &[]);
 }
 
+/// Reschedules a process that failed with a transient connector error, until its retries are
+/// exhausted. Processes that failed with any other error are finished.
 #[instrument(skip_all)]
-pub async fn consumer_error_handler<E: fmt::Display + fmt::Debug>(
+pub async fn consumer_error_handler(
     state: &AppState,
     process: storage::ProcessTracker,
-    error: E,
+    error: errors::ProcessTrackerError,
 ) -> CustomResult<(), errors::ProcessTrackerError> {
-    logger::error!(pt.name = ?process.name, pt.id = %process.id, ?error, "ERROR: Failed while executing workflow");
-
     let db: &dyn StorageInterface = &*state.store;
+    if error.get_connector_error_type() == Some(errors::ConnectorErrorType::Transient) {
+        logger::warn!(pt.name = ?process.name, pt.id = %process.id, ?error, "Transient connector error while executing workflow");
+        let schedule_time = pt_utils::get_time_from_delta(pt_utils::get_default_schedule_time(
+            process.retry_count + 1,
+        ));
+        return match schedule_time {
+            Some(schedule_time) => process.retry(db, schedule_time).await,
+            None => {
+                process
+                    .finish_with_status(db, "RETRIES_EXCEEDED".to_string())
+                    .await
+            }
+        }
+        .into_report();
+    }
+
+    logger::error!(pt.name = ?process.name, pt.id = %process.id, ?error, "ERROR: Failed while executing workflow");
     db.process_tracker_update_process_status_by_ids(
         vec![process.id],
         storage::ProcessTrackerUpdate::StatusUpdate {
@@ -5,7 +5,7 @@ use std::{
 
 use error_stack::{report, ResultExt};
 use futures::StreamExt;
-use redis_interface::{RedisConnectionPool, RedisEntryId};
+use redis_interface::{DeadLetterPolicy, RedisEntryId, StreamConsumer};
 use router_env::opentelemetry;
 use tokio::sync::oneshot;
 use uuid::Uuid;
@@ -169,60 +169,64 @@ pub fn divide_into_batches(
         })
 }
 
+/// Reads the next batch from the stream, along with any batches whose processing was left
+/// incomplete by an earlier attempt. Entries that cannot be parsed into a batch are left pending
+/// to be retried, and are moved to the dead-letter stream once they exceed the allowed
+/// deliveries.
 pub async fn get_batches(
-    conn: &RedisConnectionPool,
-    stream_name: &str,
-    group_name: &str,
-    consumer_name: &str,
+    consumer: &StreamConsumer,
+    dead_letter_policy: &DeadLetterPolicy,
 ) -> CustomResult<Vec<ProcessTrackerBatch>, errors::ProcessTrackerError> {
-    let response = conn
-        .stream_read_with_options(
-            stream_name,
-            RedisEntryId::UndeliveredEntryID,
-            // Update logic for collecting to Vec and flattening, if count > 1 is provided
-            Some(1),
-            None,
-            Some((group_name, consumer_name)),
-        )
+    let mut entries = consumer
+        .claim_stuck_entries(dead_letter_policy, 1)
         .await
         .map_err(|error| {
-            logger::warn!(%error, "Warning: finding batch in stream");
+            logger::error!(%error, "Error claiming stuck batches in stream");
             error.change_context(errors::ProcessTrackerError::BatchNotFound)
         })?;
+    entries.extend(
+        // Update logic for collecting to Vec and flattening, if count > 1 is provided
+        consumer.read(Some(1), None).await.map_err(|error| {
+            logger::warn!(%error, "Warning: finding batch in stream");
+            error.change_context(errors::ProcessTrackerError::BatchNotFound)
+        })?,
+    );
     metrics::BATCHES_CONSUMED.add(&metrics::CONTEXT, 1, &[]);
 
-    let (batches, entry_ids): (Vec<Vec<ProcessTrackerBatch>>, Vec<Vec<String>>) = response.into_values().map(|entries| {
-        entries.into_iter().try_fold(
-            (Vec::new(), Vec::new()),
-            |(mut batches, mut entry_ids), entry| {
-                // Redis entry ID
-                entry_ids.push(entry.0);
-                // Value HashMap
-                batches.push(ProcessTrackerBatch::from_redis_stream_entry(entry.1)?);
-
-                Ok((batches, entry_ids))
-            },
-        )
-    }).collect::<CustomResult<Vec<(Vec<ProcessTrackerBatch>, Vec<String>)>, errors::ProcessTrackerError>>()?
-    .into_iter()
-    .unzip();
-    // Flattening the Vec's since the count provided above is 1. This needs to be updated if a
-    // count greater than 1 is provided.
-    let batches = batches.into_iter().flatten().collect::<Vec<_>>();
-    let entry_ids = entry_ids.into_iter().flatten().collect::<Vec<_>>();
+    let mut batches = Vec::with_capacity(entries.len());
+    let mut entry_ids = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match ProcessTrackerBatch::from_redis_stream_entry(entry.fields.clone()) {
+            Ok(batch) => {
+                batches.push(batch);
+                entry_ids.push(entry.id);
+            }
+            Err(error) => {
+                logger::error!(?error, entry_id = %entry.id, "Error parsing batch from stream");
+                if let Err(error) = consumer
+                    .fail(&entry, dead_letter_policy, &format!("{error:?}"))
+                    .await
+                {
+                    logger::error!(%error, entry_id = %entry.id, "Error dead-lettering batch");
+                }
+            }
+        }
+    }
+    if entry_ids.is_empty() {
+        return Ok(batches);
+    }
 
-    conn.stream_acknowledge_entries(stream_name, group_name, entry_ids.clone())
+    consumer
+        .acknowledge(entry_ids.clone())
         .await
         .map_err(|error| {
             logger::error!(%error, "Error acknowledging batch in stream");
             error.change_context(errors::ProcessTrackerError::BatchUpdateFailed)
         })?;
-    conn.stream_delete_entries(stream_name, entry_ids.clone())
-        .await
-        .map_err(|error| {
-            logger::error!(%error, "Error deleting batch from stream");
-            error.change_context(errors::ProcessTrackerError::BatchDeleteFailed)
-        })?;
+    consumer.delete(entry_ids).await.map_err(|error| {
+        logger::error!(%error, "Error deleting batch from stream");
+        error.change_context(errors::ProcessTrackerError::BatchDeleteFailed)
+    })?;
 
     Ok(batches)
 }
@@ -314,6 +318,12 @@ pub fn get_schedule_time(
     }
 }
 
+/// Same as [`get_schedule_time`], for processes that have no connector specific schedule.
+pub fn get_default_schedule_time(retry_count: i32) -> Option<i32> {
+    // The default mapping has no merchant specific schedules
+    get_schedule_time(process_data::ConnectorPTMapping::default(), "", retry_count)
+}
+
 fn get_delay<'a>(
     retry_count: i32,
     mut array: impl Iterator<Item = (&'a i32, &'a i32)>,
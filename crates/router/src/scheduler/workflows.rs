@@ -4,8 +4,13 @@ use serde::{Deserialize, Serialize};
 use strum::EnumString;
 
 use crate::{core::errors, routes::AppState, scheduler::consumer, types::storage};
+pub mod auto_capture;
+pub mod connector_response_retention;
+pub mod outgoing_webhook_retry;
 pub mod payment_sync;
+pub mod pending_refunds_sync;
 pub mod refund_router;
+pub mod stale_payments_sync;
 
 macro_rules! runners {
     ($($body:tt),*) => {
@@ -44,7 +49,12 @@ macro_rules! as_item {
 
 runners! {
     PaymentsSyncWorkflow,
-    RefundWorkflowRouter
+    RefundWorkflowRouter,
+    OutgoingWebhookRetryWorkflow,
+    PendingRefundsSyncWorkflow,
+    StalePaymentsSyncWorkflow,
+    AutoCaptureWorkflow,
+    ConnectorResponseRetentionWorkflow
 }
 
 #[async_trait]
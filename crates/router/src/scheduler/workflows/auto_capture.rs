@@ -0,0 +1,276 @@
+use super::{AutoCaptureWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::payments::auto_capture, errors, routes::AppState, scheduler::consumer, types::storage,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for AutoCaptureWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        auto_capture::auto_capture_workflow(state, process).await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{
+        configs::settings::Settings,
+        core::payments::{auto_capture::AutoCaptureAction, helpers},
+        db::StorageImpl,
+        routes,
+        scheduler::utils as pt_utils,
+        types::storage::enums,
+    };
+
+    async fn create_authorized_payment(
+        state: &AppState,
+        authorized_at: time::PrimitiveDateTime,
+        capture_on: time::PrimitiveDateTime,
+        intent_status: enums::IntentStatus,
+    ) -> storage::PaymentAttempt {
+        let mut payment =
+            helpers::get_test_payment("pay_1", "dummy", enums::AttemptStatus::Authorized);
+        payment.payment_intent.status = intent_status;
+        payment.connector_response.created_at = authorized_at;
+        payment.connector_response.modified_at = authorized_at;
+        payment.payment_attempt = storage::PaymentAttemptNew {
+            capture_method: Some(enums::CaptureMethod::Manual),
+            capture_on: Some(capture_on),
+            // Created longer than the authorization validity before the authorization, so that
+            // only the authorization time keeps the authorization from expiring
+            created_at: Some(authorized_at.saturating_sub(time::Duration::days(30))),
+            modified_at: Some(authorized_at),
+            authorized_at: Some(authorized_at),
+            ..payment.payment_attempt
+        };
+        let (_, payment_attempt) = helpers::insert_test_payment(&*state.store, payment).await;
+        payment_attempt
+    }
+
+    async fn schedule_and_fire_auto_capture(
+        state: &AppState,
+        payment_attempt: &storage::PaymentAttempt,
+    ) -> storage::ProcessTracker {
+        let db = &*state.store;
+        let capture_on = payment_attempt
+            .capture_on
+            .expect("Payment attempt without capture_on");
+        auto_capture::add_auto_capture_task(db, payment_attempt, capture_on)
+            .await
+            .expect("Failed to schedule auto capture");
+
+        let process_tracker_id = pt_utils::get_process_tracker_id(
+            auto_capture::AUTO_CAPTURE_RUNNER,
+            auto_capture::AUTO_CAPTURE_TASK,
+            &payment_attempt.attempt_id,
+            &payment_attempt.merchant_id,
+        );
+        let process = db
+            .find_process_by_id(&process_tracker_id)
+            .await
+            .expect("Failed to fetch auto capture task")
+            .expect("Auto capture task was not scheduled");
+        assert_eq!(process.schedule_time, Some(capture_on));
+
+        AutoCaptureWorkflow
+            .execute_workflow(state, process)
+            .await
+            .expect("Failed to execute auto capture");
+        db.find_process_by_id(&process_tracker_id)
+            .await
+            .expect("Failed to fetch auto capture task")
+            .expect("Auto capture task was removed")
+    }
+
+    #[test]
+    fn test_auto_capture_action() {
+        let authorized_at = common_utils::date_time::now();
+        let validity = 3600;
+        let before_expiry = authorized_at.saturating_add(time::Duration::minutes(30));
+        let after_expiry = authorized_at.saturating_add(time::Duration::hours(2));
+
+        assert_eq!(
+            auto_capture::get_auto_capture_action(
+                enums::IntentStatus::RequiresCapture,
+                authorized_at,
+                before_expiry,
+                validity
+            ),
+            AutoCaptureAction::Capture
+        );
+        assert_eq!(
+            auto_capture::get_auto_capture_action(
+                enums::IntentStatus::RequiresCapture,
+                authorized_at,
+                after_expiry,
+                validity
+            ),
+            AutoCaptureAction::ExpireAuthorization
+        );
+        assert_eq!(
+            auto_capture::get_auto_capture_action(
+                enums::IntentStatus::Cancelled,
+                authorized_at,
+                before_expiry,
+                validity
+            ),
+            AutoCaptureAction::Skip
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_auto_capture_of_voided_payment_is_skipped() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let current_time = common_utils::date_time::now();
+        let payment_attempt = create_authorized_payment(
+            &state,
+            current_time,
+            current_time,
+            enums::IntentStatus::Cancelled,
+        )
+        .await;
+
+        let process = schedule_and_fire_auto_capture(&state, &payment_attempt).await;
+        assert_eq!(process.status, enums::ProcessTrackerStatus::Finish);
+        assert_eq!(process.business_status, "SKIPPED_BY_PT");
+    }
+
+    #[actix_rt::test]
+    async fn test_auto_capture_of_expired_authorization_fails() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let authorization_validity = state.conf.auto_capture.authorization_validity;
+        let current_time = common_utils::date_time::now();
+        let authorized_at = current_time
+            .saturating_sub(time::Duration::seconds(authorization_validity))
+            .saturating_sub(time::Duration::days(1));
+        let payment_attempt = create_authorized_payment(
+            &state,
+            authorized_at,
+            current_time,
+            enums::IntentStatus::RequiresCapture,
+        )
+        .await;
+
+        let process = schedule_and_fire_auto_capture(&state, &payment_attempt).await;
+        assert_eq!(process.status, enums::ProcessTrackerStatus::Finish);
+        assert_eq!(process.business_status, "AUTHORIZATION_EXPIRED");
+
+        let db = &*state.store;
+        let payment_attempt = db
+            .find_payment_attempt_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch payment attempt");
+        assert_eq!(payment_attempt.status, enums::AttemptStatus::CaptureFailed);
+        assert_eq!(
+            payment_attempt.error_code.as_deref(),
+            Some(auto_capture::AUTHORIZATION_EXPIRED_ERROR_CODE)
+        );
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch payment intent");
+        assert_eq!(payment_intent.status, enums::IntentStatus::Failed);
+    }
+
+    #[cfg(feature = "dummy_connector")]
+    #[actix_rt::test]
+    async fn test_scheduled_auto_capture_captures_the_payment() {
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/payments/dummy_pay_1/capture"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "id": "dummy_pay_1", "status": "succeeded" }),
+                ),
+            )
+            .expect(1)
+            .mount(&connector_server)
+            .await;
+
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.connectors.dummyconnector.base_url = format!("{}/", connector_server.uri());
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let current_time = common_utils::date_time::now();
+        let payment_attempt = create_authorized_payment(
+            &state,
+            current_time,
+            current_time,
+            enums::IntentStatus::RequiresCapture,
+        )
+        .await;
+        state
+            .store
+            .insert_merchant_connector_account(storage::MerchantConnectorAccountNew {
+                merchant_id: Some("merchant_1".to_string()),
+                connector_name: Some("dummy".to_string()),
+                connector_account_details: Some(masking::Secret::new(serde_json::json!({
+                    "auth_type": "HeaderKey",
+                    "api_key": "dummy_api_key",
+                }))),
+                merchant_connector_id: "mca_1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to insert merchant connector account");
+
+        let process = schedule_and_fire_auto_capture(&state, &payment_attempt).await;
+        assert_eq!(process.status, enums::ProcessTrackerStatus::Finish);
+        assert_eq!(process.business_status, "CAPTURED_BY_PT");
+
+        let db = &*state.store;
+        let payment_attempt = db
+            .find_payment_attempt_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch payment attempt");
+        assert_eq!(payment_attempt.status, enums::AttemptStatus::Charged);
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_1",
+                "merchant_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch payment intent");
+        assert_eq!(payment_intent.status, enums::IntentStatus::Succeeded);
+    }
+}
@@ -0,0 +1,128 @@
+use super::{ConnectorResponseRetentionWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::payments::response_retention, errors, logger::error, routes::AppState, types::storage,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for ConnectorResponseRetentionWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        response_retention::clear_expired_connector_responses_workflow(state, process).await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, "Failed while executing workflow");
+        // Keep the job recurring even if this run failed
+        let schedule_time = common_utils::date_time::now().saturating_add(time::Duration::seconds(
+            state.conf.connector_response_retention.interval,
+        ));
+        Ok(
+            response_retention::reschedule_connector_response_retention_task(
+                &*state.store,
+                process,
+                schedule_time,
+            )
+            .await?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use crate::{
+        configs::settings::Settings,
+        core::payments::response_retention,
+        db::StorageImpl,
+        routes,
+        types::storage::{self, enums, ProcessTrackerExt},
+    };
+
+    #[actix_rt::test]
+    async fn test_expired_connector_responses_are_cleared() {
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.connector_response_retention.retention_period = 24 * 60 * 60;
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let db = &*state.store;
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+
+        let current_time = common_utils::date_time::now();
+        for (attempt_id, modified_at) in [
+            (
+                "pay_old_1",
+                current_time.saturating_sub(time::Duration::days(2)),
+            ),
+            ("pay_new_1", current_time),
+        ] {
+            db.insert_payment_attempt(
+                storage::PaymentAttemptNew {
+                    payment_id: attempt_id.to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    attempt_id: attempt_id.to_string(),
+                    created_at: Some(modified_at),
+                    modified_at: Some(modified_at),
+                    connector_response_body: Some(r#"{"id":"txn_123"}"#.to_string()),
+                    ..Default::default()
+                },
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to insert payment attempt");
+        }
+
+        db.insert_process(
+            storage::ProcessTracker::make_process_tracker_new(
+                "retention_1".to_string(),
+                response_retention::CONNECTOR_RESPONSE_RETENTION_TASK,
+                response_retention::CONNECTOR_RESPONSE_RETENTION_RUNNER,
+                serde_json::json!({}),
+                current_time,
+            )
+            .expect("Failed to build process"),
+        )
+        .await
+        .expect("Failed to insert process");
+        let process = db
+            .find_process_by_id("retention_1")
+            .await
+            .expect("Failed to fetch process")
+            .expect("Process not found");
+
+        response_retention::clear_expired_connector_responses_workflow(&state, process)
+            .await
+            .expect("Failed to clear expired connector responses");
+
+        for (attempt_id, expected_body) in [
+            ("pay_old_1", None),
+            ("pay_new_1", Some(r#"{"id":"txn_123"}"#)),
+        ] {
+            let payment_attempt = db
+                .find_payment_attempt_by_merchant_id_attempt_id(
+                    "merchant_1",
+                    attempt_id,
+                    storage_scheme,
+                )
+                .await
+                .expect("Failed to fetch payment attempt");
+            assert_eq!(
+                payment_attempt.connector_response_body.as_deref(),
+                expected_body,
+                "{attempt_id}"
+            );
+        }
+    }
+}
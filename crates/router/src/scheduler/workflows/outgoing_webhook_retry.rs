@@ -0,0 +1,25 @@
+use super::{OutgoingWebhookRetryWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::webhooks as webhooks_core, errors, logger::error, routes::AppState, types::storage,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for OutgoingWebhookRetryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        webhooks_core::start_outgoing_webhook_retry_workflow(state, process).await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        _state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, "Failed while executing workflow");
+        Ok(())
+    }
+}
@@ -36,18 +36,36 @@ impl ProcessTrackerWorkflow for PaymentsSyncWorkflow {
             )
             .await?;
 
-        let (payment_data, _, _) = payment_flows::payments_operation_core::<api::PSync, _, _, _>(
-            state,
-            merchant_account.clone(),
-            operations::PaymentStatus,
-            tracking_data.clone(),
-            payment_flows::CallConnectorAction::Trigger,
-        )
-        .await?;
+        let (payment_data, _, _) =
+            match payment_flows::payments_operation_core::<api::PSync, _, _, _>(
+                state,
+                merchant_account.clone(),
+                operations::PaymentStatus,
+                tracking_data.clone(),
+                payment_flows::CallConnectorAction::Trigger,
+            )
+            .await
+            {
+                Ok(payment_data) => payment_data,
+                // Respect the connector's Retry-After instead of the regular sync schedule
+                Err(error) => match error.current_context() {
+                    errors::ApiErrorResponse::ConnectorRateLimited {
+                        retry_after: Some(retry_after),
+                    } => {
+                        let schedule_time =
+                            common_utils::date_time::now().saturating_add(time::Duration::seconds(
+                                i64::try_from(*retry_after).unwrap_or(i64::MAX),
+                            ));
+                        return process.retry(db, schedule_time).await;
+                    }
+                    _ => return Err(error.into()),
+                },
+            };
 
         let terminal_status = vec![
             enums::AttemptStatus::RouterDeclined,
             enums::AttemptStatus::Charged,
+            enums::AttemptStatus::Verified,
             enums::AttemptStatus::AutoRefunded,
             enums::AttemptStatus::Voided,
             enums::AttemptStatus::VoidFailed,
@@ -130,7 +148,77 @@ pub async fn retry_sync_task(
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use error_stack::report;
+
     use super::*;
+    use crate::db::StorageImpl;
+
+    async fn fail_payment_sync(
+        state: &AppState,
+        error: errors::ConnectorError,
+    ) -> storage::ProcessTracker {
+        let db = &*state.store;
+        let process = storage::ProcessTracker::make_process_tracker_new(
+            "sync_pay_1".to_string(),
+            "PAYMENTS_SYNC",
+            "PAYMENTS_SYNC_WORKFLOW",
+            api::PaymentsRetrieveRequest::default(),
+            common_utils::date_time::now(),
+        )
+        .expect("Failed to create payment sync task");
+        let process = db
+            .insert_process(process)
+            .await
+            .expect("Failed to insert payment sync task");
+
+        let error = errors::ProcessTrackerError::EApiErrorResponse(
+            report!(error).change_context(errors::ApiErrorResponse::InternalServerError),
+        );
+        PaymentsSyncWorkflow
+            .error_handler(state, process, error)
+            .await
+            .expect("Failed to handle payment sync error");
+        db.find_process_by_id("sync_pay_1")
+            .await
+            .expect("Failed to fetch payment sync task")
+            .expect("Payment sync task was removed")
+    }
+
+    #[actix_rt::test]
+    async fn test_transient_connector_error_reschedules_sync() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+
+        let process =
+            fail_payment_sync(&state, errors::ConnectorError::TemporarilyUnavailable).await;
+        assert_eq!(process.status, enums::ProcessTrackerStatus::Pending);
+        assert_eq!(process.retry_count, 1);
+        assert!(process
+            .schedule_time
+            .map_or(false, |schedule_time| schedule_time
+                > common_utils::date_time::now()));
+    }
+
+    #[actix_rt::test]
+    async fn test_permanent_connector_error_finishes_sync() {
+        let state = AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+
+        let process = fail_payment_sync(
+            &state,
+            errors::ConnectorError::ResponseDeserializationFailed,
+        )
+        .await;
+        assert_eq!(process.status, enums::ProcessTrackerStatus::Finish);
+        assert_eq!(process.business_status, "GLOBAL_ERROR");
+        assert_eq!(process.retry_count, 0);
+    }
 
     #[test]
     fn test_get_default_schedule_time() {
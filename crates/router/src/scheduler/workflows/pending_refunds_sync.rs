@@ -0,0 +1,213 @@
+use super::{PendingRefundsSyncWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::refunds as refund_flow, errors, logger::error, routes::AppState, types::storage,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for PendingRefundsSyncWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        refund_flow::sync_pending_refunds_workflow(state, process).await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, "Failed while executing workflow");
+        // Keep the job recurring even if this run failed
+        let schedule_time = common_utils::date_time::now()
+            .saturating_add(time::Duration::seconds(state.conf.refund.sync_interval));
+        Ok(
+            refund_flow::reschedule_pending_refunds_sync_task(
+                &*state.store,
+                process,
+                schedule_time,
+            )
+            .await?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{
+        configs::settings::Settings,
+        core::{errors, refunds as refund_flow},
+        db::{StorageImpl, StorageInterface},
+        routes,
+        types::storage::{self, enums},
+    };
+
+    async fn insert_refunds(
+        db: &dyn StorageInterface,
+        refunds: &[(&str, enums::RefundStatus)],
+    ) -> Vec<storage::Refund> {
+        let mut inserted_refunds = Vec::new();
+        for (refund_id, refund_status) in refunds {
+            let refund = db
+                .insert_refund(
+                    storage::RefundNew {
+                        refund_id: refund_id.to_string(),
+                        merchant_id: "merchant_1".to_string(),
+                        refund_status: *refund_status,
+                        sent_to_gateway: true,
+                        ..Default::default()
+                    },
+                    enums::MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .expect("Failed to insert refund");
+            inserted_refunds.push(refund);
+        }
+        inserted_refunds
+    }
+
+    #[actix_rt::test]
+    async fn test_only_pending_refunds_are_fetched_up_to_the_batch_size() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        // Not sent to the connector, there is nothing to sync
+        state
+            .store
+            .insert_refund(
+                storage::RefundNew {
+                    refund_id: "refund_0".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    refund_status: enums::RefundStatus::Pending,
+                    sent_to_gateway: false,
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to insert refund");
+        insert_refunds(
+            &*state.store,
+            &[
+                ("refund_1", enums::RefundStatus::Success),
+                ("refund_2", enums::RefundStatus::Pending),
+                ("refund_3", enums::RefundStatus::Failure),
+                ("refund_4", enums::RefundStatus::Pending),
+                ("refund_5", enums::RefundStatus::Pending),
+            ],
+        )
+        .await;
+
+        let pending_refunds = state
+            .store
+            .find_sent_refunds_by_status(enums::RefundStatus::Pending, 2)
+            .await
+            .expect("Failed to fetch pending refunds");
+        let refund_ids = pending_refunds
+            .iter()
+            .map(|refund| refund.refund_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(refund_ids, ["refund_2", "refund_4"]);
+    }
+
+    #[actix_rt::test]
+    async fn test_refunds_are_synced_with_bounded_concurrency() {
+        let state = routes::AppState::test_builder()
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let refunds = insert_refunds(
+            &*state.store,
+            &[
+                ("refund_1", enums::RefundStatus::Pending),
+                ("refund_2", enums::RefundStatus::Pending),
+                ("refund_3", enums::RefundStatus::Pending),
+                ("refund_4", enums::RefundStatus::Pending),
+                ("refund_5", enums::RefundStatus::Pending),
+            ],
+        )
+        .await;
+
+        let in_flight_syncs = AtomicUsize::new(0);
+        let max_in_flight_syncs = AtomicUsize::new(0);
+        // The sync of the third refund fails, which must not stop the other syncs
+        let synced_refunds_count = refund_flow::sync_refunds_concurrently(refunds, 2, |refund| {
+            let in_flight_syncs = &in_flight_syncs;
+            let max_in_flight_syncs = &max_in_flight_syncs;
+            async move {
+                let in_flight = in_flight_syncs.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight_syncs.fetch_max(in_flight, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                in_flight_syncs.fetch_sub(1, Ordering::SeqCst);
+                if refund.refund_id == "refund_3" {
+                    Err(errors::ApiErrorResponse::InternalServerError.into())
+                } else {
+                    Ok(refund)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(synced_refunds_count, 4);
+        assert_eq!(max_in_flight_syncs.load(Ordering::SeqCst), 2);
+    }
+
+    #[actix_rt::test]
+    async fn test_refunds_failing_to_sync_do_not_starve_other_refunds() {
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.refund.sync_batch_size = 2;
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        // The merchant account does not exist, so every sync fails
+        insert_refunds(
+            &*state.store,
+            &[
+                ("refund_1", enums::RefundStatus::Pending),
+                ("refund_2", enums::RefundStatus::Pending),
+                ("refund_3", enums::RefundStatus::Pending),
+            ],
+        )
+        .await;
+        refund_flow::add_pending_refunds_sync_task(&*state.store)
+            .await
+            .expect("Failed to schedule pending refunds sync task");
+        let process = state
+            .store
+            .find_process_by_id(&format!(
+                "{}_{}",
+                refund_flow::PENDING_REFUNDS_SYNC_RUNNER,
+                refund_flow::PENDING_REFUNDS_SYNC_TASK
+            ))
+            .await
+            .expect("Failed to fetch pending refunds sync task")
+            .expect("Pending refunds sync task was not scheduled");
+
+        refund_flow::sync_pending_refunds_workflow(&state, process)
+            .await
+            .expect("Failed to sync pending refunds");
+
+        let next_batch = state
+            .store
+            .find_sent_refunds_by_status(enums::RefundStatus::Pending, 2)
+            .await
+            .expect("Failed to fetch pending refunds");
+        let refund_ids = next_batch
+            .iter()
+            .map(|refund| refund.refund_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(refund_ids[0], "refund_3");
+    }
+}
@@ -0,0 +1,341 @@
+use super::{ProcessTrackerWorkflow, StalePaymentsSyncWorkflow};
+use crate::{core::payments::stale_sync, errors, logger::error, routes::AppState, types::storage};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for StalePaymentsSyncWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        stale_sync::sync_stale_payments_workflow(state, process).await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, "Failed while executing workflow");
+        // Keep the job recurring even if this run failed
+        let schedule_time = common_utils::date_time::now().saturating_add(time::Duration::seconds(
+            state.conf.stale_payments_sync.interval,
+        ));
+        Ok(
+            stale_sync::reschedule_stale_payments_sync_task(&*state.store, process, schedule_time)
+                .await?,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "dummy_connector"))]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use masking::Secret;
+
+    use crate::{
+        configs::settings::{Settings, StalePaymentsSync},
+        core::payments::{helpers, stale_sync},
+        db::StorageImpl,
+        routes,
+        types::storage::{self, enums},
+    };
+
+    async fn create_state(
+        connector_server: &wiremock::MockServer,
+        stale_payments_sync: StalePaymentsSync,
+    ) -> routes::AppState {
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.connectors.dummyconnector.base_url = format!("{}/", connector_server.uri());
+        conf.stale_payments_sync = stale_payments_sync;
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        let db = &*state.store;
+
+        db.insert_merchant(storage::MerchantAccountNew {
+            merchant_id: "merchant_1".to_string(),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to insert merchant account");
+        db.insert_merchant_connector_account(storage::MerchantConnectorAccountNew {
+            merchant_id: Some("merchant_1".to_string()),
+            connector_name: Some("dummy".to_string()),
+            connector_account_details: Some(Secret::new(serde_json::json!({
+                "auth_type": "HeaderKey",
+                "api_key": "dummy_api_key",
+            }))),
+            merchant_connector_id: "mca_1".to_string(),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to insert merchant connector account");
+
+        state
+    }
+
+    async fn run_stale_payments_sync(state: &routes::AppState) {
+        let db = &*state.store;
+        stale_sync::add_stale_payments_sync_task(db)
+            .await
+            .expect("Failed to add stale payments sync task");
+        let process = db
+            .find_process_by_id(&format!(
+                "{}_{}",
+                stale_sync::STALE_PAYMENTS_SYNC_RUNNER,
+                stale_sync::STALE_PAYMENTS_SYNC_TASK
+            ))
+            .await
+            .expect("Failed to fetch stale payments sync task")
+            .expect("Stale payments sync task was not added");
+        stale_sync::sync_stale_payments_workflow(state, process)
+            .await
+            .expect("Failed to sync stale payments");
+    }
+
+    fn get_stale_payment(
+        merchant_id: &str,
+        payment_id: &str,
+        modified_at: time::PrimitiveDateTime,
+    ) -> helpers::TestPayment {
+        let mut payment =
+            helpers::get_test_payment(payment_id, "dummy", enums::AttemptStatus::Authorizing);
+        payment.merchant_account = None;
+        payment.payment_intent.merchant_id = merchant_id.to_string();
+        payment.connector_response.merchant_id = merchant_id.to_string();
+        payment.connector_response.created_at = modified_at;
+        payment.connector_response.modified_at = modified_at;
+        payment.payment_attempt = storage::PaymentAttemptNew {
+            merchant_id: merchant_id.to_string(),
+            created_at: Some(modified_at),
+            modified_at: Some(modified_at),
+            ..payment.payment_attempt
+        };
+        payment
+    }
+
+    async fn insert_payment(
+        state: &routes::AppState,
+        merchant_id: &str,
+        payment_id: &str,
+        modified_at: time::PrimitiveDateTime,
+    ) {
+        let payment = get_stale_payment(merchant_id, payment_id, modified_at);
+        helpers::insert_test_payment(&*state.store, payment).await;
+    }
+
+    #[actix_rt::test]
+    async fn test_stale_authorizing_attempt_is_synced() {
+        // The connector reports the stale payment as charged
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/payments/dummy_pay_stale"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "id": "dummy_pay_stale", "status": "succeeded" }),
+            ))
+            .expect(1)
+            .mount(&connector_server)
+            .await;
+
+        let state = create_state(
+            &connector_server,
+            StalePaymentsSync {
+                batch_size: 20,
+                stale_after: 15 * 60,
+                ..Default::default()
+            },
+        )
+        .await;
+        let db = &*state.store;
+
+        let current_time = common_utils::date_time::now();
+        insert_payment(
+            &state,
+            "merchant_1",
+            "pay_stale",
+            current_time.saturating_sub(time::Duration::hours(1)),
+        )
+        .await;
+        insert_payment(&state, "merchant_1", "pay_recent", current_time).await;
+
+        run_stale_payments_sync(&state).await;
+
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+        let stale_attempt = db
+            .find_payment_attempt_by_merchant_id_attempt_id(
+                "merchant_1",
+                "pay_stale_1",
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to fetch payment attempt");
+        assert_eq!(stale_attempt.status, enums::AttemptStatus::Charged);
+        let stale_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                "pay_stale",
+                "merchant_1",
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to fetch payment intent");
+        assert_eq!(stale_intent.status, enums::IntentStatus::Succeeded);
+
+        let recent_attempt = db
+            .find_payment_attempt_by_merchant_id_attempt_id(
+                "merchant_1",
+                "pay_recent_1",
+                storage_scheme,
+            )
+            .await
+            .expect("Failed to fetch payment attempt");
+        assert_eq!(recent_attempt.status, enums::AttemptStatus::Authorizing);
+    }
+
+    #[actix_rt::test]
+    async fn test_attempts_failing_to_sync_do_not_starve_other_attempts() {
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/payments/dummy_pay_stale"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "id": "dummy_pay_stale", "status": "succeeded" }),
+            ))
+            .expect(1)
+            .mount(&connector_server)
+            .await;
+        // One attempt per run
+        let state = create_state(
+            &connector_server,
+            StalePaymentsSync {
+                batch_size: 1,
+                stale_after: 15 * 60,
+                ..Default::default()
+            },
+        )
+        .await;
+
+        // The merchant account of the oldest attempt does not exist, so its sync always fails
+        let current_time = common_utils::date_time::now();
+        insert_payment(
+            &state,
+            "merchant_2",
+            "pay_unknown_merchant",
+            current_time.saturating_sub(time::Duration::hours(2)),
+        )
+        .await;
+        insert_payment(
+            &state,
+            "merchant_1",
+            "pay_stale",
+            current_time.saturating_sub(time::Duration::hours(1)),
+        )
+        .await;
+
+        run_stale_payments_sync(&state).await;
+        run_stale_payments_sync(&state).await;
+
+        let stale_attempt = state
+            .store
+            .find_payment_attempt_by_merchant_id_attempt_id(
+                "merchant_1",
+                "pay_stale_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch payment attempt");
+        assert_eq!(stale_attempt.status, enums::AttemptStatus::Charged);
+    }
+
+    #[actix_rt::test]
+    async fn test_attempt_is_left_for_review_after_repeated_sync_failures() {
+        let connector_server = wiremock::MockServer::start().await;
+        // Attempts are stale again right after their failed sync
+        let state = create_state(
+            &connector_server,
+            StalePaymentsSync {
+                stale_after: 0,
+                max_sync_failures: 2,
+                ..Default::default()
+            },
+        )
+        .await;
+
+        // The merchant account of the attempt does not exist, so its sync always fails
+        insert_payment(
+            &state,
+            "merchant_2",
+            "pay_unknown_merchant",
+            common_utils::date_time::now().saturating_sub(time::Duration::hours(2)),
+        )
+        .await;
+
+        // The attempt is no longer picked up once it failed to sync twice
+        for expected_sync_failure_count in [1, 2, 2] {
+            run_stale_payments_sync(&state).await;
+            let payment_attempt = state
+                .store
+                .find_payment_attempt_by_merchant_id_attempt_id(
+                    "merchant_2",
+                    "pay_unknown_merchant_1",
+                    enums::MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .expect("Failed to fetch payment attempt");
+            assert_eq!(
+                payment_attempt.sync_failure_count,
+                expected_sync_failure_count
+            );
+            assert_eq!(payment_attempt.status, enums::AttemptStatus::Authorizing);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_timed_out_authorization_is_left_for_review() {
+        // The connector cannot be asked about a payment without its transaction ID
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&connector_server)
+            .await;
+        let state = create_state(
+            &connector_server,
+            StalePaymentsSync {
+                stale_after: 15 * 60,
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let mut payment = get_stale_payment(
+            "merchant_1",
+            "pay_timed_out",
+            common_utils::date_time::now().saturating_sub(time::Duration::hours(1)),
+        );
+        payment.payment_attempt.connector_transaction_id = None;
+        payment.connector_response.connector_transaction_id = None;
+        helpers::insert_test_payment(&*state.store, payment).await;
+
+        run_stale_payments_sync(&state).await;
+
+        let payment_attempt = state
+            .store
+            .find_payment_attempt_by_merchant_id_attempt_id(
+                "merchant_1",
+                "pay_timed_out_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .expect("Failed to fetch payment attempt");
+        assert_eq!(payment_attempt.status, enums::AttemptStatus::Authorizing);
+        assert_eq!(
+            payment_attempt.sync_failure_count,
+            state.conf.stale_payments_sync.max_sync_failures
+        );
+    }
+}
@@ -3,13 +3,14 @@ pub mod authentication;
 #[cfg(feature = "basilisk")]
 pub mod encryption;
 pub mod logger;
+pub mod rate_limiter;
 
 use std::sync::Arc;
 
 pub use self::api::*;
 #[cfg(feature = "basilisk")]
 pub use self::encryption::*;
-use crate::connection::{diesel_make_pg_pool, PgPool};
+use crate::connection::{diesel_make_pg_pool, register_pool_metrics, PgPool};
 
 #[derive(Clone)]
 pub struct Store {
@@ -30,10 +31,19 @@ pub(crate) struct StoreConfig {
 
 impl Store {
     pub async fn new(config: &crate::configs::settings::Settings, test_transaction: bool) -> Self {
+        let master_pool = diesel_make_pg_pool(&config.master_database, test_transaction).await;
+        register_pool_metrics(&master_pool, "master");
+        #[cfg(feature = "olap")]
+        let replica_pool = {
+            let replica_pool =
+                diesel_make_pg_pool(&config.replica_database, test_transaction).await;
+            register_pool_metrics(&replica_pool, "replica");
+            replica_pool
+        };
         Self {
-            master_pool: diesel_make_pg_pool(&config.master_database, test_transaction).await,
+            master_pool,
             #[cfg(feature = "olap")]
-            replica_pool: diesel_make_pg_pool(&config.replica_database, test_transaction).await,
+            replica_pool,
             redis_conn: Arc::new(crate::connection::redis_connection(config).await),
             #[cfg(feature = "kv_store")]
             config: StoreConfig {
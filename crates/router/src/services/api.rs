@@ -1,3 +1,4 @@
+pub mod circuit_breaker;
 mod client;
 pub(crate) mod request;
 
@@ -11,21 +12,25 @@ use std::{
 
 use actix_web::{body, HttpRequest, HttpResponse, Responder};
 use error_stack::{report, IntoReport, Report, ResultExt};
-use masking::ExposeOptionInterface;
+use masking::{ExposeOptionInterface, PeekInterface};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use router_env::{instrument, tracing, Tag};
 use serde::Serialize;
 
 use self::request::{ContentType, HeaderExt, RequestBuilderExt};
 pub use self::request::{Method, Request, RequestBuilder};
 use crate::{
-    configs::settings::Connectors,
+    configs::settings::{ConnectorRetry, Connectors, Settings},
+    consts,
     core::{
         errors::{self, CustomResult, RouterResponse, RouterResult},
         payments,
     },
     db::StorageInterface,
-    logger,
-    routes::{app::AppStateInfo, AppState},
+    headers, logger,
+    middleware::RequestIdentifier,
+    routes::{app::AppStateInfo, metrics, AppState},
     services::authentication as auth,
     types::{
         self, api,
@@ -124,13 +129,65 @@ pub trait ConnectorIntegration<T, Req, Resp>: ConnectorIntegrationAny<T, Req, Re
     ) -> CustomResult<Option<String>, errors::ConnectorError> {
         Ok(None)
     }
+
+    /// Header in which the connector accepts an idempotency key for this flow, if it supports one
+    fn get_idempotency_key_header(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Idempotency key sent in the header returned by `get_idempotency_key_header`. Defaults to
+    /// the key of the payment attempt, flows that can be performed more than once for an attempt
+    /// (such as refunds) have to derive the key from their own identifier.
+    fn get_idempotency_key(&self, req: &types::RouterData<T, Req, Resp>) -> String {
+        req.get_idempotency_key()
+    }
+
+    /// Whether the request built for `req` only reads data at the connector, so that it can be
+    /// retried even though it is not a `GET` request. Syncs that perform an operation, such as
+    /// completing a 3DS challenge, must not declare this.
+    fn is_read_only_request(&self, _req: &types::RouterData<T, Req, Resp>) -> bool {
+        false
+    }
+}
+
+tokio::task_local! {
+    /// Request ID of the API request being served, forwarded to connectors.
+    static REQUEST_ID: Option<String>;
+}
+
+/// Request ID of the API request the current task is serving, if any.
+pub(crate) fn get_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok().flatten()
+}
+
+/// Forwards the request ID, so that the connector call can be traced back to the API request.
+pub fn add_request_id_header(mut request: Request) -> Request {
+    if let Some(request_id) = get_request_id() {
+        request.add_header(headers::X_REQUEST_ID, &request_id);
+    }
+    request
+}
+
+/// Attaches the idempotency key of the request to the request, for connectors that support it.
+pub fn add_idempotency_key_header<T, Req, Resp>(
+    connector_integration: &BoxedConnectorIntegration<'_, T, Req, Resp>,
+    router_data: &types::RouterData<T, Req, Resp>,
+    mut request: Request,
+) -> Request {
+    if let Some(header) = connector_integration.get_idempotency_key_header() {
+        request.add_header(
+            header,
+            &connector_integration.get_idempotency_key(router_data),
+        );
+    }
+    request
 }
 
 #[instrument(skip_all)]
 pub async fn execute_connector_processing_step<
     'b,
     'a,
-    T: 'static,
+    T: api::FlowType + 'static,
     Req: Debug + Clone + 'static,
     Resp: Debug + Clone + 'static,
 >(
@@ -161,25 +218,56 @@ where
             Ok(router_data)
         }
         payments::CallConnectorAction::Trigger => {
-            match connector_integration.build_request(req, &state.conf.connectors)? {
+            match connector_integration
+                .build_request(req, &state.conf.connectors)
+                .attach(ConnectorRequestNotSent)?
+            {
                 Some(request) => {
-                    let response = call_connector_api(state, request).await;
+                    let circuit_permit = circuit_breaker::check(state, &req.connector)
+                        .await
+                        .attach(ConnectorRequestNotSent)?;
+                    let mut request = add_request_id_header(add_idempotency_key_header(
+                        &connector_integration,
+                        req,
+                        request,
+                    ));
+                    let (timeout, mut retry_config) =
+                        get_connector_request_policy::<T>(&state.conf, &req.connector);
+                    if !is_idempotent_request(
+                        &request,
+                        connector_integration.get_idempotency_key_header().is_some(),
+                        connector_integration.is_read_only_request(req),
+                    ) {
+                        retry_config.max_retries = 0;
+                    }
+                    request.set_timeout(timeout);
+                    logger::debug!(connector_request=%get_connector_request_log(&request));
+                    let request_started = Instant::now();
+                    let response =
+                        call_connector_api_with_retry(state, request, &retry_config).await;
+                    record_connector_request_metrics::<T>(
+                        &req.connector,
+                        request_started,
+                        response.is_ok(),
+                    );
+                    circuit_breaker::record_result(
+                        state,
+                        &req.connector,
+                        circuit_permit,
+                        is_connector_available(&response),
+                    )
+                    .await;
                     match response {
                         Ok(body) => {
-                            let response = match body {
-                                Ok(body) => connector_integration.handle_response(req, body)?,
-                                Err(body) => {
-                                    let error = connector_integration.get_error_response(body)?;
-                                    router_data.response = Err(error);
-
-                                    router_data
-                                }
-                            };
+                            let response =
+                                handle_connector_response(&connector_integration, req, body)?;
                             logger::debug!(?response);
                             Ok(response)
                         }
-                        Err(error) => Err(error
-                            .change_context(errors::ConnectorError::ProcessingStepFailed(None))),
+                        Err(error) => {
+                            let connector_error = error.current_context().get_connector_error();
+                            Err(error.change_context(connector_error))
+                        }
                     }
                 }
                 None => Ok(router_data),
@@ -188,6 +276,437 @@ where
     }
 }
 
+/// Attached to the errors of [`execute_connector_processing_step`] raised before the request was
+/// sent to the connector, such as failures to build the request or an open circuit breaker.
+#[derive(Debug)]
+pub struct ConnectorRequestNotSent;
+
+/// Whether the connector may have received the request of a failed connector call, in which case
+/// it may have performed the operation even though no response was received.
+pub fn is_connector_request_sent<C>(error: &Report<C>) -> bool {
+    !error.contains::<ConnectorRequestNotSent>()
+}
+
+/// Builds the router data from the response of the connector, keeping the masked response body
+/// alongside the result so that it can be stored for support debugging.
+fn handle_connector_response<T, Req, Resp>(
+    connector_integration: &BoxedConnectorIntegration<'_, T, Req, Resp>,
+    req: &types::RouterData<T, Req, Resp>,
+    body: Result<types::Response, types::Response>,
+) -> CustomResult<types::RouterData<T, Req, Resp>, errors::ConnectorError>
+where
+    T: Clone,
+    Req: Clone,
+    Resp: Clone,
+{
+    let response = match &body {
+        Ok(response) | Err(response) => response,
+    };
+    logger::debug!(connector_response=%get_connector_response_log(response));
+    let connector_response_body = get_connector_response_body(&req.connector, response);
+
+    let mut router_data = match body {
+        Ok(body) => connector_integration
+            .handle_response(req, body)
+            .attach_printable_lazy(|| format!("Connector response: {connector_response_body}"))?,
+        Err(body) => {
+            let error = connector_integration
+                .get_error_response(body)
+                .attach_printable_lazy(|| {
+                    format!("Connector response: {connector_response_body}")
+                })?;
+            let mut router_data = req.clone();
+            router_data.response = Err(error);
+            router_data
+        }
+    };
+    router_data.connector_response_body = Some(connector_response_body);
+    Ok(router_data)
+}
+
+/// Whether sending the request again cannot perform the operation twice at the connector, which
+/// holds for `GET` requests, requests the connector declares read only, and requests carrying an
+/// idempotency key that the connector deduplicates them on. The flow alone does not decide it, as
+/// a sync may also complete an operation at the connector.
+fn is_idempotent_request(
+    request: &Request,
+    has_idempotency_key: bool,
+    is_read_only_request: bool,
+) -> bool {
+    has_idempotency_key || is_read_only_request || matches!(request.method, Method::Get)
+}
+
+/// Whether the connector answered the request, counting server errors as failures of the
+/// connector. Client errors are answers to invalid requests and do not count as failures.
+fn is_connector_available(
+    response: &CustomResult<Result<types::Response, types::Response>, errors::ApiClientError>,
+) -> bool {
+    match response {
+        Ok(Ok(_)) => true,
+        Ok(Err(error_response)) => error_response.status_code < 500,
+        Err(_) => false,
+    }
+}
+
+/// Timeout and retry policy of a connector request of the flow `T`.
+fn get_connector_request_policy<T: api::FlowType>(
+    conf: &Settings,
+    connector: &str,
+) -> (Duration, ConnectorRetry) {
+    let flow_policy = conf.connector_flow_policies.get_policy(T::FLOW_NAME);
+    (
+        conf.connector_request_timeout
+            .get_timeout(connector, &flow_policy),
+        conf.connector_retry.with_flow_policy(&flow_policy),
+    )
+}
+
+/// Attributes of the connector request metrics, where the flow is the name of the flow type.
+fn get_connector_metric_attributes<T: api::FlowType>(connector: &str) -> [metrics::KeyValue; 2] {
+    [
+        metrics::KeyValue::new("connector", connector.to_string()),
+        metrics::KeyValue::new("flow", T::FLOW_NAME.to_string()),
+    ]
+}
+
+/// A request fails when no response was received from the connector, error responses are
+/// counted as successful requests.
+fn record_connector_request_metrics<T: api::FlowType>(
+    connector: &str,
+    request_started: Instant,
+    success: bool,
+) {
+    let attributes = get_connector_metric_attributes::<T>(connector);
+    metrics::CONNECTOR_REQUEST_TIME.record(
+        &metrics::CONTEXT,
+        request_started.elapsed().as_secs_f64() * 1000f64,
+        &attributes,
+    );
+    let counter = if success {
+        &metrics::CONNECTOR_REQUEST_SUCCESS
+    } else {
+        &metrics::CONNECTOR_REQUEST_FAILURE
+    };
+    counter.add(&metrics::CONTEXT, 1, &attributes);
+}
+
+/// Replaced for secret and personal data in connector payloads before they are logged.
+const MASKED_VALUE: &str = "****";
+
+/// Keys whose values are always masked, after lowercasing and dropping non-alphanumerics. These
+/// are the keys connectors send `Secret` and PII values under: card and bank account details,
+/// wallet tokens, credentials, and the name, contact details and address of the customer.
+const SENSITIVE_PAYLOAD_KEYS: &[&str] = &[
+    // Card details
+    "number",
+    "cardnumber",
+    "pan",
+    "cvc",
+    "cvc2",
+    "cvv",
+    "cvv2",
+    "cvn",
+    "cardcvc",
+    "cardcode",
+    "securitycode",
+    "cardsecuritycode",
+    "expirymonth",
+    "expiryyear",
+    "expirydate",
+    "expirationmonth",
+    "expirationyear",
+    "expmonth",
+    "expyear",
+    "cardexpmonth",
+    "cardexpyear",
+    "cardholdername",
+    "holdername",
+    "cardidentifier",
+    "cryptogram",
+    "paymentmethoddatacardnumber",
+    "paymentmethoddatacardexpmonth",
+    "paymentmethoddatacardexpyear",
+    "paymentmethoddatacardcvc",
+    // Bank account details
+    "iban",
+    "bic",
+    "accountnumber",
+    "routingnumber",
+    "sortcode",
+    // Wallet tokens and credentials
+    "token",
+    "paymenttoken",
+    "clientsecret",
+    "merchantsessionkey",
+    "vendorname",
+    "password",
+    "apikey",
+    "secret",
+    "secretkey",
+    "authorization",
+    // Customer details
+    "name",
+    "billingname",
+    "firstname",
+    "lastname",
+    "surname",
+    "surnameprefix",
+    "title",
+    "email",
+    "emailaddress",
+    "customeremail",
+    "shopperemail",
+    "phone",
+    "phonenumber",
+    "mobilephonenumber",
+    "telephonenumber",
+    "dateofbirth",
+    "ipaddress",
+    "line1",
+    "line2",
+    "line3",
+    "address1",
+    "address2",
+    "address3",
+    "street",
+    "zip",
+    "postalcode",
+    "postcode",
+    "state",
+];
+
+/// Keys whose values are kept in the connector response body stored on the payment attempt, after
+/// lowercasing and dropping non-alphanumerics. These identify the payment at the connector and
+/// explain its outcome, every other value is masked.
+const STORED_RESPONSE_KEYS: &[&str] = &[
+    "id",
+    "object",
+    "type",
+    "status",
+    "code",
+    "message",
+    "reason",
+    "description",
+    "errorcode",
+    "errormessage",
+    "errortype",
+    "declinecode",
+    "responsecode",
+    "resultcode",
+    "statuscode",
+    "amount",
+    "currency",
+    "created",
+    "reference",
+];
+
+/// Keys whose values are kept in the stored connector response body for the given connector, on
+/// top of [`STORED_RESPONSE_KEYS`].
+fn get_connector_stored_response_keys(connector: &str) -> &'static [&'static str] {
+    match connector {
+        "adyen" => &[
+            "pspreference",
+            "merchantreference",
+            "refusalreason",
+            "refusalreasoncode",
+        ],
+        "authorizedotnet" => &["transid", "resulttext", "errortext", "text"],
+        "checkout" => &["actionid", "approved", "responsesummary"],
+        "opayo" => &["transactionid", "statusdetail", "retrievalreference"],
+        "rapyd" => &["operationid", "responsecode"],
+        "stripe" => &[
+            "networkstatus",
+            "risklevel",
+            "sellermessage",
+            "captured",
+            "paid",
+            "refunded",
+            "amountcaptured",
+            "amountreceived",
+        ],
+        _ => &[],
+    }
+}
+
+static CARD_NUMBER_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"\b\d{13,19}\b"));
+
+fn get_connector_request_log(request: &Request) -> serde_json::Value {
+    serde_json::json!({
+        "method": request.method,
+        "url": request.url,
+        "body": request
+            .payload
+            .as_ref()
+            .map(|payload| mask_connector_payload(payload.peek())),
+    })
+}
+
+fn get_connector_response_log(response: &types::Response) -> serde_json::Value {
+    serde_json::json!({
+        "status_code": response.status_code,
+        "body": str::from_utf8(&response.response)
+            .map(mask_connector_payload)
+            .unwrap_or_else(|_| MASKED_VALUE.to_string()),
+    })
+}
+
+/// Response body of the connector stored on the payment attempt, truncated to at most
+/// [`consts::MAX_CONNECTOR_RESPONSE_BODY_LENGTH`] bytes. Only the values of the stored response
+/// keys of the connector are kept, and bodies that are neither JSON nor form encoded are not
+/// kept at all.
+fn get_connector_response_body(connector: &str, response: &types::Response) -> String {
+    let is_stored_key = |key: &str| {
+        let key = normalize_payload_key(key);
+        !SENSITIVE_PAYLOAD_KEYS.contains(&key.as_str())
+            && (STORED_RESPONSE_KEYS.contains(&key.as_str())
+                || get_connector_stored_response_keys(connector).contains(&key.as_str()))
+    };
+    let mut body = str::from_utf8(&response.response)
+        .ok()
+        .and_then(|body| mask_connector_payload_except(body, &is_stored_key))
+        .unwrap_or_else(|| MASKED_VALUE.to_string());
+    if body.len() > consts::MAX_CONNECTOR_RESPONSE_BODY_LENGTH {
+        let mut length = consts::MAX_CONNECTOR_RESPONSE_BODY_LENGTH;
+        while !body.is_char_boundary(length) {
+            length -= 1;
+        }
+        body.truncate(length);
+    }
+    body
+}
+
+/// Masks the values of sensitive keys in a JSON or form encoded connector payload.
+/// Anything else only has digit runs that look like card numbers masked.
+pub fn mask_connector_payload(payload: &str) -> String {
+    if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(payload) {
+        mask_json_value(&mut value);
+        return value.to_string();
+    }
+
+    match serde_urlencoded::from_str::<Vec<(String, String)>>(payload) {
+        Ok(pairs) if pairs.iter().any(|(_, value)| !value.is_empty()) => {
+            let masked_pairs = pairs
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = if is_sensitive_payload_key(&key) {
+                        MASKED_VALUE.to_string()
+                    } else {
+                        mask_card_numbers(&value)
+                    };
+                    (mask_card_numbers(&key), value)
+                })
+                .collect::<Vec<_>>();
+            serde_urlencoded::to_string(masked_pairs).unwrap_or_else(|_| mask_card_numbers(payload))
+        }
+        _ => mask_card_numbers(payload),
+    }
+}
+
+/// Masks every value of a JSON or form encoded connector payload except the ones of the kept keys,
+/// returning `None` for other payloads.
+fn mask_connector_payload_except(
+    payload: &str,
+    is_kept_key: &dyn Fn(&str) -> bool,
+) -> Option<String> {
+    if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(payload) {
+        mask_json_value_except(&mut value, None, is_kept_key);
+        return Some(value.to_string());
+    }
+
+    match serde_urlencoded::from_str::<Vec<(String, String)>>(payload) {
+        Ok(pairs) if pairs.iter().any(|(_, value)| !value.is_empty()) => {
+            let masked_pairs = pairs
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = if is_kept_key(&key) {
+                        mask_card_numbers(&value)
+                    } else {
+                        MASKED_VALUE.to_string()
+                    };
+                    (mask_card_numbers(&key), value)
+                })
+                .collect::<Vec<_>>();
+            serde_urlencoded::to_string(masked_pairs).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Masks the scalar values of the JSON value, unless they are held by a kept key. Values in
+/// arrays are held by the key of the array.
+fn mask_json_value_except(
+    value: &mut serde_json::Value,
+    key: Option<&str>,
+    is_kept_key: &dyn Fn(&str) -> bool,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                mask_json_value_except(value, Some(key), is_kept_key);
+            }
+        }
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| mask_json_value_except(value, key, is_kept_key)),
+        serde_json::Value::Null => {}
+        serde_json::Value::String(_)
+        | serde_json::Value::Number(_)
+        | serde_json::Value::Bool(_) => {
+            if key.map_or(false, is_kept_key) {
+                mask_json_value(value);
+            } else {
+                *value = serde_json::Value::String(MASKED_VALUE.to_string());
+            }
+        }
+    }
+}
+
+fn mask_json_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive_payload_key(key) && !value.is_object() && !value.is_array() {
+                    *value = serde_json::Value::String(MASKED_VALUE.to_string());
+                } else {
+                    mask_json_value(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(mask_json_value),
+        serde_json::Value::String(string) => *string = mask_card_numbers(string),
+        serde_json::Value::Number(number) => {
+            if mask_card_numbers(&number.to_string()) == MASKED_VALUE {
+                *value = serde_json::Value::String(MASKED_VALUE.to_string());
+            }
+        }
+        serde_json::Value::Null | serde_json::Value::Bool(_) => {}
+    }
+}
+
+fn is_sensitive_payload_key(key: &str) -> bool {
+    SENSITIVE_PAYLOAD_KEYS.contains(&normalize_payload_key(key).as_str())
+}
+
+/// Lowercases the key and drops non-alphanumerics. Form encoded keys are nested as
+/// `card[number]`, only the innermost key is kept.
+fn normalize_payload_key(key: &str) -> String {
+    key.rsplit('[')
+        .next()
+        .unwrap_or(key)
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn mask_card_numbers(text: &str) -> String {
+    match CARD_NUMBER_REGEX.as_ref() {
+        Ok(regex) => regex.replace_all(text, MASKED_VALUE).into_owned(),
+        Err(_) => MASKED_VALUE.to_string(),
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn call_connector_api(
     state: &AppState,
@@ -203,6 +722,54 @@ pub async fn call_connector_api(
     handle_response(response).await
 }
 
+#[instrument(skip_all)]
+async fn call_connector_api_with_retry(
+    state: &AppState,
+    request: Request,
+    retry_config: &ConnectorRetry,
+) -> CustomResult<Result<types::Response, types::Response>, errors::ApiClientError> {
+    retry_with_backoff(retry_config, || call_connector_api(state, request.clone())).await
+}
+
+/// Retries `call` with exponential backoff as long as it fails with a retryable error.
+/// 4xx responses are returned as `Ok(Err(_))` by the connector client and are never retried.
+async fn retry_with_backoff<T, F, Fut>(
+    retry_config: &ConnectorRetry,
+    mut call: F,
+) -> CustomResult<T, errors::ApiClientError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = CustomResult<T, errors::ApiClientError>>,
+{
+    let mut retry_count = 0;
+    loop {
+        match call().await {
+            // Only requests the connector received are retried, so a connection failure means
+            // that the connector never received the request only when it is the first attempt
+            Err(error)
+                if retry_count == 0
+                    && matches!(
+                        error.current_context(),
+                        errors::ApiClientError::ConnectionFailed(_)
+                    ) =>
+            {
+                return Err(error.attach(ConnectorRequestNotSent));
+            }
+            Err(error)
+                if retry_count < retry_config.max_retries
+                    && error.current_context().is_retryable() =>
+            {
+                let delay = Duration::from_millis(retry_config.base_delay_in_millis)
+                    .saturating_mul(2_u32.saturating_pow(retry_count));
+                logger::warn!(?error, retry_count, ?delay, "Retrying connector request");
+                tokio::time::sleep(delay).await;
+                retry_count += 1;
+            }
+            response => return response,
+        }
+    }
+}
+
 #[instrument(skip_all)]
 async fn send_request(
     state: &AppState,
@@ -210,6 +777,9 @@ async fn send_request(
 ) -> CustomResult<reqwest::Response, errors::ApiClientError> {
     logger::debug!(method=?request.method, headers=?request.headers, payload=?request.payload, ?request);
     let url = &request.url;
+    let timeout = request
+        .timeout
+        .unwrap_or_else(|| Duration::from_secs(crate::consts::REQUEST_TIME_OUT));
     let should_bypass_proxy = client::proxy_bypass_urls(&state.conf.locker).contains(url);
     let client = client::create_client(
         &state.conf.proxy,
@@ -254,17 +824,30 @@ async fn send_request(
         Method::Delete => client.delete(url),
     }
     .add_headers(headers)
-    .timeout(Duration::from_secs(crate::consts::REQUEST_TIME_OUT))
+    .timeout(timeout)
     .send()
     .await
     .map_err(|error| match error {
         error if error.is_timeout() => errors::ApiClientError::RequestTimeoutReceived,
+        error if error.is_connect() => errors::ApiClientError::ConnectionFailed(error.to_string()),
         _ => errors::ApiClientError::RequestNotSent(error.to_string()),
     })
     .into_report()
     .attach_printable("Unable to send request to connector")
 }
 
+/// Parses a `Retry-After` header value, given either as delta-seconds or as an HTTP-date.
+/// An HTTP-date in the past results in a zero duration.
+pub fn parse_retry_after(value: &str, now: time::OffsetDateTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let retry_at =
+        time::OffsetDateTime::parse(value, &time::format_description::well_known::Rfc2822).ok()?;
+    Some(Duration::try_from(retry_at - now).unwrap_or(Duration::ZERO))
+}
+
 #[instrument(skip_all)]
 async fn handle_response(
     response: CustomResult<reqwest::Response, errors::ApiClientError>,
@@ -301,6 +884,20 @@ async fn handle_response(
                     Err(Report::new(error).attach_printable("Server error response received"))
                 }
 
+                429 => {
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| {
+                            parse_retry_after(value, time::OffsetDateTime::now_utc())
+                        });
+                    Err(
+                        report!(errors::ApiClientError::TooManyRequestsReceived { retry_after })
+                            .attach_printable("Too many requests sent to the connector"),
+                    )
+                }
+
                 status_code @ 400..=499 => {
                     let bytes = response.bytes().await.map_err(|error| {
                         report!(error)
@@ -427,10 +1024,21 @@ where
     tracing::Span::current().record("request_method", request_method);
     tracing::Span::current().record("request_url_path", url_path);
 
+    let request_id = request
+        .extensions()
+        .get::<RequestIdentifier>()
+        .map(|request_id| request_id.0.clone());
+
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest);
 
-    let res = match server_wrap_util(state, request, payload, func, api_auth).await {
+    let res = match REQUEST_ID
+        .scope(
+            request_id.clone(),
+            server_wrap_util(state, request, payload, func, api_auth),
+        )
+        .await
+    {
         Ok(ApplicationResponse::Json(response)) => match serde_json::to_string(&response) {
             Ok(res) => http_response_json(res),
             Err(_) => http_response_err(
@@ -459,7 +1067,15 @@ where
             .respond_to(request)
             .map_into_boxed_body(),
 
-        Err(error) => log_and_return_error_response(error),
+        Err(error) => {
+            let body = add_request_id_to_error_body(
+                error.current_context().to_string(),
+                request_id.as_deref(),
+            );
+            log_and_return_error_response(error)
+                .set_body(body)
+                .map_into_boxed_body()
+        }
     };
 
     let response_code = res.status().as_u16();
@@ -474,6 +1090,28 @@ where
     res
 }
 
+/// Adds the request ID to the `error` object of an error response, so that it can be quoted when
+/// reporting the error.
+fn add_request_id_to_error_body(body: String, request_id: Option<&str>) -> String {
+    let request_id = match request_id {
+        Some(request_id) => request_id,
+        None => return body,
+    };
+    match serde_json::from_str::<serde_json::Value>(&body) {
+        Ok(mut value) => match value
+            .get_mut("error")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            Some(error) => {
+                error.insert("request_id".to_string(), request_id.into());
+                value.to_string()
+            }
+            None => body,
+        },
+        Err(_) => body,
+    }
+}
+
 pub fn log_and_return_error_response<T>(error: Report<T>) -> HttpResponse
 where
     T: actix_web::ResponseError + error_stack::Context,
@@ -620,8 +1258,670 @@ pub fn build_redirection_form(form: &RedirectForm) -> maud::Markup {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
+    use error_stack::report;
+
+    use super::{
+        add_idempotency_key_header, add_request_id_header, add_request_id_to_error_body,
+        execute_connector_processing_step, get_connector_metric_attributes,
+        get_connector_request_log, get_connector_request_policy, get_connector_response_body,
+        get_connector_response_log, handle_connector_response, is_connector_available,
+        is_connector_request_sent, is_idempotent_request, mask_connector_payload,
+        parse_retry_after, retry_with_backoff, ConnectorIntegration, REQUEST_ID,
+    };
+    use crate::{
+        configs::settings::{ConnectorFlowPolicy, ConnectorRetry, Settings},
+        connector::utils::get_test_router_data,
+        consts,
+        core::{errors, payments},
+        db::StorageImpl,
+        headers,
+        routes::{self, metrics},
+        services::{Method, RequestBuilder},
+        types::{self, api, storage::enums},
+    };
+
     #[test]
     fn test_mime_essence() {
         assert_eq!(mime::APPLICATION_JSON.essence_str(), "application/json");
     }
+
+    #[actix_rt::test]
+    async fn test_request_id_is_forwarded_to_connector() {
+        let build_request = || {
+            add_request_id_header(
+                RequestBuilder::new()
+                    .method(Method::Post)
+                    .url("https://connector.test/payments")
+                    .build(),
+            )
+        };
+        let request = REQUEST_ID
+            .scope(Some("req_123".to_string()), async { build_request() })
+            .await;
+        let request_id = request
+            .headers
+            .into_iter()
+            .find_map(|(header, value)| (header == headers::X_REQUEST_ID).then_some(value));
+        assert_eq!(request_id, Some("req_123".to_string()));
+
+        assert!(build_request()
+            .headers
+            .iter()
+            .all(|(header, _)| header != headers::X_REQUEST_ID));
+    }
+
+    #[test]
+    fn test_request_id_is_added_to_error_body() {
+        let body = errors::ApiErrorResponse::Unauthorized.to_string();
+        let value: serde_json::Value =
+            serde_json::from_str(&add_request_id_to_error_body(body.clone(), Some("req_123")))
+                .unwrap();
+        assert_eq!(value["error"]["request_id"], "req_123");
+        assert_eq!(value["error"]["code"], "IR_01");
+
+        assert_eq!(add_request_id_to_error_body(body.clone(), None), body);
+    }
+
+    #[test]
+    fn test_connector_metric_attributes_are_labeled_by_connector_and_flow() {
+        let [connector, flow] = get_connector_metric_attributes::<api::Authorize>("opayo");
+        assert_eq!(connector, metrics::KeyValue::new("connector", "opayo"));
+        assert_eq!(flow, metrics::KeyValue::new("flow", "Authorize"));
+
+        let [_, flow] = get_connector_metric_attributes::<api::RSync>("opayo");
+        assert_eq!(flow, metrics::KeyValue::new("flow", "RSync"));
+    }
+
+    #[test]
+    fn test_connector_request_log_masks_card_data() {
+        let request = RequestBuilder::new()
+            .method(Method::Post)
+            .url("https://connector.test/payments")
+            .body(Some(
+                r#"{"amount":1000,"card":{"number":"4111111111111111","cvc":"123","expiry_month":"10"}}"#
+                    .to_string(),
+            ))
+            .build();
+
+        let log_line = get_connector_request_log(&request).to_string();
+
+        assert!(log_line.contains("****"));
+        assert!(!log_line.contains("4111111111111111"));
+        assert!(!log_line.contains("123"));
+        assert!(log_line.contains("https://connector.test/payments"));
+        assert!(log_line.contains("POST"));
+        assert!(log_line.contains("1000"));
+    }
+
+    #[test]
+    fn test_connector_response_log_masks_card_numbers() {
+        let response = types::Response {
+            response: r#"{"id":"pay_1","source":{"pan":"5555555555554444"},"note":"card 4000056655665556"}"#.into(),
+            status_code: 200,
+        };
+
+        let log_line = get_connector_response_log(&response).to_string();
+
+        assert!(log_line.contains("200"));
+        assert!(log_line.contains("pay_1"));
+        assert!(!log_line.contains("5555555555554444"));
+        assert!(!log_line.contains("4000056655665556"));
+    }
+
+    #[test]
+    fn test_customer_and_wallet_data_is_masked() {
+        let masked = mask_connector_payload(
+            r#"{"amount":1000,"currency":"USD","customerEmail":"jane@example.com","billing_address":{"firstName":"Jane","line1":"1 Main Street","postalCode":"94105","city":"San Francisco"},"wallet":{"token":"tok_apple_pay_data"},"card":{"expiryYear":"2030","cardholder_name":"Jane Doe"}}"#,
+        );
+
+        for value in [
+            "jane@example.com",
+            "Jane",
+            "1 Main Street",
+            "94105",
+            "tok_apple_pay_data",
+            "2030",
+        ] {
+            assert!(!masked.contains(value), "{value} is not masked");
+        }
+        assert!(masked.contains("1000"));
+        assert!(masked.contains("USD"));
+        assert!(masked.contains("San Francisco"));
+    }
+
+    #[test]
+    fn test_form_encoded_payload_is_masked() {
+        let masked = mask_connector_payload(
+            "amount=1000&payment_method_data[card][number]=4242424242424242&payment_method_data[card][cvc]=314",
+        );
+
+        assert!(!masked.contains("4242424242424242"));
+        assert!(!masked.contains("314"));
+        assert!(masked.contains("amount=1000"));
+    }
+
+    fn get_retry_config(max_retries: u32) -> ConnectorRetry {
+        ConnectorRetry {
+            max_retries,
+            base_delay_in_millis: 1,
+        }
+    }
+
+    #[test]
+    fn test_sync_flows_get_their_own_request_policy() {
+        let mut conf = Settings::default();
+        conf.connector_request_timeout.default_in_secs = 30;
+        conf.connector_retry.max_retries = 0;
+        conf.connector_flow_policies.sync = ConnectorFlowPolicy {
+            timeout_in_secs: Some(90),
+            max_retries: Some(3),
+        };
+
+        let (authorize_timeout, authorize_retry) =
+            get_connector_request_policy::<api::Authorize>(&conf, "stripe");
+        let (sync_timeout, sync_retry) =
+            get_connector_request_policy::<api::PSync>(&conf, "stripe");
+        let (refund_sync_timeout, _) = get_connector_request_policy::<api::RSync>(&conf, "stripe");
+
+        assert_eq!(authorize_timeout, std::time::Duration::from_secs(30));
+        assert_eq!(authorize_retry.max_retries, 0);
+        assert_eq!(sync_timeout, std::time::Duration::from_secs(90));
+        assert_eq!(sync_retry.max_retries, 3);
+        assert_eq!(refund_sync_timeout, sync_timeout);
+        assert_eq!(
+            sync_retry.base_delay_in_millis,
+            authorize_retry.base_delay_in_millis
+        );
+    }
+
+    #[test]
+    fn test_connector_timeout_takes_precedence_over_flow_timeout() {
+        let mut conf = Settings::default();
+        conf.connector_flow_policies.sync.timeout_in_secs = Some(90);
+        conf.connector_request_timeout
+            .overrides
+            .insert("adyen".to_string(), 60);
+
+        let (timeout, _) = get_connector_request_policy::<api::PSync>(&conf, "adyen");
+        assert_eq!(timeout, std::time::Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_only_idempotent_requests_are_retried() {
+        let request = |method| {
+            RequestBuilder::new()
+                .method(method)
+                .url("https://connector.test/payments")
+                .build()
+        };
+
+        assert!(!is_idempotent_request(&request(Method::Post), false, false));
+        assert!(is_idempotent_request(&request(Method::Post), true, false));
+        assert!(is_idempotent_request(&request(Method::Post), false, true));
+        assert!(is_idempotent_request(&request(Method::Get), false, false));
+    }
+
+    #[actix_rt::test]
+    async fn test_retry_succeeds_after_transient_failures() {
+        let mut calls = 0;
+        let response = retry_with_backoff(&get_retry_config(3), || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt <= 2 {
+                    Err(report!(errors::ApiClientError::ServiceUnavailableReceived))
+                } else {
+                    Ok("success")
+                }
+            }
+        })
+        .await;
+        assert_eq!(response.unwrap(), "success");
+        assert_eq!(calls, 3);
+    }
+
+    #[actix_rt::test]
+    async fn test_retry_gives_up_after_max_retries() {
+        let mut calls = 0;
+        let response: errors::CustomResult<(), errors::ApiClientError> =
+            retry_with_backoff(&get_retry_config(2), || {
+                calls += 1;
+                async { Err(report!(errors::ApiClientError::RequestTimeoutReceived)) }
+            })
+            .await;
+        assert!(response.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[actix_rt::test]
+    async fn test_retry_skips_non_retryable_errors() {
+        let mut calls = 0;
+        let response: errors::CustomResult<(), errors::ApiClientError> =
+            retry_with_backoff(&get_retry_config(2), || {
+                calls += 1;
+                async { Err(report!(errors::ApiClientError::InternalServerErrorReceived)) }
+            })
+            .await;
+        assert!(response.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_connection_failure_is_not_sent_unless_retried() {
+        let connection_failed = || errors::ApiClientError::ConnectionFailed(String::new());
+        let response: errors::CustomResult<(), errors::ApiClientError> =
+            retry_with_backoff(&get_retry_config(2), || async move {
+                Err(report!(connection_failed()))
+            })
+            .await;
+        assert!(!is_connector_request_sent(&response.unwrap_err()));
+
+        // The connector received the request that timed out before the connection failure
+        let mut calls = 0;
+        let response: errors::CustomResult<(), errors::ApiClientError> =
+            retry_with_backoff(&get_retry_config(2), || {
+                calls += 1;
+                let attempt = calls;
+                async move {
+                    if attempt == 1 {
+                        Err(report!(errors::ApiClientError::RequestTimeoutReceived))
+                    } else {
+                        Err(report!(connection_failed()))
+                    }
+                }
+            })
+            .await;
+        assert_eq!(calls, 2);
+        assert!(is_connector_request_sent(&response.unwrap_err()));
+    }
+
+    struct IdempotentConnector;
+
+    impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsResponseData>
+        for IdempotentConnector
+    {
+        fn get_idempotency_key_header(&self) -> Option<&'static str> {
+            Some(headers::IDEMPOTENCY_KEY)
+        }
+    }
+
+    fn get_cancel_router_data(attempt_id: &str) -> types::PaymentsCancelRouterData {
+        types::RouterData {
+            attempt_id: Some(attempt_id.to_string()),
+            status: enums::AttemptStatus::Authorized,
+            ..get_test_router_data(
+                "stripe",
+                types::PaymentsCancelData {
+                    connector_transaction_id: "txn_123".to_string(),
+                    cancellation_reason: None,
+                },
+                Err(types::ErrorResponse::default()),
+            )
+        }
+    }
+
+    fn get_idempotency_key_header_value(router_data: &types::PaymentsCancelRouterData) -> String {
+        let connector_integration: super::BoxedConnectorIntegration<
+            '_,
+            api::Void,
+            types::PaymentsCancelData,
+            types::PaymentsResponseData,
+        > = Box::new(&IdempotentConnector);
+        let request = RequestBuilder::new()
+            .method(Method::Post)
+            .url("https://example.com")
+            .build();
+        add_idempotency_key_header(&connector_integration, router_data, request)
+            .headers
+            .into_iter()
+            .find_map(|(header, value)| (header == headers::IDEMPOTENCY_KEY).then_some(value))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_idempotency_key_is_derived_from_attempt_id() {
+        let first_attempt = get_idempotency_key_header_value(&get_cancel_router_data("pay_123_1"));
+        assert_eq!(first_attempt, "pay_123_pay_123_1");
+        assert_ne!(
+            first_attempt,
+            get_idempotency_key_header_value(&get_cancel_router_data("pay_123_2"))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_retried_request_carries_the_same_idempotency_key() {
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path(
+                "/v1/payment_intents/txn_123/cancel",
+            ))
+            .respond_with(wiremock::ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&connector_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path(
+                "/v1/payment_intents/txn_123/cancel",
+            ))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "id": "txn_123",
+                    "object": "payment_intent",
+                    "amount": 1000,
+                    "amount_received": 0,
+                    "amount_capturable": 0,
+                    "currency": "usd",
+                    "status": "canceled",
+                    "client_secret": "txn_123_secret",
+                    "created": 1672531200,
+                    "metadata": { "order_id": "pay_123", "txn_id": "", "txn_uuid": "" },
+                })),
+            )
+            .mount(&connector_server)
+            .await;
+
+        let mut conf = Settings::new().unwrap();
+        conf.connectors.stripe.base_url = format!("{}/", connector_server.uri());
+        conf.connector_retry = get_retry_config(1);
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let connector_integration: super::BoxedConnectorIntegration<
+            '_,
+            api::Void,
+            types::PaymentsCancelData,
+            types::PaymentsResponseData,
+        > = Box::new(&crate::connector::Stripe);
+        let router_data = get_cancel_router_data("pay_123_1");
+        let router_data = execute_connector_processing_step(
+            &state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+        )
+        .await
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Voided);
+
+        let idempotency_keys = connector_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                request.headers.iter().find_map(|(header, values)| {
+                    header
+                        .as_str()
+                        .eq_ignore_ascii_case(headers::IDEMPOTENCY_KEY)
+                        .then(|| values.last().as_str().to_string())
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(idempotency_keys.len(), 2);
+        // Distinct from the key of the authorization of the same attempt
+        assert_eq!(
+            idempotency_keys[0].as_deref(),
+            Some("pay_123_pay_123_1_void")
+        );
+        assert_eq!(idempotency_keys[0], idempotency_keys[1]);
+    }
+
+    async fn get_opayo_sync_request_count(encoded_data: Option<String>) -> usize {
+        let connector_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(503))
+            .mount(&connector_server)
+            .await;
+
+        let mut conf = Settings::new().unwrap();
+        conf.connectors.opayo.base_url = format!("{}/", connector_server.uri());
+        conf.connector_retry = get_retry_config(2);
+        conf.connector_flow_policies.sync.max_retries = None;
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        let connector_integration: super::BoxedConnectorIntegration<
+            '_,
+            api::PSync,
+            types::PaymentsSyncData,
+            types::PaymentsResponseData,
+        > = Box::new(&crate::connector::Opayo);
+        let router_data: types::PaymentsSyncRouterData = types::RouterData {
+            connector_auth_type: types::ConnectorAuthType::SignatureKey {
+                api_key: "integration_key".to_string(),
+                key1: "vendor".to_string(),
+                api_secret: "integration_password".to_string(),
+            },
+            ..get_test_router_data(
+                "opayo",
+                types::PaymentsSyncData {
+                    connector_transaction_id: types::ResponseId::ConnectorTransactionId(
+                        "txn_123".to_string(),
+                    ),
+                    encoded_data,
+                    capture_method: None,
+                },
+                Err(types::ErrorResponse::default()),
+            )
+        };
+        let _ = execute_connector_processing_step(
+            &state,
+            connector_integration,
+            &router_data,
+            payments::CallConnectorAction::Trigger,
+        )
+        .await;
+        connector_server.received_requests().await.unwrap().len()
+    }
+
+    #[actix_rt::test]
+    async fn test_sync_completing_a_challenge_is_not_retried() {
+        // Retrieving the transaction is a GET request
+        assert_eq!(get_opayo_sync_request_count(None).await, 3);
+        // Completing the 3DS challenge is a POST request that must reach Opayo only once
+        assert_eq!(
+            get_opayo_sync_request_count(Some(
+                "cres=eyJ0cmFuc1N0YXR1cyI6IlkifQ&threeDSSessionData=2B97808F".to_string()
+            ))
+            .await,
+            1
+        );
+    }
+
+    fn get_refund_idempotency_key_header_value(refund_id: &str) -> Option<String> {
+        let router_data: types::RefundExecuteRouterData = types::RouterData {
+            status: enums::AttemptStatus::Charged,
+            ..get_test_router_data(
+                "stripe",
+                types::RefundsData {
+                    refund_id: refund_id.to_string(),
+                    connector_transaction_id: "txn_123".to_string(),
+                    connector_refund_id: None,
+                    currency: enums::Currency::USD,
+                    amount: 1000,
+                    reason: None,
+                    refund_amount: 500,
+                    connector_metadata: None,
+                },
+                Err(types::ErrorResponse::default()),
+            )
+        };
+        let connector_integration: super::BoxedConnectorIntegration<
+            '_,
+            api::Execute,
+            types::RefundsData,
+            types::RefundsResponseData,
+        > = Box::new(&crate::connector::Stripe);
+        let request = RequestBuilder::new()
+            .method(Method::Post)
+            .url("https://example.com")
+            .build();
+        add_idempotency_key_header(&connector_integration, &router_data, request)
+            .headers
+            .into_iter()
+            .find_map(|(header, value)| (header == headers::IDEMPOTENCY_KEY).then_some(value))
+    }
+
+    #[test]
+    fn test_refund_idempotency_key_is_derived_from_refund_id() {
+        let first_refund = get_refund_idempotency_key_header_value("ref_1");
+        assert_eq!(first_refund.as_deref(), Some("pay_123_ref_1"));
+        assert_eq!(
+            first_refund,
+            get_refund_idempotency_key_header_value("ref_1")
+        );
+        // Partial refunds of the same payment attempt are separate requests for the connector
+        assert_ne!(
+            first_refund,
+            get_refund_idempotency_key_header_value("ref_2")
+        );
+    }
+
+    fn get_connector_response(status_code: u16) -> types::Response {
+        types::Response {
+            response: r#"{"id":"txn_123","card":{"number":"4242424242424242","cvc":"123"}}"#.into(),
+            status_code,
+        }
+    }
+
+    fn get_void_connector_integration() -> super::BoxedConnectorIntegration<
+        'static,
+        api::Void,
+        types::PaymentsCancelData,
+        types::PaymentsResponseData,
+    > {
+        Box::new(&IdempotentConnector)
+    }
+
+    #[test]
+    fn test_server_errors_count_as_connector_failures() {
+        assert!(is_connector_available(&Ok(Ok(get_connector_response(200)))));
+        // Rejections of invalid requests do not mean the connector is failing
+        assert!(is_connector_available(&Ok(Err(get_connector_response(
+            400
+        )))));
+        assert!(!is_connector_available(&Ok(Err(get_connector_response(
+            500
+        )))));
+        assert!(!is_connector_available(&Err(report!(
+            errors::ApiClientError::RequestTimeoutReceived
+        ))));
+    }
+
+    #[test]
+    fn test_response_body_is_captured_on_success() {
+        let router_data = handle_connector_response(
+            &get_void_connector_integration(),
+            &get_cancel_router_data("pay_123_1"),
+            Ok(get_connector_response(200)),
+        )
+        .unwrap();
+
+        let body = router_data.connector_response_body.unwrap();
+        assert!(body.contains("txn_123"));
+        assert!(!body.contains("4242424242424242"));
+        assert!(!body.contains(r#""cvc":"123""#));
+    }
+
+    #[test]
+    fn test_response_body_is_captured_on_failure() {
+        let router_data = handle_connector_response(
+            &get_void_connector_integration(),
+            &get_cancel_router_data("pay_123_1"),
+            Err(get_connector_response(402)),
+        )
+        .unwrap();
+
+        assert!(router_data.response.is_err());
+        let body = router_data.connector_response_body.unwrap();
+        assert!(body.contains("txn_123"));
+        assert!(!body.contains("4242424242424242"));
+    }
+
+    #[test]
+    fn test_long_response_body_is_truncated() {
+        let response = types::Response {
+            response: serde_json::json!({
+                "message": "é".repeat(consts::MAX_CONNECTOR_RESPONSE_BODY_LENGTH)
+            })
+            .to_string()
+            .into(),
+            status_code: 500,
+        };
+
+        let body = get_connector_response_body("stripe", &response);
+        assert!(body.len() <= consts::MAX_CONNECTOR_RESPONSE_BODY_LENGTH);
+        assert!(body.starts_with(r#"{"message":"éé"#));
+    }
+
+    #[test]
+    fn test_stored_response_body_only_keeps_allowed_keys() {
+        let response = types::Response {
+            response: serde_json::json!({
+                "pspReference": "8815",
+                "resultCode": "Refused",
+                "refusalReason": "Expired Card",
+                "shopperReference": "customer_1",
+                "additionalData": { "cardSummary": "4242", "cardHolderName": "John Doe" },
+            })
+            .to_string()
+            .into(),
+            status_code: 200,
+        };
+
+        let body: serde_json::Value =
+            serde_json::from_str(&get_connector_response_body("adyen", &response)).unwrap();
+        assert_eq!(body["pspReference"], "8815");
+        assert_eq!(body["resultCode"], "Refused");
+        assert_eq!(body["refusalReason"], "Expired Card");
+        assert_eq!(body["shopperReference"], "****");
+        assert_eq!(body["additionalData"]["cardSummary"], "****");
+        assert_eq!(body["additionalData"]["cardHolderName"], "****");
+
+        // Keys of another connector are not kept
+        let body: serde_json::Value =
+            serde_json::from_str(&get_connector_response_body("stripe", &response)).unwrap();
+        assert_eq!(body["pspReference"], "****");
+        assert_eq!(body["resultCode"], "Refused");
+    }
+
+    #[test]
+    fn test_unstructured_response_body_is_not_stored() {
+        let response = types::Response {
+            response: "<html>Card 4242424242424242 declined</html>".into(),
+            status_code: 500,
+        };
+
+        assert_eq!(get_connector_response_body("stripe", &response), "****");
+    }
+
+    #[test]
+    fn test_parse_retry_after_delta_seconds() {
+        let now = time::OffsetDateTime::now_utc();
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(std::time::Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let now = time::macros::datetime!(2015-10-21 07:26:00 UTC);
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:20:00 GMT", now),
+            Some(std::time::Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
 }
@@ -0,0 +1,232 @@
+//! Stops sending requests to a connector that keeps failing.
+//!
+//! Requests and failures of a connector are counted in fixed windows with atomic Redis
+//! increments, so that the counts of all router instances add up. The circuit opens once the
+//! share of failed requests in a window crosses the configured threshold, and requests are
+//! short-circuited while it is open. After the cooldown the circuit half-opens and a single probe
+//! request is let through, whose outcome either closes or reopens the circuit. Every key expires,
+//! so that no state is left behind for connectors that are no longer called.
+
+use error_stack::report;
+
+use crate::{
+    configs::settings::ConnectorCircuitBreaker,
+    core::errors::{self, CustomResult},
+    logger,
+    routes::AppState,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Permission to send a request to a connector, given by [`check`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitPermit {
+    /// The circuit breaker is disabled, or its state could not be fetched
+    Unchecked,
+    Closed,
+    /// The single request let through a half-open circuit, whose outcome closes or reopens it
+    Probe,
+}
+
+/// Whether the requests and failures counted in a window open the circuit.
+pub fn should_open(config: &ConnectorCircuitBreaker, requests: u32, failures: u32) -> bool {
+    requests > 0
+        && requests >= config.minimum_requests
+        && f64::from(failures) / f64::from(requests) >= config.failure_ratio_threshold
+}
+
+/// Unix timestamp of the start of the window a request completed at `now` is counted in.
+pub fn get_window_start(config: &ConnectorCircuitBreaker, now: i64) -> i64 {
+    now - now.rem_euclid(config.window_in_secs.max(1))
+}
+
+fn now() -> i64 {
+    common_utils::date_time::now().assume_utc().unix_timestamp()
+}
+
+/// Fails with `ConnectorError::CircuitBreakerOpen` while the circuit of the connector is open, or
+/// half-open with its probe already in flight. Circuit breaker failures never block connector
+/// calls.
+pub async fn check(
+    state: &AppState,
+    connector_name: &str,
+) -> CustomResult<CircuitPermit, errors::ConnectorError> {
+    let config = &state.conf.connector_circuit_breaker;
+    if !config.enabled {
+        return Ok(CircuitPermit::Unchecked);
+    }
+    let circuit = match state.store.find_circuit_state(connector_name).await {
+        Ok(circuit) => circuit,
+        Err(error) => {
+            logger::error!(?error, "Failed to fetch circuit breaker state");
+            return Ok(CircuitPermit::Unchecked);
+        }
+    };
+    let permit = match circuit {
+        CircuitState::Closed => Some(CircuitPermit::Closed),
+        CircuitState::Open => None,
+        CircuitState::HalfOpen => match state
+            .store
+            .acquire_circuit_probe(connector_name, config.cooldown_in_secs)
+            .await
+        {
+            Ok(true) => Some(CircuitPermit::Probe),
+            Ok(false) => None,
+            Err(error) => {
+                logger::error!(?error, "Failed to acquire circuit breaker probe");
+                Some(CircuitPermit::Unchecked)
+            }
+        },
+    };
+    permit.ok_or_else(|| {
+        logger::warn!(connector = %connector_name, ?circuit, "Circuit breaker is open");
+        report!(errors::ConnectorError::CircuitBreakerOpen {
+            connector: connector_name.to_string(),
+        })
+    })
+}
+
+pub async fn record_result(
+    state: &AppState,
+    connector_name: &str,
+    permit: CircuitPermit,
+    success: bool,
+) {
+    let config = &state.conf.connector_circuit_breaker;
+    let window_started_at = get_window_start(config, now());
+    let result = match permit {
+        CircuitPermit::Unchecked => Ok(()),
+        CircuitPermit::Probe if success => state.store.close_circuit(connector_name).await,
+        CircuitPermit::Probe => {
+            state
+                .store
+                .open_circuit(connector_name, window_started_at, config.cooldown_in_secs)
+                .await
+        }
+        CircuitPermit::Closed => match state
+            .store
+            .record_circuit_request(
+                connector_name,
+                window_started_at,
+                config.window_in_secs,
+                success,
+            )
+            .await
+        {
+            Ok((requests, failures)) if should_open(config, requests, failures) => {
+                logger::warn!(connector = %connector_name, requests, failures, "Opening circuit breaker");
+                state
+                    .store
+                    .open_circuit(connector_name, window_started_at, config.cooldown_in_secs)
+                    .await
+            }
+            result => result.map(|_| ()),
+        },
+    };
+    if let Err(error) = result {
+        logger::error!(?error, "Failed to update circuit breaker state");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::{check, get_window_start, record_result, should_open, CircuitPermit};
+    use crate::{
+        configs::settings::{ConnectorCircuitBreaker, Settings},
+        core::errors,
+        db::StorageImpl,
+        routes,
+    };
+
+    fn get_config() -> ConnectorCircuitBreaker {
+        ConnectorCircuitBreaker {
+            enabled: true,
+            failure_ratio_threshold: 0.5,
+            minimum_requests: 4,
+            window_in_secs: 60,
+            cooldown_in_secs: 30,
+        }
+    }
+
+    #[test]
+    fn test_circuit_opens_once_the_failure_ratio_is_crossed() {
+        let config = get_config();
+        // Not enough requests in the window yet
+        assert!(!should_open(&config, 3, 3));
+        assert!(should_open(&config, 4, 2));
+        assert!(should_open(&config, 10, 7));
+    }
+
+    #[test]
+    fn test_circuit_stays_closed_below_the_failure_ratio() {
+        let config = get_config();
+        assert!(!should_open(&config, 5, 1));
+        assert!(!should_open(&config, 10, 4));
+        assert!(!should_open(&config, 0, 0));
+    }
+
+    #[test]
+    fn test_requests_are_counted_in_fixed_windows() {
+        let config = get_config();
+        assert_eq!(get_window_start(&config, 120), 120);
+        assert_eq!(get_window_start(&config, 179), 120);
+        assert_eq!(get_window_start(&config, 180), 180);
+    }
+
+    fn is_open(
+        permit: &Result<CircuitPermit, error_stack::Report<errors::ConnectorError>>,
+    ) -> bool {
+        matches!(
+            permit.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ConnectorError::CircuitBreakerOpen { .. })
+        )
+    }
+
+    #[actix_rt::test]
+    async fn test_circuit_opens_half_opens_and_closes() {
+        let mut conf = Settings::new().unwrap();
+        conf.connector_circuit_breaker = ConnectorCircuitBreaker {
+            minimum_requests: 2,
+            cooldown_in_secs: 1,
+            ..get_config()
+        };
+        let state = routes::AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .unwrap();
+        // The circuit state is kept in Redis, which is shared by the test runs
+        let connector = format!("connector_{}", uuid::Uuid::new_v4().simple());
+
+        // Closed, until the failures of the window cross the threshold
+        for _ in 0..2 {
+            let permit = check(&state, &connector).await.unwrap();
+            assert_eq!(permit, CircuitPermit::Closed);
+            record_result(&state, &connector, permit, false).await;
+        }
+
+        // Open, requests are short-circuited for the cooldown
+        assert!(is_open(&check(&state, &connector).await));
+
+        // Half-open once the cooldown elapsed, a single probe is let through
+        tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+        let permit = check(&state, &connector).await.unwrap();
+        assert_eq!(permit, CircuitPermit::Probe);
+        assert!(is_open(&check(&state, &connector).await));
+
+        // Closed by the successful probe
+        record_result(&state, &connector, permit, true).await;
+        assert_eq!(
+            check(&state, &connector).await.unwrap(),
+            CircuitPermit::Closed
+        );
+    }
+}
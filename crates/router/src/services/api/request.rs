@@ -1,4 +1,4 @@
-use std::{collections, str::FromStr};
+use std::{collections, str::FromStr, time::Duration};
 
 use error_stack::{IntoReport, ResultExt};
 use masking::Secret;
@@ -24,13 +24,13 @@ pub enum Method {
     Delete,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 pub enum ContentType {
     Json,
     FormUrlEncoded,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Request {
     pub url: String,
     pub headers: Headers,
@@ -39,6 +39,8 @@ pub struct Request {
     pub content_type: Option<ContentType>,
     pub certificate: Option<String>,
     pub certificate_key: Option<String>,
+    /// Overrides the default client timeout for this request.
+    pub timeout: Option<Duration>,
 }
 
 impl Request {
@@ -51,6 +53,7 @@ impl Request {
             content_type: None,
             certificate: None,
             certificate_key: None,
+            timeout: None,
         }
     }
 
@@ -74,6 +77,10 @@ impl Request {
     pub fn add_certificate_key(&mut self, certificate_key: Option<String>) {
         self.certificate = certificate_key;
     }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
 }
 
 pub struct RequestBuilder {
@@ -149,6 +156,7 @@ impl RequestBuilder {
             content_type: self.content_type,
             certificate: self.certificate,
             certificate_key: self.certificate_key,
+            timeout: None,
         }
     }
 }
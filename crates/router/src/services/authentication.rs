@@ -5,10 +5,13 @@ use error_stack::{report, IntoReport, ResultExt};
 use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 
 use crate::{
-    core::errors::{self, RouterResult, StorageErrorExt},
+    core::{
+        api_keys,
+        errors::{self, RouterResult, StorageErrorExt},
+    },
     db::StorageInterface,
     routes::{app::AppStateInfo, AppState},
-    services::api,
+    services::{api, rate_limiter},
     types::storage,
     utils::OptionExt,
 };
@@ -37,12 +40,43 @@ impl AuthenticateAndFetch<storage::MerchantAccount, AppState> for ApiKeyAuth {
     ) -> RouterResult<storage::MerchantAccount> {
         let api_key =
             get_api_key(request_headers).change_context(errors::ApiErrorResponse::Unauthorized)?;
-        state
-            .store
-            .find_merchant_account_by_api_key(api_key)
-            .await
-            .change_context(errors::ApiErrorResponse::Unauthorized)
-            .attach_printable("Merchant not authenticated")
+        if api_key.starts_with("pk_") || api_key.starts_with("epk") {
+            Err(report!(errors::ApiErrorResponse::AccessForbidden)
+                .attach_printable("Publishable or ephemeral key used in place of an API key"))?;
+        }
+        let merchant_account = match api_keys::find_active_api_key(&*state.store, api_key).await? {
+            Some(stored_api_key) => state
+                .store
+                .find_merchant_account_by_merchant_id(&stored_api_key.merchant_id)
+                .await
+                .change_context(errors::ApiErrorResponse::Unauthorized)
+                .attach_printable("Merchant not authenticated")?,
+            // API keys of merchant accounts created before API keys were hashed
+            None => state
+                .store
+                .find_merchant_account_by_api_key(api_key)
+                .await
+                .change_context(errors::ApiErrorResponse::Unauthorized)
+                .attach_printable("Merchant not authenticated")?,
+        };
+        rate_limiter::check(state, api_key).await?;
+        Ok(merchant_account)
+    }
+}
+
+/// Merchant account authenticated by the API key authentication middleware, which stores it in
+/// the request extensions.
+#[derive(Clone, Debug)]
+pub struct AuthenticatedMerchant(pub storage::MerchantAccount);
+
+#[async_trait]
+impl AuthenticateAndFetch<storage::MerchantAccount, AppState> for AuthenticatedMerchant {
+    async fn authenticate_and_fetch(
+        &self,
+        _request_headers: &HeaderMap,
+        _state: &AppState,
+    ) -> RouterResult<storage::MerchantAccount> {
+        Ok(self.0.clone())
     }
 }
 
@@ -0,0 +1,147 @@
+//! Limits the rate of requests sent with a merchant API key.
+//!
+//! Requests of every API key are counted in fixed windows, and requests over the limit of a window
+//! are rejected with a 429 until the next window starts. The counts are kept in Redis, keyed by a
+//! hash of the API key and the start of the window, so that they are shared by all router
+//! instances. Counts are incremented atomically, so concurrent requests from different instances
+//! are never let through above the limit.
+
+use error_stack::IntoReport;
+
+use crate::{
+    configs::settings::ApiRateLimit,
+    core::errors::{self, RouterResult},
+    logger,
+    routes::AppState,
+};
+
+/// Start of the window a request sent at `now` is counted in.
+pub fn get_window_start(config: &ApiRateLimit, now: i64) -> i64 {
+    now - now.rem_euclid(config.window_in_secs.max(1))
+}
+
+/// Seconds until the next window when the request, counted as the `request_count`th of the window
+/// starting at `window_started_at`, is over the limit.
+pub fn get_retry_after(
+    config: &ApiRateLimit,
+    request_count: u32,
+    window_started_at: i64,
+    now: i64,
+) -> Option<u64> {
+    (request_count > config.max_requests).then(|| {
+        let next_window_at = window_started_at.saturating_add(config.window_in_secs.max(1));
+        u64::try_from(next_window_at.saturating_sub(now))
+            .unwrap_or(1)
+            .max(1)
+    })
+}
+
+fn now() -> i64 {
+    common_utils::date_time::now().assume_utc().unix_timestamp()
+}
+
+fn get_key_id(api_key: &str) -> String {
+    blake3::hash(api_key.as_bytes()).to_hex().to_string()
+}
+
+/// Fails with `ApiErrorResponse::RateLimited` once the API key has sent the maximum number of
+/// requests of the current window. Rate limiter failures never block requests.
+pub async fn check(state: &AppState, api_key: &str) -> RouterResult<()> {
+    let config = &state.conf.api_rate_limit;
+    if !config.enabled {
+        return Ok(());
+    }
+    let now = now();
+    let window_started_at = get_window_start(config, now);
+    let request_count = match state
+        .store
+        .increment_rate_limit_count(
+            &get_key_id(api_key),
+            window_started_at,
+            config.window_in_secs,
+        )
+        .await
+    {
+        Ok(request_count) => request_count,
+        Err(error) => {
+            logger::error!(?error, "Failed to count request of the API key");
+            return Ok(());
+        }
+    };
+
+    match get_retry_after(config, request_count, window_started_at, now) {
+        Some(retry_after) => {
+            logger::warn!(retry_after, "API key is rate limited");
+            Err(errors::ApiErrorResponse::RateLimited { retry_after }).into_report()
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use actix_web::{http::header, ResponseError};
+
+    use super::*;
+    use crate::{configs::settings::Settings, db::StorageImpl};
+
+    fn get_config() -> ApiRateLimit {
+        ApiRateLimit {
+            enabled: true,
+            max_requests: 3,
+            window_in_secs: 10,
+        }
+    }
+
+    #[test]
+    fn test_requests_over_the_limit_wait_for_the_next_window() {
+        let config = get_config();
+        assert_eq!(get_window_start(&config, 104), 100);
+        assert_eq!(get_window_start(&config, 110), 110);
+
+        assert_eq!(get_retry_after(&config, 3, 100, 104), None);
+        assert_eq!(get_retry_after(&config, 4, 100, 104), Some(6));
+        assert_eq!(get_retry_after(&config, 4, 100, 109), Some(1));
+    }
+
+    #[test]
+    fn test_rate_limited_error_is_429_with_retry_after() {
+        let config = get_config();
+        let retry_after =
+            get_retry_after(&config, 4, 100, 103).expect("Request over the limit was allowed");
+        let response = errors::ApiErrorResponse::RateLimited { retry_after }.error_response();
+        assert_eq!(response.status().as_u16(), 429);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|retry_after| retry_after.to_str().ok()),
+            Some("7")
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_concurrent_requests_are_not_let_through_above_the_limit() {
+        let mut conf = Settings::new().expect("invalid settings");
+        conf.api_rate_limit = ApiRateLimit {
+            enabled: true,
+            max_requests: 5,
+            window_in_secs: 3600,
+        };
+        let state = AppState::test_builder()
+            .settings(conf)
+            .storage(StorageImpl::Mock)
+            .build()
+            .await
+            .expect("Failed to create app state");
+        // Counts are kept in Redis, the key is unique per run
+        let api_key = format!("snd_{}", uuid::Uuid::new_v4());
+
+        let results = futures::future::join_all((0..20).map(|_| check(&state, &api_key))).await;
+
+        let allowed_requests = results.iter().filter(|result| result.is_ok()).count();
+        assert_eq!(allowed_requests, 5);
+    }
+}
@@ -7,6 +7,7 @@
 // Separation of concerns instead of separation of forms.
 
 pub mod api;
+pub mod money;
 pub mod storage;
 pub mod transformers;
 
@@ -16,6 +17,7 @@ pub use api_models::enums::Connector;
 use common_utils::pii::Email;
 use error_stack::{IntoReport, ResultExt};
 
+pub use self::money::Money;
 use self::{api::payments, storage::enums as storage_enums};
 pub use crate::core::payments::PaymentAddress;
 use crate::{core::errors, services};
@@ -28,6 +30,8 @@ pub type PaymentsCaptureRouterData =
 pub type PaymentsCancelRouterData = RouterData<api::Void, PaymentsCancelData, PaymentsResponseData>;
 pub type PaymentsSessionRouterData =
     RouterData<api::Session, PaymentsSessionData, PaymentsResponseData>;
+pub type PaymentsPreProcessingRouterData =
+    RouterData<api::PreProcessing, PaymentsPreProcessingData, PaymentsResponseData>;
 pub type RefundsRouterData<F> = RouterData<F, RefundsData, RefundsResponseData>;
 pub type RefundExecuteRouterData = RouterData<api::Execute, RefundsData, RefundsResponseData>;
 pub type RefundSyncRouterData = RouterData<api::RSync, RefundsData, RefundsResponseData>;
@@ -35,6 +39,9 @@ pub type RefundSyncRouterData = RouterData<api::RSync, RefundsData, RefundsRespo
 pub type RefreshTokenRouterData =
     RouterData<api::AccessTokenAuth, AccessTokenRequestData, AccessToken>;
 
+pub type SubmitEvidenceRouterData =
+    RouterData<api::Evidence, SubmitEvidenceRequestData, SubmitEvidenceResponse>;
+
 pub type PaymentsResponseRouterData<R> =
     ResponseRouterData<api::Authorize, R, PaymentsAuthorizeData, PaymentsResponseData>;
 pub type PaymentsCancelResponseRouterData<R> =
@@ -45,10 +52,17 @@ pub type PaymentsSessionResponseRouterData<R> =
     ResponseRouterData<api::Session, R, PaymentsSessionData, PaymentsResponseData>;
 pub type PaymentsCaptureResponseRouterData<R> =
     ResponseRouterData<api::Capture, R, PaymentsCaptureData, PaymentsResponseData>;
+pub type PaymentsPreProcessingResponseRouterData<R> =
+    ResponseRouterData<api::PreProcessing, R, PaymentsPreProcessingData, PaymentsResponseData>;
+pub type VerifyResponseRouterData<R> =
+    ResponseRouterData<api::Verify, R, VerifyRequestData, PaymentsResponseData>;
 
 pub type RefundsResponseRouterData<F, R> =
     ResponseRouterData<F, R, RefundsData, RefundsResponseData>;
 
+pub type SubmitEvidenceResponseRouterData<R> =
+    ResponseRouterData<api::Evidence, R, SubmitEvidenceRequestData, SubmitEvidenceResponse>;
+
 pub type PaymentsAuthorizeType =
     dyn services::ConnectorIntegration<api::Authorize, PaymentsAuthorizeData, PaymentsResponseData>;
 pub type PaymentsSyncType =
@@ -59,6 +73,13 @@ pub type PaymentsSessionType =
     dyn services::ConnectorIntegration<api::Session, PaymentsSessionData, PaymentsResponseData>;
 pub type PaymentsVoidType =
     dyn services::ConnectorIntegration<api::Void, PaymentsCancelData, PaymentsResponseData>;
+pub type PaymentsPreProcessingType = dyn services::ConnectorIntegration<
+    api::PreProcessing,
+    PaymentsPreProcessingData,
+    PaymentsResponseData,
+>;
+pub type VerifyType =
+    dyn services::ConnectorIntegration<api::Verify, VerifyRequestData, PaymentsResponseData>;
 
 pub type RefundExecuteType =
     dyn services::ConnectorIntegration<api::Execute, RefundsData, RefundsResponseData>;
@@ -68,6 +89,12 @@ pub type RefundSyncType =
 pub type RefreshTokenType =
     dyn services::ConnectorIntegration<api::AccessTokenAuth, AccessTokenRequestData, AccessToken>;
 
+pub type SubmitEvidenceType = dyn services::ConnectorIntegration<
+    api::Evidence,
+    SubmitEvidenceRequestData,
+    SubmitEvidenceResponse,
+>;
+
 pub type VerifyRouterData = RouterData<api::Verify, VerifyRequestData, PaymentsResponseData>;
 
 #[derive(Debug, Clone)]
@@ -87,6 +114,8 @@ pub struct RouterData<Flow, Request, Response> {
     pub auth_type: storage_enums::AuthenticationType,
     pub connector_meta_data: Option<serde_json::Value>,
     pub amount_captured: Option<i64>,
+    /// Amount the connector authorized, when it authorized less than the requested amount
+    pub authorized_amount: Option<i64>,
     pub access_token: Option<AccessToken>,
 
     /// Contains flow-specific data required to construct a request and send it to the connector.
@@ -97,6 +126,49 @@ pub struct RouterData<Flow, Request, Response> {
 
     /// Contains any error response that the connector returns.
     pub payment_method_id: Option<String>,
+
+    /// Response body of the connector call, with card details masked and its length capped.
+    pub connector_response_body: Option<String>,
+}
+
+impl<Flow, Request, Response> RouterData<Flow, Request, Response> {
+    /// Deterministic per payment attempt, so that resending a request does not create a duplicate
+    /// at the connector.
+    pub fn get_idempotency_key(&self) -> String {
+        match &self.attempt_id {
+            Some(attempt_id) => format!("{}_{}", self.payment_id, attempt_id),
+            None => self.payment_id.clone(),
+        }
+    }
+}
+
+impl PaymentsCaptureRouterData {
+    /// Deterministic per capture, so that a retried capture is not executed twice by the
+    /// connector, while the successive partial captures of a payment attempt, which start from
+    /// different captured amounts, remain separate requests.
+    pub fn get_capture_idempotency_key(&self) -> String {
+        format!(
+            "{}_capture_{}",
+            self.get_idempotency_key(),
+            self.amount_captured.unwrap_or(0)
+        )
+    }
+}
+
+impl PaymentsCancelRouterData {
+    /// Deterministic per payment attempt, which can only be voided once, and distinct from the key
+    /// of its authorization.
+    pub fn get_void_idempotency_key(&self) -> String {
+        format!("{}_void", self.get_idempotency_key())
+    }
+}
+
+impl<F> RefundsRouterData<F> {
+    /// Deterministic per refund, so that a retried refund is not executed twice by the connector,
+    /// while partial refunds of the same payment attempt remain separate requests.
+    pub fn get_refund_idempotency_key(&self) -> String {
+        format!("{}_{}", self.payment_id, self.request.refund_id)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -107,6 +179,8 @@ pub struct PaymentsAuthorizeData {
     pub currency: storage_enums::Currency,
     pub confirm: bool,
     pub statement_descriptor_suffix: Option<String>,
+    /// Shortened descriptor shown before the suffix on the customer's statement.
+    pub statement_descriptor_prefix: Option<String>,
     pub capture_method: Option<storage_enums::CaptureMethod>,
     // Mandates
     pub setup_future_usage: Option<storage_enums::FutureUsage>,
@@ -115,6 +189,24 @@ pub struct PaymentsAuthorizeData {
     pub setup_mandate_details: Option<payments::MandateData>,
     pub browser_info: Option<BrowserInformation>,
     pub order_details: Option<api_models::payments::OrderDetails>,
+    /// Breakdown of the surcharge included in `amount`, if any.
+    pub surcharge_details: Option<api_models::payments::SurchargeDetails>,
+    /// Tax included in `amount`, if any.
+    pub tax_amount: Option<i64>,
+    /// Exemption from strong customer authentication requested by the merchant, if any.
+    pub sca_exemption: Option<api_models::enums::ScaExemptionType>,
+    /// Connector specific values obtained in the pre-processing step, if any.
+    pub connector_meta: Option<serde_json::Value>,
+    /// Set when the card of `payment_method_data` is a network token rather than a card number.
+    pub network_token: Option<NetworkTokenData>,
+}
+
+/// Cryptogram authenticating one payment made with a network token.
+#[derive(Debug, Clone)]
+pub struct NetworkTokenData {
+    pub cryptogram: masking::Secret<String>,
+    /// Electronic commerce indicator returned along with the cryptogram, if any.
+    pub eci: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -147,9 +239,29 @@ pub struct PaymentsSessionData {
     pub order_details: Option<api_models::payments::OrderDetails>,
 }
 
+#[derive(Debug, Clone)]
+pub struct PaymentsPreProcessingData {
+    pub payment_method_data: payments::PaymentMethod,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub email: Option<masking::Secret<String, Email>>,
+}
+
+impl From<&PaymentsAuthorizeData> for PaymentsPreProcessingData {
+    fn from(data: &PaymentsAuthorizeData) -> Self {
+        Self {
+            payment_method_data: data.payment_method_data.clone(),
+            amount: data.amount,
+            currency: data.currency,
+            email: data.email.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct VerifyRequestData {
     pub payment_method_data: payments::PaymentMethod,
+    pub currency: storage_enums::Currency,
     pub confirm: bool,
     pub statement_descriptor_suffix: Option<String>,
     pub mandate_id: Option<api_models::payments::MandateIds>,
@@ -184,10 +296,21 @@ pub enum PaymentsResponseData {
         redirect: bool,
         mandate_reference: Option<String>,
         connector_metadata: Option<serde_json::Value>,
+        /// Outcome of the address verification, if the connector performed one
+        avs_result: Option<storage_enums::AvsResult>,
+        /// Outcome of the card security code check, if the connector performed one
+        cvv_result: Option<storage_enums::CvvResult>,
+        /// Reference the connector shows for the payment on its dashboard, when it differs from
+        /// the connector transaction id
+        connector_response_reference_id: Option<String>,
     },
     SessionResponse {
         session_token: api::SessionToken,
     },
+    PreProcessingResponse {
+        pre_processing_id: String,
+        connector_metadata: Option<serde_json::Value>,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -250,6 +373,18 @@ pub struct RefundsResponseData {
     // pub amount_received: Option<i32>, // Calculation for amount received not in place yet
 }
 
+#[derive(Debug, Clone)]
+pub struct SubmitEvidenceRequestData {
+    pub dispute_id: String,
+    pub connector_dispute_id: String,
+    pub evidence: api::SubmitEvidenceRequest,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmitEvidenceResponse {
+    pub evidence_status: storage_enums::EvidenceStatus,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Redirection {
     Redirect,
@@ -273,7 +408,7 @@ pub struct ResponseRouterData<Flow, R, Request, Response> {
 }
 
 // Different patterns of authentication.
-#[derive(Default, Debug, Clone, serde::Deserialize)]
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "auth_type")]
 pub enum ConnectorAuthType {
     HeaderKey {
@@ -309,6 +444,20 @@ pub struct ErrorResponse {
     pub message: String,
     pub reason: Option<String>,
     pub status_code: u16,
+    /// Set for card declines, to tell whether the payment could succeed if it is retried.
+    pub decline_type: Option<DeclineType>,
+}
+
+/// Classification of a declined payment, used to decide whether to retry it with another connector.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeclineType {
+    /// The decline is temporary, like insufficient funds, and the payment can be retried.
+    Soft,
+    /// The decline is permanent, like a stolen card, and retrying the payment will not help.
+    Hard,
+    /// The connector could not process the payment at the time, like when the bank did not
+    /// respond, and the same request may succeed when it is sent again later.
+    Transient,
 }
 
 impl ErrorResponse {
@@ -324,6 +473,7 @@ impl ErrorResponse {
             .error_message(),
             reason: None,
             status_code: http::StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            decline_type: None,
         }
     }
 }
@@ -359,8 +509,13 @@ impl From<errors::ApiErrorResponse> for ErrorResponse {
             reason: None,
             status_code: match error {
                 errors::ApiErrorResponse::ExternalConnectorError { status_code, .. } => status_code,
+                errors::ApiErrorResponse::ConnectorRateLimited { .. }
+                | errors::ApiErrorResponse::RateLimited { .. } => 429,
+                errors::ApiErrorResponse::ConnectorTimeout => 504,
+                errors::ApiErrorResponse::ConnectorUnavailable => 503,
                 _ => 500,
             },
+            decline_type: None,
         }
     }
 }
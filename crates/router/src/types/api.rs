@@ -2,6 +2,7 @@ pub mod admin;
 pub mod api_keys;
 pub mod configs;
 pub mod customers;
+pub mod disputes;
 pub mod enums;
 pub mod mandates;
 pub mod payment_methods;
@@ -11,11 +12,12 @@ pub mod webhooks;
 
 use std::{fmt::Debug, str::FromStr};
 
+use common_utils::errors::CryptoError;
 use error_stack::{report, IntoReport, ResultExt};
 
 pub use self::{
-    admin::*, api_keys::*, configs::*, customers::*, payment_methods::*, payments::*, refunds::*,
-    webhooks::*,
+    admin::*, api_keys::*, configs::*, customers::*, disputes::*, payment_methods::*, payments::*,
+    refunds::*, webhooks::*,
 };
 use super::ErrorResponse;
 use crate::{
@@ -23,12 +25,133 @@ use crate::{
     connector, consts,
     core::errors::{self, CustomResult},
     services::{ConnectorIntegration, ConnectorRedirectResponse},
-    types::{self, api::enums as api_enums},
+    types::{self, api::enums as api_enums, storage::enums as storage_enums},
 };
 
+/// An operation that can be requested from a connector.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectorFlow {
+    Authorize,
+    Capture,
+    Void,
+    Verify,
+    Refund,
+    /// Submitting evidence for a dispute
+    SubmitEvidence,
+    /// Authorizing a payment that requires 3DS authentication
+    ThreeDs,
+    /// Setting up or charging a mandate
+    Mandate,
+    /// Capturing a payment in several parts
+    MultipleCapture,
+    /// Authorizing a saved card stored as a network token, with its cryptogram
+    NetworkToken,
+}
+
+/// What a connector supports, used to reject requests it cannot handle before calling it.
+/// A field left as `None` places no restriction.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectorCapabilities {
+    pub flows: Option<&'static [ConnectorFlow]>,
+    pub payment_methods: Option<&'static [storage_enums::PaymentMethodType]>,
+    pub currencies: Option<&'static [storage_enums::Currency]>,
+    /// Whether an authorization can be captured in several parts. Connectors have to opt into
+    /// this, since most of them close the authorization on the first capture.
+    pub multiple_captures: bool,
+    /// Whether the connector sends the cryptogram of a card stored as a network token. Connectors
+    /// have to opt into this, since the others would send the token as if it were a card number.
+    pub network_tokens: bool,
+}
+
+impl ConnectorCapabilities {
+    pub fn supports_flow(&self, flow: ConnectorFlow) -> bool {
+        match flow {
+            ConnectorFlow::MultipleCapture => self.multiple_captures,
+            ConnectorFlow::NetworkToken => self.network_tokens,
+            _ => self.flows.map_or(true, |flows| flows.contains(&flow)),
+        }
+    }
+
+    pub fn supports_payment_method(
+        &self,
+        payment_method: storage_enums::PaymentMethodType,
+    ) -> bool {
+        self.payment_methods.map_or(true, |payment_methods| {
+            payment_methods.contains(&payment_method)
+        })
+    }
+
+    pub fn supports_currency(&self, currency: storage_enums::Currency) -> bool {
+        self.currencies
+            .map_or(true, |currencies| currencies.contains(&currency))
+    }
+
+    /// Checks that the connector can perform every flow in `flows` for the given payment method
+    /// and currency, the currency is skipped for flows that do not carry one.
+    pub fn validate(
+        &self,
+        connector: &str,
+        flows: &[ConnectorFlow],
+        payment_method: storage_enums::PaymentMethodType,
+        currency: Option<storage_enums::Currency>,
+    ) -> CustomResult<(), errors::ConnectorError> {
+        if let Some(flow) = flows.iter().find(|flow| !self.supports_flow(**flow)) {
+            Err(errors::ConnectorError::FlowNotSupported {
+                flow: flow.to_string(),
+                connector: connector.to_string(),
+            })
+            .into_report()
+        } else if !self.supports_payment_method(payment_method) {
+            Err(errors::ConnectorError::PaymentMethodNotSupported {
+                payment_method: payment_method.to_string(),
+                connector: connector.to_string(),
+            })
+            .into_report()
+        } else {
+            match currency {
+                Some(currency) if !self.supports_currency(currency) => {
+                    Err(errors::ConnectorError::CurrencyNotSupported {
+                        currency: currency.to_string(),
+                        connector: connector.to_string(),
+                    })
+                    .into_report()
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+/// Name of a flow type, which picks the policy of its connector requests and labels their
+/// metrics.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+pub enum FlowName {
+    Authorize,
+    Capture,
+    PSync,
+    Void,
+    Session,
+    Verify,
+    PreProcessing,
+    Execute,
+    RSync,
+    Evidence,
+    AccessTokenAuth,
+}
+
+/// Implemented by the flow types that connector requests are made for.
+pub trait FlowType {
+    const FLOW_NAME: FlowName;
+}
+
 #[derive(Clone, Debug)]
 pub struct AccessTokenAuth;
 
+impl FlowType for AccessTokenAuth {
+    const FLOW_NAME: FlowName = FlowName::AccessTokenAuth;
+}
+
 pub trait ConnectorAccessToken:
     ConnectorIntegration<AccessTokenAuth, types::AccessTokenRequestData, types::AccessToken>
 {
@@ -58,6 +181,12 @@ pub trait ConnectorCommon {
     /// The base URL for interacting with the connector's API.
     fn base_url<'a>(&self, connectors: &'a Connectors) -> &'a str;
 
+    /// Flows, payment methods and currencies supported by the connector.
+    /// Defaults to placing no restrictions.
+    fn get_capabilities(&self) -> ConnectorCapabilities {
+        ConnectorCapabilities::default()
+    }
+
     /// common error response for a connector if it is same in all case
     fn build_error_response(
         &self,
@@ -68,8 +197,24 @@ pub trait ConnectorCommon {
             code: consts::NO_ERROR_CODE.to_string(),
             message: consts::NO_ERROR_MESSAGE.to_string(),
             reason: None,
+            decline_type: None,
         })
     }
+
+    /// Order of the request body fields in the message the connector signs.
+    /// Defaults to keys sorted at every level of the body.
+    fn get_signature_field_order(&self) -> connector::utils::SignatureFieldOrder {
+        connector::utils::SignatureFieldOrder::SortedKeys
+    }
+
+    /// Request body in the canonical form of the connector, which is signed and sent as is so
+    /// that the connector computes its signature over the same message.
+    fn get_canonical_request_body(
+        &self,
+        body: &serde_json::Value,
+    ) -> CustomResult<String, CryptoError> {
+        connector::utils::get_canonical_json(body, self.get_signature_field_order())
+    }
 }
 
 /// Extended trait for connector common to allow functions with generic type
@@ -89,7 +234,14 @@ pub trait ConnectorCommonExt<Flow, Req, Resp>:
 pub trait Router {}
 
 pub trait Connector:
-    Send + Refund + Payment + Debug + ConnectorRedirectResponse + IncomingWebhook + ConnectorAccessToken
+    Send
+    + Refund
+    + Payment
+    + Debug
+    + ConnectorRedirectResponse
+    + IncomingWebhook
+    + ConnectorAccessToken
+    + SubmitEvidence
 {
 }
 
@@ -104,7 +256,8 @@ impl<
             + ConnectorRedirectResponse
             + Send
             + IncomingWebhook
-            + ConnectorAccessToken,
+            + ConnectorAccessToken
+            + SubmitEvidence,
     > Connector for T
 {
 }
@@ -128,6 +281,8 @@ pub enum ConnectorCallType {
     Routing,
     Multiple(Vec<ConnectorData>),
     Single(ConnectorData),
+    /// Connectors are tried in order until one of them does not soft decline the payment.
+    Failover(Vec<ConnectorData>),
 }
 
 impl ConnectorCallType {
@@ -144,9 +299,10 @@ impl ConnectorData {
     ) -> CustomResult<Self, errors::ApiErrorResponse> {
         let connector = Self::convert_connector(connectors, name)?;
         let connector_name = api_enums::Connector::from_str(name)
+            .map_err(|error| errors::ApiErrorResponse::InvalidRequestData {
+                message: error.to_string(),
+            })
             .into_report()
-            .change_context(errors::ConnectorError::InvalidConnectorName)
-            .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable_lazy(|| format!("unable to parse connector name {connector:?}"))?;
         Ok(Self {
             connector,
@@ -167,18 +323,60 @@ impl ConnectorData {
             "braintree" => Ok(Box::new(&connector::Braintree)),
             "checkout" => Ok(Box::new(&connector::Checkout)),
             "cybersource" => Ok(Box::new(&connector::Cybersource)),
+            #[cfg(feature = "dummy_connector")]
+            "dummy" => Ok(Box::new(&connector::DummyConnector)),
             "fiserv" => Ok(Box::new(&connector::Fiserv)),
             "globalpay" => Ok(Box::new(&connector::Globalpay)),
             "klarna" => Ok(Box::new(&connector::Klarna)),
+            "opayo" => Ok(Box::new(&connector::Opayo)),
             "payu" => Ok(Box::new(&connector::Payu)),
             "rapyd" => Ok(Box::new(&connector::Rapyd)),
             "shift4" => Ok(Box::new(&connector::Shift4)),
             "stripe" => Ok(Box::new(&connector::Stripe)),
             "worldline" => Ok(Box::new(&connector::Worldline)),
             "worldpay" => Ok(Box::new(&connector::Worldpay)),
-            _ => Err(report!(errors::ConnectorError::InvalidConnectorName)
-                .attach_printable(format!("invalid connector name: {connector_name}")))
-            .change_context(errors::ApiErrorResponse::InternalServerError),
+            _ => {
+                let error = api_enums::ConnectorParseError::new(connector_name);
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: error.to_string(),
+                }))
+                .attach_printable(errors::ConnectorError::InvalidConnectorName(error))
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_unknown_connector_name_is_a_request_error() {
+        let error = ConnectorData::get_connector_by_name(
+            &Connectors::default(),
+            "strpie",
+            GetToken::Connector,
+        )
+        .err()
+        .unwrap();
+
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { message }
+                if message == "Invalid connector name `strpie`, did you mean `stripe`?"
+        ));
+    }
+
+    #[test]
+    fn test_network_tokens_require_opt_in() {
+        let capabilities = ConnectorCapabilities::default();
+        let opted_in = ConnectorCapabilities {
+            network_tokens: true,
+            ..Default::default()
+        };
+
+        assert!(!capabilities.supports_flow(ConnectorFlow::NetworkToken));
+        assert!(opted_in.supports_flow(ConnectorFlow::NetworkToken));
+    }
+}
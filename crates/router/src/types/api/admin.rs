@@ -2,7 +2,7 @@ pub use api_models::admin::{
     CreateMerchantAccount, DeleteMcaResponse, DeleteMerchantAccountResponse,
     MerchantAccountResponse, MerchantConnectorId, MerchantDetails, MerchantId,
     PaymentConnectorCreate, PaymentMethods, RoutingAlgorithm, ToggleKVRequest, ToggleKVResponse,
-    WebhookDetails,
+    WebhookDetails, WeightedConnector,
 };
 
 use crate::types::{storage, transformers::Foreign};
@@ -0,0 +1,41 @@
+pub use api_models::disputes::{
+    DisputeResponse, DisputeStatus, EvidenceStatus, SubmitEvidenceRequest,
+};
+
+use crate::{
+    services::api,
+    types::{self, storage::enums as storage_enums, transformers::Foreign},
+};
+
+impl From<Foreign<storage_enums::DisputeStatus>> for Foreign<DisputeStatus> {
+    fn from(status: Foreign<storage_enums::DisputeStatus>) -> Self {
+        match status.0 {
+            storage_enums::DisputeStatus::Opened => DisputeStatus::Opened,
+            storage_enums::DisputeStatus::Won => DisputeStatus::Won,
+            storage_enums::DisputeStatus::Lost => DisputeStatus::Lost,
+        }
+        .into()
+    }
+}
+
+impl From<Foreign<storage_enums::EvidenceStatus>> for Foreign<EvidenceStatus> {
+    fn from(status: Foreign<storage_enums::EvidenceStatus>) -> Self {
+        match status.0 {
+            storage_enums::EvidenceStatus::Submitted => EvidenceStatus::Submitted,
+            storage_enums::EvidenceStatus::Failed => EvidenceStatus::Failed,
+        }
+        .into()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Evidence;
+
+impl super::FlowType for Evidence {
+    const FLOW_NAME: super::FlowName = super::FlowName::Evidence;
+}
+
+pub trait SubmitEvidence:
+    api::ConnectorIntegration<Evidence, types::SubmitEvidenceRequestData, types::SubmitEvidenceResponse>
+{
+}
@@ -64,20 +64,53 @@ impl super::Router for PaymentsRequest {}
 // Core related api layer.
 #[derive(Debug, Clone)]
 pub struct Authorize;
+
+impl super::FlowType for Authorize {
+    const FLOW_NAME: super::FlowName = super::FlowName::Authorize;
+}
+
 #[derive(Debug, Clone)]
 pub struct Capture;
 
+impl super::FlowType for Capture {
+    const FLOW_NAME: super::FlowName = super::FlowName::Capture;
+}
+
 #[derive(Debug, Clone)]
 pub struct PSync;
+
+impl super::FlowType for PSync {
+    const FLOW_NAME: super::FlowName = super::FlowName::PSync;
+}
+
 #[derive(Debug, Clone)]
 pub struct Void;
 
+impl super::FlowType for Void {
+    const FLOW_NAME: super::FlowName = super::FlowName::Void;
+}
+
 #[derive(Debug, Clone)]
 pub struct Session;
 
+impl super::FlowType for Session {
+    const FLOW_NAME: super::FlowName = super::FlowName::Session;
+}
+
 #[derive(Debug, Clone)]
 pub struct Verify;
 
+impl super::FlowType for Verify {
+    const FLOW_NAME: super::FlowName = super::FlowName::Verify;
+}
+
+#[derive(Debug, Clone)]
+pub struct PreProcessing;
+
+impl super::FlowType for PreProcessing {
+    const FLOW_NAME: super::FlowName = super::FlowName::PreProcessing;
+}
+
 pub(crate) trait PaymentIdTypeExt {
     fn get_payment_intent_id(&self) -> errors::CustomResult<String, errors::ValidationError>;
 }
@@ -164,6 +197,15 @@ pub trait PreVerify:
 {
 }
 
+pub trait PaymentsPreProcessing:
+    api::ConnectorIntegration<
+    PreProcessing,
+    types::PaymentsPreProcessingData,
+    types::PaymentsResponseData,
+>
+{
+}
+
 pub trait Payment:
     api_types::ConnectorCommon
     + PaymentAuthorize
@@ -172,6 +214,7 @@ pub trait Payment:
     + PaymentVoid
     + PreVerify
     + PaymentSession
+    + PaymentsPreProcessing
 {
 }
 
@@ -1,5 +1,6 @@
 pub use api_models::refunds::{
-    RefundRequest, RefundResponse, RefundStatus, RefundType, RefundUpdateRequest,
+    BulkRefundError, BulkRefundRequest, BulkRefundResponse, BulkRefundResult, RefundRequest,
+    RefundResponse, RefundStatus, RefundType, RefundUpdateRequest,
 };
 
 use super::ConnectorCommon;
@@ -23,9 +24,18 @@ impl From<Foreign<storage_enums::RefundStatus>> for Foreign<RefundStatus> {
 
 #[derive(Debug, Clone)]
 pub struct Execute;
+
+impl super::FlowType for Execute {
+    const FLOW_NAME: super::FlowName = super::FlowName::Execute;
+}
+
 #[derive(Debug, Clone)]
 pub struct RSync;
 
+impl super::FlowType for RSync {
+    const FLOW_NAME: super::FlowName = super::FlowName::RSync;
+}
+
 pub trait RefundExecute:
     api::ConnectorIntegration<Execute, types::RefundsData, types::RefundsResponseData>
 {
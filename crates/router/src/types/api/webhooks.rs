@@ -1,6 +1,7 @@
 pub use api_models::webhooks::{
-    IncomingWebhookDetails, IncomingWebhookEvent, MerchantWebhookConfig, OutgoingWebhook,
-    OutgoingWebhookContent, WebhookFlow,
+    DisputePayload, IncomingWebhookDetails, IncomingWebhookEvent, MerchantWebhookConfig,
+    OutgoingWebhook, OutgoingWebhookContent, WebhookDeliveryAttemptListRequest,
+    WebhookDeliveryAttemptListResponse, WebhookDeliveryAttemptResponse, WebhookFlow,
 };
 use error_stack::ResultExt;
 
@@ -8,6 +9,7 @@ use super::ConnectorCommon;
 use crate::{
     core::errors::{self, CustomResult},
     db::StorageInterface,
+    routes::AppState,
     services,
     utils::crypto,
 };
@@ -60,17 +62,22 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
             .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)
     }
 
+    /// Scheme the connector signs its webhooks with, unsigned unless the connector declares one
+    fn get_webhook_signature_scheme(&self) -> crypto::WebhookSignatureScheme {
+        crypto::WebhookSignatureScheme::None
+    }
+
     fn get_webhook_source_verification_algorithm(
         &self,
         _headers: &actix_web::http::header::HeaderMap,
         _body: &[u8],
     ) -> CustomResult<Box<dyn crypto::VerifySignature + Send>, errors::ConnectorError> {
-        Ok(Box::new(crypto::NoAlgorithm))
+        Ok(self.get_webhook_signature_scheme().get_verifier())
     }
 
     async fn get_webhook_source_verification_merchant_secret(
         &self,
-        _db: &dyn StorageInterface,
+        _state: &AppState,
         _merchant_id: &str,
     ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
         Ok(Vec::new())
@@ -96,7 +103,7 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
 
     async fn verify_webhook_source(
         &self,
-        db: &dyn StorageInterface,
+        state: &AppState,
         headers: &actix_web::http::header::HeaderMap,
         body: &[u8],
         merchant_id: &str,
@@ -109,7 +116,7 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
             .get_webhook_source_verification_signature(headers, body)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
         let secret = self
-            .get_webhook_source_verification_merchant_secret(db, merchant_id)
+            .get_webhook_source_verification_merchant_secret(state, merchant_id)
             .await
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
         let message = self
@@ -121,6 +128,14 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)
     }
 
+    /// Identifier of the webhook delivery, used to skip webhooks redelivered by the connector.
+    fn get_webhook_event_id(
+        &self,
+        _body: &[u8],
+    ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        Ok(None)
+    }
+
     fn get_webhook_object_reference_id(
         &self,
         _body: &[u8],
@@ -136,6 +151,13 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
         _body: &[u8],
     ) -> CustomResult<serde_json::Value, errors::ConnectorError>;
 
+    fn get_dispute_details(
+        &self,
+        _body: &[u8],
+    ) -> CustomResult<DisputePayload, errors::ConnectorError> {
+        Err(errors::ConnectorError::NotImplemented("get_dispute_details method".to_string()).into())
+    }
+
     fn get_webhook_api_response(
         &self,
     ) -> CustomResult<services::api::ApplicationResponse<serde_json::Value>, errors::ConnectorError>
@@ -0,0 +1,201 @@
+use common_utils::fp_utils::when;
+use error_stack::report;
+
+use super::storage::enums;
+use crate::core::errors::{self, CustomResult};
+
+/// An amount expressed in the minor unit of its currency, e.g. cents for USD and yen for JPY.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Money {
+    pub amount: i64,
+    pub currency: enums::Currency,
+}
+
+impl Money {
+    pub fn new(amount: i64, currency: enums::Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    /// Parses an amount given in the major unit of the currency, e.g. `10.50` for USD, into its
+    /// minor unit. Decimal places beyond those of the currency are only accepted when they are
+    /// zeros, so that the amount is never rounded.
+    pub fn from_major_unit_str(
+        amount: &str,
+        currency: enums::Currency,
+    ) -> CustomResult<Self, errors::ValidationError> {
+        let invalid_amount = || {
+            report!(errors::ValidationError::InvalidValue {
+                message: format!(
+                    "Amount {amount} is not a valid amount in {}",
+                    currency.iso_4217_code()
+                ),
+            })
+        };
+        let (sign, unsigned_amount) = match amount.strip_prefix('-') {
+            Some(unsigned_amount) => (-1, unsigned_amount),
+            None => (1, amount),
+        };
+        let (whole, fraction) = unsigned_amount
+            .split_once('.')
+            .unwrap_or((unsigned_amount, ""));
+        let is_digits = |part: &str| part.bytes().all(|digit| digit.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(invalid_amount());
+        }
+
+        let exponent = usize::try_from(currency.minor_unit_exponent()).unwrap_or_default();
+        let (fraction, excess_fraction) = fraction.split_at(fraction.len().min(exponent));
+        if excess_fraction.bytes().any(|digit| digit != b'0') {
+            return Err(invalid_amount());
+        }
+        let padding = std::iter::repeat(b'0').take(exponent - fraction.len());
+        let minor_amount = whole
+            .bytes()
+            .chain(fraction.bytes())
+            .chain(padding)
+            .try_fold(0_i64, |minor_amount, digit| {
+                minor_amount
+                    .checked_mul(10)?
+                    .checked_add(i64::from(digit - b'0'))
+            })
+            .ok_or_else(invalid_amount)?;
+        Ok(Self::new(sign * minor_amount, currency))
+    }
+
+    /// The amount in the minor unit of the currency, as expected by most connectors.
+    pub fn minor_unit(&self) -> i64 {
+        self.amount
+    }
+
+    /// The amount in the major unit of the currency, formatted with as many decimal places as
+    /// the currency has, e.g. `10.50` for USD, `1050` for JPY and `1.050` for BHD.
+    pub fn to_major_unit_string(&self) -> String {
+        let exponent = self.currency.minor_unit_exponent();
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let amount = self.amount.unsigned_abs();
+        if exponent == 0 {
+            return format!("{sign}{amount}");
+        }
+        let divisor = 10_u64.pow(exponent);
+        format!(
+            "{sign}{}.{:0width$}",
+            amount / divisor,
+            amount % divisor,
+            width = usize::try_from(exponent).unwrap_or_default()
+        )
+    }
+
+    /// Three letter code of the currency as defined by ISO 4217.
+    pub fn currency_code(&self) -> &'static str {
+        self.currency.iso_4217_code()
+    }
+
+    /// Validates that the amount can be charged in the currency without any rounding.
+    ///
+    /// Amounts of three decimal currencies must be a multiple of 10, since most connectors
+    /// only accept two decimal places for them and would otherwise round the amount.
+    pub fn validate(&self) -> CustomResult<(), errors::ValidationError> {
+        when(self.amount < 0, || {
+            Err(report!(errors::ValidationError::InvalidValue {
+                message: format!("Amount {} must not be negative", self.amount),
+            }))
+        })?;
+
+        let increment = match self.currency.minor_unit_exponent() {
+            3 => 10,
+            _ => 1,
+        };
+        when(self.amount % increment != 0, || {
+            Err(report!(errors::ValidationError::InvalidValue {
+                message: format!(
+                    "Amount {} in {} must be a multiple of {increment}",
+                    self.amount,
+                    self.currency_code()
+                ),
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_decimal_currency_major_unit() {
+        assert_eq!(
+            Money::new(1050, enums::Currency::USD).to_major_unit_string(),
+            "10.50"
+        );
+        assert_eq!(
+            Money::new(5, enums::Currency::GBP).to_major_unit_string(),
+            "0.05"
+        );
+        assert_eq!(
+            Money::new(-5, enums::Currency::GBP).to_major_unit_string(),
+            "-0.05"
+        );
+    }
+
+    #[test]
+    fn test_zero_decimal_currency_major_unit() {
+        let money = Money::new(1050, enums::Currency::JPY);
+        assert_eq!(money.to_major_unit_string(), "1050");
+        assert_eq!(money.minor_unit(), 1050);
+        assert_eq!(money.currency_code(), "JPY");
+    }
+
+    #[test]
+    fn test_three_decimal_currency_major_unit() {
+        let money = Money::new(1050, enums::Currency::BHD);
+        assert_eq!(money.to_major_unit_string(), "1.050");
+        assert_eq!(money.minor_unit(), 1050);
+        assert_eq!(money.currency_code(), "BHD");
+    }
+
+    #[test]
+    fn test_major_unit_amount_parsing() {
+        let parse = |amount, currency| {
+            Money::from_major_unit_str(amount, currency).map(|money| money.minor_unit())
+        };
+        assert_eq!(parse("10.50", enums::Currency::USD).ok(), Some(1050));
+        assert_eq!(parse("10.5", enums::Currency::USD).ok(), Some(1050));
+        assert_eq!(parse("10", enums::Currency::USD).ok(), Some(1000));
+        assert_eq!(parse("-0.05", enums::Currency::GBP).ok(), Some(-5));
+        assert_eq!(parse("1050.00", enums::Currency::JPY).ok(), Some(1050));
+        assert_eq!(parse("1.050", enums::Currency::BHD).ok(), Some(1050));
+
+        assert!(parse("10.505", enums::Currency::USD).is_err());
+        assert!(parse("10.5a", enums::Currency::USD).is_err());
+        assert!(parse(".50", enums::Currency::USD).is_err());
+        assert!(parse("", enums::Currency::USD).is_err());
+        assert!(parse("99999999999999999999", enums::Currency::USD).is_err());
+    }
+
+    #[test]
+    fn test_two_decimal_currency_amount_validation() {
+        assert!(Money::new(1055, enums::Currency::USD).validate().is_ok());
+        assert!(Money::new(0, enums::Currency::USD).validate().is_ok());
+        assert!(Money::new(-1055, enums::Currency::USD).validate().is_err());
+    }
+
+    #[test]
+    fn test_zero_decimal_currency_amount_validation() {
+        assert!(Money::new(1, enums::Currency::JPY).validate().is_ok());
+        assert!(Money::new(1055, enums::Currency::JPY).validate().is_ok());
+        assert!(Money::new(-1, enums::Currency::JPY).validate().is_err());
+    }
+
+    #[test]
+    fn test_three_decimal_currency_amount_validation() {
+        assert!(Money::new(1050, enums::Currency::BHD).validate().is_ok());
+        assert!(Money::new(10, enums::Currency::KWD).validate().is_ok());
+
+        let result = Money::new(1055, enums::Currency::BHD).validate();
+        assert!(matches!(
+            result.as_ref().map_err(|error| error.current_context()),
+            Err(errors::ValidationError::InvalidValue { .. })
+        ));
+        assert!(Money::new(1, enums::Currency::KWD).validate().is_err());
+    }
+}
@@ -3,9 +3,11 @@ pub mod api_keys;
 pub mod configs;
 pub mod connector_response;
 pub mod customers;
+pub mod dispute;
 pub mod enums;
 pub mod ephemeral_key;
 pub mod events;
+pub mod failed_payment_attempt;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
@@ -15,6 +17,7 @@ pub mod payment_intent;
 pub mod payment_method;
 pub mod process_tracker;
 pub mod reverse_lookup;
+pub mod webhook_delivery_attempt;
 
 mod query;
 pub mod refund;
@@ -23,8 +26,8 @@ pub mod refund;
 pub mod kv;
 
 pub use self::{
-    address::*, api_keys::*, configs::*, connector_response::*, customers::*, events::*,
-    locker_mock_up::*, mandate::*, merchant_account::*, merchant_connector_account::*,
+    address::*, api_keys::*, configs::*, connector_response::*, customers::*, dispute::*,
+    events::*, failed_payment_attempt::*, locker_mock_up::*, mandate::*, merchant_account::*, merchant_connector_account::*,
     payment_attempt::*, payment_intent::*, payment_method::*, process_tracker::*, refund::*,
-    reverse_lookup::*,
+    reverse_lookup::*, webhook_delivery_attempt::*,
 };
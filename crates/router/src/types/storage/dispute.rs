@@ -0,0 +1 @@
+pub use storage_models::dispute::{Dispute, DisputeNew, DisputeUpdate};
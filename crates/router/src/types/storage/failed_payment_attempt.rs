@@ -0,0 +1 @@
+pub use storage_models::failed_payment_attempt::{FailedPaymentAttempt, FailedPaymentAttemptNew};
@@ -1,5 +1,5 @@
 use async_bb8_diesel::AsyncRunQueryDsl;
-use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, QueryDsl};
 use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 pub use storage_models::{
@@ -10,7 +10,12 @@ pub use storage_models::{
     schema::payment_intent::dsl,
 };
 
-use crate::{connection::PgPooledConn, core::errors::CustomResult, types::api};
+use crate::{
+    connection::PgPooledConn,
+    core::errors::{CustomResult, ValidationError},
+    db::pagination::Cursor,
+    types::api,
+};
 
 #[cfg(feature = "kv_store")]
 impl crate::utils::storage_partitioning::KvStorePartition for PaymentIntent {}
@@ -21,6 +26,7 @@ pub trait PaymentIntentDbExt: Sized {
         conn: &PgPooledConn,
         merchant_id: &str,
         pc: &api::PaymentListConstraints,
+        cursor: Option<&Cursor>,
     ) -> CustomResult<Vec<Self>, errors::DatabaseError>;
 }
 
@@ -31,6 +37,7 @@ impl PaymentIntentDbExt for PaymentIntent {
         conn: &PgPooledConn,
         merchant_id: &str,
         pc: &api::PaymentListConstraints,
+        cursor: Option<&Cursor>,
     ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
         let customer_id = &pc.customer_id;
         let starting_after = &pc.starting_after;
@@ -40,7 +47,7 @@ impl PaymentIntentDbExt for PaymentIntent {
         // when https://github.com/rust-lang/rust/issues/52662 becomes stable
         let mut filter = <Self as HasTable>::table()
             .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
-            .order(dsl::modified_at.desc())
+            .order((dsl::created_at.desc(), dsl::id.desc()))
             .into_boxed();
 
         if let Some(customer_id) = customer_id {
@@ -59,7 +66,7 @@ impl PaymentIntentDbExt for PaymentIntent {
             filter = filter.filter(dsl::created_at.le(created_lte));
         }
         if let Some(created_gte) = pc.created_gte {
-            filter = filter.filter(dsl::created_at.gt(created_gte));
+            filter = filter.filter(dsl::created_at.ge(created_gte));
         }
         if let Some(starting_after) = starting_after {
             let id = Self::find_by_payment_id_merchant_id(conn, starting_after, merchant_id)
@@ -74,6 +81,14 @@ impl PaymentIntentDbExt for PaymentIntent {
             filter = filter.filter(dsl::id.lt(id.to_owned()));
         }
 
+        if let Some(cursor) = cursor {
+            filter = filter.filter(
+                dsl::created_at.lt(cursor.created_at).or(dsl::created_at
+                    .eq(cursor.created_at)
+                    .and(dsl::id.lt(cursor.id))),
+            );
+        }
+
         filter = filter.limit(pc.limit);
 
         crate::logger::debug!(query = %diesel::debug_query::<diesel::pg::Pg, _>(&filter).to_string());
@@ -86,3 +101,25 @@ impl PaymentIntentDbExt for PaymentIntent {
             .attach_printable_lazy(|| "Error filtering records by predicate")
     }
 }
+
+/// Checks that the creation time range of the payment list constraints is not inverted.
+pub fn validate_created_time_range(
+    pc: &api::PaymentListConstraints,
+) -> CustomResult<(), ValidationError> {
+    let lower_bounds = [pc.created_gt, pc.created_gte];
+    let upper_bounds = [pc.created_lt, pc.created_lte];
+    let is_inverted = lower_bounds.iter().flatten().any(|lower_bound| {
+        upper_bounds
+            .iter()
+            .flatten()
+            .any(|upper_bound| lower_bound > upper_bound)
+    });
+
+    crate::utils::when(is_inverted, || {
+        Err(ValidationError::InvalidValue {
+            message: "created.gt and created.gte must not be later than created.lt and created.lte"
+                .to_string(),
+        })
+        .into_report()
+    })
+}
@@ -1,13 +1,13 @@
 use async_bb8_diesel::AsyncRunQueryDsl;
 use common_utils::errors::CustomResult;
-use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, QueryDsl};
 use error_stack::{IntoReport, ResultExt};
 pub use storage_models::refund::{
     Refund, RefundCoreWorkflow, RefundNew, RefundUpdate, RefundUpdateInternal,
 };
 use storage_models::{errors, schema::refund::dsl};
 
-use crate::{connection::PgPooledConn, logger};
+use crate::{connection::PgPooledConn, db::pagination::Cursor, logger};
 
 #[cfg(feature = "kv_store")]
 impl crate::utils::storage_partitioning::KvStorePartition for Refund {}
@@ -19,6 +19,7 @@ pub trait RefundDbExt: Sized {
         merchant_id: &str,
         refund_list_details: &api_models::refunds::RefundListRequest,
         limit: i64,
+        cursor: Option<&Cursor>,
     ) -> CustomResult<Vec<Self>, errors::DatabaseError>;
 }
 
@@ -29,10 +30,11 @@ impl RefundDbExt for Refund {
         merchant_id: &str,
         refund_list_details: &api_models::refunds::RefundListRequest,
         limit: i64,
+        cursor: Option<&Cursor>,
     ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
         let mut filter = <Self as HasTable>::table()
             .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
-            .order(dsl::modified_at.desc())
+            .order((dsl::created_at.desc(), dsl::id.desc()))
             .into_boxed();
 
         match &refund_list_details.payment_id {
@@ -59,6 +61,13 @@ impl RefundDbExt for Refund {
         if let Some(created_gte) = refund_list_details.created_gte {
             filter = filter.filter(dsl::created_at.gt(created_gte));
         }
+        if let Some(cursor) = cursor {
+            filter = filter.filter(
+                dsl::created_at.lt(cursor.created_at).or(dsl::created_at
+                    .eq(cursor.created_at)
+                    .and(dsl::id.lt(cursor.id))),
+            );
+        }
 
         logger::debug!(query = %diesel::debug_query::<diesel::pg::Pg, _>(&filter).to_string());
 
@@ -173,9 +173,9 @@ impl From<F<api_enums::IntentStatus>> for F<storage_enums::IntentStatus> {
 impl From<F<storage_enums::AttemptStatus>> for F<storage_enums::IntentStatus> {
     fn from(s: F<storage_enums::AttemptStatus>) -> Self {
         match s.0 {
-            storage_enums::AttemptStatus::Charged | storage_enums::AttemptStatus::AutoRefunded => {
-                storage_enums::IntentStatus::Succeeded
-            }
+            storage_enums::AttemptStatus::Charged
+            | storage_enums::AttemptStatus::Verified
+            | storage_enums::AttemptStatus::AutoRefunded => storage_enums::IntentStatus::Succeeded,
 
             storage_enums::AttemptStatus::ConfirmationAwaited => {
                 storage_enums::IntentStatus::RequiresConfirmation
@@ -184,7 +184,8 @@ impl From<F<storage_enums::AttemptStatus>> for F<storage_enums::IntentStatus> {
                 storage_enums::IntentStatus::RequiresPaymentMethod
             }
 
-            storage_enums::AttemptStatus::Authorized => {
+            storage_enums::AttemptStatus::Authorized
+            | storage_enums::AttemptStatus::PartiallyAuthorized => {
                 storage_enums::IntentStatus::RequiresCapture
             }
             storage_enums::AttemptStatus::AuthenticationPending => {
@@ -375,6 +376,7 @@ impl TryFrom<F<storage::MerchantConnectorAccount>>
             disabled: merchant_ca.disabled,
             metadata: merchant_ca.metadata,
             payment_methods_enabled,
+            webhook_secret: None,
         }
         .into())
     }
@@ -1,3 +1,4 @@
+pub mod apple_pay;
 pub mod custom_serde;
 pub mod db_utils;
 pub mod ext_traits;
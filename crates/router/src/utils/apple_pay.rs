@@ -0,0 +1,708 @@
+//! Decryption of Apple Pay payment tokens, as described in
+//! <https://developer.apple.com/library/archive/documentation/PassKit/Reference/PaymentTokenJSON/PaymentTokenJSON.html>
+
+use base64::Engine;
+use common_utils::errors::{CryptoError, CustomResult};
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
+use openssl::{
+    derive::Deriver,
+    pkcs7::{Pkcs7, Pkcs7Flags, Pkcs7Ref},
+    pkey::{PKey, PKeyRef, Private},
+    sha::sha256,
+    stack::Stack,
+    symm,
+    x509::{store::X509StoreBuilder, X509PurposeId, X509Ref, X509VerifyResult, X509},
+};
+use serde::Deserialize;
+use time::PrimitiveDateTime;
+
+use crate::{consts, pii};
+
+/// The only token version that is encrypted with elliptic curve keys.
+const EC_V1: &str = "EC_v1";
+
+/// Apple Pay encrypts the token with a zeroed 16 byte initialization vector.
+const INITIALIZATION_VECTOR: [u8; 16] = [0; 16];
+
+const TAG_LENGTH: usize = 16;
+
+/// Extension marking the leaf certificate Apple Pay signs tokens with (1.2.840.113635.100.6.29).
+const LEAF_CERTIFICATE_OID: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x63, 0x64, 0x06, 0x1d];
+
+/// Extension marking the Apple Pay intermediate certificate (1.2.840.113635.100.6.2.14).
+const INTERMEDIATE_CERTIFICATE_OID: [u8; 10] =
+    [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x63, 0x64, 0x06, 0x02, 0x0e];
+
+/// Signed attribute holding the time of the signature (1.2.840.113549.1.9.5).
+const SIGNING_TIME_OID: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x05];
+
+/// Tokens signed longer ago than this are rejected, so that a captured token cannot be replayed.
+const SIGNATURE_VALIDITY: time::Duration = time::Duration::minutes(5);
+
+const DER_OBJECT_IDENTIFIER: u8 = 0x06;
+const DER_UTC_TIME: u8 = 0x17;
+const DER_GENERALIZED_TIME: u8 = 0x18;
+const DER_SEQUENCE: u8 = 0x30;
+const DER_SET: u8 = 0x31;
+const DER_CONTEXT_SPECIFIC_0: u8 = 0xa0;
+const DER_CONTEXT_SPECIFIC_3: u8 = 0xa3;
+
+/// The `paymentData` of the token Apple Pay hands to the merchant.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayPaymentData {
+    pub version: String,
+    pub data: String,
+    pub signature: String,
+    pub header: ApplePayHeader,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayHeader {
+    pub ephemeral_public_key: String,
+    pub public_key_hash: String,
+    pub transaction_id: String,
+    pub application_data: Option<String>,
+}
+
+/// The payment processing key the merchant created for their Apple Pay merchant identifier.
+#[derive(Debug, Deserialize)]
+pub struct ApplePayDecryptionKeys {
+    pub merchant_identifier: String,
+    /// Private key of the payment processing certificate, in PEM format
+    pub payment_processing_private_key: Secret<String>,
+    /// Apple Root CA - G3 certificate in PEM format, which the signature of the token must chain
+    /// up to
+    pub apple_root_certificate: String,
+}
+
+/// The device specific card details recovered from the token.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayDecryptedData {
+    pub application_primary_account_number: Secret<String, pii::CardNumber>,
+    /// Expiry of the device account number, formatted as `YYMMDD`
+    pub application_expiration_date: String,
+    pub currency_code: String,
+    pub transaction_amount: i64,
+    pub device_manufacturer_identifier: String,
+    pub payment_data_type: String,
+    pub payment_data: ApplePayCryptogramData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayCryptogramData {
+    pub online_payment_cryptogram: Secret<String>,
+    pub eci_indicator: Option<String>,
+}
+
+impl ApplePayDecryptedData {
+    /// Two digit expiry month of the device account number.
+    pub fn get_expiry_month(&self) -> CustomResult<String, CryptoError> {
+        self.get_expiry_part(2..4)
+    }
+
+    /// Four digit expiry year of the device account number.
+    pub fn get_expiry_year(&self) -> CustomResult<String, CryptoError> {
+        self.get_expiry_part(0..2).map(|year| format!("20{year}"))
+    }
+
+    fn get_expiry_part(&self, range: std::ops::Range<usize>) -> CustomResult<String, CryptoError> {
+        self.application_expiration_date
+            .get(range)
+            .map(ToString::to_string)
+            .ok_or(CryptoError::DecodingFailed)
+            .into_report()
+            .attach_printable("Invalid application expiration date in Apple Pay token")
+    }
+}
+
+/// Verifies the signature of an `EC_v1` Apple Pay token and decrypts it with the merchant's
+/// payment processing key.
+pub fn decrypt_payment_data(
+    payment_data: &ApplePayPaymentData,
+    keys: &ApplePayDecryptionKeys,
+) -> CustomResult<ApplePayDecryptedData, CryptoError> {
+    if payment_data.version != EC_V1 {
+        return Err(CryptoError::DecodingFailed)
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!(
+                    "Unsupported Apple Pay token version {}",
+                    payment_data.version
+                )
+            });
+    }
+
+    let ephemeral_public_key = consts::BASE64_ENGINE
+        .decode(&payment_data.header.ephemeral_public_key)
+        .into_report()
+        .change_context(CryptoError::DecodingFailed)
+        .attach_printable("Apple Pay ephemeral public key is not valid base64")?;
+    let data = consts::BASE64_ENGINE
+        .decode(&payment_data.data)
+        .into_report()
+        .change_context(CryptoError::DecodingFailed)
+        .attach_printable("Apple Pay token data is not valid base64")?;
+
+    verify_signature(payment_data, &ephemeral_public_key, &data, keys)?;
+
+    let ephemeral_public_key = PKey::public_key_from_der(&ephemeral_public_key)
+        .into_report()
+        .change_context(CryptoError::DecodingFailed)
+        .attach_printable("Invalid ephemeral public key in Apple Pay token")?;
+    let private_key =
+        PKey::private_key_from_pem(keys.payment_processing_private_key.peek().as_bytes())
+            .into_report()
+            .change_context(CryptoError::DecodingFailed)
+            .attach_printable("Invalid Apple Pay payment processing private key")?;
+    verify_public_key_hash(payment_data, &private_key)?;
+
+    let shared_secret = Deriver::new(&private_key)
+        .and_then(|mut deriver| {
+            deriver.set_peer(&ephemeral_public_key)?;
+            deriver.derive_to_vec()
+        })
+        .into_report()
+        .change_context(CryptoError::DecodingFailed)
+        .attach_printable("Failed to derive the shared secret of the Apple Pay token")?;
+    let symmetric_key = derive_symmetric_key(&shared_secret, &keys.merchant_identifier);
+
+    let tag_start = data
+        .len()
+        .checked_sub(TAG_LENGTH)
+        .ok_or(CryptoError::DecodingFailed)
+        .into_report()
+        .attach_printable("Apple Pay token data is shorter than the authentication tag")?;
+    let (ciphertext, tag) = data.split_at(tag_start);
+
+    let decrypted_data = symm::decrypt_aead(
+        symm::Cipher::aes_256_gcm(),
+        &symmetric_key,
+        Some(&INITIALIZATION_VECTOR),
+        &[],
+        ciphertext,
+        tag,
+    )
+    .into_report()
+    .change_context(CryptoError::DecodingFailed)
+    .attach_printable("Failed to decrypt Apple Pay token data")?;
+
+    serde_json::from_slice(&decrypted_data)
+        .into_report()
+        .change_context(CryptoError::DecodingFailed)
+        .attach_printable("Failed to parse decrypted Apple Pay token data")
+}
+
+/// Checks that the token was encrypted for the merchant's payment processing key, by comparing the
+/// hash of its public key with the one the token carries.
+fn verify_public_key_hash(
+    payment_data: &ApplePayPaymentData,
+    private_key: &PKeyRef<Private>,
+) -> CustomResult<(), CryptoError> {
+    let public_key = private_key
+        .public_key_to_der()
+        .into_report()
+        .change_context(CryptoError::DecodingFailed)
+        .attach_printable("Failed to encode the Apple Pay payment processing public key")?;
+    if consts::BASE64_ENGINE.encode(sha256(&public_key)) != payment_data.header.public_key_hash {
+        return Err(CryptoError::DecodingFailed)
+            .into_report()
+            .attach_printable("Apple Pay token was encrypted for another payment processing key");
+    }
+    Ok(())
+}
+
+/// Checks that the detached PKCS #7 signature of the token was made by a certificate issued under
+/// the Apple root certificate, over the ephemeral public key, the encrypted data, the transaction
+/// id and the application data. The signature must have been made recently by the Apple Pay leaf
+/// certificate, itself issued by the Apple Pay intermediate certificate.
+fn verify_signature(
+    payment_data: &ApplePayPaymentData,
+    ephemeral_public_key: &[u8],
+    data: &[u8],
+    keys: &ApplePayDecryptionKeys,
+) -> CustomResult<(), CryptoError> {
+    let signature = consts::BASE64_ENGINE
+        .decode(&payment_data.signature)
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)
+        .and_then(|signature| {
+            Pkcs7::from_der(&signature)
+                .into_report()
+                .change_context(CryptoError::SignatureVerificationFailed)
+        })
+        .attach_printable("Invalid signature in Apple Pay token")?;
+
+    let mut signed_data = ephemeral_public_key.to_vec();
+    signed_data.extend_from_slice(data);
+    signed_data.extend(
+        hex::decode(&payment_data.header.transaction_id)
+            .into_report()
+            .change_context(CryptoError::SignatureVerificationFailed)
+            .attach_printable("Apple Pay transaction id is not valid hex")?,
+    );
+    if let Some(application_data) = &payment_data.header.application_data {
+        signed_data.extend(
+            hex::decode(application_data)
+                .into_report()
+                .change_context(CryptoError::SignatureVerificationFailed)
+                .attach_printable("Apple Pay application data is not valid hex")?,
+        );
+    }
+
+    let root_certificate = X509::from_pem(keys.apple_root_certificate.as_bytes())
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)
+        .attach_printable("Invalid Apple root certificate")?;
+    let store = X509StoreBuilder::new()
+        .and_then(|mut store| {
+            store.add_cert(root_certificate)?;
+            // The Apple Pay certificates are not issued for S/MIME, which PKCS #7 checks for by
+            // default
+            store.set_purpose(X509PurposeId::ANY)?;
+            Ok(store.build())
+        })
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)?;
+    let certificates = Stack::<X509>::new()
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)?;
+
+    signature
+        .verify(
+            &certificates,
+            &store,
+            Some(&signed_data),
+            None,
+            Pkcs7Flags::BINARY,
+        )
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)
+        .attach_printable("Apple Pay token signature could not be verified")?;
+
+    verify_signing_certificates(&signature)?;
+    verify_signing_time(&signature, common_utils::date_time::now())
+}
+
+/// Checks that the signer of the token is the Apple Pay leaf certificate, and that the certificate
+/// that issued it is the Apple Pay intermediate certificate.
+fn verify_signing_certificates(signature: &Pkcs7Ref) -> CustomResult<(), CryptoError> {
+    let der = signature
+        .to_der()
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)?;
+    let certificates = get_signed_data(&der)
+        .and_then(|signed_data| {
+            signed_data
+                .into_iter()
+                .find(|element| element.tag == DER_CONTEXT_SPECIFIC_0)
+        })
+        .and_then(|certificates| read_der_elements(certificates.contents))
+        .ok_or(CryptoError::SignatureVerificationFailed)
+        .into_report()
+        .attach_printable("Apple Pay token signature does not embed its certificates")?
+        .into_iter()
+        .map(|certificate| X509::from_der(certificate.encoded))
+        .collect::<Result<Vec<_>, _>>()
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)
+        .attach_printable("Invalid certificate in Apple Pay token signature")?;
+
+    let mut certificate_stack = Stack::<X509>::new()
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)?;
+    for certificate in &certificates {
+        certificate_stack
+            .push(certificate.clone())
+            .into_report()
+            .change_context(CryptoError::SignatureVerificationFailed)?;
+    }
+    let leaf_certificate = signature
+        .signers(&certificate_stack, Pkcs7Flags::empty())
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)?
+        .pop()
+        .ok_or(CryptoError::SignatureVerificationFailed)
+        .into_report()
+        .attach_printable("Apple Pay token signature has no signer")?;
+    if !has_extension(&leaf_certificate, &LEAF_CERTIFICATE_OID) {
+        return Err(CryptoError::SignatureVerificationFailed)
+            .into_report()
+            .attach_printable("Apple Pay token was not signed by the Apple Pay leaf certificate");
+    }
+
+    let is_issued_by_intermediate = certificates.iter().any(|certificate| {
+        certificate.issued(&leaf_certificate) == X509VerifyResult::OK
+            && has_extension(certificate, &INTERMEDIATE_CERTIFICATE_OID)
+    });
+    if !is_issued_by_intermediate {
+        return Err(CryptoError::SignatureVerificationFailed)
+            .into_report()
+            .attach_printable(
+            "Apple Pay leaf certificate was not issued by the Apple Pay intermediate certificate",
+        );
+    }
+    Ok(())
+}
+
+/// Checks that the token was signed within [`SIGNATURE_VALIDITY`] of `now`.
+fn verify_signing_time(
+    signature: &Pkcs7Ref,
+    now: PrimitiveDateTime,
+) -> CustomResult<(), CryptoError> {
+    let der = signature
+        .to_der()
+        .into_report()
+        .change_context(CryptoError::SignatureVerificationFailed)?;
+    let signing_time = get_signing_time(&der)
+        .ok_or(CryptoError::SignatureVerificationFailed)
+        .into_report()
+        .attach_printable("Apple Pay token signature has no signing time")?;
+    if (now - signing_time).abs() > SIGNATURE_VALIDITY {
+        return Err(CryptoError::SignatureVerificationFailed)
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!("Apple Pay token was signed at {signing_time}, which is not recent")
+            });
+    }
+    Ok(())
+}
+
+/// A DER encoded element: its tag, its contents and its whole encoding.
+struct DerElement<'a> {
+    tag: u8,
+    contents: &'a [u8],
+    encoded: &'a [u8],
+}
+
+/// Splits the first element off `input`. Only the definite lengths DER mandates are supported,
+/// which holds for the signatures re-encoded by OpenSSL.
+fn read_der_element(input: &[u8]) -> Option<(DerElement<'_>, &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&length, rest) = rest.split_first()?;
+    let (length, rest) = if length & 0x80 == 0 {
+        (usize::from(length), rest)
+    } else {
+        let length_size = usize::from(length & 0x7f);
+        if length_size == 0 || length_size > std::mem::size_of::<usize>() {
+            return None;
+        }
+        let length = rest
+            .get(..length_size)?
+            .iter()
+            .fold(0, |length, byte| (length << 8) | usize::from(*byte));
+        (length, rest.get(length_size..)?)
+    };
+    let contents = rest.get(..length)?;
+    let header_length = input.len().checked_sub(rest.len())?;
+    let encoded = input.get(..header_length.checked_add(length)?)?;
+    Some((
+        DerElement {
+            tag,
+            contents,
+            encoded,
+        },
+        rest.get(length..)?,
+    ))
+}
+
+/// The elements encoded one after the other in `input`.
+fn read_der_elements(mut input: &[u8]) -> Option<Vec<DerElement<'_>>> {
+    let mut elements = Vec::new();
+    while !input.is_empty() {
+        let (element, rest) = read_der_element(input)?;
+        elements.push(element);
+        input = rest;
+    }
+    Some(elements)
+}
+
+/// The elements of the `SignedData` of a DER encoded PKCS #7 signature.
+fn get_signed_data(der: &[u8]) -> Option<Vec<DerElement<'_>>> {
+    let (content_info, _) = read_der_element(der)?;
+    let content = read_der_elements(content_info.contents)?
+        .into_iter()
+        .find(|element| element.tag == DER_CONTEXT_SPECIFIC_0)?;
+    let (signed_data, _) = read_der_element(content.contents)?;
+    if signed_data.tag != DER_SEQUENCE {
+        return None;
+    }
+    read_der_elements(signed_data.contents)
+}
+
+/// Whether the certificate has the extension identified by the encoded object identifier `oid`.
+fn has_extension(certificate: &X509Ref, oid: &[u8]) -> bool {
+    let find_extension = || -> Option<bool> {
+        let der = certificate.to_der().ok()?;
+        let (certificate, _) = read_der_element(&der)?;
+        let (tbs_certificate, _) = read_der_element(certificate.contents)?;
+        let extensions = read_der_elements(tbs_certificate.contents)?
+            .into_iter()
+            .find(|element| element.tag == DER_CONTEXT_SPECIFIC_3)?;
+        let (extensions, _) = read_der_element(extensions.contents)?;
+        Some(
+            read_der_elements(extensions.contents)?
+                .iter()
+                .filter_map(|extension| read_der_element(extension.contents))
+                .any(|(extension_oid, _)| {
+                    extension_oid.tag == DER_OBJECT_IDENTIFIER && extension_oid.contents == oid
+                }),
+        )
+    };
+    find_extension().unwrap_or(false)
+}
+
+/// The signing time attribute of the first signer of a DER encoded PKCS #7 signature.
+fn get_signing_time(der: &[u8]) -> Option<PrimitiveDateTime> {
+    // The signer infos follow the digest algorithms, which are also a set
+    let signer_infos = get_signed_data(der)?
+        .into_iter()
+        .rev()
+        .find(|element| element.tag == DER_SET)?;
+    let (signer_info, _) = read_der_element(signer_infos.contents)?;
+    let signed_attributes = read_der_elements(signer_info.contents)?
+        .into_iter()
+        .find(|element| element.tag == DER_CONTEXT_SPECIFIC_0)?;
+    read_der_elements(signed_attributes.contents)?
+        .iter()
+        .find_map(|attribute| {
+            let (attribute_type, values) = read_der_element(attribute.contents)?;
+            (attribute_type.tag == DER_OBJECT_IDENTIFIER
+                && attribute_type.contents == SIGNING_TIME_OID)
+                .then_some(values)
+        })
+        .and_then(|values| read_der_element(values).map(|(values, _)| values))
+        .and_then(|values| read_der_element(values.contents).map(|(time, _)| time))
+        .and_then(|time| parse_der_time(&time))
+}
+
+/// Parses a UTC time (`YYMMDDHHMMSSZ`) or generalized time (`YYYYMMDDHHMMSSZ`).
+fn parse_der_time(element: &DerElement<'_>) -> Option<PrimitiveDateTime> {
+    let time = std::str::from_utf8(element.contents)
+        .ok()?
+        .strip_suffix('Z')?;
+    let time = match element.tag {
+        // Two digit years from 50 are in the twentieth century, as per RFC 5280
+        DER_UTC_TIME if time >= "50" => format!("19{time}"),
+        DER_UTC_TIME => format!("20{time}"),
+        DER_GENERALIZED_TIME => time.to_string(),
+        _ => return None,
+    };
+    let format =
+        time::format_description::parse("[year][month][day][hour][minute][second]").ok()?;
+    PrimitiveDateTime::parse(&time, &format).ok()
+}
+
+/// Single step key derivation from NIST SP 800-56A with SHA-256, where a single round yields the
+/// 256 bit key used by AES-GCM.
+fn derive_symmetric_key(shared_secret: &[u8], merchant_identifier: &str) -> [u8; 32] {
+    let mut kdf_input = vec![0, 0, 0, 1];
+    kdf_input.extend_from_slice(shared_secret);
+    kdf_input.push(0x0d);
+    kdf_input.extend_from_slice(b"id-aes256-GCM");
+    kdf_input.extend_from_slice(b"Apple");
+    kdf_input.extend_from_slice(&sha256(merchant_identifier.as_bytes()));
+    sha256(&kdf_input)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use openssl::{
+        asn1::Asn1Time,
+        bn::BigNum,
+        ec::{EcGroup, EcKey},
+        hash::MessageDigest,
+        nid::Nid,
+        x509::{X509Builder, X509Extension, X509NameBuilder},
+    };
+
+    use super::*;
+
+    const LEAF_CERTIFICATE_EXTENSION: &str = "1.2.840.113635.100.6.29";
+    const INTERMEDIATE_CERTIFICATE_EXTENSION: &str = "1.2.840.113635.100.6.2.14";
+
+    fn generate_key() -> PKey<Private> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap()
+    }
+
+    fn create_certificate(
+        common_name: &str,
+        key: &PKey<Private>,
+        issuer: Option<(&X509, &PKey<Private>)>,
+        extension: Option<&str>,
+    ) -> X509 {
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", common_name).unwrap();
+        let name = name.build();
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder
+            .set_serial_number(&BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap())
+            .unwrap();
+        builder.set_subject_name(&name).unwrap();
+        match issuer {
+            Some((issuer_certificate, _)) => {
+                builder.set_issuer_name(issuer_certificate.subject_name())
+            }
+            None => builder.set_issuer_name(&name),
+        }
+        .unwrap();
+        builder.set_pubkey(key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        if let Some(extension) = extension {
+            builder
+                .append_extension(X509Extension::new(None, None, extension, "ASN1:NULL").unwrap())
+                .unwrap();
+        }
+        builder
+            .sign(
+                issuer.map_or(key, |(_, issuer_key)| issuer_key),
+                MessageDigest::sha256(),
+            )
+            .unwrap();
+        builder.build()
+    }
+
+    /// Signature of a token by a leaf certificate issued by an intermediate certificate, each
+    /// carrying the given extension.
+    fn sign_token(leaf_extension: Option<&str>, intermediate_extension: Option<&str>) -> Pkcs7 {
+        let root_key = generate_key();
+        let root = create_certificate("Test Root CA", &root_key, None, None);
+        let intermediate_key = generate_key();
+        let intermediate = create_certificate(
+            "Test Intermediate CA",
+            &intermediate_key,
+            Some((&root, &root_key)),
+            intermediate_extension,
+        );
+        let leaf_key = generate_key();
+        let leaf = create_certificate(
+            "Test Leaf",
+            &leaf_key,
+            Some((&intermediate, &intermediate_key)),
+            leaf_extension,
+        );
+        let mut certificates = Stack::new().unwrap();
+        certificates.push(intermediate).unwrap();
+        Pkcs7::sign(
+            &leaf,
+            &leaf_key,
+            &certificates,
+            b"token",
+            Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_recent_signature_of_apple_pay_certificates_is_accepted() {
+        let signature = sign_token(
+            Some(LEAF_CERTIFICATE_EXTENSION),
+            Some(INTERMEDIATE_CERTIFICATE_EXTENSION),
+        );
+        assert!(verify_signing_certificates(&signature).is_ok());
+        assert!(verify_signing_time(&signature, common_utils::date_time::now()).is_ok());
+    }
+
+    #[test]
+    fn test_signature_without_leaf_certificate_extension_is_rejected() {
+        let signature = sign_token(None, Some(INTERMEDIATE_CERTIFICATE_EXTENSION));
+        let error = verify_signing_certificates(&signature).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            CryptoError::SignatureVerificationFailed
+        ));
+    }
+
+    #[test]
+    fn test_signature_without_intermediate_certificate_extension_is_rejected() {
+        let signature = sign_token(Some(LEAF_CERTIFICATE_EXTENSION), None);
+        let error = verify_signing_certificates(&signature).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            CryptoError::SignatureVerificationFailed
+        ));
+    }
+
+    #[test]
+    fn test_replayed_signature_is_rejected() {
+        let signature = sign_token(
+            Some(LEAF_CERTIFICATE_EXTENSION),
+            Some(INTERMEDIATE_CERTIFICATE_EXTENSION),
+        );
+        let replayed_at = common_utils::date_time::now().saturating_add(time::Duration::hours(1));
+        let error = verify_signing_time(&signature, replayed_at).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            CryptoError::SignatureVerificationFailed
+        ));
+    }
+
+    #[test]
+    fn test_token_encrypted_for_another_key_is_rejected() {
+        let private_key = generate_key();
+        let mut payment_data = get_payment_data(EC_V1);
+        payment_data.header.public_key_hash =
+            consts::BASE64_ENGINE.encode(sha256(&generate_key().public_key_to_der().unwrap()));
+        let error = verify_public_key_hash(&payment_data, &private_key).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            CryptoError::DecodingFailed
+        ));
+
+        payment_data.header.public_key_hash =
+            consts::BASE64_ENGINE.encode(sha256(&private_key.public_key_to_der().unwrap()));
+        assert!(verify_public_key_hash(&payment_data, &private_key).is_ok());
+    }
+
+    fn get_payment_data(version: &str) -> ApplePayPaymentData {
+        ApplePayPaymentData {
+            version: version.to_string(),
+            data: "ZGF0YQ==".to_string(),
+            signature: "c2lnbmF0dXJl".to_string(),
+            header: ApplePayHeader {
+                ephemeral_public_key: "a2V5".to_string(),
+                public_key_hash: "aGFzaA==".to_string(),
+                transaction_id: "1234".to_string(),
+                application_data: None,
+            },
+        }
+    }
+
+    fn get_keys() -> ApplePayDecryptionKeys {
+        ApplePayDecryptionKeys {
+            merchant_identifier: "merchant.com.example".to_string(),
+            payment_processing_private_key: Secret::new("not a key".to_string()),
+            apple_root_certificate: "not a certificate".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rsa_tokens_are_rejected() {
+        let error = decrypt_payment_data(&get_payment_data("RSA_v1"), &get_keys()).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            CryptoError::DecodingFailed
+        ));
+    }
+
+    #[test]
+    fn test_unsigned_token_is_rejected() {
+        let error = decrypt_payment_data(&get_payment_data(EC_V1), &get_keys()).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            CryptoError::SignatureVerificationFailed
+        ));
+    }
+}
@@ -42,6 +42,7 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             }),
             confirm: true,
             statement_descriptor_suffix: None,
+            statement_descriptor_prefix: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -49,13 +50,20 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             capture_method: None,
             browser_info: None,
             order_details: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
             email: None,
+            connector_meta: None,
+            network_token: None,
         },
         response: Err(types::ErrorResponse::default()),
         payment_method_id: None,
+        connector_response_body: None,
         address: PaymentAddress::default(),
         connector_meta_data: None,
         amount_captured: None,
+        authorized_amount: None,
         access_token: None,
     }
 }
@@ -90,10 +98,12 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
             connector_refund_id: None,
         },
         payment_method_id: None,
+        connector_response_body: None,
         response: Err(types::ErrorResponse::default()),
         address: PaymentAddress::default(),
         connector_meta_data: None,
         amount_captured: None,
+        authorized_amount: None,
         access_token: None,
     }
 }
@@ -135,9 +145,8 @@ async fn payments_create_success() {
 #[ignore]
 async fn payments_create_failure() {
     {
-        let conf = Settings::new().unwrap();
         static CV: aci::Aci = aci::Aci;
-        let state = routes::AppState::with_storage(conf, StorageImpl::PostgresqlTest).await;
+        let state = routes::AppState::test_builder().build().await.unwrap();
         let connector = types::api::ConnectorData {
             connector: Box::new(&CV),
             connector_name: types::Connector::Aci,
@@ -70,6 +70,7 @@ impl AdyenTest {
             }),
             confirm: true,
             statement_descriptor_suffix: None,
+            statement_descriptor_prefix: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -77,7 +78,12 @@ impl AdyenTest {
             capture_method: Some(capture_method),
             browser_info: None,
             order_details: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
             email: None,
+            connector_meta: None,
+            network_token: None,
         })
     }
 }
@@ -10,7 +10,57 @@ use router::{
     types::{self, storage::enums, PaymentAddress},
 };
 
-use crate::connector_auth::ConnectorAuthentication;
+use crate::{
+    connector_auth::ConnectorAuthentication,
+    smoke::SmokeTest,
+    utils::{self, ConnectorActions},
+};
+
+#[derive(Clone, Copy)]
+pub(crate) struct AuthorizedotnetTest;
+impl ConnectorActions for AuthorizedotnetTest {}
+impl utils::Connector for AuthorizedotnetTest {
+    fn get_data(&self) -> types::api::ConnectorData {
+        types::api::ConnectorData {
+            connector: Box::new(&Authorizedotnet),
+            connector_name: types::Connector::Authorizedotnet,
+            get_token: types::api::GetToken::Connector,
+        }
+    }
+
+    fn get_auth_token(&self) -> types::ConnectorAuthType {
+        types::ConnectorAuthType::from(
+            ConnectorAuthentication::new()
+                .authorizedotnet
+                .expect("Missing Authorize.net connector authentication configuration"),
+        )
+    }
+
+    fn get_name(&self) -> String {
+        "authorizedotnet".to_string()
+    }
+}
+
+#[async_trait::async_trait]
+impl SmokeTest for AuthorizedotnetTest {
+    fn has_credentials(&self, auth: &ConnectorAuthentication) -> bool {
+        auth.authorizedotnet.is_some()
+    }
+
+    async fn prepare_payment(
+        &self,
+    ) -> Result<(types::PaymentsAuthorizeData, utils::PaymentInfo), String> {
+        let authorize_data = types::PaymentsAuthorizeData {
+            payment_method_data: types::api::PaymentMethod::Card(types::api::Card {
+                card_number: Secret::new("5424000000000015".to_string()),
+                card_exp_year: Secret::new("2030".to_string()),
+                ..utils::CCardType::default().0
+            }),
+            ..utils::PaymentAuthorizeType::default().0
+        };
+        Ok((authorize_data, utils::PaymentInfo::default()))
+    }
+}
 
 fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
     let auth = ConnectorAuthentication::new()
@@ -42,6 +92,7 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             }),
             confirm: true,
             statement_descriptor_suffix: None,
+            statement_descriptor_prefix: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -49,13 +100,20 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             capture_method: None,
             browser_info: None,
             order_details: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
             email: None,
+            connector_meta: None,
+            network_token: None,
         },
         payment_method_id: None,
+        connector_response_body: None,
         response: Err(types::ErrorResponse::default()),
         address: PaymentAddress::default(),
         connector_meta_data: None,
         amount_captured: None,
+        authorized_amount: None,
         access_token: None,
     }
 }
@@ -91,8 +149,10 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
         },
         response: Err(types::ErrorResponse::default()),
         payment_method_id: None,
+        connector_response_body: None,
         address: PaymentAddress::default(),
         amount_captured: None,
+        authorized_amount: None,
         access_token: None,
     }
 }
@@ -0,0 +1,221 @@
+//! Recording of connector interactions to cassette files, replayed by tests so that they run
+//! without calling the connector's sandbox.
+//!
+//! Tests replay the interactions of their cassette from `tests/connectors/cassettes` in order,
+//! failing if a request differs in method, URL or body from the recorded one. To record a
+//! cassette again against the sandbox, with the credentials configured in
+//! `tests/connectors/auth.toml`:
+//!
+//! ```text
+//! CONNECTOR_CASSETTE_MODE=record \
+//!     cargo test --package router --test connectors -- opayo::should_replay --nocapture
+//! ```
+//!
+//! Request and response bodies are recorded with the values of sensitive keys masked like in the
+//! connector logs, and requests are compared on their masked body. The headers, which carry the
+//! credentials, are not recorded.
+
+use std::{fmt::Debug, fs, path::PathBuf};
+
+use error_stack::{report, Report, ResultExt};
+use masking::PeekInterface;
+use router::{
+    configs::settings::Settings,
+    core::errors::ConnectorError,
+    routes,
+    services::{self, ConnectorIntegration},
+    types,
+};
+use serde::{Deserialize, Serialize};
+
+pub const CASSETTE_MODE_ENV: &str = "CONNECTOR_CASSETTE_MODE";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CassetteMode {
+    Replay,
+    Record,
+}
+
+impl CassetteMode {
+    fn from_env() -> Self {
+        match std::env::var(CASSETTE_MODE_ENV).as_deref() {
+            Ok("record") => Self::Record,
+            _ => Self::Replay,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: services::Method,
+    pub url: String,
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status_code: u16,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+/// Sends the connector requests of a test, or replays the responses recorded for them.
+pub struct CassettePlayer {
+    path: PathBuf,
+    mode: CassetteMode,
+    cassette: Cassette,
+    position: usize,
+}
+
+impl CassettePlayer {
+    /// Loads the cassette `name` to replay, or starts recording it if `CONNECTOR_CASSETTE_MODE`
+    /// is `record`.
+    pub fn new(name: &str) -> Self {
+        Self::with_mode(name, CassetteMode::from_env())
+    }
+
+    pub fn with_mode(name: &str, mode: CassetteMode) -> Self {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/connectors/cassettes")
+            .join(format!("{name}.json"));
+        let cassette = match mode {
+            CassetteMode::Record => Cassette::default(),
+            CassetteMode::Replay => {
+                let cassette = fs::read_to_string(&path)
+                    .unwrap_or_else(|error| panic!("Failed to read cassette {path:?}: {error}"));
+                serde_json::from_str(&cassette)
+                    .unwrap_or_else(|error| panic!("Invalid cassette {path:?}: {error}"))
+            }
+        };
+        Self {
+            path,
+            mode,
+            cassette,
+            position: 0,
+        }
+    }
+
+    /// Runs the connector flow of `router_data` like
+    /// [`services::api::execute_connector_processing_step`], with the response of the connector
+    /// taken from the cassette when replaying.
+    pub async fn execute<T, Req, Resp>(
+        &mut self,
+        integration: services::BoxedConnectorIntegration<'_, T, Req, Resp>,
+        router_data: &types::RouterData<T, Req, Resp>,
+    ) -> Result<types::RouterData<T, Req, Resp>, Report<ConnectorError>>
+    where
+        T: Debug + Clone + 'static,
+        Req: Debug + Clone + 'static,
+        Resp: Debug + Clone + 'static,
+    {
+        let conf = Settings::new().map_err(|error| {
+            report!(ConnectorError::ProcessingStepFailed(None)).attach_printable(error.to_string())
+        })?;
+        let request = match integration.build_request(router_data, &conf.connectors)? {
+            Some(request) => request,
+            None => return Ok(router_data.clone()),
+        };
+        let recorded_request = RecordedRequest {
+            method: request.method,
+            url: request.url.clone(),
+            body: request
+                .payload
+                .as_ref()
+                .map(|payload| services::api::mask_connector_payload(payload.peek())),
+        };
+
+        let response = match self.mode {
+            CassetteMode::Record => {
+                let state = routes::AppState::test_builder()
+                    .settings(conf)
+                    .build()
+                    .await
+                    .map_err(|error| {
+                        report!(ConnectorError::ProcessingStepFailed(None))
+                            .attach_printable(error.to_string())
+                    })?;
+                let response = services::api::call_connector_api(&state, request)
+                    .await
+                    .change_context(ConnectorError::ProcessingStepFailed(None))?;
+                let (Ok(recorded_response) | Err(recorded_response)) = response.as_ref();
+                self.cassette.interactions.push(Interaction {
+                    request: recorded_request,
+                    response: RecordedResponse {
+                        status_code: recorded_response.status_code,
+                        body: services::api::mask_connector_payload(&String::from_utf8_lossy(
+                            &recorded_response.response,
+                        )),
+                    },
+                });
+                response
+            }
+            CassetteMode::Replay => self.replay(&recorded_request)?,
+        };
+
+        match response {
+            Ok(response) => integration.handle_response(router_data, response),
+            Err(response) => {
+                let mut router_data = router_data.clone();
+                router_data.response = Err(integration.get_error_response(response)?);
+                Ok(router_data)
+            }
+        }
+    }
+
+    fn replay(
+        &mut self,
+        request: &RecordedRequest,
+    ) -> Result<Result<types::Response, types::Response>, Report<ConnectorError>> {
+        let interaction = self
+            .cassette
+            .interactions
+            .get(self.position)
+            .ok_or_else(|| {
+                report!(ConnectorError::ProcessingStepFailed(None)).attach_printable(format!(
+                    "No recorded interaction left in {:?} for {request:?}",
+                    self.path
+                ))
+            })?;
+        if interaction.request != *request {
+            return Err(
+                report!(ConnectorError::ProcessingStepFailed(None)).attach_printable(format!(
+                    "Expected {:?} in {:?}, got {request:?}",
+                    interaction.request, self.path
+                )),
+            );
+        }
+        self.position += 1;
+
+        let response = types::Response {
+            response: interaction.response.body.clone().into(),
+            status_code: interaction.response.status_code,
+        };
+        // Same split as the connector client, which hands the other status codes to the
+        // connector's error response handling
+        Ok(match interaction.response.status_code {
+            200..=202 | 302 => Ok(response),
+            _ => Err(response),
+        })
+    }
+
+    /// Writes the cassette when recording.
+    pub fn finish(self) {
+        if self.mode == CassetteMode::Record {
+            let cassette = serde_json::to_string_pretty(&self.cassette)
+                .unwrap_or_else(|error| panic!("Failed to serialize cassette: {error}"));
+            fs::write(&self.path, cassette + "\n").unwrap_or_else(|error| {
+                panic!("Failed to write cassette {:?}: {error}", self.path)
+            });
+        }
+    }
+}
@@ -1,7 +1,12 @@
 use std::marker::PhantomData;
 
+use masking::{PeekInterface, Secret};
 use router::{
-    core::payments,
+    core::{
+        errors,
+        payment_methods::network_tokenization::{self, NetworkTokenProvider},
+        payments,
+    },
     db::StorageImpl,
     routes,
     types::{self, api, storage::enums, PaymentAddress},
@@ -14,6 +19,13 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
         .checkout
         .expect("Missing Checkout connector authentication configuration");
 
+    types::RouterData {
+        connector_auth_type: auth.into(),
+        ..construct_payment_router_data_without_auth()
+    }
+}
+
+fn construct_payment_router_data_without_auth() -> types::PaymentsAuthorizeRouterData {
     types::RouterData {
         flow: PhantomData,
         merchant_id: "checkout".to_string(),
@@ -24,7 +36,7 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
         router_return_url: None,
         auth_type: enums::AuthenticationType::NoThreeDs,
         payment_method: enums::PaymentMethodType::Card,
-        connector_auth_type: auth.into(),
+        connector_auth_type: types::ConnectorAuthType::default(),
         description: Some("This is a test".to_string()),
         return_url: None,
         request: types::PaymentsAuthorizeData {
@@ -39,6 +51,7 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             }),
             confirm: true,
             statement_descriptor_suffix: None,
+            statement_descriptor_prefix: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -46,13 +59,20 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             capture_method: None,
             browser_info: None,
             order_details: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
             email: None,
+            connector_meta: None,
+            network_token: None,
         },
         response: Err(types::ErrorResponse::default()),
         payment_method_id: None,
+        connector_response_body: None,
         address: PaymentAddress::default(),
         connector_meta_data: None,
         amount_captured: None,
+        authorized_amount: None,
         access_token: None,
     }
 }
@@ -88,8 +108,10 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
         },
         response: Err(types::ErrorResponse::default()),
         payment_method_id: None,
+        connector_response_body: None,
         address: PaymentAddress::default(),
         amount_captured: None,
+        authorized_amount: None,
         access_token: None,
     }
 }
@@ -300,3 +322,354 @@ async fn test_checkout_refund_failure() {
     let code = response.response.unwrap_err().code;
     assert_eq!(code, "refund_amount_exceeds_balance");
 }
+
+/// Creates a certificate for `key`, signed by `issuer` or self signed as a root certificate.
+fn get_certificate(
+    common_name: &str,
+    key: &openssl::pkey::PKey<openssl::pkey::Private>,
+    issuer: Option<(
+        &openssl::x509::X509,
+        &openssl::pkey::PKey<openssl::pkey::Private>,
+    )>,
+) -> openssl::x509::X509 {
+    use openssl::{asn1::Asn1Time, bn::BigNum, hash::MessageDigest, x509};
+
+    let mut name = x509::X509NameBuilder::new().unwrap();
+    name.append_entry_by_text("CN", common_name).unwrap();
+    let name = name.build();
+
+    let mut builder = x509::X509Builder::new().unwrap();
+    builder.set_version(2).unwrap();
+    builder
+        .set_serial_number(&BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap())
+        .unwrap();
+    builder.set_subject_name(&name).unwrap();
+    builder
+        .set_issuer_name(issuer.map_or(&*name, |(certificate, _)| certificate.subject_name()))
+        .unwrap();
+    builder.set_pubkey(key).unwrap();
+    builder
+        .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+        .unwrap();
+    builder
+        .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+        .unwrap();
+    if issuer.is_none() {
+        builder
+            .append_extension(
+                x509::extension::BasicConstraints::new()
+                    .critical()
+                    .ca()
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+    }
+    builder
+        .sign(issuer.map_or(key, |(_, key)| key), MessageDigest::sha256())
+        .unwrap();
+    builder.build()
+}
+
+fn generate_ec_key() -> openssl::pkey::PKey<openssl::pkey::Private> {
+    let group = openssl::ec::EcGroup::from_curve_name(openssl::nid::Nid::X9_62_PRIME256V1).unwrap();
+    openssl::pkey::PKey::from_ec_key(openssl::ec::EcKey::generate(&group).unwrap()).unwrap()
+}
+
+/// Encrypts `payment_data` the way Apple Pay does for the merchant holding `merchant_key`, and
+/// signs the token with the certificate and key of `signer`.
+fn encrypt_apple_pay_token(
+    merchant_key: &openssl::pkey::PKey<openssl::pkey::Private>,
+    merchant_identifier: &str,
+    signer: (
+        &openssl::x509::X509,
+        &openssl::pkey::PKey<openssl::pkey::Private>,
+    ),
+    payment_data: &serde_json::Value,
+) -> String {
+    use base64::Engine;
+    use openssl::{derive::Deriver, pkcs7, sha::sha256, stack::Stack, symm};
+
+    let ephemeral_key = generate_ec_key();
+    let mut deriver = Deriver::new(&ephemeral_key).unwrap();
+    deriver.set_peer(merchant_key).unwrap();
+    let shared_secret = deriver.derive_to_vec().unwrap();
+
+    let mut kdf_input = vec![0, 0, 0, 1];
+    kdf_input.extend_from_slice(&shared_secret);
+    kdf_input.push(0x0d);
+    kdf_input.extend_from_slice(b"id-aes256-GCM");
+    kdf_input.extend_from_slice(b"Apple");
+    kdf_input.extend_from_slice(&sha256(merchant_identifier.as_bytes()));
+    let symmetric_key = sha256(&kdf_input);
+
+    let mut tag = [0; 16];
+    let mut data = symm::encrypt_aead(
+        symm::Cipher::aes_256_gcm(),
+        &symmetric_key,
+        Some(&[0; 16]),
+        &[],
+        payment_data.to_string().as_bytes(),
+        &mut tag,
+    )
+    .unwrap();
+    data.extend_from_slice(&tag);
+
+    let ephemeral_public_key = ephemeral_key.public_key_to_der().unwrap();
+    let transaction_id = "c1caf5ae72f0039a82bad92b828363734f85bf2f9cadf193d1bad9ddcb60a795";
+    let mut signed_data = ephemeral_public_key.clone();
+    signed_data.extend_from_slice(&data);
+    signed_data.extend(hex::decode(transaction_id).unwrap());
+    let (signer_certificate, signer_key) = signer;
+    let signature = pkcs7::Pkcs7::sign(
+        signer_certificate,
+        signer_key,
+        &Stack::new().unwrap(),
+        &signed_data,
+        pkcs7::Pkcs7Flags::DETACHED | pkcs7::Pkcs7Flags::BINARY,
+    )
+    .unwrap();
+
+    let base64 = base64::engine::general_purpose::STANDARD;
+    serde_json::json!({
+        "version": "EC_v1",
+        "data": base64.encode(data),
+        "signature": base64.encode(signature.to_der().unwrap()),
+        "header": {
+            "ephemeralPublicKey": base64.encode(ephemeral_public_key),
+            "publicKeyHash": "",
+            "transactionId": transaction_id,
+        },
+    })
+    .to_string()
+}
+
+/// Builds an Apple Pay authorize request for `amount` whose token is signed under `signing_root`,
+/// while the merchant trusts `trusted_root`.
+fn construct_apple_pay_router_data(
+    amount: i64,
+    signing_root: (
+        &openssl::x509::X509,
+        &openssl::pkey::PKey<openssl::pkey::Private>,
+    ),
+    trusted_root: &openssl::x509::X509,
+) -> types::PaymentsAuthorizeRouterData {
+    let merchant_identifier = "merchant.com.example";
+    let merchant_key = generate_ec_key();
+    let signer_key = generate_ec_key();
+    let signer_certificate = get_certificate("Apple Pay signer", &signer_key, Some(signing_root));
+    let token = encrypt_apple_pay_token(
+        &merchant_key,
+        merchant_identifier,
+        (&signer_certificate, &signer_key),
+        &serde_json::json!({
+            "applicationPrimaryAccountNumber": "4242424242424242",
+            "applicationExpirationDate": "351031",
+            "currencyCode": "840",
+            "transactionAmount": amount,
+            "deviceManufacturerIdentifier": "040010030273",
+            "paymentDataType": "3DSecure",
+            "paymentData": {
+                "onlinePaymentCryptogram": "AgAAAAAABk4DWZ4C28yUQAAAAAA=",
+                "eciIndicator": "7",
+            },
+        }),
+    );
+
+    types::RouterData {
+        connector_auth_type: types::ConnectorAuthType::BodyKey {
+            api_key: "api_key".to_string(),
+            key1: "processing_channel_id".to_string(),
+        },
+        payment_method: enums::PaymentMethodType::Wallet,
+        connector_meta_data: Some(serde_json::json!({
+            "apple_pay": {
+                "merchant_identifier": merchant_identifier,
+                "payment_processing_private_key":
+                    String::from_utf8(merchant_key.private_key_to_pem_pkcs8().unwrap()).unwrap(),
+                "apple_root_certificate":
+                    String::from_utf8(trusted_root.to_pem().unwrap()).unwrap(),
+            },
+        })),
+        request: types::PaymentsAuthorizeData {
+            payment_method_data: types::api::PaymentMethod::Wallet(api::WalletData {
+                issuer_name: api_models::enums::WalletIssuer::ApplePay,
+                token: Some(token),
+            }),
+            ..construct_payment_router_data_without_auth().request
+        },
+        ..construct_payment_router_data_without_auth()
+    }
+}
+
+fn get_apple_pay_request_body(
+    request: &types::PaymentsAuthorizeRouterData,
+) -> Result<Option<String>, error_stack::Report<errors::ConnectorError>> {
+    use router::{connector::Checkout, services};
+
+    static CV: Checkout = Checkout;
+    let connector = types::api::ConnectorData {
+        connector: Box::new(&CV),
+        connector_name: types::Connector::Checkout,
+        get_token: types::api::GetToken::Connector,
+    };
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        types::api::Authorize,
+        types::PaymentsAuthorizeData,
+        types::PaymentsResponseData,
+    > = connector.connector.get_connector_integration();
+    connector_integration.get_request_body(request)
+}
+
+#[test]
+fn test_checkout_apple_pay_request() {
+    let root_key = generate_ec_key();
+    let root_certificate = get_certificate("Apple Root CA - G3", &root_key, None);
+    let request =
+        construct_apple_pay_router_data(100, (&root_certificate, &root_key), &root_certificate);
+
+    let request_body = get_apple_pay_request_body(&request).unwrap().unwrap();
+    let request_body: serde_json::Value = serde_json::from_str(&request_body).unwrap();
+
+    assert_eq!(
+        request_body["source"],
+        serde_json::json!({
+            "type": "network_token",
+            "token": "4242424242424242",
+            "expiry_month": "10",
+            "expiry_year": "2035",
+            "token_type": "applepay",
+            "cryptogram": "AgAAAAAABk4DWZ4C28yUQAAAAAA=",
+            "eci": "7",
+        })
+    );
+}
+
+#[test]
+fn test_checkout_apple_pay_token_signed_by_untrusted_root_is_rejected() {
+    let root_key = generate_ec_key();
+    let root_certificate = get_certificate("Apple Root CA - G3", &root_key, None);
+    let other_root_key = generate_ec_key();
+    let other_root_certificate = get_certificate("Apple Root CA - G3", &other_root_key, None);
+    let request = construct_apple_pay_router_data(
+        100,
+        (&other_root_certificate, &other_root_key),
+        &root_certificate,
+    );
+
+    assert!(get_apple_pay_request_body(&request).is_err());
+}
+
+#[test]
+fn test_checkout_apple_pay_token_for_another_amount_is_rejected() {
+    let root_key = generate_ec_key();
+    let root_certificate = get_certificate("Apple Root CA - G3", &root_key, None);
+    let request =
+        construct_apple_pay_router_data(1, (&root_certificate, &root_key), &root_certificate);
+
+    let error = get_apple_pay_request_body(&request).unwrap_err();
+    assert!(matches!(
+        error.current_context(),
+        errors::ConnectorError::RequestEncodingFailedWithReason(_)
+    ));
+}
+
+/// Token service issuing a fixed Visa network token for any card.
+struct FakeNetworkTokenProvider;
+
+#[async_trait::async_trait]
+impl network_tokenization::NetworkTokenProvider for FakeNetworkTokenProvider {
+    async fn tokenize_card(
+        &self,
+        card: &api::CardDetail,
+    ) -> errors::CustomResult<network_tokenization::NetworkToken, errors::VaultError> {
+        assert_eq!(card.card_number.peek(), "4242424242424242");
+        Ok(network_tokenization::NetworkToken {
+            token_number: "4895370012003478".to_string().into(),
+            token_exp_month: "12".to_string().into(),
+            token_exp_year: "2030".to_string().into(),
+            token_reference: "ntr_123".to_string(),
+        })
+    }
+
+    async fn get_cryptogram(
+        &self,
+        token_reference: &str,
+    ) -> errors::CustomResult<types::NetworkTokenData, common_utils::errors::CryptoError> {
+        assert_eq!(token_reference, "ntr_123");
+        Ok(types::NetworkTokenData {
+            cryptogram: "AgAAAAAAAIR8CQrXcIhbQAAAAAA=".to_string().into(),
+            eci: Some("05".to_string()),
+        })
+    }
+}
+
+#[actix_web::test]
+async fn test_checkout_network_token_request() {
+    use router::{connector::Checkout, services};
+
+    let provider = FakeNetworkTokenProvider;
+    let card = api::CardDetail {
+        card_number: "4242424242424242".to_string().into(),
+        card_exp_month: "10".to_string().into(),
+        card_exp_year: "2035".to_string().into(),
+        card_holder_name: Some("John Doe".to_string().into()),
+    };
+    let network_token = provider.tokenize_card(&card).await.unwrap();
+    let stored_card = network_token.to_card_detail(&card);
+    let network_token_data = provider
+        .get_cryptogram(&network_token.token_reference)
+        .await
+        .unwrap();
+
+    let request = types::RouterData {
+        connector_auth_type: types::ConnectorAuthType::BodyKey {
+            api_key: "api_key".to_string(),
+            key1: "processing_channel_id".to_string(),
+        },
+        request: types::PaymentsAuthorizeData {
+            payment_method_data: types::api::PaymentMethod::Card(api::Card {
+                card_number: stored_card.card_number,
+                card_exp_month: stored_card.card_exp_month,
+                card_exp_year: stored_card.card_exp_year,
+                card_holder_name: stored_card.card_holder_name.unwrap_or_default(),
+                card_cvc: Secret::new(String::new()),
+            }),
+            network_token: Some(network_token_data),
+            ..construct_payment_router_data_without_auth().request
+        },
+        ..construct_payment_router_data_without_auth()
+    };
+
+    static CV: Checkout = Checkout;
+    let connector = types::api::ConnectorData {
+        connector: Box::new(&CV),
+        connector_name: types::Connector::Checkout,
+        get_token: types::api::GetToken::Connector,
+    };
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        types::api::Authorize,
+        types::PaymentsAuthorizeData,
+        types::PaymentsResponseData,
+    > = connector.connector.get_connector_integration();
+    let request_body = connector_integration
+        .get_request_body(&request)
+        .unwrap()
+        .unwrap();
+    let request_body: serde_json::Value = serde_json::from_str(&request_body).unwrap();
+
+    assert_eq!(
+        request_body["source"],
+        serde_json::json!({
+            "type": "network_token",
+            "token": "4895370012003478",
+            "expiry_month": "12",
+            "expiry_year": "2030",
+            "token_type": "vts",
+            "cryptogram": "AgAAAAAAAIR8CQrXcIhbQAAAAAA=",
+            "eci": "05",
+        })
+    );
+}
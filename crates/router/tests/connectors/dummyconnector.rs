@@ -0,0 +1,386 @@
+use std::time::Duration;
+
+use router::{
+    configs::settings::Settings,
+    core::{
+        errors::{ApiClientError, ConnectorError},
+        payments,
+    },
+    db::StorageImpl,
+    routes::AppState,
+    services,
+    types::{
+        self,
+        storage::{self, enums},
+    },
+};
+use serde_json::json;
+use serial_test::serial;
+use wiremock::{
+    matchers::{body_partial_json, header, method, path},
+    Mock, ResponseTemplate,
+};
+
+use crate::utils::{self, Connector as _, ConnectorActions, LocalMock, MockConfig};
+
+struct DummyConnector;
+
+impl LocalMock for DummyConnector {}
+impl ConnectorActions for DummyConnector {}
+impl utils::Connector for DummyConnector {
+    fn get_data(&self) -> types::api::ConnectorData {
+        use router::connector::DummyConnector;
+        types::api::ConnectorData {
+            connector: Box::new(&DummyConnector),
+            connector_name: types::Connector::Dummy,
+            get_token: types::api::GetToken::Connector,
+        }
+    }
+
+    fn get_auth_token(&self) -> types::ConnectorAuthType {
+        types::ConnectorAuthType::HeaderKey {
+            api_key: "dummy_api_key".to_string(),
+        }
+    }
+
+    fn get_name(&self) -> String {
+        "dummy".to_string()
+    }
+}
+
+/// Scripts the outcome of a payment request sent to `/payments`.
+fn mock_payment(status: &str) -> Mock {
+    Mock::given(method("POST"))
+        .and(path("/payments"))
+        .and(header("Authorization", "Bearer dummy_api_key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payment_response(status)))
+}
+
+fn payment_response(status: &str) -> serde_json::Value {
+    json!({
+        "id": "pay_123456",
+        "status": status,
+        "redirect_url": null
+    })
+}
+
+fn mock_capture() -> Mock {
+    Mock::given(method("POST"))
+        .and(path("/payments/pay_123456/capture"))
+        .and(body_partial_json(json!({ "amount": 100 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payment_response("succeeded")))
+}
+
+fn mock_refund() -> Mock {
+    Mock::given(method("POST"))
+        .and(path("/payments/pay_123456/refund"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "ref_654321",
+            "status": "succeeded"
+        })))
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_authorize_card_payment() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![mock_payment("authorized")],
+        })
+        .await;
+    let response = connector.authorize_payment(None, None).await.unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Authorized);
+    assert_eq!(
+        utils::get_connector_transaction_id(response.response),
+        Some("pay_123456".to_string())
+    );
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_make_payment() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![mock_payment("succeeded")],
+        })
+        .await;
+    let response = connector.make_payment(None, None).await.unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_capture_authorized_payment() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![mock_payment("authorized"), mock_capture()],
+        })
+        .await;
+    let response = connector
+        .authorize_and_capture_payment(None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_refund_captured_payment() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![mock_payment("authorized"), mock_capture(), mock_refund()],
+        })
+        .await;
+    let response = connector
+        .capture_payment_and_refund(None, None, None, None)
+        .await
+        .unwrap();
+    let refund = response.response.unwrap();
+    assert_eq!(refund.connector_refund_id, "ref_654321");
+    assert_eq!(refund.refund_status, enums::RefundStatus::Success);
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_sync_refund() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![Mock::given(method("GET"))
+                .and(path("/refunds/ref_654321"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": "ref_654321",
+                    "status": "pending"
+                })))],
+        })
+        .await;
+    let response = connector
+        .sync_refund("ref_654321".to_string(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap().refund_status,
+        enums::RefundStatus::Pending
+    );
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_fail_payment_for_declined_card() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![Mock::given(method("POST"))
+                .and(path("/payments"))
+                .respond_with(ResponseTemplate::new(402).set_body_json(json!({
+                    "error": {
+                        "code": "card_declined",
+                        "message": "Your card was declined",
+                        "reason": "insufficient_funds"
+                    }
+                })))],
+        })
+        .await;
+    let response = connector.make_payment(None, None).await.unwrap();
+    let err = response.response.unwrap_err();
+    assert_eq!(err.status_code, 402);
+    assert_eq!(err.code, "card_declined");
+    assert_eq!(err.message, "Your card was declined");
+    assert_eq!(err.reason, Some("insufficient_funds".to_string()));
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_fail_payment_with_failed_status() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![mock_payment("failed")],
+        })
+        .await;
+    let response = connector.make_payment(None, None).await.unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::Failure);
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_report_upstream_error_on_gateway_timeout() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![Mock::given(method("POST"))
+                .and(path("/payments"))
+                .respond_with(ResponseTemplate::new(504))],
+        })
+        .await;
+    let response = connector.make_payment(None, None).await;
+    assert!(matches!(
+        response.as_ref().map_err(|err| err.current_context()),
+        Err(ConnectorError::UpstreamErrorReceived {
+            status_code: 504,
+            ..
+        })
+    ));
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_redirect_for_3ds_payment() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![Mock::given(method("POST"))
+                .and(path("/payments"))
+                .and(body_partial_json(json!({ "three_ds": true })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "id": "pay_123456",
+                    "status": "requires_customer_action",
+                    "redirect_url": "http://localhost:9090/3ds/pay_123456"
+                })))],
+        })
+        .await;
+    let response = connector
+        .make_payment(
+            None,
+            Some(utils::PaymentInfo {
+                auth_type: Some(enums::AuthenticationType::ThreeDs),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, enums::AttemptStatus::AuthenticationPending);
+    match response.response.unwrap() {
+        types::PaymentsResponseData::TransactionResponse {
+            redirection_data, ..
+        } => assert_eq!(
+            redirection_data.map(|form| form.url),
+            Some("http://localhost:9090/3ds/pay_123456".to_string())
+        ),
+        _ => panic!("expected a transaction response"),
+    }
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_time_out_slow_connector() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![Mock::given(method("POST"))
+                .and(path("/payments"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(payment_response("succeeded"))
+                        .set_delay(Duration::from_secs(3)),
+                )],
+        })
+        .await;
+    let mut conf = Settings::new().unwrap();
+    conf.connector_retry.max_retries = 0;
+    conf.connector_request_timeout
+        .overrides
+        .insert(connector.get_name(), 1);
+    let state = AppState::test_builder()
+        .settings(conf)
+        .build()
+        .await
+        .unwrap();
+    let request: types::PaymentsAuthorizeRouterData = connector.generate_data(
+        types::PaymentsAuthorizeData {
+            confirm: true,
+            ..utils::PaymentAuthorizeType::default().0
+        },
+        None,
+    );
+    let response = services::api::execute_connector_processing_step(
+        &state,
+        connector.get_data().connector.get_connector_integration(),
+        &request,
+        payments::CallConnectorAction::Trigger,
+    )
+    .await;
+    let error = response.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<ApiClientError>(),
+        Some(ApiClientError::RequestTimeoutReceived)
+    ));
+}
+
+#[actix_web::test]
+#[serial]
+async fn should_leave_timed_out_authorization_in_flight() {
+    let connector = DummyConnector {};
+    let _mock = connector
+        .start_server(MockConfig {
+            address: None,
+            mocks: vec![Mock::given(method("POST"))
+                .and(path("/payments"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(payment_response("succeeded"))
+                        .set_delay(Duration::from_secs(3)),
+                )],
+        })
+        .await;
+    let mut conf = Settings::new().unwrap();
+    conf.connector_retry.max_retries = 0;
+    conf.connector_request_timeout
+        .overrides
+        .insert(connector.get_name(), 1);
+    let state = AppState::test_builder()
+        .settings(conf)
+        .storage(StorageImpl::Mock)
+        .build()
+        .await
+        .unwrap();
+    let merchant_account = state
+        .store
+        .insert_merchant(storage::MerchantAccountNew {
+            merchant_id: "merchant_1".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let request: types::PaymentsAuthorizeRouterData = connector.generate_data(
+        types::PaymentsAuthorizeData {
+            confirm: true,
+            ..utils::PaymentAuthorizeType::default().0
+        },
+        None,
+    );
+    let response = request
+        .decide_flow(
+            &state,
+            &connector.get_data(),
+            &None,
+            Some(true),
+            payments::CallConnectorAction::Trigger,
+            &merchant_account,
+        )
+        .await
+        .unwrap();
+    // The connector may have authorized the payment, which a sync finds out
+    assert_eq!(response.status, enums::AttemptStatus::Authorizing);
+    assert!(matches!(
+        response.response,
+        Ok(types::PaymentsResponseData::TransactionResponse {
+            resource_id: types::ResponseId::NoResponseId,
+            ..
+        })
+    ));
+}
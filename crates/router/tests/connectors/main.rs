@@ -3,14 +3,19 @@
 mod aci;
 mod adyen;
 mod authorizedotnet;
+mod cassette;
 mod checkout;
 mod connector_auth;
 mod cybersource;
+#[cfg(feature = "dummy_connector")]
+mod dummyconnector;
 mod fiserv;
 mod globalpay;
+mod opayo;
 mod payu;
 mod rapyd;
 mod shift4;
+mod smoke;
 mod stripe;
 mod utils;
 mod worldline;
@@ -0,0 +1,319 @@
+use async_trait::async_trait;
+use masking::Secret;
+use router::{
+    core::errors,
+    types::{self, api, api::ConnectorCommon, storage::enums},
+};
+
+use crate::{
+    cassette::CassettePlayer,
+    connector_auth,
+    smoke::SmokeTest,
+    utils::{self, Connector, ConnectorActions},
+};
+
+#[derive(Clone, Copy)]
+pub(crate) struct OpayoTest;
+impl ConnectorActions for OpayoTest {}
+impl utils::Connector for OpayoTest {
+    fn get_data(&self) -> types::api::ConnectorData {
+        use router::connector::Opayo;
+        types::api::ConnectorData {
+            connector: Box::new(&Opayo),
+            connector_name: types::Connector::Opayo,
+            get_token: types::api::GetToken::Connector,
+        }
+    }
+
+    fn get_auth_token(&self) -> types::ConnectorAuthType {
+        types::ConnectorAuthType::from(
+            connector_auth::ConnectorAuthentication::new()
+                .opayo
+                .expect("Missing connector authentication configuration"),
+        )
+    }
+
+    fn get_name(&self) -> String {
+        "opayo".to_string()
+    }
+}
+
+#[async_trait]
+impl SmokeTest for OpayoTest {
+    fn has_credentials(&self, auth: &connector_auth::ConnectorAuthentication) -> bool {
+        auth.opayo.is_some()
+    }
+
+    async fn prepare_payment(
+        &self,
+    ) -> Result<(types::PaymentsAuthorizeData, utils::PaymentInfo), String> {
+        let access_token = self
+            .get_access_token()
+            .await
+            .map_err(|error| format!("{error:?}"))?
+            .response
+            .map_err(|error| format!("Failed to get a merchant session key: {}", error.message))?;
+        let payment_info = utils::PaymentInfo {
+            address: Some(get_billing_address()),
+            access_token: Some(access_token),
+            ..Default::default()
+        };
+        let authorize_data = get_authorize_data();
+
+        // Opayo only authorizes cards tokenized into a card identifier
+        let pre_processing_response = self
+            .pre_process_payment(
+                types::PaymentsPreProcessingData::from(&authorize_data),
+                Some(payment_info.clone()),
+            )
+            .await
+            .map_err(|error| format!("{error:?}"))?
+            .response
+            .map_err(|error| format!("Failed to tokenize the card: {}", error.message))?;
+        let connector_meta = match pre_processing_response {
+            types::PaymentsResponseData::PreProcessingResponse {
+                connector_metadata, ..
+            } => connector_metadata,
+            response => return Err(format!("Unexpected pre-processing response {response:?}")),
+        };
+
+        Ok((
+            types::PaymentsAuthorizeData {
+                connector_meta,
+                ..authorize_data
+            },
+            payment_info,
+        ))
+    }
+}
+
+/// Opayo test whose connector calls are replayed from a cassette, so that the sandbox credentials
+/// are only needed to record the cassette again.
+#[derive(Clone, Copy)]
+struct OpayoReplayTest;
+impl ConnectorActions for OpayoReplayTest {}
+impl utils::Connector for OpayoReplayTest {
+    fn get_data(&self) -> types::api::ConnectorData {
+        OpayoTest.get_data()
+    }
+
+    fn get_auth_token(&self) -> types::ConnectorAuthType {
+        connector_auth::ConnectorAuthentication::try_new()
+            .and_then(|auth| auth.opayo)
+            .map_or_else(
+                || types::ConnectorAuthType::SignatureKey {
+                    api_key: "replay".to_string(),
+                    key1: "replay_vendor".to_string(),
+                    api_secret: "replay".to_string(),
+                },
+                types::ConnectorAuthType::from,
+            )
+    }
+
+    fn get_name(&self) -> String {
+        OpayoTest.get_name()
+    }
+}
+
+// A card the Opayo sandbox authorizes without 3-D Secure
+fn get_authorize_data() -> types::PaymentsAuthorizeData {
+    types::PaymentsAuthorizeData {
+        payment_method_data: api::PaymentMethod::Card(api::Card {
+            card_number: Secret::new("4929000000006".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_cvc: Secret::new("123".to_string()),
+            ..utils::CCardType::default().0
+        }),
+        currency: enums::Currency::GBP,
+        ..utils::PaymentAuthorizeType::default().0
+    }
+}
+
+// The address the Opayo sandbox matches the AVS checks against
+fn get_billing_address() -> types::PaymentAddress {
+    types::PaymentAddress {
+        billing: Some(api::Address {
+            address: Some(api::AddressDetails {
+                first_name: Some(Secret::new("John".to_string())),
+                last_name: Some(Secret::new("Doe".to_string())),
+                line1: Some(Secret::new("88".to_string())),
+                city: Some("London".to_string()),
+                zip: Some(Secret::new("412".to_string())),
+                country: Some("GB".to_string()),
+                ..Default::default()
+            }),
+            phone: None,
+        }),
+        ..Default::default()
+    }
+}
+
+static CONNECTOR: OpayoTest = OpayoTest {};
+
+// Captures a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+#[ignore = "the card has to be tokenized in the pre-processing step before authorizing"]
+async fn should_capture_authorized_payment() {
+    let response = CONNECTOR
+        .authorize_and_capture_payment(None, None, None)
+        .await
+        .expect("Capture payment response");
+    assert_eq!(response.status, enums::AttemptStatus::Charged);
+}
+
+// Captures a payment using an invalid connector transaction id.
+#[actix_web::test]
+async fn should_fail_capture_for_invalid_payment() {
+    let response = CONNECTOR
+        .capture_payment("123456789".to_string(), None, None)
+        .await
+        .unwrap();
+    assert!(response.response.is_err());
+}
+
+// Voids a payment using the manual capture flow (Non 3DS).
+#[actix_web::test]
+#[ignore = "the card has to be tokenized in the pre-processing step before authorizing"]
+async fn should_void_authorized_payment() {
+    let response = CONNECTOR
+        .authorize_and_void_payment(
+            None,
+            Some(types::PaymentsCancelData {
+                connector_transaction_id: String::from(""),
+                cancellation_reason: Some("requested_by_customer".to_string()),
+            }),
+            None,
+        )
+        .await
+        .expect("Void payment response");
+    assert_eq!(response.status, enums::AttemptStatus::Voided);
+}
+
+// Authorizes a payment with the interactions recorded from the sandbox, from the merchant session
+// key to the deferred transaction.
+#[actix_web::test]
+async fn should_replay_recorded_authorization() {
+    let connector = OpayoReplayTest;
+    let mut player = CassettePlayer::new("opayo_authorize");
+
+    let request: types::RefreshTokenRouterData = connector.generate_data(
+        types::AccessTokenRequestData::try_from(connector.get_auth_token()).unwrap(),
+        None,
+    );
+    let access_token = player
+        .execute(
+            connector.get_data().connector.get_connector_integration(),
+            &request,
+        )
+        .await
+        .unwrap()
+        .response
+        .unwrap();
+    let payment_info = utils::PaymentInfo {
+        address: Some(get_billing_address()),
+        access_token: Some(access_token),
+        ..Default::default()
+    };
+
+    let request: types::PaymentsPreProcessingRouterData = connector.generate_data(
+        types::PaymentsPreProcessingData::from(&get_authorize_data()),
+        Some(payment_info.clone()),
+    );
+    let connector_meta = match player
+        .execute(
+            connector.get_data().connector.get_connector_integration(),
+            &request,
+        )
+        .await
+        .unwrap()
+        .response
+    {
+        Ok(types::PaymentsResponseData::PreProcessingResponse {
+            connector_metadata, ..
+        }) => connector_metadata,
+        response => panic!("Unexpected pre-processing response {response:?}"),
+    };
+
+    // The attempt id is sent as the vendor transaction code, compared with the recorded one
+    let request = types::PaymentsAuthorizeRouterData {
+        attempt_id: Some("7c4d1c9e-5b0a-4f43-9e55-2f0f8c1d6a3b".to_string()),
+        ..connector.generate_data(
+            types::PaymentsAuthorizeData {
+                capture_method: Some(enums::CaptureMethod::Manual),
+                connector_meta,
+                ..get_authorize_data()
+            },
+            Some(payment_info),
+        )
+    };
+    let response = player
+        .execute(
+            connector.get_data().connector.get_connector_integration(),
+            &request,
+        )
+        .await
+        .unwrap();
+    player.finish();
+
+    assert_eq!(response.status, enums::AttemptStatus::Authorized);
+    assert_eq!(
+        utils::get_connector_transaction_id(response.response),
+        Some("0E8A3A4B-6E2C-4C8D-9B1F-3D6A2C1E7F50".to_string())
+    );
+}
+
+// Refunds a payment using an invalid connector transaction id.
+#[actix_web::test]
+async fn should_fail_refund_for_invalid_transaction() {
+    let response = CONNECTOR
+        .refund_payment("123456789".to_string(), None, None)
+        .await
+        .unwrap();
+    assert!(response.response.is_err());
+}
+
+// Synchronizes a refund using an invalid connector refund id.
+#[actix_web::test]
+async fn should_fail_sync_for_invalid_refund() {
+    let response = CONNECTOR
+        .sync_refund("123456789".to_string(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(response.response.unwrap_err().status_code, 404);
+}
+
+// [#478]: add payment flow tests once the authorize flow is implemented
+
+// Checks that Opayo reports support for card payments in the currencies it accepts.
+#[test]
+fn should_report_card_support() {
+    let capabilities = router::connector::Opayo.get_capabilities();
+    assert!(capabilities.supports_payment_method(enums::PaymentMethodType::Card));
+    assert!(capabilities.supports_flow(types::api::ConnectorFlow::Refund));
+    assert!(capabilities
+        .validate(
+            "opayo",
+            &[types::api::ConnectorFlow::Authorize],
+            enums::PaymentMethodType::Card,
+            Some(enums::Currency::GBP),
+        )
+        .is_ok());
+}
+
+// Rejects a payment method Opayo does not support before calling the connector.
+#[test]
+fn should_reject_unsupported_payment_method() {
+    let error = router::connector::Opayo
+        .get_capabilities()
+        .validate(
+            "opayo",
+            &[types::api::ConnectorFlow::Authorize],
+            enums::PaymentMethodType::Wallet,
+            Some(enums::Currency::GBP),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error.current_context(),
+        errors::ConnectorError::PaymentMethodNotSupported { .. }
+    ));
+}
@@ -0,0 +1,309 @@
+//! End-to-end smoke test of the connectors against their sandboxes.
+//!
+//! For every connector listed in the `CONNECTOR_SMOKE_TESTS` environment variable, a payment is
+//! authorized, captured, refunded and the refund synced with the credentials in
+//! `tests/connectors/auth.toml`, and the outcome of each flow is reported when one fails:
+//!
+//! ```text
+//! CONNECTOR_SMOKE_TESTS=opayo,authorizedotnet \
+//!     cargo test --package router --test connectors -- smoke
+//! ```
+//!
+//! Without the environment variable the smoke test does nothing, so it can stay enabled. A
+//! selected connector without credentials fails the test.
+
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use router::types::{self, storage::enums};
+
+use crate::{
+    authorizedotnet, connector_auth, opayo,
+    utils::{ConnectorActions, PaymentAuthorizeType, PaymentInfo, PaymentRefundType},
+};
+
+pub const SMOKE_TESTS_ENV: &str = "CONNECTOR_SMOKE_TESTS";
+
+#[async_trait]
+pub trait SmokeTest: ConnectorActions + Sync {
+    /// Whether the sandbox credentials of the connector are configured.
+    fn has_credentials(&self, auth: &connector_auth::ConnectorAuthentication) -> bool;
+
+    /// Authorization request and payment info used by every flow of the smoke test. Connectors
+    /// that need steps before authorizing, like tokenizing the card, run them here.
+    async fn prepare_payment(&self) -> Result<(types::PaymentsAuthorizeData, PaymentInfo), String> {
+        Ok((PaymentAuthorizeType::default().0, PaymentInfo::default()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SmokeTestFlow {
+    Authorize,
+    Capture,
+    Refund,
+    RefundSync,
+}
+
+impl SmokeTestFlow {
+    const ALL: [Self; 4] = [
+        Self::Authorize,
+        Self::Capture,
+        Self::Refund,
+        Self::RefundSync,
+    ];
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FlowOutcome {
+    Passed,
+    Failed(String),
+    /// An earlier flow failed, so the flow could not be run
+    Skipped,
+}
+
+#[derive(Debug)]
+pub struct SmokeTestReport {
+    pub connector: String,
+    pub flows: Vec<(SmokeTestFlow, FlowOutcome)>,
+}
+
+impl SmokeTestReport {
+    pub fn is_success(&self) -> bool {
+        self.flows
+            .iter()
+            .all(|(_, outcome)| *outcome == FlowOutcome::Passed)
+    }
+}
+
+impl fmt::Display for SmokeTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:", self.connector)?;
+        for (flow, outcome) in &self.flows {
+            match outcome {
+                FlowOutcome::Passed => writeln!(f, "  {flow:?}: passed")?,
+                FlowOutcome::Failed(reason) => writeln!(f, "  {flow:?}: failed, {reason}")?,
+                FlowOutcome::Skipped => writeln!(f, "  {flow:?}: skipped")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs authorize, capture, refund and refund sync in that order, skipping the flows after the
+/// first failure.
+pub async fn run_smoke_test<T: SmokeTest>(connector: &T) -> SmokeTestReport {
+    let mut report = SmokeTestReport {
+        connector: connector.get_name(),
+        flows: Vec::new(),
+    };
+    if let Err((flow, reason)) = run_flows(connector, &mut report).await {
+        report.flows.push((flow, FlowOutcome::Failed(reason)));
+    }
+    for flow in SmokeTestFlow::ALL {
+        if !report.flows.iter().any(|(ran_flow, _)| *ran_flow == flow) {
+            report.flows.push((flow, FlowOutcome::Skipped));
+        }
+    }
+    report
+}
+
+async fn run_flows<T: SmokeTest>(
+    connector: &T,
+    report: &mut SmokeTestReport,
+) -> Result<(), (SmokeTestFlow, String)> {
+    let interval = Duration::from_secs(connector.get_request_interval());
+
+    let flow = SmokeTestFlow::Authorize;
+    let (authorize_data, payment_info) = connector
+        .prepare_payment()
+        .await
+        .map_err(|reason| (flow, reason))?;
+    let response = connector
+        .authorize_payment(Some(authorize_data.clone()), Some(payment_info.clone()))
+        .await
+        .map_err(|error| (flow, format!("{error:?}")))?;
+    let (transaction_id, authorize_metadata) =
+        get_payment_details(response, enums::AttemptStatus::Authorized)
+            .map_err(|reason| (flow, reason))?;
+    report.flows.push((flow, FlowOutcome::Passed));
+
+    let flow = SmokeTestFlow::Capture;
+    tokio::time::sleep(interval).await;
+    let response = connector
+        .capture_payment(
+            transaction_id,
+            Some(types::PaymentsCaptureData {
+                amount_to_capture: Some(authorize_data.amount),
+                currency: authorize_data.currency,
+                connector_transaction_id: String::new(),
+                amount: authorize_data.amount,
+            }),
+            Some(payment_info.clone()),
+        )
+        .await
+        .map_err(|error| (flow, format!("{error:?}")))?;
+    let (transaction_id, connector_metadata) =
+        get_payment_details(response, enums::AttemptStatus::Charged)
+            .map_err(|reason| (flow, reason))?;
+    report.flows.push((flow, FlowOutcome::Passed));
+
+    let flow = SmokeTestFlow::Refund;
+    tokio::time::sleep(interval).await;
+    let refund_data = types::RefundsData {
+        amount: authorize_data.amount,
+        currency: authorize_data.currency,
+        refund_amount: authorize_data.amount,
+        // Refunds need the payment details the connector returned when authorizing
+        connector_metadata: connector_metadata.or(authorize_metadata),
+        ..PaymentRefundType::default().0
+    };
+    let response = connector
+        .refund_payment(
+            transaction_id.clone(),
+            Some(refund_data.clone()),
+            Some(payment_info.clone()),
+        )
+        .await
+        .map_err(|error| (flow, format!("{error:?}")))?;
+    let connector_refund_id =
+        get_refund_details(response.response).map_err(|reason| (flow, reason))?;
+    report.flows.push((flow, FlowOutcome::Passed));
+
+    let flow = SmokeTestFlow::RefundSync;
+    tokio::time::sleep(interval).await;
+    let response = connector
+        .sync_refund(
+            connector_refund_id.clone(),
+            Some(types::RefundsData {
+                connector_transaction_id: transaction_id,
+                connector_refund_id: Some(connector_refund_id),
+                ..refund_data
+            }),
+            Some(payment_info),
+        )
+        .await
+        .map_err(|error| (flow, format!("{error:?}")))?;
+    get_refund_details(response.response).map_err(|reason| (flow, reason))?;
+    report.flows.push((flow, FlowOutcome::Passed));
+
+    Ok(())
+}
+
+/// Connector transaction id and metadata of a payment that reached `expected_status`.
+fn get_payment_details<F, Req>(
+    router_data: types::RouterData<F, Req, types::PaymentsResponseData>,
+    expected_status: enums::AttemptStatus,
+) -> Result<(String, Option<serde_json::Value>), String> {
+    let response = router_data.response.map_err(format_error_response)?;
+    if router_data.status != expected_status {
+        return Err(format!(
+            "expected status {expected_status:?}, got {:?}",
+            router_data.status
+        ));
+    }
+    match response {
+        types::PaymentsResponseData::TransactionResponse {
+            resource_id,
+            connector_metadata,
+            ..
+        } => resource_id
+            .get_connector_transaction_id()
+            .map(|transaction_id| (transaction_id, connector_metadata))
+            .map_err(|_| "the response has no connector transaction id".to_string()),
+        response => Err(format!("unexpected response {response:?}")),
+    }
+}
+
+/// Connector refund id of a refund that has not failed.
+fn get_refund_details(
+    response: Result<types::RefundsResponseData, types::ErrorResponse>,
+) -> Result<String, String> {
+    let response = response.map_err(format_error_response)?;
+    match response.refund_status {
+        enums::RefundStatus::Success | enums::RefundStatus::Pending => {
+            Ok(response.connector_refund_id)
+        }
+        status => Err(format!("unexpected refund status {status:?}")),
+    }
+}
+
+fn format_error_response(error: types::ErrorResponse) -> String {
+    format!(
+        "{} ({}): {}",
+        error.code,
+        error.status_code,
+        error.reason.unwrap_or(error.message)
+    )
+}
+
+async fn run_if_configured<T: SmokeTest>(
+    connector: &T,
+    auth: &connector_auth::ConnectorAuthentication,
+) -> SmokeTestReport {
+    assert!(
+        connector.has_credentials(auth),
+        "The smoke test of {} was selected, but its credentials are missing from tests/connectors/auth.toml",
+        connector.get_name()
+    );
+    run_smoke_test(connector).await
+}
+
+async fn run_selected_smoke_test(
+    connector_name: &str,
+    auth: &connector_auth::ConnectorAuthentication,
+) -> SmokeTestReport {
+    match connector_name {
+        "authorizedotnet" => run_if_configured(&authorizedotnet::AuthorizedotnetTest, auth).await,
+        "opayo" => run_if_configured(&opayo::OpayoTest, auth).await,
+        connector_name => panic!("No smoke test is wired for connector {connector_name}"),
+    }
+}
+
+#[actix_web::test]
+async fn should_pass_smoke_test_for_selected_connectors() {
+    let selected_connectors = match std::env::var(SMOKE_TESTS_ENV) {
+        Ok(selected_connectors) => selected_connectors,
+        Err(_) => return,
+    };
+    let auth = connector_auth::ConnectorAuthentication::try_new().unwrap_or_else(|| {
+        panic!("Smoke tests were selected with {SMOKE_TESTS_ENV}, but tests/connectors/auth.toml is missing")
+    });
+
+    let mut failed_reports = Vec::new();
+    for connector_name in selected_connectors
+        .split(',')
+        .map(str::trim)
+        .filter(|connector_name| !connector_name.is_empty())
+    {
+        let report = run_selected_smoke_test(connector_name, &auth).await;
+        if !report.is_success() {
+            failed_reports.push(report.to_string());
+        }
+    }
+    assert!(
+        failed_reports.is_empty(),
+        "Smoke test failed:\n{}",
+        failed_reports.concat()
+    );
+}
+
+#[test]
+fn should_report_outcome_of_each_flow() {
+    let report = SmokeTestReport {
+        connector: "opayo".to_string(),
+        flows: vec![
+            (SmokeTestFlow::Authorize, FlowOutcome::Passed),
+            (
+                SmokeTestFlow::Capture,
+                FlowOutcome::Failed("declined".to_string()),
+            ),
+            (SmokeTestFlow::Refund, FlowOutcome::Skipped),
+            (SmokeTestFlow::RefundSync, FlowOutcome::Skipped),
+        ],
+    };
+    assert!(!report.is_success());
+    assert_eq!(
+        report.to_string(),
+        "opayo:\n  Authorize: passed\n  Capture: failed, declined\n  Refund: skipped\n  RefundSync: skipped\n"
+    );
+}
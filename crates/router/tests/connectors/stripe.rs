@@ -431,3 +431,25 @@ async fn should_sync_manually_captured_refund() {
         enums::RefundStatus::Success,
     );
 }
+
+// Stripe opens disputes asynchronously for payments made with its dispute test cards, so the id of
+// an open dispute has to be taken from the dashboard
+#[actix_web::test]
+#[ignore]
+async fn should_submit_dispute_evidence() {
+    let response = Stripe {}
+        .submit_evidence(
+            "dp_1MbtJnD5R7gDAGffGvKvbmTw".to_string(),
+            api::SubmitEvidenceRequest {
+                product_description: Some("Annual subscription".to_string()),
+                uncategorized_text: Some("The customer used the service after paying".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.response.unwrap().evidence_status,
+        enums::EvidenceStatus::Submitted
+    );
+}
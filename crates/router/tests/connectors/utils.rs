@@ -5,7 +5,6 @@ use error_stack::Report;
 use masking::Secret;
 use router::{
     core::{errors, errors::ConnectorError, payments},
-    db::StorageImpl,
     routes, services,
     types::{self, api, storage::enums, AccessToken, PaymentAddress},
 };
@@ -192,6 +191,43 @@ pub trait ConnectorActions: Connector {
         call_connector(request, integration).await
     }
 
+    async fn get_access_token(
+        &self,
+    ) -> Result<types::RefreshTokenRouterData, Report<ConnectorError>> {
+        let integration = self.get_data().connector.get_connector_integration();
+        let request_data = types::AccessTokenRequestData::try_from(self.get_auth_token())
+            .map_err(|_| Report::new(ConnectorError::FailedToObtainAuthType))?;
+        let request = self.generate_data(request_data, None);
+        call_connector(request, integration).await
+    }
+
+    async fn pre_process_payment(
+        &self,
+        payment_data: types::PaymentsPreProcessingData,
+        payment_info: Option<PaymentInfo>,
+    ) -> Result<types::PaymentsPreProcessingRouterData, Report<ConnectorError>> {
+        let integration = self.get_data().connector.get_connector_integration();
+        let request = self.generate_data(payment_data, payment_info);
+        call_connector(request, integration).await
+    }
+
+    async fn submit_evidence(
+        &self,
+        connector_dispute_id: String,
+        evidence: api::SubmitEvidenceRequest,
+    ) -> Result<types::SubmitEvidenceRouterData, Report<ConnectorError>> {
+        let integration = self.get_data().connector.get_connector_integration();
+        let request = self.generate_data(
+            types::SubmitEvidenceRequestData {
+                dispute_id: uuid::Uuid::new_v4().to_string(),
+                connector_dispute_id,
+                evidence,
+            },
+            None,
+        );
+        call_connector(request, integration).await
+    }
+
     async fn capture_payment_and_refund(
         &self,
         authorize_data: Option<types::PaymentsAuthorizeData>,
@@ -364,6 +400,7 @@ pub trait ConnectorActions: Connector {
             request: req,
             response: Err(types::ErrorResponse::default()),
             payment_method_id: None,
+            connector_response_body: None,
             address: info
                 .clone()
                 .and_then(|a| a.address)
@@ -371,6 +408,7 @@ pub trait ConnectorActions: Connector {
                 .unwrap(),
             connector_meta_data: self.get_connector_meta(),
             amount_captured: None,
+            authorized_amount: None,
             access_token: info.and_then(|a| a.access_token),
         }
     }
@@ -383,23 +421,22 @@ pub trait ConnectorActions: Connector {
             Ok(types::PaymentsResponseData::TransactionResponse { resource_id, .. }) => {
                 resource_id.get_connector_transaction_id().ok()
             }
-            Ok(types::PaymentsResponseData::SessionResponse { .. }) => None,
+            Ok(types::PaymentsResponseData::SessionResponse { .. })
+            | Ok(types::PaymentsResponseData::PreProcessingResponse { .. }) => None,
             Err(_) => None,
         }
     }
 }
 
 async fn call_connector<
-    T: Debug + Clone + 'static,
+    T: api::FlowType + Debug + Clone + 'static,
     Req: Debug + Clone + 'static,
     Resp: Debug + Clone + 'static,
 >(
     request: types::RouterData<T, Req, Resp>,
     integration: services::BoxedConnectorIntegration<'_, T, Req, Resp>,
 ) -> Result<types::RouterData<T, Req, Resp>, Report<ConnectorError>> {
-    use router::configs::settings::Settings;
-    let conf = Settings::new().unwrap();
-    let state = routes::AppState::with_storage(conf, StorageImpl::PostgresqlTest).await;
+    let state = routes::AppState::test_builder().build().await.unwrap();
     services::api::execute_connector_processing_step(
         &state,
         integration,
@@ -461,6 +498,7 @@ impl Default for PaymentAuthorizeType {
             currency: enums::Currency::USD,
             confirm: true,
             statement_descriptor_suffix: None,
+            statement_descriptor_prefix: None,
             capture_method: None,
             setup_future_usage: None,
             mandate_id: None,
@@ -468,7 +506,12 @@ impl Default for PaymentAuthorizeType {
             setup_mandate_details: None,
             browser_info: Some(BrowserInfoType::default().0),
             order_details: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
             email: None,
+            connector_meta: None,
+            network_token: None,
         };
         Self(data)
     }
@@ -548,7 +591,8 @@ pub fn get_connector_transaction_id(
         Ok(types::PaymentsResponseData::TransactionResponse { resource_id, .. }) => {
             resource_id.get_connector_transaction_id().ok()
         }
-        Ok(types::PaymentsResponseData::SessionResponse { .. }) => None,
+        Ok(types::PaymentsResponseData::SessionResponse { .. })
+        | Ok(types::PaymentsResponseData::PreProcessingResponse { .. }) => None,
         Err(_) => None,
     }
 }
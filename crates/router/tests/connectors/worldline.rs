@@ -72,6 +72,7 @@ impl WorldlineTest {
             }),
             confirm: true,
             statement_descriptor_suffix: None,
+            statement_descriptor_prefix: None,
             setup_future_usage: None,
             mandate_id: None,
             off_session: None,
@@ -79,7 +80,12 @@ impl WorldlineTest {
             capture_method: Some(capture_method),
             browser_info: None,
             order_details: None,
+            surcharge_details: None,
+            tax_amount: None,
+            sca_exemption: None,
             email: None,
+            connector_meta: None,
+            network_token: None,
         })
     }
 }
@@ -0,0 +1,75 @@
+#![allow(clippy::unwrap_used)]
+
+use actix_web::{
+    body,
+    http::StatusCode,
+    test::{call_service, init_service, TestRequest},
+};
+use masking::StrongSecret;
+use router::{
+    configs::settings::Settings,
+    db::{merchant_account::MerchantAccountInterface, StorageImpl},
+    routes::AppState,
+    types::storage,
+};
+use serde_json::json;
+
+async fn submit_evidence(api_key: Option<&str>) -> (StatusCode, String) {
+    let conf = Settings::new().unwrap();
+    let request_body_limit = conf.server.request_body_limit;
+    let state = AppState::test_builder()
+        .settings(conf)
+        .storage(StorageImpl::Mock)
+        .build()
+        .await
+        .unwrap();
+    state
+        .store
+        .insert_merchant(storage::MerchantAccountNew {
+            merchant_id: "merchant_1".to_string(),
+            api_key: Some(StrongSecret::new("dev_api_key".to_string())),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let server = init_service(router::mk_app(state, request_body_limit)).await;
+
+    let mut request = TestRequest::post()
+        .uri("/disputes/dp_unknown/evidence")
+        .set_json(json!({ "product_description": "A pair of shoes" }));
+    if let Some(api_key) = api_key {
+        request = request.insert_header(("api-key", api_key));
+    }
+    let response = call_service(&server, request.to_request()).await;
+    let status = response.status();
+    let body = body::to_bytes(response.into_body()).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[actix_web::test]
+async fn submit_evidence_without_api_key_is_unauthorized() {
+    let (status, body) = submit_evidence(None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body.contains("IR_01"));
+}
+
+#[actix_web::test]
+async fn submit_evidence_with_invalid_api_key_is_unauthorized() {
+    let (status, body) = submit_evidence(Some("dev_invalid_api_key")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body.contains("IR_01"));
+}
+
+#[actix_web::test]
+async fn submit_evidence_with_publishable_key_is_forbidden() {
+    let (status, body) = submit_evidence(Some("pk_dev_api_key")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body.contains("IR_20"));
+}
+
+#[actix_web::test]
+async fn submit_evidence_with_valid_api_key_reaches_the_route() {
+    let (status, body) = submit_evidence(Some("dev_api_key")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("Dispute does not exist in our records"));
+}
@@ -1,5 +1,6 @@
 mod utils;
 
+use actix_web::test::{call_service, TestRequest};
 use utils::{mk_service, AppClient};
 
 #[actix_web::test]
@@ -9,3 +10,21 @@ async fn health_check() {
 
     assert_eq!(client.health(&server).await, "health is good");
 }
+
+#[actix_web::test]
+async fn request_id_is_echoed_in_response() {
+    let server = mk_service().await;
+    let request = TestRequest::get()
+        .uri("/health")
+        .insert_header(("X-Request-Id", "req_123"))
+        .to_request();
+    let response = call_service(&server, request).await;
+
+    assert_eq!(
+        response
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok()),
+        Some("req_123")
+    );
+}
@@ -327,6 +327,9 @@ async fn payments_create_core() {
         off_session: None,
         client_secret: None,
         browser_info: None,
+        surcharge_details: None,
+        tax_amount: None,
+        sca_exemption: None,
     };
 
     let expected_response = api::PaymentsResponse {
@@ -482,6 +485,9 @@ async fn payments_create_core_adyen_no_redirect() {
         off_session: None,
         client_secret: None,
         browser_info: None,
+        surcharge_details: None,
+        tax_amount: None,
+        sca_exemption: None,
     };
 
     let expected_response = services::ApplicationResponse::Json(api::PaymentsResponse {
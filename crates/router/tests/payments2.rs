@@ -239,6 +239,9 @@ async fn payments_create_core_adyen_no_redirect() {
         mandate_id: None,
         client_secret: None,
         browser_info: None,
+        surcharge_details: None,
+        tax_amount: None,
+        sca_exemption: None,
     };
 
     let expected_response = services::ApplicationResponse::Json(api::PaymentsResponse {
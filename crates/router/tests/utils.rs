@@ -48,7 +48,12 @@ pub async fn mk_service(
         conf.connectors.stripe.base_url = url;
     }
 
-    let app_state = AppState::with_storage(conf, router::db::StorageImpl::Mock).await;
+    let app_state = AppState::test_builder()
+        .settings(conf)
+        .storage(router::db::StorageImpl::Mock)
+        .build()
+        .await
+        .expect("failed to build app state");
     actix_web::test::init_service(router::mk_app(app_state, request_body_limit)).await
 }
 
@@ -140,14 +140,22 @@ pub enum Flow {
     PayoutsAccounts,
     /// Refunds create flow.
     RefundsCreate,
+    /// Bulk refunds create flow.
+    RefundsBulkCreate,
     /// Refunds retrieve flow.
     RefundsRetrieve,
     /// Refunds update flow.
     RefundsUpdate,
     /// Refunds list flow.
     RefundsList,
+    /// Dispute evidence submit flow.
+    DisputesEvidenceSubmit,
     /// Incoming Webhook Receive
     IncomingWebhookReceive,
+    /// Webhook delivery attempts list flow
+    WebhookDeliveryAttemptsList,
+    /// Outgoing webhook redeliver flow
+    OutgoingWebhookRedeliver,
     /// Validate payment method flow
     ValidatePaymentMethod,
     /// API Key create flow
@@ -160,6 +168,8 @@ pub enum Flow {
     ApiKeyRevoke,
     /// API Key list flow
     ApiKeyList,
+    /// API Key rotate flow
+    ApiKeyRotate,
 }
 
 /// Category of log event.
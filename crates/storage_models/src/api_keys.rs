@@ -4,7 +4,7 @@ use time::PrimitiveDateTime;
 
 use crate::schema::api_keys;
 
-#[derive(Debug, Identifiable, Queryable)]
+#[derive(Clone, Debug, Identifiable, Queryable)]
 #[diesel(table_name = api_keys, primary_key(key_id))]
 pub struct ApiKey {
     pub key_id: String,
@@ -80,7 +80,20 @@ impl From<ApiKeyUpdate> for ApiKeyUpdateInternal {
     }
 }
 
-#[derive(Debug, AsExpression)]
+impl ApiKeyUpdate {
+    pub fn apply_changeset(self, source: ApiKey) -> ApiKey {
+        let api_key_update: ApiKeyUpdateInternal = self.into();
+        ApiKey {
+            name: api_key_update.name.unwrap_or(source.name),
+            description: api_key_update.description.or(source.description),
+            expires_at: api_key_update.expires_at.unwrap_or(source.expires_at),
+            last_used: api_key_update.last_used.or(source.last_used),
+            ..source
+        }
+    }
+}
+
+#[derive(Clone, Debug, AsExpression)]
 #[diesel(sql_type = diesel::sql_types::Text)]
 pub struct HashedApiKey(String);
 
@@ -90,6 +103,12 @@ impl From<String> for HashedApiKey {
     }
 }
 
+impl HashedApiKey {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
 mod diesel_impl {
     use diesel::{
         backend::Backend,
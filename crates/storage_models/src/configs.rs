@@ -41,3 +41,13 @@ impl From<ConfigUpdate> for ConfigUpdateInternal {
         }
     }
 }
+
+impl ConfigUpdate {
+    pub fn apply_changeset(self, source: Config) -> Config {
+        let config_update: ConfigUpdateInternal = self.into();
+        Config {
+            config: config_update.config.unwrap_or(source.config),
+            ..source
+        }
+    }
+}
@@ -1 +1,97 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
 
+use crate::{enums as storage_enums, schema::dispute};
+
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
+#[diesel(table_name = dispute)]
+pub struct Dispute {
+    pub id: i32,
+    pub dispute_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub connector_dispute_id: String,
+    pub connector_reason_code: Option<String>,
+    pub amount: i64,
+    pub currency: String,
+    pub dispute_status: storage_enums::DisputeStatus,
+    pub created_at: PrimitiveDateTime,
+    pub modified_at: PrimitiveDateTime,
+    pub evidence_status: Option<storage_enums::EvidenceStatus>,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay, Serialize, Deserialize)]
+#[diesel(table_name = dispute)]
+pub struct DisputeNew {
+    pub dispute_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub connector_dispute_id: String,
+    pub connector_reason_code: Option<String>,
+    pub amount: i64,
+    pub currency: String,
+    pub dispute_status: storage_enums::DisputeStatus,
+}
+
+#[derive(Debug)]
+pub enum DisputeUpdate {
+    StatusUpdate {
+        dispute_status: storage_enums::DisputeStatus,
+        connector_reason_code: Option<String>,
+    },
+    EvidenceUpdate {
+        evidence_status: storage_enums::EvidenceStatus,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = dispute)]
+pub struct DisputeUpdateInternal {
+    dispute_status: Option<storage_enums::DisputeStatus>,
+    connector_reason_code: Option<String>,
+    evidence_status: Option<storage_enums::EvidenceStatus>,
+    modified_at: Option<PrimitiveDateTime>,
+}
+
+impl From<DisputeUpdate> for DisputeUpdateInternal {
+    fn from(dispute_update: DisputeUpdate) -> Self {
+        match dispute_update {
+            DisputeUpdate::StatusUpdate {
+                dispute_status,
+                connector_reason_code,
+            } => Self {
+                dispute_status: Some(dispute_status),
+                connector_reason_code,
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+            DisputeUpdate::EvidenceUpdate { evidence_status } => Self {
+                evidence_status: Some(evidence_status),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl DisputeUpdate {
+    pub fn apply_changeset(self, source: Dispute) -> Dispute {
+        let dispute_update: DisputeUpdateInternal = self.into();
+        Dispute {
+            dispute_status: dispute_update
+                .dispute_status
+                .unwrap_or(source.dispute_status),
+            connector_reason_code: dispute_update
+                .connector_reason_code
+                .or(source.connector_reason_code),
+            evidence_status: dispute_update.evidence_status.or(source.evidence_status),
+            modified_at: dispute_update.modified_at.unwrap_or(source.modified_at),
+            ..source
+        }
+    }
+}
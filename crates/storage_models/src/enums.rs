@@ -2,12 +2,14 @@
 pub mod diesel_exports {
     pub use super::{
         DbAttemptStatus as AttemptStatus, DbAuthenticationType as AuthenticationType,
-        DbCaptureMethod as CaptureMethod, DbConnectorType as ConnectorType, DbCurrency as Currency,
-        DbEventClass as EventClass, DbEventObjectType as EventObjectType, DbEventType as EventType,
-        DbFutureUsage as FutureUsage, DbIntentStatus as IntentStatus,
-        DbMandateStatus as MandateStatus, DbMandateType as MandateType,
-        DbMerchantStorageScheme as MerchantStorageScheme, DbPaymentFlow as PaymentFlow,
-        DbPaymentMethodIssuerCode as PaymentMethodIssuerCode,
+        DbAvsResult as AvsResult, DbCaptureMethod as CaptureMethod,
+        DbConnectorType as ConnectorType, DbCurrency as Currency, DbCvvResult as CvvResult,
+        DbDisputeStatus as DisputeStatus, DbEventClass as EventClass,
+        DbEventObjectType as EventObjectType, DbEventType as EventType,
+        DbEvidenceStatus as EvidenceStatus, DbFutureUsage as FutureUsage,
+        DbIntentStatus as IntentStatus, DbMandateStatus as MandateStatus,
+        DbMandateType as MandateType, DbMerchantStorageScheme as MerchantStorageScheme,
+        DbPaymentFlow as PaymentFlow, DbPaymentMethodIssuerCode as PaymentMethodIssuerCode,
         DbPaymentMethodSubType as PaymentMethodSubType, DbPaymentMethodType as PaymentMethodType,
         DbProcessTrackerStatus as ProcessTrackerStatus, DbRefundStatus as RefundStatus,
         DbRefundType as RefundType, DbRoutingAlgorithm as RoutingAlgorithm,
@@ -37,8 +39,12 @@ pub enum AttemptStatus {
     AuthenticationPending,
     AuthenticationSuccessful,
     Authorized,
+    /// The connector authorized less than the requested amount
+    PartiallyAuthorized,
     AuthorizationFailed,
     Charged,
+    /// The payment method was verified without authorizing or charging any amount
+    Verified,
     Authorizing,
     CodInitiated,
     Voided,
@@ -55,6 +61,61 @@ pub enum AttemptStatus {
     ConfirmationAwaited,
 }
 
+impl AttemptStatus {
+    /// Whether an attempt in this status can move to `next` on a connector response.
+    ///
+    /// Attempts move freely until they are authorized. Authorized attempts can only be captured,
+    /// voided or failed, and charged, verified or voided attempts no longer change, except for
+    /// charged attempts refunded automatically. Failed attempts can still turn out to be
+    /// authorized, charged or verified, when syncing shows that the connector processed the
+    /// payment after all.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        if self == next {
+            return true;
+        }
+        match self {
+            Self::Started
+            | Self::Pending
+            | Self::PaymentMethodAwaited
+            | Self::ConfirmationAwaited
+            | Self::AuthenticationPending
+            | Self::AuthenticationSuccessful
+            | Self::Authorizing
+            | Self::CodInitiated => true,
+            Self::Authorized
+            | Self::PartiallyAuthorized
+            | Self::CaptureInitiated
+            | Self::CaptureFailed
+            | Self::PartialCharged
+            | Self::VoidInitiated
+            | Self::VoidFailed => matches!(
+                next,
+                Self::Authorized
+                    | Self::PartiallyAuthorized
+                    | Self::CaptureInitiated
+                    | Self::CaptureFailed
+                    | Self::PartialCharged
+                    | Self::Charged
+                    | Self::VoidInitiated
+                    | Self::VoidFailed
+                    | Self::Voided
+                    | Self::AutoRefunded
+                    | Self::Pending
+                    | Self::Failure
+            ),
+            Self::Charged => next == Self::AutoRefunded,
+            Self::Failure
+            | Self::AuthorizationFailed
+            | Self::AuthenticationFailed
+            | Self::RouterDeclined => matches!(
+                next,
+                Self::Authorized | Self::PartiallyAuthorized | Self::Charged | Self::Verified
+            ),
+            Self::Verified | Self::Voided | Self::AutoRefunded => false,
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -78,6 +139,56 @@ pub enum AuthenticationType {
     NoThreeDs,
 }
 
+/// Normalized outcome of checking the billing address against the one the issuer has on file.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    router_derive::DieselEnum,
+)]
+#[router_derive::diesel_enum]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AvsResult {
+    /// Both the street address and the postal code match.
+    Match,
+    /// Either the street address or the postal code matches, but not both.
+    PartialMatch,
+    /// Neither the street address nor the postal code match.
+    NoMatch,
+    /// The issuer did not perform the check or does not support it.
+    Unavailable,
+}
+
+/// Normalized outcome of checking the card security code with the issuer.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    router_derive::DieselEnum,
+)]
+#[router_derive::diesel_enum]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CvvResult {
+    Match,
+    NoMatch,
+    /// The issuer did not perform the check or does not support it.
+    Unavailable,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -260,6 +371,127 @@ pub enum Currency {
     ZAR,
 }
 
+impl Currency {
+    /// Number of digits after the decimal separator of the currency as defined by ISO 4217,
+    /// i.e. the exponent relating the minor unit to the major unit.
+    pub fn minor_unit_exponent(&self) -> u32 {
+        match self {
+            Self::JPY | Self::KRW => 0,
+            Self::BHD | Self::JOD | Self::KWD | Self::OMR => 3,
+            _ => 2,
+        }
+    }
+
+    /// Three letter currency code as defined by ISO 4217.
+    pub fn iso_4217_code(&self) -> &'static str {
+        match self {
+            Self::AED => "AED",
+            Self::ALL => "ALL",
+            Self::AMD => "AMD",
+            Self::ARS => "ARS",
+            Self::AUD => "AUD",
+            Self::AWG => "AWG",
+            Self::AZN => "AZN",
+            Self::BBD => "BBD",
+            Self::BDT => "BDT",
+            Self::BHD => "BHD",
+            Self::BMD => "BMD",
+            Self::BND => "BND",
+            Self::BOB => "BOB",
+            Self::BRL => "BRL",
+            Self::BSD => "BSD",
+            Self::BWP => "BWP",
+            Self::BZD => "BZD",
+            Self::CAD => "CAD",
+            Self::CHF => "CHF",
+            Self::CNY => "CNY",
+            Self::COP => "COP",
+            Self::CRC => "CRC",
+            Self::CUP => "CUP",
+            Self::CZK => "CZK",
+            Self::DKK => "DKK",
+            Self::DOP => "DOP",
+            Self::DZD => "DZD",
+            Self::EGP => "EGP",
+            Self::ETB => "ETB",
+            Self::EUR => "EUR",
+            Self::FJD => "FJD",
+            Self::GBP => "GBP",
+            Self::GHS => "GHS",
+            Self::GIP => "GIP",
+            Self::GMD => "GMD",
+            Self::GTQ => "GTQ",
+            Self::GYD => "GYD",
+            Self::HKD => "HKD",
+            Self::HNL => "HNL",
+            Self::HRK => "HRK",
+            Self::HTG => "HTG",
+            Self::HUF => "HUF",
+            Self::IDR => "IDR",
+            Self::ILS => "ILS",
+            Self::INR => "INR",
+            Self::JMD => "JMD",
+            Self::JOD => "JOD",
+            Self::JPY => "JPY",
+            Self::KES => "KES",
+            Self::KGS => "KGS",
+            Self::KHR => "KHR",
+            Self::KRW => "KRW",
+            Self::KWD => "KWD",
+            Self::KYD => "KYD",
+            Self::KZT => "KZT",
+            Self::LAK => "LAK",
+            Self::LBP => "LBP",
+            Self::LKR => "LKR",
+            Self::LRD => "LRD",
+            Self::LSL => "LSL",
+            Self::MAD => "MAD",
+            Self::MDL => "MDL",
+            Self::MKD => "MKD",
+            Self::MMK => "MMK",
+            Self::MNT => "MNT",
+            Self::MOP => "MOP",
+            Self::MUR => "MUR",
+            Self::MVR => "MVR",
+            Self::MWK => "MWK",
+            Self::MXN => "MXN",
+            Self::MYR => "MYR",
+            Self::NAD => "NAD",
+            Self::NGN => "NGN",
+            Self::NIO => "NIO",
+            Self::NOK => "NOK",
+            Self::NPR => "NPR",
+            Self::NZD => "NZD",
+            Self::OMR => "OMR",
+            Self::PEN => "PEN",
+            Self::PGK => "PGK",
+            Self::PHP => "PHP",
+            Self::PKR => "PKR",
+            Self::PLN => "PLN",
+            Self::QAR => "QAR",
+            Self::RUB => "RUB",
+            Self::SAR => "SAR",
+            Self::SCR => "SCR",
+            Self::SEK => "SEK",
+            Self::SGD => "SGD",
+            Self::SLL => "SLL",
+            Self::SOS => "SOS",
+            Self::SSP => "SSP",
+            Self::SVC => "SVC",
+            Self::SZL => "SZL",
+            Self::THB => "THB",
+            Self::TTD => "TTD",
+            Self::TWD => "TWD",
+            Self::TZS => "TZS",
+            Self::USD => "USD",
+            Self::UYU => "UYU",
+            Self::UZS => "UZS",
+            Self::YER => "YER",
+            Self::ZAR => "ZAR",
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -318,6 +550,58 @@ pub enum EventType {
     PaymentSucceeded,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    router_derive::DieselEnum,
+)]
+#[router_derive::diesel_enum]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DisputeStatus {
+    Opened,
+    Won,
+    Lost,
+}
+
+impl DisputeStatus {
+    /// Won and lost disputes are closed, so a webhook delivered late cannot reopen them.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        match self {
+            Self::Opened => true,
+            Self::Won | Self::Lost => self == next,
+        }
+    }
+}
+
+/// Outcome of submitting evidence for a dispute to the connector.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    router_derive::DieselEnum,
+)]
+#[router_derive::diesel_enum]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum EvidenceStatus {
+    Submitted,
+    Failed,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -496,6 +780,7 @@ pub enum PaymentMethodType {
     #[default]
     BankTransfer,
     BankDebit,
+    BankRedirect,
     PayLater,
     Netbanking,
     Upi,
@@ -580,6 +865,16 @@ pub enum RefundStatus {
     TransactionFailure,
 }
 
+impl RefundStatus {
+    /// Succeeded and failed refunds are final, so a webhook delivered late cannot change them.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        match self {
+            Self::Pending | Self::ManualReview => true,
+            Self::Success | Self::Failure | Self::TransactionFailure => self == next,
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -0,0 +1,31 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::schema::failed_payment_attempt;
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = failed_payment_attempt)]
+pub struct FailedPaymentAttemptNew {
+    pub payment_id: String,
+    pub merchant_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub error_code: String,
+    pub error_message: String,
+    pub error_reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = failed_payment_attempt)]
+pub struct FailedPaymentAttempt {
+    pub id: i32,
+    pub payment_id: String,
+    pub merchant_id: String,
+    pub attempt_id: String,
+    pub connector: String,
+    pub error_code: String,
+    pub error_message: String,
+    pub error_reason: Option<String>,
+    pub created_at: PrimitiveDateTime,
+}
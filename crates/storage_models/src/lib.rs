@@ -8,6 +8,7 @@ pub mod enums;
 pub mod ephemeral_key;
 pub mod errors;
 pub mod events;
+pub mod failed_payment_attempt;
 #[cfg(feature = "kv_store")]
 pub mod kv;
 pub mod locker_mock_up;
@@ -22,6 +23,7 @@ pub mod query;
 pub mod refund;
 pub mod reverse_lookup;
 pub mod schema;
+pub mod webhook_delivery_attempt;
 
 use diesel_impl::{DieselArray, OptionalDieselArray};
 
@@ -65,6 +65,22 @@ pub enum MandateUpdate {
     },
 }
 
+impl MandateUpdate {
+    pub fn apply_changeset(self, source: Mandate) -> Mandate {
+        let mandate_update: MandateUpdateInternal = self.into();
+        Mandate {
+            mandate_status: mandate_update
+                .mandate_status
+                .unwrap_or(source.mandate_status),
+            amount_captured: mandate_update.amount_captured.or(source.amount_captured),
+            connector_mandate_id: mandate_update
+                .connector_mandate_id
+                .or(source.connector_mandate_id),
+            ..source
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct SingleUseMandate {
     pub amount: i64,
@@ -39,6 +39,28 @@ pub struct PaymentAttempt {
     pub error_code: Option<String>,
     pub payment_token: Option<String>,
     pub connector_metadata: Option<serde_json::Value>,
+    pub connector_response_reference_id: Option<String>,
+    /// Masked response body of the last connector call, kept for support debugging
+    pub connector_response_body: Option<String>,
+    /// Where the customer is redirected to once the payment is authenticated
+    pub return_url: Option<String>,
+    /// Why the connector declined or failed the payment, when it reports one
+    pub error_reason: Option<String>,
+    /// Amount the connector authorized, when it authorized less than the requested `amount`
+    pub authorized_amount: Option<i64>,
+    /// Currency the merchant settles the payment in, when it differs from `currency`
+    pub settlement_currency: Option<storage_enums::Currency>,
+    /// `amount` converted to `settlement_currency` at `fx_rate`, for information only
+    pub settlement_amount: Option<i64>,
+    pub fx_rate: Option<String>,
+    pub avs_result: Option<storage_enums::AvsResult>,
+    pub cvv_result: Option<storage_enums::CvvResult>,
+    /// Time the attempt was first reported as authorized by the connector
+    pub authorized_at: Option<PrimitiveDateTime>,
+    /// Number of times the stale payments sync failed to sync the attempt
+    // Attempts cached in the KV store before this field was added have no count
+    #[serde(default)]
+    pub sync_failure_count: i32,
 }
 
 #[derive(
@@ -78,6 +100,17 @@ pub struct PaymentAttemptNew {
     pub payment_token: Option<String>,
     pub error_code: Option<String>,
     pub connector_metadata: Option<serde_json::Value>,
+    pub connector_response_reference_id: Option<String>,
+    pub connector_response_body: Option<String>,
+    pub return_url: Option<String>,
+    pub error_reason: Option<String>,
+    pub authorized_amount: Option<i64>,
+    pub settlement_currency: Option<storage_enums::Currency>,
+    pub settlement_amount: Option<i64>,
+    pub fx_rate: Option<String>,
+    pub avs_result: Option<storage_enums::AvsResult>,
+    pub cvv_result: Option<storage_enums::CvvResult>,
+    pub authorized_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,6 +122,12 @@ pub enum PaymentAttemptUpdate {
         authentication_type: Option<storage_enums::AuthenticationType>,
         payment_method: Option<storage_enums::PaymentMethodType>,
         payment_token: Option<String>,
+        return_url: Option<String>,
+        settlement_currency: Option<storage_enums::Currency>,
+        settlement_amount: Option<i64>,
+        fx_rate: Option<String>,
+        surcharge_amount: Option<i64>,
+        tax_amount: Option<i64>,
     },
     UpdateTrackers {
         payment_token: Option<String>,
@@ -106,6 +145,9 @@ pub enum PaymentAttemptUpdate {
         browser_info: Option<serde_json::Value>,
         connector: Option<String>,
         payment_token: Option<String>,
+        return_url: Option<String>,
+        surcharge_amount: Option<i64>,
+        tax_amount: Option<i64>,
     },
     VoidUpdate {
         status: storage_enums::AttemptStatus,
@@ -120,6 +162,12 @@ pub enum PaymentAttemptUpdate {
         redirect: Option<bool>,
         mandate_id: Option<String>,
         connector_metadata: Option<serde_json::Value>,
+        connector_response_reference_id: Option<String>,
+        connector_response_body: Option<String>,
+        authorized_amount: Option<i64>,
+        avs_result: Option<storage_enums::AvsResult>,
+        cvv_result: Option<storage_enums::CvvResult>,
+        authorized_at: Option<PrimitiveDateTime>,
     },
     StatusUpdate {
         status: storage_enums::AttemptStatus,
@@ -129,7 +177,12 @@ pub enum PaymentAttemptUpdate {
         status: storage_enums::AttemptStatus,
         error_code: Option<String>,
         error_message: Option<String>,
+        error_reason: Option<String>,
+        connector_response_body: Option<String>,
     },
+    /// Records a failed sync and marks the attempt as modified, moving it behind the attempts
+    /// synced less recently.
+    SyncFailureUpdate { sync_failure_count: i32 },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -152,6 +205,20 @@ pub struct PaymentAttemptUpdateInternal {
     payment_token: Option<String>,
     error_code: Option<String>,
     connector_metadata: Option<serde_json::Value>,
+    connector_response_reference_id: Option<String>,
+    connector_response_body: Option<String>,
+    return_url: Option<String>,
+    error_reason: Option<String>,
+    authorized_amount: Option<i64>,
+    settlement_currency: Option<Option<storage_enums::Currency>>,
+    settlement_amount: Option<Option<i64>>,
+    fx_rate: Option<Option<String>>,
+    avs_result: Option<storage_enums::AvsResult>,
+    cvv_result: Option<storage_enums::CvvResult>,
+    authorized_at: Option<PrimitiveDateTime>,
+    sync_failure_count: Option<i32>,
+    surcharge_amount: Option<i64>,
+    tax_amount: Option<i64>,
 }
 
 impl PaymentAttemptUpdate {
@@ -168,12 +235,37 @@ impl PaymentAttemptUpdate {
             authentication_type: pa_update.authentication_type.or(source.authentication_type),
             payment_method: pa_update.payment_method.or(source.payment_method),
             error_message: pa_update.error_message.or(source.error_message),
+            error_code: pa_update.error_code.or(source.error_code),
             payment_method_id: pa_update
                 .payment_method_id
                 .unwrap_or(source.payment_method_id),
             browser_info: pa_update.browser_info.or(source.browser_info),
             modified_at: common_utils::date_time::now(),
             payment_token: pa_update.payment_token.or(source.payment_token),
+            connector_response_reference_id: pa_update
+                .connector_response_reference_id
+                .or(source.connector_response_reference_id),
+            connector_response_body: pa_update
+                .connector_response_body
+                .or(source.connector_response_body),
+            return_url: pa_update.return_url.or(source.return_url),
+            error_reason: pa_update.error_reason.or(source.error_reason),
+            authorized_amount: pa_update.authorized_amount.or(source.authorized_amount),
+            settlement_currency: pa_update
+                .settlement_currency
+                .unwrap_or(source.settlement_currency),
+            settlement_amount: pa_update
+                .settlement_amount
+                .unwrap_or(source.settlement_amount),
+            fx_rate: pa_update.fx_rate.unwrap_or(source.fx_rate),
+            avs_result: pa_update.avs_result.or(source.avs_result),
+            cvv_result: pa_update.cvv_result.or(source.cvv_result),
+            authorized_at: pa_update.authorized_at.or(source.authorized_at),
+            sync_failure_count: pa_update
+                .sync_failure_count
+                .unwrap_or(source.sync_failure_count),
+            surcharge_amount: pa_update.surcharge_amount.or(source.surcharge_amount),
+            tax_amount: pa_update.tax_amount.or(source.tax_amount),
             ..source
         }
     }
@@ -190,6 +282,12 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 authentication_type,
                 payment_method,
                 payment_token,
+                return_url,
+                settlement_currency,
+                settlement_amount,
+                fx_rate,
+                surcharge_amount,
+                tax_amount,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -198,6 +296,13 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 authentication_type,
                 payment_method,
                 payment_token,
+                return_url,
+                // The settlement is quoted again for the updated amount and currency
+                settlement_currency: Some(settlement_currency),
+                settlement_amount: Some(settlement_amount),
+                fx_rate: Some(fx_rate),
+                surcharge_amount,
+                tax_amount,
                 modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
@@ -217,6 +322,9 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 browser_info,
                 connector,
                 payment_token,
+                return_url,
+                surcharge_amount,
+                tax_amount,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -227,6 +335,9 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 browser_info,
                 connector,
                 payment_token,
+                return_url,
+                surcharge_amount,
+                tax_amount,
                 ..Default::default()
             },
             PaymentAttemptUpdate::VoidUpdate {
@@ -246,6 +357,12 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 redirect,
                 mandate_id,
                 connector_metadata,
+                connector_response_reference_id,
+                connector_response_body,
+                authorized_amount,
+                avs_result,
+                cvv_result,
+                authorized_at,
             } => Self {
                 status: Some(status),
                 connector,
@@ -256,6 +373,12 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 redirect,
                 mandate_id,
                 connector_metadata,
+                connector_response_reference_id,
+                connector_response_body,
+                authorized_amount,
+                avs_result,
+                cvv_result,
+                authorized_at,
                 ..Default::default()
             },
             PaymentAttemptUpdate::ErrorUpdate {
@@ -263,11 +386,15 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 status,
                 error_code,
                 error_message,
+                error_reason,
+                connector_response_body,
             } => Self {
                 connector,
                 status: Some(status),
                 error_message,
                 error_code,
+                error_reason,
+                connector_response_body,
                 modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
@@ -283,6 +410,11 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 connector,
                 ..Default::default()
             },
+            PaymentAttemptUpdate::SyncFailureUpdate { sync_failure_count } => Self {
+                sync_failure_count: Some(sync_failure_count),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
@@ -30,6 +30,8 @@ pub struct PaymentMethod {
     pub payment_method_issuer: Option<String>,
     pub payment_method_issuer_code: Option<storage_enums::PaymentMethodIssuerCode>,
     pub metadata: Option<serde_json::Value>,
+    /// Reference of the network token the locker holds in place of the card number, if any.
+    pub network_token_reference: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Insertable, Queryable, router_derive::DebugAsDisplay)]
@@ -55,6 +57,8 @@ pub struct PaymentMethodNew {
     pub created_at: PrimitiveDateTime,
     pub last_modified: PrimitiveDateTime,
     pub metadata: Option<serde_json::Value>,
+    /// Reference of the network token the locker holds in place of the card number, if any.
+    pub network_token_reference: Option<String>,
 }
 
 impl Default for PaymentMethodNew {
@@ -82,6 +86,7 @@ impl Default for PaymentMethodNew {
             created_at: now,
             last_modified: now,
             metadata: Option::default(),
+            network_token_reference: Option::default(),
         }
     }
 }
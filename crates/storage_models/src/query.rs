@@ -3,7 +3,9 @@ pub mod api_keys;
 pub mod configs;
 pub mod connector_response;
 pub mod customers;
+pub mod dispute;
 pub mod events;
+pub mod failed_payment_attempt;
 pub mod generics;
 pub mod locker_mock_up;
 pub mod mandate;
@@ -15,3 +17,4 @@ pub mod payment_method;
 pub mod process_tracker;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod webhook_delivery_attempt;
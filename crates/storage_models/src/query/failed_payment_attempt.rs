@@ -0,0 +1,36 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    failed_payment_attempt::{FailedPaymentAttempt, FailedPaymentAttemptNew},
+    schema::failed_payment_attempt::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl FailedPaymentAttemptNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<FailedPaymentAttempt> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl FailedPaymentAttempt {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_payment_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_id.eq(payment_id.to_owned())),
+            None,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+}
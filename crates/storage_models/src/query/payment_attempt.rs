@@ -1,6 +1,7 @@
 use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, Table};
 use error_stack::IntoReport;
 use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
 
 use super::generics;
 use crate::{
@@ -153,4 +154,43 @@ impl PaymentAttempt {
         )
         .await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_statuses_modified_before(
+        conn: &PgPooledConn,
+        statuses: Vec<enums::AttemptStatus>,
+        modified_before: PrimitiveDateTime,
+        max_sync_failures: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::status
+                .eq_any(statuses)
+                .and(dsl::modified_at.lt(modified_before))
+                .and(dsl::connector.is_not_null())
+                .and(dsl::sync_failure_count.lt(max_sync_failures)),
+            Some(limit),
+            None,
+            Some(dsl::modified_at.asc()),
+        )
+        .await
+    }
+
+    /// Drops the stored connector response bodies of the attempts last updated before the given
+    /// time, returning the number of attempts cleared.
+    #[instrument(skip(conn))]
+    pub async fn clear_connector_response_bodies_modified_before(
+        conn: &PgPooledConn,
+        modified_before: PrimitiveDateTime,
+    ) -> StorageResult<usize> {
+        generics::generic_update::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::modified_at
+                .lt(modified_before)
+                .and(dsl::connector_response_body.is_not_null()),
+            dsl::connector_response_body.eq(None::<String>),
+        )
+        .await
+    }
 }
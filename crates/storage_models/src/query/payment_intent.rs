@@ -1,4 +1,6 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use diesel::{
+    associations::HasTable, dsl::sql, sql_types, BoolExpressionMethods, ExpressionMethods,
+};
 use router_env::{instrument, tracing};
 
 use super::generics;
@@ -44,6 +46,35 @@ impl PaymentIntent {
         }
     }
 
+    /// Adds `amount` to the amount captured in the database, rather than to the value read
+    /// earlier, so that concurrent captures of the same payment are all counted.
+    #[instrument(skip(conn))]
+    pub async fn add_amount_captured(
+        self,
+        conn: &PgPooledConn,
+        amount: i64,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::payment_id
+                .eq(self.payment_id.to_owned())
+                .and(dsl::merchant_id.eq(self.merchant_id.to_owned())),
+            (
+                dsl::amount_captured.eq(sql::<sql_types::Nullable<sql_types::BigInt>>(
+                    "COALESCE(amount_captured, 0) + ",
+                )
+                .bind::<sql_types::BigInt, _>(amount)),
+                dsl::modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_by_payment_id_merchant_id(
         conn: &PgPooledConn,
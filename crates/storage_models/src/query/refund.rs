@@ -3,7 +3,7 @@ use router_env::{instrument, tracing};
 
 use super::generics;
 use crate::{
-    errors,
+    enums, errors,
     refund::{Refund, RefundNew, RefundUpdate, RefundUpdateInternal},
     schema::refund::dsl,
     PgPooledConn, StorageResult,
@@ -72,6 +72,23 @@ impl Refund {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_connector_refund_id_connector(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        connector_refund_id: &str,
+        connector: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::connector_refund_id.eq(connector_refund_id.to_owned()))
+                .and(dsl::connector.eq(connector.to_owned())),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_by_merchant_id_connector_transaction_id(
         conn: &PgPooledConn,
@@ -117,4 +134,24 @@ impl Refund {
         )
         .await
     }
+
+    /// Refunds sent to the connector with the status `refund_status`, least recently modified
+    /// first.
+    #[instrument(skip(conn))]
+    pub async fn find_sent_to_gateway_by_refund_status(
+        conn: &PgPooledConn,
+        refund_status: enums::RefundStatus,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::refund_status
+                .eq(refund_status)
+                .and(dsl::sent_to_gateway.eq(true)),
+            Some(limit),
+            None,
+            Some(dsl::modified_at.asc()),
+        )
+        .await
+    }
 }
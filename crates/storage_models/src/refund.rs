@@ -95,6 +95,8 @@ pub enum RefundUpdate {
         refund_error_message: Option<String>,
         refund_error_code: Option<String>,
     },
+    /// Only marks the refund as modified, moving it behind the refunds synced less recently.
+    SyncFailureUpdate,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -108,6 +110,7 @@ pub struct RefundUpdateInternal {
     metadata: Option<serde_json::Value>,
     refund_reason: Option<String>,
     refund_error_code: Option<String>,
+    modified_at: Option<PrimitiveDateTime>,
 }
 
 impl From<RefundUpdate> for RefundUpdateInternal {
@@ -125,11 +128,13 @@ impl From<RefundUpdate> for RefundUpdateInternal {
                 sent_to_gateway: Some(sent_to_gateway),
                 refund_error_message,
                 refund_arn: Some(refund_arn),
+                modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
             RefundUpdate::MetadataAndReasonUpdate { metadata, reason } => Self {
                 metadata,
                 refund_reason: reason,
+                modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
             RefundUpdate::StatusUpdate {
@@ -140,6 +145,7 @@ impl From<RefundUpdate> for RefundUpdateInternal {
                 connector_refund_id,
                 sent_to_gateway: Some(sent_to_gateway),
                 refund_status: Some(refund_status),
+                modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
             RefundUpdate::ErrorUpdate {
@@ -150,6 +156,11 @@ impl From<RefundUpdate> for RefundUpdateInternal {
                 refund_status,
                 refund_error_message,
                 refund_error_code,
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+            RefundUpdate::SyncFailureUpdate => Self {
+                modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
         }
@@ -169,6 +180,7 @@ impl RefundUpdate {
             refund_error_code: pa_update.refund_error_code.or(source.refund_error_code),
             refund_arn: pa_update.refund_arn.or(source.refund_arn),
             metadata: pa_update.metadata.or(source.metadata),
+            updated_at: pa_update.modified_at.unwrap_or(source.updated_at),
             ..source
         }
     }
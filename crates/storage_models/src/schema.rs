@@ -90,6 +90,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    dispute (id) {
+        id -> Int4,
+        dispute_id -> Varchar,
+        merchant_id -> Varchar,
+        payment_id -> Varchar,
+        attempt_id -> Varchar,
+        connector -> Varchar,
+        connector_dispute_id -> Varchar,
+        connector_reason_code -> Nullable<Varchar>,
+        amount -> Int8,
+        currency -> Varchar,
+        dispute_status -> DisputeStatus,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+        evidence_status -> Nullable<EvidenceStatus>,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -107,6 +129,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    failed_payment_attempt (id) {
+        id -> Int4,
+        payment_id -> Varchar,
+        merchant_id -> Varchar,
+        attempt_id -> Varchar,
+        connector -> Varchar,
+        error_code -> Varchar,
+        error_message -> Text,
+        error_reason -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -236,6 +275,18 @@ diesel::table! {
         error_code -> Nullable<Varchar>,
         payment_token -> Nullable<Varchar>,
         connector_metadata -> Nullable<Jsonb>,
+        connector_response_reference_id -> Nullable<Varchar>,
+        connector_response_body -> Nullable<Text>,
+        return_url -> Nullable<Varchar>,
+        error_reason -> Nullable<Text>,
+        authorized_amount -> Nullable<Int8>,
+        settlement_currency -> Nullable<Currency>,
+        settlement_amount -> Nullable<Int8>,
+        fx_rate -> Nullable<Varchar>,
+        avs_result -> Nullable<AvsResult>,
+        cvv_result -> Nullable<CvvResult>,
+        authorized_at -> Nullable<Timestamp>,
+        sync_failure_count -> Int4,
     }
 }
 
@@ -295,6 +346,7 @@ diesel::table! {
         payment_method_issuer -> Nullable<Varchar>,
         payment_method_issuer_code -> Nullable<PaymentMethodIssuerCode>,
         metadata -> Nullable<Json>,
+        network_token_reference -> Nullable<Varchar>,
     }
 }
 
@@ -363,13 +415,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    webhook_delivery_attempt (id) {
+        id -> Int4,
+        merchant_id -> Varchar,
+        event_id -> Varchar,
+        event_type -> EventType,
+        primary_object_id -> Varchar,
+        webhook_url -> Varchar,
+        status_code -> Nullable<Int4>,
+        attempt_number -> Int4,
+        error_message -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     address,
     api_keys,
     configs,
     connector_response,
     customers,
+    dispute,
     events,
+    failed_payment_attempt,
     locker_mock_up,
     mandate,
     merchant_account,
@@ -380,4 +452,5 @@ diesel::allow_tables_to_appear_in_same_query!(
     process_tracker,
     refund,
     reverse_lookup,
+    webhook_delivery_attempt,
 );
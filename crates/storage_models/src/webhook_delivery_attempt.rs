@@ -0,0 +1,33 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::webhook_delivery_attempt};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = webhook_delivery_attempt)]
+pub struct WebhookDeliveryAttemptNew {
+    pub merchant_id: String,
+    pub event_id: String,
+    pub event_type: storage_enums::EventType,
+    pub primary_object_id: String,
+    pub webhook_url: String,
+    pub status_code: Option<i32>,
+    pub attempt_number: i32,
+    pub error_message: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = webhook_delivery_attempt)]
+pub struct WebhookDeliveryAttempt {
+    pub id: i32,
+    pub merchant_id: String,
+    pub event_id: String,
+    pub event_type: storage_enums::EventType,
+    pub primary_object_id: String,
+    pub webhook_url: String,
+    pub status_code: Option<i32>,
+    pub attempt_number: i32,
+    pub error_message: Option<String>,
+    pub created_at: PrimitiveDateTime,
+}
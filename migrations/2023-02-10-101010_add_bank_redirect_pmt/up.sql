@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TYPE "PaymentMethodType" ADD VALUE 'bank_redirect' after 'bank_debit';
@@ -0,0 +1,2 @@
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS connector_response_reference_id;
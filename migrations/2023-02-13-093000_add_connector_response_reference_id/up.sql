@@ -0,0 +1,2 @@
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS connector_response_reference_id VARCHAR(128) DEFAULT NULL;
@@ -0,0 +1,3 @@
+DROP TABLE dispute;
+
+DROP TYPE "DisputeStatus";
@@ -0,0 +1,21 @@
+CREATE TYPE "DisputeStatus" AS ENUM ('opened', 'won', 'lost');
+
+CREATE TABLE dispute (
+    id SERIAL PRIMARY KEY,
+    dispute_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    connector VARCHAR(255) NOT NULL,
+    connector_dispute_id VARCHAR(255) NOT NULL,
+    connector_reason_code VARCHAR(255),
+    amount BIGINT NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    dispute_status "DisputeStatus" NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE UNIQUE INDEX dispute_id_index ON dispute (dispute_id);
+
+CREATE UNIQUE INDEX merchant_id_connector_dispute_id_index ON dispute (merchant_id, connector, connector_dispute_id);
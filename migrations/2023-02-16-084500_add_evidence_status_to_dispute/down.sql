@@ -0,0 +1,3 @@
+ALTER TABLE dispute DROP COLUMN evidence_status;
+
+DROP TYPE "EvidenceStatus";
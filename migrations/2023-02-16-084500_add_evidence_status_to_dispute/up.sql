@@ -0,0 +1,3 @@
+CREATE TYPE "EvidenceStatus" AS ENUM ('submitted', 'failed');
+
+ALTER TABLE dispute ADD COLUMN evidence_status "EvidenceStatus";
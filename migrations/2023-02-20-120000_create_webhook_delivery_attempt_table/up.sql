@@ -0,0 +1,14 @@
+CREATE TABLE webhook_delivery_attempt (
+    id SERIAL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    event_id VARCHAR(64) NOT NULL,
+    event_type "EventType" NOT NULL,
+    primary_object_id VARCHAR(64) NOT NULL,
+    webhook_url VARCHAR(2048) NOT NULL,
+    status_code INTEGER,
+    attempt_number INTEGER NOT NULL,
+    error_message VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX webhook_delivery_attempt_merchant_id_primary_object_id_index ON webhook_delivery_attempt (merchant_id, primary_object_id);
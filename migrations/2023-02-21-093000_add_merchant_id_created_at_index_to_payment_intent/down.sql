@@ -0,0 +1 @@
+DROP INDEX payment_intent_merchant_id_created_at_index;
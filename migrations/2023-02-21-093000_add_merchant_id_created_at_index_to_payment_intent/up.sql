@@ -0,0 +1 @@
+CREATE INDEX payment_intent_merchant_id_created_at_index ON payment_intent (merchant_id, created_at);
@@ -0,0 +1,2 @@
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS return_url;
@@ -0,0 +1,2 @@
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS return_url VARCHAR(255) DEFAULT NULL;
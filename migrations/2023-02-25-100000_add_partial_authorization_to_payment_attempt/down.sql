@@ -0,0 +1,8 @@
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS authorized_amount;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'partially_authorized'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'AttemptStatus'
+);
@@ -0,0 +1,4 @@
+ALTER TYPE "AttemptStatus" ADD VALUE IF NOT EXISTS 'partially_authorized' AFTER 'authorized';
+
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS authorized_amount BIGINT DEFAULT NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS settlement_currency,
+DROP COLUMN IF EXISTS settlement_amount,
+DROP COLUMN IF EXISTS fx_rate;
@@ -0,0 +1,2 @@
+ALTER TABLE payment_methods
+DROP COLUMN IF EXISTS network_token_reference;
@@ -0,0 +1,2 @@
+ALTER TABLE payment_methods
+ADD COLUMN IF NOT EXISTS network_token_reference VARCHAR(128) DEFAULT NULL;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS webhook_delivery_attempt_merchant_id_event_id_index;
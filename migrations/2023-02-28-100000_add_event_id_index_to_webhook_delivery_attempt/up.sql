@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS webhook_delivery_attempt_merchant_id_event_id_index ON webhook_delivery_attempt (merchant_id, event_id);
@@ -0,0 +1,13 @@
+CREATE TABLE failed_payment_attempt (
+    id SERIAL PRIMARY KEY,
+    payment_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    connector VARCHAR(64) NOT NULL,
+    error_code VARCHAR(255) NOT NULL,
+    error_message TEXT NOT NULL,
+    error_reason TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX failed_payment_attempt_merchant_id_payment_id_index ON failed_payment_attempt (merchant_id, payment_id);
@@ -0,0 +1,7 @@
+ALTER TABLE payment_attempt
+DROP COLUMN IF EXISTS avs_result,
+DROP COLUMN IF EXISTS cvv_result;
+
+DROP TYPE IF EXISTS "AvsResult";
+
+DROP TYPE IF EXISTS "CvvResult";
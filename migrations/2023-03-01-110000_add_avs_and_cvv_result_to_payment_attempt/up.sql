@@ -0,0 +1,7 @@
+CREATE TYPE "AvsResult" AS ENUM ('match', 'partial_match', 'no_match', 'unavailable');
+
+CREATE TYPE "CvvResult" AS ENUM ('match', 'no_match', 'unavailable');
+
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS avs_result "AvsResult" DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS cvv_result "CvvResult" DEFAULT NULL;
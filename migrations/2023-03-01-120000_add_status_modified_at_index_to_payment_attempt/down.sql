@@ -0,0 +1 @@
+DROP INDEX IF EXISTS payment_attempt_status_modified_at_index;
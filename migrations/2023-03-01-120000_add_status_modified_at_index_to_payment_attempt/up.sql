@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS payment_attempt_status_modified_at_index ON payment_attempt (status, modified_at);
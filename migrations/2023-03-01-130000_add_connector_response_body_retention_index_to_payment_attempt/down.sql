@@ -0,0 +1 @@
+DROP INDEX IF EXISTS payment_attempt_connector_response_body_modified_at_index;
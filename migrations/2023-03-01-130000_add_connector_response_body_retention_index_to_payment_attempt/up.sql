@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS payment_attempt_connector_response_body_modified_at_index ON payment_attempt (modified_at) WHERE connector_response_body IS NOT NULL;
@@ -0,0 +1,5 @@
+DELETE FROM pg_enum
+WHERE enumlabel = 'verified'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'AttemptStatus'
+);
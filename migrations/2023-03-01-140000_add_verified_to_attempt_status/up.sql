@@ -0,0 +1 @@
+ALTER TYPE "AttemptStatus" ADD VALUE IF NOT EXISTS 'verified' AFTER 'charged';
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS refund_merchant_id_connector_refund_id_connector_index;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS refund_merchant_id_connector_refund_id_connector_index ON refund (merchant_id, connector_refund_id, connector);
@@ -0,0 +1,2 @@
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS authorized_at TIMESTAMP DEFAULT NULL;